@@ -2,7 +2,6 @@ extern crate azure_sdk_for_rust;
 extern crate env_logger;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 #[macro_use]
 extern crate log;
 extern crate tokio_core;
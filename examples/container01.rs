@@ -2,14 +2,14 @@ extern crate azure_sdk_for_rust;
 extern crate chrono;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 extern crate tokio_core;
 
+use azure_sdk_for_rust::core::lease::LeasePeriod;
+use azure_sdk_for_rust::core::metadata::Metadata;
 use azure_sdk_for_rust::prelude::*;
 use azure_sdk_for_rust::storage::container::{PublicAccess, PublicAccessSupport};
 use chrono::{Duration, FixedOffset, Utc};
 use futures::Future;
-use std::collections::HashMap;
 use std::error::Error;
 use std::ops::Add;
 use tokio_core::reactor::Core;
@@ -45,9 +45,9 @@ fn code() -> Result<(), Box<Error>> {
         println!("{:?}", res);
     }))?;
 
-    let mut metadata = HashMap::new();
-    metadata.insert("prova", "pollo");
-    metadata.insert("canotto", "cigno");
+    let mut metadata = Metadata::new();
+    metadata.insert("prova", "pollo")?;
+    metadata.insert("canotto", "cigno")?;
 
     // This is the builder pattern. Notice two things:
     // 1 - The various parameters are clearly defined.
@@ -110,7 +110,7 @@ fn code() -> Result<(), Box<Error>> {
     let future = client
         .acquire_container_lease()
         .with_container_name(&container_name)
-        .with_lease_duration(15)
+        .with_lease_duration(LeasePeriod::fixed(15)?)
         .finalize();
     let res = core.run(future)?;
     println!("\nacquire_lease() == {:?}", res);
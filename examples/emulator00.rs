@@ -3,7 +3,6 @@ extern crate chrono;
 extern crate env_logger;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 extern crate log;
 extern crate md5;
 extern crate tokio_core;
@@ -0,0 +1,85 @@
+extern crate azure_sdk_for_rust;
+extern crate futures;
+extern crate hyper;
+extern crate tokio_core;
+
+use azure_sdk_for_rust::core::range::Range;
+use azure_sdk_for_rust::prelude::*;
+use std::error::Error;
+use tokio_core::reactor::Core;
+
+fn main() {
+    code().unwrap();
+}
+
+// We run a separate method to use the elegant quotation mark operator.
+// A series of unwrap(), unwrap() would have achieved the same result.
+fn code() -> Result<(), Box<Error>> {
+    // First we retrieve the account name and master key from environment variables.
+    let account = std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let master_key = std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
+
+    let share_name = std::env::args()
+        .nth(1)
+        .expect("please specify the share name as command line parameter");
+
+    let mut core = Core::new()?;
+
+    let client = Client::new(&account, &master_key)?;
+
+    core.run(client.create_share().with_share_name(&share_name).with_share_quota(1).finalize())?;
+
+    core.run(
+        client
+            .create_directory()
+            .with_share_name(&share_name)
+            .with_directory_path("logs")
+            .finalize(),
+    )?;
+
+    // files are created with their final size and then written range by
+    // range
+    let data = b"hello from Rust";
+    core.run(
+        client
+            .create_file()
+            .with_share_name(&share_name)
+            .with_file_path("logs/hello.txt")
+            .with_file_size(data.len() as u64)
+            .with_content_type("text/plain")
+            .finalize(),
+    )?;
+
+    let range = Range::new(0, data.len() as u64 - 1);
+    core.run(
+        client
+            .put_range()
+            .with_share_name(&share_name)
+            .with_file_path("logs/hello.txt")
+            .with_range(&range)
+            .with_body(data)
+            .finalize(),
+    )?;
+
+    let future = client
+        .list_directories_and_files()
+        .with_share_name(&share_name)
+        .with_directory_path("logs")
+        .finalize();
+    let response = core.run(future)?;
+    for item in response.incomplete_vector.iter() {
+        println!("{:?}", item);
+    }
+
+    let future = client
+        .get_file()
+        .with_share_name(&share_name)
+        .with_file_path("logs/hello.txt")
+        .finalize();
+    let response = core.run(future)?;
+    println!("{}", String::from_utf8(response.data)?);
+
+    core.run(client.delete_share().with_share_name(&share_name).finalize())?;
+
+    Ok(())
+}
@@ -3,15 +3,14 @@ extern crate chrono;
 extern crate env_logger;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 #[macro_use]
 extern crate log;
 extern crate md5;
 extern crate tokio_core;
 
+use azure_sdk_for_rust::core::metadata::Metadata;
 use azure_sdk_for_rust::prelude::*;
 use futures::future::*;
-use std::collections::HashMap;
 use std::error::Error;
 use tokio_core::reactor::Core;
 
@@ -38,10 +37,10 @@ fn code() -> Result<(), Box<Error>> {
 
     let data = b"something";
 
-    let mut metadata = HashMap::new();
+    let mut metadata = Metadata::new();
 
-    metadata.insert("pollo", "arrosto");
-    metadata.insert("milk", "shake");
+    metadata.insert("pollo", "arrosto")?;
+    metadata.insert("milk", "shake")?;
 
     // this is not mandatory but it helps preventing
     // spurious data to be uploaded.
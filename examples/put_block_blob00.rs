@@ -3,12 +3,12 @@ extern crate chrono;
 extern crate env_logger;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 #[macro_use]
 extern crate log;
 extern crate md5;
 extern crate tokio_core;
 
+use azure_sdk_for_rust::core::lease::{LeaseBreakPeriod, LeasePeriod};
 use azure_sdk_for_rust::core::DeleteSnapshotsMethod;
 use azure_sdk_for_rust::prelude::*;
 use azure_sdk_for_rust::storage::blob::BlockListType;
@@ -107,7 +107,7 @@ fn code() -> Result<(), Box<Error>> {
         .acquire_blob_lease()
         .with_container_name(&container)
         .with_blob_name(&blob_name)
-        .with_lease_duration(60)
+        .with_lease_duration(LeasePeriod::fixed(60)?)
         .finalize();
     let res = core.run(future)?;
     println!("Acquire lease == {:?}", res);
@@ -127,7 +127,7 @@ fn code() -> Result<(), Box<Error>> {
         .break_blob_lease()
         .with_container_name(&container)
         .with_blob_name(&blob_name)
-        .with_lease_break_period(15)
+        .with_lease_break_period(LeaseBreakPeriod::new(15)?)
         .finalize();
     let res = core.run(future)?;
     println!("Break lease == {:?}", res);
@@ -3,17 +3,16 @@ extern crate chrono;
 extern crate env_logger;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 #[macro_use]
 extern crate log;
 extern crate md5;
 extern crate tokio_core;
 
 use azure_sdk_for_rust::core::ba512_range::BA512Range;
+use azure_sdk_for_rust::core::metadata::Metadata;
 use azure_sdk_for_rust::core::modify_conditions::SequenceNumberCondition;
 use azure_sdk_for_rust::prelude::*;
 use futures::future::*;
-use std::collections::HashMap;
 use std::error::Error;
 use tokio_core::reactor::Core;
 
@@ -41,10 +40,10 @@ fn code() -> Result<(), Box<Error>> {
 
     let data: [u8; 2000] = [51; 2000];
 
-    let mut metadata = HashMap::new();
+    let mut metadata = Metadata::new();
 
-    metadata.insert("pollo", "arrosto");
-    metadata.insert("milk", "shake");
+    metadata.insert("pollo", "arrosto")?;
+    metadata.insert("milk", "shake")?;
 
     let slice = &data[512..1024];
 
@@ -0,0 +1,72 @@
+extern crate azure_sdk_for_rust;
+extern crate futures;
+extern crate hyper;
+extern crate tokio_core;
+
+use azure_sdk_for_rust::prelude::*;
+use futures::future::*;
+use std::error::Error;
+use tokio_core::reactor::Core;
+
+fn main() {
+    code().unwrap();
+}
+
+// We run a separate method to use the elegant quotation mark operator.
+// A series of unwrap(), unwrap() would have achieved the same result.
+fn code() -> Result<(), Box<Error>> {
+    // First we retrieve the account name and master key from environment variables.
+    let account = std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let master_key = std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
+
+    let queue_name = std::env::args()
+        .nth(1)
+        .expect("please specify the queue name as command line parameter");
+
+    let mut core = Core::new()?;
+
+    let client = Client::new(&account, &master_key)?;
+
+    core.run(client.create_queue().with_queue_name(&queue_name).finalize())?;
+
+    let future = client
+        .put_message()
+        .with_queue_name(&queue_name)
+        .with_message_text("hello from Rust")
+        .finalize();
+    let response = core.run(future)?;
+    println!("put message {}", response.message_id);
+
+    let future = client.get_queue_metadata().with_queue_name(&queue_name).finalize();
+    let response = core.run(future)?;
+    println!("the queue has approximately {} messages", response.approximate_messages_count);
+
+    // we have 60 seconds to process the messages before they become
+    // visible again
+    let future = client
+        .get_messages()
+        .with_queue_name(&queue_name)
+        .with_number_of_messages(32)
+        .with_visibility_timeout(60)
+        .finalize();
+    let response = core.run(future)?;
+
+    let deletes: Vec<_> = response
+        .messages
+        .iter()
+        .map(|message| {
+            println!("received {} ==> {}", message.message_id, message.message_text);
+            client
+                .delete_message()
+                .with_queue_name(&queue_name)
+                .with_message_id(&message.message_id)
+                .with_pop_receipt(message.pop_receipt.as_ref().unwrap())
+                .finalize()
+        })
+        .collect();
+    core.run(join_all(deletes))?;
+
+    core.run(client.delete_queue().with_queue_name(&queue_name).finalize())?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! `std::future` adapters for the futures 0.1 the crate is built on,
+//! enabled by the `async` feature. Every request builder has a
+//! `finalize_async` next to `finalize`; the functions below convert the
+//! other futures and streams (the table service, the listings as
+//! streams) for async/await.
+//!
+//! The default transport is hyper 0.12, which spawns its connections on
+//! the tokio 0.1 executor: run the futures on a runtime providing one
+//! (tokio 0.1, or tokio 0.2 through `tokio-compat`) or supply another
+//! `HttpClient` with `Client::with_http_client`.
+
+use futures::{Future, Stream};
+use futures_util::compat::{Compat01As03, Future01CompatExt, Stream01CompatExt};
+
+pub fn into_std_future<F: Future>(future: F) -> Compat01As03<F> {
+    future.compat()
+}
+
+/// The stream yields a `Result` per item, as `futures::TryStream` expects.
+pub fn into_std_stream<S: Stream>(stream: S) -> Compat01As03<S> {
+    stream.compat()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::core::ContainerNameSupport;
+    use crate::azure::storage::client::{Client, Container};
+    use futures_util::FutureExt;
+    use hyper::StatusCode;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_finalize_async() {
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::ACCEPTED, &[], b""));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+
+        let future = client.delete_container().with_container_name("c").finalize_async();
+        assert!(future.now_or_never().unwrap().is_ok());
+        assert_eq!(
+            transport.requests()[0].uri,
+            "https://account.blob.core.windows.net/c?restype=container"
+        );
+    }
+}
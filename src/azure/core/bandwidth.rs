@@ -0,0 +1,216 @@
+use crate::azure::core::pipeline::{Next, PipelineFuture, PipelineRequest, Policy};
+use crate::azure::core::sleeper::{SleepFuture, Sleeper};
+use futures::{stream, Async, Future, Poll, Stream};
+use hyper::{Body, Chunk};
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// the bounds of the chunks a request body is split into, a tenth of a
+// second of the limit
+const MIN_CHUNK_SIZE: u64 = 1024;
+const MAX_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Caps the bytes per second a client sends and receives (see
+/// `Client::with_bandwidth_limit`). The request and response bodies are
+/// paced chunk by chunk as they are transferred: each chunk waits until
+/// the bytes before it, of its own request and of the other ones, are
+/// paid off, so a single large upload or download is slowed down too.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    bytes_per_second: u64,
+    // when the bytes reserved so far are paid off
+    paid_off: Mutex<Option<Instant>>,
+}
+
+impl BandwidthLimiter {
+    /// At most `bytes_per_second` (at least 1) bytes per second, on
+    /// average.
+    pub fn new(bytes_per_second: u64) -> BandwidthLimiter {
+        BandwidthLimiter {
+            bytes_per_second: bytes_per_second.max(1),
+            paid_off: Mutex::new(None),
+        }
+    }
+
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second
+    }
+
+    // reserves the time to transfer `bytes` after the previous ones,
+    // returning how long to wait before starting
+    pub(crate) fn reserve(&self, bytes: u64, now: Instant) -> Duration {
+        let mut paid_off = self.paid_off.lock().unwrap();
+        let start = paid_off.filter(|paid_off| *paid_off > now).unwrap_or(now);
+        let transfer = Duration::from_secs(bytes / self.bytes_per_second)
+            + Duration::from_nanos((bytes % self.bytes_per_second) * 1_000_000_000 / self.bytes_per_second);
+        *paid_off = Some(start + transfer);
+        start - now
+    }
+
+    fn chunk_size(&self) -> usize {
+        (self.bytes_per_second / 10).max(MIN_CHUNK_SIZE).min(MAX_CHUNK_SIZE) as usize
+    }
+}
+
+/// Paces the bodies of a request at the limit of a `BandwidthLimiter`.
+/// Set on the requests by the bandwidth stage and applied by
+/// `PipelineRequest::into_hyper_request`: an `HttpClient` sending
+/// `PipelineRequest::body` on its own is not paced.
+#[derive(Clone)]
+pub struct BodyPacer {
+    limiter: Arc<BandwidthLimiter>,
+    sleeper: Arc<dyn Sleeper>,
+}
+
+impl BodyPacer {
+    pub(crate) fn request_body(&self, body: Vec<u8>) -> Body {
+        let chunks: Vec<Chunk> = body
+            .chunks(self.limiter.chunk_size())
+            .map(|chunk| Chunk::from(chunk.to_vec()))
+            .collect();
+        Body::wrap_stream(self.pace(stream::iter_ok::<_, hyper::Error>(chunks)))
+    }
+
+    pub(crate) fn response_body(&self, body: Body) -> Body {
+        Body::wrap_stream(self.pace(body))
+    }
+
+    fn pace<S>(&self, inner: S) -> PacedStream<S>
+    where
+        S: Stream<Item = Chunk>,
+    {
+        PacedStream {
+            inner,
+            pacer: self.clone(),
+            pending: None,
+        }
+    }
+}
+
+impl fmt::Debug for BodyPacer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BodyPacer").field("limiter", &self.limiter).finish()
+    }
+}
+
+// Holds each chunk back until its bytes can be transferred.
+struct PacedStream<S> {
+    inner: S,
+    pacer: BodyPacer,
+    pending: Option<(Chunk, SleepFuture)>,
+}
+
+impl<S> Stream for PacedStream<S>
+where
+    S: Stream<Item = Chunk>,
+    S::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Item = Chunk;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll(&mut self) -> Poll<Option<Chunk>, Self::Error> {
+        if self.pending.is_none() {
+            let chunk = match self.inner.poll().map_err(Into::into)? {
+                Async::Ready(Some(chunk)) => chunk,
+                Async::Ready(None) => return Ok(Async::Ready(None)),
+                Async::NotReady => return Ok(Async::NotReady),
+            };
+            let delay = self.pacer.limiter.reserve(chunk.len() as u64, Instant::now());
+            if delay == Duration::from_secs(0) {
+                return Ok(Async::Ready(Some(chunk)));
+            }
+            self.pending = Some((chunk, self.pacer.sleeper.sleep(delay)));
+        }
+
+        if let Some((_, wait)) = &mut self.pending {
+            if wait.poll().map_err(|error| error.to_string())?.is_not_ready() {
+                return Ok(Async::NotReady);
+            }
+        }
+        Ok(Async::Ready(self.pending.take().map(|(chunk, _)| chunk)))
+    }
+}
+
+/// Pipeline stage pacing the bodies of each attempt according to the
+/// `BandwidthLimiter`.
+#[derive(Debug, Clone)]
+pub(crate) struct BandwidthStage {
+    pacer: BodyPacer,
+}
+
+impl BandwidthStage {
+    pub(crate) fn new(limiter: Arc<BandwidthLimiter>, sleeper: Arc<dyn Sleeper>) -> BandwidthStage {
+        BandwidthStage {
+            pacer: BodyPacer { limiter, sleeper },
+        }
+    }
+}
+
+impl Policy for BandwidthStage {
+    fn send(&self, mut request: PipelineRequest, next: Next) -> PipelineFuture {
+        request.pacer = Some(self.pacer.clone());
+        let pacer = self.pacer.clone();
+        Box::new(
+            next.send(request)
+                .map(move |response| response.map(|body| pacer.response_body(body))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::pipeline;
+    use crate::azure::core::sleeper::VirtualSleeper;
+    use futures::future;
+    use hyper::header::HeaderMap;
+    use hyper::{Method, Response};
+
+    #[test]
+    fn test_reserve() {
+        let limiter = BandwidthLimiter::new(1000);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(500, now), Duration::from_secs(0));
+        assert_eq!(limiter.reserve(1500, now), Duration::from_millis(500));
+        assert_eq!(limiter.reserve(0, now + Duration::from_millis(1000)), Duration::from_millis(1000));
+        // idle long enough: no wait
+        assert_eq!(limiter.reserve(100, now + Duration::from_secs(5)), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn test_stage() {
+        let limiter = Arc::new(BandwidthLimiter::new(10_000));
+        let sleeper = Arc::new(VirtualSleeper::new());
+        let stage: Arc<dyn Policy> = Arc::new(BandwidthStage::new(limiter, sleeper.clone()));
+
+        let request = PipelineRequest {
+            method: Method::PUT,
+            uri: "https://account.blob.core.windows.net/c/b".to_owned(),
+            headers: HeaderMap::new(),
+            body: Some(vec![7; 20_000]),
+            pacer: None,
+        };
+        let transport = |request: PipelineRequest| -> PipelineFuture {
+            let body = request.into_hyper_request().unwrap().into_body().concat2().wait().unwrap();
+            assert_eq!(body.as_ref(), &[7; 20_000][..]);
+            let chunks = stream::iter_ok::<_, hyper::Error>(vec![vec![0; 5_000]; 2]);
+            Box::new(future::ok(Response::new(Body::wrap_stream(chunks))))
+        };
+        let response = pipeline::send(vec![stage], transport, request).wait().unwrap();
+
+        // the request body is sent in chunks of 1 KiB, each one waiting
+        // for the ones before: the last one starts after about two seconds
+        let sleeps = sleeper.sleeps();
+        assert_eq!(sleeps.len(), 19);
+        assert!(sleeps[18] > Duration::from_millis(1900) && sleeps[18] <= Duration::from_micros(1_945_600));
+
+        // the response body is paced as it is read, after the request one
+        let body = response.into_body().concat2().wait().unwrap();
+        assert_eq!(body.len(), 10_000);
+        let sleeps = sleeper.sleeps();
+        assert_eq!(sleeps.len(), 21);
+        assert!(sleeps[20] > Duration::from_millis(2400) && sleeps[20] <= Duration::from_millis(2500));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::pipeline::{Next, PipelineFuture, PipelineRequest, Policy};
+use crate::azure::core::sleeper::Sleeper;
+use futures::future::Either;
+use futures::task::{self, Task};
+use futures::{Async, Future, Poll};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Aborts the requests of the clients it is given to (see
+/// `Client::with_cancellation_token`). Cancelling fails the pending
+/// requests, their retries included, with `AzureError::CancelledError`
+/// and the following ones before they are sent. The clones share the
+/// state: keep one to cancel, give the other to the client.
+///
+/// The cancellation covers a request until its response headers arrive.
+/// The body streamed afterwards is not aborted by the token: drop the
+/// response (or the stream) to close the connection.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    // the tasks of the pending `Cancelled`, by registration
+    waiting: Mutex<HashMap<usize, Task>>,
+    next_id: AtomicUsize,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        for (_, task) in self.inner.waiting.lock().unwrap().drain() {
+            task.notify();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Completes once the token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+            id: self.inner.next_id.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// See `CancellationToken::cancelled`.
+#[derive(Debug)]
+pub struct Cancelled {
+    token: CancellationToken,
+    id: usize,
+}
+
+impl Future for Cancelled {
+    type Item = ();
+    type Error = AzureError;
+
+    fn poll(&mut self) -> Poll<(), AzureError> {
+        if self.token.is_cancelled() {
+            return Ok(Async::Ready(()));
+        }
+
+        let mut waiting = self.token.inner.waiting.lock().unwrap();
+        // checked again under the lock, as cancel drains it after the flag
+        if self.token.is_cancelled() {
+            return Ok(Async::Ready(()));
+        }
+        // only the latest task polling is notified
+        waiting.insert(self.id, task::current());
+        Ok(Async::NotReady)
+    }
+}
+
+impl Drop for Cancelled {
+    fn drop(&mut self) {
+        self.token.inner.waiting.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Pipeline stage racing the whole operation, retries included, against
+/// the cancellation of the token.
+#[derive(Debug, Clone)]
+pub(crate) struct CancellationStage {
+    token: CancellationToken,
+}
+
+impl CancellationStage {
+    pub(crate) fn new(token: CancellationToken) -> CancellationStage {
+        CancellationStage { token }
+    }
+}
+
+impl Policy for CancellationStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        if self.token.is_cancelled() {
+            return Box::new(futures::future::err(AzureError::CancelledError));
+        }
+
+        // the request future is dropped with its connection once the
+        // token wins
+        Box::new(next.send(request).select2(self.token.cancelled()).then(|outcome| match outcome {
+            Ok(Either::A((response, _))) => Ok(response),
+            Err(Either::A((error, _))) => Err(error),
+            Ok(Either::B(_)) => Err(AzureError::CancelledError),
+            Err(Either::B((error, _))) => Err(error),
+        }))
+    }
+}
+
+/// Pipeline stage failing the attempts not answered within the timeout
+/// with `AzureError::TimeoutError`, which the retry policy can send
+/// again. The timeout ends with the response headers: it does not bound
+/// the streaming of the body.
+#[derive(Debug, Clone)]
+pub(crate) struct TimeoutStage {
+    timeout: Duration,
+    sleeper: Arc<dyn Sleeper>,
+}
+
+impl TimeoutStage {
+    pub(crate) fn new(timeout: Duration, sleeper: Arc<dyn Sleeper>) -> TimeoutStage {
+        TimeoutStage { timeout, sleeper }
+    }
+}
+
+impl Policy for TimeoutStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        let timeout = self.timeout;
+        Box::new(
+            next.send(request)
+                .select2(self.sleeper.sleep(timeout))
+                .then(move |outcome| match outcome {
+                    Ok(Either::A((response, _))) => Ok(response),
+                    Err(Either::A((error, _))) => Err(error),
+                    Ok(Either::B(_)) => {
+                        warn!("request timed out after {:?}", timeout);
+                        Err(AzureError::TimeoutError(timeout))
+                    }
+                    Err(Either::B((error, _))) => Err(error),
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::http_client::HttpClient;
+    use crate::azure::core::sleeper::VirtualSleeper;
+    use crate::azure::storage::client::Client;
+    use hyper::{Body, Method, Response};
+    use std::sync::atomic::AtomicUsize;
+
+    // never answers
+    #[derive(Debug, Default)]
+    struct HungHttpClient {
+        attempts: AtomicUsize,
+    }
+
+    impl HttpClient for HungHttpClient {
+        fn execute(&self, _request: PipelineRequest) -> PipelineFuture {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            Box::new(futures::future::empty::<Response<Body>, AzureError>())
+        }
+    }
+
+    fn client(http_client: Arc<HungHttpClient>) -> Client {
+        Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(http_client)
+    }
+
+    fn get(client: &Client) -> Result<Response<Body>, AzureError> {
+        client
+            .perform_request("https://account.blob.core.windows.net/c/b", &Method::GET, |_| {}, None)
+            .unwrap()
+            .wait()
+    }
+
+    #[test]
+    fn test_request_timeout() {
+        use crate::azure::core::retry::FixedRetry;
+
+        let http_client = Arc::new(HungHttpClient::default());
+        let sleeper = Arc::new(VirtualSleeper::new());
+        let client = client(http_client.clone())
+            .with_sleeper(sleeper.clone())
+            .with_retry_policy(Arc::new(FixedRetry::new(Duration::from_secs(1), 2)))
+            .with_request_timeout(Duration::from_secs(30));
+
+        match get(&client) {
+            Err(AzureError::TimeoutError(timeout)) => assert_eq!(timeout, Duration::from_secs(30)),
+            other => panic!("unexpected outcome {:?}", other),
+        }
+        // the timeouts are retried
+        assert_eq!(http_client.attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(sleeper.elapsed(), Duration::from_secs(92));
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let http_client = Arc::new(HungHttpClient::default());
+        let token = CancellationToken::new();
+        let client = client(http_client.clone()).with_cancellation_token(token.clone());
+
+        let canceller = token.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            canceller.cancel();
+        });
+        match get(&client) {
+            Err(AzureError::CancelledError) => {}
+            other => panic!("unexpected outcome {:?}", other),
+        }
+        handle.join().unwrap();
+        assert!(token.is_cancelled());
+
+        // not sent at all once cancelled
+        assert!(get(&client).is_err());
+        assert_eq!(http_client.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_dropped_cancelled_unregisters() {
+        let token = CancellationToken::new();
+        let mut cancelled = token.cancelled();
+        futures::future::lazy(|| {
+            assert!(cancelled.poll().unwrap().is_not_ready());
+            // polled again, it is not registered twice
+            assert!(cancelled.poll().unwrap().is_not_ready());
+            Ok::<_, ()>(())
+        })
+        .wait()
+        .unwrap();
+        assert_eq!(token.inner.waiting.lock().unwrap().len(), 1);
+
+        // a long-lived token does not keep the tasks of the past requests
+        drop(cancelled);
+        assert!(token.inner.waiting.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::{CONTENT_CRC64, CONTENT_MD5};
+use base64;
+use http::request::Builder;
+use hyper::header::HeaderMap;
+use md5;
+
+// CRC-64 polynomial used by the storage service (reflected form)
+const CRC64_POLYNOMIAL: u64 = 0x9A6C_9329_AC4B_C9B5;
+
+/// Transactional checksum computed on the uploaded payload. It's sent with
+/// the request, so the service rejects a payload corrupted in transit, and
+/// compared with the checksum returned by the service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumMode {
+    /// `Content-MD5`.
+    MD5,
+    /// `x-ms-content-crc64`, cheaper to compute than MD5.
+    CRC64,
+}
+
+/// The CRC-64 of `data` as computed by the storage service.
+pub fn crc64(data: &[u8]) -> u64 {
+    let mut table = [0u64; 256];
+    for (i, entry) in table.iter_mut().enumerate() {
+        let mut crc = i as u64;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ CRC64_POLYNOMIAL } else { crc >> 1 };
+        }
+        *entry = crc;
+    }
+
+    !data
+        .iter()
+        .fold(!0u64, |crc, byte| table[((crc ^ u64::from(*byte)) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// The checksum of a payload, ready to be sent and validated.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Checksum {
+    mode: ChecksumMode,
+    value: String,
+}
+
+impl Checksum {
+    pub(crate) fn compute(mode: ChecksumMode, data: &[u8]) -> Checksum {
+        let value = match mode {
+            ChecksumMode::MD5 => base64::encode(&md5::compute(data)[..]),
+            ChecksumMode::CRC64 => base64::encode(&crc64(data).to_le_bytes()),
+        };
+        Checksum { mode, value }
+    }
+
+    fn header_name(&self) -> &'static str {
+        match self.mode {
+            ChecksumMode::MD5 => CONTENT_MD5,
+            ChecksumMode::CRC64 => CONTENT_CRC64,
+        }
+    }
+
+    // the header is not added if already set (with `with_content_md5`)
+    pub(crate) fn add_header(&self, builder: &mut Builder) {
+        let present = builder
+            .headers_mut()
+            .is_some_and(|headers| headers.contains_key(self.header_name()));
+        if !present {
+            builder.header(self.header_name(), self.value.as_str());
+        }
+    }
+
+    /// Compares the checksum with the one returned by the service, if any.
+    pub(crate) fn validate(&self, headers: &HeaderMap) -> Result<(), AzureError> {
+        match headers.get(self.header_name()) {
+            Some(returned) if returned.to_str()? != self.value => {
+                Err(AzureError::ChecksumMismatch(self.value.clone(), returned.to_str()?.to_owned()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_crc64() {
+        assert_eq!(crc64(b""), 0);
+        assert_eq!(crc64(b"123456789"), 0xAE8B_1486_0A79_9888);
+    }
+
+    #[test]
+    fn test_validate() {
+        let checksum = Checksum::compute(ChecksumMode::MD5, b"hello world");
+        assert_eq!(checksum.value, "XrY7u+Ae7tCTyyK7j1rNww==");
+
+        let mut headers = HeaderMap::new();
+        assert!(checksum.validate(&headers).is_ok());
+
+        headers.insert(CONTENT_MD5, "XrY7u+Ae7tCTyyK7j1rNww==".parse().unwrap());
+        assert!(checksum.validate(&headers).is_ok());
+
+        headers.insert(CONTENT_MD5, "1B2M2Y8AsgTpgAmY7PhCfg==".parse().unwrap());
+        match checksum.validate(&headers) {
+            Err(AzureError::ChecksumMismatch(computed, returned)) => {
+                assert_eq!(computed, "XrY7u+Ae7tCTyyK7j1rNww==");
+                assert_eq!(returned, "1B2M2Y8AsgTpgAmY7PhCfg==");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_add_header() {
+        let mut builder = Builder::new();
+        let checksum = Checksum::compute(ChecksumMode::CRC64, b"123456789");
+        checksum.add_header(&mut builder);
+        checksum.add_header(&mut builder);
+
+        let request = builder.body(()).unwrap();
+        let values: Vec<_> = request.headers().get_all(CONTENT_CRC64).iter().collect();
+        assert_eq!(values, vec![&base64::encode(&0xAE8B_1486_0A79_9888u64.to_le_bytes())]);
+    }
+}
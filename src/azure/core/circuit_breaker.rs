@@ -0,0 +1,361 @@
+use crate::azure::core::errors::AzureError;
+use futures::Future;
+use hyper::{self, Body, Response};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Tunables of a `CircuitBreaker`. The breaker trips (opens) for a host
+/// when, within a sampling window, at least `minimum_requests` calls have
+/// completed and the ratio of failed ones reaches `failure_ratio`.
+/// While open every call to that host fails fast with
+/// `AzureError::CircuitBreakerOpen`. After `open_duration` a single probe
+/// call is let through: if it succeeds the circuit closes again, otherwise
+/// it stays open for another `open_duration`. With
+/// `LocationMode::PrimaryThenSecondary` the reads rejected by an open
+/// circuit go to the secondary endpoint instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitBreakerPolicy {
+    failure_ratio: f64,
+    minimum_requests: u32,
+    sampling_duration: Duration,
+    open_duration: Duration,
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy {
+            failure_ratio: 0.5,
+            minimum_requests: 10,
+            sampling_duration: Duration::from_secs(60),
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitBreakerPolicy {
+    pub fn new() -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy::default()
+    }
+
+    pub fn with_failure_ratio(self, failure_ratio: f64) -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy { failure_ratio, ..self }
+    }
+
+    pub fn with_minimum_requests(self, minimum_requests: u32) -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy { minimum_requests, ..self }
+    }
+
+    pub fn with_sampling_duration(self, sampling_duration: Duration) -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy { sampling_duration, ..self }
+    }
+
+    pub fn with_open_duration(self, open_duration: Duration) -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy { open_duration, ..self }
+    }
+
+    pub fn failure_ratio(&self) -> f64 {
+        self.failure_ratio
+    }
+
+    pub fn minimum_requests(&self) -> u32 {
+        self.minimum_requests
+    }
+
+    pub fn sampling_duration(&self) -> Duration {
+        self.sampling_duration
+    }
+
+    pub fn open_duration(&self) -> Duration {
+        self.open_duration
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone)]
+enum HostState {
+    Closed {
+        window_start: Instant,
+        successes: u32,
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    HalfOpen,
+}
+
+impl HostState {
+    fn closed(now: Instant) -> HostState {
+        HostState::Closed {
+            window_start: now,
+            successes: 0,
+            failures: 0,
+        }
+    }
+}
+
+/// Per-host circuit breaker. It's meant to be shared (wrapped in an `Arc`)
+/// between all the requests sent by a client.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    policy: CircuitBreakerPolicy,
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(policy: CircuitBreakerPolicy) -> CircuitBreaker {
+        CircuitBreaker {
+            policy,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn policy(&self) -> &CircuitBreakerPolicy {
+        &self.policy
+    }
+
+    pub fn state(&self, host: &str) -> CircuitState {
+        match self.hosts.lock().unwrap().get(host) {
+            None | Some(HostState::Closed { .. }) => CircuitState::Closed,
+            Some(HostState::Open { .. }) => CircuitState::Open,
+            Some(HostState::HalfOpen) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Returns `Err(AzureError::CircuitBreakerOpen)` if a call to `host`
+    /// should not be attempted right now.
+    pub fn check(&self, host: &str) -> Result<(), AzureError> {
+        self.check_at(host, Instant::now())
+    }
+
+    /// Records the outcome of a call to `host`.
+    pub fn record(&self, host: &str, success: bool) {
+        self.record_at(host, success, Instant::now())
+    }
+
+    fn check_at(&self, host: &str, now: Instant) -> Result<(), AzureError> {
+        let mut hosts = self.hosts.lock().unwrap();
+        let state = hosts.entry(host.to_owned()).or_insert_with(|| HostState::closed(now));
+
+        match *state {
+            HostState::Closed { .. } => Ok(()),
+            HostState::Open { until } if now >= until => {
+                debug!("circuit breaker for {} half open, sending probe", host);
+                *state = HostState::HalfOpen;
+                Ok(())
+            }
+            // either still open or a probe is already in flight
+            HostState::Open { .. } | HostState::HalfOpen => Err(AzureError::CircuitBreakerOpen(host.to_owned())),
+        }
+    }
+
+    fn record_at(&self, host: &str, success: bool, now: Instant) {
+        let mut hosts = self.hosts.lock().unwrap();
+        let state = hosts.entry(host.to_owned()).or_insert_with(|| HostState::closed(now));
+
+        let new_state = match *state {
+            HostState::Closed {
+                window_start,
+                successes,
+                failures,
+            } => {
+                let (successes, failures, window_start) = if now.duration_since(window_start) > self.policy.sampling_duration {
+                    (0, 0, now)
+                } else {
+                    (successes, failures, window_start)
+                };
+                let (successes, failures) = if success {
+                    (successes + 1, failures)
+                } else {
+                    (successes, failures + 1)
+                };
+
+                let total = successes + failures;
+                if total >= self.policy.minimum_requests && f64::from(failures) / f64::from(total) >= self.policy.failure_ratio {
+                    warn!(
+                        "circuit breaker for {} open ({} failures out of {} requests)",
+                        host, failures, total
+                    );
+                    HostState::Open {
+                        until: now + self.policy.open_duration,
+                    }
+                } else {
+                    HostState::Closed {
+                        window_start,
+                        successes,
+                        failures,
+                    }
+                }
+            }
+            HostState::HalfOpen => {
+                if success {
+                    debug!("circuit breaker for {} closed, probe succeeded", host);
+                    HostState::closed(now)
+                } else {
+                    warn!("circuit breaker for {} open again, probe failed", host);
+                    HostState::Open {
+                        until: now + self.policy.open_duration,
+                    }
+                }
+            }
+            // late completion of a call started before the circuit opened
+            HostState::Open { until } => HostState::Open { until },
+        };
+
+        *state = new_state;
+    }
+
+    /// Tracks the outcome of `fut`. Transport errors and 5xx responses
+    /// count as failures, everything else as success. A future dropped
+    /// before completing (timed out or cancelled) counts as a failure too,
+    /// so that a probe never leaves the circuit half open.
+    pub(crate) fn watch<F>(this: &Arc<CircuitBreaker>, host: String, fut: F) -> impl Future<Item = Response<Body>, Error = AzureError>
+    where
+        F: Future<Item = Response<Body>, Error = AzureError>,
+    {
+        let mut outcome = Outcome {
+            circuit_breaker: this.clone(),
+            host,
+            recorded: false,
+        };
+        fut.then(move |res| {
+            match res {
+                Ok(ref response) => outcome.record(!response.status().is_server_error()),
+                Err(_) => outcome.record(false),
+            }
+            res
+        })
+    }
+}
+
+// Records the outcome of a watched call, as a failure if the call is
+// dropped without completing.
+struct Outcome {
+    circuit_breaker: Arc<CircuitBreaker>,
+    host: String,
+    recorded: bool,
+}
+
+impl Outcome {
+    fn record(&mut self, success: bool) {
+        self.recorded = true;
+        self.circuit_breaker.record(&self.host, success);
+    }
+}
+
+impl Drop for Outcome {
+    fn drop(&mut self) {
+        if !self.recorded {
+            debug!("call to {} dropped before completing", self.host);
+            self.circuit_breaker.record(&self.host, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn policy() -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy::new()
+            .with_failure_ratio(0.5)
+            .with_minimum_requests(4)
+            .with_sampling_duration(Duration::from_secs(10))
+            .with_open_duration(Duration::from_secs(5))
+    }
+
+    #[test]
+    fn opens_after_failure_ratio() {
+        let cb = CircuitBreaker::new(policy());
+        let now = Instant::now();
+
+        cb.record_at("h", true, now);
+        cb.record_at("h", false, now);
+        cb.record_at("h", true, now);
+        assert_eq!(cb.state("h"), CircuitState::Closed);
+        assert!(cb.check_at("h", now).is_ok());
+
+        cb.record_at("h", false, now);
+        assert_eq!(cb.state("h"), CircuitState::Open);
+        assert!(cb.check_at("h", now).is_err());
+
+        // other hosts are unaffected
+        assert!(cb.check_at("other", now).is_ok());
+    }
+
+    #[test]
+    fn sampling_window_resets_counters() {
+        let cb = CircuitBreaker::new(policy());
+        let now = Instant::now();
+
+        cb.record_at("h", false, now);
+        cb.record_at("h", false, now);
+        cb.record_at("h", false, now);
+
+        let later = now + Duration::from_secs(11);
+        cb.record_at("h", false, later);
+        assert_eq!(cb.state("h"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn probe_closes_circuit() {
+        let cb = CircuitBreaker::new(policy());
+        let now = Instant::now();
+        for _ in 0..4 {
+            cb.record_at("h", false, now);
+        }
+        assert!(cb.check_at("h", now + Duration::from_secs(4)).is_err());
+
+        let probe_time = now + Duration::from_secs(5);
+        assert!(cb.check_at("h", probe_time).is_ok());
+        assert_eq!(cb.state("h"), CircuitState::HalfOpen);
+        // only one probe at a time
+        assert!(cb.check_at("h", probe_time).is_err());
+
+        cb.record_at("h", true, probe_time);
+        assert_eq!(cb.state("h"), CircuitState::Closed);
+        assert!(cb.check_at("h", probe_time).is_ok());
+    }
+
+    #[test]
+    fn failed_probe_reopens_circuit() {
+        let cb = CircuitBreaker::new(policy());
+        let now = Instant::now();
+        for _ in 0..4 {
+            cb.record_at("h", false, now);
+        }
+
+        let probe_time = now + Duration::from_secs(5);
+        assert!(cb.check_at("h", probe_time).is_ok());
+        cb.record_at("h", false, probe_time);
+        assert_eq!(cb.state("h"), CircuitState::Open);
+        assert!(cb.check_at("h", probe_time + Duration::from_secs(4)).is_err());
+        assert!(cb.check_at("h", probe_time + Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn dropped_probe_reopens_circuit() {
+        let cb = Arc::new(CircuitBreaker::new(policy().with_open_duration(Duration::from_secs(0))));
+        for _ in 0..4 {
+            cb.record("h", false);
+        }
+
+        assert!(cb.check("h").is_ok());
+        assert_eq!(cb.state("h"), CircuitState::HalfOpen);
+        // the probe is dropped (timed out) before a response arrives
+        drop(CircuitBreaker::watch(&cb, "h".to_owned(), futures::future::empty()));
+        assert_eq!(cb.state("h"), CircuitState::Open);
+        assert!(cb.check("h").is_ok());
+
+        let probe = CircuitBreaker::watch(&cb, "h".to_owned(), futures::future::ok(Response::new(Body::empty())));
+        probe.wait().unwrap();
+        assert_eq!(cb.state("h"), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::CLIENT_REQUEST_ID;
+use crate::azure::core::pipeline::{Next, PipelineFuture, PipelineRequest, Policy};
+use futures::Future;
+use hyper::header::HeaderValue;
+use uuid::Uuid;
+
+/// Pipeline stage giving the requests without an `x-ms-client-request-id`
+/// a new UUID, shared by their retries, and optionally checking that the
+/// service echoes it (see `Client::with_client_request_id_echo_required`).
+/// Only the successful responses are checked: an error response keeps its
+/// own error, whoever sent it.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ClientRequestIdStage {
+    generate: bool,
+    require_echo: bool,
+}
+
+impl ClientRequestIdStage {
+    pub(crate) fn new(generate: bool, require_echo: bool) -> ClientRequestIdStage {
+        ClientRequestIdStage { generate, require_echo }
+    }
+}
+
+impl Policy for ClientRequestIdStage {
+    fn send(&self, mut request: PipelineRequest, next: Next) -> PipelineFuture {
+        if self.generate && !request.headers.contains_key(CLIENT_REQUEST_ID) {
+            let client_request_id = Uuid::new_v4().to_hyphenated().to_string();
+            request
+                .headers
+                .insert(CLIENT_REQUEST_ID, HeaderValue::from_str(&client_request_id).unwrap());
+        }
+
+        let sent = match request.headers.get(CLIENT_REQUEST_ID).and_then(|sent| sent.to_str().ok()) {
+            Some(sent) if self.require_echo => sent.to_owned(),
+            _ => return next.send(request),
+        };
+        Box::new(next.send(request).and_then(move |response| {
+            if !response.status().is_success() {
+                return Ok(response);
+            }
+            let received = response
+                .headers()
+                .get(CLIENT_REQUEST_ID)
+                .and_then(|received| received.to_str().ok())
+                .map(str::to_owned);
+            if received.as_ref() == Some(&sent) {
+                Ok(response)
+            } else {
+                Err(AzureError::ClientRequestIdMismatch(sent, received))
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::core::{ClientRequestIdSupport, ContainerNameSupport};
+    use crate::azure::storage::client::{Client, Container};
+    use hyper::StatusCode;
+    use std::sync::Arc;
+
+    fn client(transport: Arc<ReplayTransport>) -> Client {
+        Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport)
+    }
+
+    #[test]
+    fn test_generated_client_request_id() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::ACCEPTED, &[], b"")
+                .with_response(StatusCode::ACCEPTED, &[], b"")
+                .with_response(StatusCode::ACCEPTED, &[], b""),
+        );
+        let generating = client(transport.clone());
+        for _ in 0..2 {
+            generating.delete_container().with_container_name("c").finalize().wait().unwrap();
+        }
+        generating
+            .delete_container()
+            .with_container_name("c")
+            .with_client_request_id("mine")
+            .finalize()
+            .wait()
+            .unwrap();
+
+        let ids: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|request| request.headers[CLIENT_REQUEST_ID].to_str().unwrap().to_owned())
+            .collect();
+        assert!(Uuid::parse_str(&ids[0]).is_ok());
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(ids[2], "mine");
+
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::ACCEPTED, &[], b""));
+        let not_generating = client(transport.clone()).without_client_request_ids();
+        not_generating.delete_container().with_container_name("c").finalize().wait().unwrap();
+        assert!(!transport.requests()[0].headers.contains_key(CLIENT_REQUEST_ID));
+    }
+
+    #[test]
+    fn test_client_request_id_echo() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::ACCEPTED, &[(CLIENT_REQUEST_ID, "mine")], b"")
+                .with_response(StatusCode::ACCEPTED, &[(CLIENT_REQUEST_ID, "someone else's")], b"")
+                .with_response(StatusCode::NOT_FOUND, &[], b""),
+        );
+        let client = client(transport).with_client_request_id_echo_required();
+        let delete = || {
+            client
+                .delete_container()
+                .with_container_name("c")
+                .with_client_request_id("mine")
+                .finalize()
+                .wait()
+        };
+
+        assert!(delete().is_ok());
+        match delete() {
+            Err(AzureError::ClientRequestIdMismatch(sent, received)) => {
+                assert_eq!(sent, "mine");
+                assert_eq!(received.as_deref(), Some("someone else's"));
+            }
+            other => panic!("unexpected outcome {:?}", other),
+        }
+        match delete() {
+            Err(error) => assert_eq!(error.status(), Some(StatusCode::NOT_FOUND)),
+            other => panic!("unexpected outcome {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::azure::core::headers::ERROR_CODE;
+use crate::azure::core::pipeline::{Next, PipelineFuture, PipelineRequest, Policy};
+use chrono::{DateTime, Utc};
+use futures::future::{self, Either};
+use futures::Future;
+use hyper::header::DATE;
+use hyper::{Body, Response, StatusCode};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The service rejects the Shared Key requests dated more than 15 minutes
+/// away from its clock.
+pub(crate) const MAX_CLOCK_SKEW_MINUTES: i64 = 15;
+
+/// Keeps track of the offset between the local clock and the clock of
+/// the service, so the requests can be dated with the service time.
+///
+/// The offset starts at zero. When a request fails with
+/// `AuthenticationFailed` and the `Date` of the response shows that the
+/// local clock is too far off, the offset is updated and the request is
+/// signed and sent again, once. Offsets larger than `max_offset` are not
+/// applied: the error is returned as is.
+#[derive(Debug)]
+pub struct ClockSkew {
+    max_offset: Duration,
+    offset_ms: AtomicI64,
+}
+
+impl ClockSkew {
+    pub fn new(max_offset: Duration) -> ClockSkew {
+        ClockSkew {
+            max_offset,
+            offset_ms: AtomicI64::new(0),
+        }
+    }
+
+    pub fn max_offset(&self) -> Duration {
+        self.max_offset
+    }
+
+    /// The service time minus the local time, as measured so far.
+    pub fn offset(&self) -> chrono::Duration {
+        chrono::Duration::milliseconds(self.offset_ms.load(Ordering::Relaxed))
+    }
+
+    /// The current time according to the service clock.
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.offset()
+    }
+
+    // Updates the offset from the date of a response that rejected a
+    // request dated `local_now + offset`. Returns false if the skew does
+    // not explain the rejection, or cannot be compensated.
+    fn correct(&self, server_date: DateTime<Utc>, local_now: DateTime<Utc>) -> bool {
+        let measured = server_date - local_now;
+        if (measured - self.offset()).num_minutes().abs() < MAX_CLOCK_SKEW_MINUTES {
+            return false;
+        }
+
+        let max_offset = chrono::Duration::from_std(self.max_offset).unwrap_or_else(|_| chrono::Duration::max_value());
+        if measured > max_offset || measured < -max_offset {
+            warn!("the local clock is off by {}, more than the compensation cap", measured);
+            return false;
+        }
+
+        warn!("the local clock is off by {}, compensating", measured);
+        self.offset_ms.store(measured.num_milliseconds(), Ordering::Relaxed);
+        true
+    }
+}
+
+fn is_authentication_failure(response: &Response<Body>) -> bool {
+    response.status() == StatusCode::FORBIDDEN
+        && response
+            .headers()
+            .get(ERROR_CODE)
+            .is_some_and(|error_code| error_code == "AuthenticationFailed")
+}
+
+fn date_from_response(response: &Response<Body>) -> Option<DateTime<Utc>> {
+    let date = response.headers().get(DATE)?.to_str().ok()?;
+    Some(DateTime::parse_from_rfc2822(date).ok()?.with_timezone(&Utc))
+}
+
+/// Pipeline stage sending the request again when it was rejected because
+/// of the clock skew. The signing stage dates the requests with
+/// `ClockSkew::now`.
+#[derive(Debug, Clone)]
+pub(crate) struct ClockSkewStage {
+    clock_skew: Arc<ClockSkew>,
+}
+
+impl ClockSkewStage {
+    pub(crate) fn new(clock_skew: Arc<ClockSkew>) -> ClockSkewStage {
+        ClockSkewStage { clock_skew }
+    }
+}
+
+impl Policy for ClockSkewStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        let clock_skew = self.clock_skew.clone();
+
+        Box::new(next.send(request.clone()).and_then(move |response| {
+            let corrected = is_authentication_failure(&response)
+                && date_from_response(&response).is_some_and(|server_date| clock_skew.correct(server_date, Utc::now()));
+            if corrected {
+                Either::A(next.send(request))
+            } else {
+                Either::B(future::ok(response))
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::pipeline;
+    use hyper::header::{HeaderMap, HeaderValue};
+    use hyper::Method;
+    use std::sync::atomic::AtomicUsize;
+
+    const SIGNED_AT: &str = "x-ms-signed-at";
+
+    // stands in for the signing stage
+    #[derive(Debug)]
+    struct Dating(Arc<ClockSkew>);
+
+    impl Policy for Dating {
+        fn send(&self, mut request: PipelineRequest, next: Next) -> PipelineFuture {
+            let now = HeaderValue::from_str(&self.0.now().to_rfc2822()).unwrap();
+            request.headers.insert(SIGNED_AT, now);
+            next.send(request)
+        }
+    }
+
+    fn send(clock_skew: &Arc<ClockSkew>, service_offset: chrono::Duration, attempts: &Arc<AtomicUsize>) -> StatusCode {
+        let policies: Vec<Arc<dyn Policy>> = vec![
+            Arc::new(ClockSkewStage::new(clock_skew.clone())),
+            Arc::new(Dating(clock_skew.clone())),
+        ];
+        let request = PipelineRequest {
+            method: Method::GET,
+            uri: "https://account.blob.core.windows.net/container".to_owned(),
+            headers: HeaderMap::new(),
+            body: None,
+            pacer: None,
+        };
+        let attempts = attempts.clone();
+
+        pipeline::send(
+            policies,
+            move |request: PipelineRequest| -> PipelineFuture {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                let server_now = Utc::now() + service_offset;
+                let signed_at = request.headers[SIGNED_AT].to_str().unwrap();
+                let signed_at = DateTime::parse_from_rfc2822(signed_at).unwrap().with_timezone(&Utc);
+
+                let mut response = Response::new(Body::empty());
+                if (server_now - signed_at).num_minutes().abs() >= MAX_CLOCK_SKEW_MINUTES {
+                    *response.status_mut() = StatusCode::FORBIDDEN;
+                    response
+                        .headers_mut()
+                        .insert(ERROR_CODE, HeaderValue::from_static("AuthenticationFailed"));
+                }
+                let date = server_now.format("%a, %d %h %Y %T GMT").to_string();
+                response.headers_mut().insert(DATE, HeaderValue::from_str(&date).unwrap());
+                Box::new(future::ok(response))
+            },
+            request,
+        )
+        .wait()
+        .unwrap()
+        .status()
+    }
+
+    #[test]
+    fn test_compensation() {
+        let clock_skew = Arc::new(ClockSkew::new(Duration::from_secs(24 * 3600)));
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        assert_eq!(send(&clock_skew, chrono::Duration::hours(2), &attempts), StatusCode::OK);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert!((clock_skew.offset() - chrono::Duration::hours(2)).num_seconds().abs() <= 2);
+
+        // the following requests are dated correctly right away
+        assert_eq!(send(&clock_skew, chrono::Duration::hours(2), &attempts), StatusCode::OK);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_cap() {
+        let clock_skew = Arc::new(ClockSkew::new(Duration::from_secs(3600)));
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        assert_eq!(send(&clock_skew, chrono::Duration::hours(-2), &attempts), StatusCode::FORBIDDEN);
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(clock_skew.offset(), chrono::Duration::zero());
+    }
+
+    #[test]
+    fn test_not_skewed() {
+        let clock_skew = ClockSkew::new(Duration::from_secs(3600));
+        let now = Utc::now();
+        assert!(!clock_skew.correct(now + chrono::Duration::minutes(1), now));
+        assert!(clock_skew.correct(now + chrono::Duration::minutes(20), now));
+        assert_eq!(clock_skew.offset(), chrono::Duration::minutes(20));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::{
+    API_VERSION, CLIENT_REQUEST_ID, ENCRYPTION_KEY_SHA256, REQUEST_ID, REQUEST_SERVER_ENCRYPTED, SERVER_ENCRYPTED,
+};
+use crate::azure::core::util::HeaderMapExt;
+use crate::azure::core::{date_from_headers, RequestId};
+use chrono::{DateTime, Utc};
+use hyper::header::{HeaderMap, DATE};
+use uuid::Uuid;
+
+/// The headers the storage services send back with every response,
+/// parsed the same way for all of them (see the `common_headers` field of
+/// the responses). A header missing from the response, as some of them
+/// are from the emulators or from the responses that do not apply, is
+/// `None`; a header that cannot be parsed is an error.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommonStorageResponseHeaders {
+    pub request_id: Option<RequestId>,
+    /// The `x-ms-client-request-id` of the request, echoed.
+    pub client_request_id: Option<String>,
+    /// The `x-ms-version` the request was processed with.
+    pub version: Option<String>,
+    pub date: Option<DateTime<Utc>>,
+    /// Whether the contents (or the metadata) written or read are
+    /// encrypted by the service: `x-ms-request-server-encrypted` on the
+    /// writes, `x-ms-server-encrypted` on the reads.
+    pub server_encrypted: Option<bool>,
+    /// The SHA-256 of the customer-provided key used, if any.
+    pub encryption_key_sha256: Option<String>,
+}
+
+impl CommonStorageResponseHeaders {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<CommonStorageResponseHeaders, AzureError> {
+        let request_id = match headers.get_as_str(REQUEST_ID) {
+            Some(request_id) => Some(Uuid::parse_str(request_id)?),
+            None => None,
+        };
+        let date = if headers.contains_key(DATE) {
+            Some(date_from_headers(headers)?)
+        } else {
+            None
+        };
+        let server_encrypted = match headers
+            .get_as_str(REQUEST_SERVER_ENCRYPTED)
+            .or_else(|| headers.get_as_str(SERVER_ENCRYPTED))
+        {
+            Some(server_encrypted) => Some(server_encrypted.parse::<bool>()?),
+            None => None,
+        };
+
+        Ok(CommonStorageResponseHeaders {
+            request_id,
+            client_request_id: headers.get_as_string(CLIENT_REQUEST_ID),
+            version: headers.get_as_string(API_VERSION),
+            date,
+            server_encrypted,
+            encryption_key_sha256: headers.get_as_string(ENCRYPTION_KEY_SHA256),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hyper::header::HeaderValue;
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID, HeaderValue::from_static("6ee5ea5b-0003-0040-4b77-2e5c7f000000"));
+        headers.insert(CLIENT_REQUEST_ID, HeaderValue::from_static("upload-42"));
+        headers.insert(API_VERSION, HeaderValue::from_static("2019-02-02"));
+        headers.insert(DATE, HeaderValue::from_static("Tue, 15 Oct 2019 10:00:00 GMT"));
+        headers.insert(REQUEST_SERVER_ENCRYPTED, HeaderValue::from_static("true"));
+        headers.insert(
+            ENCRYPTION_KEY_SHA256,
+            HeaderValue::from_static("3QFFFpRA5+XANHqwwbT4yXDmrT/2JaLt/FKHjzhOdoE="),
+        );
+
+        let common = CommonStorageResponseHeaders::from_headers(&headers).unwrap();
+        assert_eq!(
+            common.request_id,
+            Some(Uuid::parse_str("6ee5ea5b-0003-0040-4b77-2e5c7f000000").unwrap())
+        );
+        assert_eq!(common.client_request_id.as_deref(), Some("upload-42"));
+        assert_eq!(common.version.as_deref(), Some("2019-02-02"));
+        assert_eq!(common.date.unwrap().to_rfc2822(), "Tue, 15 Oct 2019 10:00:00 +0000");
+        assert_eq!(common.server_encrypted, Some(true));
+        assert!(common.encryption_key_sha256.is_some());
+
+        assert_eq!(
+            CommonStorageResponseHeaders::from_headers(&HeaderMap::new()).unwrap(),
+            CommonStorageResponseHeaders::default()
+        );
+        headers.insert(REQUEST_ID, HeaderValue::from_static("not a uuid"));
+        assert!(CommonStorageResponseHeaders::from_headers(&headers).is_err());
+    }
+}
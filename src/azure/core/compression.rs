@@ -0,0 +1,48 @@
+use crate::azure::core::errors::AzureError;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// Returns true if the Content-Encoding value denotes gzip compression.
+pub fn is_gzip_encoding(content_encoding: &str) -> bool {
+    let content_encoding = content_encoding.trim();
+    content_encoding.eq_ignore_ascii_case("gzip") || content_encoding.eq_ignore_ascii_case("x-gzip")
+}
+
+pub fn gzip(data: &[u8]) -> Result<Vec<u8>, AzureError> {
+    let mut encoder = GzEncoder::new(Vec::with_capacity(data.len() / 2), Compression::default());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+pub fn gunzip(data: &[u8]) -> Result<Vec<u8>, AzureError> {
+    let mut decoded = Vec::with_capacity(data.len() * 2);
+    GzDecoder::new(data).read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let text = "lorem ipsum dolor sit amet ".repeat(100);
+        let compressed = gzip(text.as_bytes()).unwrap();
+        assert!(compressed.len() < text.len());
+        assert_eq!(gunzip(&compressed).unwrap(), text.as_bytes());
+    }
+
+    #[test]
+    fn test_gunzip_garbage() {
+        assert!(gunzip(b"not gzipped").is_err());
+    }
+
+    #[test]
+    fn test_is_gzip_encoding() {
+        assert!(is_gzip_encoding("gzip"));
+        assert!(is_gzip_encoding("X-GZIP"));
+        assert!(!is_gzip_encoding("deflate"));
+    }
+}
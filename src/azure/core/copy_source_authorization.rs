@@ -0,0 +1,55 @@
+use crate::azure::core::headers::COPY_SOURCE_AUTHORIZATION;
+use http::request::Builder;
+use std::fmt;
+
+/// The credentials the service presents to the source of a copy from URL.
+/// An Azure AD access token (for the `https://storage.azure.com/`
+/// resource) lets the copies between accounts read the source without a
+/// SAS in its URL.
+#[derive(Clone, PartialEq, Eq)]
+pub enum CopySourceAuthorization {
+    Bearer(String),
+}
+
+impl CopySourceAuthorization {
+    pub fn bearer(token: &str) -> CopySourceAuthorization {
+        CopySourceAuthorization::Bearer(token.to_owned())
+    }
+
+    pub(crate) fn add_header(&self, builder: &mut Builder) {
+        builder.header(COPY_SOURCE_AUTHORIZATION, &self.to_string() as &str);
+    }
+}
+
+impl fmt::Display for CopySourceAuthorization {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CopySourceAuthorization::Bearer(token) => write!(f, "Bearer {}", token),
+        }
+    }
+}
+
+// the token must not end up in the logs
+impl fmt::Debug for CopySourceAuthorization {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CopySourceAuthorization::Bearer(_) => f.write_str("Bearer"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_copy_source_authorization() {
+        let authorization = CopySourceAuthorization::bearer("eyJ0eXAi");
+        assert_eq!(format!("{:?}", authorization), "Bearer");
+
+        let mut builder = Builder::new();
+        authorization.add_header(&mut builder);
+        let request = builder.body(()).unwrap();
+        assert_eq!(request.headers()[COPY_SOURCE_AUTHORIZATION], "Bearer eyJ0eXAi");
+    }
+}
@@ -0,0 +1,282 @@
+//! The signing primitives shared by the services: storage Shared Key,
+//! Service Bus (and Event Hubs) SAS tokens and Cosmos master/resource
+//! tokens. They are public so custom requests can be signed the same way.
+//!
+//! The primitives are provided by `ring` (the `ring` feature, enabled by
+//! default) or by OpenSSL (the `openssl` feature, which wins if both are
+//! enabled), for example for FIPS-targeted builds:
+//!
+//! ```toml
+//! azure_sdk_for_rust = { version = "0.11", default-features = false, features = ["openssl"] }
+//! ```
+
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::COMPLETE_ENCODE_SET;
+use base64;
+use chrono::Utc;
+use std::fmt;
+use time::Duration;
+use url::form_urlencoded::Serializer;
+use url::percent_encoding::utf8_percent_encode;
+
+#[cfg(not(any(feature = "ring", feature = "openssl")))]
+compile_error!("either the `ring` or the `openssl` feature must be enabled");
+
+#[cfg(all(feature = "ring", not(feature = "openssl")))]
+mod backend {
+    use ring::aead::{self, Aad, Nonce, OpeningKey, SealingKey, AES_256_GCM};
+    use ring::rand::{SecureRandom, SystemRandom};
+    use ring::{digest, hmac};
+    use std::sync::Arc;
+
+    pub(super) fn sha256(data: &[u8]) -> Vec<u8> {
+        digest::digest(&digest::SHA256, data).as_ref().to_vec()
+    }
+
+    #[derive(Clone)]
+    pub(super) struct Key(Arc<hmac::SigningKey>);
+
+    impl Key {
+        pub(super) fn new(key: &[u8]) -> Key {
+            Key(Arc::new(hmac::SigningKey::new(&digest::SHA256, key)))
+        }
+
+        pub(super) fn sign(&self, data: &[u8]) -> Vec<u8> {
+            hmac::sign(&self.0, data).as_ref().to_vec()
+        }
+    }
+
+    pub(super) fn random_bytes(dest: &mut [u8]) -> Result<(), ()> {
+        SystemRandom::new().fill(dest).map_err(|_| ())
+    }
+
+    pub(super) fn aes256_gcm_encrypt(key: &[u8], nonce: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, ()> {
+        let key = SealingKey::new(&AES_256_GCM, key).map_err(|_| ())?;
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| ())?;
+
+        let tag_len = AES_256_GCM.tag_len();
+        let mut in_out = Vec::with_capacity(plaintext.len() + tag_len);
+        in_out.extend_from_slice(plaintext);
+        in_out.resize(plaintext.len() + tag_len, 0);
+        let len = aead::seal_in_place(&key, nonce, Aad::empty(), &mut in_out, tag_len).map_err(|_| ())?;
+        in_out.truncate(len);
+        Ok(in_out)
+    }
+
+    pub(super) fn aes256_gcm_decrypt(key: &[u8], nonce: &[u8], ciphertext_and_tag: &[u8]) -> Result<Vec<u8>, ()> {
+        let key = OpeningKey::new(&AES_256_GCM, key).map_err(|_| ())?;
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| ())?;
+
+        let mut in_out = ciphertext_and_tag.to_vec();
+        let plaintext = aead::open_in_place(&key, nonce, Aad::empty(), 0, &mut in_out).map_err(|_| ())?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+#[cfg(feature = "openssl")]
+mod backend {
+    use openssl::hash::{hash, MessageDigest};
+    use openssl::pkey::PKey;
+    use openssl::rand::rand_bytes;
+    use openssl::sign::Signer;
+    use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+    use std::sync::Arc;
+
+    const TAG_LENGTH: usize = 16;
+
+    pub(super) fn sha256(data: &[u8]) -> Vec<u8> {
+        hash(MessageDigest::sha256(), data).expect("SHA-256 failed").to_vec()
+    }
+
+    #[derive(Clone)]
+    pub(super) struct Key(Arc<Vec<u8>>);
+
+    impl Key {
+        pub(super) fn new(key: &[u8]) -> Key {
+            Key(Arc::new(key.to_vec()))
+        }
+
+        pub(super) fn sign(&self, data: &[u8]) -> Vec<u8> {
+            let sign = || {
+                let key = PKey::hmac(&self.0)?;
+                let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+                signer.update(data)?;
+                signer.sign_to_vec()
+            };
+            sign().expect("HMAC-SHA256 failed")
+        }
+    }
+
+    pub(super) fn random_bytes(dest: &mut [u8]) -> Result<(), ()> {
+        rand_bytes(dest).map_err(|_| ())
+    }
+
+    pub(super) fn aes256_gcm_encrypt(key: &[u8], nonce: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, ()> {
+        let mut tag = [0u8; TAG_LENGTH];
+        let mut ciphertext = encrypt_aead(Cipher::aes_256_gcm(), key, Some(nonce), &[], plaintext, &mut tag).map_err(|_| ())?;
+        ciphertext.extend_from_slice(&tag);
+        Ok(ciphertext)
+    }
+
+    pub(super) fn aes256_gcm_decrypt(key: &[u8], nonce: &[u8], ciphertext_and_tag: &[u8]) -> Result<Vec<u8>, ()> {
+        if ciphertext_and_tag.len() < TAG_LENGTH {
+            return Err(());
+        }
+        let (ciphertext, tag) = ciphertext_and_tag.split_at(ciphertext_and_tag.len() - TAG_LENGTH);
+        decrypt_aead(Cipher::aes_256_gcm(), key, Some(nonce), &[], ciphertext, tag).map_err(|_| ())
+    }
+}
+
+/// SHA-256 digest of `data`.
+pub fn sha256(data: &[u8]) -> Vec<u8> {
+    backend::sha256(data)
+}
+
+/// HMAC-SHA256 of `data` with `key`.
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    HmacKey::new(key).sign(data)
+}
+
+/// Fills `dest` with cryptographically secure random bytes.
+pub fn random_bytes(dest: &mut [u8]) -> Result<(), AzureError> {
+    backend::random_bytes(dest).map_err(|_| AzureError::EncryptionError("cannot generate random bytes".to_owned()))
+}
+
+/// AES-256-GCM encryption of `plaintext` (without additional data). The
+/// 16 bytes tag is appended to the ciphertext.
+pub fn aes256_gcm_encrypt(key: &[u8], nonce: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AzureError> {
+    backend::aes256_gcm_encrypt(key, nonce, plaintext).map_err(|_| AzureError::EncryptionError("encryption failed".to_owned()))
+}
+
+/// Decrypts and authenticates the output of `aes256_gcm_encrypt`.
+pub fn aes256_gcm_decrypt(key: &[u8], nonce: &[u8], ciphertext_and_tag: &[u8]) -> Result<Vec<u8>, AzureError> {
+    backend::aes256_gcm_decrypt(key, nonce, ciphertext_and_tag)
+        .map_err(|_| AzureError::EncryptionError("decryption failed: wrong key or corrupted data".to_owned()))
+}
+
+/// An HMAC-SHA256 key, cheap to clone. Its `Debug` output does not
+/// include the key.
+#[derive(Clone)]
+pub struct HmacKey {
+    key: backend::Key,
+}
+
+impl HmacKey {
+    pub fn new(key: &[u8]) -> HmacKey {
+        HmacKey {
+            key: backend::Key::new(key),
+        }
+    }
+
+    /// The key as given by the portal (storage account and Cosmos keys).
+    pub fn from_base64(key: &str) -> Result<HmacKey, AzureError> {
+        Ok(HmacKey::new(&base64::decode(key)?))
+    }
+
+    pub fn sign(&self, data: &[u8]) -> Vec<u8> {
+        self.key.sign(data)
+    }
+
+    /// The base64 encoded signature of `str_to_sign`.
+    pub fn sign_str(&self, str_to_sign: &str) -> String {
+        base64::encode(&self.sign(str_to_sign.as_bytes()))
+    }
+}
+
+impl fmt::Debug for HmacKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("HmacKey")
+    }
+}
+
+/// The storage `Authorization` header value (`SharedKey account:signature`).
+pub fn shared_key_authorization(account: &str, key: &HmacKey, str_to_sign: &str) -> String {
+    format!("SharedKey {}:{}", account, key.sign_str(str_to_sign))
+}
+
+/// A Service Bus (or Event Hubs) SAS token for `url`, valid for `ttl`.
+pub fn service_bus_sas_token(policy_name: &str, key: &HmacKey, url: &str, ttl: Duration) -> String {
+    let expiry = (Utc::now() + ttl).timestamp();
+    debug!("expiry == {:?}", expiry);
+
+    service_bus_sas_token_expiring_at(policy_name, key, url, expiry)
+}
+
+fn service_bus_sas_token_expiring_at(policy_name: &str, key: &HmacKey, url: &str, expiry: i64) -> String {
+    let url_encoded = utf8_percent_encode(url, COMPLETE_ENCODE_SET);
+
+    let str_to_sign = format!("{}\n{}", url_encoded, expiry);
+    debug!("str_to_sign == {:?}", str_to_sign);
+
+    let sig = {
+        let mut ser = Serializer::new(String::new());
+        ser.append_pair("sig", &key.sign_str(&str_to_sign));
+        ser.finish()
+    };
+    debug!("sig == {:?}", sig);
+
+    format!(
+        "SharedAccessSignature sr={}&{}&se={}&skn={}",
+        &url_encoded, sig, expiry, policy_name
+    )
+}
+
+/// The Cosmos `Authorization` header value. `token_type` is `master` or
+/// `resource`.
+pub fn cosmos_authorization(key: &HmacKey, token_type: &str, version: &str, str_to_sign: &str) -> String {
+    let str_unencoded = format!("type={}&ver={}&sig={}", token_type, version, key.sign_str(str_to_sign));
+    trace!("cosmos_authorization::str_unencoded == {:?}", str_unencoded);
+
+    utf8_percent_encode(&str_unencoded, COMPLETE_ENCODE_SET).collect::<String>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231, test case 1
+        assert_eq!(
+            base64::encode(&hmac_sha256(&[0x0b; 20], b"Hi There")),
+            "sDRMYdjbOFNcqK/OrwvxK4gdwgDJgz2nJuk3bC4yz/c="
+        );
+        assert_eq!(base64::encode(&sha256(b"abc")), "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=");
+    }
+
+    #[test]
+    fn test_aes256_gcm() {
+        // NIST GCM test case 14 (zero key, IV and plaintext)
+        let encrypted = aes256_gcm_encrypt(&[0; 32], &[0; 12], &[0; 16]).unwrap();
+        assert_eq!(
+            encrypted,
+            vec![
+                0xce, 0xa7, 0x40, 0x3d, 0x4d, 0x60, 0x6b, 0x6e, 0x07, 0x4e, 0xc5, 0xd3, 0xba, 0xf3, 0x9d, 0x18, 0xd0, 0xd1, 0xc8, 0xa7,
+                0x99, 0x99, 0x6b, 0xf0, 0x26, 0x5b, 0x98, 0xb5, 0xd4, 0x8a, 0xb9, 0x19,
+            ]
+        );
+        assert_eq!(aes256_gcm_decrypt(&[0; 32], &[0; 12], &encrypted).unwrap(), vec![0; 16]);
+        assert!(aes256_gcm_decrypt(&[1; 32], &[0; 12], &encrypted).is_err());
+        assert!(aes256_gcm_decrypt(&[0; 32], &[0; 12], &encrypted[..10]).is_err());
+    }
+
+    #[test]
+    fn test_shared_key_authorization() {
+        let key = HmacKey::from_base64("a2V5").unwrap();
+        assert_eq!(
+            shared_key_authorization("account", &key, "abc"),
+            "SharedKey account:nBluMtwBdfhvSxy4konWYZ3mvuaZ5MN45oMJ7Zehpqs="
+        );
+        assert!(HmacKey::from_base64("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_service_bus_sas_token() {
+        let key = HmacKey::new(b"key");
+        assert_eq!(
+            service_bus_sas_token_expiring_at("policy", &key, "https://ns.servicebus.windows.net/hub", 1_500_000_000),
+            "SharedAccessSignature sr=https%3A%2F%2Fns.servicebus.windows.net%2Fhub\
+             &sig=w%2BU9MwnGj46IDp1GlJ%2F2dmJ4zem9zfSf0I8de72y3Kk%3D&se=1500000000&skn=policy"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::azure::core::crypto::sha256;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::{ENCRYPTION_ALGORITHM, ENCRYPTION_KEY, ENCRYPTION_KEY_SHA256};
+use base64;
+use http::request::Builder;
+use std::fmt;
+
+const KEY_LENGTH: usize = 32;
+
+/// An AES-256 key used by the service to encrypt (and decrypt) the blob
+/// data at rest. The key is sent with every request and never stored by
+/// the service: the blob cannot be read or modified without it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CustomerProvidedKey {
+    key: String,
+    key_sha256: String,
+}
+
+impl CustomerProvidedKey {
+    pub fn new(key: &[u8]) -> Result<CustomerProvidedKey, AzureError> {
+        if key.len() != KEY_LENGTH {
+            return Err(AzureError::InputParametersError(format!(
+                "the customer-provided key must be {} bytes long",
+                KEY_LENGTH
+            )));
+        }
+        Ok(CustomerProvidedKey {
+            key: base64::encode(key),
+            key_sha256: base64::encode(&sha256(key)),
+        })
+    }
+
+    pub fn from_base64(key: &str) -> Result<CustomerProvidedKey, AzureError> {
+        CustomerProvidedKey::new(&base64::decode(key)?)
+    }
+
+    /// The base64 encoded key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The base64 encoded SHA-256 of the key, returned by the service in
+    /// the `x-ms-encryption-key-sha256` header.
+    pub fn key_sha256(&self) -> &str {
+        &self.key_sha256
+    }
+
+    pub fn algorithm(&self) -> &str {
+        "AES256"
+    }
+
+    pub(crate) fn add_headers(&self, builder: &mut Builder) {
+        builder.header(ENCRYPTION_KEY, self.key());
+        builder.header(ENCRYPTION_KEY_SHA256, self.key_sha256());
+        builder.header(ENCRYPTION_ALGORITHM, self.algorithm());
+    }
+}
+
+// the key must not end up in the logs
+impl fmt::Debug for CustomerProvidedKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CustomerProvidedKey").field("key_sha256", &self.key_sha256).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_customer_provided_key() {
+        let key = CustomerProvidedKey::new(&[0u8; 32]).unwrap();
+        assert_eq!(key.key(), "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=");
+        assert_eq!(key.key_sha256(), "Zmh6rfhivXdsj8GLjp+OIAiXFIVu4jOzkCpZHQ1fKSU=");
+        assert_eq!(
+            format!("{:?}", key),
+            "CustomerProvidedKey { key_sha256: \"Zmh6rfhivXdsj8GLjp+OIAiXFIVu4jOzkCpZHQ1fKSU=\" }"
+        );
+        assert!(CustomerProvidedKey::new(&[0u8; 16]).is_err());
+
+        let mut builder = Builder::new();
+        key.add_headers(&mut builder);
+        let request = builder.body(()).unwrap();
+        assert_eq!(request.headers()[ENCRYPTION_ALGORITHM], "AES256");
+        assert_eq!(request.headers()[ENCRYPTION_KEY_SHA256], key.key_sha256());
+    }
+}
@@ -1,11 +1,13 @@
+use crate::azure::core::headers::{CLIENT_REQUEST_ID, ERROR_CODE, REQUEST_ID};
+use crate::azure::core::tls::TlsError;
 use crate::azure::core::{enumerations::ParsingError, range::ParseError};
+use crate::azure::storage::StorageErrorCode;
 use base64;
 use chrono;
 use futures::{Future, Stream};
 use http;
 use http::header::ToStrError;
-use hyper::{self, StatusCode};
-use hyper_tls;
+use hyper::{self, HeaderMap, StatusCode};
 use serde_json;
 use serde_xml_rs;
 use std;
@@ -15,6 +17,7 @@ use std::num::ParseIntError;
 use std::str;
 use std::str::ParseBoolError;
 use std::string;
+use std::time::Duration;
 use url::ParseError as URLParseError;
 use uuid;
 use xml::BuilderError as XMLError;
@@ -42,6 +45,38 @@ pub struct UnexpectedHTTPResult {
     expected: StatusCode,
     received: StatusCode,
     body: String,
+    request_id: Option<String>,
+    // a boxed str keeps AzureError under 128 bytes
+    client_request_id: Option<Box<str>>,
+    error_code: Option<String>,
+    date: Option<String>,
+}
+
+// the XML error body of the blob, queue and file services
+#[derive(Debug, Deserialize)]
+struct XmlErrorBody {
+    #[serde(rename = "Code")]
+    code: String,
+    #[serde(rename = "Message")]
+    message: Option<String>,
+}
+
+// the JSON error body of the table service
+#[derive(Debug, Deserialize)]
+struct JsonErrorBody {
+    #[serde(rename = "odata.error")]
+    error: JsonError,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonError {
+    code: String,
+    message: Option<JsonErrorMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonErrorMessage {
+    value: String,
 }
 
 impl UnexpectedHTTPResult {
@@ -50,21 +85,91 @@ impl UnexpectedHTTPResult {
             expected,
             received,
             body: body.to_owned(),
+            request_id: None,
+            client_request_id: None,
+            error_code: None,
+            date: None,
+        }
+    }
+
+    // picks the request ids and the error code from the response headers
+    pub(crate) fn with_headers(self, headers: &HeaderMap) -> UnexpectedHTTPResult {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok()).map(str::to_owned);
+        UnexpectedHTTPResult {
+            request_id: header(REQUEST_ID),
+            client_request_id: header(CLIENT_REQUEST_ID).map(String::into_boxed_str),
+            error_code: header(ERROR_CODE),
+            date: header("date"),
+            ..self
         }
     }
 
     pub fn status_code(&self) -> StatusCode {
         self.received
     }
+
+    pub fn expected_status_code(&self) -> StatusCode {
+        self.expected
+    }
+
+    /// The response body, as returned by the service.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// The `x-ms-request-id` of the failed request, useful when asking
+    /// the support about it.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// The `x-ms-client-request-id` the service echoed, that is the one
+    /// the request was sent with (generated by the `Client` if not given).
+    pub fn client_request_id(&self) -> Option<&str> {
+        self.client_request_id.as_deref()
+    }
+
+    /// The `Date` of the failed response, that is the service clock.
+    pub fn date(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let date = chrono::DateTime::parse_from_rfc2822(self.date.as_ref()?).ok()?;
+        Some(date.with_timezone(&chrono::Utc))
+    }
+
+    /// The storage error code, taken from the `x-ms-error-code` header or,
+    /// failing that, from the XML or JSON error body.
+    pub fn error_code(&self) -> Option<StorageErrorCode> {
+        match &self.error_code {
+            Some(error_code) => error_code.parse().ok(),
+            None => self.parse_body().and_then(|(code, _)| code.parse().ok()),
+        }
+    }
+
+    /// The error message of the body, if it could be parsed.
+    pub fn error_message(&self) -> Option<String> {
+        self.parse_body().and_then(|(_, message)| message)
+    }
+
+    fn parse_body(&self) -> Option<(String, Option<String>)> {
+        if let Ok(error) = serde_xml_rs::from_str::<XmlErrorBody>(&self.body) {
+            return Some((error.code, error.message));
+        }
+        serde_json::from_str::<JsonErrorBody>(&self.body)
+            .ok()
+            .map(|error| (error.error.code, error.error.message.map(|message| message.value)))
+    }
 }
 
 impl std::fmt::Display for UnexpectedHTTPResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "Unexpected HTTP result (expected: {}, received: {})",
+            "Unexpected HTTP result (expected: {}, received: {}",
             self.expected, self.received
-        )
+        )?;
+        if let Some(error_code) = self.error_code() {
+            write!(f, ", error code: {}", error_code)?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -231,9 +336,9 @@ quick_error! {
             display("FromUTF8 error: {}", err)
             cause(err)
         }
-        TLSError(err: hyper_tls::Error) {
+        TLSError(err: TlsError) {
             from()
-            display("Native TLS error: {}", err)
+            display("TLS error: {}", err)
             cause(err)
         }
         SerdeXMLDeserializationError(err:serde_xml_rs::Error) {
@@ -244,6 +349,42 @@ quick_error! {
         MissingHeaderError(header: String) {
             display("A required header is missing: {}", header)
         }
+        TimeoutError(timeout: Duration) {
+            display("The request did not complete within {:?}", timeout)
+        }
+        CancelledError {
+            display("The request was cancelled")
+        }
+        ClientRequestIdMismatch(sent: String, received: Option<String>) {
+            display("The service echoed the client request id {:?} instead of {}", received, sent)
+        }
+        CircuitBreakerOpen(host: String) {
+            display("Circuit breaker open for host {}", host)
+        }
+        ChecksumMismatch(computed: String, returned: String) {
+            display("Checksum mismatch: computed {}, returned by the service {}", computed, returned)
+        }
+        EncryptionError(msg: String) {
+            display("Client-side encryption error: {}", msg)
+        }
+        InvalidVhd(msg: String) {
+            display("Invalid VHD: {}", msg)
+        }
+        InvalidMetadataName(name: String) {
+            display("Invalid metadata name {:?}: it must be a C# identifier", name)
+        }
+        InvalidMetadataValue(name: String) {
+            display("Invalid value of the metadata {:?}: it must be printable ASCII", name)
+        }
+        MetadataTooLarge(size: usize) {
+            display("The metadata is {} bytes long, more than the 8 KiB allowed", size)
+        }
+        BlobModifiedDuringDownload(etag: String) {
+            display("The blob changed during the download: its ETag is no longer {}", etag)
+        }
+        UnexpectedRangeLength(range: String, expected: u64, received: u64) {
+            display("The service returned {} bytes for the range {} instead of {}", received, range, expected)
+        }
     }
 }
 
@@ -286,6 +427,87 @@ quick_error! {
    }
 }
 
+impl AzureError {
+    /// The HTTP status code returned by the service, if the error
+    /// originated from an unexpected HTTP response.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            AzureError::UnexpectedHTTPResult(result) => Some(result.status_code()),
+            _ => None,
+        }
+    }
+
+    /// True if retrying the same request might succeed: transport
+    /// errors, timeouts, throttling and transient server errors.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            AzureError::HyperError(_) | AzureError::IOError(_) | AzureError::TimeoutError(_) | AzureError::UnexpectedRangeLength(..) => {
+                true
+            }
+            _ => self.status().is_some_and(is_retriable_status),
+        }
+    }
+
+    /// The storage error code of the unexpected HTTP response, if any.
+    /// See `UnexpectedHTTPResult::error_code`.
+    pub fn storage_error_code(&self) -> Option<StorageErrorCode> {
+        match self {
+            AzureError::UnexpectedHTTPResult(result) => result.error_code(),
+            _ => None,
+        }
+    }
+
+    /// The `x-ms-request-id` of the failed request, if known.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            AzureError::UnexpectedHTTPResult(result) => result.request_id(),
+            _ => None,
+        }
+    }
+
+    /// The `x-ms-client-request-id` of the failed request, if known.
+    pub fn client_request_id(&self) -> Option<&str> {
+        match self {
+            AzureError::UnexpectedHTTPResult(result) => result.client_request_id(),
+            AzureError::ClientRequestIdMismatch(sent, _) => Some(sent),
+            _ => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        self.status() == Some(StatusCode::CONFLICT)
+    }
+
+    /// The resource the request tried to create exists already.
+    pub fn is_already_exists(&self) -> bool {
+        self.storage_error_code().is_some_and(|code| code.is_already_exists())
+    }
+
+    /// A conditional header did not match (`412 Precondition Failed`).
+    pub fn is_condition_not_met(&self) -> bool {
+        self.status() == Some(StatusCode::PRECONDITION_FAILED) || self.storage_error_code().is_some_and(|code| code.is_condition_not_met())
+    }
+
+    /// The lease given with the request is not the active one.
+    pub fn is_lease_mismatch(&self) -> bool {
+        self.storage_error_code().is_some_and(|code| code.is_lease_mismatch())
+    }
+}
+
+/// Timeouts, throttling and transient server errors.
+pub(crate) fn is_retriable_status(status: StatusCode) -> bool {
+    status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::INTERNAL_SERVER_ERROR
+        || status == StatusCode::BAD_GATEWAY
+        || status == StatusCode::SERVICE_UNAVAILABLE
+        || status == StatusCode::GATEWAY_TIMEOUT
+}
+
 impl From<()> for AzureError {
     fn from(_: ()) -> AzureError {
         AzureError::GenericError
@@ -293,9 +515,13 @@ impl From<()> for AzureError {
 }
 
 #[inline]
-pub(crate) fn extract_status_headers_and_body(
-    resp: hyper::client::ResponseFuture,
-) -> impl Future<Item = (hyper::StatusCode, hyper::HeaderMap, hyper::Chunk), Error = AzureError> {
+pub(crate) fn extract_status_headers_and_body<F>(
+    resp: F,
+) -> impl Future<Item = (hyper::StatusCode, hyper::HeaderMap, hyper::Chunk), Error = AzureError>
+where
+    F: Future<Item = hyper::Response<hyper::Body>>,
+    AzureError: From<F::Error>,
+{
     resp.from_err().and_then(|res| {
         let (head, body) = res.into_parts();
         let status = head.status;
@@ -305,28 +531,34 @@ pub(crate) fn extract_status_headers_and_body(
 }
 
 #[inline]
-pub(crate) fn check_status_extract_headers_and_body(
-    resp: hyper::client::ResponseFuture,
+pub(crate) fn check_status_extract_headers_and_body<F>(
+    resp: F,
     expected_status_code: hyper::StatusCode,
-) -> impl Future<Item = (hyper::HeaderMap, hyper::Chunk), Error = AzureError> {
+) -> impl Future<Item = (hyper::HeaderMap, hyper::Chunk), Error = AzureError>
+where
+    F: Future<Item = hyper::Response<hyper::Body>>,
+    AzureError: From<F::Error>,
+{
     extract_status_headers_and_body(resp).and_then(move |(status, headers, body)| {
         if status == expected_status_code {
             Ok((headers, body))
         } else {
-            Err(AzureError::UnexpectedHTTPResult(UnexpectedHTTPResult {
-                expected: expected_status_code,
-                received: status,
-                body: str::from_utf8(&body)?.to_owned(),
-            }))
+            Err(AzureError::UnexpectedHTTPResult(
+                UnexpectedHTTPResult::new(expected_status_code, status, str::from_utf8(&body)?).with_headers(&headers),
+            ))
         }
     })
 }
 
 #[inline]
-pub(crate) fn check_status_extract_headers_and_body_as_string(
-    resp: hyper::client::ResponseFuture,
+pub(crate) fn check_status_extract_headers_and_body_as_string<F>(
+    resp: F,
     expected_status_code: hyper::StatusCode,
-) -> impl Future<Item = (hyper::HeaderMap, String), Error = AzureError> {
+) -> impl Future<Item = (hyper::HeaderMap, String), Error = AzureError>
+where
+    F: Future<Item = hyper::Response<hyper::Body>>,
+    AzureError: From<F::Error>,
+{
     check_status_extract_headers_and_body(resp, expected_status_code).and_then(move |(headers, body)| {
         let body = str::from_utf8(&body)?.to_owned();
         Ok((headers, body))
@@ -334,7 +566,11 @@ pub(crate) fn check_status_extract_headers_and_body_as_string(
 }
 
 #[inline]
-pub(crate) fn extract_status_and_body(resp: hyper::client::ResponseFuture) -> impl Future<Item = (StatusCode, String), Error = AzureError> {
+pub(crate) fn extract_status_and_body<F>(resp: F) -> impl Future<Item = (StatusCode, String), Error = AzureError>
+where
+    F: Future<Item = hyper::Response<hyper::Body>>,
+    AzureError: From<F::Error>,
+{
     resp.from_err().and_then(|res| {
         let status = res.status();
         res.into_body()
@@ -345,19 +581,95 @@ pub(crate) fn extract_status_and_body(resp: hyper::client::ResponseFuture) -> im
 }
 
 #[inline]
-pub(crate) fn check_status_extract_body(
-    resp: hyper::client::ResponseFuture,
+pub(crate) fn check_status_extract_body<F>(
+    resp: F,
     expected_status_code: hyper::StatusCode,
-) -> impl Future<Item = String, Error = AzureError> {
-    extract_status_and_body(resp).and_then(move |(status, body)| {
+) -> impl Future<Item = String, Error = AzureError>
+where
+    F: Future<Item = hyper::Response<hyper::Body>>,
+    AzureError: From<F::Error>,
+{
+    extract_status_headers_and_body(resp).and_then(move |(status, headers, body)| {
+        let body = str::from_utf8(&body)?;
         if status == expected_status_code {
-            Ok(body)
+            Ok(body.to_owned())
         } else {
-            Err(AzureError::UnexpectedHTTPResult(UnexpectedHTTPResult {
-                expected: expected_status_code,
-                received: status,
-                body,
-            }))
+            Err(AzureError::UnexpectedHTTPResult(
+                UnexpectedHTTPResult::new(expected_status_code, status, body).with_headers(&headers),
+            ))
         }
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn unexpected(status: StatusCode) -> AzureError {
+        AzureError::UnexpectedHTTPResult(UnexpectedHTTPResult::new(StatusCode::OK, status, ""))
+    }
+
+    #[test]
+    fn test_status_classification() {
+        assert_eq!(unexpected(StatusCode::NOT_FOUND).status(), Some(StatusCode::NOT_FOUND));
+        assert!(unexpected(StatusCode::NOT_FOUND).is_not_found());
+        assert!(!unexpected(StatusCode::NOT_FOUND).is_retriable());
+        assert!(unexpected(StatusCode::CONFLICT).is_conflict());
+        assert!(unexpected(StatusCode::SERVICE_UNAVAILABLE).is_retriable());
+        assert!(unexpected(StatusCode::TOO_MANY_REQUESTS).is_retriable());
+        assert!(!unexpected(StatusCode::NOT_IMPLEMENTED).is_retriable());
+
+        let err = AzureError::GenericError;
+        assert_eq!(err.status(), None);
+        assert!(!err.is_retriable());
+        assert!(!err.is_not_found());
+    }
+
+    #[test]
+    fn test_error_code_from_xml_body() {
+        let body = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?><Error><Code>ContainerAlreadyExists</Code><Message>The specified container already exists.\nRequestId:1\nTime:2019-04-08T10:00:00.0000000Z</Message></Error>";
+        let result = UnexpectedHTTPResult::new(StatusCode::CREATED, StatusCode::CONFLICT, body);
+        assert_eq!(
+            result.to_string(),
+            "Unexpected HTTP result (expected: 201 Created, received: 409 Conflict, error code: ContainerAlreadyExists)"
+        );
+
+        let err = AzureError::UnexpectedHTTPResult(result);
+        assert_eq!(err.storage_error_code(), Some(StorageErrorCode::ContainerAlreadyExists));
+        assert!(err.is_already_exists());
+        assert!(!err.is_lease_mismatch());
+    }
+
+    #[test]
+    fn test_error_code_from_json_body() {
+        let body = r#"{"odata.error":{"code":"UpdateConditionNotSatisfied","message":{"lang":"en-US","value":"The update condition specified in the request was not satisfied."}}}"#;
+        let result = UnexpectedHTTPResult::new(StatusCode::NO_CONTENT, StatusCode::PRECONDITION_FAILED, body);
+
+        assert_eq!(result.error_code(), Some(StorageErrorCode::UpdateConditionNotSatisfied));
+        assert_eq!(
+            result.error_message().as_deref(),
+            Some("The update condition specified in the request was not satisfied.")
+        );
+        assert!(AzureError::UnexpectedHTTPResult(result).is_condition_not_met());
+    }
+
+    #[test]
+    fn test_error_code_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID, "4f6c2d3e-0001-0000-0000-000000000000".parse().unwrap());
+        headers.insert(ERROR_CODE, "LeaseIdMismatchWithBlobOperation".parse().unwrap());
+        headers.insert("date", "Tue, 15 Oct 2019 10:00:00 GMT".parse().unwrap());
+        let err = AzureError::UnexpectedHTTPResult(
+            UnexpectedHTTPResult::new(StatusCode::CREATED, StatusCode::PRECONDITION_FAILED, "").with_headers(&headers),
+        );
+
+        assert_eq!(err.request_id(), Some("4f6c2d3e-0001-0000-0000-000000000000"));
+        assert_eq!(err.storage_error_code(), Some(StorageErrorCode::LeaseIdMismatchWithBlobOperation));
+        assert!(err.is_lease_mismatch());
+        match &err {
+            AzureError::UnexpectedHTTPResult(result) => assert_eq!(result.date().unwrap().to_rfc3339(), "2019-10-15T10:00:00+00:00"),
+            _ => unreachable!(),
+        }
+        assert_eq!(AzureError::GenericError.storage_error_code(), None);
+    }
+}
@@ -1,13 +1,19 @@
 pub const RANGE_GET_CONTENT_MD5: &str = "x-ms-range-get-content-md5"; //=> [bool] }
+pub const MS_RANGE: &str = "x-ms-range";
 pub const LEASE_ID: &str = "x-ms-lease-id"; //=> [LeaseId] }
 pub const CLIENT_REQUEST_ID: &str = "x-ms-client-request-id"; //=> [String] }
 pub const BLOB_PUBLIC_ACCESS: &str = "x-ms-blob-public-access"; // [PublicAccess]
 pub const REQUEST_ID: &str = "x-ms-request-id"; //=> [String] }
+pub const ERROR_CODE: &str = "x-ms-error-code";
+pub const CONTENT_CRC64: &str = "x-ms-content-crc64";
 pub const LEASE_STATUS: &str = "x-ms-lease-status"; //=> [LeaseStatus] }
 pub const LEASE_STATE: &str = "x-ms-lease-state"; //=> [LeaseState] }
 pub const LEASE_DURATION: &str = "x-ms-lease-duration"; //=> [LeaseDuration] }
 pub const HAS_IMMUTABILITY_POLICY: &str = "x-ms-has-immutability-policy";
 pub const HAS_LEGAL_HOLD: &str = "x-ms-has-legal-hold";
+pub const IMMUTABLE_STORAGE_WITH_VERSIONING_ENABLED: &str = "x-ms-immutable-storage-with-versioning-enabled";
+pub const DEFAULT_ENCRYPTION_SCOPE: &str = "x-ms-default-encryption-scope";
+pub const DENY_ENCRYPTION_SCOPE_OVERRIDE: &str = "x-ms-deny-encryption-scope-override";
 pub const META_PREFIX: &str = "x-ms-meta-";
 pub const LEASE_ACTION: &str = "x-ms-lease-action"; //=> [LeaseAction] }
 pub const LEASE_BREAK_PERIOD: &str = "x-ms-lease-break-period"; //=> [u32] }
@@ -33,3 +39,40 @@ pub const PAGE_WRITE: &str = "x-ms-page-write";
 pub const REQUEST_SERVER_ENCRYPTED: &str = "x-ms-request-server-encrypted";
 pub const DELETE_TYPE_PERMANENT: &str = "x-ms-delete-type-permanent";
 pub const DELETE_SNAPSHOTS: &str = "x-ms-delete-snapshots";
+pub const BLOB_CONTENT_TYPE: &str = "x-ms-blob-content-type";
+pub const BLOB_CONTENT_ENCODING: &str = "x-ms-blob-content-encoding";
+pub const BLOB_CONTENT_LANGUAGE: &str = "x-ms-blob-content-language";
+pub const BLOB_CONTENT_MD5: &str = "x-ms-blob-content-md5";
+pub const BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
+pub const BLOB_CONTENT_DISPOSITION: &str = "x-ms-blob-content-disposition";
+pub const SNAPSHOT: &str = "x-ms-snapshot";
+pub const APPROXIMATE_MESSAGES_COUNT: &str = "x-ms-approximate-messages-count";
+pub const POP_RECEIPT: &str = "x-ms-popreceipt";
+pub const TIME_NEXT_VISIBLE: &str = "x-ms-time-next-visible";
+pub const SHARE_QUOTA: &str = "x-ms-share-quota";
+pub const FILE_TYPE: &str = "x-ms-type";
+pub const FILE_WRITE: &str = "x-ms-write";
+pub const FILE_CONTENT_LENGTH: &str = "x-ms-content-length";
+pub const FILE_CONTENT_TYPE: &str = "x-ms-content-type";
+pub const ENCRYPTION_KEY: &str = "x-ms-encryption-key";
+pub const ENCRYPTION_KEY_SHA256: &str = "x-ms-encryption-key-sha256";
+pub const ENCRYPTION_ALGORITHM: &str = "x-ms-encryption-algorithm";
+pub const SKU_NAME: &str = "x-ms-sku-name";
+pub const ACCOUNT_KIND: &str = "x-ms-account-kind";
+pub const SOURCE_RANGE: &str = "x-ms-source-range";
+pub const SOURCE_CONTENT_MD5: &str = "x-ms-source-content-md5";
+pub const COPY_SOURCE_AUTHORIZATION: &str = "x-ms-copy-source-authorization";
+pub const REQUIRES_SYNC: &str = "x-ms-requires-sync";
+pub const COPY_SOURCE_TAG_OPTION: &str = "x-ms-copy-source-tag-option";
+pub const VERSION_ID: &str = "x-ms-version-id";
+pub const IS_CURRENT_VERSION: &str = "x-ms-is-current-version";
+pub const API_VERSION: &str = "x-ms-version";
+pub const DELETED_CONTAINER_NAME: &str = "x-ms-deleted-container-name";
+pub const DELETED_CONTAINER_VERSION: &str = "x-ms-deleted-container-version";
+pub const TAG_COUNT: &str = "x-ms-tag-count";
+pub const TAGS: &str = "x-ms-tags";
+pub const OR_PREFIX: &str = "x-ms-or-";
+pub const OR_POLICY_ID: &str = "x-ms-or-policy-id";
+pub const IMMUTABILITY_POLICY_UNTIL_DATE: &str = "x-ms-immutability-policy-until-date";
+pub const IMMUTABILITY_POLICY_MODE: &str = "x-ms-immutability-policy-mode";
+pub const LEGAL_HOLD: &str = "x-ms-legal-hold";
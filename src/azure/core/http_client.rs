@@ -0,0 +1,261 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::pipeline::{PipelineFuture, PipelineRequest};
+use crate::azure::core::proxy::{ProxyConfig, ProxyConnector};
+use crate::azure::core::tls::TlsConfig;
+use futures::Future;
+use hyper::client::connect::{Connect, HttpConnector};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Sends the requests of a `Client` over the network, at the end of its
+/// pipeline: the request is already signed and only has to be sent as
+/// is. The default is a hyper client with a TLS connector; implement this
+/// trait to use another HTTP stack, a proxied client or a mock transport
+/// in tests.
+pub trait HttpClient: Debug + Send + Sync {
+    fn execute(&self, request: PipelineRequest) -> PipelineFuture;
+}
+
+impl<C> HttpClient for hyper::Client<C>
+where
+    C: Connect + Sync + 'static,
+    C::Transport: 'static,
+    C::Future: 'static,
+{
+    fn execute(&self, request: PipelineRequest) -> PipelineFuture {
+        match request.into_hyper_request() {
+            Ok(request) => Box::new(self.request(request).from_err()),
+            Err(error) => Box::new(futures::future::err(error)),
+        }
+    }
+}
+
+/// The connection pool and socket settings of the default HTTP client,
+/// see `Client::with_pool_config`. The defaults are hyper's: unlimited
+/// idle connections per host, kept for 90 seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolConfig {
+    max_idle_per_host: usize,
+    idle_timeout: Option<Duration>,
+    keep_alive: bool,
+    tcp_keepalive: Option<Duration>,
+    tcp_nodelay: bool,
+    connect_timeout: Option<Duration>,
+    dns_threads: usize,
+}
+
+impl Default for PoolConfig {
+    fn default() -> PoolConfig {
+        PoolConfig {
+            max_idle_per_host: usize::MAX,
+            idle_timeout: Some(Duration::from_secs(90)),
+            keep_alive: true,
+            tcp_keepalive: None,
+            tcp_nodelay: false,
+            connect_timeout: None,
+            dns_threads: 4,
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn new() -> PoolConfig {
+        PoolConfig::default()
+    }
+
+    /// The idle connections kept open per host, ready for the following
+    /// requests. Raise it along with the parallelism of the uploads.
+    pub fn with_max_idle_per_host(self, max_idle_per_host: usize) -> PoolConfig {
+        PoolConfig { max_idle_per_host, ..self }
+    }
+
+    /// How long an idle connection is kept; `None` keeps it until the
+    /// service closes it.
+    pub fn with_idle_timeout(self, idle_timeout: Option<Duration>) -> PoolConfig {
+        PoolConfig { idle_timeout, ..self }
+    }
+
+    /// Opens a new connection for every request when `false`.
+    pub fn with_keep_alive(self, keep_alive: bool) -> PoolConfig {
+        PoolConfig { keep_alive, ..self }
+    }
+
+    /// Enables the TCP keepalive probes, sent after `tcp_keepalive` of
+    /// inactivity.
+    pub fn with_tcp_keepalive(self, tcp_keepalive: Option<Duration>) -> PoolConfig {
+        PoolConfig { tcp_keepalive, ..self }
+    }
+
+    /// Disables the Nagle algorithm (`TCP_NODELAY`).
+    pub fn with_tcp_nodelay(self, tcp_nodelay: bool) -> PoolConfig {
+        PoolConfig { tcp_nodelay, ..self }
+    }
+
+    /// Fails the connections not established within `connect_timeout`.
+    pub fn with_connect_timeout(self, connect_timeout: Option<Duration>) -> PoolConfig {
+        PoolConfig { connect_timeout, ..self }
+    }
+
+    /// The threads resolving the host names. Defaults to 4.
+    pub fn with_dns_threads(self, dns_threads: usize) -> PoolConfig {
+        PoolConfig { dns_threads, ..self }
+    }
+
+    pub fn max_idle_per_host(&self) -> usize {
+        self.max_idle_per_host
+    }
+
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    pub fn keep_alive(&self) -> bool {
+        self.keep_alive
+    }
+
+    pub fn tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive
+    }
+
+    pub fn tcp_nodelay(&self) -> bool {
+        self.tcp_nodelay
+    }
+
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    pub fn dns_threads(&self) -> usize {
+        self.dns_threads
+    }
+
+    fn http_connector(&self) -> HttpConnector {
+        let mut http = HttpConnector::new(self.dns_threads);
+        http.enforce_http(false);
+        http.set_keepalive(self.tcp_keepalive);
+        http.set_nodelay(self.tcp_nodelay);
+        http.set_connect_timeout(self.connect_timeout);
+        http
+    }
+
+    fn client_builder(&self) -> hyper::client::Builder {
+        let mut builder = hyper::Client::builder();
+        builder
+            .max_idle_per_host(self.max_idle_per_host)
+            .keep_alive_timeout(self.idle_timeout)
+            .keep_alive(self.keep_alive);
+        builder
+    }
+}
+
+/// The hyper client used when none is supplied, going through the proxy
+/// of the environment if any (see `ProxyConfig::from_env`).
+pub(crate) fn default_http_client() -> Result<Arc<dyn HttpClient>, AzureError> {
+    hyper_http_client(ProxyConfig::from_env(), &TlsConfig::default(), &PoolConfig::default())
+}
+
+/// A hyper client with the given TLS and pool settings, going through
+/// `proxy` if any.
+pub(crate) fn hyper_http_client(proxy: Option<ProxyConfig>, tls: &TlsConfig, pool: &PoolConfig) -> Result<Arc<dyn HttpClient>, AzureError> {
+    let http = pool.http_connector();
+    let builder = pool.client_builder();
+    Ok(match proxy {
+        Some(proxy) => Arc::new(builder.build(tls.https_connector(ProxyConnector::new(proxy, http))?)),
+        None => Arc::new(builder.build(tls.https_connector(http)?)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::storage::client::Client;
+    use hyper::header::AUTHORIZATION;
+    use hyper::{Body, Method, Response};
+    use std::sync::Mutex;
+
+    // answers every request with 200 and keeps what it received
+    #[derive(Debug, Default)]
+    struct MockHttpClient {
+        requests: Mutex<Vec<PipelineRequest>>,
+    }
+
+    impl HttpClient for MockHttpClient {
+        fn execute(&self, request: PipelineRequest) -> PipelineFuture {
+            self.requests.lock().unwrap().push(request);
+            Box::new(futures::future::ok(Response::new(Body::empty())))
+        }
+    }
+
+    #[test]
+    fn test_custom_http_client() {
+        let mock = Arc::new(MockHttpClient::default());
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(mock.clone());
+
+        let response = client
+            .perform_request(
+                "https://account.blob.core.windows.net/container?restype=container",
+                &Method::GET,
+                |_| {},
+                None,
+            )
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let requests = mock.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::GET);
+        assert!(requests[0].headers[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .starts_with("SharedKey account:"));
+    }
+
+    #[test]
+    fn test_pool_config() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+        use std::thread;
+
+        let pool_config = PoolConfig::new()
+            .with_max_idle_per_host(32)
+            .with_idle_timeout(None)
+            .with_tcp_nodelay(true);
+        assert_eq!(pool_config.max_idle_per_host(), 32);
+        assert_eq!(pool_config.idle_timeout(), None);
+        assert!(pool_config.keep_alive());
+        assert!(pool_config.tcp_nodelay());
+
+        // without keep-alive every request opens its own connection
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut received = Vec::new();
+                let mut buffer = [0; 1024];
+                while !received.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).unwrap();
+                    received.extend_from_slice(&buffer[..read]);
+                }
+                stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").unwrap();
+            }
+        });
+
+        let blob_url = url::Url::parse(&format!("http://127.0.0.1:{}", port)).unwrap();
+        let client = Client::emulator(&blob_url, &blob_url)
+            .unwrap()
+            .with_pool_config(pool_config.with_keep_alive(false))
+            .unwrap();
+        assert!(!client.pool_config().keep_alive());
+        let mut core = tokio_core::reactor::Core::new().unwrap();
+        for _ in 0..2 {
+            let uri = format!("{}devstoreaccount1/container?restype=container", blob_url);
+            let response = client.perform_request(&uri, &Method::GET, |_| {}, None).unwrap();
+            assert!(core.run(response).unwrap().status().is_success());
+        }
+        server.join().unwrap();
+    }
+}
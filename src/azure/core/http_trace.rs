@@ -0,0 +1,229 @@
+use crate::azure::core::headers::{CLIENT_REQUEST_ID, REQUEST_ID};
+use crate::azure::core::pipeline::{Next, PipelineFuture, PipelineRequest, Policy};
+use futures::Future;
+use hyper::Method;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Follows the HTTP requests sent by a storage `Client` (see
+/// `Client::with_http_observer`), one call per attempt, to feed a tracing
+/// or metrics system. Every method has an empty default implementation.
+///
+/// As for the `TelemetryObserver`, the methods are called from within the
+/// futures: they must return quickly and must not block.
+pub trait HttpObserver: Debug + Send + Sync {
+    /// Called as the attempt is sent.
+    fn on_request(&self, _request: &HttpRequestInfo) {}
+
+    /// Called when the response headers of the attempt arrive, or when
+    /// it fails.
+    fn on_response(&self, _request: &HttpRequestInfo, _response: &HttpResponseInfo) {}
+
+    /// Called before an attempt other than the first is sent, with the
+    /// outcome of the previous one.
+    fn on_retry(&self, _request: &HttpRequestInfo, _previous: &HttpResponseInfo) {}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequestInfo {
+    pub method: Method,
+    /// The url with the SAS signature, if any, replaced by `REDACTED`.
+    pub url: String,
+    /// The `x-ms-client-request-id` sent, if any.
+    pub client_request_id: Option<String>,
+    /// Starting from 1.
+    pub attempt: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponseInfo {
+    /// `None` if no response was received.
+    pub status: Option<u16>,
+    /// The `x-ms-request-id` of the response, to look the request up in
+    /// the storage logs.
+    pub request_id: Option<String>,
+    /// From the moment the attempt is sent to the arrival of the
+    /// response headers.
+    pub latency: Duration,
+    /// The error the attempt failed with, if no response was received.
+    pub error: Option<String>,
+}
+
+/// Replaces the value of the `sig` parameter of `uri` with `REDACTED`.
+pub(crate) fn sanitize_url(uri: &str) -> String {
+    match Url::parse(uri) {
+        Ok(mut url) => {
+            if url.query_pairs().any(|(name, _)| name == "sig") {
+                let pairs: Vec<(String, String)> = url
+                    .query_pairs()
+                    .map(|(name, value)| {
+                        let value = if name == "sig" { "REDACTED".into() } else { value };
+                        (name.into_owned(), value.into_owned())
+                    })
+                    .collect();
+                url.query_pairs_mut().clear().extend_pairs(pairs);
+            }
+            url.into_string()
+        }
+        Err(_) => uri.to_owned(),
+    }
+}
+
+/// Pipeline stage reporting the attempts to the `HttpObserver`. A stage is
+/// built for each operation, so that it can number the attempts.
+#[derive(Debug)]
+pub(crate) struct HttpTraceStage {
+    observer: Arc<dyn HttpObserver>,
+    // the number of attempts so far and the outcome of the last one
+    attempts: Arc<Mutex<(u32, Option<HttpResponseInfo>)>>,
+}
+
+impl HttpTraceStage {
+    pub(crate) fn new(observer: Arc<dyn HttpObserver>) -> HttpTraceStage {
+        HttpTraceStage {
+            observer,
+            attempts: Arc::new(Mutex::new((0, None))),
+        }
+    }
+}
+
+impl Policy for HttpTraceStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        let info = {
+            let mut attempts = self.attempts.lock().unwrap();
+            attempts.0 += 1;
+            let info = HttpRequestInfo {
+                method: request.method.clone(),
+                url: sanitize_url(&request.uri),
+                client_request_id: request
+                    .headers
+                    .get(CLIENT_REQUEST_ID)
+                    .and_then(|client_request_id| client_request_id.to_str().ok())
+                    .map(str::to_owned),
+                attempt: attempts.0,
+            };
+            if let Some(previous) = &attempts.1 {
+                self.observer.on_retry(&info, previous);
+            }
+            info
+        };
+        self.observer.on_request(&info);
+
+        let observer = self.observer.clone();
+        let attempts = self.attempts.clone();
+        let start = Instant::now();
+        Box::new(next.send(request).then(move |result| {
+            let response = match &result {
+                Ok(response) => HttpResponseInfo {
+                    status: Some(response.status().as_u16()),
+                    request_id: response
+                        .headers()
+                        .get(REQUEST_ID)
+                        .and_then(|request_id| request_id.to_str().ok())
+                        .map(str::to_owned),
+                    latency: start.elapsed(),
+                    error: None,
+                },
+                Err(error) => HttpResponseInfo {
+                    status: None,
+                    request_id: None,
+                    latency: start.elapsed(),
+                    error: Some(error.to_string()),
+                },
+            };
+            observer.on_response(&info, &response);
+            attempts.lock().unwrap().1 = Some(response);
+            result
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::retry::FixedRetry;
+    use crate::azure::core::sleeper::VirtualSleeper;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::storage::client::Client;
+    use hyper::StatusCode;
+
+    #[derive(Debug, Default)]
+    struct Events(Mutex<Vec<String>>);
+
+    impl HttpObserver for Events {
+        fn on_request(&self, request: &HttpRequestInfo) {
+            self.0.lock().unwrap().push(format!(
+                "request {} {} {} {:?}",
+                request.attempt, request.method, request.url, request.client_request_id
+            ));
+        }
+
+        fn on_response(&self, request: &HttpRequestInfo, response: &HttpResponseInfo) {
+            self.0.lock().unwrap().push(format!(
+                "response {} {:?} {:?}",
+                request.attempt, response.status, response.request_id
+            ));
+        }
+
+        fn on_retry(&self, request: &HttpRequestInfo, previous: &HttpResponseInfo) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("retry {} after {:?}", request.attempt, previous.status));
+        }
+    }
+
+    #[test]
+    fn test_sanitize_url() {
+        assert_eq!(
+            sanitize_url("https://account.blob.core.windows.net/c/b?sv=2018-11-09&sig=c2VjcmV0%3D&sp=r"),
+            "https://account.blob.core.windows.net/c/b?sv=2018-11-09&sig=REDACTED&sp=r"
+        );
+        assert_eq!(
+            sanitize_url("https://account.blob.core.windows.net/c?restype=container"),
+            "https://account.blob.core.windows.net/c?restype=container"
+        );
+    }
+
+    #[test]
+    fn test_http_observer() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::SERVICE_UNAVAILABLE, &[("x-ms-request-id", "first")], b"")
+                .with_response(StatusCode::OK, &[("x-ms-request-id", "second")], b""),
+        );
+        let events = Arc::new(Events::default());
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport)
+            .with_sleeper(Arc::new(VirtualSleeper::new()))
+            .with_retry_policy(Arc::new(FixedRetry::new(Duration::from_secs(1), 1)))
+            .with_http_observer(events.clone());
+
+        client
+            .perform_request(
+                "https://account.blob.core.windows.net/c/b?sig=c2VjcmV0",
+                &Method::GET,
+                |builder| {
+                    builder.header(CLIENT_REQUEST_ID, "my-id");
+                },
+                None,
+            )
+            .unwrap()
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            *events.0.lock().unwrap(),
+            vec![
+                "request 1 GET https://account.blob.core.windows.net/c/b?sig=REDACTED Some(\"my-id\")",
+                "response 1 Some(503) Some(\"first\")",
+                "retry 2 after Some(503)",
+                "request 2 GET https://account.blob.core.windows.net/c/b?sig=REDACTED Some(\"my-id\")",
+                "response 2 Some(200) Some(\"second\")",
+            ]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::azure::core::enumerations;
-use crate::azure::core::errors::TraversingError;
+use crate::azure::core::errors::{AzureError, TraversingError};
 use crate::azure::core::parsing::FromStringOptional;
 use std::fmt;
 use std::str::FromStr;
@@ -28,3 +28,94 @@ create_enum!(
 );
 
 pub type LeaseId = Uuid;
+
+/// Duration requested when acquiring a lease: either infinite or
+/// between 15 and 60 seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeasePeriod(i8);
+
+impl LeasePeriod {
+    pub fn infinite() -> LeasePeriod {
+        LeasePeriod(-1)
+    }
+
+    pub fn fixed(seconds: u8) -> Result<LeasePeriod, AzureError> {
+        if !(15..=60).contains(&seconds) {
+            return Err(AzureError::InputParametersError(format!(
+                "lease duration must be between 15 and 60 seconds (received {})",
+                seconds
+            )));
+        }
+        Ok(LeasePeriod(seconds as i8))
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.0 == -1
+    }
+
+    pub fn seconds(&self) -> Option<u8> {
+        if self.is_infinite() {
+            None
+        } else {
+            Some(self.0 as u8)
+        }
+    }
+}
+
+impl fmt::Display for LeasePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Time, between 0 and 60 seconds, a broken lease is allowed to
+/// live before it becomes available again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaseBreakPeriod(u8);
+
+impl LeaseBreakPeriod {
+    pub fn immediate() -> LeaseBreakPeriod {
+        LeaseBreakPeriod(0)
+    }
+
+    pub fn new(seconds: u8) -> Result<LeaseBreakPeriod, AzureError> {
+        if seconds > 60 {
+            return Err(AzureError::InputParametersError(format!(
+                "lease break period must be between 0 and 60 seconds (received {})",
+                seconds
+            )));
+        }
+        Ok(LeaseBreakPeriod(seconds))
+    }
+
+    pub fn seconds(&self) -> u8 {
+        self.0
+    }
+}
+
+impl fmt::Display for LeaseBreakPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lease_period() {
+        assert_eq!(LeasePeriod::infinite().to_string(), "-1");
+        assert_eq!(LeasePeriod::fixed(15).unwrap().to_string(), "15");
+        assert_eq!(LeasePeriod::fixed(60).unwrap().seconds(), Some(60));
+        assert!(LeasePeriod::fixed(14).is_err());
+        assert!(LeasePeriod::fixed(61).is_err());
+    }
+
+    #[test]
+    fn test_lease_break_period() {
+        assert_eq!(LeaseBreakPeriod::immediate().to_string(), "0");
+        assert_eq!(LeaseBreakPeriod::new(60).unwrap().seconds(), 60);
+        assert!(LeaseBreakPeriod::new(61).is_err());
+    }
+}
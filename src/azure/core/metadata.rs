@@ -0,0 +1,191 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::META_PREFIX;
+use http::request::Builder;
+use hyper::header::HeaderMap;
+use std::collections::btree_map::{self, BTreeMap};
+use std::ops::Index;
+
+/// The most bytes the names and values of the metadata can take.
+pub const MAX_METADATA_SIZE: usize = 8 * 1024;
+
+/// The user defined metadata of a container, a blob, a share or a queue,
+/// sent and received as `x-ms-meta-*` headers.
+///
+/// The names are C# identifiers (ASCII letters, digits and underscores,
+/// not starting with a digit) and are case insensitive: they are kept
+/// lowercased, as the headers carrying them. The values are sent as is
+/// and so are limited to printable ASCII: encode other text before
+/// inserting it. It serializes as a map of the names to the values.
+///
+/// ```
+/// use azure_sdk_for_rust::core::metadata::Metadata;
+///
+/// let mut metadata = Metadata::new();
+/// metadata.insert("Owner", "ops team").unwrap();
+/// assert_eq!(metadata.get("owner"), Some("ops team"));
+/// assert!(metadata.insert("1st", "x").is_err());
+/// assert!(metadata.insert("city", "Zürich").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Metadata {
+    metadata: BTreeMap<String, String>,
+}
+
+impl Metadata {
+    pub fn new() -> Metadata {
+        Metadata::default()
+    }
+
+    /// Sets the value of `name`, returning its previous value. Fails if
+    /// `name` is not a C# identifier, if `value` is not printable ASCII or
+    /// if the metadata would exceed 8 KiB.
+    pub fn insert<K: AsRef<str>, V: Into<String>>(&mut self, name: K, value: V) -> Result<Option<String>, AzureError> {
+        let name = name.as_ref();
+        if !is_identifier(name) {
+            return Err(AzureError::InvalidMetadataName(name.to_owned()));
+        }
+
+        let name = name.to_lowercase();
+        let value = value.into();
+        if !value.bytes().all(|b| b == b' ' || b.is_ascii_graphic()) {
+            return Err(AzureError::InvalidMetadataValue(name));
+        }
+        let size = self.size() + name.len() + value.len() - self.get(&name).map(|old| name.len() + old.len()).unwrap_or(0);
+        if size > MAX_METADATA_SIZE {
+            return Err(AzureError::MetadataTooLarge(size));
+        }
+        Ok(self.metadata.insert(name, value))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.metadata.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.metadata.remove(&name.to_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.metadata.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty()
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<'_, String, String> {
+        self.metadata.iter()
+    }
+
+    // the bytes counted against MAX_METADATA_SIZE
+    fn size(&self) -> usize {
+        self.metadata.iter().map(|(name, value)| name.len() + value.len()).sum()
+    }
+
+    pub(crate) fn add_headers(&self, builder: &mut Builder) {
+        for (name, value) in &self.metadata {
+            builder.header(&format!("{}{}", META_PREFIX, name) as &str, value as &str);
+        }
+    }
+
+    // a name and a value sent by the service are used as is, even if they
+    // are not valid ones anymore
+    pub(crate) fn insert_received(&mut self, name: &str, value: &str) {
+        self.metadata.insert(name.to_lowercase(), value.to_owned());
+    }
+
+    /// The metadata of the `x-ms-meta-*` headers of a response.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<Metadata, AzureError> {
+        let mut metadata = Metadata::new();
+        for (name, value) in headers {
+            if let Some(name) = name.as_str().strip_prefix(META_PREFIX) {
+                metadata.insert_received(name, value.to_str()?);
+            }
+        }
+        Ok(metadata)
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+impl<'a> Index<&'a str> for Metadata {
+    type Output = String;
+
+    fn index(&self, name: &'a str) -> &String {
+        &self.metadata[&name.to_lowercase()]
+    }
+}
+
+impl<'a> IntoIterator for &'a Metadata {
+    type Item = (&'a String, &'a String);
+    type IntoIter = btree_map::Iter<'a, String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.metadata.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hyper::header::HeaderValue;
+    use hyper::Request;
+
+    #[test]
+    fn test_insert() {
+        let mut metadata = Metadata::new();
+        assert_eq!(metadata.insert("Owner", "ops").unwrap(), None);
+        assert_eq!(metadata.insert("OWNER", "dev").unwrap(), Some("ops".to_owned()));
+        assert_eq!(metadata["Owner"], "dev");
+        assert!(metadata.insert("_private2", "").is_ok());
+        for name in &["", "2nd", "with-dash", "with space", "naïve"] {
+            match metadata.insert(name, "x") {
+                Err(AzureError::InvalidMetadataName(invalid)) => assert_eq!(invalid, *name),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+
+        match metadata.insert("city", "Zürich") {
+            Err(AzureError::InvalidMetadataValue(name)) => assert_eq!(name, "city"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(metadata.insert("line", "a\nb").is_err());
+
+        let large = "x".repeat(MAX_METADATA_SIZE - "owner".len() - "_private2".len());
+        match metadata.insert("large", large.as_str()) {
+            Err(AzureError::MetadataTooLarge(size)) => assert_eq!(size, MAX_METADATA_SIZE + "large".len() + "dev".len()),
+            other => panic!("unexpected {:?}", other),
+        }
+        // replacing a value only counts the new one
+        metadata.insert("owner", &large[..large.len() - "dev".len()]).unwrap();
+        assert_eq!(metadata.len(), 2);
+    }
+
+    #[test]
+    fn test_headers_roundtrip() {
+        let mut metadata = Metadata::new();
+        metadata.insert("City", "Z%C3%BCrich 100%").unwrap();
+        metadata.insert("plain", "a b/c").unwrap();
+
+        // the values are sent and read back unchanged, `%` included
+        let mut builder = Request::builder();
+        metadata.add_headers(&mut builder);
+        let request = builder.body(()).unwrap();
+        assert_eq!(request.headers()["x-ms-meta-city"], "Z%C3%BCrich 100%");
+        assert_eq!(request.headers()["x-ms-meta-plain"], "a b/c");
+
+        let mut headers = request.headers().clone();
+        headers.insert("x-ms-request-id", HeaderValue::from_static("5d3e9ff8-0003-0041-5e1d-a2a0a0000000"));
+        assert_eq!(Metadata::from_headers(&headers).unwrap(), metadata);
+
+        headers.insert("x-ms-meta-written-elsewhere", HeaderValue::from_static("a%41"));
+        assert_eq!(Metadata::from_headers(&headers).unwrap()["written-elsewhere"], "a%41");
+    }
+}
@@ -1,14 +1,29 @@
 extern crate hyper;
-extern crate ring;
 extern crate url;
 
 #[macro_use]
 pub mod errors;
+#[cfg(feature = "async")]
+pub mod async_compat;
+pub mod bandwidth;
 pub mod parsing;
 #[macro_use]
 pub mod enumerations;
+pub mod cancellation;
+pub mod checksum;
+pub mod circuit_breaker;
+pub(crate) mod client_request_id;
+pub mod clock_skew;
+pub mod common_headers;
+pub mod compression;
+pub mod copy_source_authorization;
+pub mod crypto;
+pub mod customer_provided_key;
+pub mod http_client;
+pub mod http_trace;
 pub mod incompletevector;
 pub mod lease;
+pub mod metadata;
 use crate::azure::core::util::HeaderMapExt;
 use crate::azure::storage::client::Client;
 use base64;
@@ -16,17 +31,34 @@ use std::fmt::Debug;
 pub mod ba512_range;
 use base64::encode;
 pub mod modify_conditions;
+pub mod pipeline;
+pub mod progress;
+pub mod proxy;
+use self::checksum::ChecksumMode;
+use self::copy_source_authorization::CopySourceAuthorization;
+use self::customer_provided_key::CustomerProvidedKey;
+use self::metadata::Metadata;
 use self::modify_conditions::{IfMatchCondition, IfSinceCondition, SequenceNumberCondition};
+use self::progress::Progress;
 use std::fmt;
 use std::str::FromStr;
 pub mod range;
-use crate::azure::storage::blob::{BlockList, BlockListType};
+pub mod retry;
+pub mod shutdown;
+pub mod sleeper;
+pub mod telemetry;
+pub mod test_transport;
+pub mod throttling;
+pub mod tls;
+use crate::azure::storage::blob::{BlockList, BlockListType, CopyStatus};
 use std::borrow::Borrow;
 use url::percent_encoding;
 pub mod headers;
 use self::headers::{
-    BLOB_ACCESS_TIER, BLOB_CONTENT_LENGTH, BLOB_SEQUENCE_NUMBER, CLIENT_REQUEST_ID, CONTENT_MD5, DELETE_SNAPSHOTS, DELETE_TYPE_PERMANENT,
-    LEASE_BREAK_PERIOD, LEASE_DURATION, LEASE_ID, LEASE_TIME, PROPOSED_LEASE_ID, REQUEST_ID, REQUEST_SERVER_ENCRYPTED,
+    ACCOUNT_KIND, API_VERSION, APPROXIMATE_MESSAGES_COUNT, BLOB_ACCESS_TIER, BLOB_CONTENT_LENGTH, BLOB_SEQUENCE_NUMBER, CLIENT_REQUEST_ID,
+    CONTENT_MD5, COPY_ID, COPY_SOURCE, COPY_SOURCE_TAG_OPTION, COPY_STATUS, DELETE_SNAPSHOTS, DELETE_TYPE_PERMANENT, LEASE_BREAK_PERIOD,
+    LEASE_DURATION, LEASE_ID, LEASE_TIME, POP_RECEIPT, PROPOSED_LEASE_ID, REQUEST_ID, REQUEST_SERVER_ENCRYPTED, SHARE_QUOTA, SKU_NAME,
+    SNAPSHOT, SOURCE_CONTENT_MD5, SOURCE_RANGE, TIME_NEXT_VISIBLE, VERSION_ID,
 };
 use hyper::header::{
     HeaderName, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_TYPE, DATE, ETAG, LAST_MODIFIED, RANGE,
@@ -34,15 +66,14 @@ use hyper::header::{
 use uuid::Uuid;
 pub type RequestId = Uuid;
 use crate::azure::core::errors::{AzureError, TraversingError};
-use crate::azure::core::lease::LeaseId;
+use crate::azure::core::lease::{LeaseBreakPeriod, LeaseId, LeasePeriod};
 use crate::azure::core::parsing::FromStringOptional;
 use http::request::Builder;
 use http::HeaderMap;
-use std::collections::HashMap;
 mod stored_access_policy;
 pub(crate) mod util;
 pub use self::stored_access_policy::{StoredAccessPolicy, StoredAccessPolicyList};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 define_encode_set! {
     pub COMPLETE_ENCODE_SET = [percent_encoding::USERINFO_ENCODE_SET] | {
@@ -61,6 +92,7 @@ macro_rules! response_from_headers {
         #[derive(Debug, Clone, PartialEq)]
         pub struct $cn {
              $(pub $na: $typ),+,
+             pub common_headers: crate::azure::core::common_headers::CommonStorageResponseHeaders,
         }
 
         impl $cn {
@@ -68,9 +100,11 @@ macro_rules! response_from_headers {
                $(
                     let $na = $fh(headers)?;
                 )+
+                let common_headers = crate::azure::core::common_headers::CommonStorageResponseHeaders::from_headers(headers)?;
 
                 Ok($cn {
                     $($na,)+
+                    common_headers,
                 })
             }
 
@@ -95,6 +129,10 @@ impl NotAssigned for No {}
 
 create_enum!(DeleteSnapshotsMethod, (Include, "include"), (Only, "only"));
 
+// whether a copy takes the tags of its source or the ones sent with the
+// request (none if none are sent)
+create_enum!(CopySourceTagsMode, (Replace, "REPLACE"), (Copy, "COPY"));
+
 pub trait ClientRequired<'a> {
     fn client(&self) -> &'a Client;
 }
@@ -131,6 +169,78 @@ pub trait ClientRequestIdOption<'a> {
     }
 }
 
+/// Headers the crate does not model (yet), sent as they are. They are
+/// added before the request is signed, so the `x-ms-` ones are signed
+/// too.
+pub trait ExtraHeadersSupport<'a> {
+    type O;
+    fn with_extra_header(self, name: &'a str, value: &'a str) -> Self::O;
+}
+
+pub trait ExtraHeadersOption<'a> {
+    fn extra_headers(&self) -> &[(&'a str, &'a str)];
+
+    fn add_header(&self, builder: &mut Builder) {
+        for (name, value) in self.extra_headers() {
+            builder.header(*name, *value);
+        }
+    }
+}
+
+/// The `x-ms-version` of the request, to use a feature newer than the
+/// version the `Client` sends (see `Client::with_api_version`).
+pub trait ApiVersionSupport<'a> {
+    type O;
+    fn with_api_version(self, api_version: &'a str) -> Self::O;
+}
+
+pub trait ApiVersionOption<'a> {
+    fn api_version(&self) -> Option<&'a str>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(api_version) = self.api_version() {
+            builder.header(API_VERSION, api_version);
+        }
+    }
+
+    /// The version of a request needing at least `required` for the
+    /// options it uses: the version given, which must not be older, or
+    /// else the newer of `required` and the client default. `None` if
+    /// neither a version nor a minimum is given.
+    fn required_api_version(&self, client: &'a Client, required: Option<&'a str>) -> Result<Option<&'a str>, AzureError> {
+        match (self.api_version(), required) {
+            (Some(api_version), required) => {
+                check_api_version(api_version)?;
+                match required {
+                    Some(required) if api_version < required => Err(AzureError::InputParametersError(format!(
+                        "x-ms-version {} is older than {}, the version the request needs",
+                        api_version, required
+                    ))),
+                    _ => Ok(Some(api_version)),
+                }
+            }
+            (None, Some(required)) => Ok(Some(client.api_version().max(required))),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+// the versions are dates, so that the newer one is the greater string
+pub(crate) fn check_api_version(api_version: &str) -> Result<(), AzureError> {
+    if api_version.len() != 10 || NaiveDate::parse_from_str(api_version, "%Y-%m-%d").is_err() {
+        return Err(AzureError::InputParametersError(format!(
+            "{} is not a storage service version",
+            api_version
+        )));
+    }
+    Ok(())
+}
+
+// for the helpers sending their requests from a 'static future
+pub(crate) fn owned_headers(headers: &[(&str, &str)]) -> Vec<(String, String)> {
+    headers.iter().map(|(name, value)| ((*name).to_owned(), (*value).to_owned())).collect()
+}
+
 pub trait ContentDispositionSupport<'a> {
     type O;
     fn with_content_disposition(self, content_disposition: &'a str) -> Self::O;
@@ -148,17 +258,15 @@ pub trait ContentDispositionOption<'a> {
 
 pub trait MetadataSupport<'a> {
     type O;
-    fn with_metadata(self, metadata: &'a HashMap<&'a str, &'a str>) -> Self::O;
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O;
 }
 
 pub trait MetadataOption<'a> {
-    fn metadata(&self) -> Option<&'a HashMap<&'a str, &'a str>>;
+    fn metadata(&self) -> Option<&'a Metadata>;
 
     fn add_header(&self, builder: &mut Builder) {
         if let Some(metadata) = self.metadata() {
-            for (key, val) in metadata.iter() {
-                builder.header(&format!("x-ms-meta-{}", key) as &str, val as &str);
-            }
+            metadata.add_headers(builder);
         }
     }
 }
@@ -238,6 +346,14 @@ pub trait AccessTierOption<'a> {
     }
 }
 
+pub trait AccessTierRequired<'a> {
+    fn access_tier(&self) -> &'a str;
+
+    fn add_header(&self, builder: &mut Builder) {
+        builder.header(BLOB_ACCESS_TIER, self.access_tier());
+    }
+}
+
 pub trait DeleteSnapshotsMethodSupport {
     type O;
     fn with_delete_snapshots_method(self, delete_snapshots_method: DeleteSnapshotsMethod) -> Self::O;
@@ -252,6 +368,30 @@ pub trait DeleteSnapshotsMethodRequired {
     }
 }
 
+pub trait CopySourceTagsModeSupport {
+    type O;
+    fn with_copy_source_tags_mode(self, copy_source_tags_mode: CopySourceTagsMode) -> Self::O;
+}
+
+// the tags of a copy from URL are always replaced before this version
+pub(crate) const COPY_SOURCE_TAGS_MODE_VERSION: &str = "2021-04-10";
+
+pub trait CopySourceTagsModeOption {
+    fn copy_source_tags_mode(&self) -> Option<CopySourceTagsMode>;
+
+    /// The minimum `x-ms-version` of the request, if a mode is given.
+    fn required_api_version(&self) -> Option<&'static str> {
+        self.copy_source_tags_mode().map(|_| COPY_SOURCE_TAGS_MODE_VERSION)
+    }
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(copy_source_tags_mode) = self.copy_source_tags_mode() {
+            let s: &'static str = copy_source_tags_mode.into();
+            builder.header(COPY_SOURCE_TAG_OPTION, s);
+        }
+    }
+}
+
 pub trait BlockListTypeSupport {
     type O;
     fn with_block_list_type(self, block_list_type: BlockListType) -> Self::O;
@@ -304,11 +444,7 @@ pub trait SnapshotOption {
     fn snapshot(&self) -> Option<DateTime<Utc>>;
 
     fn to_uri_parameter(&self) -> Option<String> {
-        if let Some(ref nm) = self.snapshot() {
-            Some(format!("snapshot={}", nm.to_rfc2822()))
-        } else {
-            None
-        }
+        self.snapshot().map(|snapshot| format!("snapshot={}", snapshot_to_string(&snapshot)))
     }
 }
 
@@ -316,7 +452,46 @@ pub trait SnapshotRequired {
     fn snapshot(&self) -> DateTime<Utc>;
 
     fn to_uri_parameter(&self) -> String {
-        format!("snapshot={}", self.snapshot().to_rfc2822())
+        format!("snapshot={}", snapshot_to_string(&self.snapshot()))
+    }
+}
+
+pub trait VersionIdSupport<'a> {
+    type O;
+    fn with_version_id(self, version_id: &'a str) -> Self::O;
+}
+
+pub trait VersionIdOption<'a> {
+    fn version_id(&self) -> Option<&'a str>;
+
+    fn to_uri_parameter(&self) -> Option<String> {
+        self.version_id().map(|version_id| format!("versionid={}", version_id))
+    }
+}
+
+pub trait PreviousSnapshotSupport {
+    type O;
+    fn with_previous_snapshot(self, previous_snapshot: DateTime<Utc>) -> Self::O;
+}
+
+pub trait PreviousSnapshotRequired {
+    fn previous_snapshot(&self) -> DateTime<Utc>;
+
+    fn to_uri_parameter(&self) -> String {
+        format!("prevsnapshot={}", snapshot_to_string(&self.previous_snapshot()))
+    }
+}
+
+pub trait CopySourceSupport<'a> {
+    type O;
+    fn with_copy_source(self, copy_source: &'a str) -> Self::O;
+}
+
+pub trait CopySourceRequired<'a> {
+    fn copy_source(&self) -> &'a str;
+
+    fn add_header(&self, builder: &mut Builder) {
+        builder.header(COPY_SOURCE, self.copy_source());
     }
 }
 
@@ -329,11 +504,7 @@ pub trait DelimiterOption<'a> {
     fn delimiter(&self) -> Option<&'a str>;
 
     fn to_uri_parameter(&self) -> Option<String> {
-        if self.delimiter().is_some() {
-            Some("delimiter".to_owned())
-        } else {
-            None
-        }
+        self.delimiter().map(|nm| format!("delimiter={}", nm))
     }
 }
 
@@ -399,8 +570,32 @@ pub trait IncludeDeletedOption {
     fn include_deleted(&self) -> bool;
 }
 
+pub trait IncludeVersionsSupport {
+    type O;
+    fn with_include_versions(self) -> Self::O;
+}
+
+pub trait IncludeVersionsOption {
+    fn include_versions(&self) -> bool;
+}
+
+pub trait IncludeTagsSupport {
+    type O;
+    fn with_include_tags(self) -> Self::O;
+}
+
+pub trait IncludeTagsOption {
+    fn include_tags(&self) -> bool;
+}
+
 pub trait IncludeListOptions:
-    IncludeSnapshotsOption + IncludeMetadataOption + IncludeUncommittedBlobsOption + IncludeCopyOption + IncludeDeletedOption
+    IncludeSnapshotsOption
+    + IncludeMetadataOption
+    + IncludeUncommittedBlobsOption
+    + IncludeCopyOption
+    + IncludeDeletedOption
+    + IncludeVersionsOption
+    + IncludeTagsOption
 {
     fn to_uri_parameter(&self) -> Option<String> {
         let mut s = String::new();
@@ -440,9 +635,25 @@ pub trait IncludeListOptions:
                 s.push_str(",");
             }
             s.push_str("deleted");
+            f_first = false;
+        }
+
+        if self.include_versions() {
+            if !f_first {
+                s.push(',');
+            }
+            s.push_str("versions");
+            f_first = false;
+        }
+
+        if self.include_tags() {
+            if !f_first {
+                s.push(',');
+            }
+            s.push_str("tags");
         }
 
-        if s.is_empty() {
+        if !s.is_empty() {
             Some(format!("include={}", s))
         } else {
             None
@@ -633,6 +844,47 @@ pub(crate) fn add_content_md5_header<'a>(content_md5: &'a [u8], builder: &mut Bu
     builder.header(CONTENT_MD5, &s as &str);
 }
 
+pub trait ChecksumModeSupport {
+    type O;
+    fn with_checksum_mode(self, checksum_mode: ChecksumMode) -> Self::O;
+}
+
+pub trait ChecksumModeOption {
+    fn checksum_mode(&self) -> Option<ChecksumMode>;
+}
+
+pub trait CPKSupport<'a> {
+    type O;
+    fn with_customer_provided_key(self, customer_provided_key: &'a CustomerProvidedKey) -> Self::O;
+}
+
+// the customer-provided keys are rejected before this version
+pub(crate) const CPK_VERSION: &str = "2018-06-17";
+
+pub trait CPKOption<'a> {
+    fn customer_provided_key(&self) -> Option<&'a CustomerProvidedKey>;
+
+    /// The minimum `x-ms-version` of the request, if a key is given.
+    fn required_api_version(&self) -> Option<&'static str> {
+        self.customer_provided_key().map(|_| CPK_VERSION)
+    }
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(customer_provided_key) = self.customer_provided_key() {
+            customer_provided_key.add_headers(builder);
+        }
+    }
+}
+
+pub trait ProgressSupport<'a> {
+    type O;
+    fn with_progress(self, progress: &'a Progress) -> Self::O;
+}
+
+pub trait ProgressOption<'a> {
+    fn progress(&self) -> Option<&'a Progress>;
+}
+
 pub trait RangeSupport<'a> {
     type O;
     fn with_range(self, _: &'a range::Range) -> Self::O;
@@ -656,6 +908,60 @@ pub trait RangeRequired<'a> {
     }
 }
 
+pub trait SourceRangeSupport<'a> {
+    type O;
+    fn with_source_range(self, source_range: &'a range::Range) -> Self::O;
+}
+
+pub trait SourceRangeOption<'a> {
+    fn source_range(&self) -> Option<&'a range::Range>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(source_range) = self.source_range() {
+            builder.header(SOURCE_RANGE, &source_range.to_string() as &str);
+        }
+    }
+}
+
+pub trait CopySourceAuthorizationSupport<'a> {
+    type O;
+    fn with_copy_source_authorization(self, copy_source_authorization: &'a CopySourceAuthorization) -> Self::O;
+}
+
+// the copy source authorization is ignored before this version
+pub(crate) const COPY_SOURCE_AUTHORIZATION_VERSION: &str = "2020-10-02";
+
+pub trait CopySourceAuthorizationOption<'a> {
+    fn copy_source_authorization(&self) -> Option<&'a CopySourceAuthorization>;
+
+    /// The minimum `x-ms-version` of the request, if an authorization is
+    /// given.
+    fn required_api_version(&self) -> Option<&'static str> {
+        self.copy_source_authorization().map(|_| COPY_SOURCE_AUTHORIZATION_VERSION)
+    }
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(copy_source_authorization) = self.copy_source_authorization() {
+            copy_source_authorization.add_header(builder);
+        }
+    }
+}
+
+pub trait SourceContentMD5Support<'a> {
+    type O;
+    fn with_source_content_md5(self, source_content_md5: &'a [u8]) -> Self::O;
+}
+
+pub trait SourceContentMD5Option<'a> {
+    fn source_content_md5(&self) -> Option<&'a [u8]>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(source_content_md5) = self.source_content_md5() {
+            builder.header(SOURCE_CONTENT_MD5, &base64::encode(source_content_md5) as &str);
+        }
+    }
+}
+
 pub trait BA512RangeSupport<'a> {
     type O;
     fn with_ba512_range(self, _: &'a ba512_range::BA512Range) -> Self::O;
@@ -681,11 +987,11 @@ pub trait BA512RangeRequired<'a> {
 
 pub trait LeaseDurationSupport {
     type O;
-    fn with_lease_duration(self, _: i8) -> Self::O;
+    fn with_lease_duration(self, _: LeasePeriod) -> Self::O;
 }
 
 pub trait LeaseDurationRequired {
-    fn lease_duration(&self) -> i8;
+    fn lease_duration(&self) -> LeasePeriod;
 
     fn add_header(&self, builder: &mut Builder) {
         builder.header(LEASE_DURATION, &self.lease_duration().to_string() as &str);
@@ -717,11 +1023,11 @@ pub trait ProposedLeaseIdRequired<'a> {
 
 pub trait LeaseBreakPeriodSupport {
     type O;
-    fn with_lease_break_period(self, lease_break_period: u8) -> Self::O;
+    fn with_lease_break_period(self, lease_break_period: LeaseBreakPeriod) -> Self::O;
 }
 
 pub trait LeaseBreakPeriodRequired {
-    fn lease_break_period(&self) -> u8;
+    fn lease_break_period(&self) -> LeaseBreakPeriod;
 
     fn add_header(&self, builder: &mut Builder) {
         builder.header(LEASE_BREAK_PERIOD, &self.lease_break_period().to_string() as &str);
@@ -729,7 +1035,7 @@ pub trait LeaseBreakPeriodRequired {
 }
 
 pub trait LeaseBreakPeriodOption {
-    fn lease_break_period(&self) -> Option<u8>;
+    fn lease_break_period(&self) -> Option<LeaseBreakPeriod>;
 
     fn add_header(&self, builder: &mut Builder) {
         if let Some(lease_break_period) = self.lease_break_period() {
@@ -770,6 +1076,29 @@ pub(crate) fn request_id_from_headers(headers: &HeaderMap) -> Result<RequestId,
     Ok(Uuid::parse_str(request_id)?)
 }
 
+// the service echoes the x-ms-client-request-id of the request
+pub(crate) fn client_request_id_from_headers_optional(headers: &HeaderMap) -> Result<Option<String>, AzureError> {
+    Ok(headers.get_as_str(CLIENT_REQUEST_ID).map(str::to_owned))
+}
+
+pub(crate) fn version_id_from_headers_optional(headers: &HeaderMap) -> Result<Option<String>, AzureError> {
+    Ok(headers.get_as_str(VERSION_ID).map(str::to_owned))
+}
+
+pub(crate) fn sku_name_from_headers(headers: &HeaderMap) -> Result<String, AzureError> {
+    let sku_name = headers
+        .get_as_str(SKU_NAME)
+        .ok_or_else(|| AzureError::HeaderNotFound(SKU_NAME.to_owned()))?;
+    Ok(sku_name.to_owned())
+}
+
+pub(crate) fn account_kind_from_headers(headers: &HeaderMap) -> Result<String, AzureError> {
+    let account_kind = headers
+        .get_as_str(ACCOUNT_KIND)
+        .ok_or_else(|| AzureError::HeaderNotFound(ACCOUNT_KIND.to_owned()))?;
+    Ok(account_kind.to_owned())
+}
+
 pub(crate) fn content_md5_from_headers(headers: &HeaderMap) -> Result<[u8; 16], AzureError> {
     let content_md5 = headers
         .get(CONTENT_MD5)
@@ -788,6 +1117,14 @@ pub(crate) fn content_md5_from_headers(headers: &HeaderMap) -> Result<[u8; 16],
     Ok(content_md5)
 }
 
+pub(crate) fn content_md5_from_headers_optional(headers: &HeaderMap) -> Result<Option<[u8; 16]>, AzureError> {
+    if headers.contains_key(CONTENT_MD5) {
+        Ok(Some(content_md5_from_headers(headers)?))
+    } else {
+        Ok(None)
+    }
+}
+
 pub(crate) fn last_modified_from_headers_optional(headers: &HeaderMap) -> Result<Option<DateTime<Utc>>, AzureError> {
     if headers.contains_key(LAST_MODIFIED) {
         Ok(Some(last_modified_from_headers(headers)?))
@@ -811,6 +1148,28 @@ pub(crate) fn last_modified_from_headers(headers: &HeaderMap) -> Result<DateTime
     Ok(last_modified)
 }
 
+// Snapshots are identified by their timestamp, with the 100ns precision
+// Azure uses when it returns it in the x-ms-snapshot header.
+pub(crate) fn snapshot_to_string(snapshot: &DateTime<Utc>) -> String {
+    format!(
+        "{}.{:07}Z",
+        snapshot.format("%Y-%m-%dT%H:%M:%S"),
+        snapshot.timestamp_subsec_nanos() / 100
+    )
+}
+
+pub(crate) fn snapshot_from_headers(headers: &HeaderMap) -> Result<DateTime<Utc>, AzureError> {
+    let snapshot = headers
+        .get(SNAPSHOT)
+        .ok_or_else(|| AzureError::HeaderNotFound(SNAPSHOT.to_owned()))?
+        .to_str()?;
+    let snapshot = DateTime::parse_from_rfc3339(snapshot)?;
+    let snapshot = DateTime::from_utc(snapshot.naive_utc(), Utc);
+
+    trace!("snapshot == {:?}", snapshot);
+    Ok(snapshot)
+}
+
 pub(crate) fn date_from_headers(headers: &HeaderMap) -> Result<DateTime<Utc>, AzureError> {
     let date = headers
         .get(DATE)
@@ -848,6 +1207,39 @@ pub(crate) fn etag_from_headers(headers: &HeaderMap) -> Result<String, AzureErro
     Ok(etag)
 }
 
+pub(crate) fn copy_id_from_headers(headers: &HeaderMap) -> Result<String, AzureError> {
+    let copy_id = headers
+        .get(COPY_ID)
+        .ok_or_else(|| AzureError::HeaderNotFound(COPY_ID.to_owned()))?
+        .to_str()?
+        .to_owned();
+
+    trace!("copy_id == {:?}", copy_id);
+    Ok(copy_id)
+}
+
+pub(crate) fn copy_status_from_headers(headers: &HeaderMap) -> Result<CopyStatus, AzureError> {
+    let copy_status = headers
+        .get(COPY_STATUS)
+        .ok_or_else(|| AzureError::HeaderNotFound(COPY_STATUS.to_owned()))?
+        .to_str()?
+        .parse::<CopyStatus>()?;
+
+    trace!("copy_status == {:?}", copy_status);
+    Ok(copy_status)
+}
+
+pub(crate) fn blob_content_length_from_headers(headers: &HeaderMap) -> Result<u64, AzureError> {
+    let blob_content_length = headers
+        .get(BLOB_CONTENT_LENGTH)
+        .ok_or_else(|| AzureError::HeaderNotFound(BLOB_CONTENT_LENGTH.to_owned()))?
+        .to_str()?
+        .parse::<u64>()?;
+
+    trace!("blob_content_length == {:?}", blob_content_length);
+    Ok(blob_content_length)
+}
+
 pub(crate) fn lease_time_from_headers(headers: &HeaderMap) -> Result<u8, AzureError> {
     let lease_time = headers
         .get(LEASE_TIME)
@@ -895,3 +1287,214 @@ pub(crate) fn request_server_encrypted_from_headers(headers: &HeaderMap) -> Resu
     trace!("request_server_encrypted == {:?}", request_server_encrypted);
     Ok(request_server_encrypted)
 }
+
+pub(crate) fn pop_receipt_from_headers(headers: &HeaderMap) -> Result<String, AzureError> {
+    let pop_receipt = headers
+        .get_as_str(POP_RECEIPT)
+        .ok_or_else(|| AzureError::HeaderNotFound(POP_RECEIPT.to_owned()))?
+        .to_owned();
+
+    trace!("pop_receipt == {:?}", pop_receipt);
+    Ok(pop_receipt)
+}
+
+pub(crate) fn time_next_visible_from_headers(headers: &HeaderMap) -> Result<DateTime<Utc>, AzureError> {
+    let time_next_visible = headers
+        .get_as_str(TIME_NEXT_VISIBLE)
+        .ok_or_else(|| AzureError::HeaderNotFound(TIME_NEXT_VISIBLE.to_owned()))?;
+    let time_next_visible = DateTime::parse_from_rfc2822(time_next_visible)?;
+    let time_next_visible = DateTime::from_utc(time_next_visible.naive_utc(), Utc);
+
+    trace!("time_next_visible == {:?}", time_next_visible);
+    Ok(time_next_visible)
+}
+
+pub(crate) fn approximate_messages_count_from_headers(headers: &HeaderMap) -> Result<u64, AzureError> {
+    let approximate_messages_count = headers
+        .get_as_str(APPROXIMATE_MESSAGES_COUNT)
+        .ok_or_else(|| AzureError::HeaderNotFound(APPROXIMATE_MESSAGES_COUNT.to_owned()))?;
+
+    let approximate_messages_count = approximate_messages_count.parse::<u64>()?;
+
+    trace!("approximate_messages_count == {:?}", approximate_messages_count);
+    Ok(approximate_messages_count)
+}
+
+pub(crate) fn share_quota_from_headers(headers: &HeaderMap) -> Result<u64, AzureError> {
+    let share_quota = headers
+        .get_as_str(SHARE_QUOTA)
+        .ok_or_else(|| AzureError::HeaderNotFound(SHARE_QUOTA.to_owned()))?;
+
+    let share_quota = share_quota.parse::<u64>()?;
+
+    trace!("share_quota == {:?}", share_quota);
+    Ok(share_quota)
+}
+
+pub(crate) fn metadata_from_headers(headers: &HeaderMap) -> Result<Metadata, AzureError> {
+    Metadata::from_headers(headers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hyper::header::HeaderValue;
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut headers = HeaderMap::new();
+        headers.insert(SNAPSHOT, HeaderValue::from_static("2011-03-09T01:42:34.9360000Z"));
+
+        let snapshot = snapshot_from_headers(&headers).unwrap();
+        assert_eq!(snapshot.timestamp_subsec_millis(), 936);
+        assert_eq!(snapshot_to_string(&snapshot), "2011-03-09T01:42:34.9360000Z");
+    }
+
+    #[test]
+    fn test_metadata_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ms-meta-owner", HeaderValue::from_static("ops"));
+        headers.insert(REQUEST_ID, HeaderValue::from_static("5d3e9ff8-0003-0041-5e1d-a2a0a0000000"));
+
+        let metadata = metadata_from_headers(&headers).unwrap();
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata["owner"], "ops");
+    }
+
+    struct Include(&'static [&'static str]);
+
+    impl IncludeSnapshotsOption for Include {
+        fn include_snapshots(&self) -> bool {
+            self.0.contains(&"snapshots")
+        }
+    }
+    impl IncludeMetadataOption for Include {
+        fn include_metadata(&self) -> bool {
+            self.0.contains(&"metadata")
+        }
+    }
+    impl IncludeUncommittedBlobsOption for Include {
+        fn include_uncommitted_blobs(&self) -> bool {
+            self.0.contains(&"uncommittedblobs")
+        }
+    }
+    impl IncludeCopyOption for Include {
+        fn include_copy(&self) -> bool {
+            self.0.contains(&"copy")
+        }
+    }
+    impl IncludeDeletedOption for Include {
+        fn include_deleted(&self) -> bool {
+            self.0.contains(&"deleted")
+        }
+    }
+    impl IncludeVersionsOption for Include {
+        fn include_versions(&self) -> bool {
+            self.0.contains(&"versions")
+        }
+    }
+    impl IncludeTagsOption for Include {
+        fn include_tags(&self) -> bool {
+            self.0.contains(&"tags")
+        }
+    }
+    impl IncludeListOptions for Include {}
+
+    #[test]
+    fn test_include_list_options() {
+        assert_eq!(Include(&[]).to_uri_parameter(), None);
+        assert_eq!(Include(&["versions"]).to_uri_parameter(), Some("include=versions".to_owned()));
+        assert_eq!(
+            Include(&["deleted", "metadata", "versions"]).to_uri_parameter(),
+            Some("include=metadata,deleted,versions".to_owned())
+        );
+        assert_eq!(
+            Include(&["tags", "snapshots"]).to_uri_parameter(),
+            Some("include=snapshots,tags".to_owned())
+        );
+    }
+
+    struct Extra(Vec<(&'static str, &'static str)>);
+
+    impl ExtraHeadersOption<'static> for Extra {
+        fn extra_headers(&self) -> &[(&'static str, &'static str)] {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn test_extra_headers() {
+        let mut builder = http::Request::builder();
+        Extra(vec![("x-ms-new-feature", "on"), ("x-ms-other", "1")]).add_header(&mut builder);
+        let request = builder.body(()).unwrap();
+        assert_eq!(request.headers()["x-ms-new-feature"], "on");
+        assert_eq!(request.headers()["x-ms-other"], "1");
+
+        let mut builder = http::Request::builder();
+        Extra(vec![("not a header name", "on")]).add_header(&mut builder);
+        assert!(builder.body(()).is_err());
+    }
+    struct Tags(Option<CopySourceTagsMode>);
+
+    impl CopySourceTagsModeOption for Tags {
+        fn copy_source_tags_mode(&self) -> Option<CopySourceTagsMode> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_copy_source_tags_mode() {
+        let mut builder = http::Request::builder();
+        Tags(Some(CopySourceTagsMode::Copy)).add_header(&mut builder);
+        Tags(None).add_header(&mut builder);
+        let request = builder.body(()).unwrap();
+        assert_eq!(request.headers().get_all(COPY_SOURCE_TAG_OPTION).iter().count(), 1);
+        assert_eq!(request.headers()[COPY_SOURCE_TAG_OPTION], "COPY");
+    }
+
+    struct Version(Option<&'static str>);
+
+    impl<'a> ApiVersionOption<'a> for Version {
+        fn api_version(&self) -> Option<&'a str> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_required_api_version() {
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap();
+        assert_eq!(Version(None).required_api_version(&client, None).unwrap(), None);
+        assert_eq!(Version(None).required_api_version(&client, Some("2019-12-12")).unwrap(), Some("2019-12-12"));
+        assert_eq!(
+            Version(Some("2020-06-12")).required_api_version(&client, Some("2019-12-12")).unwrap(),
+            Some("2020-06-12")
+        );
+        assert!(Version(Some("2018-03-28")).required_api_version(&client, Some("2019-12-12")).is_err());
+        assert!(Version(Some("latest")).required_api_version(&client, None).is_err());
+
+        let client = client.with_api_version("2020-10-02").unwrap();
+        assert_eq!(Version(None).required_api_version(&client, Some("2019-12-12")).unwrap(), Some("2020-10-02"));
+        assert!(Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_api_version("2020-13-01").is_err());
+    }
+
+    #[test]
+    fn test_option_api_versions() {
+        // the options raise the version only when they are given
+        assert_eq!(CopySourceTagsModeOption::required_api_version(&Tags(None)), None);
+        assert_eq!(
+            CopySourceTagsModeOption::required_api_version(&Tags(Some(CopySourceTagsMode::Replace))),
+            Some(COPY_SOURCE_TAGS_MODE_VERSION)
+        );
+
+        // and the newest minimum wins, an override older than it failing
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap();
+        let required = Some(CPK_VERSION)
+            .max(Some(COPY_SOURCE_AUTHORIZATION_VERSION))
+            .max(CopySourceTagsModeOption::required_api_version(&Tags(Some(CopySourceTagsMode::Copy))));
+        assert_eq!(
+            Version(None).required_api_version(&client, required).unwrap(),
+            Some(COPY_SOURCE_TAGS_MODE_VERSION)
+        );
+        assert!(Version(Some(COPY_SOURCE_AUTHORIZATION_VERSION)).required_api_version(&client, required).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use crate::azure::core::bandwidth::BodyPacer;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::retry::{self, RetryPolicy};
+use crate::azure::core::sleeper::Sleeper;
+use futures::Future;
+use hyper::header::{HeaderMap, HeaderValue, USER_AGENT};
+use hyper::{self, Body, Method, Response};
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub type PipelineFuture = Box<dyn Future<Item = Response<Body>, Error = AzureError> + Send>;
+
+const SDK_USER_AGENT: &str = concat!("azure_sdk_for_rust/", env!("CARGO_PKG_VERSION"));
+
+/// A request travelling through the pipeline. Unlike `hyper::Request` it
+/// can be cloned, so a policy can send it more than once (the retry
+/// stage does).
+#[derive(Debug, Clone)]
+pub struct PipelineRequest {
+    pub method: Method,
+    pub uri: String,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+    /// Paces the body as it is sent, see `Client::with_bandwidth_limit`.
+    pub pacer: Option<BodyPacer>,
+}
+
+impl PipelineRequest {
+    pub(crate) fn into_hyper_request(self) -> Result<hyper::Request<Body>, AzureError> {
+        let mut request = hyper::Request::builder();
+        request.method(self.method).uri(self.uri.as_str());
+        let body = match (self.body, self.pacer) {
+            (Some(body), Some(pacer)) => pacer.request_body(body),
+            (Some(body), None) => Body::from(body),
+            (None, _) => Body::empty(),
+        };
+        let mut request = request.body(body)?;
+        *request.headers_mut() = self.headers;
+        Ok(request)
+    }
+}
+
+/// A step of the request pipeline. A policy receives the request, can
+/// inspect or change it, and passes it on with `next.send`; it receives
+/// the response (or the error) as the returned future completes. A
+/// policy can also answer on its own without calling `next`.
+///
+/// The policies added with `Client::with_policy` run for every attempt,
+/// after the retry stage and before the request is signed, so they are
+/// free to change the headers.
+pub trait Policy: Debug + Send + Sync {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture;
+}
+
+/// The rest of the pipeline, as seen by a policy.
+#[derive(Clone)]
+pub struct Next {
+    policies: Arc<Vec<Arc<dyn Policy>>>,
+    index: usize,
+    transport: Arc<dyn Fn(PipelineRequest) -> PipelineFuture + Send + Sync>,
+}
+
+impl Next {
+    /// Sends the request to the following policy, or over the network if
+    /// this is the last one.
+    pub fn send(&self, request: PipelineRequest) -> PipelineFuture {
+        match self.policies.get(self.index) {
+            Some(policy) => {
+                let next = Next {
+                    policies: self.policies.clone(),
+                    index: self.index + 1,
+                    transport: self.transport.clone(),
+                };
+                policy.send(request, next)
+            }
+            None => (self.transport)(request),
+        }
+    }
+}
+
+impl Debug for Next {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Next").field("policies", &&self.policies[self.index..]).finish()
+    }
+}
+
+pub(crate) fn send<T>(policies: Vec<Arc<dyn Policy>>, transport: T, request: PipelineRequest) -> PipelineFuture
+where
+    T: Fn(PipelineRequest) -> PipelineFuture + Send + Sync + 'static,
+{
+    let next = Next {
+        policies: Arc::new(policies),
+        index: 0,
+        transport: Arc::new(transport),
+    };
+    next.send(request)
+}
+
+/// Adds the `User-Agent` header, unless already present.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct TelemetryHeadersStage;
+
+impl Policy for TelemetryHeadersStage {
+    fn send(&self, mut request: PipelineRequest, next: Next) -> PipelineFuture {
+        if !request.headers.contains_key(USER_AGENT) {
+            request.headers.insert(USER_AGENT, HeaderValue::from_static(SDK_USER_AGENT));
+        }
+        next.send(request)
+    }
+}
+
+/// Sends the request again according to the `RetryPolicy`.
+#[derive(Debug, Clone)]
+pub(crate) struct RetryStage {
+    retry_policy: Arc<dyn RetryPolicy>,
+    sleeper: Arc<dyn Sleeper>,
+}
+
+impl RetryStage {
+    pub(crate) fn new(retry_policy: Arc<dyn RetryPolicy>, sleeper: Arc<dyn Sleeper>) -> RetryStage {
+        RetryStage { retry_policy, sleeper }
+    }
+}
+
+impl Policy for RetryStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        if !self.retry_policy.is_retriable_method(&request.method) {
+            return next.send(request);
+        }
+
+        let first = next.send(request.clone());
+        Box::new(retry::retry(self.retry_policy.clone(), self.sleeper.clone(), first, move || {
+            Ok(next.send(request.clone()))
+        }))
+    }
+}
+
+/// Logs every attempt with its outcome and duration.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct LoggingStage;
+
+impl Policy for LoggingStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        let description = format!("{} {}", request.method, request.uri);
+        debug!("sending {}", description);
+        let start = Instant::now();
+
+        Box::new(next.send(request).then(move |result| {
+            match &result {
+                Ok(response) => debug!("{} returned {} in {:?}", description, response.status(), start.elapsed()),
+                Err(error) => debug!("{} failed in {:?}: {}", description, start.elapsed(), error),
+            }
+            result
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::future;
+    use hyper::StatusCode;
+    use std::sync::Mutex;
+
+    // records the order in which the policies are crossed
+    #[derive(Debug)]
+    struct Tracing {
+        name: &'static str,
+        trace: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Policy for Tracing {
+        fn send(&self, mut request: PipelineRequest, next: Next) -> PipelineFuture {
+            self.trace.lock().unwrap().push(format!("> {}", self.name));
+            request.headers.insert("x-ms-traced-by", HeaderValue::from_static(self.name));
+
+            let name = self.name;
+            let trace = self.trace.clone();
+            Box::new(next.send(request).map(move |response| {
+                trace.lock().unwrap().push(format!("< {}", name));
+                response
+            }))
+        }
+    }
+
+    #[derive(Debug)]
+    struct ShortCircuit;
+
+    impl Policy for ShortCircuit {
+        fn send(&self, _request: PipelineRequest, _next: Next) -> PipelineFuture {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::NOT_MODIFIED;
+            Box::new(future::ok(response))
+        }
+    }
+
+    fn request() -> PipelineRequest {
+        PipelineRequest {
+            method: Method::GET,
+            uri: "https://account.blob.core.windows.net/container".to_owned(),
+            headers: HeaderMap::new(),
+            body: None,
+            pacer: None,
+        }
+    }
+
+    #[test]
+    fn test_policies_order() {
+        let trace = Arc::new(Mutex::new(Vec::new()));
+        let policies: Vec<Arc<dyn Policy>> = vec![
+            Arc::new(Tracing {
+                name: "first",
+                trace: trace.clone(),
+            }),
+            Arc::new(TelemetryHeadersStage),
+            Arc::new(Tracing {
+                name: "second",
+                trace: trace.clone(),
+            }),
+        ];
+
+        let transport_trace = trace.clone();
+        let response = send(
+            policies,
+            move |request| {
+                // the last policy wins
+                assert_eq!(request.headers["x-ms-traced-by"], "second");
+                assert_eq!(request.headers[USER_AGENT], SDK_USER_AGENT);
+                transport_trace.lock().unwrap().push("transport".to_owned());
+                Box::new(future::ok(Response::new(Body::empty())))
+            },
+            request(),
+        )
+        .wait()
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            *trace.lock().unwrap(),
+            vec!["> first", "> second", "transport", "< second", "< first"]
+        );
+    }
+
+    #[test]
+    fn test_policy_short_circuit() {
+        let policies: Vec<Arc<dyn Policy>> = vec![Arc::new(ShortCircuit)];
+
+        let response = send(policies, |_| panic!("the transport must not be called"), request())
+            .wait()
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+/// The bytes of a transfer sent or received so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferProgress {
+    pub transferred: u64,
+    /// The size of the whole transfer, when known in advance.
+    pub total: Option<u64>,
+}
+
+/// Reports the progress of the transfers of the builders it is given to
+/// (see `ProgressSupport`). A report is made once the body of a request
+/// has been sent or received, so the chunked transfers (the blocks, the
+/// pages and the download streams) report after every chunk, while a
+/// single request reports once. The clones share the callback.
+#[derive(Clone)]
+pub struct Progress {
+    report: Arc<dyn Fn(TransferProgress) + Send + Sync>,
+}
+
+impl Progress {
+    /// Calls `callback` on every report. The callback must return
+    /// quickly: it runs on the thread polling the transfer.
+    pub fn new<F>(callback: F) -> Progress
+    where
+        F: Fn(TransferProgress) + Send + Sync + 'static,
+    {
+        Progress { report: Arc::new(callback) }
+    }
+
+    /// Sends the reports to `sender`, for example to a thread drawing a
+    /// progress bar. The reports are dropped once the receiver is gone.
+    pub fn channel(sender: Sender<TransferProgress>) -> Progress {
+        let sender = Mutex::new(sender);
+        Progress::new(move |progress| {
+            let _ = sender.lock().unwrap().send(progress);
+        })
+    }
+
+    pub(crate) fn report(&self, transferred: u64, total: Option<u64>) {
+        (self.report)(TransferProgress { transferred, total });
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Progress")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_channel() {
+        let (sender, receiver) = channel();
+        let progress = Progress::channel(sender);
+        progress.report(10, Some(20));
+        progress.clone().report(20, Some(20));
+        drop(progress);
+        assert_eq!(
+            receiver.iter().collect::<Vec<_>>(),
+            vec![
+                TransferProgress {
+                    transferred: 10,
+                    total: Some(20)
+                },
+                TransferProgress {
+                    transferred: 20,
+                    total: Some(20)
+                },
+            ]
+        );
+    }
+}
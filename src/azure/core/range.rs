@@ -69,6 +69,58 @@ impl fmt::Display for Range {
     }
 }
 
+/// The `Content-Range` of a partial response: the inclusive range of
+/// bytes returned, and the length of the whole resource if known.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ContentRange {
+    pub start: u64,
+    pub end: u64,
+    pub total_length: Option<u64>,
+}
+
+impl ContentRange {
+    /// The number of bytes returned.
+    pub fn content_length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+impl FromStr for ContentRange {
+    type Err = ParseError;
+    // bytes <start>-<end>/<total length or *>
+    fn from_str(s: &str) -> Result<ContentRange, ParseError> {
+        let s = s.trim_start_matches("bytes").trim_start();
+        let (range, total_length) = split_once(s, '/')?;
+        let (start, end) = split_once(range, '-')?;
+
+        Ok(ContentRange {
+            start: start.parse::<u64>()?,
+            end: end.parse::<u64>()?,
+            total_length: match total_length {
+                "*" => None,
+                total_length => Some(total_length.parse::<u64>()?),
+            },
+        })
+    }
+}
+
+fn split_once(s: &str, separator: char) -> Result<(&str, &str), ParseError> {
+    let mut tokens = s.splitn(2, separator);
+    match (tokens.next(), tokens.next()) {
+        (Some(first), Some(second)) => Ok((first, second)),
+        _ => Err(ParseError::SplitNotFound),
+    }
+}
+
+impl fmt::Display for ContentRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.total_length {
+            Some(total_length) => write!(f, "bytes {}-{}/{}", self.start, self.end, total_length),
+            None => write!(f, "bytes {}-{}/*", self.start, self.end),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -101,4 +153,21 @@ mod test {
 
         assert_eq!(txt, "bytes=100-500");
     }
+
+    #[test]
+    fn test_content_range_parse() {
+        let content_range = "bytes 0-511/1024".parse::<ContentRange>().unwrap();
+        assert_eq!(
+            content_range,
+            ContentRange {
+                start: 0,
+                end: 511,
+                total_length: Some(1024)
+            }
+        );
+        assert_eq!(content_range.content_length(), 512);
+        assert_eq!(content_range.to_string(), "bytes 0-511/1024");
+        assert_eq!("bytes 10-19/*".parse::<ContentRange>().unwrap().total_length, None);
+        assert_eq!("bytes 10/20".parse::<ContentRange>(), Err(ParseError::SplitNotFound));
+    }
 }
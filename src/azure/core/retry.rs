@@ -0,0 +1,349 @@
+use crate::azure::core::errors::{is_retriable_status, AzureError};
+use crate::azure::core::sleeper::Sleeper;
+use chrono::{DateTime, Utc};
+use futures::future::{self, Either, Loop};
+use futures::Future;
+use hyper::header::{HeaderMap, RETRY_AFTER};
+use hyper::{self, Body, Method, Response};
+use rand::{self, Rng};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Decides if and when a failed request is sent again. A request is
+/// considered failed if it could not be sent (transport errors) or if the
+/// service replied with a transient error status: 408, 429, 500, 502,
+/// 503 or 504. When the response has a `Retry-After` header its value is
+/// used instead of the policy delay.
+///
+/// Only idempotent requests (`GET`, `HEAD`, `PUT`, `DELETE` and
+/// `OPTIONS`) are retried unless `is_retriable_method` says otherwise.
+pub trait RetryPolicy: Debug + Send + Sync {
+    /// The wait before the retry number `retry` (starting from 1), or
+    /// `None` to give up and return the last outcome to the caller.
+    fn delay(&self, retry: u32) -> Option<Duration>;
+
+    fn is_retriable_method(&self, method: &Method) -> bool {
+        is_idempotent(method)
+    }
+}
+
+/// Never retries. It's the `Client` default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn delay(&self, _retry: u32) -> Option<Duration> {
+        None
+    }
+}
+
+/// Retries up to `max_retries` times, always waiting `delay`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedRetry {
+    delay: Duration,
+    max_retries: u32,
+    retry_non_idempotent: bool,
+}
+
+impl FixedRetry {
+    pub fn new(delay: Duration, max_retries: u32) -> FixedRetry {
+        FixedRetry {
+            delay,
+            max_retries,
+            retry_non_idempotent: false,
+        }
+    }
+
+    /// Retries `POST` (and the other non idempotent) requests too. Enable
+    /// it only if sending the same request twice is harmless for your
+    /// application.
+    pub fn retry_non_idempotent(self) -> FixedRetry {
+        FixedRetry {
+            retry_non_idempotent: true,
+            ..self
+        }
+    }
+}
+
+impl RetryPolicy for FixedRetry {
+    fn delay(&self, retry: u32) -> Option<Duration> {
+        if retry <= self.max_retries {
+            Some(self.delay)
+        } else {
+            None
+        }
+    }
+
+    fn is_retriable_method(&self, method: &Method) -> bool {
+        self.retry_non_idempotent || is_idempotent(method)
+    }
+}
+
+/// Retries up to `max_retries` times, doubling the wait each time (up to
+/// `max_delay`). The actual wait is picked at random between half and
+/// the whole computed delay so clients failing together do not retry in
+/// lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExponentialRetry {
+    base_delay: Duration,
+    max_delay: Duration,
+    max_retries: u32,
+    retry_non_idempotent: bool,
+}
+
+impl Default for ExponentialRetry {
+    fn default() -> ExponentialRetry {
+        ExponentialRetry {
+            base_delay: Duration::from_millis(800),
+            max_delay: Duration::from_secs(60),
+            max_retries: 3,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl ExponentialRetry {
+    pub fn new() -> ExponentialRetry {
+        ExponentialRetry::default()
+    }
+
+    pub fn with_base_delay(self, base_delay: Duration) -> ExponentialRetry {
+        ExponentialRetry { base_delay, ..self }
+    }
+
+    pub fn with_max_delay(self, max_delay: Duration) -> ExponentialRetry {
+        ExponentialRetry { max_delay, ..self }
+    }
+
+    pub fn with_max_retries(self, max_retries: u32) -> ExponentialRetry {
+        ExponentialRetry { max_retries, ..self }
+    }
+
+    /// See `FixedRetry::retry_non_idempotent`.
+    pub fn retry_non_idempotent(self) -> ExponentialRetry {
+        ExponentialRetry {
+            retry_non_idempotent: true,
+            ..self
+        }
+    }
+
+    pub fn base_delay(&self) -> Duration {
+        self.base_delay
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    // delay before the jitter
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32 << (retry - 1).min(16);
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl RetryPolicy for ExponentialRetry {
+    fn delay(&self, retry: u32) -> Option<Duration> {
+        if retry == 0 || retry > self.max_retries {
+            return None;
+        }
+
+        let backoff = self.backoff(retry);
+        let half = backoff / 2;
+        Some(half + half.mul_f64(rand::thread_rng().gen::<f64>()))
+    }
+
+    fn is_retriable_method(&self, method: &Method) -> bool {
+        self.retry_non_idempotent || is_idempotent(method)
+    }
+}
+
+pub(crate) fn is_idempotent(method: &Method) -> bool {
+    *method == Method::GET || *method == Method::HEAD || *method == Method::PUT || *method == Method::DELETE || *method == Method::OPTIONS
+}
+
+// Retry-After is either a number of seconds or an HTTP date
+pub(crate) fn retry_after_from_headers(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((date - Utc::now()).to_std().unwrap_or_else(|_| Duration::from_secs(0)))
+}
+
+type AttemptFuture = Box<dyn Future<Item = Response<Body>, Error = AzureError> + Send>;
+
+/// Waits for `first` and, if it failed with a retriable outcome, calls
+/// `resend` to send the request again as long as `policy` allows it.
+pub(crate) fn retry<F>(
+    policy: Arc<dyn RetryPolicy>,
+    sleeper: Arc<dyn Sleeper>,
+    first: AttemptFuture,
+    resend: F,
+) -> impl Future<Item = Response<Body>, Error = AzureError>
+where
+    F: Fn() -> Result<AttemptFuture, AzureError> + Send + Sync + 'static,
+{
+    let resend = Arc::new(resend);
+
+    future::loop_fn((first, 0u32), move |(attempt, retries)| {
+        let policy = policy.clone();
+        let sleeper = sleeper.clone();
+        let resend = resend.clone();
+
+        let outcome = attempt.then(move |result| {
+            // Some(retry_after) if the outcome is retriable
+            let retriable = match &result {
+                Ok(response) if is_retriable_status(response.status()) => Some(retry_after_from_headers(response.headers())),
+                Ok(_) => None,
+                Err(error) if error.is_retriable() => Some(None),
+                Err(_) => None,
+            };
+
+            let delay = retriable.and_then(|retry_after| policy.delay(retries + 1).map(|delay| retry_after.unwrap_or(delay)));
+
+            match delay {
+                None => Ok(Either::A(result)),
+                Some(delay) => {
+                    match &result {
+                        Ok(response) => warn!("request failed with status {}, retrying in {:?}", response.status(), delay),
+                        Err(error) => warn!("request failed ({}), retrying in {:?}", error, delay),
+                    }
+                    Ok(Either::B(sleeper.sleep(delay)))
+                }
+            }
+        });
+
+        outcome.and_then(move |next| match next {
+            Either::A(result) => Either::A(future::result(result.map(Loop::Break))),
+            Either::B(wait) => Either::B(
+                wait.and_then(move |_| resend())
+                    .map(move |attempt| Loop::Continue((attempt, retries + 1))),
+            ),
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::sleeper::VirtualSleeper;
+    use hyper::StatusCode;
+    use std::sync::Mutex;
+
+    fn response(status: StatusCode, retry_after: Option<&'static str>) -> AttemptFuture {
+        let mut response = Response::builder();
+        response.status(status);
+        if let Some(retry_after) = retry_after {
+            response.header(RETRY_AFTER, retry_after);
+        }
+        Box::new(future::ok(response.body(Body::empty()).unwrap()))
+    }
+
+    // replays the given statuses, one per attempt
+    fn run(policy: Arc<dyn RetryPolicy>, statuses: Vec<(StatusCode, Option<&'static str>)>) -> (StatusCode, Arc<VirtualSleeper>) {
+        let sleeper = Arc::new(VirtualSleeper::new());
+        let mut statuses = statuses.into_iter();
+        let (status, retry_after) = statuses.next().unwrap();
+        let statuses = Mutex::new(statuses);
+
+        let result = retry(policy, sleeper.clone(), response(status, retry_after), move || {
+            let (status, retry_after) = statuses.lock().unwrap().next().expect("unexpected retry");
+            Ok(response(status, retry_after))
+        })
+        .wait()
+        .unwrap();
+
+        (result.status(), sleeper)
+    }
+
+    #[test]
+    fn test_retry_until_success() {
+        let policy = Arc::new(FixedRetry::new(Duration::from_secs(1), 3));
+        let (status, sleeper) = run(
+            policy,
+            vec![
+                (StatusCode::SERVICE_UNAVAILABLE, None),
+                (StatusCode::INTERNAL_SERVER_ERROR, None),
+                (StatusCode::OK, None),
+            ],
+        );
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(sleeper.sleeps(), vec![Duration::from_secs(1), Duration::from_secs(1)]);
+    }
+
+    #[test]
+    fn test_retry_gives_up() {
+        let policy = Arc::new(FixedRetry::new(Duration::from_secs(1), 1));
+        let (status, sleeper) = run(
+            policy,
+            vec![(StatusCode::SERVICE_UNAVAILABLE, None), (StatusCode::SERVICE_UNAVAILABLE, None)],
+        );
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(sleeper.sleeps(), vec![Duration::from_secs(1)]);
+    }
+
+    #[test]
+    fn test_retry_honors_retry_after() {
+        let policy = Arc::new(FixedRetry::new(Duration::from_secs(1), 3));
+        let (status, sleeper) = run(policy, vec![(StatusCode::TOO_MANY_REQUESTS, Some("7")), (StatusCode::OK, None)]);
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(sleeper.sleeps(), vec![Duration::from_secs(7)]);
+    }
+
+    #[test]
+    fn test_no_retry_on_client_errors() {
+        let policy = Arc::new(FixedRetry::new(Duration::from_secs(1), 3));
+        let (status, sleeper) = run(policy, vec![(StatusCode::NOT_FOUND, None)]);
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(sleeper.sleeps().is_empty());
+    }
+
+    #[test]
+    fn test_exponential_retry() {
+        let policy = ExponentialRetry::new()
+            .with_base_delay(Duration::from_secs(1))
+            .with_max_delay(Duration::from_secs(3))
+            .with_max_retries(4);
+
+        for (retry, backoff) in [(1, 1), (2, 2), (3, 3), (4, 3)].iter() {
+            let delay = policy.delay(*retry).unwrap();
+            assert!(delay >= Duration::from_secs(*backoff) / 2);
+            assert!(delay <= Duration::from_secs(*backoff));
+        }
+        assert_eq!(policy.delay(5), None);
+    }
+
+    #[test]
+    fn test_retriable_methods() {
+        assert!(NoRetry.is_retriable_method(&Method::PUT));
+        assert!(!ExponentialRetry::new().is_retriable_method(&Method::POST));
+        assert!(ExponentialRetry::new().retry_non_idempotent().is_retriable_method(&Method::POST));
+    }
+
+    #[test]
+    fn test_retry_after_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after_from_headers(&headers), None);
+
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(retry_after_from_headers(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after_from_headers(&headers), Some(Duration::from_secs(0)));
+    }
+}
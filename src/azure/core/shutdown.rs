@@ -0,0 +1,51 @@
+use crate::azure::core::cancellation::CancellationToken;
+use crate::azure::core::errors::AzureError;
+use futures::Future;
+
+/// Stops a background helper (`QueueDepthPoller::run_until_shutdown`,
+/// `QueueBridge::with_shutdown`) gracefully: unlike a
+/// `CancellationToken`, which aborts the requests, the helper completes
+/// the work in flight (the sample being taken, the batch being forwarded)
+/// and then stops. Give each helper a handle of its own; the clones share
+/// the state.
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownHandle {
+    requested: CancellationToken,
+    stopped: CancellationToken,
+}
+
+impl ShutdownHandle {
+    pub fn new() -> ShutdownHandle {
+        ShutdownHandle::default()
+    }
+
+    /// Asks the helper to stop. The returned future completes once it
+    /// has, its in-flight work done.
+    pub fn shutdown(&self) -> impl Future<Item = (), Error = AzureError> {
+        self.request_shutdown();
+        self.stopped.cancelled()
+    }
+
+    /// As `shutdown`, without waiting: for example from a signal handler.
+    pub fn request_shutdown(&self) {
+        self.requested.cancel();
+    }
+
+    pub fn is_shutdown_requested(&self) -> bool {
+        self.requested.is_cancelled()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.is_cancelled()
+    }
+
+    // completes when the shutdown is requested, to cut the waits short
+    pub(crate) fn requested(&self) -> impl Future<Item = (), Error = AzureError> {
+        self.requested.cancelled()
+    }
+
+    // called by the helper once it has stopped, whatever the reason
+    pub(crate) fn set_stopped(&self) {
+        self.stopped.cancel();
+    }
+}
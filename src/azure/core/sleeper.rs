@@ -0,0 +1,182 @@
+use crate::azure::core::errors::AzureError;
+use futures::sync::oneshot;
+use futures::{future, Future, Poll};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, Once, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub type SleepFuture = Box<dyn Future<Item = (), Error = AzureError> + Send>;
+
+/// Abstraction over timers. Everything in the crate that needs to wait
+/// (retries, pollers, etc...) goes through a `Sleeper` so the executor
+/// is free to supply its own timer and tests can run in virtual time.
+pub trait Sleeper: Debug + Send + Sync {
+    fn sleep(&self, duration: Duration) -> SleepFuture;
+}
+
+/// Runtime-agnostic sleeper: the sleeps share one timer thread, started
+/// on the first sleep, which resolves the returned futures when they are
+/// due. A sleep dropped before then, as the timeout of a request answered
+/// in time, is removed from the timer. It's the default because it does
+/// not depend on any specific executor, but if you are running on tokio
+/// consider supplying a sleeper backed by its timer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        if duration == Duration::from_secs(0) {
+            return Box::new(future::ok(()));
+        }
+
+        Box::new(Timer::global().sleep(duration))
+    }
+}
+
+// The pending sleeps of the `ThreadSleeper`s, by deadline.
+#[derive(Debug, Default)]
+struct Timer {
+    sleeps: Mutex<BTreeMap<(Instant, usize), oneshot::Sender<()>>>,
+    changed: Condvar,
+    next_id: AtomicUsize,
+}
+
+impl Timer {
+    fn global() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        static START: Once = Once::new();
+
+        let timer = TIMER.get_or_init(Timer::default);
+        START.call_once(|| timer.start());
+        timer
+    }
+
+    fn start(&'static self) {
+        thread::Builder::new()
+            .name("azure-sdk-timer".to_owned())
+            .spawn(move || self.run())
+            .expect("cannot spawn the timer thread");
+    }
+
+    fn run(&self) {
+        let mut sleeps = self.sleeps.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            while let Some(entry) = sleeps.first_entry() {
+                if entry.key().0 > now {
+                    break;
+                }
+                // the sleep may have been dropped meanwhile
+                let _ = entry.remove().send(());
+            }
+
+            sleeps = match sleeps.keys().next() {
+                Some(&(deadline, _)) => self.changed.wait_timeout(sleeps, deadline - now).unwrap().0,
+                None => self.changed.wait(sleeps).unwrap(),
+            };
+        }
+    }
+
+    fn sleep(&'static self, duration: Duration) -> Sleep {
+        let key = (Instant::now() + duration, self.next_id.fetch_add(1, Ordering::Relaxed));
+        let (tx, rx) = oneshot::channel();
+        self.sleeps.lock().unwrap().insert(key, tx);
+        self.changed.notify_one();
+        Sleep { timer: self, key, rx }
+    }
+}
+
+// A sleep of the `Timer`, removed from it when dropped.
+struct Sleep {
+    timer: &'static Timer,
+    key: (Instant, usize),
+    rx: oneshot::Receiver<()>,
+}
+
+impl Future for Sleep {
+    type Item = ();
+    type Error = AzureError;
+
+    fn poll(&mut self) -> Poll<(), AzureError> {
+        self.rx
+            .poll()
+            .map_err(|_| AzureError::GenericErrorWithText("timer thread terminated unexpectedly".to_owned()))
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        self.timer.sleeps.lock().unwrap().remove(&self.key);
+    }
+}
+
+/// Sleeper that never waits: it just keeps track of how much (virtual)
+/// time has been requested. Useful in tests.
+#[derive(Debug, Default)]
+pub struct VirtualSleeper {
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl VirtualSleeper {
+    pub fn new() -> VirtualSleeper {
+        VirtualSleeper::default()
+    }
+
+    /// The durations requested so far, in order.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap().clone()
+    }
+
+    /// Sum of all the durations requested so far.
+    pub fn elapsed(&self) -> Duration {
+        self.sleeps.lock().unwrap().iter().fold(Duration::from_secs(0), |acc, d| acc + *d)
+    }
+}
+
+impl Sleeper for VirtualSleeper {
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        self.sleeps.lock().unwrap().push(duration);
+        Box::new(future::ok(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_thread_sleeper() {
+        let start = Instant::now();
+        ThreadSleeper.sleep(Duration::from_millis(20)).wait().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_dropped_sleep() {
+        let timer: &'static Timer = Box::leak(Box::new(Timer::default()));
+        timer.start();
+
+        let pending = || timer.sleeps.lock().unwrap().len();
+
+        let short = timer.sleep(Duration::from_millis(20));
+        let long = timer.sleep(Duration::from_secs(3600));
+        assert_eq!(pending(), 2);
+        short.wait().unwrap();
+        assert_eq!(pending(), 1);
+        drop(long);
+        assert_eq!(pending(), 0);
+    }
+
+    #[test]
+    fn test_virtual_sleeper() {
+        let sleeper = VirtualSleeper::new();
+        sleeper.sleep(Duration::from_secs(60)).wait().unwrap();
+        sleeper.sleep(Duration::from_secs(30)).wait().unwrap();
+        assert_eq!(sleeper.sleeps(), vec![Duration::from_secs(60), Duration::from_secs(30)]);
+        assert_eq!(sleeper.elapsed(), Duration::from_secs(90));
+    }
+}
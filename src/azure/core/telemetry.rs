@@ -0,0 +1,375 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::REQUEST_ID;
+use crate::azure::core::pipeline::{Next, PipelineFuture, PipelineRequest, Policy};
+use chrono::{DateTime, Utc};
+use futures::Future;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Receives the measurements collected by the crate (for example by the
+/// `QueueDepthPoller`). Every method has an empty default implementation
+/// so observers implement only the events they care about.
+///
+/// The methods are called from within the futures: they must return
+/// quickly and must not block. Hand the values over to your metrics
+/// system instead of, for example, pushing them over the network.
+pub trait TelemetryObserver: Debug + Send + Sync {
+    fn on_queue_depth(&self, _queue_name: &str, _approximate_messages_count: u64, _sampled_at: DateTime<Utc>) {}
+
+    fn on_queue_depth_error(&self, _queue_name: &str, _error: &AzureError) {}
+
+    /// Called once per HTTP request sent by a storage `Client`, retries
+    /// included, when its response headers arrive (or it fails).
+    fn on_request(&self, _sample: &RequestSample) {}
+}
+
+/// The duration of a request, from the moment it is sent to the arrival
+/// of the response headers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestSample {
+    /// The method followed by the `comp` parameter, if any, for example
+    /// `PUT block` or `GET list`.
+    pub operation: String,
+    /// `None` if no response was received.
+    pub status: Option<u16>,
+    pub duration: Duration,
+    /// The `x-ms-request-id` of the response, to look the request up in
+    /// the storage logs.
+    pub request_id: Option<String>,
+}
+
+/// The upper bounds, in milliseconds, of the `LatencyHistogram` buckets.
+/// The last bucket holds the longer durations.
+pub const LATENCY_BUCKETS_MS: [u64; 16] = [
+    1, 2, 5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000, 10_000, 20_000, 30_000, 60_000,
+];
+
+/// Counts of durations in fixed, roughly exponential buckets (see
+/// `LATENCY_BUCKETS_MS`), from which the percentiles can be estimated
+/// and which can be summed across clients or hosts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyHistogram {
+    counts: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    max: Duration,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> LatencyHistogram {
+        LatencyHistogram {
+            counts: [0; LATENCY_BUCKETS_MS.len() + 1],
+            max: Duration::from_secs(0),
+        }
+    }
+}
+
+impl LatencyHistogram {
+    pub fn new() -> LatencyHistogram {
+        LatencyHistogram::default()
+    }
+
+    pub fn record(&mut self, duration: Duration) {
+        let millis = duration.as_secs() * 1000 + u64::from(duration.subsec_nanos()) / 1_000_000;
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| millis < bound)
+            .unwrap_or_else(|| LATENCY_BUCKETS_MS.len());
+        self.counts[bucket] += 1;
+        self.max = self.max.max(duration);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// The number of durations in each bucket, with the upper bound of
+    /// the bucket (`None` for the last one).
+    pub fn buckets(&self) -> Vec<(Option<Duration>, u64)> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| (LATENCY_BUCKETS_MS.get(i).map(|&bound| Duration::from_millis(bound)), count))
+            .collect()
+    }
+
+    /// The upper bound of the bucket holding the `percentile` (between 0
+    /// and 100) duration, or the longest duration if it falls in the last
+    /// bucket. `None` if nothing was recorded.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+
+        // the out of range percentiles are the first and the last ones
+        let fraction = if percentile <= 0.0 {
+            0.0
+        } else if percentile >= 100.0 {
+            1.0
+        } else {
+            percentile / 100.0
+        };
+        let rank = ((fraction * count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (i, &bucket_count) in self.counts.iter().enumerate() {
+            seen += bucket_count;
+            if seen >= rank {
+                return Some(
+                    LATENCY_BUCKETS_MS
+                        .get(i)
+                        .map_or(self.max, |&bound| Duration::from_millis(bound).min(self.max)),
+                );
+            }
+        }
+        Some(self.max)
+    }
+
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (count, other_count) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *count += other_count;
+        }
+        self.max = self.max.max(other.max);
+    }
+}
+
+type SlowRequestCallback = Arc<dyn Fn(&RequestSample) + Send + Sync>;
+
+/// Observer keeping a `LatencyHistogram` per operation and, optionally,
+/// calling back on each request slower than a threshold.
+#[derive(Default)]
+pub struct LatencyObserver {
+    histograms: Mutex<HashMap<String, LatencyHistogram>>,
+    slow_requests: Option<(Duration, SlowRequestCallback)>,
+}
+
+impl LatencyObserver {
+    pub fn new() -> LatencyObserver {
+        LatencyObserver::default()
+    }
+
+    /// Calls `callback` for every request taking longer than `threshold`.
+    /// Like the observer methods, the callback must return quickly.
+    pub fn with_slow_request_callback<F>(self, threshold: Duration, callback: F) -> LatencyObserver
+    where
+        F: Fn(&RequestSample) + Send + Sync + 'static,
+    {
+        LatencyObserver {
+            slow_requests: Some((threshold, Arc::new(callback))),
+            ..self
+        }
+    }
+
+    pub fn histogram(&self, operation: &str) -> Option<LatencyHistogram> {
+        self.histograms.lock().unwrap().get(operation).cloned()
+    }
+
+    /// A copy of the histograms, by operation.
+    pub fn histograms(&self) -> HashMap<String, LatencyHistogram> {
+        self.histograms.lock().unwrap().clone()
+    }
+
+    /// Empties the histograms, for example after they have been exported.
+    pub fn reset(&self) {
+        self.histograms.lock().unwrap().clear();
+    }
+}
+
+impl Debug for LatencyObserver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LatencyObserver")
+            .field("histograms", &self.histograms)
+            .field(
+                "slow_request_threshold",
+                &self.slow_requests.as_ref().map(|(threshold, _)| threshold),
+            )
+            .finish()
+    }
+}
+
+impl TelemetryObserver for LatencyObserver {
+    fn on_request(&self, sample: &RequestSample) {
+        self.histograms
+            .lock()
+            .unwrap()
+            .entry(sample.operation.clone())
+            .or_default()
+            .record(sample.duration);
+
+        if let Some((threshold, callback)) = &self.slow_requests {
+            if sample.duration > *threshold {
+                callback(sample);
+            }
+        }
+    }
+}
+
+// e.g. PUT block for https://account.blob.core.windows.net/c/b?comp=block
+fn operation_name(request: &PipelineRequest) -> String {
+    let comp = Url::parse(&request.uri)
+        .ok()
+        .and_then(|uri| uri.query_pairs().find(|(key, _)| key == "comp").map(|(_, comp)| comp.into_owned()));
+    match comp {
+        Some(comp) => format!("{} {}", request.method, comp),
+        None => request.method.to_string(),
+    }
+}
+
+/// Pipeline stage reporting the duration of each attempt to the
+/// `TelemetryObserver`.
+#[derive(Debug, Clone)]
+pub(crate) struct LatencyStage {
+    observer: Arc<dyn TelemetryObserver>,
+}
+
+impl LatencyStage {
+    pub(crate) fn new(observer: Arc<dyn TelemetryObserver>) -> LatencyStage {
+        LatencyStage { observer }
+    }
+}
+
+impl Policy for LatencyStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        let operation = operation_name(&request);
+        let observer = self.observer.clone();
+        let start = Instant::now();
+
+        Box::new(next.send(request).then(move |result| {
+            let (status, request_id) = match &result {
+                Ok(response) => (
+                    Some(response.status().as_u16()),
+                    response
+                        .headers()
+                        .get(REQUEST_ID)
+                        .and_then(|request_id| request_id.to_str().ok())
+                        .map(str::to_owned),
+                ),
+                Err(_) => (None, None),
+            };
+            observer.on_request(&RequestSample {
+                operation,
+                status,
+                duration: start.elapsed(),
+                request_id,
+            });
+            result
+        }))
+    }
+}
+
+/// Observer that discards everything. It's the `Client` default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopObserver;
+
+impl TelemetryObserver for NoopObserver {}
+
+/// Observer that keeps the queue depths it receives in memory, in
+/// arrival order. Useful in tests.
+#[derive(Debug, Default)]
+pub struct RecordingObserver {
+    queue_depths: Mutex<Vec<(String, u64)>>,
+    queue_depth_errors: Mutex<Vec<String>>,
+}
+
+impl RecordingObserver {
+    pub fn new() -> RecordingObserver {
+        RecordingObserver::default()
+    }
+
+    pub fn queue_depths(&self) -> Vec<(String, u64)> {
+        self.queue_depths.lock().unwrap().clone()
+    }
+
+    /// The names of the queues that could not be sampled.
+    pub fn queue_depth_errors(&self) -> Vec<String> {
+        self.queue_depth_errors.lock().unwrap().clone()
+    }
+}
+
+impl TelemetryObserver for RecordingObserver {
+    fn on_queue_depth(&self, queue_name: &str, approximate_messages_count: u64, _sampled_at: DateTime<Utc>) {
+        self.queue_depths
+            .lock()
+            .unwrap()
+            .push((queue_name.to_owned(), approximate_messages_count));
+    }
+
+    fn on_queue_depth_error(&self, queue_name: &str, _error: &AzureError) {
+        self.queue_depth_errors.lock().unwrap().push(queue_name.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::pipeline;
+    use futures::future;
+    use hyper::header::HeaderMap;
+    use hyper::{Body, Method, Response};
+
+    #[test]
+    fn test_latency_histogram() {
+        let mut histogram = LatencyHistogram::new();
+        assert_eq!(histogram.percentile(50.0), None);
+
+        for millis in &[3, 4, 4, 15, 15, 15, 15, 15, 150, 90_000] {
+            histogram.record(Duration::from_millis(*millis));
+        }
+        assert_eq!(histogram.count(), 10);
+        assert_eq!(histogram.percentile(20.0), Some(Duration::from_millis(5)));
+        assert_eq!(histogram.percentile(50.0), Some(Duration::from_millis(20)));
+        assert_eq!(histogram.percentile(90.0), Some(Duration::from_millis(200)));
+        assert_eq!(histogram.percentile(99.0), Some(Duration::from_secs(90)));
+
+        let mut merged = LatencyHistogram::new();
+        merged.merge(&histogram);
+        merged.merge(&histogram);
+        assert_eq!(merged.count(), 20);
+        assert_eq!(merged.buckets()[2], (Some(Duration::from_millis(5)), 6));
+        assert_eq!(merged.buckets()[LATENCY_BUCKETS_MS.len()], (None, 2));
+    }
+
+    #[test]
+    fn test_latency_stage() {
+        let slow = Arc::new(Mutex::new(Vec::new()));
+        let observer = {
+            let slow = slow.clone();
+            Arc::new(
+                LatencyObserver::new()
+                    .with_slow_request_callback(Duration::from_secs(0), move |sample| slow.lock().unwrap().push(sample.clone())),
+            )
+        };
+
+        let request = PipelineRequest {
+            method: Method::PUT,
+            uri: "https://account.blob.core.windows.net/c/b?comp=block&blockid=AA%3D%3D".to_owned(),
+            headers: HeaderMap::new(),
+            body: None,
+            pacer: None,
+        };
+        pipeline::send(
+            vec![Arc::new(LatencyStage::new(observer.clone()))],
+            |_| -> PipelineFuture {
+                std::thread::sleep(Duration::from_millis(1));
+                let mut response = Response::new(Body::empty());
+                response.headers_mut().insert(REQUEST_ID, "b0a5e9cc".parse().unwrap());
+                Box::new(future::ok(response))
+            },
+            request,
+        )
+        .wait()
+        .unwrap();
+
+        assert_eq!(observer.histogram("PUT block").unwrap().count(), 1);
+        let slow = slow.lock().unwrap();
+        assert_eq!(slow.len(), 1);
+        assert_eq!(slow[0].operation, "PUT block");
+        assert_eq!(slow[0].status, Some(200));
+        assert_eq!(slow[0].request_id.as_deref(), Some("b0a5e9cc"));
+    }
+}
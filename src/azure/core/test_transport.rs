@@ -0,0 +1,247 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::http_client::HttpClient;
+use crate::azure::core::http_trace::sanitize_url;
+use crate::azure::core::pipeline::{PipelineFuture, PipelineRequest};
+use futures::{Future, Stream};
+use hyper::{Body, Response, StatusCode};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// A request and the response the service sent back, as saved by
+/// `RecordingTransport` and replayed by `ReplayTransport`. The body is
+/// base64 encoded, the SAS signatures of the uri are redacted and the
+/// request headers (the authorization among them) are not kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub method: String,
+    pub uri: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedExchange {
+    fn matches(&self, request: &PipelineRequest) -> bool {
+        // the canned responses answer any request
+        self.method.is_empty() || (self.method == request.method.as_str() && path(&self.uri) == path(&request.uri))
+    }
+
+    fn to_response(&self) -> Result<Response<Body>, AzureError> {
+        let mut response = Response::builder();
+        response.status(StatusCode::from_u16(self.status).map_err(|e| AzureError::GenericErrorWithText(e.to_string()))?);
+        for (name, value) in &self.headers {
+            response.header(name.as_str(), value.as_str());
+        }
+        Ok(response.body(Body::from(base64::decode(&self.body)?))?)
+    }
+}
+
+/// An `HttpClient` answering with canned or recorded responses, in order,
+/// without touching the network. A recorded response is only returned to
+/// a request with the same method and path; the others fail, as do the
+/// requests sent once the responses are exhausted.
+///
+/// ```no_run
+/// # use azure_sdk_for_rust::core::test_transport::ReplayTransport;
+/// # use azure_sdk_for_rust::storage::client::Client;
+/// # use hyper::StatusCode;
+/// # use std::sync::Arc;
+/// let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::CREATED, &[("etag", "\"0x1\"")], b""));
+/// let client = Client::azure("account", "a2V5").unwrap().with_http_client(transport.clone());
+/// ```
+#[derive(Debug, Default)]
+pub struct ReplayTransport {
+    exchanges: Mutex<VecDeque<RecordedExchange>>,
+    requests: Mutex<Vec<PipelineRequest>>,
+}
+
+impl ReplayTransport {
+    pub fn new() -> ReplayTransport {
+        ReplayTransport::default()
+    }
+
+    /// Replays a session saved with `RecordingTransport::save`.
+    pub fn from_session<P: AsRef<Path>>(path: P) -> Result<ReplayTransport, AzureError> {
+        let exchanges: Vec<RecordedExchange> = serde_json::from_slice(&std::fs::read(path)?)?;
+        Ok(ReplayTransport::from_exchanges(exchanges))
+    }
+
+    pub fn from_exchanges(exchanges: Vec<RecordedExchange>) -> ReplayTransport {
+        ReplayTransport {
+            exchanges: Mutex::new(exchanges.into()),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Queues a response returned to the next request, whatever it is.
+    pub fn with_response(self, status: StatusCode, headers: &[(&str, &str)], body: &[u8]) -> ReplayTransport {
+        self.exchanges.lock().unwrap().push_back(RecordedExchange {
+            method: String::new(),
+            uri: String::new(),
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+                .collect(),
+            body: base64::encode(body),
+        });
+        self
+    }
+
+    /// The requests received so far, signed and with their headers.
+    pub fn requests(&self) -> Vec<PipelineRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The number of responses not replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.exchanges.lock().unwrap().len()
+    }
+}
+
+impl HttpClient for ReplayTransport {
+    fn execute(&self, request: PipelineRequest) -> PipelineFuture {
+        let exchange = self.exchanges.lock().unwrap().pop_front();
+        let response = match exchange {
+            Some(ref exchange) if exchange.matches(&request) => exchange.to_response(),
+            Some(exchange) => Err(AzureError::GenericErrorWithText(format!(
+                "the recorded request is {} {}, not {} {}",
+                exchange.method, exchange.uri, request.method, request.uri
+            ))),
+            None => Err(AzureError::GenericErrorWithText(format!(
+                "no response left to replay for {} {}",
+                request.method, request.uri
+            ))),
+        };
+        self.requests.lock().unwrap().push(request);
+        Box::new(futures::future::result(response))
+    }
+}
+
+/// An `HttpClient` sending the requests with another one and keeping the
+/// exchanges, to be saved as a session replayed later by
+/// `ReplayTransport`.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn HttpClient>,
+    exchanges: Arc<Mutex<Vec<RecordedExchange>>>,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Arc<dyn HttpClient>) -> RecordingTransport {
+        RecordingTransport {
+            inner,
+            exchanges: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn exchanges(&self) -> Vec<RecordedExchange> {
+        self.exchanges.lock().unwrap().clone()
+    }
+
+    /// Writes the exchanges recorded so far as JSON.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AzureError> {
+        std::fs::write(path, serde_json::to_vec_pretty(&self.exchanges())?)?;
+        Ok(())
+    }
+}
+
+impl HttpClient for RecordingTransport {
+    fn execute(&self, request: PipelineRequest) -> PipelineFuture {
+        let method = request.method.to_string();
+        let uri = sanitize_url(&request.uri);
+        let exchanges = self.exchanges.clone();
+
+        Box::new(self.inner.execute(request).and_then(move |response| {
+            let (parts, body) = response.into_parts();
+            body.concat2().from_err().map(move |body| {
+                exchanges.lock().unwrap().push(RecordedExchange {
+                    method,
+                    uri,
+                    status: parts.status.as_u16(),
+                    headers: parts
+                        .headers
+                        .iter()
+                        .filter_map(|(name, value)| value.to_str().ok().map(|value| (name.as_str().to_owned(), value.to_owned())))
+                        .collect(),
+                    body: base64::encode(&body),
+                });
+                Response::from_parts(parts, Body::from(body))
+            })
+        }))
+    }
+}
+
+fn path(uri: &str) -> &str {
+    uri.split('?').next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::storage::client::Client;
+    use hyper::Method;
+
+    #[test]
+    fn test_replay() {
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::OK, &[("x-ms-request-id", "1")], b"abc"));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+
+        let response = client
+            .perform_request("https://account.blob.core.windows.net/c/b", &Method::GET, |_| {}, None)
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert_eq!(response.headers()["x-ms-request-id"], "1");
+        assert_eq!(&response.into_body().concat2().wait().unwrap()[..], b"abc");
+        assert_eq!(transport.remaining(), 0);
+        assert_eq!(transport.requests()[0].uri, "https://account.blob.core.windows.net/c/b");
+
+        // nothing left to replay
+        assert!(client
+            .perform_request("https://account.blob.core.windows.net/c/b", &Method::GET, |_| {}, None)
+            .unwrap()
+            .wait()
+            .is_err());
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let inner = Arc::new(ReplayTransport::new().with_response(StatusCode::CREATED, &[("etag", "\"0x1\"")], b""));
+        let recorder = RecordingTransport::new(inner);
+        let request = PipelineRequest {
+            method: Method::PUT,
+            uri: "https://account.blob.core.windows.net/c/b?sv=2018-11-09&sig=c2ln".to_owned(),
+            headers: Default::default(),
+            body: None,
+            pacer: None,
+        };
+        assert_eq!(recorder.execute(request.clone()).wait().unwrap().status(), StatusCode::CREATED);
+
+        let exchanges = recorder.exchanges();
+        assert_eq!(exchanges[0].method, "PUT");
+        assert_eq!(
+            exchanges[0].uri,
+            "https://account.blob.core.windows.net/c/b?sv=2018-11-09&sig=REDACTED"
+        );
+        assert_eq!(exchanges[0].headers, vec![("etag".to_owned(), "\"0x1\"".to_owned())]);
+
+        let path = std::env::temp_dir().join(format!("azure-sdk-session-{}.json", uuid::Uuid::new_v4()));
+        recorder.save(&path).unwrap();
+        let replay = ReplayTransport::from_session(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // a recorded response answers the same method and path only
+        let mut get = request.clone();
+        get.method = Method::GET;
+        assert!(replay.execute(get).wait().is_err());
+
+        let replay = ReplayTransport::from_exchanges(exchanges);
+        let response = replay.execute(request).wait().unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["etag"], "\"0x1\"");
+    }
+}
@@ -0,0 +1,561 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::pipeline::{Next, PipelineFuture, PipelineRequest, Policy};
+use crate::azure::core::retry::retry_after_from_headers;
+use crate::azure::core::sleeper::Sleeper;
+use futures::future::{self, Either};
+use futures::sync::oneshot;
+use futures::{Async, Future, Poll};
+use hyper::{Body, Response, StatusCode};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::{Host, Url};
+
+/// Tunables of a `Throttler`. When an account answers `503 Server Busy`
+/// (for example because the ingress or egress limits have been reached)
+/// or `429 Too Many Requests`, the following requests to the same
+/// account wait `initial_delay` first. Each further throttled response
+/// doubles the wait, up to `max_delay` (a `Retry-After` header, if
+/// present, is used as a lower bound); each successful one halves it,
+/// until it drops back to zero.
+///
+/// `max_concurrency` caps the number of requests in flight per account:
+/// the exceeding ones are queued and sent in order as slots free up.
+///
+/// `request_rate` enforces a budget of requests per second per account
+/// (a token bucket): the requests beyond it are delayed instead of being
+/// sent in bursts the account would answer with `503 Server Busy`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThrottlingPolicy {
+    initial_delay: Duration,
+    max_delay: Duration,
+    max_concurrency: Option<usize>,
+    request_rate: Option<RequestRate>,
+}
+
+/// A budget of `per_second` requests per second, of which up to `burst`
+/// can be sent at once after an idle period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequestRate {
+    pub per_second: u32,
+    pub burst: u32,
+}
+
+impl Default for ThrottlingPolicy {
+    fn default() -> ThrottlingPolicy {
+        ThrottlingPolicy {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_concurrency: None,
+            request_rate: None,
+        }
+    }
+}
+
+impl ThrottlingPolicy {
+    pub fn new() -> ThrottlingPolicy {
+        ThrottlingPolicy::default()
+    }
+
+    pub fn with_initial_delay(self, initial_delay: Duration) -> ThrottlingPolicy {
+        ThrottlingPolicy { initial_delay, ..self }
+    }
+
+    pub fn with_max_delay(self, max_delay: Duration) -> ThrottlingPolicy {
+        ThrottlingPolicy { max_delay, ..self }
+    }
+
+    /// At most `max_concurrency` requests in flight per account. It must
+    /// be at least 1.
+    pub fn with_max_concurrency(self, max_concurrency: usize) -> ThrottlingPolicy {
+        ThrottlingPolicy {
+            max_concurrency: Some(max_concurrency.max(1)),
+            ..self
+        }
+    }
+
+    /// At most `per_second` requests per second per account, for example
+    /// 20,000 for the default limit of a general purpose v2 account. Up
+    /// to `burst` requests (at least 1) go through without waiting after
+    /// an idle period.
+    pub fn with_request_rate(self, per_second: u32, burst: u32) -> ThrottlingPolicy {
+        ThrottlingPolicy {
+            request_rate: Some(RequestRate {
+                per_second: per_second.max(1),
+                burst: burst.max(1),
+            }),
+            ..self
+        }
+    }
+
+    pub fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    pub fn max_concurrency(&self) -> Option<usize> {
+        self.max_concurrency
+    }
+
+    pub fn request_rate(&self) -> Option<RequestRate> {
+        self.request_rate
+    }
+}
+
+#[derive(Debug, Default)]
+struct AccountState {
+    delay: Duration,
+    in_flight: usize,
+    // the queued requests, by the id of their Waiter
+    waiting: VecDeque<(u64, oneshot::Sender<()>)>,
+    next_waiter: u64,
+    // the token bucket of the request rate: the tokens available at
+    // refilled_at, negative when requests are already scheduled ahead
+    tokens: f64,
+    refilled_at: Option<Instant>,
+}
+
+/// Per-account adaptive throttling. It's meant to be shared (wrapped in
+/// an `Arc`) between all the requests sent by a client.
+#[derive(Debug)]
+pub struct Throttler {
+    policy: ThrottlingPolicy,
+    accounts: Mutex<HashMap<String, AccountState>>,
+}
+
+impl Throttler {
+    pub fn new(policy: ThrottlingPolicy) -> Throttler {
+        Throttler {
+            policy,
+            accounts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn policy(&self) -> &ThrottlingPolicy {
+        &self.policy
+    }
+
+    /// The wait currently imposed on the requests to `account`.
+    pub fn delay(&self, account: &str) -> Duration {
+        self.accounts
+            .lock()
+            .unwrap()
+            .get(account)
+            .map_or(Duration::from_secs(0), |state| state.delay)
+    }
+
+    /// Number of requests to `account` currently in flight.
+    pub fn in_flight(&self, account: &str) -> usize {
+        self.accounts.lock().unwrap().get(account).map_or(0, |state| state.in_flight)
+    }
+
+    /// Records a throttled response from `account`.
+    pub fn record_throttled(&self, account: &str, retry_after: Option<Duration>) {
+        let mut accounts = self.accounts.lock().unwrap();
+        let state = accounts.entry(account.to_owned()).or_default();
+
+        let delay = if state.delay == Duration::from_secs(0) {
+            self.policy.initial_delay
+        } else {
+            state.delay * 2
+        };
+        state.delay = delay.max(retry_after.unwrap_or_default()).min(self.policy.max_delay);
+        warn!("account {} is throttling, delaying its requests by {:?}", account, state.delay);
+    }
+
+    /// Records a response from `account` that was not throttled.
+    pub fn record_success(&self, account: &str) {
+        if let Some(state) = self.accounts.lock().unwrap().get_mut(account) {
+            state.delay = if state.delay <= self.policy.initial_delay {
+                Duration::from_secs(0)
+            } else {
+                state.delay / 2
+            };
+        }
+    }
+
+    // Takes a token from the bucket of account at now and returns how
+    // long the request must wait for it. The token is taken even if it is
+    // not available yet, so that the waiting requests are spread evenly.
+    fn reserve(&self, account: &str, now: Instant) -> Duration {
+        let rate = match self.policy.request_rate {
+            Some(rate) => rate,
+            None => return Duration::from_secs(0),
+        };
+        let per_second = f64::from(rate.per_second);
+        let burst = f64::from(rate.burst);
+
+        let mut accounts = self.accounts.lock().unwrap();
+        let state = accounts.entry(account.to_owned()).or_default();
+
+        state.tokens = match state.refilled_at {
+            Some(refilled_at) if now > refilled_at => {
+                let elapsed = now - refilled_at;
+                let elapsed = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1e9;
+                (state.tokens + elapsed * per_second).min(burst)
+            }
+            Some(_) => state.tokens,
+            None => burst,
+        };
+        state.refilled_at = Some(state.refilled_at.map_or(now, |refilled_at| refilled_at.max(now)));
+        state.tokens -= 1.0;
+
+        if state.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            let wait = -state.tokens / per_second;
+            Duration::new(wait.trunc() as u64, (wait.fract() * 1e9) as u32)
+        }
+    }
+
+    // Resolves when a request to account can be sent. The returned
+    // permit frees the slot when dropped.
+    fn acquire(this: &Arc<Throttler>, account: &str) -> impl Future<Item = Permit, Error = AzureError> {
+        let permit = || Permit {
+            throttler: this.clone(),
+            account: account.to_owned(),
+        };
+
+        let max_concurrency = match this.policy.max_concurrency {
+            Some(max_concurrency) => max_concurrency,
+            None => return Either::A(future::ok(permit())),
+        };
+
+        let mut accounts = this.accounts.lock().unwrap();
+        let state = accounts.entry(account.to_owned()).or_default();
+
+        if state.in_flight < max_concurrency {
+            state.in_flight += 1;
+            Either::A(future::ok(permit()))
+        } else {
+            // the slot is handed over by the permit released before
+            let (tx, rx) = oneshot::channel();
+            let id = state.next_waiter;
+            state.next_waiter += 1;
+            state.waiting.push_back((id, tx));
+            Either::B(Waiter {
+                throttler: this.clone(),
+                account: account.to_owned(),
+                id,
+                rx,
+                done: false,
+            })
+        }
+    }
+
+    fn release(&self, account: &str) {
+        if self.policy.max_concurrency.is_none() {
+            return;
+        }
+
+        let mut accounts = self.accounts.lock().unwrap();
+        if let Some(state) = accounts.get_mut(account) {
+            // skip the waiters that gave up in the meantime
+            while let Some((_, tx)) = state.waiting.pop_front() {
+                if tx.send(()).is_ok() {
+                    return;
+                }
+            }
+            state.in_flight -= 1;
+        }
+    }
+}
+
+// A request queued for a slot. Dropped before getting one (for example
+// by a timeout or a cancellation) it leaves the queue, or frees the slot
+// it has just been handed.
+#[derive(Debug)]
+struct Waiter {
+    throttler: Arc<Throttler>,
+    account: String,
+    id: u64,
+    rx: oneshot::Receiver<()>,
+    done: bool,
+}
+
+impl Future for Waiter {
+    type Item = Permit;
+    type Error = AzureError;
+
+    fn poll(&mut self) -> Poll<Permit, AzureError> {
+        match self.rx.poll() {
+            Ok(Async::Ready(())) => {
+                self.done = true;
+                Ok(Async::Ready(Permit {
+                    throttler: self.throttler.clone(),
+                    account: self.account.clone(),
+                }))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(_) => {
+                self.done = true;
+                Err(AzureError::GenericErrorWithText("throttler dropped".to_owned()))
+            }
+        }
+    }
+}
+
+impl Drop for Waiter {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+
+        let queued = {
+            let mut accounts = self.throttler.accounts.lock().unwrap();
+            match accounts.get_mut(&self.account) {
+                Some(state) => {
+                    let before = state.waiting.len();
+                    state.waiting.retain(|(id, _)| *id != self.id);
+                    state.waiting.len() < before
+                }
+                None => false,
+            }
+        };
+        if !queued {
+            self.throttler.release(&self.account);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Permit {
+    throttler: Arc<Throttler>,
+    account: String,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.throttler.release(&self.account);
+    }
+}
+
+// The storage host is <account>.<service>.core.windows.net: the blob,
+// queue, table and file requests of an account share the same limits.
+// IP addresses (the emulator) are kept whole.
+fn account_from_uri(uri: &str) -> String {
+    match Url::parse(uri).ok().as_ref().and_then(Url::host) {
+        Some(Host::Domain(domain)) => domain.split('.').next().unwrap_or(domain).to_owned(),
+        Some(host) => host.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Pipeline stage enforcing the `Throttler` limits on each attempt.
+#[derive(Debug, Clone)]
+pub(crate) struct ThrottlingStage {
+    throttler: Arc<Throttler>,
+    sleeper: Arc<dyn Sleeper>,
+}
+
+impl ThrottlingStage {
+    pub(crate) fn new(throttler: Arc<Throttler>, sleeper: Arc<dyn Sleeper>) -> ThrottlingStage {
+        ThrottlingStage { throttler, sleeper }
+    }
+}
+
+impl Policy for ThrottlingStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        let account = account_from_uri(&request.uri);
+        Box::new(throttled(&self.throttler, self.sleeper.clone(), account, move || {
+            next.send(request)
+        }))
+    }
+}
+
+/// Calls `send` once the `Throttler` allows a request for `key`, and
+/// records the outcome.
+pub(crate) fn throttled<F, R>(
+    throttler: &Arc<Throttler>,
+    sleeper: Arc<dyn Sleeper>,
+    key: String,
+    send: F,
+) -> impl Future<Item = Response<Body>, Error = AzureError>
+where
+    F: FnOnce() -> R,
+    R: Future<Item = Response<Body>, Error = AzureError>,
+{
+    let throttler = throttler.clone();
+
+    Throttler::acquire(&throttler, &key).and_then(move |permit| {
+        // the delay is read once the slot is available so it reflects
+        // the responses received while waiting; it covers the wait for
+        // the request rate budget too
+        let delay = throttler.delay(&key).max(throttler.reserve(&key, Instant::now()));
+        let wait = if delay > Duration::from_secs(0) {
+            debug!("delaying request to {} by {:?}", key, delay);
+            Either::A(sleeper.sleep(delay))
+        } else {
+            Either::B(future::ok(()))
+        };
+
+        wait.and_then(move |_| send()).map(move |response| {
+            let status = response.status();
+            if status == StatusCode::SERVICE_UNAVAILABLE || status == StatusCode::TOO_MANY_REQUESTS {
+                throttler.record_throttled(&key, retry_after_from_headers(response.headers()));
+            } else {
+                throttler.record_success(&key);
+            }
+            drop(permit);
+            response
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::pipeline;
+    use crate::azure::core::sleeper::VirtualSleeper;
+    use hyper::header::HeaderMap;
+    use hyper::Method;
+
+    fn request() -> PipelineRequest {
+        PipelineRequest {
+            method: Method::GET,
+            uri: "https://account.blob.core.windows.net/container".to_owned(),
+            headers: HeaderMap::new(),
+            body: None,
+            pacer: None,
+        }
+    }
+
+    fn send_with_status(stage: &ThrottlingStage, status: StatusCode) {
+        let stage: Arc<dyn Policy> = Arc::new(stage.clone());
+        pipeline::send(
+            vec![stage],
+            move |_| {
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = status;
+                Box::new(future::ok(response))
+            },
+            request(),
+        )
+        .wait()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_account_from_uri() {
+        assert_eq!(account_from_uri("https://account.blob.core.windows.net/c"), "account");
+        assert_eq!(account_from_uri("https://account.queue.core.windows.net/q"), "account");
+        assert_eq!(account_from_uri("http://127.0.0.1:10000/devstoreaccount1"), "127.0.0.1");
+    }
+
+    #[test]
+    fn test_adaptive_delay() {
+        let throttler = Arc::new(Throttler::new(
+            ThrottlingPolicy::new()
+                .with_initial_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(3)),
+        ));
+        let sleeper = Arc::new(VirtualSleeper::new());
+        let stage = ThrottlingStage::new(throttler.clone(), sleeper.clone());
+
+        send_with_status(&stage, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(throttler.delay("account"), Duration::from_secs(1));
+        send_with_status(&stage, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(throttler.delay("account"), Duration::from_secs(2));
+        send_with_status(&stage, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(throttler.delay("account"), Duration::from_secs(3));
+        send_with_status(&stage, StatusCode::OK);
+        assert_eq!(throttler.delay("account"), Duration::from_millis(1500));
+        send_with_status(&stage, StatusCode::OK);
+        assert_eq!(throttler.delay("account"), Duration::from_millis(750));
+        send_with_status(&stage, StatusCode::OK);
+        assert_eq!(throttler.delay("account"), Duration::from_secs(0));
+
+        assert_eq!(
+            sleeper.sleeps(),
+            vec![
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(3),
+                Duration::from_millis(1500),
+                Duration::from_millis(750)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_request_rate() {
+        let throttler = Throttler::new(ThrottlingPolicy::new().with_request_rate(10, 2));
+        let start = Instant::now();
+
+        // the burst goes through, then the requests are spaced by 100ms
+        assert_eq!(throttler.reserve("account", start), Duration::from_secs(0));
+        assert_eq!(throttler.reserve("account", start), Duration::from_secs(0));
+        assert_eq!(throttler.reserve("account", start), Duration::from_millis(100));
+        assert_eq!(throttler.reserve("account", start), Duration::from_millis(200));
+        assert_eq!(throttler.reserve("other", start), Duration::from_secs(0));
+
+        // the two reserved tokens are refilled after 200ms, the bucket is
+        // full again after 400ms
+        assert_eq!(throttler.reserve("account", start + Duration::from_millis(250)), Duration::from_millis(50));
+        assert_eq!(throttler.reserve("account", start + Duration::from_secs(10)), Duration::from_secs(0));
+        assert_eq!(throttler.reserve("account", start + Duration::from_secs(10)), Duration::from_secs(0));
+        assert_eq!(throttler.reserve("account", start + Duration::from_secs(10)), Duration::from_millis(100));
+
+        let unlimited = Throttler::new(ThrottlingPolicy::new());
+        assert_eq!(unlimited.reserve("account", start), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn test_max_concurrency() {
+        let throttler = Arc::new(Throttler::new(ThrottlingPolicy::new().with_max_concurrency(2)));
+
+        let first = Throttler::acquire(&throttler, "account").wait().unwrap();
+        let second = Throttler::acquire(&throttler, "account").wait().unwrap();
+        assert_eq!(throttler.in_flight("account"), 2);
+
+        // other accounts are not affected
+        let other = Throttler::acquire(&throttler, "other").wait().unwrap();
+        assert_eq!(throttler.in_flight("other"), 1);
+        drop(other);
+        assert_eq!(throttler.in_flight("other"), 0);
+
+        let mut third = Throttler::acquire(&throttler, "account");
+        let ready = future::lazy(|| Ok::<_, ()>(third.poll().unwrap().is_ready())).wait().unwrap();
+        assert!(!ready);
+
+        drop(first);
+        let third = third.wait().unwrap();
+        assert_eq!(throttler.in_flight("account"), 2);
+
+        drop(second);
+        drop(third);
+        assert_eq!(throttler.in_flight("account"), 0);
+    }
+    #[test]
+    fn test_max_concurrency_dropped_waiter() {
+        let throttler = Arc::new(Throttler::new(ThrottlingPolicy::new().with_max_concurrency(2)));
+        let first = Throttler::acquire(&throttler, "account").wait().unwrap();
+        let second = Throttler::acquire(&throttler, "account").wait().unwrap();
+
+        // a queued request dropped (timed out, cancelled) does not free a
+        // slot it never held
+        let mut queued = Throttler::acquire(&throttler, "account");
+        let ready = future::lazy(|| Ok::<_, ()>(queued.poll().unwrap().is_ready())).wait().unwrap();
+        assert!(!ready);
+        drop(queued);
+        assert_eq!(throttler.in_flight("account"), 2);
+        assert!(throttler.accounts.lock().unwrap()["account"].waiting.is_empty());
+
+        // one dropped right after being handed a slot frees it
+        let handed_over = Throttler::acquire(&throttler, "account");
+        drop(first);
+        assert_eq!(throttler.in_flight("account"), 2);
+        drop(handed_over);
+        assert_eq!(throttler.in_flight("account"), 1);
+
+        drop(second);
+        assert_eq!(throttler.in_flight("account"), 0);
+        let third = Throttler::acquire(&throttler, "account").wait().unwrap();
+        assert_eq!(throttler.in_flight("account"), 1);
+        drop(third);
+        assert_eq!(throttler.in_flight("account"), 0);
+    }
+}
@@ -0,0 +1,350 @@
+//! The TLS settings of the HTTP clients.
+//!
+//! The connections are made with the platform TLS library through
+//! `native-tls` (the `native-tls` feature, enabled by default): SChannel on
+//! Windows, Secure Transport on macOS and OpenSSL elsewhere. The `rustls`
+//! feature uses rustls instead, trusting the Mozilla root certificates of
+//! `webpki-roots` rather than the ones of the system; `native-tls` wins if
+//! both are enabled:
+//!
+//! ```toml
+//! azure_sdk_for_rust = { version = "0.11", default-features = false, features = ["ring", "rustls"] }
+//! ```
+//!
+//! Without either feature the clients speak plain HTTP only, for example
+//! to an emulator: the `https` endpoints are refused when connecting.
+
+use crate::azure::core::errors::AzureError;
+use hyper::client::HttpConnector;
+use std::fmt;
+
+#[cfg(feature = "native-tls")]
+mod backend {
+    use super::TlsConfig;
+    use native_tls::{Certificate, TlsConnector};
+
+    pub type TlsError = native_tls::Error;
+
+    pub type HttpsConnector<T> = hyper_tls::HttpsConnector<T>;
+
+    pub(super) fn pem_to_der(pem: &[u8]) -> Result<Vec<u8>, TlsError> {
+        Certificate::from_pem(pem)?.to_der()
+    }
+
+    pub(super) fn check_der(der: &[u8]) -> Result<(), TlsError> {
+        Certificate::from_der(der).map(|_| ())
+    }
+
+    pub(super) fn https_connector<T>(http: T, config: &TlsConfig) -> Result<HttpsConnector<T>, TlsError> {
+        let mut builder = TlsConnector::builder();
+        for der in &config.root_certificates {
+            builder.add_root_certificate(Certificate::from_der(der)?);
+        }
+        builder
+            .danger_accept_invalid_certs(config.accept_invalid_certs)
+            .danger_accept_invalid_hostnames(config.accept_invalid_hostnames);
+        Ok(HttpsConnector::from((http, builder.build()?)))
+    }
+}
+
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+mod backend {
+    use super::TlsConfig;
+    use rustls::internal::pemfile;
+    use rustls::{Certificate, ClientConfig, RootCertStore, ServerCertVerified, ServerCertVerifier};
+    use std::sync::Arc;
+    use std::time::SystemTime;
+    use webpki::DNSNameRef;
+
+    pub type TlsError = rustls::TLSError;
+
+    pub type HttpsConnector<T> = hyper_rustls::HttpsConnector<T>;
+
+    // the algorithms rustls accepts in the certificate signatures
+    static SIGNATURE_ALGORITHMS: &[&webpki::SignatureAlgorithm] = &[
+        &webpki::ECDSA_P256_SHA256,
+        &webpki::ECDSA_P256_SHA384,
+        &webpki::ECDSA_P384_SHA256,
+        &webpki::ECDSA_P384_SHA384,
+        &webpki::RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+        &webpki::RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+        &webpki::RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+        &webpki::RSA_PKCS1_2048_8192_SHA256,
+        &webpki::RSA_PKCS1_2048_8192_SHA384,
+        &webpki::RSA_PKCS1_2048_8192_SHA512,
+        &webpki::RSA_PKCS1_3072_8192_SHA384,
+    ];
+
+    pub(super) fn pem_to_der(mut pem: &[u8]) -> Result<Vec<u8>, TlsError> {
+        let der = pemfile::certs(&mut pem)
+            .ok()
+            .and_then(|certificates| certificates.into_iter().next())
+            .ok_or_else(|| TlsError::General("no PEM certificate found".to_owned()))?
+            .0;
+        check_der(&der)?;
+        Ok(der)
+    }
+
+    pub(super) fn check_der(der: &[u8]) -> Result<(), TlsError> {
+        RootCertStore::empty()
+            .add(&Certificate(der.to_vec()))
+            .map_err(TlsError::WebPKIError)
+    }
+
+    pub(super) fn https_connector<T>(http: T, config: &TlsConfig) -> Result<HttpsConnector<T>, TlsError> {
+        let mut client_config = ClientConfig::new();
+        client_config.root_store.add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+        for der in &config.root_certificates {
+            client_config
+                .root_store
+                .add(&Certificate(der.clone()))
+                .map_err(TlsError::WebPKIError)?;
+        }
+        if config.accept_invalid_certs {
+            client_config.dangerous().set_certificate_verifier(Arc::new(AcceptAnyCertificate));
+        } else if config.accept_invalid_hostnames {
+            client_config.dangerous().set_certificate_verifier(Arc::new(AcceptAnyHostname));
+        }
+        Ok(HttpsConnector::from((http, client_config)))
+    }
+
+    struct AcceptAnyCertificate;
+
+    impl ServerCertVerifier for AcceptAnyCertificate {
+        fn verify_server_cert(
+            &self,
+            _roots: &RootCertStore,
+            _presented_certs: &[Certificate],
+            _dns_name: DNSNameRef,
+            _ocsp_response: &[u8],
+        ) -> Result<ServerCertVerified, TlsError> {
+            Ok(ServerCertVerified::assertion())
+        }
+    }
+
+    // checks the chain as rustls does, without matching the host name
+    struct AcceptAnyHostname;
+
+    impl ServerCertVerifier for AcceptAnyHostname {
+        fn verify_server_cert(
+            &self,
+            roots: &RootCertStore,
+            presented_certs: &[Certificate],
+            _dns_name: DNSNameRef,
+            _ocsp_response: &[u8],
+        ) -> Result<ServerCertVerified, TlsError> {
+            let (end_entity, intermediates) = presented_certs.split_first().ok_or(TlsError::NoCertificatesPresented)?;
+            let end_entity = webpki::EndEntityCert::from(untrusted::Input::from(&end_entity.0)).map_err(TlsError::WebPKIError)?;
+            let intermediates: Vec<_> = intermediates
+                .iter()
+                .map(|certificate| untrusted::Input::from(&certificate.0))
+                .collect();
+            let anchors: Vec<_> = roots.roots.iter().map(|root| root.to_trust_anchor()).collect();
+            let now = webpki::Time::try_from(SystemTime::now()).map_err(|_| TlsError::FailedToGetCurrentTime)?;
+            end_entity
+                .verify_is_valid_tls_server_cert(SIGNATURE_ALGORITHMS, &webpki::TLSServerTrustAnchors(&anchors), &intermediates, now)
+                .map_err(TlsError::WebPKIError)?;
+            Ok(ServerCertVerified::assertion())
+        }
+    }
+}
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+mod backend {
+    use super::TlsConfig;
+    use futures::{future, Future};
+    use hyper::client::connect::{Connect, Connected, Destination};
+    use std::{error, fmt, io};
+
+    #[derive(Debug)]
+    pub struct TlsError;
+
+    impl fmt::Display for TlsError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("no TLS backend: enable the `native-tls` or the `rustls` feature")
+        }
+    }
+
+    impl error::Error for TlsError {}
+
+    /// Connects to the `http` endpoints only.
+    #[derive(Debug, Clone)]
+    pub struct HttpsConnector<T> {
+        http: T,
+    }
+
+    impl<T> Connect for HttpsConnector<T>
+    where
+        T: Connect<Error = io::Error>,
+        T::Future: 'static,
+    {
+        type Transport = T::Transport;
+        type Error = io::Error;
+        type Future = Box<dyn Future<Item = (T::Transport, Connected), Error = io::Error> + Send>;
+
+        fn connect(&self, dst: Destination) -> Self::Future {
+            if dst.scheme() == "https" {
+                Box::new(future::err(io::Error::new(io::ErrorKind::Other, TlsError)))
+            } else {
+                Box::new(self.http.connect(dst))
+            }
+        }
+    }
+
+    pub(super) fn pem_to_der(_pem: &[u8]) -> Result<Vec<u8>, TlsError> {
+        Err(TlsError)
+    }
+
+    pub(super) fn check_der(_der: &[u8]) -> Result<(), TlsError> {
+        Err(TlsError)
+    }
+
+    pub(super) fn https_connector<T>(http: T, _config: &TlsConfig) -> Result<HttpsConnector<T>, TlsError> {
+        Ok(HttpsConnector { http })
+    }
+}
+
+/// The error of the TLS backend, `native_tls::Error` or `rustls::TLSError`.
+pub use self::backend::TlsError;
+
+/// The HTTPS connector of the TLS backend, from `hyper-tls` or
+/// `hyper-rustls`.
+pub use self::backend::HttpsConnector;
+
+/// The TLS settings of the default HTTP client, see
+/// `Client::with_tls_config`.
+#[derive(Clone, Default)]
+pub struct TlsConfig {
+    root_certificates: Vec<Vec<u8>>,
+    accept_invalid_certs: bool,
+    accept_invalid_hostnames: bool,
+}
+
+impl TlsConfig {
+    pub fn new() -> TlsConfig {
+        TlsConfig::default()
+    }
+
+    /// Trusts, besides the default roots, the certificate authority of
+    /// `pem`, for example the one of a TLS inspecting proxy or of an
+    /// emulator with its own certificate.
+    pub fn with_root_certificate_pem(self, pem: &[u8]) -> Result<TlsConfig, AzureError> {
+        let der = backend::pem_to_der(pem)?;
+        Ok(self.with_root_certificate(der))
+    }
+
+    /// As `with_root_certificate_pem`, for a DER encoded certificate.
+    pub fn with_root_certificate_der(self, der: &[u8]) -> Result<TlsConfig, AzureError> {
+        backend::check_der(der)?;
+        Ok(self.with_root_certificate(der.to_vec()))
+    }
+
+    fn with_root_certificate(mut self, der: Vec<u8>) -> TlsConfig {
+        self.root_certificates.push(der);
+        self
+    }
+
+    /// Accepts any server certificate, expired or self-signed included.
+    /// The connections are then open to man-in-the-middle attacks: meant
+    /// for emulators and tests only.
+    pub fn danger_accept_invalid_certs(self, accept_invalid_certs: bool) -> TlsConfig {
+        TlsConfig {
+            accept_invalid_certs,
+            ..self
+        }
+    }
+
+    /// Accepts server certificates issued for another host. Meant for
+    /// emulators and tests only.
+    pub fn danger_accept_invalid_hostnames(self, accept_invalid_hostnames: bool) -> TlsConfig {
+        TlsConfig {
+            accept_invalid_hostnames,
+            ..self
+        }
+    }
+
+    pub fn root_certificates_count(&self) -> usize {
+        self.root_certificates.len()
+    }
+
+    pub fn accept_invalid_certs(&self) -> bool {
+        self.accept_invalid_certs
+    }
+
+    pub fn accept_invalid_hostnames(&self) -> bool {
+        self.accept_invalid_hostnames
+    }
+
+    /// Wraps `http` in the HTTPS connector of the TLS backend.
+    pub(crate) fn https_connector<T>(&self, http: T) -> Result<HttpsConnector<T>, AzureError> {
+        Ok(backend::https_connector(http, self)?)
+    }
+}
+
+/// The HTTPS connector of the service clients without TLS settings of
+/// their own.
+pub(crate) fn default_https_connector() -> Result<HttpsConnector<HttpConnector>, AzureError> {
+    let mut http = HttpConnector::new(4);
+    http.enforce_http(false);
+    TlsConfig::default().https_connector(http)
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("root_certificates", &self.root_certificates.len())
+            .field("accept_invalid_certs", &self.accept_invalid_certs)
+            .field("accept_invalid_hostnames", &self.accept_invalid_hostnames)
+            .finish()
+    }
+}
+
+#[cfg(all(test, any(feature = "native-tls", feature = "rustls")))]
+mod test {
+    use super::*;
+
+    const CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBejCCASGgAwIBAgIUBV6GVHAuY1rJD5X4FcAKAP1ujPYwCgYIKoZIzj0EAwIw
+EjEQMA4GA1UEAwwHYXp1cml0ZTAgFw0yNjEwMTUxNjQ4MTlaGA8yMTI2MDkyMTE2
+NDgxOVowEjEQMA4GA1UEAwwHYXp1cml0ZTBZMBMGByqGSM49AgEGCCqGSM49AwEH
+A0IABMF3me19xYS+CRwB8NxsDsQXR4IiJtuBiFDV3SEGOiQ1fr6KxzO06D2p9pqh
+x6WoUt7S+q7LcRJlIf5/ZxVmxOSjUzBRMB0GA1UdDgQWBBR1seQrAai/FuRpr1y5
+xRtVimV9NTAfBgNVHSMEGDAWgBR1seQrAai/FuRpr1y5xRtVimV9NTAPBgNVHRMB
+Af8EBTADAQH/MAoGCCqGSM49BAMCA0cAMEQCIBRoog5ue1kxVMbOZ7UwJqrhp8++
+sH84ZhMGS9HNFPncAiABfltjDYfE45MatX1mmAAGUpgoEG3g7ah2O6esbJLKkA==
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn test_tls_config() {
+        let config = TlsConfig::new()
+            .with_root_certificate_pem(CERTIFICATE.as_bytes())
+            .unwrap()
+            .danger_accept_invalid_hostnames(true);
+        assert_eq!(config.root_certificates_count(), 1);
+        assert!(!config.accept_invalid_certs());
+        assert!(config.accept_invalid_hostnames());
+        config.https_connector(HttpConnector::new(1)).unwrap();
+
+        assert!(TlsConfig::new().with_root_certificate_pem(b"not a certificate").is_err());
+    }
+
+    #[test]
+    fn test_client_tls_config() {
+        use crate::azure::core::proxy::ProxyConfig;
+        use crate::azure::storage::client::Client;
+
+        // the proxy survives the new TLS settings and the other way around
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_tls_config(TlsConfig::new().danger_accept_invalid_certs(true))
+            .unwrap()
+            .with_proxy(ProxyConfig::new("http://proxy:3128").unwrap())
+            .unwrap()
+            .with_tls_config(TlsConfig::new().with_root_certificate_pem(CERTIFICATE.as_bytes()).unwrap())
+            .unwrap();
+        assert_eq!(client.proxy().map(|proxy| proxy.host()), Some("proxy"));
+        assert_eq!(client.tls_config().root_certificates_count(), 1);
+        assert!(!client.tls_config().accept_invalid_certs());
+    }
+}
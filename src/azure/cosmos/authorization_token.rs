@@ -15,8 +15,15 @@ pub struct AuthorizationToken {
 }
 
 impl AuthorizationToken {
-    pub fn new(account: String, token_type: TokenType, base64_encoded: &str) -> Result<AuthorizationToken, base64::DecodeError> {
-        let key = base64::decode(&base64_encoded)?;
+    /// `token` is the base64 encoded primary or secondary key for
+    /// `TokenType::Master`, or the `_token` of a permission (as returned by
+    /// the service) for `TokenType::Resource`.
+    pub fn new(account: String, token_type: TokenType, token: &str) -> Result<AuthorizationToken, base64::DecodeError> {
+        let key = match token_type {
+            TokenType::Master => base64::decode(&token)?,
+            // resource tokens are already signed: they are sent as they are
+            TokenType::Resource => token.as_bytes().to_vec(),
+        };
         Ok(AuthorizationToken { account, token_type, key })
     }
 
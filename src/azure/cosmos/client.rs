@@ -1,33 +1,31 @@
 use crate::azure::core::{
+    crypto::{cosmos_authorization, HmacKey},
     errors::{check_status_extract_body, AzureError},
-    util::RequestBuilderExt,
     COMPLETE_ENCODE_SET,
 };
+use url::percent_encoding::utf8_percent_encode;
 
 use super::{
     collection::Collection,
     database::Database,
     query::Query,
-    request_response::{Document, ListCollectionsResponse, ListDatabasesResponse},
+    request_response::{Document, ListDatabasesResponse},
     requests::*,
-    AuthorizationToken, TokenType,
+    AuthorizationToken, DatabaseClient, TokenType,
 };
 
-use base64;
 use http::request::Builder as RequestBuilder;
 use hyper::{
     self,
     header::{self, HeaderValue},
     StatusCode,
 };
-use ring::{digest::SHA256, hmac};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json;
 use std::sync::Arc;
 
 use chrono;
-use hyper_tls::HttpsConnector;
-use url::percent_encoding::utf8_percent_encode;
+use crate::azure::core::tls::{default_https_connector, HttpsConnector};
 
 use futures::future::*;
 
@@ -50,13 +48,15 @@ pub(crate) mod headers {
     pub const HEADER_REQUEST_CHARGE: &str = "x-ms-request-charge"; // [f64]
     pub const HEADER_DOCUMENTDB_PARTITIONKEY: &str = "x-ms-documentdb-partitionkey"; // [String]
     pub const HEADER_DOCUMENTDB_ISQUERY: &str = "x-ms-documentdb-isquery"; // [bool]
+    pub const HEADER_DOCUMENTDB_PRE_TRIGGER_INCLUDE: &str = "x-ms-documentdb-pre-trigger-include"; // [String]
+    pub const HEADER_DOCUMENTDB_POST_TRIGGER_INCLUDE: &str = "x-ms-documentdb-post-trigger-include"; // [String]
     pub const HEADER_DOCUMENTDB_QUERY_ENABLECROSSPARTITION: &str = "x-ms-documentdb-query-enablecrosspartition"; // [bool]
     pub const HEADER_DOCUMENTDB_QUERY_PARALLELIZECROSSPARTITIONQUERY: &str = "x-ms-documentdb-query-parallelizecrosspartitionquery"; // [bool]
 }
 use self::headers::*;
 
 #[derive(Clone, Copy)]
-enum ResourceType {
+pub(crate) enum ResourceType {
     Databases,
     Collections,
     Documents,
@@ -70,7 +70,7 @@ pub struct Client {
 
 impl Client {
     pub fn new(auth_token: AuthorizationToken) -> Result<Client, AzureError> {
-        let client = hyper::Client::builder().build(HttpsConnector::new(4)?);
+        let client = hyper::Client::builder().build(default_https_connector()?);
 
         Ok(Client {
             hyper_client: Arc::new(client),
@@ -82,6 +82,17 @@ impl Client {
         self.auth_token = at;
     }
 
+    /// Returns a client bound to the database. Chain `collection` to
+    /// reach a collection: `client.database("db").collection("coll")`.
+    pub fn database<'a>(&'a self, database_name: &str) -> DatabaseClient<'a> {
+        DatabaseClient::new(self, database_name)
+    }
+
+    #[inline]
+    pub(crate) fn hyper_client(&self) -> &Arc<hyper::Client<HttpsConnector<hyper::client::HttpConnector>>> {
+        &self.hyper_client
+    }
+
     fn list_databases_create_request(&self) -> Result<hyper::client::ResponseFuture, AzureError> {
         // No specific headers are required, list databases only needs standard headers
         // which will be provied by perform_request. This is handled by passing an
@@ -112,36 +123,8 @@ impl Client {
         })
     }
 
-    #[inline]
-    fn list_collections_create_request(&self, database_name: &str) -> Result<hyper::client::ResponseFuture, AzureError> {
-        // No specific headers are required, list collections only needs standard headers
-        // which will be provied by perform_request. This is handled by passing an
-        // empty closure.
-        let request = self
-            .prepare_request(
-                &format!("dbs/{}/colls", database_name),
-                hyper::Method::GET,
-                ResourceType::Collections,
-            )
-            .body(hyper::Body::empty())?;
-
-        trace!("request prepared");
-
-        Ok(self.hyper_client.request(request))
-    }
-
     pub fn list_collections(&self, database_name: &str) -> impl Future<Item = Vec<Collection>, Error = AzureError> {
-        trace!("list_collections called");
-
-        let req = self.list_collections_create_request(database_name);
-
-        done(req).from_err().and_then(move |future_response| {
-            check_status_extract_body(future_response, StatusCode::OK).and_then(move |body| {
-                done(serde_json::from_str::<ListCollectionsResponse>(&body))
-                    .from_err()
-                    .and_then(|database_response| ok(database_response.collections))
-            })
-        })
+        self.database(database_name).list_collections()
     }
 
     #[inline]
@@ -173,113 +156,16 @@ impl Client {
         })
     }
 
-    #[inline]
-    fn get_database_create_request(&self, database_name: &str) -> Result<hyper::client::ResponseFuture, AzureError> {
-        // No specific headers are required, get database only needs standard headers
-        // which will be provied by perform_request
-        let request = self
-            .prepare_request(&format!("dbs/{}", database_name), hyper::Method::GET, ResourceType::Databases)
-            .body(hyper::Body::empty())?;
-
-        trace!("request prepared");
-
-        Ok(self.hyper_client.request(request))
-    }
-
     pub fn get_database(&self, database_name: &str) -> impl Future<Item = Database, Error = AzureError> {
-        trace!("get_database called (database_name == {})", database_name);
-
-        let req = self.get_database_create_request(database_name);
-
-        done(req).from_err().and_then(move |future_response| {
-            check_status_extract_body(future_response, StatusCode::OK)
-                .and_then(move |body| done(serde_json::from_str::<Database>(&body)).from_err())
-        })
-    }
-
-    #[inline]
-    fn delete_database_create_request(&self, database_name: &str) -> Result<hyper::client::ResponseFuture, AzureError> {
-        // No specific headers are required, delete database only needs standard headers
-        // which will be provied by perform_request
-        let request = self
-            .prepare_request(&format!("dbs/{}", database_name), hyper::Method::DELETE, ResourceType::Databases)
-            .body(hyper::Body::empty())?;
-
-        trace!("request prepared");
-
-        Ok(self.hyper_client.request(request))
+        self.database(database_name).get()
     }
 
     pub fn delete_database(&self, database_name: &str) -> impl Future<Item = (), Error = AzureError> {
-        trace!("delete_database called (database_name == {})", database_name);
-
-        let req = self.delete_database_create_request(database_name);
-
-        done(req)
-            .from_err()
-            .and_then(move |future_response| check_status_extract_body(future_response, StatusCode::NO_CONTENT).and_then(|_| ok(())))
-    }
-
-    #[inline]
-    fn get_collection_create_request(
-        &self,
-        database_name: &str,
-        collection_name: &str,
-    ) -> Result<hyper::client::ResponseFuture, AzureError> {
-        // No specific headers are required, get database only needs standard headers
-        // which will be provied by perform_request
-        let request = self
-            .prepare_request(
-                &format!("dbs/{}/colls/{}", database_name, collection_name),
-                hyper::Method::GET,
-                ResourceType::Collections,
-            )
-            .body(hyper::Body::empty())?;
-
-        trace!("request prepared");
-
-        Ok(self.hyper_client.request(request))
+        self.database(database_name).delete()
     }
 
     pub fn get_collection(&self, database_name: &str, collection_name: &str) -> impl Future<Item = Collection, Error = AzureError> {
-        trace!(
-            "get_collection called (database_name == {}, collection_name == {})",
-            database_name,
-            collection_name
-        );
-
-        let req = self.get_collection_create_request(database_name, collection_name);
-
-        done(req).from_err().and_then(move |future_response| {
-            check_status_extract_body(future_response, StatusCode::OK)
-                .and_then(move |body| done(serde_json::from_str::<Collection>(&body)).from_err())
-        })
-    }
-
-    #[inline]
-    fn create_collection_create_request(
-        &self,
-        database_name: &str,
-        required_throughput: u64,
-        collection: &Collection,
-    ) -> Result<hyper::client::ResponseFuture, AzureError> {
-        // Headers added as per
-        // https://docs.microsoft.com/en-us/rest/api/documentdb/create-a-collection
-        // Standard headers (auth and version) will be provied by perform_request
-        let collection_serialized = serde_json::to_string(collection)?;
-        trace!("collection_serialized == {}", collection_serialized);
-
-        let mut request = self.prepare_request(
-            &format!("dbs/{}/colls", database_name),
-            hyper::Method::POST,
-            ResourceType::Collections,
-        );
-        request.header_formatted(HEADER_OFFER_THROUGHPUT, required_throughput);
-        let request = request.body(collection_serialized.into())?;
-
-        trace!("request prepared");
-
-        Ok(self.hyper_client.request(request))
+        self.database(database_name).collection(collection_name).get()
     }
 
     pub fn create_collection(
@@ -288,55 +174,11 @@ impl Client {
         required_throughput: u64,
         collection: &Collection,
     ) -> impl Future<Item = Collection, Error = AzureError> {
-        trace!(
-            "create_collection(database_name == {:?}, \
-             required_throughput == {:?}, collection == {:?} called",
-            database_name,
-            required_throughput,
-            collection
-        );
-
-        let req = self.create_collection_create_request(database_name, required_throughput, collection);
-
-        done(req).from_err().and_then(move |future_response| {
-            check_status_extract_body(future_response, StatusCode::CREATED)
-                .and_then(move |body| done(serde_json::from_str::<Collection>(&body)).from_err())
-        })
-    }
-
-    #[inline]
-    fn delete_collection_create_request(
-        &self,
-        database_name: &str,
-        collection_name: &str,
-    ) -> Result<hyper::client::ResponseFuture, AzureError> {
-        // No specific headers are required.
-        // Standard headers (auth and version) will be provied by perform_request
-        let request = self
-            .prepare_request(
-                &format!("dbs/{}/colls/{}", database_name, collection_name),
-                hyper::Method::DELETE,
-                ResourceType::Collections,
-            )
-            .body(hyper::Body::empty())?;
-
-        trace!("request prepared");
-
-        Ok(self.hyper_client.request(request))
+        self.database(database_name).create_collection(required_throughput, collection)
     }
 
     pub fn delete_collection(&self, database_name: &str, collection_name: &str) -> impl Future<Item = (), Error = AzureError> {
-        trace!(
-            "delete_collection called (database_name == {}, collection_name == {}",
-            database_name,
-            collection_name
-        );
-
-        let req = self.delete_collection_create_request(database_name, collection_name);
-
-        done(req)
-            .from_err()
-            .and_then(move |future_response| check_status_extract_body(future_response, StatusCode::NO_CONTENT).and_then(|_| ok(())))
+        self.database(database_name).collection(collection_name).delete()
     }
 
     #[inline]
@@ -374,17 +216,6 @@ impl Client {
         })
     }
 
-    #[inline]
-    fn create_document_as_str_create_request(&self, database: &str, collection: &str) -> RequestBuilder {
-        let uri = format!("dbs/{}/colls/{}/docs", database, collection);
-
-        let request = self.prepare_request(&uri, hyper::Method::POST, ResourceType::Documents);
-
-        trace!("request prepared");
-
-        request
-    }
-
     pub fn create_document_as_str<T, S1, S2, S3>(&self, database: S1, collection: S2, document: S3) -> CreateDocumentRequest
     where
         T: Serialize,
@@ -392,19 +223,9 @@ impl Client {
         S2: AsRef<str>,
         S3: Into<String>,
     {
-        let database = database.as_ref();
-        let collection = collection.as_ref();
-        let document = document.into();
-
-        trace!(
-            "create_document_as_str called(database == {}, collection == {}, document = {}",
-            database,
-            collection,
-            document,
-        );
-
-        let req = self.create_document_as_str_create_request(database, collection);
-        CreateDocumentRequest::new(self.hyper_client.clone(), req, Ok(document))
+        self.database(database.as_ref())
+            .collection(collection.as_ref())
+            .create_document_as_str(document)
     }
 
     pub fn create_document<T, S1, S2>(&self, database: S1, collection: S2, document: &T) -> CreateDocumentRequest
@@ -413,17 +234,9 @@ impl Client {
         S1: AsRef<str>,
         S2: AsRef<str>,
     {
-        let db = database.as_ref();
-        let coll = collection.as_ref();
-        let document_serialized = serde_json::to_string(document);
-        trace!(
-            "create_document_as called(database == {}, collection == {}, document = {:?}",
-            db,
-            coll,
-            document_serialized
-        );
-        let req = self.create_document_as_str_create_request(db, coll);
-        CreateDocumentRequest::new(self.hyper_client.clone(), req, document_serialized)
+        self.database(database.as_ref())
+            .collection(collection.as_ref())
+            .create_document(document)
     }
 
     pub fn delete_document<D: AsRef<str>, C: AsRef<str>, Dc: AsRef<str>>(
@@ -432,22 +245,9 @@ impl Client {
         collection_id: C,
         document_id: Dc,
     ) -> DeleteDocumentRequest {
-        trace!(
-            "delete_document called (db_id == {}, collection_id == {}, doc_id = {}",
-            database_id.as_ref(),
-            collection_id.as_ref(),
-            document_id.as_ref()
-        );
-
-        let uri = format!(
-            "dbs/{}/colls/{}/docs/{}",
-            database_id.as_ref(),
-            collection_id.as_ref(),
-            document_id.as_ref()
-        );
-
-        let req = self.prepare_request(&uri, hyper::Method::DELETE, ResourceType::Documents);
-        DeleteDocumentRequest::new(self.hyper_client.clone(), req)
+        self.database(database_id.as_ref())
+            .collection(collection_id.as_ref())
+            .delete_document(document_id)
     }
 
     pub fn replace_document<D: AsRef<str>, C: AsRef<str>, T: Serialize + DeserializeOwned>(
@@ -456,38 +256,13 @@ impl Client {
         collection_id: C,
         document: &Document<T>,
     ) -> ReplaceDocumentRequest<T> {
-        let document_serialized = serde_json::to_string(&document.entity);
-
-        trace!(
-            "replace_document called(db_id == {}, collection == {}, document == {:?}",
-            database_id.as_ref(),
-            collection_id.as_ref(),
-            document_serialized,
-        );
-
-        let req = self.prepare_request_with_resource_link(
-            &document.document_attributes._self,
-            hyper::Method::PUT,
-            ResourceType::Documents,
-            &document.document_attributes.rid.to_lowercase(),
-        );
-
-        ReplaceDocumentRequest::new(self.hyper_client.clone(), req, document_serialized)
+        self.database(database_id.as_ref())
+            .collection(collection_id.as_ref())
+            .replace_document(document)
     }
 
     pub fn list_documents<S1: AsRef<str>, S2: AsRef<str>>(&self, database: S1, collection: S2) -> ListDocumentsRequest {
-        let database = database.as_ref();
-        let collection = collection.as_ref();
-
-        trace!("list_documents called(database == {}, collection == {}", database, collection);
-
-        let req = self.prepare_request(
-            &format!("dbs/{}/colls/{}/docs", database, collection),
-            hyper::Method::GET,
-            ResourceType::Documents,
-        );
-
-        ListDocumentsRequest::new(self.hyper_client.clone(), req)
+        self.database(database.as_ref()).collection(collection.as_ref()).list_documents()
     }
 
     pub fn get_document<S1, S2, S3>(&self, database: S1, collection: S2, document_id: S3) -> GetDocumentRequest
@@ -496,17 +271,9 @@ impl Client {
         S2: AsRef<str>,
         S3: AsRef<str>,
     {
-        let db = database.as_ref();
-        let coll = collection.as_ref();
-        let doc_id = document_id.as_ref();
-
-        let req = self.prepare_request(
-            &format!("dbs/{}/colls/{}/docs/{}", db, coll, doc_id),
-            hyper::Method::GET,
-            ResourceType::Documents,
-        );
-
-        GetDocumentRequest::new(self.hyper_client.clone(), req)
+        self.database(database.as_ref())
+            .collection(collection.as_ref())
+            .get_document(document_id)
     }
 
     pub fn query_documents<'b, S1: AsRef<str>, S2: AsRef<str>, Q: AsRef<Query<'b>>>(
@@ -515,16 +282,9 @@ impl Client {
         collection: S2,
         query: Q,
     ) -> QueryDocumentRequest {
-        let database = database.as_ref();
-        let collection = collection.as_ref();
-
-        let req = self.prepare_request(
-            &format!("dbs/{}/colls/{}/docs", database, collection),
-            hyper::Method::POST,
-            ResourceType::Documents,
-        );
-        let query_json = serde_json::to_string(query.as_ref());
-        QueryDocumentRequest::new(self.hyper_client.clone(), req, query_json)
+        self.database(database.as_ref())
+            .collection(collection.as_ref())
+            .query_documents(query)
     }
 
     pub fn execute_stored_procedure<S1, S2, S3, I>(
@@ -540,22 +300,13 @@ impl Client {
         S3: AsRef<str>,
         I: Serialize,
     {
-        let req = self.prepare_request(
-            &format!(
-                "dbs/{}/colls/{}/sprocs/{}",
-                database.as_ref(),
-                collection.as_ref(),
-                sproc_name.as_ref()
-            ),
-            hyper::Method::POST,
-            ResourceType::StoredProcedures,
-        );
-        let input_json = serde_json::to_string(&input);
-        ExecuteStoredProcedureRequest::new(self.hyper_client.clone(), req, input_json)
+        self.database(database.as_ref())
+            .collection(collection.as_ref())
+            .execute_stored_procedure(sproc_name, input)
     }
 
     #[inline]
-    fn prepare_request(&self, uri_path: &str, http_method: hyper::Method, resource_type: ResourceType) -> RequestBuilder {
+    pub(crate) fn prepare_request(&self, uri_path: &str, http_method: hyper::Method, resource_type: ResourceType) -> RequestBuilder {
         let time = format!("{}", chrono::Utc::now().format(TIME_FORMAT));
 
         let auth = {
@@ -566,7 +317,7 @@ impl Client {
     }
 
     #[inline]
-    fn prepare_request_with_resource_link(
+    pub(crate) fn prepare_request_with_resource_link(
         &self,
         uri_path: &str,
         http_method: hyper::Method,
@@ -601,28 +352,18 @@ fn generate_authorization(
     resource_link: &str,
     time: &str,
 ) -> String {
-    let string_to_sign = string_to_sign(http_method, resource_type, resource_link, time);
-    trace!("generate_authorization::string_to_sign == {:?}", string_to_sign);
-
-    let str_unencoded = format!(
-        "type={}&ver={}&sig={}",
-        match auth_token.token_type() {
-            TokenType::Master => "master",
-            TokenType::Resource => "resource",
-        },
-        VERSION,
-        encode_str_to_sign(&string_to_sign, auth_token)
-    );
-
-    trace!("generate_authorization::str_unencoded == {:?}", str_unencoded);
+    match auth_token.token_type() {
+        TokenType::Master => {
+            let string_to_sign = string_to_sign(http_method, resource_type, resource_link, time);
+            trace!("generate_authorization::string_to_sign == {:?}", string_to_sign);
 
-    utf8_percent_encode(&str_unencoded, COMPLETE_ENCODE_SET).collect::<String>()
-}
-
-fn encode_str_to_sign(str_to_sign: &str, auth_token: &AuthorizationToken) -> String {
-    let key = hmac::SigningKey::new(&SHA256, auth_token.key());
-    let sig = hmac::sign(&key, str_to_sign.as_bytes());
-    base64::encode(sig.as_ref())
+            cosmos_authorization(&HmacKey::new(auth_token.key()), "master", VERSION, &string_to_sign)
+        }
+        TokenType::Resource => {
+            let token = String::from_utf8_lossy(auth_token.key());
+            utf8_percent_encode(&token, COMPLETE_ENCODE_SET).collect::<String>()
+        }
+    }
 }
 
 fn string_to_sign(http_method: &hyper::Method, rt: ResourceType, resource_link: &str, time: &str) -> String {
@@ -766,6 +507,25 @@ mon, 01 jan 1900 01:00:00 gmt
         );
     }
 
+    #[test]
+    fn generate_authorization_resource() {
+        let auth_token = AuthorizationToken::new(
+            "mindflavor".to_owned(),
+            TokenType::Resource,
+            "type=resource&ver=1&sig=a+b/c==;d/e==;",
+        )
+        .unwrap();
+
+        let ret = generate_authorization(
+            &auth_token,
+            &hyper::Method::GET,
+            ResourceType::Documents,
+            "dbs/ToDoList/colls/Items/docs/1",
+            "Thu, 27 Apr 2017 00:51:12 GMT",
+        );
+        assert_eq!(ret, "type%3Dresource%26ver%3D1%26sig%3Da%2Bb%2Fc%3D%3D%3Bd%2Fe%3D%3D%3B");
+    }
+
     #[test]
     fn generate_resource_link_00() {
         assert_eq!(generate_resource_link("dbs/second"), "dbs/second");
@@ -0,0 +1,211 @@
+use crate::azure::core::errors::{check_status_extract_body, AzureError};
+use crate::azure::cosmos::client::{Client, ResourceType};
+use crate::azure::cosmos::collection::Collection;
+use crate::azure::cosmos::query::Query;
+use crate::azure::cosmos::request_response::Document;
+use crate::azure::cosmos::requests::*;
+use crate::azure::cosmos::DatabaseClient;
+use futures::future::*;
+use hyper::{self, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json;
+
+/// Client bound to a collection. It carries the database and collection
+/// resource links so the document and stored procedure operations do not
+/// have to specify them again. Get one with
+/// `Client::database("db").collection("coll")`.
+#[derive(Clone)]
+pub struct CollectionClient<'a> {
+    database_client: DatabaseClient<'a>,
+    collection_name: String,
+}
+
+impl<'a> CollectionClient<'a> {
+    pub(crate) fn new(database_client: DatabaseClient<'a>, collection_name: &str) -> CollectionClient<'a> {
+        CollectionClient {
+            database_client,
+            collection_name: collection_name.to_owned(),
+        }
+    }
+
+    pub fn client(&self) -> &'a Client {
+        self.database_client.client()
+    }
+
+    pub fn database_client(&self) -> &DatabaseClient<'a> {
+        &self.database_client
+    }
+
+    pub fn database_name(&self) -> &str {
+        self.database_client.database_name()
+    }
+
+    pub fn collection_name(&self) -> &str {
+        &self.collection_name
+    }
+
+    /// The resource link of the collection, as used in the authorization
+    /// token (for example `dbs/mydb/colls/mycoll`).
+    pub fn resource_link(&self) -> String {
+        self.database_client.uri_path(&format!("/colls/{}", self.collection_name))
+    }
+
+    pub fn get(&self) -> impl Future<Item = Collection, Error = AzureError> {
+        trace!(
+            "get_collection called (database_name == {}, collection_name == {})",
+            self.database_name(),
+            self.collection_name
+        );
+
+        let req = self.request("", hyper::Method::GET, ResourceType::Collections);
+
+        done(req).from_err().and_then(move |future_response| {
+            check_status_extract_body(future_response, StatusCode::OK)
+                .and_then(move |body| done(serde_json::from_str::<Collection>(&body)).from_err())
+        })
+    }
+
+    pub fn delete(&self) -> impl Future<Item = (), Error = AzureError> {
+        trace!(
+            "delete_collection called (database_name == {}, collection_name == {}",
+            self.database_name(),
+            self.collection_name
+        );
+
+        let req = self.request("", hyper::Method::DELETE, ResourceType::Collections);
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_body(future_response, StatusCode::NO_CONTENT).and_then(|_| ok(())))
+    }
+
+    pub fn create_document_as_str<S: Into<String>>(&self, document: S) -> CreateDocumentRequest {
+        let document = document.into();
+        trace!(
+            "create_document_as_str called(collection == {}, document = {}",
+            self.resource_link(),
+            document
+        );
+
+        let req = self.prepare_request("/docs", hyper::Method::POST, ResourceType::Documents);
+        CreateDocumentRequest::new(self.client().hyper_client().clone(), req, Ok(document))
+    }
+
+    pub fn create_document<T: Serialize>(&self, document: &T) -> CreateDocumentRequest {
+        let document_serialized = serde_json::to_string(document);
+        trace!(
+            "create_document called(collection == {}, document = {:?}",
+            self.resource_link(),
+            document_serialized
+        );
+
+        let req = self.prepare_request("/docs", hyper::Method::POST, ResourceType::Documents);
+        CreateDocumentRequest::new(self.client().hyper_client().clone(), req, document_serialized)
+    }
+
+    pub fn delete_document<S: AsRef<str>>(&self, document_id: S) -> DeleteDocumentRequest {
+        trace!(
+            "delete_document called (collection == {}, doc_id = {}",
+            self.resource_link(),
+            document_id.as_ref()
+        );
+
+        let req = self.prepare_request(
+            &format!("/docs/{}", document_id.as_ref()),
+            hyper::Method::DELETE,
+            ResourceType::Documents,
+        );
+        DeleteDocumentRequest::new(self.client().hyper_client().clone(), req)
+    }
+
+    /// Replaces the document. The document is addressed by its `_self`
+    /// link, not by the collection path.
+    pub fn replace_document<T: Serialize + DeserializeOwned>(&self, document: &Document<T>) -> ReplaceDocumentRequest<T> {
+        let document_serialized = serde_json::to_string(&document.entity);
+
+        trace!(
+            "replace_document called(collection == {}, document == {:?}",
+            self.resource_link(),
+            document_serialized,
+        );
+
+        let req = self.client().prepare_request_with_resource_link(
+            &document.document_attributes._self,
+            hyper::Method::PUT,
+            ResourceType::Documents,
+            &document.document_attributes.rid.to_lowercase(),
+        );
+
+        ReplaceDocumentRequest::new(self.client().hyper_client().clone(), req, document_serialized)
+    }
+
+    pub fn list_documents(&self) -> ListDocumentsRequest {
+        trace!("list_documents called(collection == {}", self.resource_link());
+
+        let req = self.prepare_request("/docs", hyper::Method::GET, ResourceType::Documents);
+        ListDocumentsRequest::new(self.client().hyper_client().clone(), req)
+    }
+
+    pub fn get_document<S: AsRef<str>>(&self, document_id: S) -> GetDocumentRequest {
+        let req = self.prepare_request(
+            &format!("/docs/{}", document_id.as_ref()),
+            hyper::Method::GET,
+            ResourceType::Documents,
+        );
+        GetDocumentRequest::new(self.client().hyper_client().clone(), req)
+    }
+
+    pub fn query_documents<'b, Q: AsRef<Query<'b>>>(&self, query: Q) -> QueryDocumentRequest {
+        let req = self.prepare_request("/docs", hyper::Method::POST, ResourceType::Documents);
+        let query_json = serde_json::to_string(query.as_ref());
+        QueryDocumentRequest::new(self.client().hyper_client().clone(), req, query_json)
+    }
+
+    pub fn execute_stored_procedure<S: AsRef<str>, I: Serialize>(&self, sproc_name: S, input: I) -> ExecuteStoredProcedureRequest {
+        let req = self.prepare_request(
+            &format!("/sprocs/{}", sproc_name.as_ref()),
+            hyper::Method::POST,
+            ResourceType::StoredProcedures,
+        );
+        let input_json = serde_json::to_string(&input);
+        ExecuteStoredProcedureRequest::new(self.client().hyper_client().clone(), req, input_json)
+    }
+
+    fn prepare_request(&self, suffix: &str, http_method: hyper::Method, resource_type: ResourceType) -> http::request::Builder {
+        let uri_path = format!("{}{}", self.resource_link(), suffix);
+        self.client().prepare_request(&uri_path, http_method, resource_type)
+    }
+
+    fn request(
+        &self,
+        suffix: &str,
+        http_method: hyper::Method,
+        resource_type: ResourceType,
+    ) -> Result<hyper::client::ResponseFuture, AzureError> {
+        let request = self
+            .prepare_request(suffix, http_method, resource_type)
+            .body(hyper::Body::empty())?;
+
+        trace!("request prepared");
+
+        Ok(self.client().hyper_client().request(request))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::azure::cosmos::{AuthorizationToken, Client, TokenType};
+
+    #[test]
+    fn test_resource_links() {
+        let auth_token = AuthorizationToken::new("account".to_owned(), TokenType::Master, "AAAA").unwrap();
+        let client = Client::new(auth_token).unwrap();
+
+        let database = client.database("db");
+        assert_eq!(database.resource_link(), "dbs/db");
+
+        let collection = database.collection("coll");
+        assert_eq!(collection.database_name(), "db");
+        assert_eq!(collection.resource_link(), "dbs/db/colls/coll");
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+
+/// Opaque position of a query or change feed. Its content is defined by
+/// the service: just hand it back to continue from where the previous
+/// response stopped.
+///
+/// It serializes to (and from) a plain string so the paging state can be
+/// persisted, for example to resume after a restart or to let another
+/// worker continue.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ContinuationToken(String);
+
+impl ContinuationToken {
+    pub(crate) fn new(token: String) -> ContinuationToken {
+        ContinuationToken(token)
+    }
+}
+
+impl AsRef<str> for ContinuationToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ContinuationToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let token = ContinuationToken::new(r#"{"token":"+RID:~abc#RT:1#TRC:2","range":{"min":"","max":"FF"}}"#.to_owned());
+
+        let serialized = serde_json::to_string(&token).unwrap();
+        assert_eq!(
+            serialized,
+            r#""{\"token\":\"+RID:~abc#RT:1#TRC:2\",\"range\":{\"min\":\"\",\"max\":\"FF\"}}""#
+        );
+
+        let deserialized: ContinuationToken = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, token);
+    }
+}
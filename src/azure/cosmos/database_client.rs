@@ -0,0 +1,142 @@
+use crate::azure::core::errors::{check_status_extract_body, AzureError};
+use crate::azure::core::util::RequestBuilderExt;
+use crate::azure::cosmos::client::headers::HEADER_OFFER_THROUGHPUT;
+use crate::azure::cosmos::client::{Client, ResourceType};
+use crate::azure::cosmos::collection::Collection;
+use crate::azure::cosmos::database::Database;
+use crate::azure::cosmos::request_response::ListCollectionsResponse;
+use crate::azure::cosmos::CollectionClient;
+use futures::future::*;
+use hyper::{self, StatusCode};
+use serde_json;
+
+/// Client bound to a database. It carries the database resource link so
+/// the operations do not have to specify it again. Get one with
+/// `Client::database`.
+#[derive(Clone)]
+pub struct DatabaseClient<'a> {
+    client: &'a Client,
+    database_name: String,
+}
+
+impl<'a> DatabaseClient<'a> {
+    pub(crate) fn new(client: &'a Client, database_name: &str) -> DatabaseClient<'a> {
+        DatabaseClient {
+            client,
+            database_name: database_name.to_owned(),
+        }
+    }
+
+    pub fn client(&self) -> &'a Client {
+        self.client
+    }
+
+    pub fn database_name(&self) -> &str {
+        &self.database_name
+    }
+
+    /// The resource link of the database, as used in the authorization
+    /// token (for example `dbs/mydb`).
+    pub fn resource_link(&self) -> String {
+        format!("dbs/{}", self.database_name)
+    }
+
+    pub fn collection(&self, collection_name: &str) -> CollectionClient<'a> {
+        CollectionClient::new(self.clone(), collection_name)
+    }
+
+    pub fn get(&self) -> impl Future<Item = Database, Error = AzureError> {
+        trace!("get_database called (database_name == {})", self.database_name);
+
+        let req = self.request("", hyper::Method::GET, ResourceType::Databases, hyper::Body::empty());
+
+        done(req).from_err().and_then(move |future_response| {
+            check_status_extract_body(future_response, StatusCode::OK)
+                .and_then(move |body| done(serde_json::from_str::<Database>(&body)).from_err())
+        })
+    }
+
+    pub fn delete(&self) -> impl Future<Item = (), Error = AzureError> {
+        trace!("delete_database called (database_name == {})", self.database_name);
+
+        let req = self.request("", hyper::Method::DELETE, ResourceType::Databases, hyper::Body::empty());
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_body(future_response, StatusCode::NO_CONTENT).and_then(|_| ok(())))
+    }
+
+    pub fn list_collections(&self) -> impl Future<Item = Vec<Collection>, Error = AzureError> {
+        trace!("list_collections called (database_name == {})", self.database_name);
+
+        let req = self.request("/colls", hyper::Method::GET, ResourceType::Collections, hyper::Body::empty());
+
+        done(req).from_err().and_then(move |future_response| {
+            check_status_extract_body(future_response, StatusCode::OK).and_then(move |body| {
+                done(serde_json::from_str::<ListCollectionsResponse>(&body))
+                    .from_err()
+                    .and_then(|database_response| ok(database_response.collections))
+            })
+        })
+    }
+
+    pub fn create_collection(
+        &self,
+        required_throughput: u64,
+        collection: &Collection,
+    ) -> impl Future<Item = Collection, Error = AzureError> {
+        trace!(
+            "create_collection(database_name == {:?}, \
+             required_throughput == {:?}, collection == {:?} called",
+            self.database_name,
+            required_throughput,
+            collection
+        );
+
+        // Headers added as per
+        // https://docs.microsoft.com/en-us/rest/api/documentdb/create-a-collection
+        let req = serde_json::to_string(collection)
+            .map_err(AzureError::from)
+            .and_then(|collection_serialized| {
+                trace!("collection_serialized == {}", collection_serialized);
+
+                let mut request = self
+                    .client
+                    .prepare_request(&self.uri_path("/colls"), hyper::Method::POST, ResourceType::Collections);
+                request.header_formatted(HEADER_OFFER_THROUGHPUT, required_throughput);
+                let request = request.body(collection_serialized.into())?;
+
+                trace!("request prepared");
+
+                Ok(self.client.hyper_client().request(request))
+            });
+
+        done(req).from_err().and_then(move |future_response| {
+            check_status_extract_body(future_response, StatusCode::CREATED)
+                .and_then(move |body| done(serde_json::from_str::<Collection>(&body)).from_err())
+        })
+    }
+
+    // every path below the database is built here so the resource link
+    // is computed in a single place
+    pub(crate) fn uri_path(&self, suffix: &str) -> String {
+        format!("{}{}", self.resource_link(), suffix)
+    }
+
+    fn request(
+        &self,
+        suffix: &str,
+        http_method: hyper::Method,
+        resource_type: ResourceType,
+        body: hyper::Body,
+    ) -> Result<hyper::client::ResponseFuture, AzureError> {
+        let request = self
+            .client
+            .prepare_request(&self.uri_path(suffix), http_method, resource_type)
+            .body(body)?;
+
+        trace!("request prepared");
+
+        Ok(self.client.hyper_client().request(request))
+    }
+}
@@ -1,6 +1,9 @@
 mod authorization_token;
 mod client;
+mod collection_client;
+mod continuation_token;
 pub mod database;
+mod database_client;
 mod requests;
 
 pub mod request_response;
@@ -9,10 +12,16 @@ pub mod collection;
 pub mod document;
 mod partition_key;
 pub mod query;
+pub mod query_builder;
+mod request_options;
 
 pub use self::authorization_token::*;
 pub use self::client::*;
+pub use self::collection_client::CollectionClient;
+pub use self::continuation_token::ContinuationToken;
+pub use self::database_client::DatabaseClient;
 pub use self::partition_key::*;
+pub use self::request_options::RequestOptions;
 pub use self::requests::*;
 
 use crate::azure::core::enumerations;
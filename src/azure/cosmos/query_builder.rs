@@ -0,0 +1,315 @@
+use crate::azure::cosmos::query::{Param, Query};
+use serde_json::Value;
+
+// alias of the collection in the generated queries
+const ROOT: &str = "c";
+
+/// Sort direction of an `ORDER BY` clause.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Order {
+    Ascending,
+    Descending,
+}
+
+/// Filter of a `QueryBuilder`. Fields are the serde (JSON) names of the
+/// document properties: nested properties are separated by dots, as in
+/// `address.city`. Values are never written into the query text, they
+/// are always passed as parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Eq(String, Value),
+    Ne(String, Value),
+    Lt(String, Value),
+    Le(String, Value),
+    Gt(String, Value),
+    Ge(String, Value),
+    /// The field is one of the values.
+    In(String, Vec<Value>),
+    /// The string field starts with the value.
+    StartsWith(String, Value),
+    /// The string field contains the value.
+    Contains(String, Value),
+    IsDefined(String),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    pub fn eq<F: Into<String>, V: Into<Value>>(field: F, value: V) -> Condition {
+        Condition::Eq(field.into(), value.into())
+    }
+
+    pub fn ne<F: Into<String>, V: Into<Value>>(field: F, value: V) -> Condition {
+        Condition::Ne(field.into(), value.into())
+    }
+
+    pub fn lt<F: Into<String>, V: Into<Value>>(field: F, value: V) -> Condition {
+        Condition::Lt(field.into(), value.into())
+    }
+
+    pub fn le<F: Into<String>, V: Into<Value>>(field: F, value: V) -> Condition {
+        Condition::Le(field.into(), value.into())
+    }
+
+    pub fn gt<F: Into<String>, V: Into<Value>>(field: F, value: V) -> Condition {
+        Condition::Gt(field.into(), value.into())
+    }
+
+    pub fn ge<F: Into<String>, V: Into<Value>>(field: F, value: V) -> Condition {
+        Condition::Ge(field.into(), value.into())
+    }
+
+    pub fn is_in<F: Into<String>, I, V>(field: F, values: I) -> Condition
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Value>,
+    {
+        Condition::In(field.into(), values.into_iter().map(Into::into).collect())
+    }
+
+    pub fn starts_with<F: Into<String>, V: Into<Value>>(field: F, value: V) -> Condition {
+        Condition::StartsWith(field.into(), value.into())
+    }
+
+    pub fn contains<F: Into<String>, V: Into<Value>>(field: F, value: V) -> Condition {
+        Condition::Contains(field.into(), value.into())
+    }
+
+    pub fn is_defined<F: Into<String>>(field: F) -> Condition {
+        Condition::IsDefined(field.into())
+    }
+
+    pub fn and(self, other: Condition) -> Condition {
+        Condition::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Condition) -> Condition {
+        Condition::Or(Box::new(self), Box::new(other))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Condition {
+        Condition::Not(Box::new(self))
+    }
+
+    fn compile(&self, parameters: &mut Vec<(String, Value)>) -> String {
+        match self {
+            Condition::Eq(field, value) => binary(field, "=", value, parameters),
+            Condition::Ne(field, value) => binary(field, "!=", value, parameters),
+            Condition::Lt(field, value) => binary(field, "<", value, parameters),
+            Condition::Le(field, value) => binary(field, "<=", value, parameters),
+            Condition::Gt(field, value) => binary(field, ">", value, parameters),
+            Condition::Ge(field, value) => binary(field, ">=", value, parameters),
+            Condition::In(field, values) => {
+                let name = push_parameter(Value::Array(values.clone()), parameters);
+                format!("ARRAY_CONTAINS({}, {})", name, field_path(field))
+            }
+            Condition::StartsWith(field, value) => {
+                let name = push_parameter(value.clone(), parameters);
+                format!("STARTSWITH({}, {})", field_path(field), name)
+            }
+            Condition::Contains(field, value) => {
+                let name = push_parameter(value.clone(), parameters);
+                format!("CONTAINS({}, {})", field_path(field), name)
+            }
+            Condition::IsDefined(field) => format!("IS_DEFINED({})", field_path(field)),
+            Condition::And(left, right) => format!("({} AND {})", left.compile(parameters), right.compile(parameters)),
+            Condition::Or(left, right) => format!("({} OR {})", left.compile(parameters), right.compile(parameters)),
+            Condition::Not(condition) => format!("NOT ({})", condition.compile(parameters)),
+        }
+    }
+}
+
+/// Builds a `SELECT` over a collection from field names and values
+/// instead of string concatenation. The values end up in the query
+/// parameters and the field names are quoted when needed, so user input
+/// cannot alter the query.
+#[derive(Debug, Clone, Default)]
+pub struct QueryBuilder {
+    fields: Vec<String>,
+    condition: Option<Condition>,
+    order_by: Vec<(String, Order)>,
+    top: Option<u64>,
+}
+
+impl QueryBuilder {
+    pub fn new() -> QueryBuilder {
+        QueryBuilder::default()
+    }
+
+    /// Restricts the returned properties. Without it the whole
+    /// documents are returned.
+    pub fn select<F: AsRef<str>>(mut self, fields: &[F]) -> QueryBuilder {
+        self.fields.extend(fields.iter().map(|field| field.as_ref().to_owned()));
+        self
+    }
+
+    /// Adds a filter. Calling it more than once combines the conditions
+    /// with `AND`.
+    pub fn filter(mut self, condition: Condition) -> QueryBuilder {
+        self.condition = Some(match self.condition.take() {
+            Some(previous) => previous.and(condition),
+            None => condition,
+        });
+        self
+    }
+
+    pub fn order_by<F: Into<String>>(mut self, field: F, order: Order) -> QueryBuilder {
+        self.order_by.push((field.into(), order));
+        self
+    }
+
+    pub fn top(mut self, top: u64) -> QueryBuilder {
+        self.top = Some(top);
+        self
+    }
+
+    pub fn build(&self) -> BuiltQuery {
+        let mut parameters = Vec::new();
+
+        let mut sql = String::from("SELECT ");
+        if let Some(top) = self.top {
+            sql.push_str(&format!("TOP {} ", top));
+        }
+        if self.fields.is_empty() {
+            sql.push('*');
+        } else {
+            let fields: Vec<String> = self.fields.iter().map(|field| field_path(field)).collect();
+            sql.push_str(&fields.join(", "));
+        }
+        sql.push_str(" FROM ");
+        sql.push_str(ROOT);
+
+        if let Some(ref condition) = self.condition {
+            sql.push_str(" WHERE ");
+            sql.push_str(&condition.compile(&mut parameters));
+        }
+
+        if !self.order_by.is_empty() {
+            let order_by: Vec<String> = self
+                .order_by
+                .iter()
+                .map(|(field, order)| {
+                    let direction = match order {
+                        Order::Ascending => "ASC",
+                        Order::Descending => "DESC",
+                    };
+                    format!("{} {}", field_path(field), direction)
+                })
+                .collect();
+            sql.push_str(" ORDER BY ");
+            sql.push_str(&order_by.join(", "));
+        }
+
+        BuiltQuery { sql, parameters }
+    }
+}
+
+/// The SQL text and the parameters generated by a `QueryBuilder`. Pass
+/// `as_query()` to `query_documents`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuiltQuery {
+    sql: String,
+    parameters: Vec<(String, Value)>,
+}
+
+impl BuiltQuery {
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    pub fn parameters(&self) -> &[(String, Value)] {
+        &self.parameters
+    }
+
+    pub fn as_query(&self) -> Query<'_> {
+        let params: Vec<Param> = self.parameters.iter().map(|(name, value)| Param::new_ref(name, value)).collect();
+        Query::with_params(&self.sql, params)
+    }
+}
+
+fn binary(field: &str, operator: &str, value: &Value, parameters: &mut Vec<(String, Value)>) -> String {
+    let name = push_parameter(value.clone(), parameters);
+    format!("{} {} {}", field_path(field), operator, name)
+}
+
+fn push_parameter(value: Value, parameters: &mut Vec<(String, Value)>) -> String {
+    let name = format!("@p{}", parameters.len());
+    parameters.push((name.clone(), value));
+    name
+}
+
+// Segments that are not plain identifiers use the quoted property
+// notation, so a field name can never escape its position in the query.
+fn field_path(field: &str) -> String {
+    let mut path = ROOT.to_owned();
+    for segment in field.split('.') {
+        if is_identifier(segment) {
+            path.push('.');
+            path.push_str(segment);
+        } else {
+            path.push('[');
+            path.push_str(&Value::from(segment).to_string());
+            path.push(']');
+        }
+    }
+    path
+}
+
+fn is_identifier(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_select_all() {
+        let query = QueryBuilder::new().build();
+        assert_eq!(query.sql(), "SELECT * FROM c");
+        assert!(query.parameters().is_empty());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let query = QueryBuilder::new()
+            .top(10)
+            .filter(Condition::eq("name", "O'Neil\" OR 1=1"))
+            .filter(Condition::is_in("tier", vec!["gold", "silver"]).or(Condition::lt("age", 30).not()))
+            .order_by("age", Order::Descending)
+            .order_by("id", Order::Ascending)
+            .build();
+
+        assert_eq!(
+            query.sql(),
+            "SELECT TOP 10 * FROM c WHERE (c.name = @p0 AND (ARRAY_CONTAINS(@p1, c.tier) OR NOT (c.age < @p2))) \
+             ORDER BY c.age DESC, c.id ASC"
+        );
+
+        let ser = serde_json::to_string(&query.as_query()).unwrap();
+        assert_eq!(
+            ser,
+            r#"{"query":"SELECT TOP 10 * FROM c WHERE (c.name = @p0 AND (ARRAY_CONTAINS(@p1, c.tier) OR NOT (c.age < @p2))) ORDER BY c.age DESC, c.id ASC","parameters":[{"name":"@p0","value":"O'Neil\" OR 1=1"},{"name":"@p1","value":["gold","silver"]},{"name":"@p2","value":30}]}"#
+        );
+    }
+
+    #[test]
+    fn test_field_names_are_quoted() {
+        let query = QueryBuilder::new()
+            .select(&["id", "first-name", "address.zip code"])
+            .filter(Condition::is_defined("x) OR (1=1"))
+            .build();
+
+        assert_eq!(
+            query.sql(),
+            r#"SELECT c.id, c["first-name"], c.address["zip code"] FROM c WHERE IS_DEFINED(c["x) OR (1=1"])"#
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::azure::core::util::RequestBuilderExt;
+use crate::azure::cosmos::client::headers::*;
+use crate::azure::cosmos::document::IndexingDirective;
+use crate::azure::cosmos::partition_key::PartitionKey;
+use crate::azure::cosmos::ConsistencyLevel;
+use http::request::Builder as RequestBuilder;
+use std::fmt::Display;
+
+/// Options shared by every document request. Pass them with
+/// `DocumentRequestExt::with_options` so the same set can be reused
+/// across operations. The service ignores the options that do not apply
+/// to an operation (for example the indexing directive of a read).
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions<'a> {
+    pub consistency_level: Option<ConsistencyLevel>,
+    pub session_token: Option<&'a str>,
+    pub indexing_directive: Option<IndexingDirective>,
+    /// Names of the triggers to run before the operation.
+    pub pre_triggers: Vec<&'a str>,
+    /// Names of the triggers to run after the operation.
+    pub post_triggers: Vec<&'a str>,
+    pub partition_key: PartitionKey<'a>,
+}
+
+impl<'a> RequestOptions<'a> {
+    pub fn new() -> RequestOptions<'a> {
+        RequestOptions::default()
+    }
+
+    pub fn with_consistency_level(self, consistency_level: ConsistencyLevel) -> Self {
+        RequestOptions {
+            consistency_level: Some(consistency_level),
+            ..self
+        }
+    }
+
+    pub fn with_session_token(self, session_token: &'a str) -> Self {
+        RequestOptions {
+            session_token: Some(session_token),
+            ..self
+        }
+    }
+
+    pub fn with_indexing_directive(self, indexing_directive: IndexingDirective) -> Self {
+        RequestOptions {
+            indexing_directive: Some(indexing_directive),
+            ..self
+        }
+    }
+
+    pub fn with_pre_trigger(mut self, trigger: &'a str) -> Self {
+        self.pre_triggers.push(trigger);
+        self
+    }
+
+    pub fn with_post_trigger(mut self, trigger: &'a str) -> Self {
+        self.post_triggers.push(trigger);
+        self
+    }
+
+    pub fn with_partition_key<P: Into<PartitionKey<'a>>>(self, partition_key: P) -> Self {
+        RequestOptions {
+            partition_key: partition_key.into(),
+            ..self
+        }
+    }
+
+    pub(crate) fn apply(&self, request: &mut RequestBuilder) {
+        if let Some(consistency_level) = self.consistency_level {
+            set_header(request, HEADER_CONSISTENCY_LEVEL, consistency_level);
+        }
+        if let Some(session_token) = self.session_token {
+            set_header(request, HEADER_SESSION_TOKEN, session_token);
+        }
+        if let Some(indexing_directive) = self.indexing_directive {
+            set_header(request, HEADER_INDEXING_DIRECTIVE, indexing_directive);
+        }
+        if !self.pre_triggers.is_empty() {
+            set_header(request, HEADER_DOCUMENTDB_PRE_TRIGGER_INCLUDE, self.pre_triggers.join(","));
+        }
+        if !self.post_triggers.is_empty() {
+            set_header(request, HEADER_DOCUMENTDB_POST_TRIGGER_INCLUDE, self.post_triggers.join(","));
+        }
+        // todo: move unwrap into PartitionKey impl itself as we control the impl and it surely won't error out
+        if let Some(partition_key) = self.partition_key.to_json().unwrap() {
+            set_header(request, HEADER_DOCUMENTDB_PARTITIONKEY, partition_key);
+        }
+    }
+}
+
+// the builder appends headers: drop the value set before, if any
+fn set_header<D: Display>(request: &mut RequestBuilder, name: &'static str, value: D) {
+    if let Some(headers) = request.headers_mut() {
+        headers.remove(name);
+    }
+    request.header_formatted(name, value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let options = RequestOptions::new()
+            .with_consistency_level(ConsistencyLevel::Session)
+            .with_session_token("0:123")
+            .with_indexing_directive(IndexingDirective::Exclude)
+            .with_pre_trigger("validate")
+            .with_pre_trigger("stamp")
+            .with_post_trigger("audit")
+            .with_partition_key("pk");
+
+        let mut request = RequestBuilder::new();
+        options.apply(&mut request);
+        let request = request.body(()).unwrap();
+        let headers = request.headers();
+
+        assert_eq!(headers[HEADER_CONSISTENCY_LEVEL], "Session");
+        assert_eq!(headers[HEADER_SESSION_TOKEN], "0:123");
+        assert_eq!(headers[HEADER_INDEXING_DIRECTIVE], "Exclude");
+        assert_eq!(headers[HEADER_DOCUMENTDB_PRE_TRIGGER_INCLUDE], "validate,stamp");
+        assert_eq!(headers[HEADER_DOCUMENTDB_POST_TRIGGER_INCLUDE], "audit");
+        assert_eq!(headers[HEADER_DOCUMENTDB_PARTITIONKEY], "[\"pk\"]");
+    }
+
+    #[test]
+    fn test_apply_replaces() {
+        let mut request = RequestBuilder::new();
+        request.header(HEADER_SESSION_TOKEN, "0:1");
+        RequestOptions::new().with_session_token("0:2").apply(&mut request);
+
+        let request = request.body(()).unwrap();
+        let values: Vec<_> = request.headers().get_all(HEADER_SESSION_TOKEN).iter().collect();
+        assert_eq!(values, vec!["0:2"]);
+    }
+
+    #[test]
+    fn test_apply_default() {
+        let mut request = RequestBuilder::new();
+        RequestOptions::default().apply(&mut request);
+        assert!(request.body(()).unwrap().headers().is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::azure::core::{errors::AzureError, util::HeaderMapExt};
 use crate::azure::cosmos::{
-    client::headers::HEADER_REQUEST_CHARGE, collection::Collection, database::Database, document::DocumentAttributes,
+    client::headers::HEADER_REQUEST_CHARGE, collection::Collection, database::Database, document::DocumentAttributes, ContinuationToken,
 };
 use serde::de::DeserializeOwned;
 
@@ -63,14 +63,23 @@ pub struct QueryResult<T> {
 
 #[derive(Debug, Clone)]
 pub struct ListDocumentsResponseAdditionalHeaders {
-    pub continuation_token: Option<String>,
+    pub continuation_token: Option<ContinuationToken>,
     pub charge: f64,
     pub etag: Option<String>,
 }
 
+impl ListDocumentsResponseAdditionalHeaders {
+    /// In change feed mode (see `ListDocumentsRequest::incremental_feed`)
+    /// the position of the feed is the response ETag: pass it to
+    /// `if_none_match` to receive only the later changes.
+    pub fn change_feed_continuation(&self) -> Option<ContinuationToken> {
+        self.etag.clone().map(ContinuationToken::new)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct QueryDocumentResponseAdditonalHeaders {
-    pub continuation_token: Option<String>,
+    pub continuation_token: Option<ContinuationToken>,
     pub charge: f64,
 }
 
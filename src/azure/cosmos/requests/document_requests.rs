@@ -248,6 +248,12 @@ pub struct ListDocumentsRequest {
     request: RequestBuilder,
 }
 
+impl DocumentRequestExt for ListDocumentsRequest {
+    fn request(&mut self) -> &mut RequestBuilder {
+        &mut self.request
+    }
+}
+
 impl ListDocumentsRequest {
     pub(crate) fn new(hyper_client: HyperClient, request: RequestBuilder) -> ListDocumentsRequest {
         ListDocumentsRequest { hyper_client, request }
@@ -416,10 +422,20 @@ pub trait DocumentRequestExt: Sized {
         }
         self
     }
+
+    /// Sets every option specified in `options`. Options set earlier on
+    /// the request with the same header are replaced.
+    fn with_options(mut self, options: &RequestOptions) -> Self {
+        options.apply(self.request());
+        self
+    }
 }
 
-fn derive_continuation_token(headers: &HeaderMap) -> Option<String> {
-    headers.get(HEADER_CONTINUATION).and_then(|v| v.to_str().ok()).map(|v| v.to_owned())
+fn derive_continuation_token(headers: &HeaderMap) -> Option<ContinuationToken> {
+    headers
+        .get(HEADER_CONTINUATION)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| ContinuationToken::new(v.to_owned()))
 }
 
 fn derive_request_charge(headers: &HeaderMap) -> f64 {
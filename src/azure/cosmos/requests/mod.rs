@@ -3,7 +3,6 @@ use crate::azure::core::{
     errors::{
         check_status_extract_body, check_status_extract_headers_and_body, extract_status_headers_and_body, AzureError, UnexpectedHTTPResult,
     },
-    incompletevector::ContinuationToken,
     util::RequestBuilderExt,
 };
 use crate::azure::cosmos::{
@@ -11,7 +10,7 @@ use crate::azure::cosmos::{
     document::{DocumentAttributes, IndexingDirective},
     partition_key::PartitionKey,
     request_response::*,
-    ConsistencyLevel,
+    ConsistencyLevel, ContinuationToken, RequestOptions,
 };
 use futures::{future, prelude::*};
 use http::request::Builder as RequestBuilder;
@@ -25,7 +24,7 @@ use serde_json;
 use std::sync::Arc;
 use std::{marker::PhantomData, str};
 
-type HyperClient = Arc<hyper::Client<::hyper_tls::HttpsConnector<hyper::client::HttpConnector>>>;
+type HyperClient = Arc<hyper::Client<crate::azure::core::tls::HttpsConnector<hyper::client::HttpConnector>>>;
 
 macro_rules! request_bytes_ref {
     ($name:ident, $ty:ty, $h:path) => {
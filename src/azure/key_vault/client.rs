@@ -0,0 +1,203 @@
+use crate::azure::core::errors::{check_status_extract_body, AzureError};
+use crate::azure::core::tls::{default_https_connector, HttpsConnector};
+use crate::azure::key_vault::key::{KeyOperationBody, KeyOperationResponse, KeyOperationResult, VerifyBody, VerifyResponse};
+use crate::azure::key_vault::secret::{DeletedSecret, Secret, SetSecretBody};
+use crate::azure::key_vault::API_VERSION;
+use base64;
+use futures::future::{self, Either};
+use futures::Future;
+use hyper::header::{self, HeaderValue};
+use hyper::{self, Body, Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json;
+use url::percent_encoding::utf8_percent_encode;
+use url::percent_encoding::PATH_SEGMENT_ENCODE_SET;
+
+type HttpClient = hyper::Client<HttpsConnector<hyper::client::HttpConnector>>;
+
+/// Reads and writes the secrets of a Key Vault and performs cryptographic
+/// operations with its keys. The requests are authenticated with an
+/// Azure AD access token for the `https://vault.azure.net` resource. The
+/// token is not refreshed: build a new client before it expires.
+#[derive(Clone)]
+pub struct Client {
+    vault_uri: String,
+    bearer_token: String,
+    http_client: HttpClient,
+}
+
+impl Client {
+    pub fn new(vault_name: &str, bearer_token: &str) -> Result<Client, AzureError> {
+        Client::with_vault_uri(&format!("https://{}.vault.azure.net", vault_name), bearer_token)
+    }
+
+    /// For the vaults outside of the public cloud, for example
+    /// `https://<vault>.vault.azure.cn`.
+    pub fn with_vault_uri(vault_uri: &str, bearer_token: &str) -> Result<Client, AzureError> {
+        let http_client = hyper::Client::builder().build(default_https_connector()?);
+
+        Ok(Client {
+            vault_uri: vault_uri.trim_end_matches('/').to_owned(),
+            bearer_token: bearer_token.to_owned(),
+            http_client,
+        })
+    }
+
+    pub fn vault_uri(&self) -> &str {
+        &self.vault_uri
+    }
+
+    /// The latest version of the secret if `version` is `None`.
+    pub fn get_secret(&self, name: &str, version: Option<&str>) -> impl Future<Item = Secret, Error = AzureError> {
+        let path = match version {
+            Some(version) => format!("secrets/{}/{}", encode(name), encode(version)),
+            None => format!("secrets/{}", encode(name)),
+        };
+        self.request(Method::GET, &path, Ok(None))
+    }
+
+    /// Creates a new version of the secret, the secret itself if it does
+    /// not exist.
+    pub fn set_secret(&self, name: &str, value: &str, content_type: Option<&str>) -> impl Future<Item = Secret, Error = AzureError> {
+        let path = format!("secrets/{}", encode(name));
+        self.request(Method::PUT, &path, json(&SetSecretBody { value, content_type }))
+    }
+
+    /// Deletes every version of the secret.
+    pub fn delete_secret(&self, name: &str) -> impl Future<Item = DeletedSecret, Error = AzureError> {
+        let path = format!("secrets/{}", encode(name));
+        self.request(Method::DELETE, &path, Ok(None))
+    }
+
+    /// Wraps (encrypts) a symmetric key with the key `key_name`. An empty
+    /// `key_version` selects the latest version of the key. `algorithm`
+    /// is for example `RSA-OAEP` or `A256KW`.
+    pub fn wrap_key(
+        &self,
+        key_name: &str,
+        key_version: &str,
+        algorithm: &str,
+        key: &[u8],
+    ) -> impl Future<Item = KeyOperationResult, Error = AzureError> {
+        self.key_operation(key_name, key_version, "wrapkey", algorithm, key)
+    }
+
+    pub fn unwrap_key(
+        &self,
+        key_name: &str,
+        key_version: &str,
+        algorithm: &str,
+        wrapped_key: &[u8],
+    ) -> impl Future<Item = KeyOperationResult, Error = AzureError> {
+        self.key_operation(key_name, key_version, "unwrapkey", algorithm, wrapped_key)
+    }
+
+    /// Signs a digest computed by the caller, for example the SHA-256 of
+    /// the data with the `RS256` or `ES256` algorithm.
+    pub fn sign(
+        &self,
+        key_name: &str,
+        key_version: &str,
+        algorithm: &str,
+        digest: &[u8],
+    ) -> impl Future<Item = KeyOperationResult, Error = AzureError> {
+        self.key_operation(key_name, key_version, "sign", algorithm, digest)
+    }
+
+    /// Whether `signature` is a valid signature of `digest`.
+    pub fn verify(
+        &self,
+        key_name: &str,
+        key_version: &str,
+        algorithm: &str,
+        digest: &[u8],
+        signature: &[u8],
+    ) -> impl Future<Item = bool, Error = AzureError> {
+        let path = key_path(key_name, key_version, "verify");
+        let body = VerifyBody {
+            alg: algorithm,
+            digest: base64::encode_config(digest, base64::URL_SAFE_NO_PAD),
+            value: base64::encode_config(signature, base64::URL_SAFE_NO_PAD),
+        };
+        self.request(Method::POST, &path, json(&body))
+            .map(|response: VerifyResponse| response.value)
+    }
+
+    fn key_operation(
+        &self,
+        key_name: &str,
+        key_version: &str,
+        operation: &str,
+        algorithm: &str,
+        value: &[u8],
+    ) -> impl Future<Item = KeyOperationResult, Error = AzureError> {
+        let path = key_path(key_name, key_version, operation);
+        let body = KeyOperationBody {
+            alg: algorithm,
+            value: base64::encode_config(value, base64::URL_SAFE_NO_PAD),
+        };
+        self.request(Method::POST, &path, json(&body))
+            .and_then(|response: KeyOperationResponse| response.decode())
+    }
+
+    fn request<T>(&self, method: Method, path: &str, body: Result<Option<String>, AzureError>) -> impl Future<Item = T, Error = AzureError>
+    where
+        T: DeserializeOwned,
+    {
+        let uri = format!("{}/{}?api-version={}", self.vault_uri, path, API_VERSION);
+        trace!("uri == {:?}", uri);
+
+        let request = || -> Result<hyper::Request<Body>, AzureError> {
+            let body = body?.map(Body::from).unwrap_or_else(Body::empty);
+            Ok(hyper::Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(header::AUTHORIZATION, format!("Bearer {}", self.bearer_token))
+                .header(header::CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .body(body)?)
+        };
+
+        let response = match request() {
+            Ok(request) => Either::A(self.http_client.request(request).from_err()),
+            Err(error) => Either::B(future::err(error)),
+        };
+
+        check_status_extract_body(response, StatusCode::OK).and_then(|body| Ok(serde_json::from_str(&body)?))
+    }
+}
+
+// the token must not end up in the logs
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Client").field("vault_uri", &self.vault_uri).finish()
+    }
+}
+
+fn json<B: Serialize>(body: &B) -> Result<Option<String>, AzureError> {
+    Ok(Some(serde_json::to_string(body)?))
+}
+
+fn encode(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT_ENCODE_SET).to_string()
+}
+
+// an empty version is left empty, as the other SDKs do
+fn key_path(key_name: &str, key_version: &str, operation: &str) -> String {
+    format!("keys/{}/{}/{}", encode(key_name), encode(key_version), operation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(key_path("mykey", "", "wrapkey"), "keys/mykey//wrapkey");
+        assert_eq!(key_path("my key", "78deebed", "sign"), "keys/my%20key/78deebed/sign");
+
+        let client = Client::with_vault_uri("https://myvault.vault.azure.cn/", "token").unwrap();
+        assert_eq!(client.vault_uri(), "https://myvault.vault.azure.cn");
+        assert!(!format!("{:?}", client).contains("token"));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::key_vault::client::Client;
+use crate::azure::storage::blob::KeyEncryptionKey;
+use base64;
+use futures::Future;
+
+/// The outcome of a key operation (wrap, unwrap or sign).
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyOperationResult {
+    /// The identifier of the key used, version included.
+    pub kid: String,
+    pub value: Vec<u8>,
+}
+
+// the bodies of the requests and responses, the binary values being
+// base64url encoded without padding
+#[derive(Debug, Serialize)]
+pub(crate) struct KeyOperationBody<'a> {
+    pub(crate) alg: &'a str,
+    pub(crate) value: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct VerifyBody<'a> {
+    pub(crate) alg: &'a str,
+    pub(crate) digest: String,
+    pub(crate) value: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct KeyOperationResponse {
+    kid: String,
+    value: String,
+}
+
+impl KeyOperationResponse {
+    pub(crate) fn decode(self) -> Result<KeyOperationResult, AzureError> {
+        Ok(KeyOperationResult {
+            kid: self.kid,
+            value: base64::decode_config(&self.value, base64::URL_SAFE_NO_PAD)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct VerifyResponse {
+    pub(crate) value: bool,
+}
+
+/// A Key Vault key wrapping the content keys of the client-side
+/// encryption (see `ClientSideEncryption`).
+///
+/// `KeyEncryptionKey` is synchronous: the wrap and unwrap requests block
+/// the calling thread until the vault answers. Do not use it from a
+/// single threaded executor.
+#[derive(Debug, Clone)]
+pub struct KeyVaultKey {
+    client: Client,
+    key_name: String,
+    key_version: String,
+    key_id: String,
+    algorithm: String,
+}
+
+impl KeyVaultKey {
+    /// `algorithm` is the wrapping algorithm, for example `RSA-OAEP`.
+    /// Pin `key_version`: the content keys wrapped with a version can only
+    /// be unwrapped with that version.
+    pub fn new(client: Client, key_name: &str, key_version: &str, algorithm: &str) -> KeyVaultKey {
+        let key_id = format!("{}/keys/{}/{}", client.vault_uri(), key_name, key_version);
+        KeyVaultKey {
+            client,
+            key_name: key_name.to_owned(),
+            key_version: key_version.to_owned(),
+            key_id,
+            algorithm: algorithm.to_owned(),
+        }
+    }
+}
+
+impl KeyEncryptionKey for KeyVaultKey {
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    fn wrap_key(&self, key: &[u8]) -> Result<Vec<u8>, AzureError> {
+        let result = self
+            .client
+            .wrap_key(&self.key_name, &self.key_version, &self.algorithm, key)
+            .wait()?;
+        Ok(result.value)
+    }
+
+    fn unwrap_key(&self, algorithm: &str, wrapped_key: &[u8]) -> Result<Vec<u8>, AzureError> {
+        let result = self
+            .client
+            .unwrap_key(&self.key_name, &self.key_version, algorithm, wrapped_key)
+            .wait()?;
+        Ok(result.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_decode() {
+        let response: KeyOperationResponse =
+            serde_json::from_str(r#"{"kid":"https://myvault.vault.azure.net/keys/mykey/78deebed","value":"AQID_w"}"#).unwrap();
+        let result = response.decode().unwrap();
+        assert_eq!(result.kid, "https://myvault.vault.azure.net/keys/mykey/78deebed");
+        assert_eq!(result.value, vec![1, 2, 3, 255]);
+
+        let body = KeyOperationBody {
+            alg: "RSA-OAEP",
+            value: base64::encode_config(&[1, 2, 3, 255], base64::URL_SAFE_NO_PAD),
+        };
+        assert_eq!(serde_json::to_string(&body).unwrap(), r#"{"alg":"RSA-OAEP","value":"AQID_w"}"#);
+    }
+
+    #[test]
+    fn test_key_id() {
+        let client = Client::new("myvault", "token").unwrap();
+        let key = KeyVaultKey::new(client, "mykey", "78deebed", "RSA-OAEP");
+        assert_eq!(key.key_id(), "https://myvault.vault.azure.net/keys/mykey/78deebed");
+        assert_eq!(key.algorithm(), "RSA-OAEP");
+    }
+}
@@ -0,0 +1,9 @@
+mod client;
+pub use self::client::Client;
+mod key;
+pub use self::key::{KeyOperationResult, KeyVaultKey};
+mod secret;
+pub use self::secret::{DeletedSecret, Secret, SecretAttributes};
+
+// the REST API version of the requests
+const API_VERSION: &str = "7.0";
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+/// The attributes of a secret. The times are Unix timestamps.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretAttributes {
+    pub enabled: Option<bool>,
+    #[serde(rename = "nbf")]
+    pub not_before: Option<i64>,
+    #[serde(rename = "exp")]
+    pub expires: Option<i64>,
+    pub created: Option<i64>,
+    pub updated: Option<i64>,
+    pub recovery_level: Option<String>,
+}
+
+/// A version of a secret.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Secret {
+    /// The secret identifier, version included:
+    /// `https://<vault>.vault.azure.net/secrets/<name>/<version>`.
+    pub id: String,
+    pub value: String,
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub attributes: SecretAttributes,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+}
+
+impl Secret {
+    /// The last segment of the identifier.
+    pub fn version(&self) -> &str {
+        self.id.rsplit('/').next().unwrap_or_default()
+    }
+}
+
+/// A deleted secret. It can be recovered until the purge date if soft
+/// delete is enabled on the vault. The times are Unix timestamps.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedSecret {
+    pub id: String,
+    pub recovery_id: Option<String>,
+    pub deleted_date: Option<i64>,
+    pub scheduled_purge_date: Option<i64>,
+    #[serde(default)]
+    pub attributes: SecretAttributes,
+}
+
+// the body of Set Secret
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SetSecretBody<'a> {
+    pub(crate) value: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) content_type: Option<&'a str>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_parse() {
+        let secret: Secret = serde_json::from_str(
+            r#"{
+                "value": "mysecretvalue",
+                "contentType": "text/plain",
+                "id": "https://myvault.vault.azure.net/secrets/mysecret/4387e9f3d6e14c459867679a90fd0f79",
+                "attributes": {"enabled": true, "created": 1493938410, "updated": 1493938410, "recoveryLevel": "Recoverable+Purgeable"},
+                "tags": {"env": "test"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(secret.value, "mysecretvalue");
+        assert_eq!(secret.version(), "4387e9f3d6e14c459867679a90fd0f79");
+        assert_eq!(secret.attributes.enabled, Some(true));
+        assert_eq!(secret.attributes.recovery_level.as_deref(), Some("Recoverable+Purgeable"));
+        assert_eq!(secret.tags["env"], "test");
+
+        assert_eq!(
+            serde_json::to_string(&SetSecretBody {
+                value: "v",
+                content_type: None
+            })
+            .unwrap(),
+            r#"{"value":"v"}"#
+        );
+    }
+}
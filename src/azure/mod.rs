@@ -1,5 +1,6 @@
 #[macro_use]
 pub mod core;
 pub mod cosmos;
+pub mod key_vault;
 pub mod service_bus;
 pub mod storage;
@@ -0,0 +1,224 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::shutdown::ShutdownHandle;
+use crate::azure::service_bus::message::ReceivedMessage;
+use crate::azure::service_bus::queue_client::QueueClient;
+use crate::azure::storage::client::{Client, Queue};
+use crate::azure::storage::queue::{
+    MessageIdSupport, MessageTextSupport, NumberOfMessagesSupport, PopReceiptSupport, QueueMessage, QueueNameSupport,
+    VisibilityTimeoutSupport,
+};
+use futures::future::{self, Either, Loop};
+use futures::{stream, Future, Stream};
+
+// the maximum number of messages a single Get Messages call can return
+const MAX_BATCH_SIZE: u32 = 32;
+
+/// Pumps the messages of a storage queue to a Service Bus queue, or the
+/// other way around, for example while migrating the producers and the
+/// consumers from one service to the other.
+///
+/// The delivery is at-least-once: a message is deleted from the source
+/// only after it has been accepted by the destination. If the deletion
+/// fails (or the source lock expires in between) the message becomes
+/// visible again and is forwarded a second time, so the consumers must
+/// tolerate duplicates. Only the message body is forwarded.
+#[derive(Clone)]
+pub struct QueueBridge {
+    storage: Client,
+    storage_queue: String,
+    service_bus: QueueClient,
+    batch_size: u32,
+    visibility_timeout: u64,
+    shutdown: Option<ShutdownHandle>,
+}
+
+impl QueueBridge {
+    pub fn new(storage: &Client, storage_queue: &str, service_bus: &QueueClient) -> QueueBridge {
+        QueueBridge {
+            storage: storage.clone(),
+            storage_queue: storage_queue.to_owned(),
+            service_bus: service_bus.clone(),
+            batch_size: MAX_BATCH_SIZE,
+            visibility_timeout: 30,
+            shutdown: None,
+        }
+    }
+
+    /// How many storage queue messages are dequeued at once (1 to 32,
+    /// defaults to 32).
+    pub fn with_batch_size(self, batch_size: u32) -> QueueBridge {
+        QueueBridge {
+            batch_size: batch_size.clamp(1, MAX_BATCH_SIZE),
+            ..self
+        }
+    }
+
+    /// How long, in seconds, a dequeued storage queue message stays
+    /// invisible. The whole batch must be forwarded within this time,
+    /// otherwise its messages are dequeued (and forwarded) again.
+    /// Defaults to 30 seconds.
+    pub fn with_visibility_timeout(self, visibility_timeout: u64) -> QueueBridge {
+        QueueBridge {
+            visibility_timeout,
+            ..self
+        }
+    }
+
+    /// Stops forwarding once `shutdown` is requested: the batch (or the
+    /// Service Bus message) in flight is forwarded and deleted from the
+    /// source, so nothing is left locked, and the future resolves early
+    /// with the number of messages forwarded so far.
+    /// `ShutdownHandle::shutdown` resolves once it has.
+    pub fn with_shutdown(self, shutdown: &ShutdownHandle) -> QueueBridge {
+        QueueBridge {
+            shutdown: Some(shutdown.clone()),
+            ..self
+        }
+    }
+
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+
+    pub fn visibility_timeout(&self) -> u64 {
+        self.visibility_timeout
+    }
+
+    /// Forwards the storage queue messages to the Service Bus queue until
+    /// the storage queue is empty. The messages of a batch are forwarded
+    /// one at a time, in order. Resolves to the number of messages
+    /// forwarded.
+    pub fn to_service_bus(&self) -> impl Future<Item = usize, Error = AzureError> {
+        let bridge = self.clone();
+
+        let forwarding = future::loop_fn(0, move |forwarded| {
+            if bridge.is_shutdown_requested() {
+                return Either::A(future::ok(Loop::Break(forwarded)));
+            }
+
+            let bridge = bridge.clone();
+            let batch = bridge
+                .storage
+                .get_messages()
+                .with_queue_name(&bridge.storage_queue)
+                .with_number_of_messages(bridge.batch_size)
+                .with_visibility_timeout(bridge.visibility_timeout)
+                .finalize();
+
+            Either::B(batch.and_then(move |response| {
+                let count = response.messages.len();
+                stream::iter_ok(response.messages)
+                    .for_each(move |message| bridge.forward_to_service_bus(message))
+                    .map(move |_| {
+                        if count == 0 {
+                            Loop::Break(forwarded)
+                        } else {
+                            Loop::Continue(forwarded + count)
+                        }
+                    })
+            }))
+        });
+
+        self.stopped_after(forwarding)
+    }
+
+    fn forward_to_service_bus(&self, message: QueueMessage) -> impl Future<Item = (), Error = AzureError> {
+        let storage = self.storage.clone();
+        let storage_queue = self.storage_queue.clone();
+
+        self.service_bus.send_message(&message.message_text).and_then(move |_| {
+            // dequeued messages always come with a pop receipt
+            let pop_receipt = message.pop_receipt.unwrap_or_default();
+            storage
+                .delete_message()
+                .with_queue_name(&storage_queue)
+                .with_message_id(&message.message_id)
+                .with_pop_receipt(&pop_receipt)
+                .finalize()
+                .map(|_| ())
+        })
+    }
+
+    /// Forwards the Service Bus queue messages to the storage queue until
+    /// the Service Bus queue has no more unlocked messages. Resolves to the
+    /// number of messages forwarded.
+    pub fn to_storage_queue(&self) -> impl Future<Item = usize, Error = AzureError> {
+        let bridge = self.clone();
+
+        let forwarding = future::loop_fn(0, move |forwarded| {
+            if bridge.is_shutdown_requested() {
+                return Either::A(future::ok(Loop::Break(forwarded)));
+            }
+
+            let bridge = bridge.clone();
+
+            Either::B(bridge.service_bus.receive_message().and_then(move |message| match message {
+                Some(message) => Either::A(bridge.forward_to_storage_queue(message).map(move |_| Loop::Continue(forwarded + 1))),
+                None => Either::B(future::ok(Loop::Break(forwarded))),
+            }))
+        });
+
+        self.stopped_after(forwarding)
+    }
+
+    fn is_shutdown_requested(&self) -> bool {
+        self.shutdown.as_ref().is_some_and(ShutdownHandle::is_shutdown_requested)
+    }
+
+    // reports the end of the forwarding, failed or not, to the shutdown
+    // handle
+    fn stopped_after<F>(&self, forwarding: F) -> impl Future<Item = usize, Error = AzureError>
+    where
+        F: Future<Item = usize, Error = AzureError>,
+    {
+        let shutdown = self.shutdown.clone();
+        forwarding.then(move |result| {
+            if let Some(shutdown) = shutdown {
+                shutdown.set_stopped();
+            }
+            result
+        })
+    }
+
+    fn forward_to_storage_queue(&self, message: ReceivedMessage) -> impl Future<Item = (), Error = AzureError> {
+        let service_bus = self.service_bus.clone();
+
+        self.storage
+            .put_message()
+            .with_queue_name(&self.storage_queue)
+            .with_message_text(message.body())
+            .finalize()
+            .and_then(move |_| service_bus.complete_message(&message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_batch_size() {
+        let storage = Client::new("account", "a2V5").unwrap();
+        let service_bus = QueueClient::new("ns", "orders", "policy", "key").unwrap();
+
+        let bridge = QueueBridge::new(&storage, "orders", &service_bus);
+        assert_eq!(bridge.batch_size(), 32);
+        assert_eq!(bridge.visibility_timeout(), 30);
+        assert_eq!(bridge.clone().with_batch_size(0).batch_size(), 1);
+        assert_eq!(bridge.with_batch_size(100).batch_size(), 32);
+    }
+
+    #[test]
+    fn test_shutdown() {
+        let storage = Client::new("account", "a2V5").unwrap();
+        let service_bus = QueueClient::new("ns", "orders", "policy", "key").unwrap();
+        let shutdown = ShutdownHandle::new();
+        let stopped = shutdown.shutdown();
+
+        // requested before the first batch: nothing is dequeued
+        let bridge = QueueBridge::new(&storage, "orders", &service_bus).with_shutdown(&shutdown);
+        assert_eq!(bridge.to_service_bus().wait().unwrap(), 0);
+        stopped.wait().unwrap();
+        assert_eq!(bridge.to_storage_queue().wait().unwrap(), 0);
+    }
+}
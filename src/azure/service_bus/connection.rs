@@ -0,0 +1,116 @@
+use crate::azure::core::crypto::{service_bus_sas_token, HmacKey};
+use crate::azure::core::errors::{check_status_extract_body, extract_status_headers_and_body, AzureError, UnexpectedHTTPResult};
+use crate::azure::core::tls::default_https_connector;
+use crate::azure::service_bus::message::ReceivedMessage;
+use crate::azure::service_bus::HttpClient;
+use futures::future::{self, Either};
+use futures::Future;
+use hyper::header::{self, HeaderMap};
+use hyper::{self, Body, Method, StatusCode};
+use std::str;
+use time::Duration;
+
+// how long the service waits for a message before answering 204
+const RECEIVE_TIMEOUT_SECONDS: u32 = 5;
+
+// The REST plumbing shared by the Service Bus clients: every request is
+// signed with a SAS token generated from the policy key.
+#[derive(Clone)]
+pub(crate) struct Connection {
+    namespace: String,
+    policy_name: String,
+    signing_key: HmacKey,
+    http_client: HttpClient,
+    token_duration: Duration,
+}
+
+impl Connection {
+    pub(crate) fn new(namespace: String, policy_name: String, key: &str) -> Result<Connection, AzureError> {
+        let http_client = hyper::Client::builder().build(default_https_connector()?);
+
+        Ok(Connection {
+            namespace,
+            policy_name,
+            signing_key: HmacKey::new(key.as_bytes()),
+            http_client,
+            token_duration: Duration::hours(1),
+        })
+    }
+
+    pub(crate) fn set_token_duration(&mut self, token_duration: Duration) {
+        self.token_duration = token_duration;
+    }
+
+    /// The url of the entity (queue, topic, subscription...) at `path`.
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("https://{}.servicebus.windows.net/{}", self.namespace, path)
+    }
+
+    pub(crate) fn perform_request<F>(
+        &self,
+        url: &str,
+        method: Method,
+        headers_func: F,
+        body: Option<String>,
+    ) -> Result<hyper::client::ResponseFuture, AzureError>
+    where
+        F: FnOnce(&mut HeaderMap) -> Result<(), AzureError>,
+    {
+        let sas = service_bus_sas_token(&self.policy_name, &self.signing_key, url, self.token_duration);
+
+        let mut request = hyper::Request::builder()
+            .method(method)
+            .uri(url)
+            .header(header::AUTHORIZATION, sas)
+            .body(body.map(Body::from).unwrap_or_else(Body::empty))?;
+        headers_func(request.headers_mut())?;
+
+        Ok(self.http_client.request(request))
+    }
+
+    /// Sends a message to the entity at `path`, with the additional
+    /// `headers` (broker and custom properties).
+    pub(crate) fn send(&self, path: &str, body: &str, headers: HeaderMap) -> impl Future<Item = (), Error = AzureError> {
+        let url = format!("{}/messages", self.url(path));
+        let req = self.perform_request(
+            &url,
+            Method::POST,
+            |request_headers| {
+                request_headers.extend(headers);
+                Ok(())
+            },
+            Some(body.to_owned()),
+        );
+
+        future::result(req)
+            .and_then(|future_response| check_status_extract_body(future_response, StatusCode::CREATED))
+            .map(|_| ())
+    }
+
+    /// Locks the next message of the entity at `path`, if any.
+    pub(crate) fn receive(&self, path: &str) -> impl Future<Item = Option<ReceivedMessage>, Error = AzureError> {
+        let url = format!("{}/messages/head?timeout={}", self.url(path), RECEIVE_TIMEOUT_SECONDS);
+        let req = self.perform_request(&url, Method::POST, |_| Ok(()), None);
+
+        future::result(req)
+            .and_then(extract_status_headers_and_body)
+            .and_then(|(status, headers, body)| match status {
+                StatusCode::NO_CONTENT => Ok(None),
+                StatusCode::CREATED => ReceivedMessage::from_response(&headers, &body).map(Some),
+                status => Err(AzureError::UnexpectedHTTPResult(UnexpectedHTTPResult::new(
+                    StatusCode::CREATED,
+                    status,
+                    str::from_utf8(&body)?,
+                ))),
+            })
+    }
+
+    /// Deletes (`DELETE`), unlocks (`PUT`) or renews the lock (`POST`) of a
+    /// locked message.
+    pub(crate) fn lock_request(&self, message: &ReceivedMessage, method: Method) -> impl Future<Item = (), Error = AzureError> {
+        match self.perform_request(message.lock_location(), method, |_| Ok(()), None) {
+            Ok(future_response) => Either::A(check_status_extract_body(future_response, StatusCode::OK).map(|_| ())),
+            Err(error) => Either::B(future::err(error)),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::azure::core::errors::AzureError;
+
+/// The connection string of a Service Bus or Event Hubs namespace (or of
+/// one of its entities), as shown in the portal:
+/// `Endpoint=sb://<namespace>.servicebus.windows.net/;SharedAccessKeyName=<policy>;SharedAccessKey=<key>[;EntityPath=<entity>]`.
+#[derive(Clone, PartialEq)]
+pub struct ConnectionString {
+    pub namespace: String,
+    pub policy_name: String,
+    pub key: String,
+    /// Present in the connection strings of the entity level policies.
+    pub entity_path: Option<String>,
+}
+
+impl ConnectionString {
+    pub fn parse(connection_string: &str) -> Result<ConnectionString, AzureError> {
+        let mut endpoint = None;
+        let mut policy_name = None;
+        let mut key = None;
+        let mut entity_path = None;
+
+        for pair in connection_string.split(';').filter(|pair| !pair.trim().is_empty()) {
+            // the key is base64 encoded and may end with '='
+            let mut tokens = pair.splitn(2, '=');
+            let name = tokens.next().unwrap_or_default().trim();
+            let value = tokens.next().ok_or_else(|| invalid(&format!("{} has no value", name)))?;
+            match name {
+                "Endpoint" => endpoint = Some(value),
+                "SharedAccessKeyName" => policy_name = Some(value.to_owned()),
+                "SharedAccessKey" => key = Some(value.to_owned()),
+                "EntityPath" => entity_path = Some(value.to_owned()),
+                _ => debug!("ignoring the connection string setting {}", name),
+            }
+        }
+
+        let endpoint = endpoint.ok_or_else(|| invalid("Endpoint is missing"))?;
+        let namespace = endpoint
+            .trim_start_matches("sb://")
+            .split('.')
+            .next()
+            .filter(|namespace| !namespace.is_empty())
+            .ok_or_else(|| invalid("Endpoint has no namespace"))?;
+
+        Ok(ConnectionString {
+            namespace: namespace.to_owned(),
+            policy_name: policy_name.ok_or_else(|| invalid("SharedAccessKeyName is missing"))?,
+            key: key.ok_or_else(|| invalid("SharedAccessKey is missing"))?,
+            entity_path,
+        })
+    }
+}
+
+fn invalid(reason: &str) -> AzureError {
+    AzureError::InputParametersError(format!("invalid connection string: {}", reason))
+}
+
+// the key must not end up in the logs
+impl std::fmt::Debug for ConnectionString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ConnectionString")
+            .field("namespace", &self.namespace)
+            .field("policy_name", &self.policy_name)
+            .field("entity_path", &self.entity_path)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let connection_string = ConnectionString::parse(
+            "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=send;\
+             SharedAccessKey=c2VjcmV0a2V5PQ==;EntityPath=telemetry",
+        )
+        .unwrap();
+        assert_eq!(connection_string.namespace, "mynamespace");
+        assert_eq!(connection_string.policy_name, "send");
+        assert_eq!(connection_string.key, "c2VjcmV0a2V5PQ==");
+        assert_eq!(connection_string.entity_path.as_deref(), Some("telemetry"));
+        assert!(!format!("{:?}", connection_string).contains("c2VjcmV0a2V5PQ=="));
+
+        let connection_string =
+            ConnectionString::parse("Endpoint=sb://ns.servicebus.windows.net/;SharedAccessKeyName=root;SharedAccessKey=a2V5;").unwrap();
+        assert_eq!(connection_string.entity_path, None);
+
+        assert!(ConnectionString::parse("SharedAccessKeyName=root;SharedAccessKey=a2V5").is_err());
+        assert!(ConnectionString::parse("Endpoint=sb://ns.servicebus.windows.net/;SharedAccessKey=a2V5").is_err());
+    }
+}
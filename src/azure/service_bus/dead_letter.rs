@@ -0,0 +1,159 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::service_bus::connection::Connection;
+use crate::azure::service_bus::message::ReceivedMessage;
+use futures::future::{self, Either};
+use futures::{stream, Future, Stream};
+use hyper::header::HeaderMap;
+use hyper::Method;
+use time::Duration;
+
+/// A message locked in a dead-letter sub-queue.
+pub type DeadLetteredMessage = ReceivedMessage;
+
+#[derive(Debug, Clone)]
+enum Entity {
+    Queue(String),
+    Subscription { topic: String, subscription: String },
+}
+
+/// Browses the dead-letter sub-queue of a Service Bus queue or topic
+/// subscription and resubmits its messages.
+///
+/// Browsing locks the messages (peek-lock): each one has to be
+/// resubmitted, removed or released before its lock expires, otherwise
+/// it becomes visible again.
+#[derive(Clone)]
+pub struct DeadLetterClient {
+    connection: Connection,
+    entity: Entity,
+}
+
+impl DeadLetterClient {
+    pub fn for_queue<N, Q, P, K>(namespace: N, queue: Q, policy_name: P, key: K) -> Result<DeadLetterClient, AzureError>
+    where
+        N: Into<String>,
+        Q: Into<String>,
+        P: Into<String>,
+        K: AsRef<str>,
+    {
+        Ok(DeadLetterClient {
+            connection: Connection::new(namespace.into(), policy_name.into(), key.as_ref())?,
+            entity: Entity::Queue(queue.into()),
+        })
+    }
+
+    pub fn for_subscription<N, T, S, P, K>(
+        namespace: N,
+        topic: T,
+        subscription: S,
+        policy_name: P,
+        key: K,
+    ) -> Result<DeadLetterClient, AzureError>
+    where
+        N: Into<String>,
+        T: Into<String>,
+        S: Into<String>,
+        P: Into<String>,
+        K: AsRef<str>,
+    {
+        Ok(DeadLetterClient {
+            connection: Connection::new(namespace.into(), policy_name.into(), key.as_ref())?,
+            entity: Entity::Subscription {
+                topic: topic.into(),
+                subscription: subscription.into(),
+            },
+        })
+    }
+
+    /// Validity of the SAS tokens generated for each request. Defaults to
+    /// one hour.
+    pub fn with_token_duration(mut self, token_duration: Duration) -> DeadLetterClient {
+        self.connection.set_token_duration(token_duration);
+        self
+    }
+
+    fn dead_letter_path(&self) -> String {
+        match &self.entity {
+            Entity::Queue(queue) => format!("{}/$DeadLetterQueue", queue),
+            Entity::Subscription { topic, subscription } => format!("{}/subscriptions/{}/$DeadLetterQueue", topic, subscription),
+        }
+    }
+
+    // the messages of a subscription are resubmitted to its topic
+    fn main_entity_path(&self) -> &str {
+        match &self.entity {
+            Entity::Queue(queue) => queue,
+            Entity::Subscription { topic, .. } => topic,
+        }
+    }
+
+    /// Locks the next dead-lettered message, if any.
+    pub fn receive(&self) -> impl Future<Item = Option<DeadLetteredMessage>, Error = AzureError> {
+        self.connection.receive(&self.dead_letter_path())
+    }
+
+    /// Pages through the dead-letter sub-queue, locking up to
+    /// `max_messages` messages (all of them if `None`). The stream ends
+    /// when the sub-queue has no more unlocked messages.
+    pub fn messages(&self, max_messages: Option<usize>) -> impl Stream<Item = DeadLetteredMessage, Error = AzureError> {
+        let client = self.clone();
+
+        stream::unfold((0, false), move |(received, exhausted)| {
+            if exhausted || max_messages.is_some_and(|max_messages| received >= max_messages) {
+                return None;
+            }
+            Some(client.receive().map(move |message| {
+                let exhausted = message.is_none();
+                (message, (received + 1, exhausted))
+            }))
+        })
+        .filter_map(|message| message)
+    }
+
+    /// Sends a copy of `message` (body, content type, label, ids and custom
+    /// properties, except the dead-letter reason and description) to the
+    /// main entity and removes it from the dead-letter sub-queue. The
+    /// messages of a subscription are sent to its topic, so every
+    /// subscription whose filter matches receives them again.
+    pub fn resubmit(&self, message: &DeadLetteredMessage) -> impl Future<Item = (), Error = AzureError> {
+        let mut headers = HeaderMap::new();
+        let send = match message.forward_headers(&mut headers) {
+            Ok(()) => Either::A(self.connection.send(self.main_entity_path(), message.body(), headers)),
+            Err(error) => Either::B(future::err(error)),
+        };
+        let remove = self.remove(message);
+
+        send.and_then(move |_| remove)
+    }
+
+    /// Deletes `message` from the dead-letter sub-queue.
+    pub fn remove(&self, message: &DeadLetteredMessage) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.lock_request(message, Method::DELETE)
+    }
+
+    /// Unlocks `message`, making it visible again in the dead-letter
+    /// sub-queue.
+    pub fn release(&self, message: &DeadLetteredMessage) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.lock_request(message, Method::PUT)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_urls() {
+        let queue = DeadLetterClient::for_queue("ns", "orders", "policy", "key").unwrap();
+        assert_eq!(queue.dead_letter_path(), "orders/$DeadLetterQueue");
+        assert_eq!(queue.main_entity_path(), "orders");
+        assert_eq!(
+            queue.connection.url(&queue.dead_letter_path()),
+            "https://ns.servicebus.windows.net/orders/$DeadLetterQueue"
+        );
+
+        let subscription = DeadLetterClient::for_subscription("ns", "events", "audit", "policy", "key").unwrap();
+        assert_eq!(subscription.dead_letter_path(), "events/subscriptions/audit/$DeadLetterQueue");
+        assert_eq!(subscription.main_entity_path(), "events");
+    }
+}
@@ -1,21 +1,25 @@
 use futures::future::*;
 
 use crate::azure::core::errors::AzureError;
-use crate::azure::service_bus::event_hub::send_event;
+use crate::azure::core::retry::{NoRetry, RetryPolicy};
+use crate::azure::core::sleeper::{Sleeper, ThreadSleeper};
+use crate::azure::core::throttling::{Throttler, ThrottlingPolicy};
+use crate::azure::core::tls::default_https_connector;
+use crate::azure::service_bus::event_hub::{send_batch, send_event, EventBatch, Sender};
+use crate::azure::service_bus::ConnectionString;
 use hyper;
+use std::sync::Arc;
 
 use time::Duration;
 
-use ring::{digest::SHA256, hmac::SigningKey};
-
-type HttpClient = hyper::Client<::hyper_tls::HttpsConnector<hyper::client::HttpConnector>>;
+use crate::azure::core::crypto::HmacKey;
 
 pub struct Client {
     namespace: String,
     event_hub: String,
     policy_name: String,
-    signing_key: SigningKey,
-    http_client: HttpClient,
+    signing_key: HmacKey,
+    sender: Sender,
 }
 
 impl Client {
@@ -26,38 +30,146 @@ impl Client {
         P: Into<String>,
         K: AsRef<str>,
     {
-        let signing_key = SigningKey::new(&SHA256, key.as_ref().as_bytes());
-        let http_client = hyper::Client::builder().build(::hyper_tls::HttpsConnector::new(4)?);
+        let signing_key = HmacKey::new(key.as_ref().as_bytes());
+        let http_client = hyper::Client::builder().build(default_https_connector()?);
 
         Ok(Client {
             namespace: namespace.into(),
             event_hub: event_hub.into(),
             policy_name: policy_name.into(),
             signing_key,
-            http_client,
+            sender: Sender {
+                http_client,
+                retry_policy: Arc::new(NoRetry),
+                sleeper: Arc::new(ThreadSleeper),
+                throttler: None,
+            },
         })
     }
 
+    /// Builds the client from the connection string of a shared access
+    /// policy. The connection strings of the namespace level policies do
+    /// not name the Event Hub: pass it as `event_hub`, it takes precedence
+    /// over the `EntityPath` of the connection string.
+    pub fn from_connection_string(connection_string: &str, event_hub: Option<&str>) -> Result<Client, AzureError> {
+        let connection_string = ConnectionString::parse(connection_string)?;
+        let event_hub = match (event_hub, connection_string.entity_path) {
+            (Some(event_hub), _) => event_hub.to_owned(),
+            (None, Some(entity_path)) => entity_path,
+            (None, None) => {
+                return Err(AzureError::InputParametersError(
+                    "the connection string has no EntityPath and no event hub was given".to_owned(),
+                ))
+            }
+        };
+
+        Client::new(
+            connection_string.namespace,
+            event_hub,
+            connection_string.policy_name,
+            connection_string.key,
+        )
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn event_hub(&self) -> &str {
+        &self.event_hub
+    }
+
+    /// Sets the policy used to retry the failed sends. Sending an event is
+    /// a `POST`, so the policy must allow it (see
+    /// `FixedRetry::retry_non_idempotent`): a retried event may be
+    /// delivered twice. Defaults to `NoRetry`.
+    pub fn with_retry_policy(mut self, retry_policy: Arc<dyn RetryPolicy>) -> Client {
+        self.sender.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &Arc<dyn RetryPolicy> {
+        &self.sender.retry_policy
+    }
+
+    /// Replaces the timer used between retries and by the throttling.
+    /// Defaults to `ThreadSleeper`.
+    pub fn with_sleeper(mut self, sleeper: Arc<dyn Sleeper>) -> Client {
+        self.sender.sleeper = sleeper;
+        self
+    }
+
+    pub fn sleeper(&self) -> &Arc<dyn Sleeper> {
+        &self.sender.sleeper
+    }
+
+    /// Limits the sends in flight and backs off when the service is busy,
+    /// per partition key: the events without a partition key share the
+    /// same limits. See `ThrottlingPolicy`.
+    pub fn with_throttling(mut self, policy: ThrottlingPolicy) -> Client {
+        self.sender.throttler = Some(Arc::new(Throttler::new(policy)));
+        self
+    }
+
+    pub fn throttler(&self) -> Option<&Throttler> {
+        self.sender.throttler.as_ref().map(|throttler| throttler.as_ref())
+    }
+
     pub fn send_event(&mut self, event_body: &str, duration: Duration) -> impl Future<Item = (), Error = AzureError> {
         {
             send_event(
-                &self.http_client,
+                &self.sender,
                 &self.namespace,
                 &self.event_hub,
                 &self.policy_name,
                 &self.signing_key,
+                None,
                 event_body,
                 duration,
             )
         }
     }
+
+    /// Sends an event to the partition chosen by `partition_key`: the
+    /// events with the same key are stored in order in the same partition.
+    pub fn send_event_with_partition_key(
+        &mut self,
+        partition_key: &str,
+        event_body: &str,
+        duration: Duration,
+    ) -> impl Future<Item = (), Error = AzureError> {
+        send_event(
+            &self.sender,
+            &self.namespace,
+            &self.event_hub,
+            &self.policy_name,
+            &self.signing_key,
+            Some(partition_key),
+            event_body,
+            duration,
+        )
+    }
+
+    /// Sends the events of `batch` with one request per partition key.
+    /// The future fails if any of the requests fails; the other requests
+    /// may have been delivered anyway.
+    pub fn send_batch(&mut self, batch: &EventBatch, duration: Duration) -> impl Future<Item = (), Error = AzureError> {
+        send_batch(
+            &self.sender,
+            &self.namespace,
+            &self.event_hub,
+            &self.policy_name,
+            &self.signing_key,
+            batch,
+            duration,
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
     #[allow(unused_imports)]
     use super::Client;
-    use ring::hmac;
 
     #[test]
     pub fn client_enc() {
@@ -65,9 +177,26 @@ mod test {
 
         let c = Client::new("namespace", "event_hub", "policy", "key").unwrap();
 
-        let sig = hmac::sign(&c.signing_key, str_to_sign.as_bytes());
-        let sig = ::base64::encode(sig.as_ref());
+        let sig = c.signing_key.sign_str(str_to_sign);
 
         assert_eq!(sig, "2UNXaoPpeJBAhh6qxmTqXyNzTpOflGO6IhxegeUQBcU=");
     }
+
+    #[test]
+    pub fn from_connection_string() {
+        let connection_string = "Endpoint=sb://namespace.servicebus.windows.net/;SharedAccessKeyName=policy;SharedAccessKey=key";
+
+        let c = Client::from_connection_string(connection_string, Some("event_hub")).unwrap();
+        assert_eq!(c.namespace(), "namespace");
+        assert_eq!(c.event_hub(), "event_hub");
+        assert_eq!(
+            c.signing_key.sign_str("This must be secret!"),
+            "2UNXaoPpeJBAhh6qxmTqXyNzTpOflGO6IhxegeUQBcU="
+        );
+
+        let c = Client::from_connection_string(&format!("{};EntityPath=other", connection_string), None).unwrap();
+        assert_eq!(c.event_hub(), "other");
+
+        assert!(Client::from_connection_string(connection_string, None).is_err());
+    }
 }
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+/// Events to send with `Client::send_batch`. The events sharing the same
+/// partition key (or without one) travel in a single request, so they
+/// land in order in the same partition; the requests of the different
+/// keys are sent concurrently.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventBatch {
+    events: Vec<(Option<String>, String)>,
+}
+
+impl EventBatch {
+    pub fn new() -> EventBatch {
+        EventBatch::default()
+    }
+
+    /// Adds an event the service assigns to a partition.
+    pub fn push<B: Into<String>>(&mut self, event_body: B) -> &mut EventBatch {
+        self.events.push((None, event_body.into()));
+        self
+    }
+
+    /// Adds an event sent to the partition chosen by `partition_key`.
+    pub fn push_with_partition_key<P, B>(&mut self, partition_key: P, event_body: B) -> &mut EventBatch
+    where
+        P: Into<String>,
+        B: Into<String>,
+    {
+        self.events.push((Some(partition_key.into()), event_body.into()));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    // the event bodies grouped by partition key, in insertion order
+    pub(crate) fn by_partition_key(&self) -> BTreeMap<Option<&str>, Vec<&str>> {
+        let mut groups = BTreeMap::new();
+        for (partition_key, event_body) in &self.events {
+            groups
+                .entry(partition_key.as_deref())
+                .or_insert_with(Vec::new)
+                .push(event_body.as_str());
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::service_bus::event_hub::batch_body;
+
+    #[test]
+    fn test_by_partition_key() {
+        let mut batch = EventBatch::new();
+        batch
+            .push_with_partition_key("b", "1")
+            .push("2")
+            .push_with_partition_key("a", "3")
+            .push_with_partition_key("b", "4");
+        assert_eq!(batch.len(), 4);
+
+        let groups: Vec<_> = batch.by_partition_key().into_iter().collect();
+        assert_eq!(groups, vec![(None, vec!["2"]), (Some("a"), vec!["3"]), (Some("b"), vec!["1", "4"])]);
+    }
+
+    #[test]
+    fn test_batch_body() {
+        assert_eq!(
+            batch_body(Some("pk"), &["one", "two"]).unwrap(),
+            r#"[{"Body":"one","BrokerProperties":{"PartitionKey":"pk"}},{"Body":"two","BrokerProperties":{"PartitionKey":"pk"}}]"#
+        );
+        assert_eq!(batch_body(None, &["one"]).unwrap(), r#"[{"Body":"one"}]"#);
+    }
+}
@@ -1,88 +1,168 @@
-use crate::azure::core::{
-    errors::{check_status_extract_body, AzureError},
-    COMPLETE_ENCODE_SET,
-};
-use futures::future::{self, Future};
-use hyper::{self, header, StatusCode};
-use ring::hmac;
-use std::ops::Add;
+use crate::azure::core::crypto::{service_bus_sas_token, HmacKey};
+use crate::azure::core::errors::{check_status_extract_body, AzureError};
+use crate::azure::core::pipeline::{PipelineFuture, PipelineRequest};
+use crate::azure::core::retry::{retry, RetryPolicy};
+use crate::azure::core::sleeper::Sleeper;
+use crate::azure::core::throttling::{throttled, Throttler};
+use crate::azure::service_bus::HttpClient;
+use futures::future::{self, Either, Future};
+use http;
+use hyper::header::{HeaderMap, HeaderValue};
+use hyper::{self, header, Method, StatusCode};
+use serde_json;
+use std::sync::Arc;
 use time::Duration;
 
 mod client;
 pub use self::client::Client;
+mod event_batch;
+pub use self::event_batch::EventBatch;
 
-type HttpClient = hyper::Client<::hyper_tls::HttpsConnector<hyper::client::HttpConnector>>;
+const BROKER_PROPERTIES: &str = "BrokerProperties";
+const BATCH_CONTENT_TYPE: &str = "application/vnd.microsoft.servicebus.json";
+
+#[derive(Debug, Serialize)]
+struct BrokerProperties<'a> {
+    #[serde(rename = "PartitionKey")]
+    partition_key: &'a str,
+}
+
+// an element of the batch request body
+#[derive(Debug, Serialize)]
+struct BatchedEvent<'a> {
+    #[serde(rename = "Body")]
+    body: &'a str,
+    #[serde(rename = "BrokerProperties", skip_serializing_if = "Option::is_none")]
+    broker_properties: Option<BrokerProperties<'a>>,
+}
+
+fn batch_body(partition_key: Option<&str>, events: &[&str]) -> Result<String, AzureError> {
+    let events: Vec<_> = events
+        .iter()
+        .map(|body| BatchedEvent {
+            body,
+            broker_properties: partition_key.map(|partition_key| BrokerProperties { partition_key }),
+        })
+        .collect();
+    Ok(serde_json::to_string(&events)?)
+}
 
 #[inline]
 fn send_event_prepare<B: Into<String>>(
-    http_client: &HttpClient,
     namespace: &str,
     event_hub: &str,
     policy_name: &str,
-    signing_key: &hmac::SigningKey,
+    signing_key: &HmacKey,
     event_body: B,
     duration: Duration,
-) -> Result<hyper::client::ResponseFuture, AzureError> {
+) -> Result<PipelineRequest, AzureError> {
     // prepare the url to call
     let url = format!("https://{}.servicebus.windows.net/{}/messages", namespace, event_hub);
     debug!("url == {:?}", url);
 
     // generate sas signature based on key name, key value, url and duration.
-    let sas = generate_signature(policy_name, signing_key, &url, duration);
+    let sas = service_bus_sas_token(policy_name, signing_key, &url, duration);
     debug!("sas == {}", sas);
 
-    let event_body = event_body.into();
-    let request = hyper::Request::post(url)
-        .header(header::AUTHORIZATION, ::bytes::Bytes::from(sas))
-        .body(event_body.into())?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::AUTHORIZATION, HeaderValue::from_str(&sas).map_err(http::Error::from)?);
 
-    Ok(http_client.request(request))
+    Ok(PipelineRequest {
+        method: Method::POST,
+        uri: url,
+        headers,
+        body: Some(event_body.into().into_bytes()),
+        pacer: None,
+    })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn send_event(
-    http_client: &HttpClient,
+    sender: &Sender,
     namespace: &str,
     event_hub: &str,
     policy_name: &str,
-    hmac: &hmac::SigningKey,
+    hmac: &HmacKey,
+    partition_key: Option<&str>,
     event_body: &str,
     duration: Duration,
 ) -> impl Future<Item = (), Error = AzureError> {
-    let req = send_event_prepare(http_client, namespace, event_hub, policy_name, hmac, event_body, duration);
+    let request = || -> Result<PipelineRequest, AzureError> {
+        let mut request = send_event_prepare(namespace, event_hub, policy_name, hmac, event_body, duration)?;
+        if let Some(partition_key) = partition_key {
+            let broker_properties = serde_json::to_string(&BrokerProperties { partition_key })?;
+            request.headers.insert(
+                BROKER_PROPERTIES,
+                HeaderValue::from_str(&broker_properties).map_err(http::Error::from)?,
+            );
+        }
+        Ok(request)
+    };
 
-    future::result(req)
-        .from_err()
-        .and_then(move |future_response| check_status_extract_body(future_response, StatusCode::CREATED))
-        .and_then(|_| Ok(()))
+    match request() {
+        Ok(request) => Either::A(sender.send(partition_key, request)),
+        Err(error) => Either::B(future::err(error)),
+    }
 }
 
-fn generate_signature(policy_name: &str, signing_key: &hmac::SigningKey, url: &str, ttl: Duration) -> String {
-    use url::{form_urlencoded::Serializer, percent_encoding::utf8_percent_encode};
+// one request per partition key, sent concurrently
+fn send_batch(
+    sender: &Sender,
+    namespace: &str,
+    event_hub: &str,
+    policy_name: &str,
+    hmac: &HmacKey,
+    batch: &EventBatch,
+    duration: Duration,
+) -> impl Future<Item = (), Error = AzureError> {
+    let requests: Result<Vec<_>, AzureError> = batch
+        .by_partition_key()
+        .into_iter()
+        .map(|(partition_key, events)| {
+            let body = batch_body(partition_key, &events)?;
+            let mut request = send_event_prepare(namespace, event_hub, policy_name, hmac, body, duration)?;
+            request
+                .headers
+                .insert(header::CONTENT_TYPE, HeaderValue::from_static(BATCH_CONTENT_TYPE));
+            Ok(sender.send(partition_key, request))
+        })
+        .collect();
+
+    future::result(requests).and_then(future::join_all).map(|_| ())
+}
 
-    let expiry = ::chrono::Utc::now().add(ttl).timestamp();
-    debug!("expiry == {:?}", expiry);
+// Sends the requests applying the retry policy and the throttling.
+#[derive(Debug, Clone)]
+struct Sender {
+    http_client: HttpClient,
+    retry_policy: Arc<dyn RetryPolicy>,
+    sleeper: Arc<dyn Sleeper>,
+    throttler: Option<Arc<Throttler>>,
+}
 
-    let url_encoded = utf8_percent_encode(url, COMPLETE_ENCODE_SET);
-    //debug!("url_encoded == {:?}", url_encoded);
+impl Sender {
+    fn send(&self, partition_key: Option<&str>, request: PipelineRequest) -> impl Future<Item = (), Error = AzureError> {
+        let sender = self.clone();
+        let partition_key = partition_key.unwrap_or_default().to_owned();
+        let attempt = move || sender.attempt(&partition_key, request.clone());
 
-    let str_to_sign = format!("{}\n{}", url_encoded, expiry);
-    debug!("str_to_sign == {:?}", str_to_sign);
+        let first = attempt();
+        let response: PipelineFuture = if self.retry_policy.is_retriable_method(&Method::POST) {
+            Box::new(retry(self.retry_policy.clone(), self.sleeper.clone(), first, move || Ok(attempt())))
+        } else {
+            first
+        };
 
-    let sig = hmac::sign(signing_key, str_to_sign.as_bytes());
-    let sig = {
-        let sig = ::base64::encode(sig.as_ref());
-        debug!("sig == {}", sig);
-        let mut ser = Serializer::new(String::new());
-        ser.append_pair("sig", &sig);
-        let sig = ser.finish();
-        debug!("sig == {}", sig);
-        sig
-    };
+        check_status_extract_body(response, StatusCode::CREATED).map(|_| ())
+    }
 
-    debug!("sig == {:?}", sig);
+    fn attempt(&self, partition_key: &str, request: PipelineRequest) -> PipelineFuture {
+        let http_client = self.http_client.clone();
+        let send = move || future::result(request.into_hyper_request()).and_then(move |request| http_client.request(request).from_err());
 
-    format!(
-        "SharedAccessSignature sr={}&{}&se={}&skn={}",
-        &url_encoded, sig, expiry, policy_name
-    )
+        match &self.throttler {
+            Some(throttler) => Box::new(throttled(throttler, self.sleeper.clone(), partition_key.to_owned(), send)),
+            None => Box::new(send()),
+        }
+    }
 }
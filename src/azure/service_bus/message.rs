@@ -0,0 +1,404 @@
+use crate::azure::core::errors::AzureError;
+use chrono::{DateTime, Utc};
+use http;
+use hyper::header::{self, HeaderMap, HeaderName, HeaderValue};
+use serde_json;
+use std::collections::BTreeMap;
+use std::str;
+use time::Duration;
+
+pub(crate) const BROKER_PROPERTIES: &str = "BrokerProperties";
+const DEAD_LETTER_REASON: &str = "DeadLetterReason";
+const DEAD_LETTER_ERROR_DESCRIPTION: &str = "DeadLetterErrorDescription";
+
+// the response headers that are not custom message properties
+const STANDARD_HEADERS: &[&str] = &[
+    BROKER_PROPERTIES,
+    "Content-Type",
+    "Content-Length",
+    "Date",
+    "Location",
+    "Server",
+    "Strict-Transport-Security",
+    "Transfer-Encoding",
+];
+
+/// The system properties of a received message.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BrokerProperties {
+    pub message_id: Option<String>,
+    pub correlation_id: Option<String>,
+    pub session_id: Option<String>,
+    pub label: Option<String>,
+    pub delivery_count: Option<u32>,
+    pub sequence_number: Option<u64>,
+    pub lock_token: Option<String>,
+    pub enqueued_time_utc: Option<String>,
+}
+
+// the properties preserved when a message is sent again
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct ForwardedBrokerProperties<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a str>,
+}
+
+// the properties that can be set on a sent message
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SentBrokerProperties {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partition_key: Option<String>,
+    // in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_to_live: Option<i64>,
+    // RFC 2822
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_enqueue_time_utc: Option<String>,
+}
+
+/// A message to send to a queue or a topic, with its system (broker)
+/// properties and its custom properties.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Message {
+    body: String,
+    content_type: Option<String>,
+    broker_properties: SentBrokerProperties,
+    // JSON encoded values
+    properties: BTreeMap<String, String>,
+}
+
+impl Message {
+    pub fn new<B: Into<String>>(body: B) -> Message {
+        Message {
+            body: body.into(),
+            ..Message::default()
+        }
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn with_content_type<S: Into<String>>(mut self, content_type: S) -> Message {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// The id used by the duplicate detection.
+    pub fn with_message_id<S: Into<String>>(mut self, message_id: S) -> Message {
+        self.broker_properties.message_id = Some(message_id.into());
+        self
+    }
+
+    pub fn with_correlation_id<S: Into<String>>(mut self, correlation_id: S) -> Message {
+        self.broker_properties.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Required by the session-enabled entities.
+    pub fn with_session_id<S: Into<String>>(mut self, session_id: S) -> Message {
+        self.broker_properties.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_label<S: Into<String>>(mut self, label: S) -> Message {
+        self.broker_properties.label = Some(label.into());
+        self
+    }
+
+    pub fn with_reply_to<S: Into<String>>(mut self, reply_to: S) -> Message {
+        self.broker_properties.reply_to = Some(reply_to.into());
+        self
+    }
+
+    pub fn with_to<S: Into<String>>(mut self, to: S) -> Message {
+        self.broker_properties.to = Some(to.into());
+        self
+    }
+
+    /// Chooses the partition of the partitioned entities.
+    pub fn with_partition_key<S: Into<String>>(mut self, partition_key: S) -> Message {
+        self.broker_properties.partition_key = Some(partition_key.into());
+        self
+    }
+
+    /// Whole seconds only.
+    pub fn with_time_to_live(mut self, time_to_live: Duration) -> Message {
+        self.broker_properties.time_to_live = Some(time_to_live.num_seconds());
+        self
+    }
+
+    /// The message is not visible to the receivers before this time.
+    pub fn with_scheduled_enqueue_time(mut self, time: DateTime<Utc>) -> Message {
+        self.broker_properties.scheduled_enqueue_time_utc = Some(time.format("%a, %d %b %Y %T GMT").to_string());
+        self
+    }
+
+    /// Adds a custom property, available to the subscription filters.
+    pub fn with_property<N, V>(mut self, name: N, value: V) -> Message
+    where
+        N: Into<String>,
+        V: Into<serde_json::Value>,
+    {
+        self.properties.insert(name.into(), value.into().to_string());
+        self
+    }
+
+    pub(crate) fn headers(&self) -> Result<HeaderMap, AzureError> {
+        let mut headers = HeaderMap::new();
+        if self.broker_properties != SentBrokerProperties::default() {
+            let broker_properties = serde_json::to_string(&self.broker_properties)?;
+            headers.insert(
+                BROKER_PROPERTIES,
+                HeaderValue::from_str(&broker_properties).map_err(http::Error::from)?,
+            );
+        }
+
+        if let Some(content_type) = &self.content_type {
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_str(content_type).map_err(http::Error::from)?,
+            );
+        }
+
+        for (name, value) in &self.properties {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(http::Error::from)?;
+            headers.insert(name, HeaderValue::from_str(value).map_err(http::Error::from)?);
+        }
+
+        Ok(headers)
+    }
+}
+
+/// A message received in peek-lock mode. It stays invisible to the other
+/// receivers until it's completed (removed), abandoned (released) or its
+/// lock expires.
+#[derive(Debug, Clone)]
+pub struct ReceivedMessage {
+    body: String,
+    content_type: Option<String>,
+    broker_properties: BrokerProperties,
+    // raw (JSON encoded) values
+    properties: BTreeMap<String, String>,
+    lock_location: String,
+}
+
+impl ReceivedMessage {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &[u8]) -> Result<ReceivedMessage, AzureError> {
+        let broker_properties = match headers.get(BROKER_PROPERTIES) {
+            Some(broker_properties) => serde_json::from_str(broker_properties.to_str()?)?,
+            None => BrokerProperties::default(),
+        };
+
+        let lock_location = headers
+            .get(header::LOCATION)
+            .ok_or_else(|| AzureError::HeaderNotFound(header::LOCATION.as_str().to_owned()))?
+            .to_str()?
+            .to_owned();
+
+        let content_type = match headers.get(header::CONTENT_TYPE) {
+            Some(content_type) => Some(content_type.to_str()?.to_owned()),
+            None => None,
+        };
+
+        let mut properties = BTreeMap::new();
+        for (name, value) in headers {
+            if !STANDARD_HEADERS.iter().any(|standard| name.as_str().eq_ignore_ascii_case(standard)) {
+                properties.insert(name.as_str().to_owned(), value.to_str()?.to_owned());
+            }
+        }
+
+        Ok(ReceivedMessage {
+            body: str::from_utf8(body)?.to_owned(),
+            content_type,
+            broker_properties,
+            properties,
+            lock_location,
+        })
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    pub fn broker_properties(&self) -> &BrokerProperties {
+        &self.broker_properties
+    }
+
+    /// The names of the custom properties, in lowercase.
+    pub fn property_names(&self) -> impl Iterator<Item = &str> {
+        self.properties.keys().map(String::as_str)
+    }
+
+    /// The custom property `name` (case insensitive). String values are
+    /// returned unquoted, the others as JSON.
+    pub fn property(&self, name: &str) -> Option<String> {
+        self.properties
+            .get(&name.to_ascii_lowercase())
+            .map(|value| match serde_json::from_str::<serde_json::Value>(value) {
+                Ok(serde_json::Value::String(value)) => value,
+                _ => value.clone(),
+            })
+    }
+
+    /// Why the message was dead-lettered, for example
+    /// `MaxDeliveryCountExceeded`.
+    pub fn dead_letter_reason(&self) -> Option<String> {
+        self.property(DEAD_LETTER_REASON)
+    }
+
+    pub fn dead_letter_error_description(&self) -> Option<String> {
+        self.property(DEAD_LETTER_ERROR_DESCRIPTION)
+    }
+
+    pub fn lock_location(&self) -> &str {
+        &self.lock_location
+    }
+
+    // the headers to send a copy of the message (body, content type,
+    // label, ids and custom properties, except the dead-letter ones)
+    pub(crate) fn forward_headers(&self, headers: &mut HeaderMap) -> Result<(), AzureError> {
+        let broker_properties = serde_json::to_string(&ForwardedBrokerProperties {
+            message_id: self.broker_properties.message_id.as_deref(),
+            correlation_id: self.broker_properties.correlation_id.as_deref(),
+            session_id: self.broker_properties.session_id.as_deref(),
+            label: self.broker_properties.label.as_deref(),
+        })?;
+        headers.insert(
+            BROKER_PROPERTIES,
+            HeaderValue::from_str(&broker_properties).map_err(http::Error::from)?,
+        );
+
+        if let Some(content_type) = &self.content_type {
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_str(content_type).map_err(http::Error::from)?,
+            );
+        }
+
+        for (name, value) in &self.properties {
+            if name.eq_ignore_ascii_case(DEAD_LETTER_REASON) || name.eq_ignore_ascii_case(DEAD_LETTER_ERROR_DESCRIPTION) {
+                continue;
+            }
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(http::Error::from)?;
+            headers.insert(name, HeaderValue::from_str(value).map_err(http::Error::from)?);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dead_lettered() -> ReceivedMessage {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            BROKER_PROPERTIES,
+            HeaderValue::from_static(
+                r#"{"DeliveryCount":10,"LockToken":"6ab4e5fb-2ca5-4d49-ba8c-2e0a0a1d4e8b","MessageId":"m1","SequenceNumber":31,"Label":"order"}"#,
+            ),
+        );
+        headers.insert(
+            header::LOCATION,
+            HeaderValue::from_static("https://ns.servicebus.windows.net/q/$DeadLetterQueue/messages/31/6ab4e5fb"),
+        );
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(header::DATE, HeaderValue::from_static("Mon, 08 Apr 2019 10:00:00 GMT"));
+        headers.insert("DeadLetterReason", HeaderValue::from_static("\"MaxDeliveryCountExceeded\""));
+        headers.insert(
+            "DeadLetterErrorDescription",
+            HeaderValue::from_static("\"Message could not be consumed after 10 delivery attempts.\""),
+        );
+        headers.insert("Priority", HeaderValue::from_static("3"));
+
+        ReceivedMessage::from_response(&headers, b"{\"id\":1}").unwrap()
+    }
+
+    #[test]
+    fn test_from_response() {
+        let message = dead_lettered();
+
+        assert_eq!(message.body(), "{\"id\":1}");
+        assert_eq!(message.content_type(), Some("application/json"));
+        assert_eq!(message.broker_properties().delivery_count, Some(10));
+        assert_eq!(message.broker_properties().sequence_number, Some(31));
+        assert_eq!(message.broker_properties().message_id.as_deref(), Some("m1"));
+        assert_eq!(
+            message.lock_location(),
+            "https://ns.servicebus.windows.net/q/$DeadLetterQueue/messages/31/6ab4e5fb"
+        );
+        assert_eq!(message.dead_letter_reason().as_deref(), Some("MaxDeliveryCountExceeded"));
+        assert_eq!(
+            message.dead_letter_error_description().as_deref(),
+            Some("Message could not be consumed after 10 delivery attempts.")
+        );
+        assert_eq!(message.property("priority").as_deref(), Some("3"));
+        assert_eq!(
+            message.property_names().collect::<Vec<_>>(),
+            vec!["deadlettererrordescription", "deadletterreason", "priority"]
+        );
+    }
+
+    #[test]
+    fn test_message_headers() {
+        let time = DateTime::parse_from_rfc3339("2019-04-08T10:00:00Z").unwrap().with_timezone(&Utc);
+        let message = Message::new("{\"id\":1}")
+            .with_content_type("application/json")
+            .with_message_id("m1")
+            .with_label("order")
+            .with_time_to_live(Duration::minutes(10))
+            .with_scheduled_enqueue_time(time)
+            .with_property("Priority", 3)
+            .with_property("Region", "emea");
+        let headers = message.headers().unwrap();
+
+        assert_eq!(
+            headers[BROKER_PROPERTIES],
+            r#"{"MessageId":"m1","Label":"order","TimeToLive":600,"ScheduledEnqueueTimeUtc":"Mon, 08 Apr 2019 10:00:00 GMT"}"#
+        );
+        assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+        assert_eq!(headers["priority"], "3");
+        assert_eq!(headers["region"], "\"emea\"");
+
+        assert!(Message::new("plain").headers().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_forward_headers() {
+        let mut headers = HeaderMap::new();
+        dead_lettered().forward_headers(&mut headers).unwrap();
+
+        assert_eq!(headers[BROKER_PROPERTIES], r#"{"MessageId":"m1","Label":"order"}"#);
+        assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+        assert_eq!(headers["priority"], "3");
+        assert!(!headers.contains_key(DEAD_LETTER_REASON));
+        assert!(!headers.contains_key(DEAD_LETTER_ERROR_DESCRIPTION));
+    }
+}
@@ -1 +1,13 @@
+use hyper;
+
+pub mod bridge;
+mod connection;
+mod connection_string;
+pub use self::connection_string::ConnectionString;
+pub mod dead_letter;
 pub mod event_hub;
+pub mod message;
+pub mod queue_client;
+pub mod topic_client;
+
+type HttpClient = hyper::Client<crate::azure::core::tls::HttpsConnector<hyper::client::HttpConnector>>;
@@ -0,0 +1,74 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::service_bus::connection::Connection;
+use crate::azure::service_bus::message::{Message, ReceivedMessage};
+use futures::future::{self, Either};
+use futures::Future;
+use hyper::header::HeaderMap;
+use hyper::Method;
+use time::Duration;
+
+/// Sends and receives (peek-lock) the messages of a Service Bus queue.
+#[derive(Clone)]
+pub struct QueueClient {
+    connection: Connection,
+    queue: String,
+}
+
+impl QueueClient {
+    pub fn new<N, Q, P, K>(namespace: N, queue: Q, policy_name: P, key: K) -> Result<QueueClient, AzureError>
+    where
+        N: Into<String>,
+        Q: Into<String>,
+        P: Into<String>,
+        K: AsRef<str>,
+    {
+        Ok(QueueClient {
+            connection: Connection::new(namespace.into(), policy_name.into(), key.as_ref())?,
+            queue: queue.into(),
+        })
+    }
+
+    /// Validity of the SAS tokens generated for each request. Defaults to
+    /// one hour.
+    pub fn with_token_duration(mut self, token_duration: Duration) -> QueueClient {
+        self.connection.set_token_duration(token_duration);
+        self
+    }
+
+    pub fn queue(&self) -> &str {
+        &self.queue
+    }
+
+    pub fn send_message(&self, body: &str) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.send(&self.queue, body, HeaderMap::new())
+    }
+
+    /// Sends a message with its broker and custom properties.
+    pub fn send(&self, message: &Message) -> impl Future<Item = (), Error = AzureError> {
+        match message.headers() {
+            Ok(headers) => Either::A(self.connection.send(&self.queue, message.body(), headers)),
+            Err(error) => Either::B(future::err(error)),
+        }
+    }
+
+    /// Locks the next message of the queue, if any.
+    pub fn receive_message(&self) -> impl Future<Item = Option<ReceivedMessage>, Error = AzureError> {
+        self.connection.receive(&self.queue)
+    }
+
+    /// Deletes a received message from the queue.
+    pub fn complete_message(&self, message: &ReceivedMessage) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.lock_request(message, Method::DELETE)
+    }
+
+    /// Unlocks a received message, making it visible again.
+    pub fn abandon_message(&self, message: &ReceivedMessage) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.lock_request(message, Method::PUT)
+    }
+
+    /// Extends the lock of a received message by the lock duration of the
+    /// queue.
+    pub fn renew_lock(&self, message: &ReceivedMessage) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.lock_request(message, Method::POST)
+    }
+}
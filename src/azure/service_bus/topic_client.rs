@@ -0,0 +1,133 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::service_bus::connection::Connection;
+use crate::azure::service_bus::message::{Message, ReceivedMessage};
+use futures::future::{self, Either};
+use futures::Future;
+use hyper::header::HeaderMap;
+use hyper::Method;
+use time::Duration;
+
+/// Sends messages to a Service Bus topic. Every subscription whose filter
+/// matches gets a copy.
+#[derive(Clone)]
+pub struct TopicClient {
+    connection: Connection,
+    topic: String,
+}
+
+impl TopicClient {
+    pub fn new<N, T, P, K>(namespace: N, topic: T, policy_name: P, key: K) -> Result<TopicClient, AzureError>
+    where
+        N: Into<String>,
+        T: Into<String>,
+        P: Into<String>,
+        K: AsRef<str>,
+    {
+        Ok(TopicClient {
+            connection: Connection::new(namespace.into(), policy_name.into(), key.as_ref())?,
+            topic: topic.into(),
+        })
+    }
+
+    /// Validity of the SAS tokens generated for each request. Defaults to
+    /// one hour.
+    pub fn with_token_duration(mut self, token_duration: Duration) -> TopicClient {
+        self.connection.set_token_duration(token_duration);
+        self
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn send_message(&self, body: &str) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.send(&self.topic, body, HeaderMap::new())
+    }
+
+    /// Sends a message with its broker and custom properties.
+    pub fn send(&self, message: &Message) -> impl Future<Item = (), Error = AzureError> {
+        match message.headers() {
+            Ok(headers) => Either::A(self.connection.send(&self.topic, message.body(), headers)),
+            Err(error) => Either::B(future::err(error)),
+        }
+    }
+}
+
+/// Receives (peek-lock) the messages of a topic subscription.
+#[derive(Clone)]
+pub struct SubscriptionClient {
+    connection: Connection,
+    topic: String,
+    subscription: String,
+}
+
+impl SubscriptionClient {
+    pub fn new<N, T, S, P, K>(namespace: N, topic: T, subscription: S, policy_name: P, key: K) -> Result<SubscriptionClient, AzureError>
+    where
+        N: Into<String>,
+        T: Into<String>,
+        S: Into<String>,
+        P: Into<String>,
+        K: AsRef<str>,
+    {
+        Ok(SubscriptionClient {
+            connection: Connection::new(namespace.into(), policy_name.into(), key.as_ref())?,
+            topic: topic.into(),
+            subscription: subscription.into(),
+        })
+    }
+
+    /// Validity of the SAS tokens generated for each request. Defaults to
+    /// one hour.
+    pub fn with_token_duration(mut self, token_duration: Duration) -> SubscriptionClient {
+        self.connection.set_token_duration(token_duration);
+        self
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn subscription(&self) -> &str {
+        &self.subscription
+    }
+
+    fn path(&self) -> String {
+        format!("{}/subscriptions/{}", self.topic, self.subscription)
+    }
+
+    /// Locks the next message of the subscription, if any.
+    pub fn receive_message(&self) -> impl Future<Item = Option<ReceivedMessage>, Error = AzureError> {
+        self.connection.receive(&self.path())
+    }
+
+    /// Deletes a received message from the subscription.
+    pub fn complete_message(&self, message: &ReceivedMessage) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.lock_request(message, Method::DELETE)
+    }
+
+    /// Unlocks a received message, making it visible again.
+    pub fn abandon_message(&self, message: &ReceivedMessage) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.lock_request(message, Method::PUT)
+    }
+
+    /// Extends the lock of a received message by the lock duration of the
+    /// subscription.
+    pub fn renew_lock(&self, message: &ReceivedMessage) -> impl Future<Item = (), Error = AzureError> {
+        self.connection.lock_request(message, Method::POST)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_urls() {
+        let subscription = SubscriptionClient::new("ns", "events", "audit", "policy", "key").unwrap();
+        assert_eq!(
+            subscription.connection.url(&subscription.path()),
+            "https://ns.servicebus.windows.net/events/subscriptions/audit"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::storage::client::Client;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+/// Builds the client of a storage account the first time the
+/// `AccountRegistry` is asked for it. Implement it to look the
+/// credentials up in a secret store, or to configure the clients (retry
+/// policy, throttling, etc...) before they are shared.
+pub trait CredentialsProvider: Debug + Send + Sync {
+    fn client(&self, account: &str) -> Result<Client, AzureError>;
+}
+
+/// The account keys, by account name.
+impl CredentialsProvider for HashMap<String, String> {
+    fn client(&self, account: &str) -> Result<Client, AzureError> {
+        match self.get(account) {
+            Some(key) => Client::new(account, key),
+            None => Err(AzureError::InputParametersError(format!(
+                "no credentials for the account {}",
+                account
+            ))),
+        }
+    }
+}
+
+/// The clients of many storage accounts, by account name, for the
+/// services that fan out across them. The clients are built on first use
+/// with the `CredentialsProvider` and then kept: the clones returned
+/// share the connection pool (and the circuit breaker, the throttler,
+/// etc...) of the registered client.
+#[derive(Debug)]
+pub struct AccountRegistry {
+    provider: Arc<dyn CredentialsProvider>,
+    clients: Mutex<HashMap<String, Client>>,
+}
+
+impl AccountRegistry {
+    pub fn new(provider: Arc<dyn CredentialsProvider>) -> AccountRegistry {
+        AccountRegistry {
+            provider,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The client of `account`, built with the provider if it is not
+    /// registered yet. The provider errors are returned as they are and
+    /// nothing is registered: the next call asks the provider again.
+    pub fn client(&self, account: &str) -> Result<Client, AzureError> {
+        if let Some(client) = self.clients.lock().unwrap().get(account) {
+            return Ok(client.clone());
+        }
+
+        // the lock is not held while the provider runs, if two threads
+        // race for the same account the first client registered wins
+        let client = self.provider.client(account)?;
+        Ok(self.clients.lock().unwrap().entry(account.to_owned()).or_insert(client).clone())
+    }
+
+    /// Registers a client built elsewhere, replacing the current one of
+    /// the same account.
+    pub fn insert(&self, client: Client) {
+        self.clients.lock().unwrap().insert(client.account().to_owned(), client);
+    }
+
+    /// Forgets the client of `account`, for example after its key has
+    /// been rotated: the next `client` call asks the provider again.
+    pub fn remove(&self, account: &str) -> Option<Client> {
+        self.clients.lock().unwrap().remove(account)
+    }
+
+    /// The accounts registered so far.
+    pub fn accounts(&self) -> Vec<String> {
+        self.clients.lock().unwrap().keys().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct Counting(AtomicUsize);
+
+    impl CredentialsProvider for Counting {
+        fn client(&self, account: &str) -> Result<Client, AzureError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            if account == "unknown" {
+                return Err(AzureError::GenericError);
+            }
+            Client::new(account, "YWNjb3VudGtleQ==")
+        }
+    }
+
+    #[test]
+    fn test_lazy_construction() {
+        let provider = Arc::new(Counting::default());
+        let registry = AccountRegistry::new(provider.clone());
+        assert!(registry.is_empty());
+
+        assert_eq!(registry.client("first").unwrap().account(), "first");
+        assert_eq!(registry.client("first").unwrap().account(), "first");
+        assert_eq!(registry.client("second").unwrap().account(), "second");
+        assert_eq!(provider.0.load(Ordering::SeqCst), 2);
+
+        assert!(registry.client("unknown").is_err());
+        assert_eq!(registry.len(), 2);
+
+        assert!(registry.remove("first").is_some());
+        registry.client("first").unwrap();
+        assert_eq!(provider.0.load(Ordering::SeqCst), 4);
+
+        let mut accounts = registry.accounts();
+        accounts.sort();
+        assert_eq!(accounts, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_static_keys() {
+        let mut keys = HashMap::new();
+        keys.insert("account".to_owned(), "YWNjb3VudGtleQ==".to_owned());
+        let registry = AccountRegistry::new(Arc::new(keys));
+
+        assert_eq!(registry.client("account").unwrap().key(), "YWNjb3VudGtleQ==");
+        assert!(registry.client("other").is_err());
+    }
+}
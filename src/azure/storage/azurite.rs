@@ -0,0 +1,54 @@
+//! Helpers for the tests run against Azurite, the storage emulator, with
+//! the `test_azurite` feature. Start it with the default ports, for
+//! example `docker run -p 10000:10000 -p 10001:10001 -p 10002:10002
+//! mcr.microsoft.com/azure-storage/azurite`, or point the tests at
+//! another instance with the `AZURITE_CONNECTION_STRING` environment
+//! variable.
+
+use crate::azure::core::errors::AzureError;
+use crate::azure::storage::client::Client;
+use uuid::Uuid;
+
+pub const CONNECTION_STRING_VAR: &str = "AZURITE_CONNECTION_STRING";
+
+/// A client for the emulator of `AZURITE_CONNECTION_STRING`, the local one
+/// if the variable is not set.
+pub fn client() -> Result<Client, AzureError> {
+    match std::env::var(CONNECTION_STRING_VAR) {
+        Ok(connection_string) => Client::from_connection_string(&connection_string),
+        Err(_) => Client::local_emulator(),
+    }
+}
+
+/// A name no other test uses, valid for the containers, the queues and
+/// the tables: lowercase alphanumeric, starting with a letter and at most
+/// 63 characters long.
+pub fn unique_name(prefix: &str) -> String {
+    let mut name: String = prefix
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase();
+    name.push_str(&Uuid::new_v4().to_simple().to_string());
+    name.truncate(63);
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert(0, 't');
+        name.truncate(63);
+    }
+    name
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unique_name() {
+        let name = unique_name("Blob-Test");
+        assert!(name.starts_with("blobtest"));
+        assert_eq!(name.len(), 8 + 32);
+        assert_ne!(name, unique_name("Blob-Test"));
+        assert!(unique_name("").starts_with(|c: char| c.is_ascii_alphabetic()));
+        assert_eq!(unique_name(&"a".repeat(100)).len(), 63);
+    }
+}
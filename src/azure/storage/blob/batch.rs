@@ -0,0 +1,478 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError, UnexpectedHTTPResult};
+use crate::azure::core::util::format_header_value;
+use crate::azure::core::{
+    client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, ApiVersionOption, ApiVersionSupport,
+    ExtraHeadersOption, ExtraHeadersSupport, RequestId, COMPLETE_ENCODE_SET,
+};
+use crate::azure::storage::blob::BLOB_NAME_ENCODE_SET;
+use crate::azure::storage::client::Client;
+use crate::azure::storage::multipart::{boundary, find_header, split_headers, split_parts, split_status_line};
+use crate::azure::storage::rest_client::{generate_authorization, ServiceType, HEADER_DATE, HEADER_VERSION};
+use chrono::{DateTime, Utc};
+use futures::future::*;
+use hyper::header::{HeaderMap, HeaderName, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::{Method, StatusCode};
+use url::percent_encoding::utf8_percent_encode;
+use url::Url;
+use uuid::Uuid;
+
+/// Azure rejects batches with more than 256 sub-requests.
+pub const MAX_BATCH_SUBREQUESTS: usize = 256;
+
+// Blob Batch is not available before this version
+const BLOB_BATCH_VERSION: &str = "2018-11-09";
+
+const ACCESS_TIER: &str = "x-ms-access-tier";
+
+#[derive(Debug, Clone, PartialEq)]
+enum BlobBatchOperation {
+    Delete(String, String),
+    SetTier(String, String, String),
+}
+
+impl BlobBatchOperation {
+    fn is_delete(&self) -> bool {
+        match self {
+            BlobBatchOperation::Delete(..) => true,
+            BlobBatchOperation::SetTier(..) => false,
+        }
+    }
+
+    fn expected_status_code(&self) -> StatusCode {
+        match self {
+            BlobBatchOperation::Delete(..) => StatusCode::ACCEPTED,
+            BlobBatchOperation::SetTier(..) => StatusCode::OK,
+        }
+    }
+}
+
+/// Blob batch: up to 256 delete or set tier sub-requests sent in a single
+/// request. Unlike the table batches, the sub-requests are not atomic:
+/// each one succeeds or fails on its own. A batch cannot mix deletes and
+/// set tiers. The sub-requests are signed when `finalize` builds the
+/// batch: if the account key is rotated with `Client::set_key` while the
+/// batch is in flight only the batch request itself is signed again, and
+/// the sub-requests fail with 403. Such a batch has to be sent again.
+///
+/// The extra headers and the `x-ms-version` apply to the batch request,
+/// not to the sub-requests.
+#[derive(Clone)]
+pub struct BlobBatchBuilder<'a> {
+    client: &'a Client,
+    operations: Vec<BlobBatchOperation>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> BlobBatchBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> BlobBatchBuilder<'a> {
+        BlobBatchBuilder {
+            client,
+            operations: Vec::new(),
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn delete(mut self, container_name: &str, blob_name: &str) -> Self {
+        self.operations
+            .push(BlobBatchOperation::Delete(container_name.to_owned(), blob_name.to_owned()));
+        self
+    }
+
+    /// Sets the access tier (`Hot`, `Cool` or `Archive`) of a block blob.
+    pub fn set_tier(mut self, container_name: &str, blob_name: &str, access_tier: &str) -> Self {
+        self.operations.push(BlobBatchOperation::SetTier(
+            container_name.to_owned(),
+            blob_name.to_owned(),
+            access_tier.to_owned(),
+        ));
+        self
+    }
+
+    fn validate(&self) -> Result<(), AzureError> {
+        if self.operations.is_empty() || self.operations.len() > MAX_BATCH_SUBREQUESTS {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "a blob batch must contain between 1 and {} sub-requests, found {}",
+                MAX_BATCH_SUBREQUESTS,
+                self.operations.len()
+            )));
+        }
+        let is_delete = self.operations[0].is_delete();
+        if self.operations.iter().any(|operation| operation.is_delete() != is_delete) {
+            return Err(AzureError::GenericErrorWithText(
+                "a blob batch cannot mix deletes and set tiers".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sends the batch. The future fails only if the batch as a whole is
+    /// rejected: the outcome of the single sub-requests is in the returned
+    /// `BlobBatchResponse`.
+    pub fn finalize(self) -> impl Future<Item = BlobBatchResponse, Error = AzureError> {
+        let expected: Vec<StatusCode> = self.operations.iter().map(|operation| operation.expected_status_code()).collect();
+        let now = match self.client.clock_skew() {
+            Some(clock_skew) => clock_skew.now(),
+            None => Utc::now(),
+        };
+
+        let req = self.validate().and_then(|()| {
+            let api_version = ApiVersionOption::required_api_version(&self, self.client, Some(BLOB_BATCH_VERSION))?;
+            let batch_boundary = format!("batch_{}", Uuid::new_v4());
+            let payload = generate_payload(self.client, &self.operations, &batch_boundary, now)?;
+            let content_type = format!("multipart/mixed; boundary={}", batch_boundary);
+
+            let uri = format!("{}/?comp=batch", self.client.blob_uri());
+            trace!("uri == {:?}", uri);
+
+            self.client.perform_request(
+                &uri,
+                &Method::POST,
+                |ref mut request| {
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                    request.header(CONTENT_TYPE, &content_type as &str);
+                    ExtraHeadersOption::add_header(&self, request);
+                },
+                Some(payload.as_bytes()),
+            )
+        });
+
+        done(req).from_err().and_then(move |future_response| {
+            check_status_extract_headers_and_body_as_string(future_response, StatusCode::ACCEPTED)
+                .and_then(move |(headers, body)| done(BlobBatchResponse::from_response(&headers, &body, &expected)))
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<BlobBatchResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
+
+impl<'a> ExtraHeadersOption<'a> for BlobBatchBuilder<'a> {
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a> ExtraHeadersSupport<'a> for BlobBatchBuilder<'a> {
+    type O = Self;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a> ApiVersionOption<'a> for BlobBatchBuilder<'a> {
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a> ApiVersionSupport<'a> for BlobBatchBuilder<'a> {
+    type O = Self;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        BlobBatchBuilder {
+            api_version: Some(api_version),
+            ..self
+        }
+    }
+}
+
+// Each sub-request is dated and signed on its own, as if it were sent
+// alone. The sub-requests of a SAS client carry the SAS token instead,
+// those of a bearer token client the bearer token.
+fn generate_payload(
+    client: &Client,
+    operations: &[BlobBatchOperation],
+    batch_boundary: &str,
+    now: DateTime<Utc>,
+) -> Result<String, AzureError> {
+    let date = now.format("%a, %d %h %Y %T GMT").to_string();
+    let mut payload = String::new();
+
+    for (content_id, operation) in operations.iter().enumerate() {
+        let mut headers = HeaderMap::new();
+        headers.insert(HEADER_DATE, format_header_value(&date)?);
+        headers.insert(CONTENT_LENGTH, format_header_value(0)?);
+
+        let (method, container_name, blob_name, params) = match operation {
+            BlobBatchOperation::Delete(container_name, blob_name) => (Method::DELETE, container_name, blob_name, ""),
+            BlobBatchOperation::SetTier(container_name, blob_name, access_tier) => {
+                headers.insert(ACCESS_TIER, format_header_value(access_tier)?);
+                (Method::PUT, container_name, blob_name, "?comp=tier")
+            }
+        };
+
+        let uri = format!(
+            "{}/{}/{}{}",
+            client.blob_uri(),
+            utf8_percent_encode(container_name, COMPLETE_ENCODE_SET),
+            utf8_percent_encode(blob_name, BLOB_NAME_ENCODE_SET),
+            params
+        );
+        let url = Url::parse(&client.add_sas_token_to_uri(&uri))?;
+
+        if let Some(bearer_token) = client.bearer_token() {
+            headers.insert(AUTHORIZATION, format_header_value(format!("Bearer {}", bearer_token))?);
+        } else if url.query_pairs().find(|p| p.0 == "sig").is_none() {
+            let auth = generate_authorization(&headers, &url, &method, client.account(), &client.key(), ServiceType::Blob);
+            headers.insert(AUTHORIZATION, format_header_value(auth)?);
+        }
+
+        payload.push_str(&format!(
+            "--{}\r\nContent-Type: application/http\r\nContent-Transfer-Encoding: binary\r\nContent-ID: {}\r\n\r\n",
+            batch_boundary, content_id
+        ));
+        match url.query() {
+            Some(query) => payload.push_str(&format!("{} {}?{} HTTP/1.1\r\n", method, url.path(), query)),
+            None => payload.push_str(&format!("{} {} HTTP/1.1\r\n", method, url.path())),
+        }
+        for (name, value) in headers.iter() {
+            payload.push_str(&format!("{}: {}\r\n", name, value.to_str()?));
+        }
+        payload.push_str("\r\n");
+    }
+
+    payload.push_str(&format!("--{}--\r\n", batch_boundary));
+    Ok(payload)
+}
+
+/// The outcome of a blob batch: a response for each sub-request, in the
+/// same order they were added to the `BlobBatchBuilder`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlobBatchResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub operations: Vec<BlobBatchOperationResponse>,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlobBatchOperationResponse {
+    /// The position of the sub-request in the batch. Missing when the
+    /// service rejected the batch content without running it.
+    pub index: Option<usize>,
+    pub status_code: StatusCode,
+    pub request_id: Option<String>,
+    pub error: Option<UnexpectedHTTPResult>,
+}
+
+impl BlobBatchOperationResponse {
+    pub fn is_successful(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl BlobBatchResponse {
+    pub fn is_successful(&self) -> bool {
+        self.operations.iter().all(|operation| operation.is_successful())
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &BlobBatchOperationResponse> {
+        self.operations.iter().filter(|operation| !operation.is_successful())
+    }
+
+    pub(crate) fn from_response(headers: &HeaderMap, body: &str, expected: &[StatusCode]) -> Result<BlobBatchResponse, AzureError> {
+        let mut operations = parse_batch_body(body, expected)?;
+        operations.sort_by_key(|operation| operation.index);
+
+        Ok(BlobBatchResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            date: date_from_headers(headers)?,
+            operations,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
+
+fn parse_batch_body(body: &str, expected: &[StatusCode]) -> Result<Vec<BlobBatchOperationResponse>, AzureError> {
+    let boundary = boundary(body)?;
+
+    split_parts(body, boundary)
+        .into_iter()
+        .map(|part| {
+            let (part_headers, http) = split_headers(part);
+            let index = find_header(&part_headers, "Content-ID").and_then(|content_id| content_id.parse::<usize>().ok());
+
+            let (status_code, rest) = split_status_line(http)?;
+            let (headers, content) = split_headers(rest);
+
+            let mut header_map = HeaderMap::new();
+            for (name, value) in &headers {
+                if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+                    header_map.append(name, format_header_value(value)?);
+                }
+            }
+
+            let request_id = find_header(&headers, "x-ms-request-id").map(|request_id| request_id.to_owned());
+            let error = if status_code.is_success() {
+                None
+            } else {
+                let expected = index.and_then(|index| expected.get(index)).cloned().unwrap_or(StatusCode::ACCEPTED);
+                Some(UnexpectedHTTPResult::new(expected, status_code, content.trim()).with_headers(&header_map))
+            };
+
+            Ok(BlobBatchOperationResponse {
+                index,
+                status_code,
+                request_id,
+                error,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::storage::StorageErrorCode;
+    use hyper::header::HeaderValue;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_generate_payload() {
+        let client = Client::new("account", "YWNjb3VudGtleQ==").unwrap();
+        let now = DateTime::parse_from_rfc3339("2019-10-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let operations = vec![
+            BlobBatchOperation::SetTier("container".to_owned(), "a blob".to_owned(), "Cool".to_owned()),
+            BlobBatchOperation::SetTier("container".to_owned(), "b".to_owned(), "Cool".to_owned()),
+        ];
+
+        let payload = generate_payload(&client, &operations, "batch_1", now).unwrap();
+        let parts = split_parts(&payload, "batch_1");
+        assert_eq!(parts.len(), 2);
+
+        let (part_headers, http) = split_headers(parts[0]);
+        assert_eq!(find_header(&part_headers, "Content-Type"), Some("application/http"));
+        assert_eq!(find_header(&part_headers, "Content-ID"), Some("0"));
+        assert!(http.starts_with("PUT /container/a%20blob?comp=tier HTTP/1.1\r\n"));
+
+        let (headers, _) = split_headers(http.splitn(2, '\n').nth(1).unwrap());
+        assert_eq!(find_header(&headers, "x-ms-date"), Some("Tue, 15 Oct 2019 10:00:00 GMT"));
+        assert_eq!(find_header(&headers, "x-ms-access-tier"), Some("Cool"));
+        assert!(find_header(&headers, "authorization").unwrap().starts_with("SharedKey account:"));
+        assert!(payload.ends_with("--batch_1--\r\n"));
+    }
+
+    #[test]
+    fn test_generate_payload_bearer_token() {
+        let client = Client::azure_bearer_token("account", "eyJ0eXAi").unwrap();
+        let now = DateTime::parse_from_rfc3339("2019-10-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let operations = vec![BlobBatchOperation::Delete("container".to_owned(), "a".to_owned())];
+
+        let payload = generate_payload(&client, &operations, "batch_1", now).unwrap();
+        let parts = split_parts(&payload, "batch_1");
+        assert_eq!(parts.len(), 1);
+
+        let (_, http) = split_headers(parts[0]);
+        let (headers, _) = split_headers(http.splitn(2, '\n').nth(1).unwrap());
+        assert_eq!(find_header(&headers, "authorization"), Some("Bearer eyJ0eXAi"));
+    }
+
+    #[test]
+    fn test_validate() {
+        let client = Client::new("account", "YWNjb3VudGtleQ==").unwrap();
+        assert!(BlobBatchBuilder::new(&client).validate().is_err());
+        assert!(BlobBatchBuilder::new(&client).delete("c", "a").delete("c", "b").validate().is_ok());
+        assert!(BlobBatchBuilder::new(&client)
+            .delete("c", "a")
+            .set_tier("c", "b", "Hot")
+            .validate()
+            .is_err());
+
+        let batch = (0..=MAX_BATCH_SUBREQUESTS).fold(BlobBatchBuilder::new(&client), |batch, i| batch.delete("c", &i.to_string()));
+        assert!(batch.validate().is_err());
+    }
+
+    #[test]
+    fn test_extra_headers_and_api_version() {
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::BAD_REQUEST, &[], b""));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+
+        let batch = BlobBatchBuilder::new(&client).delete("c", "a");
+        assert!(batch.clone().finalize().wait().is_err());
+        assert!(batch
+            .clone()
+            .with_extra_header("x-ms-foo", "bar")
+            .with_api_version("2019-12-12")
+            .finalize()
+            .wait()
+            .is_err());
+        // older than Blob Batch
+        assert!(batch.with_api_version("2018-03-28").finalize().wait().is_err());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].headers[HEADER_VERSION], client.api_version().max(BLOB_BATCH_VERSION));
+        assert_eq!(requests[1].headers[HEADER_VERSION], "2019-12-12");
+        assert_eq!(requests[1].headers["x-ms-foo"], "bar");
+    }
+
+    #[test]
+    fn test_parse_batch_response() {
+        let body = "--batchresponse_66925647-d0cb-4109-b6d3-28efe3e1e5ed\r
+Content-Type: application/http\r
+Content-ID: 1\r
+\r
+HTTP/1.1 404 The specified blob does not exist.\r
+x-ms-error-code: BlobNotFound\r
+x-ms-request-id: 778fdc83-801e-0000-62ff-0334671e2852\r
+x-ms-version: 2018-11-09\r
+Content-Length: 216\r
+Content-Type: application/xml\r
+\r
+<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<Error><Code>BlobNotFound</Code><Message>The specified blob does not exist.</Message></Error>\r
+--batchresponse_66925647-d0cb-4109-b6d3-28efe3e1e5ed\r
+Content-Type: application/http\r
+Content-ID: 0\r
+\r
+HTTP/1.1 202 Accepted\r
+x-ms-delete-type-permanent: true\r
+x-ms-request-id: 778fdc83-801e-0000-62ff-0334671e284f\r
+x-ms-version: 2018-11-09\r
+\r
+--batchresponse_66925647-d0cb-4109-b6d3-28efe3e1e5ed--\r
+";
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ms-request-id", HeaderValue::from_static("778fdc83-801e-0000-62ff-033467000000"));
+        headers.insert("date", HeaderValue::from_static("Tue, 15 Oct 2019 10:00:00 GMT"));
+
+        let response = BlobBatchResponse::from_response(&headers, body, &[StatusCode::ACCEPTED; 2]).unwrap();
+        assert!(!response.is_successful());
+        assert_eq!(response.operations.len(), 2);
+
+        assert_eq!(response.operations[0].index, Some(0));
+        assert_eq!(response.operations[0].status_code, StatusCode::ACCEPTED);
+        assert!(response.operations[0].is_successful());
+
+        let failure = response.failures().next().unwrap();
+        assert_eq!(failure.index, Some(1));
+        assert_eq!(failure.status_code, StatusCode::NOT_FOUND);
+        assert_eq!(failure.request_id.as_deref(), Some("778fdc83-801e-0000-62ff-0334671e2852"));
+        let error = failure.error.as_ref().unwrap();
+        assert_eq!(error.error_code(), Some(StorageErrorCode::BlobNotFound));
+        assert_eq!(error.error_message().as_deref(), Some("The specified blob does not exist."));
+    }
+}
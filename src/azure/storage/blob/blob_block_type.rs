@@ -9,3 +9,23 @@ where
     Uncommitted(T),
     Latest(T),
 }
+
+impl<T> BlobBlockType<T>
+where
+    T: Borrow<[u8]>,
+{
+    /// The block id, as passed to put block.
+    pub fn id(&self) -> &[u8] {
+        match self {
+            BlobBlockType::Committed(id) | BlobBlockType::Uncommitted(id) | BlobBlockType::Latest(id) => id.borrow(),
+        }
+    }
+
+    pub fn is_committed(&self) -> bool {
+        matches!(self, BlobBlockType::Committed(_))
+    }
+
+    pub fn is_uncommitted(&self) -> bool {
+        matches!(self, BlobBlockType::Uncommitted(_))
+    }
+}
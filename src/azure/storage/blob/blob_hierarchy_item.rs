@@ -0,0 +1,35 @@
+use crate::azure::storage::blob::Blob;
+
+/// An entry of a hierarchical (delimited) blob listing: either a blob or
+/// a virtual directory. They are returned in the same, lexicographical,
+/// order Azure uses so a directory-style view can be rendered as is.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlobHierarchyItem {
+    Blob(Box<Blob>),
+    /// Common prefix of all the blobs below it, delimiter included
+    /// (for example `photos/2019/`).
+    Prefix(String),
+}
+
+impl BlobHierarchyItem {
+    pub fn name(&self) -> &str {
+        match self {
+            BlobHierarchyItem::Blob(blob) => &blob.name,
+            BlobHierarchyItem::Prefix(prefix) => prefix,
+        }
+    }
+
+    pub fn blob(&self) -> Option<&Blob> {
+        match self {
+            BlobHierarchyItem::Blob(blob) => Some(blob),
+            BlobHierarchyItem::Prefix(_) => None,
+        }
+    }
+
+    pub fn prefix(&self) -> Option<&str> {
+        match self {
+            BlobHierarchyItem::Blob(_) => None,
+            BlobHierarchyItem::Prefix(prefix) => Some(prefix),
+        }
+    }
+}
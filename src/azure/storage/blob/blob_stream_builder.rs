@@ -2,9 +2,9 @@ use crate::azure::core::errors::AzureError;
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::range::Range;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
-    ContainerNameSupport, LeaseIdOption, LeaseIdSupport, No, RangeRequired, RangeSupport, SnapshotOption, SnapshotSupport, TimeoutOption,
-    TimeoutSupport, ToAssign, Yes,
+    owned_headers, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport,
+    ClientRequired, ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport,
+    No, RangeRequired, RangeSupport, SnapshotOption, SnapshotSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
 };
 use crate::azure::storage::blob::requests::GetBlobBuilder;
 use crate::azure::storage::client::Client;
@@ -32,6 +32,8 @@ where
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
     increment: u64,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> BlobStreamBuilder<'a, No, No, No> {
@@ -50,6 +52,8 @@ impl<'a> BlobStreamBuilder<'a, No, No, No> {
             lease_id: None,
             client_request_id: None,
             increment: 1024 * 1024,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -172,6 +176,8 @@ where
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
             increment: self.increment,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -199,6 +205,8 @@ where
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
             increment: self.increment,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -226,6 +234,8 @@ where
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
             increment: self.increment,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -253,6 +263,8 @@ where
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
             increment: self.increment,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -280,6 +292,8 @@ where
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
             increment: self.increment,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -307,6 +321,8 @@ where
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
             increment: self.increment,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -335,6 +351,74 @@ where
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
             increment: self.increment,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, RangeSet> ExtraHeadersOption<'a> for BlobStreamBuilder<'a, ContainerNameSet, BlobNameSet, RangeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    RangeSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, RangeSet> ExtraHeadersSupport<'a> for BlobStreamBuilder<'a, ContainerNameSet, BlobNameSet, RangeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    RangeSet: ToAssign,
+{
+    type O = BlobStreamBuilder<'a, ContainerNameSet, BlobNameSet, RangeSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, RangeSet> ApiVersionOption<'a> for BlobStreamBuilder<'a, ContainerNameSet, BlobNameSet, RangeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    RangeSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, RangeSet> ApiVersionSupport<'a> for BlobStreamBuilder<'a, ContainerNameSet, BlobNameSet, RangeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    RangeSet: ToAssign,
+{
+    type O = BlobStreamBuilder<'a, ContainerNameSet, BlobNameSet, RangeSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        BlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_range: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            increment: self.increment,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -359,6 +443,8 @@ impl<'a> BlobStreamBuilder<'a, Yes, Yes, Yes> {
         let timeout = self.timeout.to_owned();
         let lease_id = self.lease_id.cloned();
         let client_request_id = self.client_request_id.map(|v| v.to_owned());
+        let extra_headers = owned_headers(&self.extra_headers);
+        let api_version = self.api_version.map(|v| v.to_owned());
         let increment = self.increment;
 
         let client = self.client().clone();
@@ -392,6 +478,12 @@ impl<'a> BlobStreamBuilder<'a, Yes, Yes, Yes> {
             if let Some(ref client_request_id) = &client_request_id {
                 req = req.with_client_request_id(client_request_id);
             }
+            for (name, value) in &extra_headers {
+                req = req.with_extra_header(name, value);
+            }
+            if let Some(ref api_version) = &api_version {
+                req = req.with_api_version(api_version);
+            }
 
             let req = req.finalize();
             Some(req.map(move |response| {
@@ -406,4 +498,9 @@ impl<'a> BlobStreamBuilder<'a, Yes, Yes, Yes> {
             }))
         })
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl futures_util::stream::Stream<Item = Result<Vec<u8>, AzureError>> {
+        crate::azure::core::async_compat::into_std_stream(self.finalize())
+    }
 }
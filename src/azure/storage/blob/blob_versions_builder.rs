@@ -0,0 +1,440 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{
+    owned_headers, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport,
+    ClientRequired, ContainerNameRequired, ContainerNameSupport, DeleteSnapshotsMethod, DeleteSnapshotsMethodSupport, ExtraHeadersOption,
+    ExtraHeadersSupport, IncludeVersionsSupport, No, PrefixSupport, TimeoutOption, TimeoutSupport, ToAssign, VersionIdSupport, Yes,
+};
+use crate::azure::storage::blob::Blob;
+use crate::azure::storage::client::{Blob as BlobTrait, Client};
+use chrono::{DateTime, Utc};
+use futures::prelude::*;
+use futures::stream;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> BlobVersionsBuilder<'a, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> BlobVersionsBuilder<'a, No, No> {
+        BlobVersionsBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequired<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet> ContainerNameRequired<'a> for BlobVersionsBuilder<'a, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet> BlobNameRequired<'a> for BlobVersionsBuilder<'a, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutOption for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ContainerNameSupport<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobVersionsBuilder<'a, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        BlobVersionsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> BlobNameSupport<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobVersionsBuilder<'a, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        BlobVersionsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutSupport for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        BlobVersionsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        BlobVersionsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersOption<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersSupport<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionOption<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionSupport<'a> for BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        BlobVersionsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet> BlobVersionsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+}
+impl<'a> BlobVersionsBuilder<'a, Yes, Yes> {
+    /// Lists every version of the blob, the current one included, oldest
+    /// first. Versioning must be enabled on the account: otherwise the
+    /// result only holds the base blob.
+    pub fn finalize(self) -> impl Future<Item = Vec<Blob>, Error = AzureError> {
+        list_versions(
+            self.client().clone(),
+            self.container_name().to_owned(),
+            self.blob_name().to_owned(),
+            self.timeout(),
+            self.client_request_id().map(|v| v.to_owned()),
+            owned_headers(self.extra_headers()),
+            self.api_version().map(|v| v.to_owned()),
+        )
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<Vec<Blob>, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+
+    /// Deletes the previous versions of the blob not retained by the
+    /// policy. The current version is never deleted. Deletes are sent one
+    /// at a time: if one fails the following versions are left in place.
+    pub fn prune(self, retention: VersionRetention) -> impl Future<Item = PruneBlobVersionsResponse, Error = AzureError> {
+        let client = self.client().clone();
+        let container_name = self.container_name().to_owned();
+        let blob_name = self.blob_name().to_owned();
+        let timeout = self.timeout();
+        let client_request_id = self.client_request_id().map(|v| v.to_owned());
+        let extra_headers = owned_headers(self.extra_headers());
+        let api_version = self.api_version().map(|v| v.to_owned());
+
+        list_versions(
+            client.clone(),
+            container_name.clone(),
+            blob_name.clone(),
+            timeout,
+            client_request_id.clone(),
+            extra_headers.clone(),
+            api_version.clone(),
+        )
+        .and_then(move |versions| {
+            let previous = versions
+                .into_iter()
+                .filter(|blob| blob.is_current_version != Some(true))
+                .filter_map(|blob| blob.version_id)
+                .collect::<Vec<_>>();
+            let (deleted, kept) = retention.split(previous);
+
+            stream::iter_ok(deleted)
+                .and_then(move |version_id| {
+                    let mut req = client
+                        .delete_blob()
+                        .with_container_name(&container_name)
+                        .with_blob_name(&blob_name)
+                        .with_version_id(&version_id)
+                        .with_delete_snapshots_method(DeleteSnapshotsMethod::Include);
+                    if let Some(timeout) = timeout {
+                        req = req.with_timeout(timeout);
+                    }
+                    if let Some(ref client_request_id) = client_request_id {
+                        req = req.with_client_request_id(client_request_id);
+                    }
+                    for (name, value) in &extra_headers {
+                        req = req.with_extra_header(name, value);
+                    }
+                    if let Some(ref api_version) = api_version {
+                        req = req.with_api_version(api_version);
+                    }
+                    req.finalize().map(move |_| version_id)
+                })
+                .collect()
+                .map(move |deleted| PruneBlobVersionsResponse { deleted, kept })
+        })
+    }
+}
+
+/// Which previous versions of a blob `BlobVersionsBuilder::prune` keeps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VersionRetention {
+    /// Deletes the versions created before this time.
+    OlderThan(DateTime<Utc>),
+    /// Keeps this many versions, the most recent ones, and deletes the
+    /// others.
+    KeepLatest(usize),
+}
+
+impl VersionRetention {
+    // splits the previous versions, oldest first, in the ones to delete
+    // and the ones to keep. Version IDs are the creation times, in a
+    // fixed width format: they sort chronologically.
+    fn split(&self, mut previous: Vec<String>) -> (Vec<String>, Vec<String>) {
+        previous.sort();
+        match *self {
+            VersionRetention::OlderThan(cutoff) => previous
+                .into_iter()
+                .partition(|version_id| version_time(version_id).is_some_and(|time| time < cutoff)),
+            VersionRetention::KeepLatest(count) => {
+                let kept = previous.split_off(previous.len().saturating_sub(count));
+                (previous, kept)
+            }
+        }
+    }
+}
+
+fn version_time(version_id: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(version_id).ok().map(|time| time.with_timezone(&Utc))
+}
+
+/// The outcome of `BlobVersionsBuilder::prune`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PruneBlobVersionsResponse {
+    /// The IDs of the versions deleted, oldest first.
+    pub deleted: Vec<String>,
+    /// The IDs of the previous versions left in place, oldest first.
+    pub kept: Vec<String>,
+}
+
+fn list_versions(
+    client: Client,
+    container_name: String,
+    blob_name: String,
+    timeout: Option<u64>,
+    client_request_id: Option<String>,
+    extra_headers: Vec<(String, String)>,
+    api_version: Option<String>,
+) -> impl Future<Item = Vec<Blob>, Error = AzureError> {
+    let mut req = client
+        .list_blobs()
+        .with_container_name(&container_name)
+        .with_prefix(&blob_name)
+        .with_include_versions();
+    if let Some(timeout) = timeout {
+        req = req.with_timeout(timeout);
+    }
+    if let Some(ref client_request_id) = client_request_id {
+        req = req.with_client_request_id(client_request_id);
+    }
+    for (name, value) in &extra_headers {
+        req = req.with_extra_header(name, value);
+    }
+    if let Some(ref api_version) = api_version {
+        req = req.with_api_version(api_version);
+    }
+
+    // the prefix matches the blobs whose name starts with this one too
+    req.stream()
+        .filter(move |blob| blob.name == blob_name)
+        .collect()
+        .map(|mut versions| {
+            versions.sort_by(|a, b| a.version_id.cmp(&b.version_id));
+            versions
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn versions() -> Vec<String> {
+        vec![
+            "2019-10-15T10:00:00.3000000Z".to_owned(),
+            "2019-10-13T10:00:00.1000000Z".to_owned(),
+            "2019-10-14T10:00:00.2000000Z".to_owned(),
+        ]
+    }
+
+    #[test]
+    fn test_retention() {
+        let cutoff = DateTime::parse_from_rfc3339("2019-10-14T12:00:00Z").unwrap().with_timezone(&Utc);
+        let (deleted, kept) = VersionRetention::OlderThan(cutoff).split(versions());
+        assert_eq!(deleted, vec!["2019-10-13T10:00:00.1000000Z", "2019-10-14T10:00:00.2000000Z"]);
+        assert_eq!(kept, vec!["2019-10-15T10:00:00.3000000Z"]);
+
+        let (deleted, kept) = VersionRetention::KeepLatest(1).split(versions());
+        assert_eq!(deleted, vec!["2019-10-13T10:00:00.1000000Z", "2019-10-14T10:00:00.2000000Z"]);
+        assert_eq!(kept, vec!["2019-10-15T10:00:00.3000000Z"]);
+
+        let (deleted, kept) = VersionRetention::KeepLatest(5).split(versions());
+        assert!(deleted.is_empty());
+        assert_eq!(kept.len(), 3);
+    }
+}
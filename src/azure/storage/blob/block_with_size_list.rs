@@ -46,8 +46,38 @@ where
     pub blocks: Vec<BlobBlockWithSize<T>>,
 }
 
+impl<T> BlockWithSizeList<T>
+where
+    T: Borrow<[u8]>,
+{
+    /// The committed blocks, in the order they make up the blob.
+    pub fn committed(&self) -> impl Iterator<Item = &BlobBlockWithSize<T>> {
+        self.blocks.iter().filter(|block| block.block_list_type.is_committed())
+    }
+
+    /// The blocks uploaded but not committed yet. Uncommitted blocks are
+    /// discarded a week after their upload, or by the next put block list
+    /// (or put blob) on the same blob.
+    pub fn uncommitted(&self) -> impl Iterator<Item = &BlobBlockWithSize<T>> {
+        self.blocks.iter().filter(|block| block.block_list_type.is_uncommitted())
+    }
+
+    /// Finds an uncommitted block by id: a resumed upload can skip the
+    /// blocks found with the expected size.
+    pub fn find_uncommitted(&self, id: &[u8]) -> Option<&BlobBlockWithSize<T>> {
+        self.uncommitted().find(|block| block.block_list_type.id() == id)
+    }
+
+    /// Size in bytes of the committed blocks, that is of the blob.
+    pub fn committed_size(&self) -> u64 {
+        self.committed().map(|block| block.size_in_bytes).sum()
+    }
+}
+
 impl BlockWithSizeList<Vec<u8>> {
     pub fn try_from(xml: &str) -> Result<BlockWithSizeList<Vec<u8>>, AzureError> {
+        // the service prepends a byte order mark, the emulator does not
+        let xml = xml.trim_start_matches('\u{feff}');
         let bl: BlockList = serde_xml_rs::de::from_reader(xml.as_bytes())?;
         debug!("bl == {:?}", bl);
 
@@ -122,4 +152,19 @@ mod test {
         assert!(bl.blocks[1].size_in_bytes == 62);
         assert!(bl.blocks[2].size_in_bytes == 62);
     }
+
+    #[test]
+    fn committed_and_uncommitted() {
+        let range = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?><BlockList><CommittedBlocks><Block><Name>YmxvY2sx</Name><Size>100</Size></Block><Block><Name>YmxvY2sy</Name><Size>50</Size></Block></CommittedBlocks><UncommittedBlocks><Block><Name>YmxvY2sz</Name><Size>62</Size></Block></UncommittedBlocks></BlockList>";
+
+        let bl = BlockWithSizeList::try_from(range).unwrap();
+        assert_eq!(bl.committed().count(), 2);
+        assert_eq!(bl.committed_size(), 150);
+        assert_eq!(
+            bl.uncommitted().map(|block| block.block_list_type.id()).collect::<Vec<_>>(),
+            vec![b"block3"]
+        );
+        assert_eq!(bl.find_uncommitted(b"block3").map(|block| block.size_in_bytes), Some(62));
+        assert!(bl.find_uncommitted(b"block1").is_none());
+    }
 }
@@ -0,0 +1,917 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::headers::{API_VERSION, CLIENT_REQUEST_ID, LEASE_ID};
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::progress::Progress;
+use crate::azure::core::range::Range;
+use crate::azure::core::retry::NoRetry;
+use crate::azure::core::util::HeaderMapExt;
+use crate::azure::core::{
+    etag_from_headers_optional, owned_headers, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport,
+    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption,
+    ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, No, ProgressOption, ProgressSupport, RangeOption, RangeSupport, SnapshotOption,
+    SnapshotSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::blob::generate_blob_uri;
+use crate::azure::storage::blob::spooled_body::{spool, SpooledBody};
+use crate::azure::storage::client::Client;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::future::{done, loop_fn, ok, Either, Loop};
+use futures::prelude::*;
+use futures::stream;
+use hyper::header::{CONTENT_LENGTH, IF_MATCH, RANGE};
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const RETRY_BASE_DELAY_MS: u64 = 200;
+
+#[derive(Debug, Clone)]
+pub struct DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    snapshot: Option<DateTime<Utc>>,
+    timeout: Option<u64>,
+    range: Option<&'a Range>,
+    lease_id: Option<&'a LeaseId>,
+    client_request_id: Option<&'a str>,
+    chunk_size: u64,
+    max_retries: u32,
+    parallelism: usize,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+    progress: Option<&'a Progress>,
+}
+
+impl<'a> DownloadBlobStreamBuilder<'a, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> DownloadBlobStreamBuilder<'a, No, No> {
+        DownloadBlobStreamBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            snapshot: None,
+            timeout: None,
+            range: None,
+            lease_id: None,
+            client_request_id: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_retries: DEFAULT_MAX_RETRIES,
+            parallelism: 1,
+            extra_headers: Vec::new(),
+            api_version: None,
+            progress: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequired<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet> ContainerNameRequired<'a> for DownloadBlobStreamBuilder<'a, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet> BlobNameRequired<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> SnapshotOption for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutOption for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> RangeOption<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn range(&self) -> Option<&'a Range> {
+        self.range
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> LeaseIdOption<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ContainerNameSupport<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> BlobNameSupport<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> SnapshotSupport for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: Some(snapshot),
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutSupport for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: Some(timeout),
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> RangeSupport<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_range(self, range: &'a Range) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: Some(range),
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> LeaseIdSupport<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: Some(lease_id),
+            client_request_id: self.client_request_id,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: Some(client_request_id),
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersOption<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersSupport<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionOption<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionSupport<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ProgressOption<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn progress(&self) -> Option<&'a Progress> {
+        self.progress
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ProgressSupport<'a> for DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_progress(self, progress: &'a Progress) -> Self::O {
+        DownloadBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            parallelism: self.parallelism,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: Some(progress),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet> DownloadBlobStreamBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    /// Size of each ranged GET. Defaults to 4 MB.
+    #[inline]
+    pub fn with_chunk_size(self, chunk_size: u64) -> Self {
+        DownloadBlobStreamBuilder { chunk_size, ..self }
+    }
+
+    /// How many times a failed chunk (or the initial HEAD) is retried
+    /// before the stream errors out. Only retriable failures (see
+    /// `AzureError::is_retriable`) are retried, a body cut short included,
+    /// with an exponential backoff timed by the client's `Sleeper`. The
+    /// client retry policy does not apply to these requests, so the two
+    /// do not multiply. Defaults to 3.
+    #[inline]
+    pub fn with_max_retries(self, max_retries: u32) -> Self {
+        DownloadBlobStreamBuilder { max_retries, ..self }
+    }
+
+    /// How many chunks are downloaded concurrently. Chunks are always
+    /// yielded in order. Defaults to 1 (no prefetching).
+    #[inline]
+    pub fn with_parallelism(self, parallelism: usize) -> Self {
+        DownloadBlobStreamBuilder {
+            parallelism: std::cmp::max(parallelism, 1),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn chunk_size(&self) -> u64 {
+        self.chunk_size
+    }
+
+    #[inline]
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    #[inline]
+    pub fn parallelism(&self) -> usize {
+        self.parallelism
+    }
+}
+
+impl<'a> DownloadBlobStreamBuilder<'a, Yes, Yes> {
+    /// Returns the blob contents as a stream of chunks. If no range is
+    /// specified the whole blob is downloaded (its length is retrieved
+    /// first with a HEAD request). Range ends are inclusive, as in
+    /// `GetBlobBuilder`. The chunks are read from the version of the blob
+    /// of the HEAD (or of the first chunk) with `If-Match`: if the blob
+    /// changes meanwhile the stream fails with
+    /// `AzureError::BlobModifiedDuringDownload`.
+    pub fn finalize(self) -> impl Stream<Item = Bytes, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, None);
+
+        let mut f_first = true;
+        if let Some(snapshot) = SnapshotOption::to_uri_parameter(&self) {
+            uri = format!("{}?{}", uri, snapshot);
+            f_first = false;
+        }
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}{}{}", uri, if f_first { "?" } else { "&" }, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let chunk_size = std::cmp::max(self.chunk_size, 1);
+        let parallelism = self.parallelism;
+
+        let request = Arc::new(ChunkRequest {
+            // the requests are retried by the stream, not by the pipeline
+            client: self.client().clone().with_retry_policy(Arc::new(NoRetry)),
+            uri,
+            lease_id: self.lease_id.cloned(),
+            client_request_id: self.client_request_id.map(|v| v.to_owned()),
+            extra_headers: owned_headers(&self.extra_headers),
+            api_version: self.api_version.map(|v| v.to_owned()),
+            max_retries: self.max_retries,
+        });
+
+        let blob = match self.range() {
+            Some(range) => Either::A(ok((Some(*range), None))),
+            None => Either::B(ChunkRequest::retrying(&request, "HEAD".to_owned(), ChunkRequest::blob_range)),
+        };
+
+        let progress = self.progress().cloned();
+
+        blob.map(move |(range, etag)| {
+            // the ranges are inclusive
+            let total = range.map(|range| range.end - range.start + 1).unwrap_or(0);
+            let mut chunks = range.map(|range| chunk_ranges(range, chunk_size)).unwrap_or_default().into_iter();
+            let first = match chunks.next() {
+                Some(first) => first,
+                None => return Either::A(stream::empty()),
+            };
+            let rest = chunks.collect::<Vec<_>>();
+
+            // without a HEAD the chunks are pinned to the ETag of the first one
+            let mut transferred = 0;
+            let chunks = ChunkRequest::fetch(&request, first, etag.clone())
+                .map(move |(first, first_etag)| {
+                    let etag = etag.or(first_etag);
+                    stream::once(Ok(first)).chain(
+                        stream::iter_ok(rest)
+                            .map(move |chunk| ChunkRequest::fetch(&request, chunk, etag.clone()).map(|(bytes, _)| bytes))
+                            .buffered(parallelism),
+                    )
+                })
+                .flatten_stream()
+                .inspect(move |chunk| {
+                    if let Some(progress) = &progress {
+                        transferred += chunk.len() as u64;
+                        progress.report(transferred, Some(total));
+                    }
+                });
+            Either::B(chunks)
+        })
+        .flatten_stream()
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl futures_util::stream::Stream<Item = Result<Bytes, AzureError>> {
+        crate::azure::core::async_compat::into_std_stream(self.finalize())
+    }
+
+    /// Downloads the blob keeping it in memory up to `threshold` bytes:
+    /// larger blobs are written to a temporary file instead, deleted when
+    /// the returned body is dropped. The file writes are blocking.
+    pub fn finalize_spooled(self, threshold: u64) -> impl Future<Item = SpooledBody, Error = AzureError> {
+        spool(self.finalize(), threshold, std::env::temp_dir())
+    }
+}
+
+#[derive(Debug)]
+struct ChunkRequest {
+    client: Client,
+    uri: String,
+    lease_id: Option<LeaseId>,
+    client_request_id: Option<String>,
+    extra_headers: Vec<(String, String)>,
+    api_version: Option<String>,
+    max_retries: u32,
+}
+
+impl ChunkRequest {
+    fn add_headers(&self, request: &mut ::http::request::Builder) {
+        if let Some(lease_id) = &self.lease_id {
+            request.header(LEASE_ID, &lease_id.to_string() as &str);
+        }
+        if let Some(client_request_id) = &self.client_request_id {
+            request.header(CLIENT_REQUEST_ID, client_request_id as &str);
+        }
+        for (name, value) in &self.extra_headers {
+            request.header(name as &str, value as &str);
+        }
+        if let Some(api_version) = &self.api_version {
+            request.header(API_VERSION, api_version as &str);
+        }
+    }
+
+    // the whole blob, None if the blob is empty, and its ETag
+    fn blob_range(&self) -> impl Future<Item = (Option<Range>, Option<String>), Error = AzureError> {
+        let req = self
+            .client
+            .perform_request(&self.uri, &Method::HEAD, |ref mut request| self.add_headers(request), None);
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
+            .and_then(move |(headers, _)| {
+                let content_length = headers
+                    .get_as_u64(CONTENT_LENGTH)
+                    .ok_or_else(|| AzureError::HeaderNotFound(CONTENT_LENGTH.as_str().to_owned()))?;
+                trace!("content_length == {:?}", content_length);
+
+                let range = if content_length == 0 {
+                    None
+                } else {
+                    Some(Range::new(0, content_length - 1))
+                };
+                Ok((range, etag_from_headers_optional(&headers)?))
+            })
+    }
+
+    // the chunk, if the blob still has `etag`, and the ETag returned
+    fn fetch_once(&self, range: Range, etag: Option<String>) -> impl Future<Item = (Bytes, Option<String>), Error = AzureError> {
+        let req = self.client.perform_request(
+            &self.uri,
+            &Method::GET,
+            |ref mut request| {
+                request.header(RANGE, &range.to_string() as &str);
+                if let Some(etag) = &etag {
+                    request.header(IF_MATCH, etag as &str);
+                }
+                self.add_headers(request);
+            },
+            None,
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::PARTIAL_CONTENT))
+            .then(move |res| match res {
+                Err(ref err) if err.status() == Some(StatusCode::PRECONDITION_FAILED) => {
+                    Err(AzureError::BlobModifiedDuringDownload(etag.unwrap_or_default()))
+                }
+                res => res,
+            })
+            .and_then(move |(headers, body)| {
+                let expected = range.end - range.start + 1;
+                if body.len() as u64 != expected {
+                    return Err(AzureError::UnexpectedRangeLength(range.to_string(), expected, body.len() as u64));
+                }
+                Ok((body.into_bytes(), etag_from_headers_optional(&headers)?))
+            })
+    }
+
+    fn fetch(
+        this: &Arc<ChunkRequest>,
+        range: Range,
+        etag: Option<String>,
+    ) -> impl Future<Item = (Bytes, Option<String>), Error = AzureError> {
+        ChunkRequest::retrying(this, format!("chunk {}", range), move |this| this.fetch_once(range, etag.clone()))
+    }
+
+    // retries `send` on the retriable failures, up to `max_retries` times
+    fn retrying<T, F, R>(this: &Arc<ChunkRequest>, what: String, send: F) -> impl Future<Item = T, Error = AzureError>
+    where
+        F: Fn(&ChunkRequest) -> R,
+        R: Future<Item = T, Error = AzureError>,
+    {
+        let this = this.clone();
+
+        loop_fn(0, move |attempt| {
+            let max_retries = this.max_retries;
+            let sleeper = this.client.sleeper().clone();
+            let what = what.clone();
+
+            send(&this).then(move |res| match res {
+                Ok(item) => Either::A(done(Ok(Loop::Break(item)))),
+                Err(ref err) if attempt < max_retries && err.is_retriable() => {
+                    warn!("{} failed (attempt {}): {}, retrying", what, attempt + 1, err);
+                    let delay = Duration::from_millis(RETRY_BASE_DELAY_MS << attempt.min(10));
+                    Either::B(sleeper.sleep(delay).map(move |_| Loop::Continue(attempt + 1)))
+                }
+                Err(err) => Either::A(done(Err(err))),
+            })
+        })
+    }
+}
+
+// splits an inclusive range in inclusive chunks of at most chunk_size bytes
+fn chunk_ranges(range: Range, chunk_size: u64) -> Vec<Range> {
+    let mut chunks = Vec::new();
+    let mut start = Some(range.start);
+    while let Some(s) = start.filter(|s| *s <= range.end) {
+        let end = std::cmp::min(s.saturating_add(chunk_size - 1), range.end);
+        chunks.push(Range::new(s, end));
+        start = end.checked_add(1);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+
+    #[test]
+    fn test_chunk_ranges() {
+        assert_eq!(
+            chunk_ranges(Range::new(0, 9), 4),
+            vec![Range::new(0, 3), Range::new(4, 7), Range::new(8, 9)]
+        );
+        assert_eq!(chunk_ranges(Range::new(10, 13), 4), vec![Range::new(10, 13)]);
+        assert_eq!(chunk_ranges(Range::new(5, 5), 4), vec![Range::new(5, 5)]);
+    }
+
+    #[test]
+    fn test_progress() {
+        use crate::azure::core::progress::TransferProgress;
+        use crate::azure::storage::client::Blob;
+        use std::sync::Mutex;
+
+        let headers = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::PARTIAL_CONTENT, &headers, b"abcd")
+                .with_response(StatusCode::PARTIAL_CONTENT, &headers, b"ef"),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let progress = {
+            let reports = reports.clone();
+            Progress::new(move |progress| reports.lock().unwrap().push(progress))
+        };
+
+        let range = Range::new(0, 5);
+        let chunks = client
+            .download_blob_stream()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_range(&range)
+            .with_chunk_size(4)
+            .with_progress(&progress)
+            .finalize()
+            .collect()
+            .wait()
+            .unwrap();
+        assert_eq!(chunks.concat(), b"abcdef");
+        assert_eq!(
+            *reports.lock().unwrap(),
+            vec![
+                TransferProgress {
+                    transferred: 4,
+                    total: Some(6)
+                },
+                TransferProgress {
+                    transferred: 6,
+                    total: Some(6)
+                },
+            ]
+        );
+    }
+
+    fn download(transport: &Arc<ReplayTransport>, range: Option<Range>) -> Result<Vec<u8>, AzureError> {
+        use crate::azure::core::retry::FixedRetry;
+        use crate::azure::core::sleeper::VirtualSleeper;
+        use crate::azure::storage::client::Blob;
+        use std::time::Duration;
+
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone())
+            .with_sleeper(Arc::new(VirtualSleeper::new()))
+            .with_retry_policy(Arc::new(FixedRetry::new(Duration::from_secs(1), 3)));
+        let builder = client
+            .download_blob_stream()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_chunk_size(4)
+            .with_max_retries(1);
+        let chunks = match range {
+            Some(range) => builder.with_range(&range).finalize().collect().wait()?,
+            None => builder.finalize().collect().wait()?,
+        };
+        Ok(chunks.concat())
+    }
+
+    #[test]
+    fn test_etag_pinned() {
+        let headers = [("etag", "\"0x8D7\""), ("content-length", "6")];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::OK, &headers, b"")
+                .with_response(StatusCode::PARTIAL_CONTENT, &headers, b"abcd")
+                .with_response(StatusCode::PARTIAL_CONTENT, &headers, b"ef"),
+        );
+
+        assert_eq!(download(&transport, None).unwrap(), b"abcdef");
+        let requests = transport.requests();
+        assert!(requests[0].headers.get("if-match").is_none());
+        assert_eq!(requests[1].headers["if-match"], "\"0x8D7\"");
+        assert_eq!(requests[2].headers["if-match"], "\"0x8D7\"");
+    }
+
+    #[test]
+    fn test_etag_of_first_chunk_pinned() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::PARTIAL_CONTENT, &[("etag", "\"0x8D7\"")], b"abcd")
+                .with_response(StatusCode::PARTIAL_CONTENT, &[], b"ef"),
+        );
+
+        assert_eq!(download(&transport, Some(Range::new(0, 5))).unwrap(), b"abcdef");
+        let requests = transport.requests();
+        assert!(requests[0].headers.get("if-match").is_none());
+        assert_eq!(requests[1].headers["if-match"], "\"0x8D7\"");
+    }
+
+    #[test]
+    fn test_blob_modified() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::PARTIAL_CONTENT, &[("etag", "\"0x8D7\"")], b"abcd")
+                .with_response(StatusCode::PRECONDITION_FAILED, &[], b""),
+        );
+
+        match download(&transport, Some(Range::new(0, 5))) {
+            Err(AzureError::BlobModifiedDuringDownload(etag)) => assert_eq!(etag, "\"0x8D7\""),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_short_chunk() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::PARTIAL_CONTENT, &[], b"ab")
+                .with_response(StatusCode::PARTIAL_CONTENT, &[], b"abc"),
+        );
+
+        match download(&transport, Some(Range::new(0, 3))) {
+            Err(AzureError::UnexpectedRangeLength(range, expected, received)) => {
+                assert_eq!(range, Range::new(0, 3).to_string());
+                assert_eq!((expected, received), (4, 3));
+            }
+            res => panic!("unexpected result {:?}", res),
+        }
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_client_retry_policy_not_applied() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::SERVICE_UNAVAILABLE, &[], b"")
+                .with_response(StatusCode::SERVICE_UNAVAILABLE, &[], b"")
+                .with_response(StatusCode::PARTIAL_CONTENT, &[], b"abcd"),
+        );
+
+        let err = download(&transport, Some(Range::new(0, 3))).unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        // one attempt and the stream's single retry, none from the client
+        assert_eq!(transport.requests().len(), 2);
+    }
+}
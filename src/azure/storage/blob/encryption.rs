@@ -0,0 +1,302 @@
+use crate::azure::core::crypto::{aes256_gcm_decrypt, aes256_gcm_encrypt, random_bytes};
+use crate::azure::core::errors::AzureError;
+use base64;
+use serde_json;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// The blob metadata holding the encryption data.
+pub const ENCRYPTION_DATA_METADATA: &str = "encryptiondata";
+
+const PROTOCOL: &str = "2.0";
+const ENCRYPTION_ALGORITHM: &str = "AES_GCM_256";
+const ENCRYPTION_MODE: &str = "FullBlob";
+// the plaintext is encrypted in regions of 4 MiB, each one with its own nonce
+const REGION_LENGTH: usize = 4 * 1024 * 1024;
+const NONCE_LENGTH: usize = 12;
+const TAG_LENGTH: usize = 16;
+const KEY_LENGTH: usize = 32;
+// the protocol version, padded to 8 bytes, is wrapped along with the key
+const WRAPPED_PROTOCOL_LENGTH: usize = 8;
+
+/// The key encryption key (KEK) wrapping the random content encryption
+/// key generated for each blob. Implement it to delegate the wrapping to
+/// a key management service (for example a Key Vault key).
+pub trait KeyEncryptionKey: fmt::Debug + Send + Sync {
+    /// Stored along with the wrapped key, to find the KEK back.
+    fn key_id(&self) -> &str;
+
+    /// The key wrapping algorithm, for example `A256KW` or `RSA-OAEP`.
+    fn algorithm(&self) -> &str;
+
+    fn wrap_key(&self, key: &[u8]) -> Result<Vec<u8>, AzureError>;
+
+    fn unwrap_key(&self, algorithm: &str, wrapped_key: &[u8]) -> Result<Vec<u8>, AzureError>;
+}
+
+/// A 256 bit key encryption key held by the application. The content
+/// keys are wrapped with AES-256-GCM (`A256GCMKW`), the nonce and the tag
+/// being stored with the wrapped key.
+#[derive(Clone)]
+pub struct LocalKey {
+    key_id: String,
+    key: Vec<u8>,
+}
+
+impl LocalKey {
+    pub fn new(key_id: &str, key: &[u8]) -> Result<LocalKey, AzureError> {
+        if key.len() != KEY_LENGTH {
+            return Err(AzureError::InputParametersError(format!(
+                "the key encryption key must be {} bytes long",
+                KEY_LENGTH
+            )));
+        }
+        Ok(LocalKey {
+            key_id: key_id.to_owned(),
+            key: key.to_vec(),
+        })
+    }
+}
+
+// the key must not end up in the logs
+impl fmt::Debug for LocalKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LocalKey").field("key_id", &self.key_id).finish()
+    }
+}
+
+impl KeyEncryptionKey for LocalKey {
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn algorithm(&self) -> &str {
+        "A256GCMKW"
+    }
+
+    fn wrap_key(&self, key: &[u8]) -> Result<Vec<u8>, AzureError> {
+        seal(&self.key, key)
+    }
+
+    fn unwrap_key(&self, algorithm: &str, wrapped_key: &[u8]) -> Result<Vec<u8>, AzureError> {
+        if algorithm != self.algorithm() {
+            return Err(AzureError::EncryptionError(format!(
+                "unsupported key wrapping algorithm {}",
+                algorithm
+            )));
+        }
+        open(&self.key, wrapped_key)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EncryptionData {
+    encryption_mode: String,
+    wrapped_content_key: WrappedContentKey,
+    encryption_agent: EncryptionAgent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encrypted_region_info: Option<EncryptedRegionInfo>,
+    #[serde(default)]
+    key_wrapping_metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WrappedContentKey {
+    key_id: String,
+    encrypted_key: String,
+    algorithm: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EncryptionAgent {
+    protocol: String,
+    encryption_algorithm: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EncryptedRegionInfo {
+    data_length: usize,
+    nonce_length: usize,
+}
+
+/// Client-side encryption of the blob contents, compatible with the
+/// version 2 format of the other Azure SDKs: the data is encrypted with
+/// AES-256-GCM using a random content key, which is wrapped by the key
+/// encryption key and stored, with the other encryption parameters, in
+/// the `encryptiondata` metadata of the blob.
+///
+/// Once set on the client (see `Client::with_client_side_encryption`),
+/// `PutBlockBlob` encrypts the body and `GetBlob` decrypts the blobs
+/// having the encryption metadata. Encrypted blobs can only be downloaded
+/// whole: ranged downloads fail. The blocks uploaded with `PutBlock` are
+/// not encrypted.
+#[derive(Debug, Clone)]
+pub struct ClientSideEncryption {
+    key: Arc<dyn KeyEncryptionKey>,
+}
+
+impl ClientSideEncryption {
+    pub fn new(key: Arc<dyn KeyEncryptionKey>) -> ClientSideEncryption {
+        ClientSideEncryption { key }
+    }
+
+    pub fn key(&self) -> &Arc<dyn KeyEncryptionKey> {
+        &self.key
+    }
+
+    /// Encrypts `data`, returning the encrypted data and the encryption
+    /// data to store in the `encryptiondata` metadata.
+    pub fn encrypt(&self, data: &[u8]) -> Result<(Vec<u8>, String), AzureError> {
+        let mut content_key = [0u8; KEY_LENGTH];
+        random_bytes(&mut content_key)?;
+
+        let mut encrypted = Vec::with_capacity(data.len() + (data.len() / REGION_LENGTH + 1) * (NONCE_LENGTH + TAG_LENGTH));
+        for region in data.chunks(REGION_LENGTH) {
+            encrypted.extend(seal(&content_key, region)?);
+        }
+
+        let mut key_to_wrap = PROTOCOL.as_bytes().to_vec();
+        key_to_wrap.resize(WRAPPED_PROTOCOL_LENGTH, 0);
+        key_to_wrap.extend_from_slice(&content_key);
+
+        let mut key_wrapping_metadata = HashMap::new();
+        key_wrapping_metadata.insert("EncryptionLibrary".to_owned(), format!("Rust {}", env!("CARGO_PKG_VERSION")));
+
+        let encryption_data = EncryptionData {
+            encryption_mode: ENCRYPTION_MODE.to_owned(),
+            wrapped_content_key: WrappedContentKey {
+                key_id: self.key.key_id().to_owned(),
+                encrypted_key: base64::encode(&self.key.wrap_key(&key_to_wrap)?),
+                algorithm: self.key.algorithm().to_owned(),
+            },
+            encryption_agent: EncryptionAgent {
+                protocol: PROTOCOL.to_owned(),
+                encryption_algorithm: ENCRYPTION_ALGORITHM.to_owned(),
+            },
+            encrypted_region_info: Some(EncryptedRegionInfo {
+                data_length: REGION_LENGTH,
+                nonce_length: NONCE_LENGTH,
+            }),
+            key_wrapping_metadata,
+        };
+
+        Ok((encrypted, serde_json::to_string(&encryption_data)?))
+    }
+
+    /// Decrypts `data` encrypted as described by `encryption_data` (the
+    /// `encryptiondata` metadata of the blob).
+    pub fn decrypt(&self, data: &[u8], encryption_data: &str) -> Result<Vec<u8>, AzureError> {
+        let encryption_data: EncryptionData = serde_json::from_str(encryption_data)?;
+
+        if encryption_data.encryption_agent.protocol != PROTOCOL
+            || encryption_data.encryption_agent.encryption_algorithm != ENCRYPTION_ALGORITHM
+        {
+            return Err(AzureError::EncryptionError(format!(
+                "unsupported encryption protocol {} ({})",
+                encryption_data.encryption_agent.protocol, encryption_data.encryption_agent.encryption_algorithm
+            )));
+        }
+        let wrapped_content_key = &encryption_data.wrapped_content_key;
+        if wrapped_content_key.key_id != self.key.key_id() {
+            return Err(AzureError::EncryptionError(format!(
+                "the blob is encrypted with key {}, not {}",
+                wrapped_content_key.key_id,
+                self.key.key_id()
+            )));
+        }
+
+        let unwrapped = self
+            .key
+            .unwrap_key(&wrapped_content_key.algorithm, &base64::decode(&wrapped_content_key.encrypted_key)?)?;
+        if unwrapped.len() != WRAPPED_PROTOCOL_LENGTH + KEY_LENGTH || !unwrapped.starts_with(PROTOCOL.as_bytes()) {
+            return Err(AzureError::EncryptionError("invalid content encryption key".to_owned()));
+        }
+        let content_key = &unwrapped[WRAPPED_PROTOCOL_LENGTH..];
+
+        let region_info = encryption_data.encrypted_region_info.unwrap_or(EncryptedRegionInfo {
+            data_length: REGION_LENGTH,
+            nonce_length: NONCE_LENGTH,
+        });
+        if region_info.nonce_length != NONCE_LENGTH || region_info.data_length == 0 {
+            return Err(AzureError::EncryptionError("invalid encrypted region info".to_owned()));
+        }
+
+        let mut decrypted = Vec::with_capacity(data.len());
+        for region in data.chunks(NONCE_LENGTH + region_info.data_length + TAG_LENGTH) {
+            decrypted.extend(open(content_key, region)?);
+        }
+        Ok(decrypted)
+    }
+}
+
+// AES-256-GCM with a random nonce: the output is nonce || ciphertext || tag
+fn seal(key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AzureError> {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    random_bytes(&mut nonce)?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(aes256_gcm_encrypt(key, &nonce, plaintext)?);
+    Ok(sealed)
+}
+
+fn open(key: &[u8], sealed: &[u8]) -> Result<Vec<u8>, AzureError> {
+    if sealed.len() < NONCE_LENGTH + TAG_LENGTH {
+        return Err(AzureError::EncryptionError("truncated encrypted data".to_owned()));
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+    aes256_gcm_decrypt(key, nonce, ciphertext)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encryption() -> ClientSideEncryption {
+        ClientSideEncryption::new(Arc::new(LocalKey::new("local-1", &[7u8; 32]).unwrap()))
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let encryption = encryption();
+        let data: Vec<u8> = (0..REGION_LENGTH + 10).map(|i| (i % 251) as u8).collect();
+
+        let (encrypted, encryption_data) = encryption.encrypt(&data).unwrap();
+        assert_eq!(encrypted.len(), data.len() + 2 * (NONCE_LENGTH + TAG_LENGTH));
+        assert_eq!(encryption.decrypt(&encrypted, &encryption_data).unwrap(), data);
+
+        let (encrypted, encryption_data) = encryption.encrypt(b"").unwrap();
+        assert!(encrypted.is_empty());
+        assert!(encryption.decrypt(&encrypted, &encryption_data).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_encryption_data() {
+        let (_, encryption_data) = encryption().encrypt(b"hello").unwrap();
+        let encryption_data: serde_json::Value = serde_json::from_str(&encryption_data).unwrap();
+
+        assert_eq!(encryption_data["EncryptionMode"], "FullBlob");
+        assert_eq!(encryption_data["WrappedContentKey"]["KeyId"], "local-1");
+        assert_eq!(encryption_data["WrappedContentKey"]["Algorithm"], "A256GCMKW");
+        assert_eq!(encryption_data["EncryptionAgent"]["Protocol"], "2.0");
+        assert_eq!(encryption_data["EncryptionAgent"]["EncryptionAlgorithm"], "AES_GCM_256");
+        assert_eq!(encryption_data["EncryptedRegionInfo"]["DataLength"], 4 * 1024 * 1024);
+        assert_eq!(encryption_data["EncryptedRegionInfo"]["NonceLength"], 12);
+    }
+
+    #[test]
+    fn test_tampering() {
+        let encryption = encryption();
+        let (mut encrypted, encryption_data) = encryption.encrypt(b"hello").unwrap();
+        encrypted[NONCE_LENGTH] ^= 1;
+        assert!(encryption.decrypt(&encrypted, &encryption_data).is_err());
+
+        let other = ClientSideEncryption::new(Arc::new(LocalKey::new("local-2", &[7u8; 32]).unwrap()));
+        let (encrypted, encryption_data) = encryption.encrypt(b"hello").unwrap();
+        assert!(other.decrypt(&encrypted, &encryption_data).is_err());
+    }
+}
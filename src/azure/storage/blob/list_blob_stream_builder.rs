@@ -1,12 +1,14 @@
 use crate::azure::core::errors::AzureError;
-use crate::azure::core::incompletevector::IncompleteVector;
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, DelimiterOption,
-    DelimiterSupport, IncludeCopyOption, IncludeCopySupport, IncludeDeletedOption, IncludeDeletedSupport, IncludeListOptions,
-    IncludeMetadataOption, IncludeMetadataSupport, IncludeSnapshotsOption, IncludeSnapshotsSupport, IncludeUncommittedBlobsOption,
-    IncludeUncommittedBlobsSupport, NextMarkerSupport, No, PrefixOption, PrefixSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    owned_headers, ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, DelimiterOption, DelimiterSupport, ExtraHeadersOption, ExtraHeadersSupport,
+    IncludeCopyOption, IncludeCopySupport, IncludeDeletedOption, IncludeDeletedSupport, IncludeListOptions, IncludeMetadataOption,
+    IncludeMetadataSupport, IncludeSnapshotsOption, IncludeSnapshotsSupport, IncludeTagsOption, IncludeTagsSupport,
+    IncludeUncommittedBlobsOption, IncludeUncommittedBlobsSupport, IncludeVersionsOption, IncludeVersionsSupport, MaxResultsOption,
+    MaxResultsSupport, NextMarkerOption, NextMarkerSupport, No, PrefixOption, PrefixSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
 };
-use crate::azure::storage::blob::requests::ListBlobBuilder;
+use crate::azure::storage::blob::requests::{ListBlobBuilder, ListBlobsFlatBuilder};
+use crate::azure::storage::blob::responses::ListBlobsResponse;
 use crate::azure::storage::blob::Blob;
 use crate::azure::storage::client::Client;
 use futures::prelude::*;
@@ -30,6 +32,12 @@ where
     include_uncommitted_blobs: bool,
     include_copy: bool,
     include_deleted: bool,
+    include_versions: bool,
+    include_tags: bool,
+    next_marker: Option<&'a str>,
+    max_results: Option<u32>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> ListBlobStreamBuilder<'a, No> {
@@ -48,6 +56,12 @@ impl<'a> ListBlobStreamBuilder<'a, No> {
             include_uncommitted_blobs: false,
             include_copy: false,
             include_deleted: false,
+            include_versions: false,
+            include_tags: false,
+            next_marker: None,
+            max_results: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -159,6 +173,46 @@ where
     }
 }
 
+impl<'a, ContainerNameSet> IncludeVersionsOption for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_versions(&self) -> bool {
+        self.include_versions
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeTagsOption for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_tags(&self) -> bool {
+        self.include_tags
+    }
+}
+
+impl<'a, ContainerNameSet> NextMarkerOption<'a> for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn next_marker(&self) -> Option<&'a str> {
+        self.next_marker
+    }
+}
+
+impl<'a, ContainerNameSet> MaxResultsOption for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn max_results(&self) -> Option<u32> {
+        self.max_results
+    }
+}
+
 impl<'a, ContainerNameSet> ContainerNameSupport<'a> for ListBlobStreamBuilder<'a, ContainerNameSet>
 where
     ContainerNameSet: ToAssign,
@@ -180,6 +234,12 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -205,6 +265,74 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersOption<'a> for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersSupport<'a> for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobStreamBuilder<'a, ContainerNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionOption<'a> for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionSupport<'a> for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobStreamBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ListBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -230,6 +358,12 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -255,6 +389,12 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -280,6 +420,12 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -305,6 +451,12 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -330,6 +482,12 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -355,6 +513,12 @@ where
             include_uncommitted_blobs: true,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -380,6 +544,12 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: true,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -405,6 +575,186 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: true,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeVersionsSupport for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobStreamBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_versions(self) -> Self::O {
+        ListBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: true,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeTagsSupport for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobStreamBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_tags(self) -> Self::O {
+        ListBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: true,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> NextMarkerSupport<'a> for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobStreamBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_next_marker(self, next_marker: &'a str) -> Self::O {
+        ListBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: Some(next_marker),
+            max_results: self.max_results,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> MaxResultsSupport for ListBlobStreamBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobStreamBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_max_results(self, max_results: u32) -> Self::O {
+        ListBlobStreamBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            next_marker: self.next_marker,
+            max_results: Some(max_results),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a> From<ListBlobBuilder<'a, Yes>> for ListBlobStreamBuilder<'a, Yes> {
+    fn from(builder: ListBlobBuilder<'a, Yes>) -> ListBlobStreamBuilder<'a, Yes> {
+        ListBlobStreamBuilder {
+            client: builder.client(),
+            p_container_name: PhantomData {},
+            container_name: Some(builder.container_name()),
+            client_request_id: builder.client_request_id(),
+            timeout: builder.timeout(),
+            prefix: builder.prefix(),
+            delimiter: builder.delimiter(),
+            include_snapshots: builder.include_snapshots(),
+            include_metadata: builder.include_metadata(),
+            include_uncommitted_blobs: builder.include_uncommitted_blobs(),
+            include_copy: builder.include_copy(),
+            include_deleted: builder.include_deleted(),
+            include_versions: builder.include_versions(),
+            include_tags: builder.include_tags(),
+            next_marker: builder.next_marker(),
+            max_results: builder.max_results(),
+            extra_headers: builder.extra_headers().to_vec(),
+            api_version: builder.api_version(),
+        }
+    }
+}
+
+impl<'a> From<ListBlobsFlatBuilder<'a, Yes>> for ListBlobStreamBuilder<'a, Yes> {
+    fn from(builder: ListBlobsFlatBuilder<'a, Yes>) -> ListBlobStreamBuilder<'a, Yes> {
+        ListBlobStreamBuilder {
+            client: builder.client(),
+            p_container_name: PhantomData {},
+            container_name: Some(builder.container_name()),
+            client_request_id: builder.client_request_id(),
+            timeout: builder.timeout(),
+            prefix: builder.prefix(),
+            delimiter: None,
+            include_snapshots: builder.include_snapshots(),
+            include_metadata: builder.include_metadata(),
+            include_uncommitted_blobs: builder.include_uncommitted_blobs(),
+            include_copy: builder.include_copy(),
+            include_deleted: builder.include_deleted(),
+            include_versions: builder.include_versions(),
+            include_tags: builder.include_tags(),
+            next_marker: builder.next_marker(),
+            max_results: builder.max_results(),
+            extra_headers: builder.extra_headers().to_vec(),
+            api_version: builder.api_version(),
         }
     }
 }
@@ -422,23 +772,48 @@ enum ContinuationState {
 }
 
 impl<'a> ListBlobStreamBuilder<'a, Yes> {
+    /// Streams every blob in the container, transparently following the
+    /// continuation markers.
     #[inline]
     pub fn finalize(self) -> impl Stream<Item = Blob, Error = AzureError> {
+        self.pages()
+            .map(|response| stream::iter_ok(response.incomplete_vector.vector))
+            .flatten()
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl futures_util::stream::Stream<Item = Result<Blob, AzureError>> {
+        crate::azure::core::async_compat::into_std_stream(self.finalize())
+    }
+
+    /// Like `finalize` but yields the raw pages instead of the single
+    /// blobs. The last page is the one without a `NextMarker`.
+    pub fn pages(self) -> impl Stream<Item = ListBlobsResponse, Error = AzureError> {
         let container_name = self.container_name().to_owned();
 
         let client_request_id = self.client_request_id.map(|v| v.to_owned());
+        let extra_headers = owned_headers(&self.extra_headers);
+        let api_version = self.api_version.map(|v| v.to_owned());
         let timeout = self.timeout.to_owned();
         let prefix = self.prefix.map(|v| v.to_owned());
         let delimiter = self.delimiter.map(|v| v.to_owned());
+        let max_results = self.max_results;
         let include_snapshots = self.include_snapshots;
         let include_metadata = self.include_metadata;
         let include_uncommitted_blobs = self.include_uncommitted_blobs;
         let include_copy = self.include_copy;
         let include_deleted = self.include_deleted;
+        let include_versions = self.include_versions;
+        let include_tags = self.include_tags;
 
         let client = self.client().clone();
 
-        stream::unfold(ContinuationState::Start, move |cont_token| {
+        let start = match self.next_marker {
+            Some(marker) => ContinuationState::Next(Some(marker.to_owned())),
+            None => ContinuationState::Start,
+        };
+
+        stream::unfold(start, move |cont_token| {
             let marker = match cont_token {
                 ContinuationState::Start => None,
                 ContinuationState::Next(Some(marker)) => Some(marker),
@@ -453,6 +828,12 @@ impl<'a> ListBlobStreamBuilder<'a, Yes> {
             if let Some(ref client_request_id) = &client_request_id {
                 req = req.with_client_request_id(client_request_id);
             }
+            for (name, value) in &extra_headers {
+                req = req.with_extra_header(name, value);
+            }
+            if let Some(ref api_version) = &api_version {
+                req = req.with_api_version(api_version);
+            }
             if let Some(timeout) = timeout {
                 req = req.with_timeout(timeout);
             }
@@ -462,6 +843,9 @@ impl<'a> ListBlobStreamBuilder<'a, Yes> {
             if let Some(ref delimiter) = &delimiter {
                 req = req.with_delimiter(delimiter);
             }
+            if let Some(max_results) = max_results {
+                req = req.with_max_results(max_results);
+            }
 
             if include_snapshots {
                 req = req.with_include_snapshots();
@@ -478,13 +862,18 @@ impl<'a> ListBlobStreamBuilder<'a, Yes> {
             if include_deleted {
                 req = req.with_include_deleted();
             }
+            if include_versions {
+                req = req.with_include_versions();
+            }
+            if include_tags {
+                req = req.with_include_tags();
+            }
 
             let req = req.finalize();
             Some(req.map(move |response| {
-                let IncompleteVector { token, vector } = response.incomplete_vector;
-                (stream::iter_ok(vector), ContinuationState::Next(token))
+                let token = response.incomplete_vector.token().map(|t| t.to_owned());
+                (response, ContinuationState::Next(token))
             }))
         })
-        .flatten()
     }
 }
@@ -1,9 +1,21 @@
 mod lease_blob_options;
 pub use self::lease_blob_options::{LeaseBlobOptions, LEASE_BLOB_OPTIONS_DEFAULT};
-use url::percent_encoding::utf8_percent_encode;
+use url::percent_encoding::{percent_decode, utf8_percent_encode};
 mod blob_stream_builder;
 pub use self::blob_stream_builder::BlobStreamBuilder;
+mod download_blob_stream_builder;
+pub use self::download_blob_stream_builder::DownloadBlobStreamBuilder;
+mod spooled_body;
+pub use self::spooled_body::{SpooledBody, TempFile, TempFileReader};
+mod record_framing;
+pub use self::record_framing::RecordFraming;
+mod upload_records_builder;
+pub use self::upload_records_builder::UploadRecordsBuilder;
+mod blob_versions_builder;
+pub use self::blob_versions_builder::{BlobVersionsBuilder, PruneBlobVersionsResponse, VersionRetention};
 mod blob_block_type;
+mod blob_hierarchy_item;
+pub use self::blob_hierarchy_item::BlobHierarchyItem;
 mod list_blob_stream_builder;
 pub use self::blob_block_type::BlobBlockType;
 pub use self::list_blob_stream_builder::ListBlobStreamBuilder;
@@ -15,30 +27,54 @@ mod block_with_size_list;
 pub use self::block_with_size_list::BlockWithSizeList;
 mod block_list;
 pub use self::block_list::BlockList;
+mod page_range_list;
+pub use self::page_range_list::PageRangeList;
+mod batch;
+pub use self::batch::{BlobBatchBuilder, BlobBatchOperationResponse, BlobBatchResponse, MAX_BATCH_SUBREQUESTS};
+mod encryption;
+pub use self::encryption::{ClientSideEncryption, KeyEncryptionKey, LocalKey, ENCRYPTION_DATA_METADATA};
+mod sync_directory_builder;
+pub use self::sync_directory_builder::{SyncAction, SyncComparison, SyncDirectoryBuilder, SyncDirectoryResponse, SyncProgress};
+mod upload_vhd_builder;
+pub use self::upload_vhd_builder::{UploadVhdBuilder, UploadVhdResponse};
+mod vhd_footer;
+pub use self::vhd_footer::{VhdFooter, VHD_FOOTER_SIZE};
+mod tags;
+pub use self::tags::{FilteredBlob, TagFilter, Tags};
+pub(crate) use self::tags::filtered_blobs_from_response;
 pub mod requests;
 pub mod responses;
 use crate::azure::core::headers::{
     BLOB_SEQUENCE_NUMBER, BLOB_TYPE, CONTENT_MD5, COPY_COMPLETION_TIME, COPY_ID, COPY_PROGRESS, COPY_SOURCE, COPY_STATUS,
-    COPY_STATUS_DESCRIPTION, CREATION_TIME, LEASE_DURATION, LEASE_STATE, LEASE_STATUS, SERVER_ENCRYPTED,
+    COPY_STATUS_DESCRIPTION, CREATION_TIME, IMMUTABILITY_POLICY_MODE, IMMUTABILITY_POLICY_UNTIL_DATE, IS_CURRENT_VERSION, LEASE_DURATION,
+    LEASE_STATE, LEASE_STATUS, LEGAL_HOLD, OR_POLICY_ID, OR_PREFIX, SERVER_ENCRYPTED, TAGS, TAG_COUNT, VERSION_ID,
 };
 use chrono::{DateTime, Utc};
+use http::request::Builder;
 use hyper::header;
 use std::collections::HashMap;
 use std::{fmt, str::FromStr};
 use xml::Element;
-use xml::Xml::ElementNode;
+use xml::Xml::{CDATANode, CharacterNode, ElementNode};
 
 use crate::azure::core::{
     enumerations,
     errors::{AzureError, TraversingError},
     incompletevector::IncompleteVector,
     lease::{LeaseDuration, LeaseState, LeaseStatus},
-    parsing::{cast_must, cast_optional, from_azure_time, inner_text, traverse, FromStringOptional},
+    metadata::Metadata,
+    parsing::{cast_must, cast_optional, from_azure_time, inner_text, traverse, traverse_single_must, FromStringOptional},
     range::Range,
     util::HeaderMapExt,
     BlobNameRequired, ClientRequired, ContainerNameRequired, COMPLETE_ENCODE_SET,
 };
 
+define_encode_set! {
+    // the blob names may contain '%', which must not be taken for an
+    // escape sequence
+    pub BLOB_NAME_ENCODE_SET = [COMPLETE_ENCODE_SET] | { '%' }
+}
+
 create_enum!(
     BlobType,
     (BlockBlob, "BlockBlob"),
@@ -56,6 +92,138 @@ create_enum!(
 
 create_enum!(PageWriteType, (Update, "update"), (Clear, "clear"));
 
+// the blob versions are not listed nor addressable before this version
+pub(crate) const VERSIONING_VERSION: &str = "2019-12-12";
+
+// nor are the blob index tags
+pub(crate) const TAGS_VERSION: &str = "2019-12-12";
+
+// Find Blobs by Tags returns the matching tags (instead of a lone
+// `TagValue`) from this version on
+pub(crate) const FIND_BLOBS_BY_TAGS_VERSION: &str = "2020-04-08";
+
+pub trait TagsSupport<'a> {
+    type O;
+    fn with_tags(self, tags: &'a Tags) -> Self::O;
+}
+
+pub trait TagsRequired<'a> {
+    fn tags(&self) -> &'a Tags;
+}
+
+pub trait TagsOption<'a> {
+    fn tags(&self) -> Option<&'a Tags>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(tags) = self.tags() {
+            builder.header(TAGS, &tags.to_header() as &str);
+        }
+    }
+}
+
+/// The `where` expression of `find_blobs_by_tags`, see `TagFilter`.
+pub trait TagFilterSupport<'a> {
+    type O;
+    fn with_tag_filter(self, tag_filter: &'a str) -> Self::O;
+}
+
+pub trait TagFilterRequired<'a> {
+    fn tag_filter(&self) -> &'a str;
+
+    fn to_uri_parameter(&self) -> String {
+        format!("where={}", utf8_percent_encode(self.tag_filter(), COMPLETE_ENCODE_SET))
+    }
+}
+
+// the immutability policies and legal holds of the blobs (as opposed to
+// the container wide ones) are not available before this version
+pub(crate) const IMMUTABILITY_VERSION: &str = "2020-06-12";
+
+/// The mode of a blob immutability policy. An unlocked policy can be
+/// shortened or deleted; a locked one can only be extended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImmutabilityPolicyMode {
+    Unlocked,
+    Locked,
+    /// No policy: returned once a policy has been deleted.
+    Mutable,
+}
+
+impl From<ImmutabilityPolicyMode> for &'static str {
+    fn from(mode: ImmutabilityPolicyMode) -> &'static str {
+        match mode {
+            ImmutabilityPolicyMode::Unlocked => "Unlocked",
+            ImmutabilityPolicyMode::Locked => "Locked",
+            ImmutabilityPolicyMode::Mutable => "Mutable",
+        }
+    }
+}
+
+// the service answers in lowercase
+impl FromStr for ImmutabilityPolicyMode {
+    type Err = enumerations::ParsingError;
+
+    fn from_str(s: &str) -> Result<ImmutabilityPolicyMode, enumerations::ParsingError> {
+        [
+            ImmutabilityPolicyMode::Unlocked,
+            ImmutabilityPolicyMode::Locked,
+            ImmutabilityPolicyMode::Mutable,
+        ]
+        .iter()
+        .cloned()
+        .find(|mode| s.eq_ignore_ascii_case((*mode).into()))
+        .ok_or_else(|| enumerations::ParsingError::ElementNotFound(s.to_owned()))
+    }
+}
+
+impl FromStringOptional<ImmutabilityPolicyMode> for ImmutabilityPolicyMode {
+    fn from_str_optional(s: &str) -> Result<ImmutabilityPolicyMode, TraversingError> {
+        s.parse::<ImmutabilityPolicyMode>().map_err(TraversingError::ParsingError)
+    }
+}
+
+pub trait ImmutabilityPolicyUntilDateSupport {
+    type O;
+    fn with_immutability_policy_until_date(self, immutability_policy_until_date: DateTime<Utc>) -> Self::O;
+}
+
+pub trait ImmutabilityPolicyUntilDateRequired {
+    fn immutability_policy_until_date(&self) -> DateTime<Utc>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        builder.header(IMMUTABILITY_POLICY_UNTIL_DATE, &self.immutability_policy_until_date().to_rfc2822() as &str);
+    }
+}
+
+pub trait ImmutabilityPolicyModeSupport {
+    type O;
+    fn with_immutability_policy_mode(self, immutability_policy_mode: ImmutabilityPolicyMode) -> Self::O;
+}
+
+pub trait ImmutabilityPolicyModeOption {
+    fn immutability_policy_mode(&self) -> Option<ImmutabilityPolicyMode>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(immutability_policy_mode) = self.immutability_policy_mode() {
+            let mode: &'static str = immutability_policy_mode.into();
+            builder.header(IMMUTABILITY_POLICY_MODE, mode);
+        }
+    }
+}
+
+pub trait LegalHoldSupport {
+    type O;
+    fn with_legal_hold(self, legal_hold: bool) -> Self::O;
+}
+
+pub trait LegalHoldRequired {
+    fn legal_hold(&self) -> bool;
+
+    fn add_header(&self, builder: &mut Builder) {
+        builder.header(LEGAL_HOLD, if self.legal_hold() { "true" } else { "false" });
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Blob {
     pub name: String,
@@ -89,13 +257,32 @@ pub struct Blob {
     pub access_tier_change_time: Option<DateTime<Utc>>,
     pub deleted_time: Option<DateTime<Utc>>,
     pub remaining_retention_days: Option<u64>,
-    pub metadata: HashMap<String, String>,
+    pub metadata: Metadata,
+    // only returned when versioning is enabled on the account
+    pub version_id: Option<String>,
+    pub is_current_version: Option<bool>,
+    /// Whether the blob is a soft-deleted one, listed with
+    /// `with_include_deleted`.
+    pub deleted: bool,
+    /// The index tags, only listed with `with_include_tags`.
+    pub tags: Tags,
+    pub tag_count: Option<u64>,
+    /// The object replication status of a source blob by
+    /// `or-<policy id>_<rule id>`, lowercase: `complete` or `failed`.
+    pub or_metadata: HashMap<String, String>,
+    // only set on the blobs of the containers with version-level
+    // immutability support
+    pub immutability_policy_until_date: Option<DateTime<Utc>>,
+    pub immutability_policy_mode: Option<ImmutabilityPolicyMode>,
+    pub legal_hold: Option<bool>,
 }
 
 impl Blob {
     pub(crate) fn parse(elem: &Element, container_name: &str) -> Result<Blob, AzureError> {
-        let name = cast_must::<String>(elem, &["Name"])?;
+        let name = parse_name(elem)?;
         let snapshot_time = cast_optional::<DateTime<Utc>>(elem, &["Snapshot"])?;
+        let version_id = cast_optional::<String>(elem, &["VersionId"])?;
+        let is_current_version = cast_optional::<bool>(elem, &["IsCurrentVersion"])?;
         let creation_time = cast_must::<DateTime<Utc>>(elem, &["Properties", "Creation-Time"])?;
         let last_modified = cast_optional::<DateTime<Utc>>(elem, &["Properties", "Last-Modified"])?;
         let etag = cast_optional::<String>(elem, &["Properties", "Etag"])?;
@@ -136,6 +323,11 @@ impl Blob {
         let access_tier_change_time = cast_optional::<DateTime<Utc>>(elem, &["Properties", "AccessTierChangeTime"])?;
         let deleted_time = cast_optional::<DateTime<Utc>>(elem, &["Properties", "DeletedTime"])?;
         let remaining_retention_days = cast_optional::<u64>(elem, &["Properties", "RemainingRetentionDays"])?;
+        let deleted = cast_optional::<bool>(elem, &["Deleted"])?.unwrap_or(false);
+        let tag_count = cast_optional::<u64>(elem, &["Properties", "TagCount"])?;
+        let immutability_policy_until_date = cast_optional::<DateTime<Utc>>(elem, &["Properties", "ImmutabilityPolicyUntilDate"])?;
+        let immutability_policy_mode = cast_optional::<ImmutabilityPolicyMode>(elem, &["Properties", "ImmutabilityPolicyMode"])?;
+        let legal_hold = cast_optional::<bool>(elem, &["Properties", "LegalHold"])?;
 
         let mut cp_bytes: Option<Range> = None;
         if let Some(txt) = copy_progress {
@@ -144,7 +336,7 @@ impl Blob {
 
         // metadata parsing
         let metadata = {
-            let mut metadata: HashMap<String, String> = HashMap::new();
+            let mut metadata = Metadata::new();
             let mds = traverse(elem, &["Metadata"], true)?;
             for md_node in mds {
                 for node in md_node.children.iter() {
@@ -153,7 +345,7 @@ impl Blob {
                             let key = elem.name.to_owned();
                             let value = inner_text(elem)?.to_owned();
                             debug!("key == {:?}, value == {:?}", key, value);
-                            metadata.insert(key, value);
+                            metadata.insert_received(&key, &value);
                         }
                         _ => return Err(TraversingError::UnexpectedNodeTypeError("ElementNode".to_owned()).into()),
                     }
@@ -162,6 +354,15 @@ impl Blob {
             metadata
         };
 
+        let tags = Tags::parse(elem, &["Tags"])?;
+
+        let mut or_metadata = HashMap::new();
+        for node in traverse(elem, &["OrMetadata"], true)?.iter().flat_map(|or_metadata| or_metadata.children.iter()) {
+            if let ElementNode(elem) = node {
+                or_metadata.insert(elem.name.to_lowercase(), inner_text(elem)?.to_owned());
+            }
+        }
+
         Ok(Blob {
             name,
             container_name: container_name.to_owned(),
@@ -195,6 +396,15 @@ impl Blob {
             deleted_time,
             remaining_retention_days,
             metadata,
+            version_id,
+            is_current_version,
+            deleted,
+            tags,
+            tag_count,
+            or_metadata,
+            immutability_policy_until_date,
+            immutability_policy_mode,
+            legal_hold,
         })
     }
 
@@ -303,6 +513,20 @@ impl Blob {
             .ok_or_else(|| AzureError::HeaderNotFound(SERVER_ENCRYPTED.to_owned()))?
             .parse::<bool>()?;
 
+        let version_id = h.get_as_string(VERSION_ID);
+        let is_current_version = h.get_as_str(IS_CURRENT_VERSION).and_then(|v| v.parse::<bool>().ok());
+        let tag_count = h.get_as_u64(TAG_COUNT);
+        let immutability_policy_until_date = h.get_as_str(IMMUTABILITY_POLICY_UNTIL_DATE).map(from_azure_time).transpose()?;
+        let immutability_policy_mode = h.get_as_enum(IMMUTABILITY_POLICY_MODE)?;
+        let legal_hold = h.get_as_str(LEGAL_HOLD).and_then(|v| v.parse::<bool>().ok());
+
+        // x-ms-or-<policy id>_<rule id> on the source blobs, as listed
+        let or_metadata = h
+            .iter()
+            .filter(|(name, _)| name.as_str().starts_with(OR_PREFIX) && name.as_str() != OR_POLICY_ID)
+            .filter_map(|(name, value)| Some((name.as_str()["x-ms-".len()..].to_owned(), value.to_str().ok()?.to_owned())))
+            .collect();
+
         Ok(Blob {
             name: blob_name.to_owned(),
             container_name: container_name.to_owned(),
@@ -335,11 +559,40 @@ impl Blob {
             access_tier_change_time: None,  // TODO: Not present
             deleted_time: None,             // TODO
             remaining_retention_days: None, // TODO: Not present or documentation bug?
-            metadata: HashMap::new(),       // TODO: Not present or documentation bug?
+            metadata: Metadata::from_headers(h)?,
+            version_id,
+            is_current_version,
+            deleted: false,
+            tags: Tags::new(),
+            tag_count,
+            or_metadata,
+            immutability_policy_until_date,
+            immutability_policy_mode,
+            legal_hold,
         })
     }
 }
 
+// The names holding characters not allowed in XML are percent encoded by
+// the service and flagged with Encoded="true".
+fn parse_name(elem: &Element) -> Result<String, AzureError> {
+    let node = traverse_single_must(elem, &["Name"])?;
+    let name = node
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            CharacterNode(text) | CDATANode(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<String>();
+
+    if node.get_attribute("Encoded", None) == Some("true") {
+        Ok(percent_decode(name.as_bytes()).decode_utf8()?.into_owned())
+    } else {
+        Ok(name)
+    }
+}
+
 #[inline]
 pub(crate) fn incomplete_vector_from_response(body: &str, container_name: &str) -> Result<IncompleteVector<Blob>, AzureError> {
     trace!("body = {}", body);
@@ -364,24 +617,750 @@ pub(crate) fn incomplete_vector_from_response(body: &str, container_name: &str)
     Ok(IncompleteVector::<Blob>::new(next_marker, v))
 }
 
+#[inline]
+pub(crate) fn incomplete_hierarchy_from_response(
+    body: &str,
+    container_name: &str,
+) -> Result<IncompleteVector<BlobHierarchyItem>, AzureError> {
+    trace!("body = {}", body);
+
+    let elem: Element = body.parse()?;
+
+    let next_marker = cast_optional::<String>(&elem, &["NextMarker"])?.filter(|nm| !nm.is_empty());
+
+    debug!("next_marker == {:?}", next_marker);
+
+    let mut v = Vec::new();
+    for node_blobs in traverse(&elem, &["Blobs"], true)? {
+        // blobs and prefixes are interleaved, keep the service order
+        for child in &node_blobs.children {
+            if let ElementNode(ref node) = *child {
+                match &node.name as &str {
+                    "Blob" => v.push(BlobHierarchyItem::Blob(Box::new(Blob::parse(node, container_name)?))),
+                    "BlobPrefix" => v.push(BlobHierarchyItem::Prefix(parse_name(node)?)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(IncompleteVector::new(next_marker, v))
+}
+
 #[inline]
 pub(crate) fn generate_blob_uri<'a, T>(t: &T, params: Option<&str>) -> String
 where
     T: ClientRequired<'a> + ContainerNameRequired<'a> + BlobNameRequired<'a>,
 {
+    blob_uri(t.client().blob_uri(), t.container_name(), t.blob_name(), params)
+}
+
+/// The uri of a blob of the service at `blob_service_uri` (for example
+/// `https://myaccount.blob.core.windows.net`), with the query `params`.
+/// The names are percent-encoded: the slashes of the blob name are sent
+/// as `%2F`, which the service takes as path separators.
+pub fn blob_uri(blob_service_uri: &str, container_name: &str, blob_name: &str, params: Option<&str>) -> String {
+    let uri = format!(
+        "{}/{}/{}",
+        blob_service_uri,
+        utf8_percent_encode(container_name, COMPLETE_ENCODE_SET),
+        utf8_percent_encode(blob_name, BLOB_NAME_ENCODE_SET)
+    );
     match params {
-        Some(ref params) => format!(
-            "{}/{}/{}?{}",
-            t.client().blob_uri(),
-            utf8_percent_encode(t.container_name(), COMPLETE_ENCODE_SET),
-            utf8_percent_encode(t.blob_name(), COMPLETE_ENCODE_SET),
-            params
-        ),
-        None => format!(
-            "{}/{}/{}",
-            t.client().blob_uri(),
-            utf8_percent_encode(t.container_name(), COMPLETE_ENCODE_SET),
-            utf8_percent_encode(t.blob_name(), COMPLETE_ENCODE_SET)
-        ),
+        Some(params) => format!("{}?{}", uri, params),
+        None => uri,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_name() {
+        let elem: Element = "<Blob><Name>a &amp; b</Name></Blob>".parse().unwrap();
+        assert_eq!(parse_name(&elem).unwrap(), "a & b");
+
+        let elem: Element = "<Blob><Name Encoded=\"true\">dir/bell%07%25.txt</Name></Blob>".parse().unwrap();
+        assert_eq!(parse_name(&elem).unwrap(), "dir/bell\u{7}%.txt");
+
+        // and back
+        assert_eq!(
+            utf8_percent_encode("dir/bell\u{7}%.txt", BLOB_NAME_ENCODE_SET).to_string(),
+            "dir%2Fbell%07%25.txt"
+        );
+    }
+
+    #[test]
+    fn test_parse_include() {
+        let body = "<Blob><Name>b</Name><Deleted>true</Deleted><VersionId>2019-10-15T10:00:00.0000000Z</VersionId><Properties>\
+                    <Creation-Time>Tue, 15 Oct 2019 10:00:00 GMT</Creation-Time><Content-Length>3</Content-Length>\
+                    <Content-Type>text/plain</Content-Type><BlobType>BlockBlob</BlobType><LeaseState>available</LeaseState>\
+                    <ServerEncrypted>true</ServerEncrypted><CopyStatus>success</CopyStatus>\
+                    <DeletedTime>Wed, 16 Oct 2019 08:30:00 GMT</DeletedTime><RemainingRetentionDays>6</RemainingRetentionDays>\
+                    <TagCount>1</TagCount></Properties><OrMetadata><Or-policy1_rule1>complete</Or-policy1_rule1></OrMetadata>\
+                    <Tags><TagSet><Tag><Key>project</Key><Value>alpha</Value></Tag></TagSet></Tags></Blob>";
+        let blob = Blob::parse(&body.parse::<Element>().unwrap(), "c").unwrap();
+        assert!(blob.deleted);
+        assert_eq!(blob.version_id.as_deref(), Some("2019-10-15T10:00:00.0000000Z"));
+        assert_eq!(blob.copy_status, Some(CopyStatus::Success));
+        assert_eq!(blob.deleted_time.unwrap().to_rfc3339(), "2019-10-16T08:30:00+00:00");
+        assert_eq!(blob.remaining_retention_days, Some(6));
+        assert_eq!(blob.tag_count, Some(1));
+        assert_eq!(&blob.tags["project"], "alpha");
+        assert_eq!(blob.or_metadata["or-policy1_rule1"], "complete");
+    }
+
+    #[test]
+    fn test_version_id() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{AccessTierSupport, BlobNameSupport, ContainerNameSupport, VersionIdSupport};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let version = "2019-10-15T10:00:00.0000000Z";
+        let properties = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("last-modified", "Tue, 15 Oct 2019 09:00:00 GMT"),
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("content-length", "3"),
+            ("x-ms-blob-type", "BlockBlob"),
+            ("x-ms-creation-time", "Tue, 15 Oct 2019 09:00:00 GMT"),
+            ("x-ms-lease-status", "unlocked"),
+            ("x-ms-lease-state", "available"),
+            ("x-ms-server-encrypted", "true"),
+            ("x-ms-version-id", version),
+        ];
+        let tiered = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::OK, &properties, b"")
+                .with_response(StatusCode::ACCEPTED, &tiered, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+
+        let blob = client
+            .get_blob_properties()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_version_id(version)
+            .finalize()
+            .wait()
+            .unwrap()
+            .blob;
+        assert_eq!(blob.version_id.as_deref(), Some(version));
+
+        let response = client
+            .set_blob_tier()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_access_tier("Hot")
+            .with_version_id(version)
+            .finalize()
+            .wait()
+            .unwrap();
+        assert!(response.rehydrating);
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].uri, format!("https://account.blob.core.windows.net/c/b?versionid={}", version));
+        assert_eq!(
+            requests[1].uri,
+            format!("https://account.blob.core.windows.net/c/b?comp=tier&versionid={}", version)
+        );
+        assert_eq!(requests[1].headers["x-ms-access-tier"], "Hot");
+        for request in &requests {
+            assert_eq!(request.headers["x-ms-version"], VERSIONING_VERSION);
+        }
+    }
+
+    #[test]
+    fn test_metadata() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{BlobNameSupport, BodySupport, ContainerNameSupport, MetadataSupport};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let uploaded = [
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("content-md5", "kAFQmDzST7DWlj99KOF/cg=="),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-server-encrypted", "true"),
+        ];
+        let properties = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("content-length", "3"),
+            ("x-ms-blob-type", "BlockBlob"),
+            ("x-ms-creation-time", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-lease-status", "unlocked"),
+            ("x-ms-lease-state", "available"),
+            ("x-ms-server-encrypted", "true"),
+            ("x-ms-meta-owner", "ops"),
+            ("x-ms-meta-city", "Z%C3%BCrich"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::CREATED, &uploaded, b"")
+                .with_response(StatusCode::OK, &properties, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+
+        let mut metadata = Metadata::new();
+        metadata.insert("Owner", "ops").unwrap();
+        metadata.insert("City", "Z%C3%BCrich").unwrap();
+        client
+            .put_block_blob()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_body(b"abc")
+            .with_metadata(&metadata)
+            .finalize()
+            .wait()
+            .unwrap();
+        let response = client
+            .get_blob_properties()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .finalize()
+            .wait()
+            .unwrap();
+        assert_eq!(response.blob.metadata, metadata);
+        assert_eq!(response.common_headers.request_id, Some(response.request_id));
+        assert_eq!(response.common_headers.server_encrypted, Some(true));
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].headers["x-ms-meta-owner"], "ops");
+        assert_eq!(requests[0].headers["x-ms-meta-city"], "Z%C3%BCrich");
+    }
+
+    #[test]
+    fn test_blob_exists() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{BlobNameSupport, ContainerNameSupport, SnapshotSupport};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::{Method, StatusCode};
+        use std::sync::Arc;
+
+        let properties = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("content-length", "3"),
+            ("x-ms-blob-type", "BlockBlob"),
+            ("x-ms-creation-time", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-lease-status", "unlocked"),
+            ("x-ms-lease-state", "available"),
+            ("x-ms-server-encrypted", "true"),
+        ];
+        let missing = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::OK, &properties, b"")
+                .with_response(StatusCode::NOT_FOUND, &missing, b"")
+                .with_response(StatusCode::INTERNAL_SERVER_ERROR, &missing, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+
+        let snapshot = "2019-10-15T10:00:00.0000000Z".parse::<DateTime<Utc>>().unwrap();
+        let exists = || {
+            client
+                .blob_exists()
+                .with_container_name("c")
+                .with_blob_name("b")
+                .with_snapshot(snapshot)
+                .finalize()
+                .wait()
+        };
+        assert!(exists().unwrap());
+        assert!(!exists().unwrap());
+        assert!(exists().is_err());
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::HEAD);
+        assert_eq!(request.uri, "https://account.blob.core.windows.net/c/b?snapshot=2019-10-15T10:00:00.0000000Z");
+    }
+
+    #[test]
+    fn test_delete_blob_if_exists() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{BlobNameSupport, ContainerNameSupport, DeleteSnapshotsMethod, DeleteSnapshotsMethodSupport};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::{Method, StatusCode};
+        use std::sync::Arc;
+
+        let deleted = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-delete-type-permanent", "true"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::ACCEPTED, &deleted, b"")
+                .with_response(StatusCode::NOT_FOUND, &[("x-ms-error-code", "BlobNotFound")], b"")
+                .with_response(StatusCode::PRECONDITION_FAILED, &[("x-ms-error-code", "ConditionNotMet")], b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+
+        let delete = || {
+            client
+                .delete_blob()
+                .with_container_name("c")
+                .with_blob_name("b")
+                .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+                .delete_if_exists()
+                .wait()
+        };
+        assert!(delete().unwrap());
+        assert!(!delete().unwrap());
+        assert!(delete().unwrap_err().is_condition_not_met());
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::DELETE);
+        assert_eq!(request.uri, "https://account.blob.core.windows.net/c/b");
+    }
+
+    #[test]
+    fn test_tags() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{BlobNameSupport, ContainerNameSupport};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let headers = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ];
+        let tags: Tags = vec![("project", "alpha")].into_iter().collect();
+        let found = "<EnumerationResults><Blobs><Blob><Name>b</Name><ContainerName>c</ContainerName><Tags><TagSet><Tag>\
+                     <Key>project</Key><Value>alpha</Value></Tag></TagSet></Tags></Blob></Blobs><NextMarker /></EnumerationResults>";
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::NO_CONTENT, &headers, b"")
+                .with_response(StatusCode::OK, &headers, tags.to_xml().as_bytes())
+                .with_response(StatusCode::OK, &headers, found.as_bytes()),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+
+        client
+            .set_blob_tags()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_tags(&tags)
+            .finalize()
+            .wait()
+            .unwrap();
+        let read = client.get_blob_tags().with_container_name("c").with_blob_name("b").finalize().wait().unwrap();
+        assert_eq!(read.tags, tags);
+        let filter = TagFilter::eq("project", "alpha");
+        let found = client.find_blobs_by_tags().with_tag_filter(filter.as_str()).finalize().wait().unwrap();
+        assert!(found.is_complete());
+        assert_eq!(found.incomplete_vector.vector[0].name, "b");
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].uri, "https://account.blob.core.windows.net/c/b?comp=tags");
+        assert_eq!(requests[0].body.as_deref(), Some(tags.to_xml().as_bytes()));
+        assert_eq!(
+            requests[2].uri,
+            "https://account.blob.core.windows.net/?comp=blobs&where=%22project%22%20%3D%20'alpha'"
+        );
+        assert_eq!(requests[1].headers["x-ms-version"], TAGS_VERSION);
+        assert_eq!(requests[2].headers["x-ms-version"], FIND_BLOBS_BY_TAGS_VERSION);
+    }
+
+    #[test]
+    fn test_immutability() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{BlobNameSupport, ContainerNameSupport};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use chrono::TimeZone;
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let until = Utc.ymd(2030, 10, 15).and_hms(10, 0, 0);
+        let headers = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-immutability-policy-until-date", "Tue, 15 Oct 2030 10:00:00 GMT"),
+            ("x-ms-immutability-policy-mode", "locked"),
+            ("x-ms-legal-hold", "true"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::OK, &headers, b"")
+                .with_response(StatusCode::OK, &headers, b"")
+                .with_response(StatusCode::OK, &headers[..2], b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+
+        let policy = client
+            .set_blob_immutability_policy()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_immutability_policy_until_date(until)
+            .with_immutability_policy_mode(ImmutabilityPolicyMode::Locked)
+            .finalize()
+            .wait()
+            .unwrap();
+        assert_eq!(policy.immutability_policy_until_date, until);
+        assert_eq!(policy.immutability_policy_mode, ImmutabilityPolicyMode::Locked);
+        let hold = client
+            .set_blob_legal_hold()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_legal_hold(true)
+            .finalize()
+            .wait()
+            .unwrap();
+        assert!(hold.legal_hold);
+        client
+            .delete_blob_immutability_policy()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .finalize()
+            .wait()
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].uri, "https://account.blob.core.windows.net/c/b?comp=immutabilityPolicies");
+        assert_eq!(requests[0].headers["x-ms-immutability-policy-mode"], "Locked");
+        assert_eq!(requests[1].uri, "https://account.blob.core.windows.net/c/b?comp=legalhold");
+        assert_eq!(requests[1].headers["x-ms-legal-hold"], "true");
+        assert_eq!(requests[2].method, hyper::Method::DELETE);
+        assert_eq!(requests[2].headers["x-ms-version"], IMMUTABILITY_VERSION);
+
+        let body = "<Blob><Name>b</Name><Properties><Creation-Time>Tue, 15 Oct 2019 10:00:00 GMT</Creation-Time>\
+                    <Content-Length>3</Content-Length><Content-Type>text/plain</Content-Type><BlobType>BlockBlob</BlobType>\
+                    <LeaseState>available</LeaseState><ServerEncrypted>true</ServerEncrypted>\
+                    <ImmutabilityPolicyUntilDate>Tue, 15 Oct 2030 10:00:00 GMT</ImmutabilityPolicyUntilDate>\
+                    <ImmutabilityPolicyMode>unlocked</ImmutabilityPolicyMode><LegalHold>false</LegalHold></Properties></Blob>";
+        let blob = Blob::parse(&body.parse::<Element>().unwrap(), "c").unwrap();
+        assert_eq!(blob.immutability_policy_until_date, Some(until));
+        assert_eq!(blob.immutability_policy_mode, Some(ImmutabilityPolicyMode::Unlocked));
+        assert_eq!(blob.legal_hold, Some(false));
+    }
+
+    #[test]
+    fn test_get_blob_range() {
+        use crate::azure::core::range::ContentRange;
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{BlobNameSupport, ContainerNameSupport, RangeSupport};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let partial = |content_md5: &'static str| {
+            [
+                ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+                ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+                ("last-modified", "Tue, 15 Oct 2019 09:00:00 GMT"),
+                ("etag", "\"0x8D75157AA1C4E03\""),
+                ("content-length", "3"),
+                ("content-range", "bytes 1-3/10"),
+                ("content-md5", content_md5),
+                ("x-ms-blob-type", "BlockBlob"),
+                ("x-ms-creation-time", "Tue, 15 Oct 2019 09:00:00 GMT"),
+                ("x-ms-lease-status", "unlocked"),
+                ("x-ms-lease-state", "available"),
+                ("x-ms-server-encrypted", "true"),
+            ]
+        };
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::PARTIAL_CONTENT, &partial("1LfChIgsqeIIu2Xoq9X0yA=="), b"bcd")
+                .with_response(StatusCode::PARTIAL_CONTENT, &partial("1B2M2Y8AsgTpgAmY7PhCfg=="), b"bcd"),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+        let range = Range::new(1, 3);
+        let get = || client.get_blob().with_container_name("c").with_blob_name("b").with_range(&range).finalize().wait();
+
+        let response = get().unwrap();
+        assert_eq!(response.data, b"bcd");
+        assert_eq!(
+            response.content_range,
+            Some(ContentRange {
+                start: 1,
+                end: 3,
+                total_length: Some(10)
+            })
+        );
+        // the MD5 of the range is checked
+        match get() {
+            Err(AzureError::ChecksumMismatch(computed, returned)) => {
+                assert_eq!(computed, "1LfChIgsqeIIu2Xoq9X0yA==");
+                assert_eq!(returned, "1B2M2Y8AsgTpgAmY7PhCfg==");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].headers["x-ms-range"], "bytes=1-3");
+        assert_eq!(requests[0].headers["x-ms-range-get-content-md5"], "true");
+    }
+
+    #[test]
+    fn test_get_page_ranges() {
+        use crate::azure::core::ba512_range::BA512Range;
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{BA512RangeSupport, BlobNameSupport, ContainerNameSupport, SnapshotSupport};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use chrono::TimeZone;
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let headers = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("last-modified", "Tue, 15 Oct 2019 09:00:00 GMT"),
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("x-ms-blob-content-length", "1048576"),
+        ];
+        let body = "<?xml version=\"1.0\" encoding=\"utf-8\"?><PageList><PageRange><Start>0</Start><End>511</End></PageRange>\
+                    <PageRange><Start>4096</Start><End>8191</End></PageRange></PageList>";
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::OK, &headers, body.as_bytes()));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+
+        let range = BA512Range::new(0, 65535).unwrap();
+        let response = client
+            .get_page_ranges()
+            .with_container_name("c")
+            .with_blob_name("disk.vhd")
+            .with_snapshot(Utc.ymd(2019, 10, 15).and_hms(9, 0, 0))
+            .with_ba512_range(&range)
+            .finalize()
+            .wait()
+            .unwrap();
+        assert_eq!(response.blob_content_length, 1_048_576);
+        assert_eq!(
+            response.page_range_list.page_ranges,
+            vec![BA512Range::new(0, 511).unwrap(), BA512Range::new(4096, 8191).unwrap()]
+        );
+        assert!(response.page_range_list.clear_ranges.is_empty());
+
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].uri,
+            "https://account.blob.core.windows.net/c/disk.vhd?comp=pagelist&snapshot=2019-10-15T09:00:00.0000000Z"
+        );
+        assert_eq!(requests[0].headers["x-ms-range"], "bytes=0-65535");
+    }
+
+    #[test]
+    fn test_customer_provided_key_version() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::customer_provided_key::CustomerProvidedKey;
+        use crate::azure::core::{ApiVersionSupport, BlobNameSupport, BlockIdSupport, BodySupport, CPKSupport, ContainerNameSupport, CPK_VERSION};
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let created = [
+            ("content-md5", "kAFQmDzST7DWlj99KOF/cg=="),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-server-encrypted", "true"),
+        ];
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::CREATED, &created, b""));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+        let key = CustomerProvidedKey::new(&[0u8; 32]).unwrap();
+
+        client
+            .put_block()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_body(b"abc")
+            .with_block_id(b"block")
+            .with_customer_provided_key(&key)
+            .finalize()
+            .wait()
+            .unwrap();
+        // the key needs a newer version than the client default
+        let requests = transport.requests();
+        assert_eq!(requests[0].headers["x-ms-version"], CPK_VERSION);
+        assert_eq!(requests[0].headers["x-ms-encryption-key-sha256"], key.key_sha256());
+
+        let error = client
+            .snapshot_blob()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_customer_provided_key(&key)
+            .with_api_version("2017-11-09")
+            .finalize()
+            .wait()
+            .unwrap_err();
+        assert!(matches!(error, AzureError::InputParametersError(_)));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_copy_source_authorization() {
+        use crate::azure::core::copy_source_authorization::CopySourceAuthorization;
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{
+            ApiVersionSupport, BlobNameSupport, BlockIdSupport, ContainerNameSupport, CopySourceAuthorizationSupport, CopySourceSupport,
+            COPY_SOURCE_AUTHORIZATION_VERSION,
+        };
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let copied = [
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-copy-id", "c2a1b1f2-3c8a-4f9e-8b0a-4a4e7b1f2d3c"),
+            ("x-ms-copy-status", "success"),
+        ];
+        let staged = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-server-encrypted", "true"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::ACCEPTED, &copied, b"")
+                .with_response(StatusCode::CREATED, &staged, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+        let authorization = CopySourceAuthorization::bearer("eyJ0eXAi");
+        let source = "https://other.blob.core.windows.net/c/source";
+
+        let response = client
+            .copy_blob_from_url()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_copy_source(source)
+            .with_copy_source_authorization(&authorization)
+            .finalize()
+            .wait()
+            .unwrap();
+        assert_eq!(response.copy_status, CopyStatus::Success);
+        client
+            .put_block_from_url()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_block_id(b"block")
+            .with_copy_source(source)
+            .with_copy_source_authorization(&authorization)
+            .finalize()
+            .wait()
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].headers["x-ms-requires-sync"], "true");
+        for request in &requests {
+            assert_eq!(request.headers["x-ms-copy-source-authorization"], "Bearer eyJ0eXAi");
+            assert_eq!(request.headers["x-ms-version"], COPY_SOURCE_AUTHORIZATION_VERSION);
+        }
+
+        // the authorization is not sent with an older version
+        let error = client
+            .copy_blob_from_url()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_copy_source(source)
+            .with_copy_source_authorization(&authorization)
+            .with_api_version("2019-12-12")
+            .finalize()
+            .wait()
+            .unwrap_err();
+        assert!(matches!(error, AzureError::InputParametersError(_)));
+    }
+
+    #[test]
+    fn test_copy_source_tags_mode() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{
+            ApiVersionSupport, BlobNameSupport, ContainerNameSupport, CopySourceSupport, CopySourceTagsMode, CopySourceTagsModeSupport,
+            COPY_SOURCE_TAGS_MODE_VERSION,
+        };
+        use crate::azure::storage::client::{Blob as BlobClient, Client};
+        use futures::Future;
+        use hyper::StatusCode;
+        use std::sync::Arc;
+
+        let copied = [
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-copy-id", "c2a1b1f2-3c8a-4f9e-8b0a-4a4e7b1f2d3c"),
+            ("x-ms-copy-status", "success"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::ACCEPTED, &copied, b"")
+                .with_response(StatusCode::ACCEPTED, &copied, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+        let source = "https://other.blob.core.windows.net/c/source";
+        let mut tags = Tags::new();
+        tags.insert("project", "rust sdk");
+        tags.insert("stage", "a&b=c");
+
+        client
+            .copy_blob_from_url()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_copy_source(source)
+            .with_tags(&tags)
+            .with_copy_source_tags_mode(CopySourceTagsMode::Replace)
+            .finalize()
+            .wait()
+            .unwrap();
+        client
+            .copy_blob_from_url()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_copy_source(source)
+            .with_tags(&tags)
+            .finalize()
+            .wait()
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].headers["x-ms-tags"], "project=rust%20sdk&stage=a%26b%3Dc");
+        assert_eq!(requests[0].headers["x-ms-copy-source-tag-option"], "REPLACE");
+        assert_eq!(requests[0].headers["x-ms-version"], COPY_SOURCE_TAGS_MODE_VERSION);
+        // the tags alone need an older version
+        assert!(requests[1].headers.get("x-ms-copy-source-tag-option").is_none());
+        assert_eq!(requests[1].headers["x-ms-version"], TAGS_VERSION);
+
+        let error = client
+            .copy_blob_from_url()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_copy_source(source)
+            .with_copy_source_tags_mode(CopySourceTagsMode::Copy)
+            .with_api_version("2020-10-02")
+            .finalize()
+            .wait()
+            .unwrap_err();
+        assert!(matches!(error, AzureError::InputParametersError(_)));
     }
 }
@@ -0,0 +1,70 @@
+use crate::azure::core::ba512_range::BA512Range;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::parsing::cast_must;
+use xml::Element;
+use xml::Xml::ElementNode;
+
+/// The ranges of a page blob, as returned by Get Page Ranges. When the
+/// ranges are diffed against a previous snapshot, `page_ranges` holds the
+/// ranges written and `clear_ranges` the ranges cleared since that
+/// snapshot. Otherwise `clear_ranges` is always empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageRangeList {
+    pub page_ranges: Vec<BA512Range>,
+    pub clear_ranges: Vec<BA512Range>,
+}
+
+impl PageRangeList {
+    pub fn try_from(xml: &str) -> Result<PageRangeList, AzureError> {
+        let elem: Element = xml.trim_start_matches('\u{feff}').parse()?;
+
+        let mut page_range_list = PageRangeList::default();
+
+        // page and clear ranges are interleaved, sorted by offset
+        for node in &elem.children {
+            let node = match node {
+                ElementNode(node) => node,
+                _ => continue,
+            };
+
+            let ranges = match &node.name as &str {
+                "PageRange" => &mut page_range_list.page_ranges,
+                "ClearRange" => &mut page_range_list.clear_ranges,
+                _ => continue,
+            };
+
+            let start = cast_must::<u64>(node, &["Start"])?;
+            let end = cast_must::<u64>(node, &["End"])?;
+            ranges.push(BA512Range::new(start, end).map_err(|_| AzureError::PageNot512ByteAlignedError(start, end))?);
+        }
+
+        Ok(page_range_list)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_diff() {
+        let xml = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>
+            <PageList>
+                <PageRange><Start>0</Start><End>511</End></PageRange>
+                <ClearRange><Start>512</Start><End>1023</End></ClearRange>
+                <PageRange><Start>2048</Start><End>4095</End></PageRange>
+            </PageList>";
+
+        let list = PageRangeList::try_from(xml).unwrap();
+        assert_eq!(
+            list.page_ranges,
+            vec![BA512Range::new(0, 511).unwrap(), BA512Range::new(2048, 4095).unwrap()]
+        );
+        assert_eq!(list.clear_ranges, vec![BA512Range::new(512, 1023).unwrap()]);
+
+        let list = PageRangeList::try_from("<PageList />").unwrap();
+        assert_eq!(list, PageRangeList::default());
+
+        assert!(PageRangeList::try_from("<PageList><PageRange><Start>1</Start><End>511</End></PageRange></PageList>").is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::azure::core::errors::AzureError;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// How records are laid out in the blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFraming {
+    /// One JSON document per line (`application/x-ndjson`).
+    NdJson,
+    /// RFC 4180 comma separated values (`text/csv`). The header row is
+    /// taken from the field names of the first record: every record must
+    /// serialize as a struct or map. Missing fields are left empty,
+    /// unknown ones are an error.
+    Csv,
+}
+
+impl RecordFraming {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            RecordFraming::NdJson => "application/x-ndjson",
+            RecordFraming::Csv => "text/csv",
+        }
+    }
+}
+
+/// Appends serialized records to a buffer. Stateful because the CSV
+/// header must be written just once.
+#[derive(Debug, Clone)]
+pub(crate) struct RecordEncoder {
+    framing: RecordFraming,
+    columns: Option<Vec<String>>,
+}
+
+impl RecordEncoder {
+    pub(crate) fn new(framing: RecordFraming) -> RecordEncoder {
+        RecordEncoder { framing, columns: None }
+    }
+
+    pub(crate) fn encode<T>(&mut self, record: &T, buffer: &mut Vec<u8>) -> Result<(), AzureError>
+    where
+        T: Serialize,
+    {
+        match self.framing {
+            RecordFraming::NdJson => {
+                serde_json::to_writer(&mut *buffer, record)?;
+                buffer.push(b'\n');
+            }
+            RecordFraming::Csv => {
+                // going through serde_json keeps the field order, unlike
+                // serde_json::Value (which sorts the keys)
+                let row: CsvRow = serde_json::from_str(&serde_json::to_string(record)?)?;
+
+                if self.columns.is_none() {
+                    let columns: Vec<String> = row.0.iter().map(|(name, _)| name.to_owned()).collect();
+                    write_csv_line(columns.iter().map(|c| c as &str), buffer);
+                    self.columns = Some(columns);
+                }
+                let columns = self.columns.as_ref().unwrap();
+
+                if let Some((name, _)) = row.0.iter().find(|(name, _)| !columns.contains(name)) {
+                    return Err(AzureError::GenericErrorWithText(format!(
+                        "record field \"{}\" is not in the CSV header {:?}",
+                        name, columns
+                    )));
+                }
+
+                let fields: Vec<String> = columns
+                    .iter()
+                    .map(|column| {
+                        row.0
+                            .iter()
+                            .find(|(name, _)| name == column)
+                            .map(|(_, value)| csv_field(value))
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                write_csv_line(fields.iter().map(|f| f as &str), buffer);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.to_owned(),
+        other => other.to_string(),
+    }
+}
+
+fn write_csv_line<'a, I>(fields: I, buffer: &mut Vec<u8>)
+where
+    I: Iterator<Item = &'a str>,
+{
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            buffer.push(b',');
+        }
+        if field.contains(&[',', '"', '\r', '\n'][..]) {
+            buffer.push(b'"');
+            buffer.extend_from_slice(field.replace('"', "\"\"").as_bytes());
+            buffer.push(b'"');
+        } else {
+            buffer.extend_from_slice(field.as_bytes());
+        }
+    }
+    buffer.extend_from_slice(b"\r\n");
+}
+
+// a JSON object with its keys in document order
+struct CsvRow(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for CsvRow {
+    fn deserialize<D>(deserializer: D) -> Result<CsvRow, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CsvRowVisitor;
+
+        impl<'de> Visitor<'de> for CsvRowVisitor {
+            type Value = CsvRow;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a struct or a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<CsvRow, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut fields = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    fields.push(entry);
+                }
+                Ok(CsvRow(fields))
+            }
+        }
+
+        deserializer.deserialize_map(CsvRowVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Reading {
+        sensor: &'static str,
+        value: f64,
+        note: Option<&'static str>,
+    }
+
+    #[test]
+    fn test_ndjson() {
+        let mut encoder = RecordEncoder::new(RecordFraming::NdJson);
+        let mut buffer = Vec::new();
+        encoder
+            .encode(
+                &Reading {
+                    sensor: "a",
+                    value: 1.5,
+                    note: None,
+                },
+                &mut buffer,
+            )
+            .unwrap();
+        encoder.encode(&vec![1, 2], &mut buffer).unwrap();
+
+        assert_eq!(
+            std::str::from_utf8(&buffer).unwrap(),
+            "{\"sensor\":\"a\",\"value\":1.5,\"note\":null}\n[1,2]\n"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut encoder = RecordEncoder::new(RecordFraming::Csv);
+        let mut buffer = Vec::new();
+        encoder
+            .encode(
+                &Reading {
+                    sensor: "a",
+                    value: 1.5,
+                    note: None,
+                },
+                &mut buffer,
+            )
+            .unwrap();
+        encoder
+            .encode(
+                &Reading {
+                    sensor: "b,c",
+                    value: 2.0,
+                    note: Some("say \"hi\""),
+                },
+                &mut buffer,
+            )
+            .unwrap();
+
+        assert_eq!(
+            std::str::from_utf8(&buffer).unwrap(),
+            "sensor,value,note\r\na,1.5,\r\n\"b,c\",2.0,\"say \"\"hi\"\"\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_rejects_unknown_fields() {
+        let mut encoder = RecordEncoder::new(RecordFraming::Csv);
+        let mut buffer = Vec::new();
+
+        let mut first = std::collections::BTreeMap::new();
+        first.insert("a", 1);
+        encoder.encode(&first, &mut buffer).unwrap();
+
+        let mut second = std::collections::BTreeMap::new();
+        second.insert("b", 2);
+        assert!(encoder.encode(&second, &mut buffer).is_err());
+        assert!(encoder.encode(&"not a struct", &mut buffer).is_err());
+    }
+}
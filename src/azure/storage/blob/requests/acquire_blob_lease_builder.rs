@@ -1,10 +1,10 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::headers::LEASE_ACTION;
-use crate::azure::core::lease::LeaseId;
+use crate::azure::core::lease::{LeaseId, LeasePeriod};
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
-    ContainerNameSupport, LeaseDurationRequired, LeaseDurationSupport, ProposedLeaseIdOption, ProposedLeaseIdSupport, TimeoutOption,
-    TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseDurationRequired, LeaseDurationSupport,
+    ProposedLeaseIdOption, ProposedLeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::blob::generate_blob_uri;
@@ -27,10 +27,12 @@ where
     p_lease_duration: PhantomData<LeaseDurationSet>,
     container_name: Option<&'a str>,
     blob_name: Option<&'a str>,
-    lease_duration: i8,
+    lease_duration: LeasePeriod,
     proposed_lease_id: Option<&'a LeaseId>,
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> AcquireBlobLeaseBuilder<'a, No, No, No> {
@@ -43,10 +45,12 @@ impl<'a> AcquireBlobLeaseBuilder<'a, No, No, No> {
             p_blob_name: PhantomData {},
             blob_name: None,
             p_lease_duration: PhantomData {},
-            lease_duration: -1,
+            lease_duration: LeasePeriod::infinite(),
             proposed_lease_id: None,
             timeout: None,
             client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -92,7 +96,7 @@ where
     BlobNameSet: ToAssign,
 {
     #[inline]
-    fn lease_duration(&self) -> i8 {
+    fn lease_duration(&self) -> LeasePeriod {
         self.lease_duration
     }
 }
@@ -158,6 +162,8 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -184,6 +190,8 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -198,7 +206,7 @@ where
     type O = AcquireBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
 
     #[inline]
-    fn with_lease_duration(self, lease_duration: i8) -> Self::O {
+    fn with_lease_duration(self, lease_duration: LeasePeriod) -> Self::O {
         AcquireBlobLeaseBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -210,6 +218,8 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -236,6 +246,8 @@ where
             proposed_lease_id: Some(proposed_lease_id),
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -262,6 +274,8 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -288,6 +302,76 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet> ExtraHeadersOption<'a>
+    for AcquireBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseDurationSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet> ExtraHeadersSupport<'a>
+    for AcquireBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseDurationSet: ToAssign,
+{
+    type O = AcquireBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet> ApiVersionOption<'a>
+    for AcquireBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseDurationSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet> ApiVersionSupport<'a>
+    for AcquireBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseDurationSet: ToAssign,
+{
+    type O = AcquireBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseDurationSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        AcquireBlobLeaseBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_lease_duration: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            lease_duration: self.lease_duration,
+            proposed_lease_id: self.proposed_lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -317,6 +401,8 @@ impl<'a> AcquireBlobLeaseBuilder<'a, Yes, Yes, Yes> {
                 LeaseDurationRequired::add_header(&self, request);
                 ProposedLeaseIdOption::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -326,4 +412,9 @@ impl<'a> AcquireBlobLeaseBuilder<'a, Yes, Yes, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::CREATED))
             .and_then(|(headers, _body)| done(AcquireBlobLeaseResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<AcquireBlobLeaseResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
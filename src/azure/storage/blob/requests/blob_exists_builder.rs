@@ -0,0 +1,433 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, No,
+    SnapshotOption, SnapshotSupport, TimeoutOption, TimeoutSupport, ToAssign, VersionIdOption, VersionIdSupport, Yes,
+};
+use crate::azure::storage::client::{Blob, Client};
+use chrono::{DateTime, Utc};
+use futures::prelude::*;
+use std::marker::PhantomData;
+#[derive(Debug, Clone)]
+pub struct BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    snapshot: Option<DateTime<Utc>>,
+    version_id: Option<&'a str>,
+    lease_id: Option<&'a LeaseId>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> BlobExistsBuilder<'a, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> BlobExistsBuilder<'a, No, No> {
+        BlobExistsBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            snapshot: None,
+            version_id: None,
+            lease_id: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequired<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet> ContainerNameRequired<'a> for BlobExistsBuilder<'a, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet> BlobNameRequired<'a> for BlobExistsBuilder<'a, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> SnapshotOption for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> VersionIdOption<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn version_id(&self) -> Option<&'a str> {
+        self.version_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> LeaseIdOption<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutOption for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersOption<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionOption<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ContainerNameSupport<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        BlobExistsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> BlobNameSupport<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        BlobExistsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> SnapshotSupport for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        BlobExistsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: Some(snapshot),
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> VersionIdSupport<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_version_id(self, version_id: &'a str) -> Self::O {
+        BlobExistsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: Some(version_id),
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> LeaseIdSupport<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        BlobExistsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: Some(lease_id),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutSupport for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        BlobExistsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        BlobExistsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersSupport<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionSupport<'a> for BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        BlobExistsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet> BlobExistsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+}
+
+impl<'a> BlobExistsBuilder<'a, Yes, Yes> {
+    /// Resolves to whether the blob (or its snapshot or version) exists,
+    /// by getting its properties: a `404 Not Found`, whether the blob or
+    /// its container is missing, is `false` rather than an error.
+    pub fn finalize(self) -> impl Future<Item = bool, Error = AzureError> {
+        let mut req = self
+            .client()
+            .get_blob_properties()
+            .with_container_name(self.container_name())
+            .with_blob_name(self.blob_name());
+        if let Some(snapshot) = self.snapshot() {
+            req = req.with_snapshot(snapshot);
+        }
+        if let Some(version_id) = self.version_id() {
+            req = req.with_version_id(version_id);
+        }
+        if let Some(lease_id) = self.lease_id() {
+            req = req.with_lease_id(lease_id);
+        }
+        if let Some(timeout) = self.timeout() {
+            req = req.with_timeout(timeout);
+        }
+        if let Some(client_request_id) = self.client_request_id() {
+            req = req.with_client_request_id(client_request_id);
+        }
+        for &(name, value) in self.extra_headers() {
+            req = req.with_extra_header(name, value);
+        }
+        if let Some(api_version) = self.api_version() {
+            req = req.with_api_version(api_version);
+        }
+
+        req.finalize().then(|result| match result {
+            Ok(_) => Ok(true),
+            Err(ref error) if error.is_not_found() => Ok(false),
+            Err(error) => Err(error),
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<bool, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
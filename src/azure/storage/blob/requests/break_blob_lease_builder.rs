@@ -1,8 +1,10 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::headers::LEASE_ACTION;
+use crate::azure::core::lease::LeaseBreakPeriod;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
-    ContainerNameSupport, LeaseBreakPeriodRequired, LeaseBreakPeriodSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseBreakPeriodRequired,
+    LeaseBreakPeriodSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::blob::generate_blob_uri;
@@ -25,9 +27,11 @@ where
     p_lease_break_period: PhantomData<BreakPeriodSet>,
     container_name: Option<&'a str>,
     blob_name: Option<&'a str>,
-    lease_break_period: u8,
+    lease_break_period: LeaseBreakPeriod,
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> BreakBlobLeaseBuilder<'a, No, No, No> {
@@ -40,9 +44,11 @@ impl<'a> BreakBlobLeaseBuilder<'a, No, No, No> {
             p_blob_name: PhantomData {},
             blob_name: None,
             p_lease_break_period: PhantomData {},
-            lease_break_period: 0,
+            lease_break_period: LeaseBreakPeriod::immediate(),
             timeout: None,
             client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -88,7 +94,7 @@ where
     BlobNameSet: ToAssign,
 {
     #[inline]
-    fn lease_break_period(&self) -> u8 {
+    fn lease_break_period(&self) -> LeaseBreakPeriod {
         self.lease_break_period
     }
 }
@@ -140,6 +146,8 @@ where
             lease_break_period: self.lease_break_period,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -165,6 +173,8 @@ where
             lease_break_period: self.lease_break_period,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -179,7 +189,7 @@ where
     type O = BreakBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
 
     #[inline]
-    fn with_lease_break_period(self, lease_break_period: u8) -> Self::O {
+    fn with_lease_break_period(self, lease_break_period: LeaseBreakPeriod) -> Self::O {
         BreakBlobLeaseBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -190,6 +200,8 @@ where
             lease_break_period,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -215,6 +227,8 @@ where
             lease_break_period: self.lease_break_period,
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -240,6 +254,75 @@ where
             lease_break_period: self.lease_break_period,
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet> ExtraHeadersOption<'a>
+    for BreakBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BreakPeriodSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet> ExtraHeadersSupport<'a>
+    for BreakBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BreakPeriodSet: ToAssign,
+{
+    type O = BreakBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet> ApiVersionOption<'a>
+    for BreakBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BreakPeriodSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet> ApiVersionSupport<'a>
+    for BreakBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BreakPeriodSet: ToAssign,
+{
+    type O = BreakBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, BreakPeriodSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        BreakBlobLeaseBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_lease_break_period: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            lease_break_period: self.lease_break_period,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -268,6 +351,8 @@ impl<'a> BreakBlobLeaseBuilder<'a, Yes, Yes, Yes> {
                 request.header(LEASE_ACTION, "break");
                 LeaseBreakPeriodRequired::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -277,4 +362,9 @@ impl<'a> BreakBlobLeaseBuilder<'a, Yes, Yes, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::ACCEPTED))
             .and_then(|(headers, _body)| done(BreakBlobLeaseResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<BreakBlobLeaseResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
@@ -2,8 +2,9 @@ use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureErr
 use crate::azure::core::headers::LEASE_ACTION;
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
-    ContainerNameSupport, LeaseIdRequired, LeaseIdSupport, ProposedLeaseIdRequired, ProposedLeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdRequired, LeaseIdSupport,
+    ProposedLeaseIdRequired, ProposedLeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::blob::generate_blob_uri;
@@ -32,6 +33,8 @@ where
     proposed_lease_id: Option<&'a LeaseId>,
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> ChangeBlobLeaseBuilder<'a, No, No, No, No> {
@@ -49,6 +52,8 @@ impl<'a> ChangeBlobLeaseBuilder<'a, No, No, No, No> {
             proposed_lease_id: None,
             timeout: None,
             client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -171,6 +176,8 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -199,6 +206,8 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -227,6 +236,8 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -255,6 +266,8 @@ where
             proposed_lease_id: Some(proposed_lease_id),
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -283,6 +296,8 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -311,6 +326,81 @@ where
             proposed_lease_id: self.proposed_lease_id,
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet> ExtraHeadersOption<'a>
+    for ChangeBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+    ProposedLeaseIdSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet> ExtraHeadersSupport<'a>
+    for ChangeBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+    ProposedLeaseIdSet: ToAssign,
+{
+    type O = ChangeBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet> ApiVersionOption<'a>
+    for ChangeBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+    ProposedLeaseIdSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet> ApiVersionSupport<'a>
+    for ChangeBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+    ProposedLeaseIdSet: ToAssign,
+{
+    type O = ChangeBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet, ProposedLeaseIdSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ChangeBlobLeaseBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_lease_id: PhantomData {},
+            p_proposed_lease_id: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            lease_id: self.lease_id,
+            proposed_lease_id: self.proposed_lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -342,6 +432,8 @@ impl<'a> ChangeBlobLeaseBuilder<'a, Yes, Yes, Yes, Yes> {
                 request.header(LEASE_ACTION, "change");
                 ProposedLeaseIdRequired::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -351,4 +443,9 @@ impl<'a> ChangeBlobLeaseBuilder<'a, Yes, Yes, Yes, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
             .and_then(|(headers, _body)| done(ChangeBlobLeaseResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<ChangeBlobLeaseResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
@@ -4,10 +4,10 @@ use crate::azure::core::headers::PAGE_WRITE;
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition, SequenceNumberCondition};
 use crate::azure::core::{
-    BA512RangeRequired, BA512RangeSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport,
-    ClientRequired, ContainerNameRequired, ContainerNameSupport, IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption,
-    IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, No, SequenceNumberConditionOption, SequenceNumberConditionSupport,
-    TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    ApiVersionOption, ApiVersionSupport, BA512RangeRequired, BA512RangeSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport,
+    IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, No,
+    SequenceNumberConditionOption, SequenceNumberConditionSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
 };
 use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::ClearPageResponse;
@@ -37,6 +37,8 @@ where
     if_match_condition: Option<IfMatchCondition<'a>>,
     client_request_id: Option<&'a str>,
     timeout: Option<u64>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> ClearPageBuilder<'a, No, No, No> {
@@ -56,6 +58,8 @@ impl<'a> ClearPageBuilder<'a, No, No, No> {
             if_match_condition: None,
             client_request_id: None,
             timeout: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -208,6 +212,8 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -237,6 +243,8 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -266,6 +274,8 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -295,6 +305,8 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -324,6 +336,8 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -353,6 +367,8 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -382,6 +398,8 @@ where
             if_match_condition: Some(if_match_condition),
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -411,6 +429,79 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: Some(client_request_id),
             timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet> ExtraHeadersOption<'a>
+    for ClearPageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet> ExtraHeadersSupport<'a>
+    for ClearPageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+{
+    type O = ClearPageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet> ApiVersionOption<'a>
+    for ClearPageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet> ApiVersionSupport<'a>
+    for ClearPageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+{
+    type O = ClearPageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ClearPageBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_ba512_range: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            sequence_number_condition: self.sequence_number_condition,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -439,6 +530,8 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: Some(timeout),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -474,6 +567,8 @@ impl<'a> ClearPageBuilder<'a, Yes, Yes, Yes> {
                 IfSinceConditionOption::add_header(&self, request);
                 IfMatchConditionOption::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -483,4 +578,9 @@ impl<'a> ClearPageBuilder<'a, Yes, Yes, Yes> {
             .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
             .and_then(move |(headers, _body)| done(ClearPageResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<ClearPageResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
@@ -0,0 +1,647 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
+use crate::azure::core::{
+    AccessTierOption, AccessTierSupport, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, CopySourceRequired, CopySourceSupport,
+    ExtraHeadersOption, ExtraHeadersSupport, IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption,
+    IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, MetadataOption, MetadataSupport, No, TimeoutOption, TimeoutSupport, ToAssign,
+    Yes,
+};
+use crate::azure::storage::blob::generate_blob_uri;
+use crate::azure::storage::blob::responses::CopyBlobResponse;
+use crate::azure::storage::client::Client;
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    p_copy_source: PhantomData<CopySourceSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    copy_source: Option<&'a str>,
+    metadata: Option<&'a Metadata>,
+    access_tier: Option<&'a str>,
+    lease_id: Option<&'a LeaseId>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    client_request_id: Option<&'a str>,
+    timeout: Option<u64>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> CopyBlobBuilder<'a, No, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> CopyBlobBuilder<'a, No, No, No> {
+        CopyBlobBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            p_copy_source: PhantomData {},
+            copy_source: None,
+            metadata: None,
+            access_tier: None,
+            lease_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            client_request_id: None,
+            timeout: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ClientRequired<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet, CopySourceSet> ContainerNameRequired<'a> for CopyBlobBuilder<'a, Yes, BlobNameSet, CopySourceSet>
+where
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, CopySourceSet> BlobNameRequired<'a> for CopyBlobBuilder<'a, ContainerNameSet, Yes, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> CopySourceRequired<'a> for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn copy_source(&self) -> &'a str {
+        self.copy_source.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> MetadataOption<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn metadata(&self) -> Option<&'a Metadata> {
+        self.metadata
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> AccessTierOption<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn access_tier(&self) -> Option<&'a str> {
+        self.access_tier
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> LeaseIdOption<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> IfSinceConditionOption
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> IfMatchConditionOption<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ClientRequestIdOption<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> TimeoutOption for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ExtraHeadersOption<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ContainerNameSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, Yes, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> BlobNameSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, Yes, CopySourceSet>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> CopySourceSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
+
+    #[inline]
+    fn with_copy_source(self, copy_source: &'a str) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: Some(copy_source),
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> MetadataSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: Some(metadata),
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> AccessTierSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_access_tier(self, access_tier: &'a str) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: Some(access_tier),
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> LeaseIdSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: Some(lease_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> IfSinceConditionSupport
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> IfMatchConditionSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ClientRequestIdSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: Some(client_request_id),
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> TimeoutSupport for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: Some(timeout),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ExtraHeadersSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ApiVersionOption<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ApiVersionSupport<'a>
+    for CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        CopyBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> CopyBlobBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+}
+
+impl<'a> CopyBlobBuilder<'a, Yes, Yes, Yes> {
+    /// Starts the copy of the blob at the copy source URL (with a SAS
+    /// token if the source is not public). The destination takes the
+    /// metadata of the source unless `with_metadata` is given, in which
+    /// case it replaces them. The copy is asynchronous: poll the
+    /// destination properties to follow its progress.
+    pub fn finalize(self) -> impl Future<Item = CopyBlobResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, None);
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}?{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::PUT,
+            |ref mut request| {
+                CopySourceRequired::add_header(&self, request);
+                MetadataOption::add_header(&self, request);
+                AccessTierOption::add_header(&self, request);
+                LeaseIdOption::add_header(&self, request);
+                IfSinceConditionOption::add_header(&self, request);
+                IfMatchConditionOption::add_header(&self, request);
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(&[]),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::ACCEPTED))
+            .and_then(move |(headers, _body)| done(CopyBlobResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<CopyBlobResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
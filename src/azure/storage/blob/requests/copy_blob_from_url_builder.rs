@@ -0,0 +1,926 @@
+use crate::azure::core::copy_source_authorization::CopySourceAuthorization;
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::headers::REQUIRES_SYNC;
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
+use crate::azure::core::{
+    AccessTierOption, AccessTierSupport, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, CopySourceAuthorizationOption,
+    CopySourceAuthorizationSupport, CopySourceRequired, CopySourceSupport, CopySourceTagsMode, CopySourceTagsModeOption,
+    CopySourceTagsModeSupport, ExtraHeadersOption, ExtraHeadersSupport, IfMatchConditionOption, IfMatchConditionSupport,
+    IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, MetadataOption, MetadataSupport, No,
+    SourceContentMD5Option, SourceContentMD5Support, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::blob::responses::CopyBlobResponse;
+use crate::azure::storage::blob::{generate_blob_uri, Tags, TagsOption, TagsSupport, TAGS_VERSION};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+// the synchronous copy from URL is not available before this version
+const COPY_BLOB_FROM_URL_VERSION: &str = "2018-11-09";
+
+#[derive(Debug, Clone)]
+pub struct CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    p_copy_source: PhantomData<CopySourceSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    copy_source: Option<&'a str>,
+    copy_source_authorization: Option<&'a CopySourceAuthorization>,
+    source_content_md5: Option<&'a [u8]>,
+    metadata: Option<&'a Metadata>,
+    access_tier: Option<&'a str>,
+    tags: Option<&'a Tags>,
+    copy_source_tags_mode: Option<CopySourceTagsMode>,
+    lease_id: Option<&'a LeaseId>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    client_request_id: Option<&'a str>,
+    timeout: Option<u64>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> CopyBlobFromUrlBuilder<'a, No, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> CopyBlobFromUrlBuilder<'a, No, No, No> {
+        CopyBlobFromUrlBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            p_copy_source: PhantomData {},
+            copy_source: None,
+            copy_source_authorization: None,
+            source_content_md5: None,
+            metadata: None,
+            access_tier: None,
+            tags: None,
+            copy_source_tags_mode: None,
+            lease_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            client_request_id: None,
+            timeout: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ClientRequired<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet, CopySourceSet> ContainerNameRequired<'a> for CopyBlobFromUrlBuilder<'a, Yes, BlobNameSet, CopySourceSet>
+where
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, CopySourceSet> BlobNameRequired<'a> for CopyBlobFromUrlBuilder<'a, ContainerNameSet, Yes, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> CopySourceRequired<'a> for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn copy_source(&self) -> &'a str {
+        self.copy_source.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> CopySourceAuthorizationOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn copy_source_authorization(&self) -> Option<&'a CopySourceAuthorization> {
+        self.copy_source_authorization
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> SourceContentMD5Option<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn source_content_md5(&self) -> Option<&'a [u8]> {
+        self.source_content_md5
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> MetadataOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn metadata(&self) -> Option<&'a Metadata> {
+        self.metadata
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> AccessTierOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn access_tier(&self) -> Option<&'a str> {
+        self.access_tier
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> TagsOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn tags(&self) -> Option<&'a Tags> {
+        self.tags
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> CopySourceTagsModeOption
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn copy_source_tags_mode(&self) -> Option<CopySourceTagsMode> {
+        self.copy_source_tags_mode
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> LeaseIdOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> IfSinceConditionOption
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> IfMatchConditionOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ClientRequestIdOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> TimeoutOption
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ExtraHeadersOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ApiVersionOption<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ContainerNameSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, Yes, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> BlobNameSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, Yes, CopySourceSet>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> CopySourceSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
+
+    #[inline]
+    fn with_copy_source(self, copy_source: &'a str) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: Some(copy_source),
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> CopySourceAuthorizationSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_copy_source_authorization(self, copy_source_authorization: &'a CopySourceAuthorization) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: Some(copy_source_authorization),
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> SourceContentMD5Support<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_source_content_md5(self, source_content_md5: &'a [u8]) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: Some(source_content_md5),
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> MetadataSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: Some(metadata),
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> AccessTierSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_access_tier(self, access_tier: &'a str) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: Some(access_tier),
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> TagsSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_tags(self, tags: &'a Tags) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: Some(tags),
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> CopySourceTagsModeSupport
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_copy_source_tags_mode(self, copy_source_tags_mode: CopySourceTagsMode) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: Some(copy_source_tags_mode),
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> LeaseIdSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: Some(lease_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> IfSinceConditionSupport
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> IfMatchConditionSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ClientRequestIdSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: Some(client_request_id),
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> TimeoutSupport
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: Some(timeout),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ExtraHeadersSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> ApiVersionSupport<'a>
+    for CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        CopyBlobFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            copy_source: self.copy_source,
+            copy_source_authorization: self.copy_source_authorization,
+            source_content_md5: self.source_content_md5,
+            metadata: self.metadata,
+            access_tier: self.access_tier,
+            tags: self.tags,
+            copy_source_tags_mode: self.copy_source_tags_mode,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> CopyBlobFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+}
+
+impl<'a> CopyBlobFromUrlBuilder<'a, Yes, Yes, Yes> {
+    /// Copies the blob at the copy source URL (with a SAS token or a
+    /// copy source authorization if the source is not public) before
+    /// answering: unlike copy blob the response comes once the copy is
+    /// done. The source must be a block blob of up to 256 MiB. The
+    /// destination takes the metadata of the source unless
+    /// `with_metadata` is given, in which case it replaces them, and the
+    /// tags given with `with_tags` unless `with_copy_source_tags_mode`
+    /// selects `CopySourceTagsMode::Copy`, the tags of the source.
+    pub fn finalize(self) -> impl Future<Item = CopyBlobResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, None);
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}?{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let api_version = ApiVersionOption::required_api_version(
+            &self,
+            self.client(),
+            CopySourceAuthorizationOption::required_api_version(&self)
+                .max(CopySourceTagsModeOption::required_api_version(&self))
+                .max(TagsOption::tags(&self).map(|_| TAGS_VERSION))
+                .max(Some(COPY_BLOB_FROM_URL_VERSION)),
+        );
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    request.header(REQUIRES_SYNC, "true");
+                    CopySourceRequired::add_header(&self, request);
+                    CopySourceAuthorizationOption::add_header(&self, request);
+                    SourceContentMD5Option::add_header(&self, request);
+                    MetadataOption::add_header(&self, request);
+                    AccessTierOption::add_header(&self, request);
+                    TagsOption::add_header(&self, request);
+                    CopySourceTagsModeOption::add_header(&self, request);
+                    LeaseIdOption::add_header(&self, request);
+                    IfSinceConditionOption::add_header(&self, request);
+                    IfMatchConditionOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(&[]),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::ACCEPTED))
+            .and_then(move |(headers, _body)| done(CopyBlobResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<CopyBlobResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
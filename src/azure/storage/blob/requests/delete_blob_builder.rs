@@ -1,14 +1,18 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
-    ContainerNameSupport, DeleteSnapshotsMethodRequired, DeleteSnapshotsMethodSupport, LeaseIdOption, LeaseIdSupport, TimeoutOption,
-    TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, DeleteSnapshotsMethodRequired, DeleteSnapshotsMethodSupport, ExtraHeadersOption,
+    ExtraHeadersSupport, IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption,
+    LeaseIdSupport, SnapshotOption, SnapshotSupport, TimeoutOption, TimeoutSupport, VersionIdOption, VersionIdSupport,
 };
 use crate::azure::core::{DeleteSnapshotsMethod, No, ToAssign, Yes};
-use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::DeleteBlobResponse;
+use crate::azure::storage::blob::{generate_blob_uri, VERSIONING_VERSION};
 use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use chrono::{DateTime, Utc};
 use futures::future::{done, Future};
 use hyper::{Method, StatusCode};
 use std::marker::PhantomData;
@@ -30,6 +34,12 @@ where
     timeout: Option<u64>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    snapshot: Option<DateTime<Utc>>,
+    version_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> DeleteBlobBuilder<'a, No, No, No> {
@@ -46,6 +56,12 @@ impl<'a> DeleteBlobBuilder<'a, No, No, No> {
             timeout: None,
             lease_id: None,
             client_request_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            snapshot: None,
+            version_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -159,6 +175,12 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -185,6 +207,12 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -211,6 +239,12 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -237,6 +271,12 @@ where
             timeout: Some(timeout),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -263,6 +303,12 @@ where
             timeout: self.timeout,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -289,6 +335,264 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> ExtraHeadersOption<'a>
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> ExtraHeadersSupport<'a>
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    type O = DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> ApiVersionOption<'a>
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> ApiVersionSupport<'a>
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    type O = DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        DeleteBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_delete_snapshots_method: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            delete_snapshots_method: self.delete_snapshots_method,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> IfSinceConditionOption
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> IfMatchConditionOption<'a>
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> IfSinceConditionSupport
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    type O = DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        DeleteBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_delete_snapshots_method: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            delete_snapshots_method: self.delete_snapshots_method,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> IfMatchConditionSupport<'a>
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    type O = DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        DeleteBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_delete_snapshots_method: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            delete_snapshots_method: self.delete_snapshots_method,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> SnapshotOption
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    #[inline]
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> SnapshotSupport
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    type O = DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>;
+
+    #[inline]
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        DeleteBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_delete_snapshots_method: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            delete_snapshots_method: self.delete_snapshots_method,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: Some(snapshot),
+            version_id: self.version_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> VersionIdOption<'a>
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    #[inline]
+    fn version_id(&self) -> Option<&'a str> {
+        self.version_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet> VersionIdSupport<'a>
+    for DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    DeleteSnapshotMethodSet: ToAssign,
+{
+    type O = DeleteBlobBuilder<'a, ContainerNameSet, BlobNameSet, DeleteSnapshotMethodSet>;
+
+    #[inline]
+    fn with_version_id(self, version_id: &'a str) -> Self::O {
+        DeleteBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_delete_snapshots_method: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            delete_snapshots_method: self.delete_snapshots_method,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            snapshot: self.snapshot,
+            version_id: Some(version_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -304,29 +608,73 @@ where
 }
 
 impl<'a> DeleteBlobBuilder<'a, Yes, Yes, Yes> {
+    /// Deletes the blob. If a snapshot or a version is specified only that
+    /// snapshot or version is deleted and the delete snapshots method is
+    /// ignored.
     pub fn finalize(self) -> impl Future<Item = DeleteBlobResponse, Error = AzureError> {
         let mut uri = generate_blob_uri(&self, None);
 
+        let mut f_first = true;
+        if let Some(snapshot) = SnapshotOption::to_uri_parameter(&self) {
+            uri = format!("{}?{}", uri, snapshot);
+            f_first = false;
+        }
+        if let Some(version_id) = VersionIdOption::to_uri_parameter(&self) {
+            uri = format!("{}{}{}", uri, if f_first { "?" } else { "&" }, version_id);
+            f_first = false;
+        }
         if let Some(nm) = TimeoutOption::to_uri_parameter(&self) {
-            uri = format!("{}?{}", uri, nm);
+            uri = format!("{}{}{}", uri, if f_first { "?" } else { "&" }, nm);
         }
 
         trace!("delete_blob uri == {:?}", uri);
 
-        let req = self.client().perform_request(
-            &uri,
-            &Method::DELETE,
-            |ref mut request| {
-                DeleteSnapshotsMethodRequired::add_header(&self, request);
-                LeaseIdOption::add_header(&self, request);
-                ClientRequestIdOption::add_header(&self, request);
-            },
-            None,
-        );
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), self.version_id().map(|_| VERSIONING_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::DELETE,
+                |ref mut request| {
+                    // x-ms-delete-snapshots is only allowed on the base blob
+                    if self.snapshot().is_none() && self.version_id().is_none() {
+                        DeleteSnapshotsMethodRequired::add_header(&self, request);
+                    }
+                    LeaseIdOption::add_header(&self, request);
+                    IfSinceConditionOption::add_header(&self, request);
+                    IfMatchConditionOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                None,
+            )
+        });
 
         done(req)
             .from_err()
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::ACCEPTED))
             .and_then(|(headers, _body)| done(DeleteBlobResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<DeleteBlobResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+
+    /// Deletes the blob if it exists. Resolves to `true` if the blob was
+    /// deleted, `false` if there was nothing to delete.
+    pub fn delete_if_exists(self) -> impl Future<Item = bool, Error = AzureError> {
+        self.finalize().then(|result| match result {
+            Ok(_) => Ok(true),
+            Err(ref err) if err.is_not_found() => Ok(false),
+            Err(err) => Err(err),
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn delete_if_exists_async(self) -> impl std::future::Future<Output = Result<bool, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.delete_if_exists())
+    }
 }
@@ -0,0 +1,375 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, SnapshotOption, SnapshotSupport, TimeoutOption,
+    TimeoutSupport, VersionIdOption, VersionIdSupport,
+};
+use crate::azure::core::{No, ToAssign, Yes};
+use crate::azure::storage::blob::responses::DeleteBlobImmutabilityPolicyResponse;
+use crate::azure::storage::blob::{generate_blob_uri, IMMUTABILITY_VERSION};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use chrono::{DateTime, Utc};
+use futures::future::{done, Future};
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+#[derive(Debug, Clone)]
+pub struct DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    snapshot: Option<DateTime<Utc>>,
+    version_id: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> DeleteBlobImmutabilityPolicyBuilder<'a, No, No> {
+    pub(crate) fn new(client: &'a Client) -> DeleteBlobImmutabilityPolicyBuilder<'a, No, No> {
+        DeleteBlobImmutabilityPolicyBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            snapshot: None,
+            version_id: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequired<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet> ContainerNameRequired<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+{
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet> BlobNameRequired<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> SnapshotOption for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> VersionIdOption<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn version_id(&self) -> Option<&'a str> {
+        self.version_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutOption for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersOption<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionOption<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ContainerNameSupport<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DeleteBlobImmutabilityPolicyBuilder<'a, Yes, BlobNameSet>;
+
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        DeleteBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> BlobNameSupport<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, Yes>;
+
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        DeleteBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> SnapshotSupport for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        DeleteBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: Some(snapshot),
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> VersionIdSupport<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_version_id(self, version_id: &'a str) -> Self::O {
+        DeleteBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: Some(version_id),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutSupport for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        DeleteBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a>
+    for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        DeleteBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersSupport<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionSupport<'a> for DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        DeleteBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet> DeleteBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+}
+
+impl<'a> DeleteBlobImmutabilityPolicyBuilder<'a, Yes, Yes> {
+    /// Deletes the immutability policy of the blob. Only an unlocked
+    /// policy can be deleted.
+    pub fn finalize(self) -> impl Future<Item = DeleteBlobImmutabilityPolicyResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, Some("comp=immutabilityPolicies"));
+
+        if let Some(snapshot) = SnapshotOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, snapshot);
+        }
+        if let Some(version_id) = VersionIdOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, version_id);
+        }
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(IMMUTABILITY_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::DELETE,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                None,
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
+            .and_then(|(headers, _body)| done(DeleteBlobImmutabilityPolicyResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<DeleteBlobImmutabilityPolicyResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -1,8 +1,9 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
-    ContainerNameSupport, LeaseIdOption, LeaseIdSupport, SnapshotRequired, SnapshotSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, SnapshotRequired,
+    SnapshotSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::blob::generate_blob_uri;
@@ -30,6 +31,8 @@ where
     timeout: Option<u64>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> DeleteBlobSnapshotBuilder<'a, No, No, No> {
@@ -46,6 +49,8 @@ impl<'a> DeleteBlobSnapshotBuilder<'a, No, No, No> {
             timeout: None,
             lease_id: None,
             client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -157,6 +162,8 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -183,6 +190,8 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -209,6 +218,8 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -235,6 +246,8 @@ where
             timeout: Some(timeout),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -261,6 +274,8 @@ where
             timeout: self.timeout,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -287,6 +302,76 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, SnapshotSet> ExtraHeadersOption<'a>
+    for DeleteBlobSnapshotBuilder<'a, ContainerNameSet, BlobNameSet, SnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    SnapshotSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, SnapshotSet> ExtraHeadersSupport<'a>
+    for DeleteBlobSnapshotBuilder<'a, ContainerNameSet, BlobNameSet, SnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    SnapshotSet: ToAssign,
+{
+    type O = DeleteBlobSnapshotBuilder<'a, ContainerNameSet, BlobNameSet, SnapshotSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, SnapshotSet> ApiVersionOption<'a>
+    for DeleteBlobSnapshotBuilder<'a, ContainerNameSet, BlobNameSet, SnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    SnapshotSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, SnapshotSet> ApiVersionSupport<'a>
+    for DeleteBlobSnapshotBuilder<'a, ContainerNameSet, BlobNameSet, SnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    SnapshotSet: ToAssign,
+{
+    type O = DeleteBlobSnapshotBuilder<'a, ContainerNameSet, BlobNameSet, SnapshotSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        DeleteBlobSnapshotBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -314,6 +399,8 @@ impl<'a> DeleteBlobSnapshotBuilder<'a, Yes, Yes, Yes> {
             |ref mut request| {
                 LeaseIdOption::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -323,4 +410,9 @@ impl<'a> DeleteBlobSnapshotBuilder<'a, Yes, Yes, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::ACCEPTED))
             .and_then(|(headers, _body)| done(DeleteBlobResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<DeleteBlobResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
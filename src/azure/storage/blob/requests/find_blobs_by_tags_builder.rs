@@ -0,0 +1,305 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, MaxResultsOption, MaxResultsSupport, NextMarkerOption, NextMarkerSupport, TimeoutOption, TimeoutSupport,
+};
+use crate::azure::core::{No, ToAssign, Yes};
+use crate::azure::storage::blob::responses::FindBlobsByTagsResponse;
+use crate::azure::storage::blob::{TagFilterRequired, TagFilterSupport, FIND_BLOBS_BY_TAGS_VERSION};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use futures::future::{done, Future};
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+#[derive(Debug, Clone)]
+pub struct FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    client: &'a Client,
+    p_tag_filter: PhantomData<TagFilterSet>,
+    tag_filter: Option<&'a str>,
+    max_results: Option<u32>,
+    next_marker: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> FindBlobsByTagsBuilder<'a, No> {
+    pub(crate) fn new(client: &'a Client) -> FindBlobsByTagsBuilder<'a, No> {
+        FindBlobsByTagsBuilder {
+            client,
+            p_tag_filter: PhantomData {},
+            tag_filter: None,
+            max_results: None,
+            next_marker: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, TagFilterSet> ClientRequired<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> TagFilterRequired<'a> for FindBlobsByTagsBuilder<'a, Yes> {
+    fn tag_filter(&self) -> &'a str {
+        self.tag_filter.unwrap()
+    }
+}
+
+impl<'a, TagFilterSet> MaxResultsOption for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    fn max_results(&self) -> Option<u32> {
+        self.max_results
+    }
+}
+
+impl<'a, TagFilterSet> NextMarkerOption<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    fn next_marker(&self) -> Option<&'a str> {
+        self.next_marker
+    }
+}
+
+impl<'a, TagFilterSet> TimeoutOption for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, TagFilterSet> ClientRequestIdOption<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, TagFilterSet> ExtraHeadersOption<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, TagFilterSet> ApiVersionOption<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, TagFilterSet> TagFilterSupport<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    type O = FindBlobsByTagsBuilder<'a, Yes>;
+
+    fn with_tag_filter(self, tag_filter: &'a str) -> Self::O {
+        FindBlobsByTagsBuilder {
+            client: self.client,
+            p_tag_filter: PhantomData {},
+            tag_filter: Some(tag_filter),
+            max_results: self.max_results,
+            next_marker: self.next_marker,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, TagFilterSet> MaxResultsSupport for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    type O = FindBlobsByTagsBuilder<'a, TagFilterSet>;
+
+    fn with_max_results(self, max_results: u32) -> Self::O {
+        FindBlobsByTagsBuilder {
+            client: self.client,
+            p_tag_filter: PhantomData {},
+            tag_filter: self.tag_filter,
+            max_results: Some(max_results),
+            next_marker: self.next_marker,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, TagFilterSet> NextMarkerSupport<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    type O = FindBlobsByTagsBuilder<'a, TagFilterSet>;
+
+    fn with_next_marker(self, next_marker: &'a str) -> Self::O {
+        FindBlobsByTagsBuilder {
+            client: self.client,
+            p_tag_filter: PhantomData {},
+            tag_filter: self.tag_filter,
+            max_results: self.max_results,
+            next_marker: Some(next_marker),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, TagFilterSet> TimeoutSupport for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    type O = FindBlobsByTagsBuilder<'a, TagFilterSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        FindBlobsByTagsBuilder {
+            client: self.client,
+            p_tag_filter: PhantomData {},
+            tag_filter: self.tag_filter,
+            max_results: self.max_results,
+            next_marker: self.next_marker,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, TagFilterSet> ClientRequestIdSupport<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    type O = FindBlobsByTagsBuilder<'a, TagFilterSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        FindBlobsByTagsBuilder {
+            client: self.client,
+            p_tag_filter: PhantomData {},
+            tag_filter: self.tag_filter,
+            max_results: self.max_results,
+            next_marker: self.next_marker,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, TagFilterSet> ExtraHeadersSupport<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    type O = FindBlobsByTagsBuilder<'a, TagFilterSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, TagFilterSet> ApiVersionSupport<'a> for FindBlobsByTagsBuilder<'a, TagFilterSet>
+where
+    TagFilterSet: ToAssign,
+{
+    type O = FindBlobsByTagsBuilder<'a, TagFilterSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        FindBlobsByTagsBuilder {
+            client: self.client,
+            p_tag_filter: PhantomData {},
+            tag_filter: self.tag_filter,
+            max_results: self.max_results,
+            next_marker: self.next_marker,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, TagFilterSet> FindBlobsByTagsBuilder<'a, TagFilterSet> where TagFilterSet: ToAssign {}
+
+impl<'a> FindBlobsByTagsBuilder<'a, Yes> {
+    /// Lists the blobs of the account, in any container, whose index tags
+    /// match the filter. The index is updated asynchronously: a blob
+    /// whose tags have just been set may not be found yet.
+    pub fn finalize(self) -> impl Future<Item = FindBlobsByTagsResponse, Error = AzureError> {
+        let mut uri = format!(
+            "{}/?comp=blobs&{}",
+            self.client().blob_uri(),
+            TagFilterRequired::to_uri_parameter(&self)
+        );
+
+        if let Some(nm) = MaxResultsOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, nm);
+        }
+        if let Some(nm) = NextMarkerOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, nm);
+        }
+        if let Some(nm) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, nm);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(FIND_BLOBS_BY_TAGS_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::GET,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                None,
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_headers_and_body_as_string(future_response, StatusCode::OK))
+            .and_then(|(headers, body)| done(FindBlobsByTagsResponse::from_response(&headers, &body)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<FindBlobsByTagsResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -1,20 +1,29 @@
+use crate::azure::core::compression::{gunzip, is_gzip_encoding};
+use crate::azure::core::customer_provided_key::CustomerProvidedKey;
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
-use crate::azure::core::headers::RANGE_GET_CONTENT_MD5;
+use crate::azure::core::checksum::{Checksum, ChecksumMode};
+use crate::azure::core::headers::{MS_RANGE, RANGE_GET_CONTENT_MD5};
 use crate::azure::core::lease::LeaseId;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
+use crate::azure::core::progress::Progress;
 use crate::azure::core::range::Range;
 use crate::azure::core::util::RequestBuilderExt;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
-    ContainerNameSupport, LeaseIdOption, LeaseIdSupport, No, RangeOption, RangeSupport, SnapshotOption, SnapshotSupport, TimeoutOption,
-    TimeoutSupport, ToAssign, Yes,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, CPKOption, CPKSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport,
+    IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, No,
+    ProgressOption, ProgressSupport, RangeOption, RangeSupport, SnapshotOption, SnapshotSupport, TimeoutOption, TimeoutSupport, ToAssign,
+    VersionIdOption, VersionIdSupport, Yes,
 };
 use crate::azure::storage::blob::responses::GetBlobResponse;
-use crate::azure::storage::blob::{generate_blob_uri, Blob};
+use crate::azure::storage::blob::{generate_blob_uri, Blob, ENCRYPTION_DATA_METADATA, VERSIONING_VERSION};
 use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
 use chrono::{DateTime, Utc};
 use futures::future::done;
 use futures::prelude::*;
 use hyper::{Method, StatusCode};
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
 #[derive(Debug, Clone)]
@@ -33,6 +42,14 @@ where
     range: Option<&'a Range>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    decompress: bool,
+    customer_provided_key: Option<&'a CustomerProvidedKey>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+    progress: Option<&'a Progress>,
+    version_id: Option<&'a str>,
 }
 
 impl<'a> GetBlobBuilder<'a, No, No> {
@@ -49,6 +66,14 @@ impl<'a> GetBlobBuilder<'a, No, No> {
             range: None,
             lease_id: None,
             client_request_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            customer_provided_key: None,
+            decompress: true,
+            extra_headers: Vec::new(),
+            api_version: None,
+            progress: None,
+            version_id: None,
         }
     }
 }
@@ -159,6 +184,14 @@ where
             range: self.range,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
         }
     }
 }
@@ -183,6 +216,14 @@ where
             range: self.range,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
         }
     }
 }
@@ -207,6 +248,14 @@ where
             range: self.range,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
         }
     }
 }
@@ -231,6 +280,14 @@ where
             range: self.range,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
         }
     }
 }
@@ -255,6 +312,14 @@ where
             range: Some(range),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
         }
     }
 }
@@ -279,6 +344,14 @@ where
             range: self.range,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
         }
     }
 }
@@ -303,6 +376,295 @@ where
             range: self.range,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersOption<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersSupport<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionOption<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionSupport<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+            progress: self.progress,
+            version_id: self.version_id,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ProgressOption<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn progress(&self) -> Option<&'a Progress> {
+        self.progress
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ProgressSupport<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_progress(self, progress: &'a Progress) -> Self::O {
+        GetBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: Some(progress),
+            version_id: self.version_id,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> VersionIdOption<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn version_id(&self) -> Option<&'a str> {
+        self.version_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> VersionIdSupport<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_version_id(self, version_id: &'a str) -> Self::O {
+        GetBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: Some(version_id),
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> IfSinceConditionOption for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> IfMatchConditionOption<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> IfSinceConditionSupport for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        GetBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> IfMatchConditionSupport<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        GetBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            decompress: self.decompress,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> CPKOption<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn customer_provided_key(&self) -> Option<&'a CustomerProvidedKey> {
+        self.customer_provided_key
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> CPKSupport<'a> for GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = GetBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_customer_provided_key(self, customer_provided_key: &'a CustomerProvidedKey) -> Self::O {
+        GetBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            decompress: self.decompress,
+            customer_provided_key: Some(customer_provided_key),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+            version_id: self.version_id,
         }
     }
 }
@@ -313,6 +675,19 @@ where
     ContainerNameSet: ToAssign,
     BlobNameSet: ToAssign,
 {
+    /// If the blob Content-Encoding is gzip its contents are decompressed
+    /// transparently. This is the default; disable it to get the raw
+    /// bytes instead. Ranged reads are never decompressed since a range
+    /// of a gzip stream cannot be decoded on its own.
+    #[inline]
+    pub fn with_decompress(self, decompress: bool) -> Self {
+        GetBlobBuilder { decompress, ..self }
+    }
+
+    #[inline]
+    pub fn decompress(&self) -> bool {
+        self.decompress
+    }
 }
 
 impl<'a> GetBlobBuilder<'a, Yes, Yes> {
@@ -321,6 +696,13 @@ impl<'a> GetBlobBuilder<'a, Yes, Yes> {
         let container_name = self.container_name().to_owned();
         let blob_name = self.blob_name().to_owned();
         let snapshot_time = self.snapshot();
+        let decompress = self.decompress && self.range().is_none();
+        let ranged = self.range().is_some();
+        let encryption = self.client().client_side_encryption().cloned();
+        let progress = self.progress().cloned();
+        // the service returns the MD5 of the ranges up to 4 MB (the range
+        // ends are inclusive)
+        let range_md5 = self.range().is_some_and(|r| r.len() < 4 * 1024 * 1024);
 
         let mut uri = generate_blob_uri(&self, None);
 
@@ -329,27 +711,48 @@ impl<'a> GetBlobBuilder<'a, Yes, Yes> {
             uri = format!("{}?{}", uri, snapshot);
             f_first = false;
         }
+        if let Some(version_id) = VersionIdOption::to_uri_parameter(&self) {
+            uri = format!("{}{}{}", uri, if f_first { "?" } else { "&" }, version_id);
+            f_first = false;
+        }
         if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
             uri = format!("{}{}{}", uri, if f_first { "?" } else { "&" }, timeout);
         }
 
         trace!("uri == {:?}", uri);
 
-        let req = self.client().perform_request(
-            &uri,
-            &Method::GET,
-            |ref mut request| {
-                if let Some(r) = self.range() {
+        let api_version = ApiVersionOption::required_api_version(
+            &self,
+            self.client(),
+            self.version_id()
+                .map(|_| VERSIONING_VERSION)
+                .max(CPKOption::required_api_version(&self)),
+        );
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::GET,
+                |ref mut request| {
                     LeaseIdOption::add_header(&self, request);
-                    RangeOption::add_header(&self, request);
-
-                    if r.len() <= 4 * 1024 * 1024 {
-                        request.header_static(RANGE_GET_CONTENT_MD5, "true");
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
                     }
-                }
-            },
-            None,
-        );
+                    CPKOption::add_header(&self, request);
+                    IfSinceConditionOption::add_header(&self, request);
+                    IfMatchConditionOption::add_header(&self, request);
+
+                    if let Some(r) = self.range() {
+                        request.header(MS_RANGE, &r.to_string() as &str);
+                        if range_md5 {
+                            request.header_static(RANGE_GET_CONTENT_MD5, "true");
+                        }
+                    }
+                },
+                None,
+            )
+        });
 
         let expected_status_code = if self.range().is_some() {
             StatusCode::PARTIAL_CONTENT
@@ -361,8 +764,40 @@ impl<'a> GetBlobBuilder<'a, Yes, Yes> {
             .from_err()
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, expected_status_code))
             .and_then(move |(headers, body)| {
-                done(Blob::from_headers(&blob_name, &container_name, snapshot_time, &headers))
-                    .and_then(move |blob| done(GetBlobResponse::from_response(&headers, blob, &body)))
+                if range_md5 {
+                    Checksum::compute(ChecksumMode::MD5, &body).validate(&headers)?;
+                }
+                Ok((headers, body))
+            })
+            .and_then(move |(headers, body)| {
+                if let Some(progress) = progress {
+                    progress.report(body.len() as u64, Some(body.len() as u64));
+                }
+                done(Blob::from_headers(&blob_name, &container_name, snapshot_time, &headers)).and_then(move |blob| {
+                    let gzipped = blob.content_encoding.as_ref().map(|e| is_gzip_encoding(e)).unwrap_or(false);
+                    let body = if decompress && gzipped {
+                        gunzip(&body).map(Cow::Owned)
+                    } else {
+                        Ok(Cow::Borrowed(&body[..]))
+                    };
+                    done(
+                        body.and_then(|body| match (encryption, blob.metadata.get(ENCRYPTION_DATA_METADATA)) {
+                            (Some(_), Some(_)) if ranged => Err(AzureError::EncryptionError(
+                                "client-side encrypted blobs cannot be downloaded by range".to_owned(),
+                            )),
+                            (Some(encryption), Some(encryption_data)) => {
+                                let body = encryption.decrypt(&body, encryption_data)?;
+                                GetBlobResponse::from_response(&headers, blob, &body)
+                            }
+                            _ => GetBlobResponse::from_response(&headers, blob, &body),
+                        }),
+                    )
+                })
             })
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetBlobResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
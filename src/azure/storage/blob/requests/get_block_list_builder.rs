@@ -1,9 +1,9 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, BlockListTypeRequired, BlockListTypeSupport, ClientRequestIdOption, ClientRequestIdSupport,
-    ClientRequired, ContainerNameRequired, ContainerNameSupport, LeaseIdOption, LeaseIdSupport, No, TimeoutOption, TimeoutSupport,
-    ToAssign, Yes,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, BlockListTypeRequired, BlockListTypeSupport,
+    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption,
+    ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
 };
 use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::GetBlockListResponse;
@@ -31,6 +31,8 @@ where
     timeout: Option<u64>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> GetBlockListBuilder<'a, No, No, No> {
@@ -47,6 +49,8 @@ impl<'a> GetBlockListBuilder<'a, No, No, No> {
             timeout: None,
             lease_id: None,
             client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -158,6 +162,8 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -184,6 +190,8 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -210,6 +218,8 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -236,6 +246,8 @@ where
             timeout: Some(timeout),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -262,6 +274,8 @@ where
             timeout: self.timeout,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -288,6 +302,76 @@ where
             timeout: self.timeout,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet> ExtraHeadersOption<'a>
+    for GetBlockListBuilder<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlobListTypeSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet> ExtraHeadersSupport<'a>
+    for GetBlockListBuilder<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlobListTypeSet: ToAssign,
+{
+    type O = GetBlockListBuilder<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet> ApiVersionOption<'a>
+    for GetBlockListBuilder<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlobListTypeSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet> ApiVersionSupport<'a>
+    for GetBlockListBuilder<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlobListTypeSet: ToAssign,
+{
+    type O = GetBlockListBuilder<'a, ContainerNameSet, BlobNameSet, BlobListTypeSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetBlockListBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_list_type: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_list_type: self.block_list_type,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -319,6 +403,8 @@ impl<'a> GetBlockListBuilder<'a, Yes, Yes, Yes> {
             |ref mut request| {
                 LeaseIdOption::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -328,4 +414,9 @@ impl<'a> GetBlockListBuilder<'a, Yes, Yes, Yes> {
             .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::OK))
             .and_then(move |(headers, body)| done(GetBlockListResponse::from_response(&headers, &body)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetBlockListResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
@@ -0,0 +1,651 @@
+use crate::azure::core::ba512_range::BA512Range;
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, BA512RangeOption, BA512RangeSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport,
+    IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, No,
+    PreviousSnapshotRequired, PreviousSnapshotSupport, SnapshotOption, SnapshotSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::blob::generate_blob_uri;
+use crate::azure::storage::blob::responses::GetPageRangesDiffResponse;
+use crate::azure::storage::client::Client;
+use chrono::{DateTime, Utc};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    p_previous_snapshot: PhantomData<PreviousSnapshotSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    previous_snapshot: Option<DateTime<Utc>>,
+    snapshot: Option<DateTime<Utc>>,
+    ba512_range: Option<&'a BA512Range>,
+    lease_id: Option<&'a LeaseId>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    client_request_id: Option<&'a str>,
+    timeout: Option<u64>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> GetPageRangesDiffBuilder<'a, No, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> GetPageRangesDiffBuilder<'a, No, No, No> {
+        GetPageRangesDiffBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            p_previous_snapshot: PhantomData {},
+            previous_snapshot: None,
+            snapshot: None,
+            ba512_range: None,
+            lease_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            client_request_id: None,
+            timeout: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> ClientRequired<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet, PreviousSnapshotSet> ContainerNameRequired<'a> for GetPageRangesDiffBuilder<'a, Yes, BlobNameSet, PreviousSnapshotSet>
+where
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, PreviousSnapshotSet> BlobNameRequired<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, Yes, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> PreviousSnapshotRequired for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn previous_snapshot(&self) -> DateTime<Utc> {
+        self.previous_snapshot.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> SnapshotOption
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> BA512RangeOption<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn ba512_range(&self) -> Option<&'a BA512Range> {
+        self.ba512_range
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> LeaseIdOption<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> IfSinceConditionOption
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> IfMatchConditionOption<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> ClientRequestIdOption<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> TimeoutOption
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> ContainerNameSupport<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, Yes, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> BlobNameSupport<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, Yes, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> PreviousSnapshotSupport
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
+
+    #[inline]
+    fn with_previous_snapshot(self, previous_snapshot: DateTime<Utc>) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: Some(previous_snapshot),
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> SnapshotSupport
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: Some(snapshot),
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> BA512RangeSupport<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_ba512_range(self, ba512_range: &'a BA512Range) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: Some(ba512_range),
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> LeaseIdSupport<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: Some(lease_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> IfSinceConditionSupport
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> IfMatchConditionSupport<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> ClientRequestIdSupport<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: Some(client_request_id),
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> ExtraHeadersOption<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> ExtraHeadersSupport<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> ApiVersionOption<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> ApiVersionSupport<'a>
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet> TimeoutSupport
+    for GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+    type O = GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        GetPageRangesDiffBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_previous_snapshot: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            previous_snapshot: self.previous_snapshot,
+            snapshot: self.snapshot,
+            ba512_range: self.ba512_range,
+            lease_id: self.lease_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: Some(timeout),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+    GetPageRangesDiffBuilder<'a, ContainerNameSet, BlobNameSet, PreviousSnapshotSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    PreviousSnapshotSet: ToAssign,
+{
+}
+
+impl<'a> GetPageRangesDiffBuilder<'a, Yes, Yes, Yes> {
+    /// Lists the pages written and cleared between the previous snapshot
+    /// and the blob (or the snapshot set with `with_snapshot`), for
+    /// example to back up only the pages changed since the last backup.
+    pub fn finalize(self) -> impl Future<Item = GetPageRangesDiffResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, Some("comp=pagelist"));
+
+        if let Some(snapshot) = SnapshotOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, snapshot);
+        }
+        uri = format!("{}&{}", uri, PreviousSnapshotRequired::to_uri_parameter(&self));
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::GET,
+            |ref mut request| {
+                BA512RangeOption::add_header(&self, request);
+                LeaseIdOption::add_header(&self, request);
+                IfSinceConditionOption::add_header(&self, request);
+                IfMatchConditionOption::add_header(&self, request);
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            None,
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::OK))
+            .and_then(move |(headers, body)| done(GetPageRangesDiffResponse::from_response(&headers, &body)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetPageRangesDiffResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
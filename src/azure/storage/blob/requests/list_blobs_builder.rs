@@ -1,14 +1,17 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, DelimiterOption,
-    DelimiterSupport, IncludeCopyOption, IncludeCopySupport, IncludeDeletedOption, IncludeDeletedSupport, IncludeListOptions,
-    IncludeMetadataOption, IncludeMetadataSupport, IncludeSnapshotsOption, IncludeSnapshotsSupport, IncludeUncommittedBlobsOption,
-    IncludeUncommittedBlobsSupport, MaxResultsOption, MaxResultsSupport, NextMarkerOption, NextMarkerSupport, No, PrefixOption,
-    PrefixSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, DelimiterOption, DelimiterSupport, ExtraHeadersOption, ExtraHeadersSupport, IncludeCopyOption,
+    IncludeCopySupport, IncludeDeletedOption, IncludeDeletedSupport, IncludeListOptions, IncludeMetadataOption, IncludeMetadataSupport,
+    IncludeSnapshotsOption, IncludeSnapshotsSupport, IncludeTagsOption, IncludeTagsSupport, IncludeUncommittedBlobsOption,
+    IncludeUncommittedBlobsSupport, IncludeVersionsOption, IncludeVersionsSupport, MaxResultsOption, MaxResultsSupport, NextMarkerOption,
+    NextMarkerSupport, No, PrefixOption, PrefixSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
 };
 use crate::azure::storage::blob::responses::ListBlobsResponse;
+use crate::azure::storage::blob::{Blob, ListBlobStreamBuilder, TAGS_VERSION, VERSIONING_VERSION};
 use crate::azure::storage::client::Client;
 use crate::azure::storage::container::generate_container_uri;
+use crate::azure::storage::rest_client::HEADER_VERSION;
 use futures::future::done;
 use futures::prelude::*;
 use hyper::{Method, StatusCode};
@@ -33,6 +36,10 @@ where
     include_uncommitted_blobs: bool,
     include_copy: bool,
     include_deleted: bool,
+    include_versions: bool,
+    include_tags: bool,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> ListBlobBuilder<'a, No> {
@@ -53,6 +60,10 @@ impl<'a> ListBlobBuilder<'a, No> {
             include_uncommitted_blobs: false,
             include_copy: false,
             include_deleted: false,
+            include_versions: false,
+            include_tags: false,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -207,6 +218,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -234,6 +249,72 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersOption<'a> for ListBlobBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersSupport<'a> for ListBlobBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobBuilder<'a, ContainerNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionOption<'a> for ListBlobBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionSupport<'a> for ListBlobBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ListBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -261,6 +342,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -288,6 +373,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -315,6 +404,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -342,6 +435,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -369,6 +466,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -396,6 +497,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -423,6 +528,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -450,6 +559,10 @@ where
             include_uncommitted_blobs: true,
             include_copy: self.include_copy,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -477,6 +590,10 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: true,
             include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -504,6 +621,92 @@ where
             include_uncommitted_blobs: self.include_uncommitted_blobs,
             include_copy: self.include_copy,
             include_deleted: true,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeVersionsOption for ListBlobBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_versions(&self) -> bool {
+        self.include_versions
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeTagsOption for ListBlobBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_tags(&self) -> bool {
+        self.include_tags
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeVersionsSupport for ListBlobBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_versions(self) -> Self::O {
+        ListBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: true,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeTagsSupport for ListBlobBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_tags(self) -> Self::O {
+        ListBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: true,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -543,11 +746,54 @@ impl<'a> ListBlobBuilder<'a, Yes> {
 
         trace!("list blob uri = {}", uri);
 
-        let req = self.client().perform_request(&uri, &Method::GET, |_| {}, None);
+        let api_version = ApiVersionOption::required_api_version(
+            &self,
+            self.client(),
+            if self.include_versions() {
+                Some(VERSIONING_VERSION)
+            } else if self.include_tags() {
+                Some(TAGS_VERSION)
+            } else {
+                None
+            },
+        );
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::GET,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                None,
+            )
+        });
 
         done(req).from_err().and_then(move |future_response| {
             check_status_extract_headers_and_body_as_string(future_response, StatusCode::OK)
                 .and_then(move |(headers, body_as_str)| done(ListBlobsResponse::from_response(&container_name, &headers, &body_as_str)))
         })
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<ListBlobsResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+
+    /// Returns every blob matching the request, following the `NextMarker`
+    /// continuation tokens as needed. If a marker has been specified the
+    /// listing starts from there.
+    #[inline]
+    pub fn stream(self) -> impl Stream<Item = Blob, Error = AzureError> {
+        ListBlobStreamBuilder::from(self).finalize()
+    }
+
+    /// Same as `stream` but yields one `ListBlobsResponse` per page.
+    #[inline]
+    pub fn pages(self) -> impl Stream<Item = ListBlobsResponse, Error = AzureError> {
+        ListBlobStreamBuilder::from(self).pages()
+    }
 }
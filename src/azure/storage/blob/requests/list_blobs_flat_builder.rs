@@ -0,0 +1,759 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, IncludeCopyOption, IncludeCopySupport, IncludeDeletedOption,
+    IncludeDeletedSupport, IncludeListOptions, IncludeMetadataOption, IncludeMetadataSupport, IncludeSnapshotsOption,
+    IncludeSnapshotsSupport, IncludeTagsOption, IncludeTagsSupport, IncludeUncommittedBlobsOption, IncludeUncommittedBlobsSupport,
+    IncludeVersionsOption, IncludeVersionsSupport, MaxResultsOption, MaxResultsSupport, NextMarkerOption, NextMarkerSupport, No,
+    PrefixOption, PrefixSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::blob::responses::ListBlobsResponse;
+use crate::azure::storage::blob::{Blob, ListBlobStreamBuilder, TAGS_VERSION, VERSIONING_VERSION};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::container::generate_container_uri;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+/// Lists every blob of the container, whatever its name, with no
+/// `BlobPrefix` entries: there is no delimiter to set, use
+/// `list_blobs_hierarchical` to walk the virtual directories.
+///
+/// ```no_run
+/// # use azure_sdk_for_rust::prelude::*;
+/// # use azure_sdk_for_rust::storage::client::{Blob, Client};
+/// # let client = Client::new("account", "a2V5").unwrap();
+/// let future = client.list_blobs_flat().with_container_name("logs").with_prefix("2019/").finalize();
+/// ```
+///
+/// ```compile_fail
+/// # use azure_sdk_for_rust::prelude::*;
+/// # use azure_sdk_for_rust::storage::client::{Blob, Client};
+/// # let client = Client::new("account", "a2V5").unwrap();
+/// let future = client.list_blobs_flat().with_container_name("logs").with_delimiter("/").finalize();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    container_name: Option<&'a str>,
+    client_request_id: Option<&'a str>,
+    timeout: Option<u64>,
+    prefix: Option<&'a str>,
+    next_marker: Option<&'a str>,
+    max_results: Option<u32>,
+    include_snapshots: bool,
+    include_metadata: bool,
+    include_uncommitted_blobs: bool,
+    include_copy: bool,
+    include_deleted: bool,
+    include_versions: bool,
+    include_tags: bool,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> ListBlobsFlatBuilder<'a, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> ListBlobsFlatBuilder<'a, No> {
+        ListBlobsFlatBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            client_request_id: None,
+            timeout: None,
+            prefix: None,
+            next_marker: None,
+            max_results: None,
+            include_snapshots: false,
+            include_metadata: false,
+            include_uncommitted_blobs: false,
+            include_copy: false,
+            include_deleted: false,
+            include_versions: false,
+            include_tags: false,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ClientRequired<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> ContainerNameRequired<'a> for ListBlobsFlatBuilder<'a, Yes> {
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet> ClientRequestIdOption<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet> TimeoutOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet> PrefixOption<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn prefix(&self) -> Option<&'a str> {
+        self.prefix
+    }
+}
+
+impl<'a, ContainerNameSet> NextMarkerOption<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn next_marker(&self) -> Option<&'a str> {
+        self.next_marker
+    }
+}
+
+impl<'a, ContainerNameSet> MaxResultsOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn max_results(&self) -> Option<u32> {
+        self.max_results
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeSnapshotsOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_snapshots(&self) -> bool {
+        self.include_snapshots
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeMetadataOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_metadata(&self) -> bool {
+        self.include_metadata
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeUncommittedBlobsOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_uncommitted_blobs(&self) -> bool {
+        self.include_uncommitted_blobs
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeCopyOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_copy(&self) -> bool {
+        self.include_copy
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeDeletedOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_deleted(&self) -> bool {
+        self.include_deleted
+    }
+}
+
+impl<'a, ContainerNameSet> ContainerNameSupport<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, Yes>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: Some(container_name),
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ClientRequestIdSupport<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: Some(client_request_id),
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersOption<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersSupport<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionOption<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionSupport<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> TimeoutSupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: Some(timeout),
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> PrefixSupport<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_prefix(self, prefix: &'a str) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: Some(prefix),
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> NextMarkerSupport<'a> for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_next_marker(self, next_marker: &'a str) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: Some(next_marker),
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> MaxResultsSupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_max_results(self, max_results: u32) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: Some(max_results),
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeSnapshotsSupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_snapshots(self) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: true,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeMetadataSupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_metadata(self) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: true,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeUncommittedBlobsSupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_uncommitted_blobs(self) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: true,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeCopySupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_copy(self) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: true,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeDeletedSupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_deleted(self) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: true,
+            include_versions: self.include_versions,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeVersionsOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_versions(&self) -> bool {
+        self.include_versions
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeTagsOption for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn include_tags(&self) -> bool {
+        self.include_tags
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeVersionsSupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_versions(self) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: true,
+            include_tags: self.include_tags,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> IncludeTagsSupport for ListBlobsFlatBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = ListBlobsFlatBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_include_tags(self) -> Self::O {
+        ListBlobsFlatBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            include_snapshots: self.include_snapshots,
+            include_metadata: self.include_metadata,
+            include_uncommitted_blobs: self.include_uncommitted_blobs,
+            include_copy: self.include_copy,
+            include_deleted: self.include_deleted,
+            include_versions: self.include_versions,
+            include_tags: true,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet> ListBlobsFlatBuilder<'a, ContainerNameSet> where ContainerNameSet: ToAssign {}
+
+// this empty trait is required in order to use IncludeListOptions methods. No duck typing, sorry
+// :(
+impl<'a> IncludeListOptions for ListBlobsFlatBuilder<'a, Yes> {}
+
+impl<'a> ListBlobsFlatBuilder<'a, Yes> {
+    #[inline]
+    pub fn finalize(self) -> impl Future<Item = ListBlobsResponse, Error = AzureError> {
+        // we create a copy to move into the future's closure.
+        // We need to do this since the closure only accepts
+        // 'static lifetimes.
+        let container_name = self.container_name().to_owned();
+
+        let mut uri = generate_container_uri(&self, Some("restype=container&comp=list"));
+
+        if let Some(mr) = MaxResultsOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, mr);
+        }
+        if let Some(mr) = NextMarkerOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, mr);
+        }
+        if let Some(mr) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, mr);
+        }
+        if let Some(mr) = PrefixOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, mr);
+        }
+        if let Some(mr) = IncludeListOptions::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, mr);
+        }
+
+        trace!("list blob uri = {}", uri);
+
+        let api_version = ApiVersionOption::required_api_version(
+            &self,
+            self.client(),
+            if self.include_versions() {
+                Some(VERSIONING_VERSION)
+            } else if self.include_tags() {
+                Some(TAGS_VERSION)
+            } else {
+                None
+            },
+        );
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::GET,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                None,
+            )
+        });
+
+        done(req).from_err().and_then(move |future_response| {
+            check_status_extract_headers_and_body_as_string(future_response, StatusCode::OK)
+                .and_then(move |(headers, body_as_str)| done(ListBlobsResponse::from_response(&container_name, &headers, &body_as_str)))
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<ListBlobsResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+
+    /// Returns every blob matching the request, following the `NextMarker`
+    /// continuation tokens as needed. If a marker has been specified the
+    /// listing starts from there.
+    #[inline]
+    pub fn stream(self) -> impl Stream<Item = Blob, Error = AzureError> {
+        ListBlobStreamBuilder::from(self).finalize()
+    }
+
+    /// Same as `stream` but yields one `ListBlobsResponse` per page.
+    #[inline]
+    pub fn pages(self) -> impl Stream<Item = ListBlobsResponse, Error = AzureError> {
+        ListBlobStreamBuilder::from(self).pages()
+    }
+}
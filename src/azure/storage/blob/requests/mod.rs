@@ -1,34 +1,74 @@
 mod acquire_blob_lease_builder;
+mod blob_exists_builder;
 mod break_blob_lease_builder;
 mod change_blob_lease_builder;
 mod clear_page_builder;
+mod copy_blob_builder;
+mod copy_blob_from_url_builder;
 mod delete_blob_builder;
+mod delete_blob_immutability_policy_builder;
 mod delete_blob_snapshot_builder;
+mod find_blobs_by_tags_builder;
 mod get_blob_builder;
+mod get_blob_properties_builder;
+mod get_blob_tags_builder;
 mod get_block_list_builder;
+mod get_page_ranges_builder;
+mod get_page_ranges_diff_builder;
+mod incremental_copy_blob_builder;
 mod list_blobs_builder;
+mod list_blobs_flat_builder;
+mod list_blobs_hierarchical_builder;
 mod put_append_blob_builder;
 mod put_block_blob_builder;
 mod put_block_builder;
+mod put_block_from_url_builder;
 mod put_block_list_builder;
 mod put_page_blob_builder;
 mod release_blob_lease_builder;
 mod renew_blob_lease_builder;
+mod set_blob_immutability_policy_builder;
+mod set_blob_legal_hold_builder;
+mod set_blob_metadata_builder;
+mod set_blob_properties_builder;
+mod set_blob_tags_builder;
+mod set_blob_tier_builder;
+mod snapshot_blob_builder;
 mod update_page_builder;
 pub use self::acquire_blob_lease_builder::AcquireBlobLeaseBuilder;
+pub use self::blob_exists_builder::BlobExistsBuilder;
 pub use self::break_blob_lease_builder::BreakBlobLeaseBuilder;
 pub use self::change_blob_lease_builder::ChangeBlobLeaseBuilder;
 pub use self::clear_page_builder::ClearPageBuilder;
+pub use self::copy_blob_builder::CopyBlobBuilder;
+pub use self::copy_blob_from_url_builder::CopyBlobFromUrlBuilder;
 pub use self::delete_blob_builder::DeleteBlobBuilder;
+pub use self::delete_blob_immutability_policy_builder::DeleteBlobImmutabilityPolicyBuilder;
 pub use self::delete_blob_snapshot_builder::DeleteBlobSnapshotBuilder;
+pub use self::find_blobs_by_tags_builder::FindBlobsByTagsBuilder;
 pub use self::get_blob_builder::GetBlobBuilder;
+pub use self::get_blob_properties_builder::GetBlobPropertiesBuilder;
+pub use self::get_blob_tags_builder::GetBlobTagsBuilder;
 pub use self::get_block_list_builder::GetBlockListBuilder;
+pub use self::get_page_ranges_builder::GetPageRangesBuilder;
+pub use self::get_page_ranges_diff_builder::GetPageRangesDiffBuilder;
+pub use self::incremental_copy_blob_builder::IncrementalCopyBlobBuilder;
 pub use self::list_blobs_builder::ListBlobBuilder;
+pub use self::list_blobs_flat_builder::ListBlobsFlatBuilder;
+pub use self::list_blobs_hierarchical_builder::ListBlobsHierarchicalBuilder;
 pub use self::put_append_blob_builder::PutAppendBlobBuilder;
 pub use self::put_block_blob_builder::PutBlockBlobBuilder;
 pub use self::put_block_builder::PutBlockBuilder;
+pub use self::put_block_from_url_builder::PutBlockFromUrlBuilder;
 pub use self::put_block_list_builder::PutBlockListBuilder;
 pub use self::put_page_blob_builder::PutPageBlobBuilder;
 pub use self::release_blob_lease_builder::ReleaseBlobLeaseBuilder;
 pub use self::renew_blob_lease_builder::RenewBlobLeaseBuilder;
+pub use self::set_blob_immutability_policy_builder::SetBlobImmutabilityPolicyBuilder;
+pub use self::set_blob_legal_hold_builder::SetBlobLegalHoldBuilder;
+pub use self::set_blob_metadata_builder::SetBlobMetadataBuilder;
+pub use self::set_blob_properties_builder::SetBlobPropertiesBuilder;
+pub use self::set_blob_tags_builder::SetBlobTagsBuilder;
+pub use self::set_blob_tier_builder::SetBlobTierBuilder;
+pub use self::snapshot_blob_builder::SnapshotBlobBuilder;
 pub use self::update_page_builder::UpdatePageBuilder;
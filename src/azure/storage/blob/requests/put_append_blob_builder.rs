@@ -1,11 +1,15 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::headers::BLOB_TYPE;
 use crate::azure::core::lease::LeaseId;
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, CacheControlOption, CacheControlSupport, ClientRequestIdOption, ClientRequestIdSupport,
-    ClientRequired, ContainerNameRequired, ContainerNameSupport, ContentDispositionOption, ContentDispositionSupport,
-    ContentEncodingOption, ContentEncodingSupport, ContentLanguageOption, ContentLanguageSupport, ContentTypeOption, ContentTypeSupport,
-    LeaseIdOption, LeaseIdSupport, MetadataOption, MetadataSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, CacheControlOption, CacheControlSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ContentDispositionOption,
+    ContentDispositionSupport, ContentEncodingOption, ContentEncodingSupport, ContentLanguageOption, ContentLanguageSupport,
+    ContentTypeOption, ContentTypeSupport, ExtraHeadersOption, ExtraHeadersSupport, IfMatchConditionOption, IfMatchConditionSupport,
+    IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, MetadataOption, MetadataSupport, No, TimeoutOption,
+    TimeoutSupport, ToAssign, Yes,
 };
 use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::PutBlobResponse;
@@ -13,7 +17,6 @@ use crate::azure::storage::client::Client;
 use futures::future::{done, ok};
 use futures::prelude::*;
 use hyper::{Method, StatusCode};
-use std::collections::HashMap;
 use std::marker::PhantomData;
 
 #[derive(Debug, Clone)]
@@ -33,9 +36,13 @@ where
     content_language: Option<&'a str>,
     cache_control: Option<&'a str>,
     content_disposition: Option<&'a str>,
-    metadata: Option<&'a HashMap<&'a str, &'a str>>,
+    metadata: Option<&'a Metadata>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> PutAppendBlobBuilder<'a, No, No> {
@@ -56,6 +63,10 @@ impl<'a> PutAppendBlobBuilder<'a, No, No> {
             metadata: None,
             lease_id: None,
             client_request_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -163,7 +174,7 @@ where
     BlobNameSet: ToAssign,
 {
     #[inline]
-    fn metadata(&self) -> Option<&'a HashMap<&'a str, &'a str>> {
+    fn metadata(&self) -> Option<&'a Metadata> {
         self.metadata
     }
 }
@@ -214,6 +225,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -242,6 +257,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -270,6 +289,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -298,6 +321,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -326,6 +353,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -354,6 +385,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -382,6 +417,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -410,6 +449,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -422,7 +465,7 @@ where
     type O = PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
 
     #[inline]
-    fn with_metadata(self, metadata: &'a HashMap<&'a str, &'a str>) -> Self::O {
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
         PutAppendBlobBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -438,6 +481,10 @@ where
             metadata: Some(metadata),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -466,6 +513,10 @@ where
             metadata: self.metadata,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -494,6 +545,162 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersOption<'a> for PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersSupport<'a> for PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionOption<'a> for PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionSupport<'a> for PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        PutAppendBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> IfSinceConditionOption for PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> IfMatchConditionOption<'a> for PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> IfSinceConditionSupport for PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        PutAppendBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> IfMatchConditionSupport<'a> for PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = PutAppendBlobBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        PutAppendBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -529,7 +736,11 @@ impl<'a> PutAppendBlobBuilder<'a, Yes, Yes> {
                 MetadataOption::add_header(&self, request);
                 request.header(BLOB_TYPE, "AppendBlob");
                 LeaseIdOption::add_header(&self, request);
+                IfSinceConditionOption::add_header(&self, request);
+                IfMatchConditionOption::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -539,4 +750,9 @@ impl<'a> PutAppendBlobBuilder<'a, Yes, Yes> {
             .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
             .and_then(move |(headers, _body)| done(PutBlobResponse::from_headers(&headers)).and_then(ok))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<PutBlobResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
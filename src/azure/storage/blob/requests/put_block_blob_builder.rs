@@ -1,20 +1,29 @@
+use crate::azure::core::checksum::{Checksum, ChecksumMode};
+use crate::azure::core::compression::gzip;
+use crate::azure::core::customer_provided_key::CustomerProvidedKey;
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
-use crate::azure::core::headers::BLOB_TYPE;
+use crate::azure::core::headers::{BLOB_TYPE, META_PREFIX};
 use crate::azure::core::lease::LeaseId;
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
+use crate::azure::core::progress::Progress;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, BodyRequired, BodySupport, CacheControlOption, CacheControlSupport, ClientRequestIdOption,
+    add_content_md5_header, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, BodyRequired, BodySupport, CPKOption,
+    CPKSupport, CacheControlOption, CacheControlSupport, ChecksumModeOption, ChecksumModeSupport, ClientRequestIdOption,
     ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ContentDispositionOption,
     ContentDispositionSupport, ContentEncodingOption, ContentEncodingSupport, ContentLanguageOption, ContentLanguageSupport,
-    ContentMD5Option, ContentMD5Support, ContentTypeOption, ContentTypeSupport, LeaseIdOption, LeaseIdSupport, MetadataOption,
-    MetadataSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    ContentMD5Option, ContentMD5Support, ContentTypeOption, ContentTypeSupport, ExtraHeadersOption, ExtraHeadersSupport,
+    IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport,
+    MetadataOption, MetadataSupport, No, ProgressOption, ProgressSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
 };
-use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::PutBlockBlobResponse;
+use crate::azure::storage::blob::{generate_blob_uri, ENCRYPTION_DATA_METADATA};
 use crate::azure::storage::client::Client;
-use futures::future::{done, ok};
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use futures::future::done;
 use futures::prelude::*;
+use hyper::header::CONTENT_ENCODING;
 use hyper::{Method, StatusCode};
-use std::collections::HashMap;
 use std::marker::PhantomData;
 
 #[derive(Debug, Clone)]
@@ -38,9 +47,17 @@ where
     cache_control: Option<&'a str>,
     content_md5: Option<&'a [u8]>,
     content_disposition: Option<&'a str>,
-    metadata: Option<&'a HashMap<&'a str, &'a str>>,
+    metadata: Option<&'a Metadata>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    gzip: bool,
+    checksum_mode: Option<ChecksumMode>,
+    customer_provided_key: Option<&'a CustomerProvidedKey>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+    progress: Option<&'a Progress>,
 }
 
 impl<'a> PutBlockBlobBuilder<'a, No, No, No> {
@@ -64,6 +81,14 @@ impl<'a> PutBlockBlobBuilder<'a, No, No, No> {
             metadata: None,
             lease_id: None,
             client_request_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            checksum_mode: None,
+            customer_provided_key: None,
+            gzip: false,
+            extra_headers: Vec::new(),
+            api_version: None,
+            progress: None,
         }
     }
 }
@@ -207,7 +232,7 @@ where
     BodySet: ToAssign,
 {
     #[inline]
-    fn metadata(&self) -> Option<&'a HashMap<&'a str, &'a str>> {
+    fn metadata(&self) -> Option<&'a Metadata> {
         self.metadata
     }
 }
@@ -266,6 +291,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -298,6 +331,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -330,6 +371,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -362,6 +411,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -394,6 +451,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -427,6 +492,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -460,6 +533,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -492,6 +573,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -524,6 +613,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -557,6 +654,14 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -570,7 +675,7 @@ where
     type O = PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>;
 
     #[inline]
-    fn with_metadata(self, metadata: &'a HashMap<&'a str, &'a str>) -> Self::O {
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
         PutBlockBlobBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -589,6 +694,14 @@ where
             metadata: Some(metadata),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -621,6 +734,14 @@ where
             metadata: self.metadata,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -654,6 +775,353 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> ExtraHeadersOption<'a> for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> ExtraHeadersSupport<'a> for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> ApiVersionOption<'a> for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> ApiVersionSupport<'a> for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        PutBlockBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_md5: self.content_md5,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> ProgressOption<'a> for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn progress(&self) -> Option<&'a Progress> {
+        self.progress
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> ProgressSupport<'a> for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>;
+
+    #[inline]
+    fn with_progress(self, progress: &'a Progress) -> Self::O {
+        PutBlockBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_md5: self.content_md5,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: Some(progress),
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> IfSinceConditionOption for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> IfMatchConditionOption<'a>
+    for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> IfSinceConditionSupport for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        PutBlockBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_md5: self.content_md5,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> IfMatchConditionSupport<'a>
+    for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        PutBlockBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_md5: self.content_md5,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> ChecksumModeOption for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn checksum_mode(&self) -> Option<ChecksumMode> {
+        self.checksum_mode
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> ChecksumModeSupport for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>;
+
+    #[inline]
+    fn with_checksum_mode(self, checksum_mode: ChecksumMode) -> Self::O {
+        PutBlockBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_md5: self.content_md5,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: Some(checksum_mode),
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> CPKOption<'a> for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn customer_provided_key(&self) -> Option<&'a CustomerProvidedKey> {
+        self.customer_provided_key
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet> CPKSupport<'a> for PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutBlockBlobBuilder<'a, ContainerNameSet, BlobNameSet, BodySet>;
+
+    #[inline]
+    fn with_customer_provided_key(self, customer_provided_key: &'a CustomerProvidedKey) -> Self::O {
+        PutBlockBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_md5: self.content_md5,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            gzip: self.gzip,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: Some(customer_provided_key),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -665,6 +1133,19 @@ where
     BlobNameSet: ToAssign,
     BodySet: ToAssign,
 {
+    /// Compresses the body with gzip before uploading it and sets the
+    /// Content-Encoding header accordingly (overriding the one specified,
+    /// if any). Since the specified Content-MD5 would not match the
+    /// compressed payload it is replaced by the hash of the latter.
+    #[inline]
+    pub fn with_gzip(self) -> Self {
+        PutBlockBlobBuilder { gzip: true, ..self }
+    }
+
+    #[inline]
+    pub fn gzip(&self) -> bool {
+        self.gzip
+    }
 }
 
 impl<'a> PutBlockBlobBuilder<'a, Yes, Yes, Yes> {
@@ -678,27 +1159,82 @@ impl<'a> PutBlockBlobBuilder<'a, Yes, Yes, Yes> {
 
         trace!("uri == {:?}", uri);
 
-        let req = self.client().perform_request(
-            &uri,
-            &Method::PUT,
-            |ref mut request| {
-                ContentTypeOption::add_header(&self, request);
-                ContentEncodingOption::add_header(&self, request);
-                ContentLanguageOption::add_header(&self, request);
-                ContentMD5Option::add_header(&self, request);
-                CacheControlOption::add_header(&self, request);
-                ContentDispositionOption::add_header(&self, request);
-                MetadataOption::add_header(&self, request);
-                request.header(BLOB_TYPE, "BlockBlob");
-                LeaseIdOption::add_header(&self, request);
-                ClientRequestIdOption::add_header(&self, request);
-            },
-            Some(self.body()),
-        );
+        let encrypted = self
+            .client()
+            .client_side_encryption()
+            .map(|encryption| encryption.encrypt(self.body()))
+            .transpose();
+
+        let progress = self.progress().cloned();
+        let length = self.body().len() as u64;
+        let mut checksum = None;
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), CPKOption::required_api_version(&self));
+        let req = encrypted.and_then(|encrypted| {
+            let api_version = api_version?;
+            let plain = encrypted.as_ref().map(|(data, _)| data as &[u8]).unwrap_or_else(|| self.body());
+            let compressed = if self.gzip { Some(gzip(plain)?) } else { None };
+            let body = compressed.as_ref().map(|c| c as &[u8]).unwrap_or(plain);
+            checksum = ChecksumModeOption::checksum_mode(&self).map(|mode| Checksum::compute(mode, body));
+
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    ContentTypeOption::add_header(&self, request);
+                    ContentLanguageOption::add_header(&self, request);
+                    if compressed.is_some() {
+                        request.header(CONTENT_ENCODING, "gzip");
+                        add_content_md5_header(&md5::compute(body)[..], request);
+                    } else if encrypted.is_some() {
+                        // the given MD5 is the one of the plaintext
+                        ContentEncodingOption::add_header(&self, request);
+                    } else {
+                        ContentEncodingOption::add_header(&self, request);
+                        ContentMD5Option::add_header(&self, request);
+                    }
+                    if let Some(checksum) = &checksum {
+                        checksum.add_header(request);
+                    }
+                    CacheControlOption::add_header(&self, request);
+                    ContentDispositionOption::add_header(&self, request);
+                    MetadataOption::add_header(&self, request);
+                    if let Some((_, encryption_data)) = &encrypted {
+                        request.header(
+                            &format!("{}{}", META_PREFIX, ENCRYPTION_DATA_METADATA) as &str,
+                            encryption_data as &str,
+                        );
+                    }
+                    request.header(BLOB_TYPE, "BlockBlob");
+                    LeaseIdOption::add_header(&self, request);
+                    IfSinceConditionOption::add_header(&self, request);
+                    IfMatchConditionOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                    CPKOption::add_header(&self, request);
+                },
+                Some(body),
+            )
+        });
 
         done(req)
             .from_err()
             .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
-            .and_then(move |(headers, _body)| done(PutBlockBlobResponse::from_headers(&headers)).and_then(ok))
+            .and_then(move |(headers, _body)| {
+                if let Some(checksum) = checksum {
+                    checksum.validate(&headers)?;
+                }
+                if let Some(progress) = progress {
+                    progress.report(length, Some(length));
+                }
+                PutBlockBlobResponse::from_headers(&headers)
+            })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<PutBlockBlobResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
     }
 }
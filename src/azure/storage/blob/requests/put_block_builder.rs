@@ -1,13 +1,18 @@
+use crate::azure::core::checksum::{Checksum, ChecksumMode};
+use crate::azure::core::customer_provided_key::CustomerProvidedKey;
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
+use crate::azure::core::progress::Progress;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, BlockIdRequired, BlockIdSupport, BodyRequired, BodySupport, ClientRequestIdOption,
-    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ContentMD5Option, ContentMD5Support,
-    LeaseIdOption, LeaseIdSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, BlockIdRequired, BlockIdSupport, BodyRequired, BodySupport,
+    CPKOption, CPKSupport, ChecksumModeOption, ChecksumModeSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ContentMD5Option, ContentMD5Support, ExtraHeadersOption, ExtraHeadersSupport,
+    LeaseIdOption, LeaseIdSupport, No, ProgressOption, ProgressSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
 };
 use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::PutBlockResponse;
 use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
 use futures::future::done;
 use futures::prelude::*;
 use hyper::{Method, StatusCode};
@@ -34,6 +39,11 @@ where
     content_md5: Option<&'a [u8]>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    checksum_mode: Option<ChecksumMode>,
+    customer_provided_key: Option<&'a CustomerProvidedKey>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+    progress: Option<&'a Progress>,
 }
 
 impl<'a> PutBlockBuilder<'a, No, No, No, No> {
@@ -53,6 +63,11 @@ impl<'a> PutBlockBuilder<'a, No, No, No, No> {
             content_md5: None,
             lease_id: None,
             client_request_id: None,
+            checksum_mode: None,
+            customer_provided_key: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+            progress: None,
         }
     }
 }
@@ -201,6 +216,11 @@ where
             content_md5: self.content_md5,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -231,6 +251,11 @@ where
             content_md5: self.content_md5,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -261,6 +286,11 @@ where
             content_md5: self.content_md5,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -291,6 +321,11 @@ where
             content_md5: self.content_md5,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -321,6 +356,11 @@ where
             content_md5: self.content_md5,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -351,6 +391,11 @@ where
             content_md5: Some(content_md5),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -381,6 +426,11 @@ where
             content_md5: self.content_md5,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -411,6 +461,236 @@ where
             content_md5: self.content_md5,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> ExtraHeadersOption<'a>
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> ExtraHeadersSupport<'a>
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    type O = PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> ApiVersionOption<'a>
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> ApiVersionSupport<'a>
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    type O = PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        PutBlockBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            p_block_id: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            block_id: self.block_id,
+            timeout: self.timeout,
+            content_md5: self.content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> ProgressOption<'a>
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    #[inline]
+    fn progress(&self) -> Option<&'a Progress> {
+        self.progress
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> ProgressSupport<'a>
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    type O = PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>;
+
+    #[inline]
+    fn with_progress(self, progress: &'a Progress) -> Self::O {
+        PutBlockBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            p_block_id: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            block_id: self.block_id,
+            timeout: self.timeout,
+            content_md5: self.content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: Some(progress),
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> ChecksumModeOption
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    #[inline]
+    fn checksum_mode(&self) -> Option<ChecksumMode> {
+        self.checksum_mode
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> ChecksumModeSupport
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    type O = PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>;
+
+    #[inline]
+    fn with_checksum_mode(self, checksum_mode: ChecksumMode) -> Self::O {
+        PutBlockBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            p_block_id: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            block_id: self.block_id,
+            timeout: self.timeout,
+            content_md5: self.content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            checksum_mode: Some(checksum_mode),
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> CPKOption<'a>
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    #[inline]
+    fn customer_provided_key(&self) -> Option<&'a CustomerProvidedKey> {
+        self.customer_provided_key
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet> CPKSupport<'a>
+    for PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    type O = PutBlockBuilder<'a, ContainerNameSet, BlobNameSet, BodySet, BlockIdSet>;
+
+    #[inline]
+    fn with_customer_provided_key(self, customer_provided_key: &'a CustomerProvidedKey) -> Self::O {
+        PutBlockBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            p_block_id: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            block_id: self.block_id,
+            timeout: self.timeout,
+            content_md5: self.content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            checksum_mode: self.checksum_mode,
+            customer_provided_key: Some(customer_provided_key),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -437,20 +717,48 @@ impl<'a> PutBlockBuilder<'a, Yes, Yes, Yes, Yes> {
 
         trace!("uri == {:?}", uri);
 
-        let req = self.client().perform_request(
-            &uri,
-            &Method::PUT,
-            |ref mut request| {
-                ContentMD5Option::add_header(&self, request);
-                LeaseIdOption::add_header(&self, request);
-                ClientRequestIdOption::add_header(&self, request);
-            },
-            Some(self.body()),
-        );
+        let checksum = ChecksumModeOption::checksum_mode(&self).map(|mode| Checksum::compute(mode, self.body()));
+        let progress = self.progress().cloned();
+        let length = self.body().len() as u64;
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), CPKOption::required_api_version(&self));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    ContentMD5Option::add_header(&self, request);
+                    if let Some(checksum) = &checksum {
+                        checksum.add_header(request);
+                    }
+                    LeaseIdOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                    CPKOption::add_header(&self, request);
+                },
+                Some(self.body()),
+            )
+        });
 
         done(req)
             .from_err()
             .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
-            .and_then(move |(headers, _body)| done(PutBlockResponse::from_headers(&headers)))
+            .and_then(move |(headers, _body)| {
+                if let Some(checksum) = checksum {
+                    checksum.validate(&headers)?;
+                }
+                if let Some(progress) = progress {
+                    progress.report(length, Some(length));
+                }
+                PutBlockResponse::from_headers(&headers)
+            })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<PutBlockResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
     }
 }
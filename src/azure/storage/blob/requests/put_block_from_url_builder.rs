@@ -0,0 +1,705 @@
+use crate::azure::core::copy_source_authorization::CopySourceAuthorization;
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::range::Range;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, BlockIdRequired, BlockIdSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, CopySourceAuthorizationOption,
+    CopySourceAuthorizationSupport, CopySourceRequired, CopySourceSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption,
+    LeaseIdSupport, No, SourceContentMD5Option, SourceContentMD5Support, SourceRangeOption, SourceRangeSupport, TimeoutOption,
+    TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::blob::generate_blob_uri;
+use crate::azure::storage::blob::responses::PutBlockFromUrlResponse;
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+// put block from URL is not available before this version
+const PUT_BLOCK_FROM_URL_VERSION: &str = "2018-03-28";
+
+#[derive(Debug, Clone)]
+pub struct PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    p_block_id: PhantomData<BlockIdSet>,
+    p_copy_source: PhantomData<CopySourceSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    block_id: Option<&'a [u8]>,
+    copy_source: Option<&'a str>,
+    source_range: Option<&'a Range>,
+    source_content_md5: Option<&'a [u8]>,
+    lease_id: Option<&'a LeaseId>,
+    client_request_id: Option<&'a str>,
+    timeout: Option<u64>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+    copy_source_authorization: Option<&'a CopySourceAuthorization>,
+}
+
+impl<'a> PutBlockFromUrlBuilder<'a, No, No, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> PutBlockFromUrlBuilder<'a, No, No, No, No> {
+        PutBlockFromUrlBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            p_block_id: PhantomData {},
+            block_id: None,
+            p_copy_source: PhantomData {},
+            copy_source: None,
+            source_range: None,
+            source_content_md5: None,
+            lease_id: None,
+            client_request_id: None,
+            timeout: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+            copy_source_authorization: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> ClientRequired<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet, BlockIdSet, CopySourceSet> ContainerNameRequired<'a>
+    for PutBlockFromUrlBuilder<'a, Yes, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlockIdSet, CopySourceSet> BlobNameRequired<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, Yes, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, CopySourceSet> BlockIdRequired<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, Yes, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn block_id(&self) -> &'a [u8] {
+        self.block_id.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet> CopySourceRequired<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+{
+    #[inline]
+    fn copy_source(&self) -> &'a str {
+        self.copy_source.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> SourceRangeOption<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn source_range(&self) -> Option<&'a Range> {
+        self.source_range
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> SourceContentMD5Option<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn source_content_md5(&self) -> Option<&'a [u8]> {
+        self.source_content_md5
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> LeaseIdOption<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> ClientRequestIdOption<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> TimeoutOption
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> ContainerNameSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, Yes, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> BlobNameSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, Yes, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> BlockIdSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, Yes, CopySourceSet>;
+
+    #[inline]
+    fn with_block_id(self, block_id: &'a [u8]) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: Some(block_id),
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> CopySourceSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, Yes>;
+
+    #[inline]
+    fn with_copy_source(self, copy_source: &'a str) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: Some(copy_source),
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> SourceRangeSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_source_range(self, source_range: &'a Range) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: Some(source_range),
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> SourceContentMD5Support<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_source_content_md5(self, source_content_md5: &'a [u8]) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: Some(source_content_md5),
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> LeaseIdSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: Some(lease_id),
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> ClientRequestIdSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: Some(client_request_id),
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> ExtraHeadersOption<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> ExtraHeadersSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> ApiVersionOption<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> ApiVersionSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> TimeoutSupport
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: Some(timeout),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: self.copy_source_authorization,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> CopySourceAuthorizationOption<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    #[inline]
+    fn copy_source_authorization(&self) -> Option<&'a CopySourceAuthorization> {
+        self.copy_source_authorization
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet> CopySourceAuthorizationSupport<'a>
+    for PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+    type O = PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>;
+
+    #[inline]
+    fn with_copy_source_authorization(self, copy_source_authorization: &'a CopySourceAuthorization) -> Self::O {
+        PutBlockFromUrlBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_id: PhantomData {},
+            p_copy_source: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_id: self.block_id,
+            copy_source: self.copy_source,
+            source_range: self.source_range,
+            source_content_md5: self.source_content_md5,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            copy_source_authorization: Some(copy_source_authorization),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+    PutBlockFromUrlBuilder<'a, ContainerNameSet, BlobNameSet, BlockIdSet, CopySourceSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockIdSet: ToAssign,
+    CopySourceSet: ToAssign,
+{
+}
+
+impl<'a> PutBlockFromUrlBuilder<'a, Yes, Yes, Yes, Yes> {
+    /// Stages a block read by the service from the copy source URL (a
+    /// blob, with a SAS token if it is not public, or any public URL):
+    /// the data does not go through the client. Set a source range to
+    /// stage only a part of the source. The block is committed later
+    /// with put block list, like the blocks staged with put block.
+    pub fn finalize(self) -> impl Future<Item = PutBlockFromUrlResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, Some("comp=block"));
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+        uri = format!("{}&{}", uri, BlockIdRequired::to_uri_parameter(&self));
+
+        trace!("uri == {:?}", uri);
+
+        let api_version = ApiVersionOption::required_api_version(
+            &self,
+            self.client(),
+            CopySourceAuthorizationOption::required_api_version(&self).max(Some(PUT_BLOCK_FROM_URL_VERSION)),
+        );
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    CopySourceRequired::add_header(&self, request);
+                    CopySourceAuthorizationOption::add_header(&self, request);
+                    SourceRangeOption::add_header(&self, request);
+                    SourceContentMD5Option::add_header(&self, request);
+                    LeaseIdOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(&[]),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
+            .and_then(move |(headers, _body)| done(PutBlockFromUrlResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<PutBlockFromUrlResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
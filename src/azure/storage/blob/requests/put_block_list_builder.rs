@@ -1,22 +1,26 @@
+use crate::azure::core::customer_provided_key::CustomerProvidedKey;
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
 use crate::azure::core::{
-    add_content_md5_header, BlobNameRequired, BlobNameSupport, BlockListRequired, BlockListSupport, CacheControlOption,
-    CacheControlSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport,
-    ContentDispositionOption, ContentDispositionSupport, ContentEncodingOption, ContentEncodingSupport, ContentLanguageOption,
-    ContentLanguageSupport, ContentTypeOption, ContentTypeSupport, LeaseIdOption, LeaseIdSupport, MetadataOption, MetadataSupport, No,
-    TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    add_content_md5_header, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, BlockListRequired, BlockListSupport,
+    CPKOption, CPKSupport, CacheControlOption, CacheControlSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ContentDispositionOption, ContentDispositionSupport, ContentEncodingOption,
+    ContentEncodingSupport, ContentLanguageOption, ContentLanguageSupport, ContentTypeOption, ContentTypeSupport, ExtraHeadersOption,
+    ExtraHeadersSupport, IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption,
+    LeaseIdSupport, MetadataOption, MetadataSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
 };
 use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::PutBlockListResponse;
 use crate::azure::storage::blob::BlockList;
 use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
 use futures::future::{done, ok};
 use futures::prelude::*;
 use hyper::{Method, StatusCode};
 use md5;
 use std::borrow::Borrow;
-use std::collections::HashMap;
 use std::marker::PhantomData;
 
 #[derive(Debug, Clone)]
@@ -40,9 +44,14 @@ where
     content_language: Option<&'a str>,
     cache_control: Option<&'a str>,
     content_disposition: Option<&'a str>,
-    metadata: Option<&'a HashMap<&'a str, &'a str>>,
+    metadata: Option<&'a Metadata>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    customer_provided_key: Option<&'a CustomerProvidedKey>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a, T> PutBlockListBuilder<'a, T, No, No, No>
@@ -68,6 +77,11 @@ where
             metadata: None,
             lease_id: None,
             client_request_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            customer_provided_key: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -215,7 +229,7 @@ where
     T: Borrow<[u8]> + 'a,
 {
     #[inline]
-    fn metadata(&self) -> Option<&'a HashMap<&'a str, &'a str>> {
+    fn metadata(&self) -> Option<&'a Metadata> {
         self.metadata
     }
 }
@@ -277,6 +291,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -310,6 +329,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -343,6 +367,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -376,6 +405,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -409,6 +443,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -442,6 +481,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -475,6 +519,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -508,6 +557,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -541,6 +595,11 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -556,7 +615,7 @@ where
     type O = PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>;
 
     #[inline]
-    fn with_metadata(self, metadata: &'a HashMap<&'a str, &'a str>) -> Self::O {
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
         PutBlockListBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -574,6 +633,11 @@ where
             metadata: Some(metadata),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -607,6 +671,11 @@ where
             metadata: self.metadata,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -640,6 +709,248 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> ExtraHeadersOption<'a>
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> ExtraHeadersSupport<'a>
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    type O = PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> ApiVersionOption<'a>
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> ApiVersionSupport<'a>
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    type O = PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        PutBlockListBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_list: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_list: self.block_list,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> IfSinceConditionOption
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> IfMatchConditionOption<'a>
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> IfSinceConditionSupport
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    type O = PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        PutBlockListBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_list: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_list: self.block_list,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> IfMatchConditionSupport<'a>
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    type O = PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        PutBlockListBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_list: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_list: self.block_list,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            customer_provided_key: self.customer_provided_key,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> CPKOption<'a>
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    #[inline]
+    fn customer_provided_key(&self) -> Option<&'a CustomerProvidedKey> {
+        self.customer_provided_key
+    }
+}
+
+impl<'a, T, ContainerNameSet, BlobNameSet, BlockListSet> CPKSupport<'a>
+    for PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    T: Borrow<[u8]> + 'a,
+{
+    type O = PutBlockListBuilder<'a, T, ContainerNameSet, BlobNameSet, BlockListSet>;
+
+    #[inline]
+    fn with_customer_provided_key(self, customer_provided_key: &'a CustomerProvidedKey) -> Self::O {
+        PutBlockListBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_list: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_list: self.block_list,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            customer_provided_key: Some(customer_provided_key),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -680,26 +991,41 @@ where
             hash
         };
 
-        let req = self.client().perform_request(
-            &uri,
-            &Method::PUT,
-            |ref mut request| {
-                ContentTypeOption::add_header(&self, request);
-                ContentEncodingOption::add_header(&self, request);
-                ContentLanguageOption::add_header(&self, request);
-                add_content_md5_header(&md5[..], request);
-                CacheControlOption::add_header(&self, request);
-                ContentDispositionOption::add_header(&self, request);
-                MetadataOption::add_header(&self, request);
-                LeaseIdOption::add_header(&self, request);
-                ClientRequestIdOption::add_header(&self, request);
-            },
-            Some(body_bytes),
-        );
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), CPKOption::required_api_version(&self));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    ContentTypeOption::add_header(&self, request);
+                    ContentEncodingOption::add_header(&self, request);
+                    ContentLanguageOption::add_header(&self, request);
+                    add_content_md5_header(&md5[..], request);
+                    CacheControlOption::add_header(&self, request);
+                    ContentDispositionOption::add_header(&self, request);
+                    MetadataOption::add_header(&self, request);
+                    LeaseIdOption::add_header(&self, request);
+                    IfSinceConditionOption::add_header(&self, request);
+                    IfMatchConditionOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                    CPKOption::add_header(&self, request);
+                },
+                Some(body_bytes),
+            )
+        });
 
         done(req)
             .from_err()
             .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
             .and_then(move |(headers, _body)| done(PutBlockListResponse::from_headers(&headers)).and_then(ok))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<PutBlockListResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
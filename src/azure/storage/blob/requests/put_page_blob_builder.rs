@@ -1,12 +1,16 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::headers::BLOB_TYPE;
 use crate::azure::core::lease::LeaseId;
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition};
 use crate::azure::core::{
-    AccessTierOption, AccessTierSupport, BlobNameRequired, BlobNameSupport, CacheControlOption, CacheControlSupport, ClientRequestIdOption,
-    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ContentDispositionOption,
-    ContentDispositionSupport, ContentEncodingOption, ContentEncodingSupport, ContentLanguageOption, ContentLanguageSupport,
-    ContentTypeOption, ContentTypeSupport, LeaseIdOption, LeaseIdSupport, MetadataOption, MetadataSupport, No, PageBlobLengthRequired,
-    PageBlobLengthSupport, SequenceNumberOption, SequenceNumberSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    AccessTierOption, AccessTierSupport, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, CacheControlOption,
+    CacheControlSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport,
+    ContentDispositionOption, ContentDispositionSupport, ContentEncodingOption, ContentEncodingSupport, ContentLanguageOption,
+    ContentLanguageSupport, ContentTypeOption, ContentTypeSupport, ExtraHeadersOption, ExtraHeadersSupport, IfMatchConditionOption,
+    IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, MetadataOption,
+    MetadataSupport, No, PageBlobLengthRequired, PageBlobLengthSupport, SequenceNumberOption, SequenceNumberSupport, TimeoutOption,
+    TimeoutSupport, ToAssign, Yes,
 };
 use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::PutBlobResponse;
@@ -14,7 +18,6 @@ use crate::azure::storage::client::Client;
 use futures::future::{done, ok};
 use futures::prelude::*;
 use hyper::{Method, StatusCode};
-use std::collections::HashMap;
 use std::marker::PhantomData;
 
 #[derive(Debug, Clone)]
@@ -39,9 +42,13 @@ where
     content_language: Option<&'a str>,
     cache_control: Option<&'a str>,
     content_disposition: Option<&'a str>,
-    metadata: Option<&'a HashMap<&'a str, &'a str>>,
+    metadata: Option<&'a Metadata>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
+    if_since_condition: Option<IfSinceCondition>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> PutPageBlobBuilder<'a, No, No, No> {
@@ -66,6 +73,10 @@ impl<'a> PutPageBlobBuilder<'a, No, No, No> {
             metadata: None,
             lease_id: None,
             client_request_id: None,
+            if_since_condition: None,
+            if_match_condition: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -228,7 +239,7 @@ where
     ContentLengthSet: ToAssign,
 {
     #[inline]
-    fn metadata(&self) -> Option<&'a HashMap<&'a str, &'a str>> {
+    fn metadata(&self) -> Option<&'a Metadata> {
         self.metadata
     }
 }
@@ -289,6 +300,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -323,6 +338,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -361,6 +380,10 @@ where
                 metadata: self.metadata,
                 lease_id: self.lease_id,
                 client_request_id: self.client_request_id,
+                if_since_condition: self.if_since_condition,
+                if_match_condition: self.if_match_condition,
+                extra_headers: self.extra_headers,
+                api_version: self.api_version,
             })
         }
     }
@@ -396,6 +419,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -430,6 +457,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -464,6 +495,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -498,6 +533,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -532,6 +571,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -566,6 +609,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -600,6 +647,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -634,6 +685,10 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -648,7 +703,7 @@ where
     type O = PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>;
 
     #[inline]
-    fn with_metadata(self, metadata: &'a HashMap<&'a str, &'a str>) -> Self::O {
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
         PutPageBlobBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -668,6 +723,10 @@ where
             metadata: Some(metadata),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -702,6 +761,10 @@ where
             metadata: self.metadata,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -736,6 +799,190 @@ where
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ContentLengthSet> ExtraHeadersOption<'a>
+    for PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ContentLengthSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ContentLengthSet> ExtraHeadersSupport<'a>
+    for PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ContentLengthSet: ToAssign,
+{
+    type O = PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ContentLengthSet> ApiVersionOption<'a>
+    for PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ContentLengthSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ContentLengthSet> ApiVersionSupport<'a>
+    for PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ContentLengthSet: ToAssign,
+{
+    type O = PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        PutPageBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_content_length: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            content_length: self.content_length,
+            sequence_number: self.sequence_number,
+            access_tier: self.access_tier,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ContentLengthSet> IfSinceConditionOption
+    for PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ContentLengthSet: ToAssign,
+{
+    #[inline]
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ContentLengthSet> IfMatchConditionOption<'a>
+    for PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ContentLengthSet: ToAssign,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ContentLengthSet> IfSinceConditionSupport
+    for PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ContentLengthSet: ToAssign,
+{
+    type O = PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>;
+
+    #[inline]
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        PutPageBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_content_length: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            content_length: self.content_length,
+            sequence_number: self.sequence_number,
+            access_tier: self.access_tier,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: Some(if_since_condition),
+            if_match_condition: self.if_match_condition,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ContentLengthSet> IfMatchConditionSupport<'a>
+    for PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ContentLengthSet: ToAssign,
+{
+    type O = PutPageBlobBuilder<'a, ContainerNameSet, BlobNameSet, ContentLengthSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        PutPageBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_content_length: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            content_length: self.content_length,
+            sequence_number: self.sequence_number,
+            access_tier: self.access_tier,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: Some(if_match_condition),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -775,7 +1022,11 @@ impl<'a> PutPageBlobBuilder<'a, Yes, Yes, Yes> {
                 MetadataOption::add_header(&self, request);
                 request.header(BLOB_TYPE, "PageBlob");
                 LeaseIdOption::add_header(&self, request);
+                IfSinceConditionOption::add_header(&self, request);
+                IfMatchConditionOption::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -785,4 +1036,9 @@ impl<'a> PutPageBlobBuilder<'a, Yes, Yes, Yes> {
             .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
             .and_then(move |(headers, _body)| done(PutBlobResponse::from_headers(&headers)).and_then(ok))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<PutBlobResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
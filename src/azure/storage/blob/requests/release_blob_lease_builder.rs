@@ -2,8 +2,9 @@ use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureErr
 use crate::azure::core::headers::LEASE_ACTION;
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
-    ContainerNameSupport, LeaseIdRequired, LeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdRequired, LeaseIdSupport, TimeoutOption,
+    TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::blob::generate_blob_uri;
@@ -29,6 +30,8 @@ where
     lease_id: Option<&'a LeaseId>,
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> ReleaseBlobLeaseBuilder<'a, No, No, No> {
@@ -44,6 +47,8 @@ impl<'a> ReleaseBlobLeaseBuilder<'a, No, No, No> {
             lease_id: None,
             timeout: None,
             client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -140,6 +145,8 @@ where
             lease_id: self.lease_id,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -165,6 +172,8 @@ where
             lease_id: self.lease_id,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -190,6 +199,8 @@ where
             lease_id: Some(lease_id),
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -215,6 +226,8 @@ where
             lease_id: self.lease_id,
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -240,6 +253,75 @@ where
             lease_id: self.lease_id,
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseIdSet> ExtraHeadersOption<'a>
+    for ReleaseBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseIdSet> ExtraHeadersSupport<'a>
+    for ReleaseBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+{
+    type O = ReleaseBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseIdSet> ApiVersionOption<'a>
+    for ReleaseBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LeaseIdSet> ApiVersionSupport<'a>
+    for ReleaseBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+{
+    type O = ReleaseBlobLeaseBuilder<'a, ContainerNameSet, BlobNameSet, LeaseIdSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ReleaseBlobLeaseBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_lease_id: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -268,6 +350,8 @@ impl<'a> ReleaseBlobLeaseBuilder<'a, Yes, Yes, Yes> {
                 LeaseIdRequired::add_header(&self, request);
                 request.header(LEASE_ACTION, "release");
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             None,
         );
@@ -277,4 +361,9 @@ impl<'a> ReleaseBlobLeaseBuilder<'a, Yes, Yes, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
             .and_then(|(headers, _body)| done(ReleaseBlobLeaseResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<ReleaseBlobLeaseResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
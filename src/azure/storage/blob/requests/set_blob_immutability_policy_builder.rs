@@ -0,0 +1,582 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::modify_conditions::IfSinceCondition;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, SnapshotOption, SnapshotSupport, TimeoutOption,
+    TimeoutSupport, VersionIdOption, VersionIdSupport,
+};
+use crate::azure::core::{IfSinceConditionOption, IfSinceConditionSupport};
+use crate::azure::core::{No, ToAssign, Yes};
+use crate::azure::storage::blob::responses::SetBlobImmutabilityPolicyResponse;
+use crate::azure::storage::blob::{
+    generate_blob_uri, ImmutabilityPolicyMode, ImmutabilityPolicyModeOption, ImmutabilityPolicyModeSupport,
+    ImmutabilityPolicyUntilDateRequired, ImmutabilityPolicyUntilDateSupport, IMMUTABILITY_VERSION,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use chrono::{DateTime, Utc};
+use futures::future::{done, Future};
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+#[derive(Debug, Clone)]
+pub struct SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    p_immutability_policy_until_date: PhantomData<ImmutabilityPolicyUntilDateSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    immutability_policy_until_date: Option<DateTime<Utc>>,
+    immutability_policy_mode: Option<ImmutabilityPolicyMode>,
+    if_since_condition: Option<IfSinceCondition>,
+    snapshot: Option<DateTime<Utc>>,
+    version_id: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> SetBlobImmutabilityPolicyBuilder<'a, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> SetBlobImmutabilityPolicyBuilder<'a, No, No, No> {
+        SetBlobImmutabilityPolicyBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            p_immutability_policy_until_date: PhantomData {},
+            immutability_policy_until_date: None,
+            immutability_policy_mode: None,
+            if_since_condition: None,
+            snapshot: None,
+            version_id: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ClientRequired<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet, ImmutabilityPolicyUntilDateSet> ContainerNameRequired<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, Yes, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, ImmutabilityPolicyUntilDateSet> BlobNameRequired<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, Yes, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ImmutabilityPolicyUntilDateRequired
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn immutability_policy_until_date(&self) -> DateTime<Utc> {
+        self.immutability_policy_until_date.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ImmutabilityPolicyModeOption
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn immutability_policy_mode(&self) -> Option<ImmutabilityPolicyMode> {
+        self.immutability_policy_mode
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> IfSinceConditionOption
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn if_since_condition(&self) -> Option<IfSinceCondition> {
+        self.if_since_condition.clone()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> SnapshotOption
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> VersionIdOption<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn version_id(&self) -> Option<&'a str> {
+        self.version_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> TimeoutOption
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ClientRequestIdOption<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ExtraHeadersOption<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ApiVersionOption<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ContainerNameSupport<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, Yes, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: self.if_since_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> BlobNameSupport<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, Yes, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: self.if_since_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ImmutabilityPolicyUntilDateSupport
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
+
+    fn with_immutability_policy_until_date(self, immutability_policy_until_date: DateTime<Utc>) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            immutability_policy_until_date: Some(immutability_policy_until_date),
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: self.if_since_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ImmutabilityPolicyModeSupport
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_immutability_policy_mode(self, immutability_policy_mode: ImmutabilityPolicyMode) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: Some(immutability_policy_mode),
+            if_since_condition: self.if_since_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> IfSinceConditionSupport
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_if_since_condition(self, if_since_condition: IfSinceCondition) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: Some(if_since_condition),
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> SnapshotSupport
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: self.if_since_condition,
+            snapshot: Some(snapshot),
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> VersionIdSupport<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_version_id(self, version_id: &'a str) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: self.if_since_condition,
+            snapshot: self.snapshot,
+            version_id: Some(version_id),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> TimeoutSupport
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: self.if_since_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ClientRequestIdSupport<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: self.if_since_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ExtraHeadersSupport<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet> ApiVersionSupport<'a>
+    for SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+    type O = SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SetBlobImmutabilityPolicyBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_immutability_policy_until_date: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            immutability_policy_until_date: self.immutability_policy_until_date,
+            immutability_policy_mode: self.immutability_policy_mode,
+            if_since_condition: self.if_since_condition,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+    SetBlobImmutabilityPolicyBuilder<'a, ContainerNameSet, BlobNameSet, ImmutabilityPolicyUntilDateSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    ImmutabilityPolicyUntilDateSet: ToAssign,
+{
+}
+
+impl<'a> SetBlobImmutabilityPolicyBuilder<'a, Yes, Yes, Yes> {
+    /// Sets (or extends) the immutability policy of the blob: it cannot be
+    /// modified nor deleted until `immutability_policy_until_date`. The
+    /// container must have version-level immutability support enabled.
+    /// Only `IfSinceCondition::Unmodified` is accepted by the service.
+    pub fn finalize(self) -> impl Future<Item = SetBlobImmutabilityPolicyResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, Some("comp=immutabilityPolicies"));
+
+        if let Some(snapshot) = SnapshotOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, snapshot);
+        }
+        if let Some(version_id) = VersionIdOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, version_id);
+        }
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(IMMUTABILITY_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    ImmutabilityPolicyUntilDateRequired::add_header(&self, request);
+                    ImmutabilityPolicyModeOption::add_header(&self, request);
+                    IfSinceConditionOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(&[]),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
+            .and_then(|(headers, _body)| done(SetBlobImmutabilityPolicyResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<SetBlobImmutabilityPolicyResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,465 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, SnapshotOption, SnapshotSupport, TimeoutOption,
+    TimeoutSupport, VersionIdOption, VersionIdSupport,
+};
+use crate::azure::core::{No, ToAssign, Yes};
+use crate::azure::storage::blob::responses::SetBlobLegalHoldResponse;
+use crate::azure::storage::blob::{generate_blob_uri, LegalHoldRequired, LegalHoldSupport, IMMUTABILITY_VERSION};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use chrono::{DateTime, Utc};
+use futures::future::{done, Future};
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+#[derive(Debug, Clone)]
+pub struct SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    p_legal_hold: PhantomData<LegalHoldSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    legal_hold: Option<bool>,
+    snapshot: Option<DateTime<Utc>>,
+    version_id: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> SetBlobLegalHoldBuilder<'a, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> SetBlobLegalHoldBuilder<'a, No, No, No> {
+        SetBlobLegalHoldBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            p_legal_hold: PhantomData {},
+            legal_hold: None,
+            snapshot: None,
+            version_id: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> ClientRequired<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet, LegalHoldSet> ContainerNameRequired<'a> for SetBlobLegalHoldBuilder<'a, Yes, BlobNameSet, LegalHoldSet>
+where
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, LegalHoldSet> BlobNameRequired<'a> for SetBlobLegalHoldBuilder<'a, ContainerNameSet, Yes, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> LegalHoldRequired for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn legal_hold(&self) -> bool {
+        self.legal_hold.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> SnapshotOption
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> VersionIdOption<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn version_id(&self) -> Option<&'a str> {
+        self.version_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> TimeoutOption
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> ClientRequestIdOption<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> ExtraHeadersOption<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> ApiVersionOption<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> ContainerNameSupport<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, Yes, BlobNameSet, LegalHoldSet>;
+
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        SetBlobLegalHoldBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_legal_hold: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            legal_hold: self.legal_hold,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> BlobNameSupport<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, ContainerNameSet, Yes, LegalHoldSet>;
+
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        SetBlobLegalHoldBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_legal_hold: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            legal_hold: self.legal_hold,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> LegalHoldSupport
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
+
+    fn with_legal_hold(self, legal_hold: bool) -> Self::O {
+        SetBlobLegalHoldBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_legal_hold: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            legal_hold: Some(legal_hold),
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> SnapshotSupport
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>;
+
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        SetBlobLegalHoldBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_legal_hold: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            legal_hold: self.legal_hold,
+            snapshot: Some(snapshot),
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> VersionIdSupport<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>;
+
+    fn with_version_id(self, version_id: &'a str) -> Self::O {
+        SetBlobLegalHoldBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_legal_hold: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            legal_hold: self.legal_hold,
+            snapshot: self.snapshot,
+            version_id: Some(version_id),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> TimeoutSupport
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        SetBlobLegalHoldBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_legal_hold: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            legal_hold: self.legal_hold,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> ClientRequestIdSupport<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        SetBlobLegalHoldBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_legal_hold: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            legal_hold: self.legal_hold,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> ExtraHeadersSupport<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> ApiVersionSupport<'a>
+    for SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+    type O = SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SetBlobLegalHoldBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_legal_hold: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            legal_hold: self.legal_hold,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet, LegalHoldSet> SetBlobLegalHoldBuilder<'a, ContainerNameSet, BlobNameSet, LegalHoldSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    LegalHoldSet: ToAssign,
+{
+}
+
+impl<'a> SetBlobLegalHoldBuilder<'a, Yes, Yes, Yes> {
+    /// Sets or clears the legal hold of the blob. A blob under legal hold
+    /// cannot be modified nor deleted, whatever its immutability policy.
+    pub fn finalize(self) -> impl Future<Item = SetBlobLegalHoldResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, Some("comp=legalhold"));
+
+        if let Some(snapshot) = SnapshotOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, snapshot);
+        }
+        if let Some(version_id) = VersionIdOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, version_id);
+        }
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(IMMUTABILITY_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    LegalHoldRequired::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(&[]),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
+            .and_then(|(headers, _body)| done(SetBlobLegalHoldResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<SetBlobLegalHoldResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,452 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, TimeoutOption,
+    TimeoutSupport, VersionIdOption, VersionIdSupport,
+};
+use crate::azure::core::{No, ToAssign, Yes};
+use crate::azure::storage::blob::responses::SetBlobTagsResponse;
+use crate::azure::storage::blob::{generate_blob_uri, Tags, TagsRequired, TagsSupport, TAGS_VERSION};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use futures::future::{done, Future};
+use hyper::header::CONTENT_TYPE;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+#[derive(Debug, Clone)]
+pub struct SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    p_tags: PhantomData<TagsSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    tags: Option<&'a Tags>,
+    version_id: Option<&'a str>,
+    lease_id: Option<&'a LeaseId>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> SetBlobTagsBuilder<'a, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> SetBlobTagsBuilder<'a, No, No, No> {
+        SetBlobTagsBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            p_tags: PhantomData {},
+            tags: None,
+            version_id: None,
+            lease_id: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> ClientRequired<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet, TagsSet> ContainerNameRequired<'a> for SetBlobTagsBuilder<'a, Yes, BlobNameSet, TagsSet>
+where
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, TagsSet> BlobNameRequired<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, Yes, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TagsRequired<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn tags(&self) -> &'a Tags {
+        self.tags.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> VersionIdOption<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn version_id(&self) -> Option<&'a str> {
+        self.version_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> LeaseIdOption<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> TimeoutOption for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> ClientRequestIdOption<'a>
+    for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> ExtraHeadersOption<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> ApiVersionOption<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> ContainerNameSupport<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, Yes, BlobNameSet, TagsSet>;
+
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        SetBlobTagsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_tags: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            tags: self.tags,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> BlobNameSupport<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, ContainerNameSet, Yes, TagsSet>;
+
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        SetBlobTagsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_tags: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            tags: self.tags,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> TagsSupport<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
+
+    fn with_tags(self, tags: &'a Tags) -> Self::O {
+        SetBlobTagsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_tags: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            tags: Some(tags),
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> VersionIdSupport<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>;
+
+    fn with_version_id(self, version_id: &'a str) -> Self::O {
+        SetBlobTagsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_tags: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            tags: self.tags,
+            version_id: Some(version_id),
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> LeaseIdSupport<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>;
+
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        SetBlobTagsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_tags: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            tags: self.tags,
+            version_id: self.version_id,
+            lease_id: Some(lease_id),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> TimeoutSupport for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        SetBlobTagsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_tags: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            tags: self.tags,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> ClientRequestIdSupport<'a>
+    for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        SetBlobTagsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_tags: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            tags: self.tags,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> ExtraHeadersSupport<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> ApiVersionSupport<'a> for SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+    type O = SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SetBlobTagsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_tags: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            tags: self.tags,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet, TagsSet> SetBlobTagsBuilder<'a, ContainerNameSet, BlobNameSet, TagsSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    TagsSet: ToAssign,
+{
+}
+
+impl<'a> SetBlobTagsBuilder<'a, Yes, Yes, Yes> {
+    /// Replaces all the index tags of the blob (or of one of its versions)
+    /// with `tags`. An empty `Tags` removes them.
+    pub fn finalize(self) -> impl Future<Item = SetBlobTagsResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, Some("comp=tags"));
+
+        if let Some(version_id) = VersionIdOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, version_id);
+        }
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let xml = self.tags().to_xml();
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(TAGS_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    request.header(CONTENT_TYPE, "application/xml");
+                    LeaseIdOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(xml.as_bytes()),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::NO_CONTENT))
+            .and_then(|(headers, _body)| done(SetBlobTagsResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<SetBlobTagsResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,525 @@
+use crate::azure::core::errors::{extract_status_headers_and_body, AzureError, UnexpectedHTTPResult};
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::{
+    AccessTierRequired, AccessTierSupport, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport,
+    LeaseIdOption, LeaseIdSupport, SnapshotOption, SnapshotSupport, TimeoutOption, TimeoutSupport, VersionIdOption, VersionIdSupport,
+};
+use crate::azure::core::{No, ToAssign, Yes};
+use crate::azure::storage::blob::responses::SetBlobTierResponse;
+use crate::azure::storage::blob::{generate_blob_uri, VERSIONING_VERSION};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use chrono::{DateTime, Utc};
+use futures::future::{done, Future};
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+use std::str;
+#[derive(Debug, Clone)]
+pub struct SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    p_access_tier: PhantomData<AccessTierSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    access_tier: Option<&'a str>,
+    snapshot: Option<DateTime<Utc>>,
+    version_id: Option<&'a str>,
+    lease_id: Option<&'a LeaseId>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> SetBlobTierBuilder<'a, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> SetBlobTierBuilder<'a, No, No, No> {
+        SetBlobTierBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            p_access_tier: PhantomData {},
+            access_tier: None,
+            snapshot: None,
+            version_id: None,
+            lease_id: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> ClientRequired<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet, AccessTierSet> ContainerNameRequired<'a> for SetBlobTierBuilder<'a, Yes, BlobNameSet, AccessTierSet>
+where
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, AccessTierSet> BlobNameRequired<'a> for SetBlobTierBuilder<'a, ContainerNameSet, Yes, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> AccessTierRequired<'a> for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn access_tier(&self) -> &'a str {
+        self.access_tier.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> SnapshotOption
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> VersionIdOption<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn version_id(&self) -> Option<&'a str> {
+        self.version_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> LeaseIdOption<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> TimeoutOption
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> ClientRequestIdOption<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> ExtraHeadersOption<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> ApiVersionOption<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> ContainerNameSupport<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, Yes, BlobNameSet, AccessTierSet>;
+
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            access_tier: self.access_tier,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> BlobNameSupport<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, Yes, AccessTierSet>;
+
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            access_tier: self.access_tier,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> AccessTierSupport<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, Yes>;
+
+    fn with_access_tier(self, access_tier: &'a str) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            access_tier: Some(access_tier),
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> SnapshotSupport
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>;
+
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            access_tier: self.access_tier,
+            snapshot: Some(snapshot),
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> VersionIdSupport<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>;
+
+    fn with_version_id(self, version_id: &'a str) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            access_tier: self.access_tier,
+            snapshot: self.snapshot,
+            version_id: Some(version_id),
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> LeaseIdSupport<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>;
+
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            access_tier: self.access_tier,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: Some(lease_id),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> TimeoutSupport
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            access_tier: self.access_tier,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> ClientRequestIdSupport<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            access_tier: self.access_tier,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> ExtraHeadersSupport<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> ApiVersionSupport<'a>
+    for SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+    type O = SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SetBlobTierBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_access_tier: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            access_tier: self.access_tier,
+            snapshot: self.snapshot,
+            version_id: self.version_id,
+            lease_id: self.lease_id,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet, AccessTierSet> SetBlobTierBuilder<'a, ContainerNameSet, BlobNameSet, AccessTierSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    AccessTierSet: ToAssign,
+{
+}
+
+impl<'a> SetBlobTierBuilder<'a, Yes, Yes, Yes> {
+    /// Sets the access tier of a block blob, or of one of its snapshots or
+    /// versions. Moving a blob out of the archive tier is not immediate:
+    /// the service answers 202 and `SetBlobTierResponse::rehydrating` is set.
+    pub fn finalize(self) -> impl Future<Item = SetBlobTierResponse, Error = AzureError> {
+        let mut uri = generate_blob_uri(&self, Some("comp=tier"));
+
+        if let Some(snapshot) = SnapshotOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, snapshot);
+        }
+        if let Some(version_id) = VersionIdOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, version_id);
+        }
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), self.version_id().map(|_| VERSIONING_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    AccessTierRequired::add_header(&self, request);
+                    LeaseIdOption::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(&[]),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(extract_status_headers_and_body)
+            .and_then(|(status, headers, body)| match status {
+                StatusCode::OK | StatusCode::ACCEPTED => SetBlobTierResponse::from_headers(&headers, status == StatusCode::ACCEPTED),
+                _ => Err(AzureError::UnexpectedHTTPResult(
+                    UnexpectedHTTPResult::new(StatusCode::OK, status, str::from_utf8(&body)?).with_headers(&headers),
+                )),
+            })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<SetBlobTierResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
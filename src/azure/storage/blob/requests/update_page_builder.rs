@@ -1,13 +1,17 @@
 use crate::azure::core::ba512_range::BA512Range;
+use crate::azure::core::checksum::{Checksum, ChecksumMode};
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::headers::PAGE_WRITE;
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::modify_conditions::{IfMatchCondition, IfSinceCondition, SequenceNumberCondition};
+use crate::azure::core::progress::Progress;
 use crate::azure::core::{
-    BA512RangeRequired, BA512RangeSupport, BlobNameRequired, BlobNameSupport, BodyRequired, BodySupport, ClientRequestIdOption,
-    ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ContentMD5Option, ContentMD5Support,
+    ApiVersionOption, ApiVersionSupport, BA512RangeRequired, BA512RangeSupport, BlobNameRequired, BlobNameSupport, BodyRequired,
+    BodySupport, ChecksumModeOption, ChecksumModeSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContainerNameRequired, ContainerNameSupport, ContentMD5Option, ContentMD5Support, ExtraHeadersOption, ExtraHeadersSupport,
     IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport, LeaseIdOption, LeaseIdSupport, No,
-    SequenceNumberConditionOption, SequenceNumberConditionSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+    ProgressOption, ProgressSupport, SequenceNumberConditionOption, SequenceNumberConditionSupport, TimeoutOption, TimeoutSupport,
+    ToAssign, Yes,
 };
 use crate::azure::storage::blob::generate_blob_uri;
 use crate::azure::storage::blob::responses::UpdatePageResponse;
@@ -41,6 +45,10 @@ where
     if_match_condition: Option<IfMatchCondition<'a>>,
     client_request_id: Option<&'a str>,
     timeout: Option<u64>,
+    checksum_mode: Option<ChecksumMode>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+    progress: Option<&'a Progress>,
 }
 
 impl<'a> UpdatePageBuilder<'a, No, No, No, No> {
@@ -63,6 +71,10 @@ impl<'a> UpdatePageBuilder<'a, No, No, No, No> {
             if_match_condition: None,
             client_request_id: None,
             timeout: None,
+            checksum_mode: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+            progress: None,
         }
     }
 }
@@ -259,6 +271,10 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -292,6 +308,10 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -325,6 +345,10 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -358,6 +382,10 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -391,6 +419,10 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -424,6 +456,10 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -457,6 +493,10 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -490,6 +530,10 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -523,6 +567,10 @@ where
             if_match_condition: Some(if_match_condition),
             client_request_id: self.client_request_id,
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -556,6 +604,141 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: Some(client_request_id),
             timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet> ExtraHeadersOption<'a>
+    for UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet> ExtraHeadersSupport<'a>
+    for UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet> ApiVersionOption<'a>
+    for UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet> ApiVersionSupport<'a>
+    for UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        UpdatePageBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_ba512_range: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            ba512_range: self.ba512_range,
+            body: self.body,
+            content_md5: self.content_md5,
+            lease_id: self.lease_id,
+            sequence_number_condition: self.sequence_number_condition,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet> ProgressOption<'a>
+    for UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn progress(&self) -> Option<&'a Progress> {
+        self.progress
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet> ProgressSupport<'a>
+    for UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>;
+
+    #[inline]
+    fn with_progress(self, progress: &'a Progress) -> Self::O {
+        UpdatePageBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_ba512_range: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            ba512_range: self.ba512_range,
+            body: self.body,
+            content_md5: self.content_md5,
+            lease_id: self.lease_id,
+            sequence_number_condition: self.sequence_number_condition,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: Some(progress),
         }
     }
 }
@@ -589,6 +772,61 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             timeout: Some(timeout),
+            checksum_mode: self.checksum_mode,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet> ChecksumModeOption
+    for UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    #[inline]
+    fn checksum_mode(&self) -> Option<ChecksumMode> {
+        self.checksum_mode
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet> ChecksumModeSupport
+    for UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BA512RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = UpdatePageBuilder<'a, ContainerNameSet, BlobNameSet, BA512RangeSet, BodySet>;
+
+    #[inline]
+    fn with_checksum_mode(self, checksum_mode: ChecksumMode) -> Self::O {
+        UpdatePageBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_ba512_range: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            ba512_range: self.ba512_range,
+            body: self.body,
+            content_md5: self.content_md5,
+            lease_id: self.lease_id,
+            sequence_number_condition: self.sequence_number_condition,
+            if_since_condition: self.if_since_condition,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            checksum_mode: Some(checksum_mode),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            progress: self.progress,
         }
     }
 }
@@ -616,6 +854,9 @@ impl<'a> UpdatePageBuilder<'a, Yes, Yes, Yes, Yes> {
         let upper = self.ba512_range().size() as usize;
         trace!("upper == {}", upper);
         let b = &self.body()[0..upper];
+        let checksum = ChecksumModeOption::checksum_mode(&self).map(|mode| Checksum::compute(mode, b));
+        let progress = self.progress().cloned();
+        let length = b.len() as u64;
 
         let req = self.client().perform_request(
             &uri,
@@ -623,12 +864,17 @@ impl<'a> UpdatePageBuilder<'a, Yes, Yes, Yes, Yes> {
             |ref mut request| {
                 BA512RangeRequired::add_header(&self, request);
                 ContentMD5Option::add_header(&self, request);
+                if let Some(checksum) = &checksum {
+                    checksum.add_header(request);
+                }
                 request.header(PAGE_WRITE, "update");
                 LeaseIdOption::add_header(&self, request);
                 SequenceNumberConditionOption::add_header(&self, request);
                 IfSinceConditionOption::add_header(&self, request);
                 IfMatchConditionOption::add_header(&self, request);
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
             },
             Some(b),
         );
@@ -636,6 +882,19 @@ impl<'a> UpdatePageBuilder<'a, Yes, Yes, Yes, Yes> {
         done(req)
             .from_err()
             .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
-            .and_then(move |(headers, _body)| done(UpdatePageResponse::from_headers(&headers)))
+            .and_then(move |(headers, _body)| {
+                if let Some(checksum) = checksum {
+                    checksum.validate(&headers)?;
+                }
+                if let Some(progress) = progress {
+                    progress.report(length, Some(length));
+                }
+                UpdatePageResponse::from_headers(&headers)
+            })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<UpdatePageResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
     }
 }
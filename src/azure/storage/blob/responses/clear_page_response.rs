@@ -7,5 +7,6 @@ response_from_headers!(ClearPageResponse,
 		       last_modified_from_headers -> last_modified: DateTime<Utc>,
 		       sequence_number_from_headers -> sequence_number: u64,
 		       request_id_from_headers -> request_id: RequestId,
+		       client_request_id_from_headers_optional -> client_request_id: Option<String>,
 		       date_from_headers -> date: DateTime<Utc>
 );
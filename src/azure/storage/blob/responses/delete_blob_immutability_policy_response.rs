@@ -0,0 +1,8 @@
+use crate::azure::core::RequestId;
+use chrono::{DateTime, Utc};
+
+response_from_headers!(DeleteBlobImmutabilityPolicyResponse ,
+		       request_id_from_headers -> request_id: RequestId,
+		       client_request_id_from_headers_optional -> client_request_id: Option<String>,
+		       date_from_headers -> date: DateTime<Utc>
+);
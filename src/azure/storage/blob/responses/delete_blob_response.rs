@@ -5,5 +5,6 @@ response_from_headers!(DeleteBlobResponse ,
 		       
                        delete_type_permanent_from_headers -> delete_type_permanent: bool,
 		       request_id_from_headers -> request_id: RequestId,
+		       client_request_id_from_headers_optional -> client_request_id: Option<String>,
 		       date_from_headers -> date: DateTime<Utc>
 );
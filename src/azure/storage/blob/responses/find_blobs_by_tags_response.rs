@@ -0,0 +1,32 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::incompletevector::IncompleteVector;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::storage::blob::{filtered_blobs_from_response, FilteredBlob};
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone)]
+pub struct FindBlobsByTagsResponse {
+    pub incomplete_vector: IncompleteVector<FilteredBlob>,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl FindBlobsByTagsResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &str) -> Result<FindBlobsByTagsResponse, AzureError> {
+        Ok(FindBlobsByTagsResponse {
+            incomplete_vector: filtered_blobs_from_response(body)?,
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            date: date_from_headers(headers)?,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.incomplete_vector.is_complete()
+    }
+}
@@ -0,0 +1,31 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::storage::blob::Blob;
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone)]
+pub struct GetBlobPropertiesResponse {
+    pub blob: Blob,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl GetBlobPropertiesResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, blob: Blob) -> Result<GetBlobPropertiesResponse, AzureError> {
+        let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
+        let date = date_from_headers(headers)?;
+
+        Ok(GetBlobPropertiesResponse {
+            blob,
+            request_id,
+            client_request_id,
+            date,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
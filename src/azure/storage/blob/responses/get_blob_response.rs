@@ -1,27 +1,142 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
-use crate::azure::core::{date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::core::range::ContentRange;
+use crate::azure::core::util::HeaderMapExt;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
 use crate::azure::storage::blob::Blob;
 use chrono::{DateTime, Utc};
+use http::header::CONTENT_RANGE;
 use http::HeaderMap;
+use serde::de::DeserializeOwned;
 
 #[derive(Debug, Clone)]
 pub struct GetBlobResponse {
+    /// The blob properties. On a ranged read `content_length` and
+    /// `content_md5` are the ones of the range returned: the length of
+    /// the whole blob is in `content_range`.
     pub blob: Blob,
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub data: Vec<u8>,
     pub date: DateTime<Utc>,
+    /// Only returned for a ranged read (a `206 Partial Content`).
+    pub content_range: Option<ContentRange>,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl GetBlobResponse {
     pub(crate) fn from_response(headers: &HeaderMap, blob: Blob, body: &[u8]) -> Result<GetBlobResponse, AzureError> {
         let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
         let date = date_from_headers(headers)?;
+        let content_range = match headers.get_as_str(CONTENT_RANGE) {
+            Some(content_range) => Some(content_range.parse::<ContentRange>()?),
+            None => None,
+        };
 
         Ok(GetBlobResponse {
             blob,
             request_id,
+            client_request_id,
             data: body.to_vec(),
             date,
+            content_range,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
+
+    /// Decodes the blob contents using the charset specified in its
+    /// Content-Type (UTF-8 if missing). Supported charsets are UTF-8,
+    /// US-ASCII, ISO-8859-1 and UTF-16.
+    pub fn text(&self) -> Result<String, AzureError> {
+        decode_text(&self.data, self.blob.content_type.as_ref().map(|s| s as &str))
+    }
+
+    pub fn json<T>(&self) -> Result<T, AzureError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(serde_json::from_str(&self.text()?)?)
+    }
+}
+
+fn charset(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let mut tokens = param.splitn(2, '=');
+        let name = tokens.next()?.trim();
+        if name.eq_ignore_ascii_case("charset") {
+            Some(tokens.next()?.trim().trim_matches('"').to_ascii_lowercase())
+        } else {
+            None
+        }
+    })
+}
+
+fn decode_text(data: &[u8], content_type: Option<&str>) -> Result<String, AzureError> {
+    let charset = content_type.and_then(charset);
+
+    match charset.as_ref().map(|s| s as &str) {
+        None | Some("utf-8") | Some("utf8") | Some("us-ascii") | Some("ascii") => {
+            let data = if data.starts_with(b"\xEF\xBB\xBF") { &data[3..] } else { data };
+            Ok(String::from_utf8(data.to_vec())?)
+        }
+        Some("iso-8859-1") | Some("latin1") | Some("latin-1") => Ok(data.iter().map(|b| char::from(*b)).collect()),
+        Some("utf-16") | Some("utf-16le") | Some("utf-16be") => {
+            if data.len() & 1 == 1 {
+                return Err(AzureError::GenericErrorWithText("odd number of bytes in UTF-16 text".to_owned()));
+            }
+
+            // the BOM, if present, wins over the declared endianness
+            let (little_endian, data) = match (data.first(), data.get(1)) {
+                (Some(0xFF), Some(0xFE)) => (true, &data[2..]),
+                (Some(0xFE), Some(0xFF)) => (false, &data[2..]),
+                _ => (charset.as_ref().map(|s| s as &str) == Some("utf-16le"), data),
+            };
+
+            let units: Vec<u16> = data
+                .chunks(2)
+                .map(|c| {
+                    if little_endian {
+                        u16::from_le_bytes([c[0], c[1]])
+                    } else {
+                        u16::from_be_bytes([c[0], c[1]])
+                    }
+                })
+                .collect();
+            String::from_utf16(&units).map_err(|err| AzureError::GenericErrorWithText(err.to_string()))
+        }
+        Some(other) => Err(AzureError::GenericErrorWithText(format!("unsupported charset {}", other))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_charset() {
+        assert_eq!(charset("text/plain"), None);
+        assert_eq!(charset("text/plain; charset=UTF-8"), Some("utf-8".to_owned()));
+        assert_eq!(
+            charset("text/plain;format=flowed; Charset=\"ISO-8859-1\""),
+            Some("iso-8859-1".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(decode_text(b"\xEF\xBB\xBFciao", None).unwrap(), "ciao");
+        assert_eq!(
+            decode_text("perché".as_bytes(), Some("text/plain; charset=utf-8")).unwrap(),
+            "perché"
+        );
+        assert_eq!(decode_text(b"perch\xE9", Some("text/plain; charset=iso-8859-1")).unwrap(), "perché");
+        assert_eq!(
+            decode_text(b"\xFF\xFEh\x00i\x00", Some("text/plain; charset=utf-16")).unwrap(),
+            "hi"
+        );
+        assert_eq!(decode_text(b"\x00h\x00i", Some("text/plain; charset=utf-16be")).unwrap(), "hi");
+        assert!(decode_text(b"\xFF", None).is_err());
+        assert!(decode_text(b"abc", Some("text/plain; charset=koi8-r")).is_err());
+    }
 }
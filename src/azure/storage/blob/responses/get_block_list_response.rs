@@ -1,6 +1,10 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::BLOB_CONTENT_LENGTH;
+use crate::azure::core::util::HeaderMapExt;
 use crate::azure::core::{
-    date_from_headers, etag_from_headers_optional, last_modified_from_headers_optional, request_id_from_headers, RequestId,
+    client_request_id_from_headers_optional, date_from_headers, etag_from_headers_optional, last_modified_from_headers_optional,
+    request_id_from_headers, RequestId,
 };
 use crate::azure::storage::blob::BlockWithSizeList;
 use chrono::{DateTime, Utc};
@@ -11,27 +15,35 @@ use std::str::from_utf8;
 pub struct GetBlockListResponse {
     pub etag: Option<String>,
     pub last_modified: Option<DateTime<Utc>>,
+    pub blob_content_length: Option<u64>,
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub date: DateTime<Utc>,
     pub block_with_size_list: BlockWithSizeList<Vec<u8>>,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl GetBlockListResponse {
     pub(crate) fn from_response(headers: &HeaderMap, body: &[u8]) -> Result<GetBlockListResponse, AzureError> {
         let etag = etag_from_headers_optional(headers)?;
         let last_modified = last_modified_from_headers_optional(headers)?;
+        let blob_content_length = headers.get_as_u64(BLOB_CONTENT_LENGTH);
         let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
         let date = date_from_headers(headers)?;
 
         let body = from_utf8(body)?;
-        let block_with_size_list = BlockWithSizeList::try_from(&body[3..] as &str)?;
+        let block_with_size_list = BlockWithSizeList::try_from(body)?;
 
         Ok(GetBlockListResponse {
             etag,
             last_modified,
+            blob_content_length,
             request_id,
+            client_request_id,
             date,
             block_with_size_list,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
 }
@@ -0,0 +1,46 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{
+    blob_content_length_from_headers, client_request_id_from_headers_optional, date_from_headers, etag_from_headers,
+    last_modified_from_headers, request_id_from_headers, RequestId,
+};
+use crate::azure::storage::blob::PageRangeList;
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+use std::str::from_utf8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetPageRangesDiffResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub blob_content_length: u64,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub page_range_list: PageRangeList,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl GetPageRangesDiffResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &[u8]) -> Result<GetPageRangesDiffResponse, AzureError> {
+        let etag = etag_from_headers(headers)?;
+        let last_modified = last_modified_from_headers(headers)?;
+        let blob_content_length = blob_content_length_from_headers(headers)?;
+        let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
+        let date = date_from_headers(headers)?;
+
+        let page_range_list = PageRangeList::try_from(from_utf8(body)?)?;
+
+        Ok(GetPageRangesDiffResponse {
+            etag,
+            last_modified,
+            blob_content_length,
+            request_id,
+            client_request_id,
+            date,
+            page_range_list,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
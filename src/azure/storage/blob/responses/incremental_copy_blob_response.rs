@@ -0,0 +1,13 @@
+use crate::azure::core::RequestId;
+use crate::azure::storage::blob::CopyStatus;
+use chrono::{DateTime, Utc};
+
+response_from_headers!(IncrementalCopyBlobResponse ,
+		       etag_from_headers -> etag: String,
+		       last_modified_from_headers -> last_modified: DateTime<Utc>,
+		       copy_id_from_headers -> copy_id: String,
+		       copy_status_from_headers -> copy_status: CopyStatus,
+		       request_id_from_headers -> request_id: RequestId,
+		       client_request_id_from_headers_optional -> client_request_id: Option<String>,
+		       date_from_headers -> date: DateTime<Utc>
+);
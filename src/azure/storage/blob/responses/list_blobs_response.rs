@@ -1,6 +1,7 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
 use crate::azure::core::incompletevector::IncompleteVector;
-use crate::azure::core::{date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
 use crate::azure::storage::blob::{incomplete_vector_from_response, Blob};
 use chrono::{DateTime, Utc};
 use http::HeaderMap;
@@ -9,19 +10,24 @@ use http::HeaderMap;
 pub struct ListBlobsResponse {
     pub incomplete_vector: IncompleteVector<Blob>,
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub date: DateTime<Utc>,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl ListBlobsResponse {
     pub(crate) fn from_response(container_name: &str, headers: &HeaderMap, body: &str) -> Result<ListBlobsResponse, AzureError> {
         let incomplete_vector = incomplete_vector_from_response(body, container_name)?;
         let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
         let date = date_from_headers(headers)?;
 
         Ok(ListBlobsResponse {
             incomplete_vector,
             request_id,
+            client_request_id,
             date,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
 }
@@ -28,3 +28,37 @@ mod update_page_response;
 pub use self::update_page_response::UpdatePageResponse;
 mod break_blob_lease_response;
 pub use self::break_blob_lease_response::BreakBlobLeaseResponse;
+mod set_blob_metadata_response;
+pub use self::set_blob_metadata_response::SetBlobMetadataResponse;
+mod set_blob_properties_response;
+pub use self::set_blob_properties_response::SetBlobPropertiesResponse;
+mod snapshot_blob_response;
+pub use self::snapshot_blob_response::SnapshotBlobResponse;
+mod get_blob_properties_response;
+pub use self::get_blob_properties_response::GetBlobPropertiesResponse;
+mod list_blobs_hierarchical_response;
+pub use self::list_blobs_hierarchical_response::ListBlobsHierarchicalResponse;
+mod incremental_copy_blob_response;
+pub use self::incremental_copy_blob_response::IncrementalCopyBlobResponse;
+mod get_page_ranges_response;
+pub use self::get_page_ranges_response::GetPageRangesResponse;
+mod get_page_ranges_diff_response;
+pub use self::get_page_ranges_diff_response::GetPageRangesDiffResponse;
+mod put_block_from_url_response;
+pub use self::put_block_from_url_response::PutBlockFromUrlResponse;
+mod copy_blob_response;
+pub use self::copy_blob_response::CopyBlobResponse;
+mod set_blob_tier_response;
+pub use self::set_blob_tier_response::SetBlobTierResponse;
+mod set_blob_tags_response;
+pub use self::set_blob_tags_response::SetBlobTagsResponse;
+mod get_blob_tags_response;
+pub use self::get_blob_tags_response::GetBlobTagsResponse;
+mod find_blobs_by_tags_response;
+pub use self::find_blobs_by_tags_response::FindBlobsByTagsResponse;
+mod set_blob_immutability_policy_response;
+pub use self::set_blob_immutability_policy_response::SetBlobImmutabilityPolicyResponse;
+mod delete_blob_immutability_policy_response;
+pub use self::delete_blob_immutability_policy_response::DeleteBlobImmutabilityPolicyResponse;
+mod set_blob_legal_hold_response;
+pub use self::set_blob_legal_hold_response::SetBlobLegalHoldResponse;
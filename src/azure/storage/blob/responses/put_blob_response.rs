@@ -1,7 +1,8 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
 use crate::azure::core::{
-    date_from_headers, etag_from_headers, last_modified_from_headers, request_id_from_headers, request_server_encrypted_from_headers,
-    RequestId,
+    client_request_id_from_headers_optional, date_from_headers, etag_from_headers, last_modified_from_headers, request_id_from_headers,
+    request_server_encrypted_from_headers, version_id_from_headers_optional, RequestId,
 };
 use chrono::{DateTime, Utc};
 use http::HeaderMap;
@@ -11,8 +12,11 @@ pub struct PutBlobResponse {
     pub etag: String,
     pub last_modified: DateTime<Utc>,
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub date: DateTime<Utc>,
     pub request_server_encrypted: bool,
+    pub version_id: Option<String>,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl PutBlobResponse {
@@ -20,15 +24,20 @@ impl PutBlobResponse {
         let etag = etag_from_headers(headers)?;
         let last_modified = last_modified_from_headers(headers)?;
         let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+        let version_id = version_id_from_headers_optional(headers)?;
 
         Ok(PutBlobResponse {
             etag,
             last_modified,
             request_id,
+            client_request_id,
             date,
             request_server_encrypted,
+            version_id,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
 }
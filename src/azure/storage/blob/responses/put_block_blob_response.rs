@@ -1,7 +1,8 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
 use crate::azure::core::{
-    content_md5_from_headers, date_from_headers, etag_from_headers, last_modified_from_headers, request_id_from_headers,
-    request_server_encrypted_from_headers, RequestId,
+    client_request_id_from_headers_optional, content_md5_from_headers, date_from_headers, etag_from_headers, last_modified_from_headers,
+    request_id_from_headers, request_server_encrypted_from_headers, version_id_from_headers_optional, RequestId,
 };
 use chrono::{DateTime, Utc};
 use http::HeaderMap;
@@ -12,8 +13,11 @@ pub struct PutBlockBlobResponse {
     pub last_modified: DateTime<Utc>,
     pub content_md5: [u8; 16],
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub date: DateTime<Utc>,
     pub request_server_encrypted: bool,
+    pub version_id: Option<String>,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl PutBlockBlobResponse {
@@ -22,16 +26,21 @@ impl PutBlockBlobResponse {
         let last_modified = last_modified_from_headers(headers)?;
         let content_md5 = content_md5_from_headers(headers)?;
         let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+        let version_id = version_id_from_headers_optional(headers)?;
 
         Ok(PutBlockBlobResponse {
             etag,
             last_modified,
             content_md5,
             request_id,
+            client_request_id,
             date,
             request_server_encrypted,
+            version_id,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
 }
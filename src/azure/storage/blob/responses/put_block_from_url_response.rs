@@ -0,0 +1,37 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{
+    client_request_id_from_headers_optional, content_md5_from_headers_optional, date_from_headers, request_id_from_headers,
+    request_server_encrypted_from_headers, RequestId,
+};
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PutBlockFromUrlResponse {
+    pub content_md5: Option<[u8; 16]>,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub request_server_encrypted: bool,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl PutBlockFromUrlResponse {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<PutBlockFromUrlResponse, AzureError> {
+        let content_md5 = content_md5_from_headers_optional(headers)?;
+        let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
+        let date = date_from_headers(headers)?;
+        let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+
+        Ok(PutBlockFromUrlResponse {
+            content_md5,
+            request_id,
+            client_request_id,
+            date,
+            request_server_encrypted,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
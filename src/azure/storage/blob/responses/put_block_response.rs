@@ -1,6 +1,8 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
 use crate::azure::core::{
-    content_md5_from_headers, date_from_headers, request_id_from_headers, request_server_encrypted_from_headers, RequestId,
+    client_request_id_from_headers_optional, content_md5_from_headers, date_from_headers, request_id_from_headers,
+    request_server_encrypted_from_headers, RequestId,
 };
 use chrono::{DateTime, Utc};
 use http::HeaderMap;
@@ -9,22 +11,27 @@ use http::HeaderMap;
 pub struct PutBlockResponse {
     pub content_md5: [u8; 16],
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub date: DateTime<Utc>,
     pub request_server_encrypted: bool,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl PutBlockResponse {
     pub(crate) fn from_headers(headers: &HeaderMap) -> Result<PutBlockResponse, AzureError> {
         let content_md5 = content_md5_from_headers(headers)?;
         let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
 
         Ok(PutBlockResponse {
             content_md5,
             request_id,
+            client_request_id,
             date,
             request_server_encrypted,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
 }
@@ -0,0 +1,39 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::{IMMUTABILITY_POLICY_MODE, IMMUTABILITY_POLICY_UNTIL_DATE};
+use crate::azure::core::parsing::from_azure_time;
+use crate::azure::core::util::HeaderMapExt;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::storage::blob::ImmutabilityPolicyMode;
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetBlobImmutabilityPolicyResponse {
+    pub immutability_policy_until_date: DateTime<Utc>,
+    pub immutability_policy_mode: ImmutabilityPolicyMode,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl SetBlobImmutabilityPolicyResponse {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<SetBlobImmutabilityPolicyResponse, AzureError> {
+        let immutability_policy_until_date = headers
+            .get_as_str(IMMUTABILITY_POLICY_UNTIL_DATE)
+            .ok_or_else(|| AzureError::HeaderNotFound(IMMUTABILITY_POLICY_UNTIL_DATE.to_owned()))?;
+        let immutability_policy_mode = headers
+            .get_as_enum(IMMUTABILITY_POLICY_MODE)?
+            .ok_or_else(|| AzureError::HeaderNotFound(IMMUTABILITY_POLICY_MODE.to_owned()))?;
+
+        Ok(SetBlobImmutabilityPolicyResponse {
+            immutability_policy_until_date: from_azure_time(immutability_policy_until_date)?,
+            immutability_policy_mode,
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            date: date_from_headers(headers)?,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
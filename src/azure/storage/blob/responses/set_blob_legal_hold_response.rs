@@ -0,0 +1,33 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::LEGAL_HOLD;
+use crate::azure::core::util::HeaderMapExt;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetBlobLegalHoldResponse {
+    pub legal_hold: bool,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl SetBlobLegalHoldResponse {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<SetBlobLegalHoldResponse, AzureError> {
+        let legal_hold = headers
+            .get_as_str(LEGAL_HOLD)
+            .ok_or_else(|| AzureError::HeaderNotFound(LEGAL_HOLD.to_owned()))?
+            .parse::<bool>()?;
+
+        Ok(SetBlobLegalHoldResponse {
+            legal_hold,
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            date: date_from_headers(headers)?,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
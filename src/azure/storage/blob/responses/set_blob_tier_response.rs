@@ -0,0 +1,32 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetBlobTierResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    /// `true` if the blob is being rehydrated from the archive tier: the
+    /// new tier applies once the rehydration completes.
+    pub rehydrating: bool,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl SetBlobTierResponse {
+    pub(crate) fn from_headers(headers: &HeaderMap, rehydrating: bool) -> Result<SetBlobTierResponse, AzureError> {
+        let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
+        let date = date_from_headers(headers)?;
+
+        Ok(SetBlobTierResponse {
+            request_id,
+            client_request_id,
+            date,
+            rehydrating,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
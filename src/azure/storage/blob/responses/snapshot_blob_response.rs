@@ -0,0 +1,11 @@
+use crate::azure::core::RequestId;
+use chrono::{DateTime, Utc};
+
+response_from_headers!(SnapshotBlobResponse ,
+		       snapshot_from_headers -> snapshot: DateTime<Utc>,
+		       etag_from_headers -> etag: String,
+		       last_modified_from_headers -> last_modified: DateTime<Utc>,
+		       request_id_from_headers -> request_id: RequestId,
+		       client_request_id_from_headers_optional -> client_request_id: Option<String>,
+		       date_from_headers -> date: DateTime<Utc>
+);
@@ -1,7 +1,8 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
 use crate::azure::core::{
-    content_md5_from_headers, date_from_headers, etag_from_headers, last_modified_from_headers, request_id_from_headers,
-    request_server_encrypted_from_headers, sequence_number_from_headers, RequestId,
+    client_request_id_from_headers_optional, content_md5_from_headers, date_from_headers, etag_from_headers, last_modified_from_headers,
+    request_id_from_headers, request_server_encrypted_from_headers, sequence_number_from_headers, RequestId,
 };
 use chrono::{DateTime, Utc};
 use http::HeaderMap;
@@ -13,8 +14,10 @@ pub struct UpdatePageResponse {
     pub content_md5: [u8; 16],
     pub sequence_number: u64,
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub date: DateTime<Utc>,
     pub request_server_encrypted: bool,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl UpdatePageResponse {
@@ -24,6 +27,7 @@ impl UpdatePageResponse {
         let content_md5 = content_md5_from_headers(headers)?;
         let sequence_number = sequence_number_from_headers(headers)?;
         let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
 
@@ -33,8 +37,10 @@ impl UpdatePageResponse {
             content_md5,
             sequence_number,
             request_id,
+            client_request_id,
             date,
             request_server_encrypted,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
 }
@@ -0,0 +1,196 @@
+use crate::azure::core::errors::AzureError;
+use bytes::Bytes;
+use futures::prelude::*;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// A downloaded body, kept in memory up to the threshold passed to
+/// `DownloadBlobStreamBuilder::finalize_spooled` and written to a
+/// temporary file past it.
+#[derive(Debug)]
+pub enum SpooledBody {
+    Memory(Vec<u8>),
+    File(TempFile),
+}
+
+impl SpooledBody {
+    pub fn len(&self) -> u64 {
+        match self {
+            SpooledBody::Memory(data) => data.len() as u64,
+            SpooledBody::File(file) => file.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the body ended up in a temporary file.
+    pub fn is_spooled(&self) -> bool {
+        match self {
+            SpooledBody::Memory(_) => false,
+            SpooledBody::File(_) => true,
+        }
+    }
+
+    /// Reads the body from the start. The temporary file, if any, is
+    /// deleted once the reader is dropped.
+    pub fn into_reader(self) -> Result<Box<dyn Read + Send>, AzureError> {
+        match self {
+            SpooledBody::Memory(data) => Ok(Box::new(Cursor::new(data))),
+            SpooledBody::File(file) => Ok(Box::new(file.into_reader()?)),
+        }
+    }
+
+    /// Writes the body to `path`. The temporary file is moved there when
+    /// possible instead of being copied.
+    pub fn persist<P: AsRef<Path>>(self, path: P) -> Result<(), AzureError> {
+        match self {
+            SpooledBody::Memory(data) => fs::write(path, data)?,
+            SpooledBody::File(file) => file.persist(path)?,
+        }
+        Ok(())
+    }
+}
+
+/// A file in the temporary directory, deleted when dropped unless
+/// persisted.
+#[derive(Debug)]
+pub struct TempFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
+}
+
+impl TempFile {
+    fn create(dir: &Path) -> Result<TempFile, AzureError> {
+        let path = dir.join(format!("azure-sdk-{}.tmp", Uuid::new_v4()));
+        let file = OpenOptions::new().read(true).write(true).create_new(true).open(&path)?;
+        Ok(TempFile { path, file, len: 0 })
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> Result<(), AzureError> {
+        self.file.write_all(data)?;
+        self.len += data.len() as u64;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn into_reader(mut self) -> Result<TempFileReader, AzureError> {
+        self.file.flush()?;
+        self.file.seek(SeekFrom::Start(0))?;
+        Ok(TempFileReader(self))
+    }
+
+    pub fn persist<P: AsRef<Path>>(self, path: P) -> Result<(), AzureError> {
+        let path = path.as_ref();
+        // the rename fails across file systems
+        if fs::rename(&self.path, path).is_err() {
+            fs::copy(&self.path, path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if let Err(error) = fs::remove_file(&self.path) {
+            if error.kind() != io::ErrorKind::NotFound {
+                warn!("cannot remove the temporary file {:?}: {}", self.path, error);
+            }
+        }
+    }
+}
+
+/// Reads a `TempFile` from the start, deleting it when dropped.
+#[derive(Debug)]
+pub struct TempFileReader(TempFile);
+
+impl Read for TempFileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.file.read(buf)
+    }
+}
+
+// Collects the chunks in memory until they exceed `threshold` bytes, then
+// moves them to a temporary file in `dir`. The writes are blocking.
+pub(crate) fn spool<S>(chunks: S, threshold: u64, dir: PathBuf) -> impl Future<Item = SpooledBody, Error = AzureError>
+where
+    S: Stream<Item = Bytes, Error = AzureError>,
+{
+    chunks.fold(SpooledBody::Memory(Vec::new()), move |body, chunk| {
+        append(body, &chunk, threshold, &dir)
+    })
+}
+
+fn append(body: SpooledBody, chunk: &[u8], threshold: u64, dir: &Path) -> Result<SpooledBody, AzureError> {
+    match body {
+        SpooledBody::Memory(mut data) => {
+            if data.len() as u64 + chunk.len() as u64 <= threshold {
+                data.extend_from_slice(chunk);
+                return Ok(SpooledBody::Memory(data));
+            }
+
+            debug!("the body exceeds {} bytes, spooling it to {:?}", threshold, dir);
+            let mut file = TempFile::create(dir)?;
+            file.write_all(&data)?;
+            file.write_all(chunk)?;
+            Ok(SpooledBody::File(file))
+        }
+        SpooledBody::File(mut file) => {
+            file.write_all(chunk)?;
+            Ok(SpooledBody::File(file))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::stream;
+
+    fn chunks(chunks: &[&'static [u8]]) -> impl Stream<Item = Bytes, Error = AzureError> {
+        stream::iter_ok(chunks.iter().map(|chunk| Bytes::from_static(chunk)).collect::<Vec<_>>())
+    }
+
+    fn read(body: SpooledBody) -> Vec<u8> {
+        let mut data = Vec::new();
+        body.into_reader().unwrap().read_to_end(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_memory() {
+        let body = spool(chunks(&[b"abc", b"def"]), 6, std::env::temp_dir()).wait().unwrap();
+        assert!(!body.is_spooled());
+        assert_eq!(read(body), b"abcdef");
+    }
+
+    #[test]
+    fn test_file() {
+        let body = spool(chunks(&[b"abc", b"def", b"gh"]), 4, std::env::temp_dir()).wait().unwrap();
+        assert!(body.is_spooled());
+        assert_eq!(body.len(), 8);
+
+        let path = match &body {
+            SpooledBody::File(file) => file.path().to_owned(),
+            SpooledBody::Memory(_) => unreachable!(),
+        };
+        assert!(path.exists());
+        assert_eq!(read(body), b"abcdefgh");
+        assert!(!path.exists());
+    }
+}
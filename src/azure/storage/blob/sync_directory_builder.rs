@@ -0,0 +1,704 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{
+    owned_headers, ApiVersionOption, ApiVersionSupport, BlobNameSupport, BodySupport, ClientRequestIdOption, ClientRequestIdSupport,
+    ClientRequired, ContainerNameRequired, ContainerNameSupport, ContentTypeSupport, DeleteSnapshotsMethod, DeleteSnapshotsMethodSupport,
+    ExtraHeadersOption, ExtraHeadersSupport, No, PrefixOption, PrefixSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::blob::Blob;
+use crate::azure::storage::client::{Blob as BlobTrait, Client};
+use crate::azure::storage::static_website::{content_type_for, list_files};
+use chrono::{DateTime, Utc};
+use futures::future;
+use futures::prelude::*;
+use futures::stream;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    container_name: Option<&'a str>,
+    prefix: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+    comparison: SyncComparison,
+    delete_orphans: bool,
+    dry_run: bool,
+    progress: Option<ProgressCallback>,
+}
+
+impl<'a> SyncDirectoryBuilder<'a, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> SyncDirectoryBuilder<'a, No> {
+        SyncDirectoryBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            prefix: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+            comparison: SyncComparison::default(),
+            delete_orphans: false,
+            dry_run: false,
+            progress: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ClientRequired<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> ContainerNameRequired<'a> for SyncDirectoryBuilder<'a, Yes> {
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet> PrefixOption<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn prefix(&self) -> Option<&'a str> {
+        self.prefix
+    }
+}
+
+impl<'a, ContainerNameSet> TimeoutOption for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet> ClientRequestIdOption<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersOption<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionOption<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet> ContainerNameSupport<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = SyncDirectoryBuilder<'a, Yes>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        SyncDirectoryBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: Some(container_name),
+            prefix: self.prefix,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            comparison: self.comparison,
+            delete_orphans: self.delete_orphans,
+            dry_run: self.dry_run,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> PrefixSupport<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = SyncDirectoryBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_prefix(self, prefix: &'a str) -> Self::O {
+        SyncDirectoryBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            prefix: Some(prefix),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            comparison: self.comparison,
+            delete_orphans: self.delete_orphans,
+            dry_run: self.dry_run,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> TimeoutSupport for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = SyncDirectoryBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        SyncDirectoryBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            prefix: self.prefix,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            comparison: self.comparison,
+            delete_orphans: self.delete_orphans,
+            dry_run: self.dry_run,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ClientRequestIdSupport<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = SyncDirectoryBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        SyncDirectoryBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            prefix: self.prefix,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            comparison: self.comparison,
+            delete_orphans: self.delete_orphans,
+            dry_run: self.dry_run,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersSupport<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = SyncDirectoryBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionSupport<'a> for SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = SyncDirectoryBuilder<'a, ContainerNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SyncDirectoryBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            prefix: self.prefix,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+            comparison: self.comparison,
+            delete_orphans: self.delete_orphans,
+            dry_run: self.dry_run,
+            progress: self.progress,
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet> SyncDirectoryBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    /// Defaults to `SyncComparison::LastModified`.
+    #[inline]
+    pub fn with_comparison(self, comparison: SyncComparison) -> Self {
+        SyncDirectoryBuilder { comparison, ..self }
+    }
+
+    /// Also deletes the blobs below the prefix without a matching file,
+    /// with their snapshots.
+    #[inline]
+    pub fn with_delete_orphans(self) -> Self {
+        SyncDirectoryBuilder {
+            delete_orphans: true,
+            ..self
+        }
+    }
+
+    /// Only lists the container and compares: nothing is uploaded or
+    /// deleted, and the response holds what would have been.
+    #[inline]
+    pub fn with_dry_run(self) -> Self {
+        SyncDirectoryBuilder { dry_run: true, ..self }
+    }
+
+    /// Calls `progress` after each upload or delete. The callback must
+    /// return quickly: it runs on the thread polling the future.
+    #[inline]
+    pub fn with_progress<F>(self, progress: F) -> Self
+    where
+        F: Fn(&SyncProgress) + Send + Sync + 'static,
+    {
+        SyncDirectoryBuilder {
+            progress: Some(ProgressCallback(Arc::new(progress))),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn comparison(&self) -> SyncComparison {
+        self.comparison
+    }
+
+    #[inline]
+    pub fn delete_orphans(&self) -> bool {
+        self.delete_orphans
+    }
+
+    #[inline]
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+impl<'a> SyncDirectoryBuilder<'a, Yes> {
+    /// Uploads the files of `directory`, recursively, that are missing
+    /// from the container or differ from their blob, as block blobs named
+    /// after the prefix followed by their path relative to `directory`
+    /// (with `/` separators). The prefix is prepended as is: it usually
+    /// ends with `/`.
+    pub fn finalize(self, directory: &Path) -> impl Future<Item = SyncDirectoryResponse, Error = AzureError> {
+        let request = Arc::new(SyncRequest {
+            client: self.client().clone(),
+            container_name: self.container_name().to_owned(),
+            timeout: self.timeout(),
+            client_request_id: self.client_request_id().map(|v| v.to_owned()),
+            extra_headers: owned_headers(self.extra_headers()),
+            api_version: self.api_version().map(|v| v.to_owned()),
+        });
+        let prefix = self.prefix().unwrap_or_default().to_owned();
+        let comparison = self.comparison;
+        let delete_orphans = self.delete_orphans;
+        let dry_run = self.dry_run;
+        let progress = self.progress;
+
+        future::result(list_files(directory))
+            .join(request.list(&prefix))
+            .and_then(move |(files, blobs)| future::result(plan(files, blobs, &prefix, comparison, delete_orphans)))
+            .and_then(move |(actions, unchanged)| {
+                let total = actions.len();
+                let completed = Arc::new(AtomicUsize::new(0));
+                let response = SyncDirectoryResponse {
+                    unchanged,
+                    dry_run,
+                    ..SyncDirectoryResponse::default()
+                };
+
+                stream::iter_ok(actions)
+                    .map(move |action| {
+                        let done = if dry_run {
+                            future::Either::A(future::ok(action))
+                        } else {
+                            future::Either::B(request.perform(action))
+                        };
+                        let completed = completed.clone();
+                        let progress = progress.clone();
+                        done.map(move |action| {
+                            let completed = completed.fetch_add(1, Ordering::SeqCst) + 1;
+                            if let Some(progress) = progress {
+                                (progress.0)(&SyncProgress {
+                                    action: &action,
+                                    completed,
+                                    total,
+                                });
+                            }
+                            action
+                        })
+                    })
+                    .buffered(SYNC_CONCURRENCY)
+                    .fold(response, |mut response, action| {
+                        match action {
+                            SyncAction::Upload { blob_name, bytes, .. } => {
+                                response.uploaded.push(blob_name);
+                                response.bytes_uploaded += bytes;
+                            }
+                            SyncAction::Delete { blob_name } => response.deleted.push(blob_name),
+                        }
+                        Ok::<_, AzureError>(response)
+                    })
+            })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self, directory: &Path) -> impl std::future::Future<Output = Result<SyncDirectoryResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize(directory))
+    }
+}
+
+// how many uploads and deletes are sent at the same time
+const SYNC_CONCURRENCY: usize = 8;
+
+/// How `SyncDirectoryBuilder` decides that a file differs from its blob.
+/// A size mismatch always means the file changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncComparison {
+    /// Only compares the sizes.
+    Size,
+    /// Uploads the files modified after their blob. This is the default.
+    #[default]
+    LastModified,
+    /// Compares the MD5 of the file with the `Content-MD5` of the blob,
+    /// reading every file. The blobs without one (such as the ones
+    /// uploaded in blocks) are uploaded again.
+    ContentMD5,
+}
+
+/// A change made, or planned in a dry run, by `SyncDirectoryBuilder`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+    Upload {
+        path: PathBuf,
+        blob_name: String,
+        bytes: u64,
+    },
+    /// A blob without a matching file, with `with_delete_orphans`.
+    Delete {
+        blob_name: String,
+    },
+}
+
+/// Passed to the `with_progress` callback after each action.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncProgress<'a> {
+    pub action: &'a SyncAction,
+    /// The actions completed so far, this one included.
+    pub completed: usize,
+    pub total: usize,
+}
+
+#[derive(Clone)]
+struct ProgressCallback(Arc<dyn Fn(&SyncProgress) + Send + Sync>);
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// The outcome of `SyncDirectoryBuilder::finalize`. In a dry run the
+/// blobs are the ones that would have been uploaded or deleted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncDirectoryResponse {
+    pub uploaded: Vec<String>,
+    pub deleted: Vec<String>,
+    /// The blobs matching their file, left in place.
+    pub unchanged: Vec<String>,
+    pub bytes_uploaded: u64,
+    pub dry_run: bool,
+}
+
+// the uploads (files first, in directory order) and deletes needed, and
+// the blobs already up to date
+fn plan(
+    files: Vec<(PathBuf, String)>,
+    blobs: Vec<Blob>,
+    prefix: &str,
+    comparison: SyncComparison,
+    delete_orphans: bool,
+) -> Result<(Vec<SyncAction>, Vec<String>), AzureError> {
+    let mut blobs = blobs.into_iter().map(|blob| (blob.name.clone(), blob)).collect::<HashMap<_, _>>();
+    let mut actions = Vec::new();
+    let mut unchanged = Vec::new();
+
+    for (path, relative_name) in files {
+        let blob_name = format!("{}{}", prefix, relative_name);
+        let metadata = fs::metadata(&path)?;
+        let changed = match blobs.remove(&blob_name) {
+            Some(blob) => is_changed(&path, &metadata, &blob, comparison)?,
+            None => true,
+        };
+        if changed {
+            actions.push(SyncAction::Upload {
+                path,
+                blob_name,
+                bytes: metadata.len(),
+            });
+        } else {
+            unchanged.push(blob_name);
+        }
+    }
+
+    if delete_orphans {
+        let mut orphans = blobs.into_keys().collect::<Vec<_>>();
+        orphans.sort();
+        actions.extend(orphans.into_iter().map(|blob_name| SyncAction::Delete { blob_name }));
+    }
+
+    Ok((actions, unchanged))
+}
+
+fn is_changed(path: &Path, metadata: &fs::Metadata, blob: &Blob, comparison: SyncComparison) -> Result<bool, AzureError> {
+    if metadata.len() != blob.content_length {
+        return Ok(true);
+    }
+    Ok(match comparison {
+        SyncComparison::Size => false,
+        SyncComparison::LastModified => match blob.last_modified {
+            Some(last_modified) => DateTime::<Utc>::from(metadata.modified()?) > last_modified,
+            None => true,
+        },
+        SyncComparison::ContentMD5 => match blob.content_md5 {
+            Some(ref content_md5) => base64::encode(&md5::compute(fs::read(path)?)[..]) != *content_md5,
+            None => true,
+        },
+    })
+}
+
+#[derive(Debug)]
+struct SyncRequest {
+    client: Client,
+    container_name: String,
+    timeout: Option<u64>,
+    client_request_id: Option<String>,
+    extra_headers: Vec<(String, String)>,
+    api_version: Option<String>,
+}
+
+impl SyncRequest {
+    fn list(&self, prefix: &str) -> impl Future<Item = Vec<Blob>, Error = AzureError> {
+        let mut req = self
+            .client
+            .list_blobs()
+            .with_container_name(&self.container_name)
+            .with_prefix(prefix);
+        if let Some(timeout) = self.timeout {
+            req = req.with_timeout(timeout);
+        }
+        if let Some(ref client_request_id) = self.client_request_id {
+            req = req.with_client_request_id(client_request_id);
+        }
+        for (name, value) in &self.extra_headers {
+            req = req.with_extra_header(name, value);
+        }
+        if let Some(ref api_version) = self.api_version {
+            req = req.with_api_version(api_version);
+        }
+        req.stream().collect()
+    }
+
+    // the files are read when their upload starts, so that at most
+    // SYNC_CONCURRENCY of them are held in memory
+    fn perform(&self, action: SyncAction) -> Box<dyn Future<Item = SyncAction, Error = AzureError> + Send> {
+        match action {
+            SyncAction::Upload {
+                ref path, ref blob_name, ..
+            } => {
+                let body = match fs::read(path) {
+                    Ok(body) => body,
+                    Err(error) => return Box::new(future::err(error.into())),
+                };
+                let mut req = self
+                    .client
+                    .put_block_blob()
+                    .with_container_name(&self.container_name)
+                    .with_blob_name(blob_name)
+                    .with_content_type(content_type_for(path))
+                    .with_body(&body);
+                if let Some(timeout) = self.timeout {
+                    req = req.with_timeout(timeout);
+                }
+                if let Some(ref client_request_id) = self.client_request_id {
+                    req = req.with_client_request_id(client_request_id);
+                }
+                for (name, value) in &self.extra_headers {
+                    req = req.with_extra_header(name, value);
+                }
+                if let Some(ref api_version) = self.api_version {
+                    req = req.with_api_version(api_version);
+                }
+                Box::new(req.finalize().map(move |_| action))
+            }
+            SyncAction::Delete { ref blob_name } => {
+                let mut req = self
+                    .client
+                    .delete_blob()
+                    .with_container_name(&self.container_name)
+                    .with_blob_name(blob_name)
+                    .with_delete_snapshots_method(DeleteSnapshotsMethod::Include);
+                if let Some(timeout) = self.timeout {
+                    req = req.with_timeout(timeout);
+                }
+                if let Some(ref client_request_id) = self.client_request_id {
+                    req = req.with_client_request_id(client_request_id);
+                }
+                for (name, value) in &self.extra_headers {
+                    req = req.with_extra_header(name, value);
+                }
+                if let Some(ref api_version) = self.api_version {
+                    req = req.with_api_version(api_version);
+                }
+                Box::new(req.finalize().map(move |_| action))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use hyper::StatusCode;
+
+    fn listing() -> String {
+        let blob = |name: &str, content_length: u64, content_md5: &str| {
+            format!(
+                "<Blob><Name>{}</Name><Properties><Creation-Time>Tue, 15 Oct 2019 10:00:00 GMT</Creation-Time>\
+                 <Last-Modified>Tue, 15 Oct 2019 10:00:00 GMT</Last-Modified><Content-Length>{}</Content-Length>\
+                 <Content-Type>text/plain</Content-Type><Content-MD5>{}</Content-MD5><BlobType>BlockBlob</BlobType><LeaseState>available</LeaseState>\
+                 <ServerEncrypted>true</ServerEncrypted></Properties></Blob>",
+                name, content_length, content_md5
+            )
+        };
+        format!(
+            "<EnumerationResults><Prefix>site/</Prefix><Blobs>{}{}</Blobs><NextMarker /></EnumerationResults>",
+            blob("site/a.txt", 3, "kAFQmDzST7DWlj99KOF/cg=="),
+            blob("site/old.txt", 1, "DMF1ucDxtqgxw5niaXcmYQ==")
+        )
+    }
+
+    #[test]
+    fn test_sync_directory() {
+        let directory = std::env::temp_dir().join(format!("sync_directory_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("a.txt"), "abc").unwrap();
+        fs::write(directory.join("b.txt"), "hello").unwrap();
+
+        let headers = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ];
+        let uploaded = [
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("content-md5", "XUFAKrxLKna5cZ2REBfFkg=="),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-server-encrypted", "true"),
+        ];
+        let deleted = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-delete-type-permanent", "false"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::OK, &headers, listing().as_bytes())
+                .with_response(StatusCode::OK, &headers, listing().as_bytes())
+                .with_response(StatusCode::CREATED, &uploaded, b"")
+                .with_response(StatusCode::ACCEPTED, &deleted, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+
+        let dry_run = client
+            .sync_directory()
+            .with_container_name("c")
+            .with_prefix("site/")
+            .with_comparison(SyncComparison::ContentMD5)
+            .with_delete_orphans()
+            .with_dry_run()
+            .finalize(&directory)
+            .wait();
+        let completed = Arc::new(AtomicUsize::new(0));
+        let counter = completed.clone();
+        let sync = client
+            .sync_directory()
+            .with_container_name("c")
+            .with_prefix("site/")
+            .with_comparison(SyncComparison::ContentMD5)
+            .with_delete_orphans()
+            .with_progress(move |progress| {
+                assert_eq!(progress.total, 2);
+                counter.store(progress.completed, Ordering::SeqCst);
+            })
+            .finalize(&directory)
+            .wait();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let expected = SyncDirectoryResponse {
+            uploaded: vec!["site/b.txt".to_owned()],
+            deleted: vec!["site/old.txt".to_owned()],
+            unchanged: vec!["site/a.txt".to_owned()],
+            bytes_uploaded: 5,
+            dry_run: true,
+        };
+        assert_eq!(dry_run.unwrap(), expected);
+        assert_eq!(sync.unwrap(), SyncDirectoryResponse { dry_run: false, ..expected });
+        assert_eq!(completed.load(Ordering::SeqCst), 2);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[2].uri, "https://account.blob.core.windows.net/c/site%2Fb.txt");
+        assert_eq!(requests[2].headers["content-type"], "text/plain; charset=utf-8");
+        assert_eq!(requests[3].method, hyper::Method::DELETE);
+        assert_eq!(requests[3].uri, "https://account.blob.core.windows.net/c/site%2Fold.txt");
+    }
+}
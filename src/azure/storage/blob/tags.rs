@@ -0,0 +1,250 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::incompletevector::IncompleteVector;
+use crate::azure::core::parsing::{cast_must, cast_optional, traverse};
+use crate::azure::core::COMPLETE_ENCODE_SET;
+use std::collections::btree_map::{self, BTreeMap};
+use std::fmt;
+use std::ops::Index;
+use url::percent_encoding::utf8_percent_encode;
+use xml::Element;
+
+/// The index tags of a blob, by key. A blob can have up to 10 tags; the
+/// keys are 1 to 128 characters long, the values up to 256, and both
+/// are case sensitive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tags {
+    tags: BTreeMap<String, String>,
+}
+
+impl Tags {
+    pub fn new() -> Tags {
+        Tags::default()
+    }
+
+    /// Sets the tag `key`, returning its previous value.
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) -> Option<String> {
+        self.tags.insert(key.into(), value.into())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tags.get(key).map(String::as_str)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.tags.remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<'_, String, String> {
+        self.tags.iter()
+    }
+
+    // the tags are below `path`/TagSet/Tag; a missing `path` leaf (the
+    // blobs listed without `include=tags`) means no tags
+    pub(crate) fn parse(elem: &Element, path: &[&str]) -> Result<Tags, AzureError> {
+        let mut tags = Tags::new();
+        for tag_set in traverse(elem, path, true)? {
+            for tag in traverse(tag_set, &["TagSet", "Tag"], true)? {
+                tags.insert(cast_must::<String>(tag, &["Key"])?, cast_must::<String>(tag, &["Value"])?);
+            }
+        }
+        Ok(tags)
+    }
+
+    pub(crate) fn from_xml(body: &str) -> Result<Tags, AzureError> {
+        Tags::parse(&body.parse::<Element>()?, &[])
+    }
+
+    // the `x-ms-tags` header value: the tags as a query string
+    pub(crate) fn to_header(&self) -> String {
+        self.tags
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}={}",
+                    utf8_percent_encode(key, COMPLETE_ENCODE_SET),
+                    utf8_percent_encode(value, COMPLETE_ENCODE_SET)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut s = "<?xml version=\"1.0\" encoding=\"utf-8\"?><Tags><TagSet>".to_owned();
+        for (key, value) in &self.tags {
+            s.push_str(&format!(
+                "<Tag><Key>{}</Key><Value>{}</Value></Tag>",
+                xml::escape(key),
+                xml::escape(value)
+            ));
+        }
+        s.push_str("</TagSet></Tags>");
+        s
+    }
+}
+
+impl<'a> Index<&'a str> for Tags {
+    type Output = str;
+
+    fn index(&self, key: &'a str) -> &str {
+        &self.tags[key]
+    }
+}
+
+impl<'a> IntoIterator for &'a Tags {
+    type Item = (&'a String, &'a String);
+    type IntoIter = btree_map::Iter<'a, String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tags.iter()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> std::iter::FromIterator<(K, V)> for Tags {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Tags {
+        Tags {
+            tags: iter.into_iter().map(|(key, value)| (key.into(), value.into())).collect(),
+        }
+    }
+}
+
+/// The `where` expression of `find_blobs_by_tags`: comparisons of a tag
+/// with a value, optionally restricted to a container, joined with `AND`
+/// (the only operator the service supports).
+///
+/// ```
+/// use azure_sdk_for_rust::storage::blob::TagFilter;
+///
+/// let filter = TagFilter::eq("project", "alpha").and(TagFilter::ge("date", "2019-10-15"));
+/// assert_eq!(filter.as_str(), "\"project\" = 'alpha' AND \"date\" >= '2019-10-15'");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagFilter {
+    expression: String,
+}
+
+impl TagFilter {
+    fn compare(key: &str, operator: &str, value: &str) -> TagFilter {
+        TagFilter {
+            expression: format!("\"{}\" {} '{}'", key, operator, value),
+        }
+    }
+
+    pub fn eq(key: &str, value: &str) -> TagFilter {
+        TagFilter::compare(key, "=", value)
+    }
+
+    pub fn gt(key: &str, value: &str) -> TagFilter {
+        TagFilter::compare(key, ">", value)
+    }
+
+    pub fn ge(key: &str, value: &str) -> TagFilter {
+        TagFilter::compare(key, ">=", value)
+    }
+
+    pub fn lt(key: &str, value: &str) -> TagFilter {
+        TagFilter::compare(key, "<", value)
+    }
+
+    pub fn le(key: &str, value: &str) -> TagFilter {
+        TagFilter::compare(key, "<=", value)
+    }
+
+    /// Only matches the blobs of `container_name`.
+    pub fn container(container_name: &str) -> TagFilter {
+        TagFilter {
+            expression: format!("@container = '{}'", container_name),
+        }
+    }
+
+    pub fn and(self, other: TagFilter) -> TagFilter {
+        TagFilter {
+            expression: format!("{} AND {}", self.expression, other.expression),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.expression
+    }
+}
+
+impl fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+/// A blob found by `find_blobs_by_tags`, with the tags that matched the
+/// filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilteredBlob {
+    pub name: String,
+    pub container_name: String,
+    pub tags: Tags,
+}
+
+pub(crate) fn filtered_blobs_from_response(body: &str) -> Result<IncompleteVector<FilteredBlob>, AzureError> {
+    let elem: Element = body.parse()?;
+
+    let mut v = Vec::new();
+    for blob in traverse(&elem, &["Blobs", "Blob"], true)? {
+        v.push(FilteredBlob {
+            name: cast_must::<String>(blob, &["Name"])?,
+            container_name: cast_must::<String>(blob, &["ContainerName"])?,
+            tags: Tags::parse(blob, &["Tags"])?,
+        });
+    }
+
+    let next_marker = match cast_optional::<String>(&elem, &["NextMarker"])? {
+        Some(ref nm) if nm.is_empty() => None,
+        Some(nm) => Some(nm),
+        None => None,
+    };
+
+    Ok(IncompleteVector::new(next_marker, v))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tags_xml() {
+        let tags: Tags = vec![("project", "alpha & beta"), ("date", "2019-10-15")].into_iter().collect();
+        let xml = tags.to_xml();
+        assert_eq!(
+            xml,
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?><Tags><TagSet><Tag><Key>date</Key><Value>2019-10-15</Value></Tag>\
+             <Tag><Key>project</Key><Value>alpha &amp; beta</Value></Tag></TagSet></Tags>"
+        );
+        assert_eq!(Tags::from_xml(&xml).unwrap(), tags);
+        assert_eq!(&tags["project"], "alpha & beta");
+    }
+
+    #[test]
+    fn test_tag_filter() {
+        let filter = TagFilter::container("c").and(TagFilter::lt("size", "10"));
+        assert_eq!(filter.to_string(), "@container = 'c' AND \"size\" < '10'");
+    }
+
+    #[test]
+    fn test_filtered_blobs() {
+        let body = "<?xml version=\"1.0\" encoding=\"utf-8\"?><EnumerationResults ServiceEndpoint=\"https://account.blob.core.windows.net/\">\
+                    <Where>\"project\" = 'alpha'</Where><Blobs><Blob><Name>b</Name><ContainerName>c</ContainerName><Tags><TagSet>\
+                    <Tag><Key>project</Key><Value>alpha</Value></Tag></TagSet></Tags></Blob></Blobs><NextMarker>marker</NextMarker>\
+                    </EnumerationResults>";
+        let blobs = filtered_blobs_from_response(body).unwrap();
+        assert_eq!(blobs.token(), Some("marker"));
+        assert_eq!(blobs.vector.len(), 1);
+        assert_eq!(blobs.vector[0].container_name, "c");
+        assert_eq!(blobs.vector[0].tags.get("project"), Some("alpha"));
+    }
+}
@@ -0,0 +1,618 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::lease::LeaseId;
+use crate::azure::core::{
+    owned_headers, ApiVersionOption, ApiVersionSupport, BlobNameRequired, BlobNameSupport, BlockIdSupport, BlockListSupport, BodySupport,
+    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ContentTypeOption,
+    ContentTypeSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, MetadataOption, MetadataSupport, No,
+    TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::blob::record_framing::RecordEncoder;
+use crate::azure::storage::blob::requests::{PutBlockBuilder, PutBlockListBuilder};
+use crate::azure::storage::blob::responses::PutBlockListResponse;
+use crate::azure::storage::blob::{BlobBlockType, BlockList, RecordFraming};
+use crate::azure::storage::client::Client;
+use futures::future::{err, ok, Either};
+use futures::prelude::*;
+use futures::stream;
+use serde::Serialize;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+const DEFAULT_BLOCK_SIZE: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    timeout: Option<u64>,
+    content_type: Option<&'a str>,
+    metadata: Option<&'a Metadata>,
+    lease_id: Option<&'a LeaseId>,
+    client_request_id: Option<&'a str>,
+    framing: RecordFraming,
+    block_size: u64,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> UploadRecordsBuilder<'a, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> UploadRecordsBuilder<'a, No, No> {
+        UploadRecordsBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            timeout: None,
+            content_type: None,
+            metadata: None,
+            lease_id: None,
+            client_request_id: None,
+            framing: RecordFraming::NdJson,
+            block_size: DEFAULT_BLOCK_SIZE,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequired<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet> ContainerNameRequired<'a> for UploadRecordsBuilder<'a, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet> BlobNameRequired<'a> for UploadRecordsBuilder<'a, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutOption for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ContentTypeOption<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn content_type(&self) -> Option<&'a str> {
+        self.content_type
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> MetadataOption<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn metadata(&self) -> Option<&'a Metadata> {
+        self.metadata
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> LeaseIdOption<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ContainerNameSupport<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        UploadRecordsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            framing: self.framing,
+            block_size: self.block_size,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> BlobNameSupport<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        UploadRecordsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            timeout: self.timeout,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            framing: self.framing,
+            block_size: self.block_size,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutSupport for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        UploadRecordsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: Some(timeout),
+            content_type: self.content_type,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            framing: self.framing,
+            block_size: self.block_size,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ContentTypeSupport<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_type(self, content_type: &'a str) -> Self::O {
+        UploadRecordsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: Some(content_type),
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            framing: self.framing,
+            block_size: self.block_size,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> MetadataSupport<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
+        UploadRecordsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            metadata: Some(metadata),
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            framing: self.framing,
+            block_size: self.block_size,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> LeaseIdSupport<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        UploadRecordsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            lease_id: Some(lease_id),
+            client_request_id: self.client_request_id,
+            framing: self.framing,
+            block_size: self.block_size,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        UploadRecordsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: Some(client_request_id),
+            framing: self.framing,
+            block_size: self.block_size,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersOption<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersSupport<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionOption<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionSupport<'a> for UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        UploadRecordsBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+            framing: self.framing,
+            block_size: self.block_size,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet> UploadRecordsBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    /// How the records are serialized. Defaults to `RecordFraming::NdJson`.
+    /// Unless a content type is specified explicitly the blob gets the
+    /// one matching the framing.
+    #[inline]
+    pub fn with_framing(self, framing: RecordFraming) -> Self {
+        UploadRecordsBuilder { framing, ..self }
+    }
+
+    /// Serialized records are accumulated until the buffer reaches this
+    /// size, then uploaded as a block. Blocks never split a record so they
+    /// can be slightly larger. Defaults to 4 MB.
+    #[inline]
+    pub fn with_block_size(self, block_size: u64) -> Self {
+        UploadRecordsBuilder { block_size, ..self }
+    }
+
+    #[inline]
+    pub fn framing(&self) -> RecordFraming {
+        self.framing
+    }
+
+    #[inline]
+    pub fn block_size(&self) -> u64 {
+        self.block_size
+    }
+}
+
+impl<'a> UploadRecordsBuilder<'a, Yes, Yes> {
+    /// Serializes the records as they come and uploads them as blocks,
+    /// committing the block list once the stream ends. The blob is
+    /// replaced only at commit time: if the stream errors out the staged
+    /// blocks are simply left uncommitted.
+    pub fn finalize<S>(self, records: S) -> impl Future<Item = PutBlockListResponse, Error = AzureError>
+    where
+        S: Stream<Error = AzureError>,
+        S::Item: Serialize,
+    {
+        let target = Arc::new(UploadTarget {
+            client: self.client().clone(),
+            container_name: self.container_name().to_owned(),
+            blob_name: self.blob_name().to_owned(),
+            timeout: self.timeout(),
+            lease_id: self.lease_id().cloned(),
+            client_request_id: self.client_request_id().map(|v| v.to_owned()),
+            extra_headers: owned_headers(self.extra_headers()),
+            api_version: self.api_version().map(|v| v.to_owned()),
+        });
+        let content_type = self.content_type().unwrap_or_else(|| self.framing.content_type()).to_owned();
+        let metadata = self.metadata().cloned();
+        let block_size = std::cmp::max(self.block_size, 1) as usize;
+
+        let state = UploadState {
+            encoder: RecordEncoder::new(self.framing),
+            buffer: Vec::new(),
+            block_ids: Vec::new(),
+        };
+
+        let stage_target = target.clone();
+        records
+            .fold(state, move |mut state, record| {
+                if let Err(error) = state.encoder.encode(&record, &mut state.buffer) {
+                    return Either::A(err(error));
+                }
+                if state.buffer.len() >= block_size {
+                    Either::B(Either::A(stage_target.stage(state)))
+                } else {
+                    Either::B(Either::B(ok(state)))
+                }
+            })
+            .and_then(move |state| {
+                let commit_target = target.clone();
+                target
+                    .stage(state)
+                    .and_then(move |state| commit_target.commit(state.block_ids, content_type, metadata))
+            })
+    }
+
+    /// Same as `finalize` but takes any iterator of records.
+    pub fn finalize_iter<I>(self, records: I) -> impl Future<Item = PutBlockListResponse, Error = AzureError>
+    where
+        I: IntoIterator,
+        I::Item: Serialize,
+    {
+        self.finalize(stream::iter_ok(records))
+    }
+}
+
+#[derive(Debug)]
+struct UploadState {
+    encoder: RecordEncoder,
+    buffer: Vec<u8>,
+    block_ids: Vec<Vec<u8>>,
+}
+
+#[derive(Debug)]
+struct UploadTarget {
+    client: Client,
+    container_name: String,
+    blob_name: String,
+    timeout: Option<u64>,
+    lease_id: Option<LeaseId>,
+    client_request_id: Option<String>,
+    extra_headers: Vec<(String, String)>,
+    api_version: Option<String>,
+}
+
+impl UploadTarget {
+    // uploads the buffered records, if any, as a new block
+    fn stage(&self, mut state: UploadState) -> impl Future<Item = UploadState, Error = AzureError> {
+        if state.buffer.is_empty() {
+            return Either::A(ok(state));
+        }
+
+        let block_id = block_id(state.block_ids.len());
+        let body = std::mem::take(&mut state.buffer);
+        debug!("staging block {:?} ({} bytes)", std::str::from_utf8(&block_id), body.len());
+
+        let mut builder = PutBlockBuilder::new(&self.client)
+            .with_container_name(&self.container_name)
+            .with_blob_name(&self.blob_name)
+            .with_block_id(&block_id)
+            .with_body(&body);
+        if let Some(timeout) = self.timeout {
+            builder = builder.with_timeout(timeout);
+        }
+        if let Some(lease_id) = &self.lease_id {
+            builder = builder.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = &self.client_request_id {
+            builder = builder.with_client_request_id(client_request_id);
+        }
+        for (name, value) in &self.extra_headers {
+            builder = builder.with_extra_header(name, value);
+        }
+        if let Some(api_version) = &self.api_version {
+            builder = builder.with_api_version(api_version);
+        }
+
+        Either::B(builder.finalize().map(move |_| {
+            state.block_ids.push(block_id);
+            state
+        }))
+    }
+
+    fn commit(
+        &self,
+        block_ids: Vec<Vec<u8>>,
+        content_type: String,
+        metadata: Option<Metadata>,
+    ) -> impl Future<Item = PutBlockListResponse, Error = AzureError> {
+        let block_list = BlockList {
+            blocks: block_ids.into_iter().map(BlobBlockType::Uncommitted).collect(),
+        };
+        let mut builder = PutBlockListBuilder::new(&self.client)
+            .with_container_name(&self.container_name)
+            .with_blob_name(&self.blob_name)
+            .with_block_list(&block_list)
+            .with_content_type(&content_type);
+        if let Some(metadata) = &metadata {
+            builder = builder.with_metadata(metadata);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.with_timeout(timeout);
+        }
+        if let Some(lease_id) = &self.lease_id {
+            builder = builder.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = &self.client_request_id {
+            builder = builder.with_client_request_id(client_request_id);
+        }
+        for (name, value) in &self.extra_headers {
+            builder = builder.with_extra_header(name, value);
+        }
+        if let Some(api_version) = &self.api_version {
+            builder = builder.with_api_version(api_version);
+        }
+
+        builder.finalize()
+    }
+}
+
+// Block ids must all have the same length. Twelve ASCII digits encode to
+// sixteen base64 characters with no padding and no '+' or '/', so they
+// can go in the query string as they are.
+fn block_id(index: usize) -> Vec<u8> {
+    format!("{:012}", index).into_bytes()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_block_id() {
+        assert_eq!(block_id(0), b"000000000000".to_vec());
+        for index in &[0, 1, 9, 10, 49_999, 123_456_789] {
+            let encoded = base64::encode(&block_id(*index));
+            assert_eq!(encoded.len(), 16);
+            assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric()));
+        }
+    }
+}
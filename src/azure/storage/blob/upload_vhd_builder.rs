@@ -0,0 +1,624 @@
+use crate::azure::core::ba512_range::BA512Range;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{
+    owned_headers, ApiVersionOption, ApiVersionSupport, BA512RangeSupport, BlobNameRequired, BlobNameSupport, BodySupport,
+    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, ExtraHeadersOption,
+    ExtraHeadersSupport, No, PageBlobLengthSupport, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::blob::vhd_footer::{VhdFooter, VHD_FOOTER_SIZE};
+use crate::azure::storage::client::{Blob as BlobTrait, Client};
+use futures::future;
+use futures::prelude::*;
+use futures::stream;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::Arc;
+#[derive(Debug, Clone)]
+pub struct UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+    parallelism: usize,
+}
+
+impl<'a> UploadVhdBuilder<'a, No, No> {
+    #[inline]
+    pub(crate) fn new(client: &'a Client) -> UploadVhdBuilder<'a, No, No> {
+        UploadVhdBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+            parallelism: 4,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequired<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, BlobNameSet> ContainerNameRequired<'a> for UploadVhdBuilder<'a, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet> BlobNameRequired<'a> for UploadVhdBuilder<'a, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutOption for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersOption<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionOption<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    #[inline]
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ContainerNameSupport<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadVhdBuilder<'a, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        UploadVhdBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            parallelism: self.parallelism,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> BlobNameSupport<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadVhdBuilder<'a, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        UploadVhdBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            parallelism: self.parallelism,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> TimeoutSupport for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        UploadVhdBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            parallelism: self.parallelism,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        UploadVhdBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+            parallelism: self.parallelism,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ExtraHeadersSupport<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, BlobNameSet> ApiVersionSupport<'a> for UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    type O = UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        UploadVhdBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+            parallelism: self.parallelism,
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, BlobNameSet> UploadVhdBuilder<'a, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+{
+    /// How many page ranges are sent at the same time (4 by default, at
+    /// least 1). Each one holds up to 4 MiB in memory.
+    pub fn with_parallelism(self, parallelism: usize) -> Self {
+        UploadVhdBuilder {
+            parallelism: parallelism.max(1),
+            ..self
+        }
+    }
+}
+
+impl<'a> UploadVhdBuilder<'a, Yes, Yes> {
+    /// Uploads the fixed VHD at `path`, footer included, to a page blob of
+    /// the same size, replacing the blob if it exists. The file is
+    /// validated before anything is sent. Only the pages holding data are
+    /// uploaded: the new blob reads as zeros, so the empty parts of the
+    /// disk (usually most of it) cost nothing.
+    pub fn finalize(self, path: &Path) -> impl Future<Item = UploadVhdResponse, Error = AzureError> {
+        let request = Arc::new(VhdRequest {
+            client: self.client().clone(),
+            container_name: self.container_name().to_owned(),
+            blob_name: self.blob_name().to_owned(),
+            timeout: self.timeout(),
+            client_request_id: self.client_request_id().map(|v| v.to_owned()),
+            extra_headers: owned_headers(self.extra_headers()),
+            api_version: self.api_version().map(|v| v.to_owned()),
+        });
+        let parallelism = self.parallelism;
+
+        future::result(open_vhd(path)).and_then(move |(file, footer)| {
+            let size = footer.current_size + VHD_FOOTER_SIZE;
+            let response = UploadVhdResponse {
+                footer,
+                size,
+                uploaded_bytes: 0,
+                ranges: 0,
+            };
+            request.create(size).and_then(move |_| {
+                let chunks = VhdChunks { file, offset: 0, size };
+                stream::iter_result(chunks)
+                    .map(stream::iter_ok)
+                    .flatten()
+                    .map(move |(range, body)| request.update(range, body))
+                    .buffer_unordered(parallelism)
+                    .fold(response, |mut response, bytes| {
+                        response.uploaded_bytes += bytes;
+                        response.ranges += 1;
+                        Ok::<_, AzureError>(response)
+                    })
+            })
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self, path: &Path) -> impl std::future::Future<Output = Result<UploadVhdResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize(path))
+    }
+}
+
+// the file is read in chunks of the largest page range a request can
+// update
+const CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+const PAGE_SIZE: usize = 512;
+
+/// The outcome of `UploadVhdBuilder::finalize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadVhdResponse {
+    pub footer: VhdFooter,
+    /// The size of the page blob: the disk followed by its footer.
+    pub size: u64,
+    /// The bytes of the pages holding data, the ones actually sent.
+    pub uploaded_bytes: u64,
+    pub ranges: usize,
+}
+
+// opens the file at `path`, validating its footer against its length
+fn open_vhd(path: &Path) -> Result<(File, VhdFooter), AzureError> {
+    let mut file = File::open(path)?;
+    let length = file.metadata()?.len();
+    if length < VHD_FOOTER_SIZE || length % PAGE_SIZE as u64 != 0 {
+        return Err(AzureError::InvalidVhd(format!(
+            "the file is {} bytes long, not a multiple of 512",
+            length
+        )));
+    }
+
+    let mut footer = vec![0; VHD_FOOTER_SIZE as usize];
+    file.seek(SeekFrom::Start(length - VHD_FOOTER_SIZE))?;
+    file.read_exact(&mut footer)?;
+    let footer = VhdFooter::parse(&footer)?;
+    if footer.current_size.checked_add(VHD_FOOTER_SIZE) != Some(length) {
+        return Err(AzureError::InvalidVhd(format!(
+            "the footer gives a disk of {} bytes but the file holds {}",
+            footer.current_size,
+            length - VHD_FOOTER_SIZE
+        )));
+    }
+
+    file.seek(SeekFrom::Start(0))?;
+    Ok((file, footer))
+}
+
+// the page ranges holding data, read one chunk at a time so that only
+// the ranges being uploaded are held in memory
+struct VhdChunks {
+    file: File,
+    offset: u64,
+    size: u64,
+}
+
+impl Iterator for VhdChunks {
+    type Item = Result<Vec<(BA512Range, Vec<u8>)>, AzureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.size {
+            return None;
+        }
+        let mut chunk = vec![0; CHUNK_SIZE.min(self.size - self.offset) as usize];
+        if let Err(error) = self.file.read_exact(&mut chunk) {
+            self.offset = self.size;
+            return Some(Err(error.into()));
+        }
+
+        let offset = self.offset;
+        self.offset += chunk.len() as u64;
+        // the pages, as the chunks, start at a multiple of 512
+        Some(Ok(non_zero_ranges(&chunk)
+            .into_iter()
+            .map(|(start, end)| {
+                let range = BA512Range::new(offset + start as u64, offset + end as u64 - 1).unwrap();
+                (range, chunk[start..end].to_vec())
+            })
+            .collect()))
+    }
+}
+
+// the [start, end) spans of consecutive pages of `chunk` that are not
+// all zeros
+fn non_zero_ranges(chunk: &[u8]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (index, page) in chunk.chunks(PAGE_SIZE).enumerate() {
+        if page.iter().all(|byte| *byte == 0) {
+            continue;
+        }
+        let (start, end) = (index * PAGE_SIZE, index * PAGE_SIZE + page.len());
+        match ranges.last_mut() {
+            Some(last) if last.1 == start => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+#[derive(Debug)]
+struct VhdRequest {
+    client: Client,
+    container_name: String,
+    blob_name: String,
+    timeout: Option<u64>,
+    client_request_id: Option<String>,
+    extra_headers: Vec<(String, String)>,
+    api_version: Option<String>,
+}
+
+impl VhdRequest {
+    fn create(&self, size: u64) -> impl Future<Item = (), Error = AzureError> {
+        let mut req = match self
+            .client
+            .put_page_blob()
+            .with_container_name(&self.container_name)
+            .with_blob_name(&self.blob_name)
+            .with_content_length(size)
+        {
+            Ok(req) => req,
+            Err(error) => return future::Either::A(future::err(error)),
+        };
+        if let Some(timeout) = self.timeout {
+            req = req.with_timeout(timeout);
+        }
+        if let Some(ref client_request_id) = self.client_request_id {
+            req = req.with_client_request_id(client_request_id);
+        }
+        for (name, value) in &self.extra_headers {
+            req = req.with_extra_header(name, value);
+        }
+        if let Some(ref api_version) = self.api_version {
+            req = req.with_api_version(api_version);
+        }
+        future::Either::B(req.finalize().map(|_| ()))
+    }
+
+    // resolves to the bytes sent
+    fn update(&self, range: BA512Range, body: Vec<u8>) -> impl Future<Item = u64, Error = AzureError> {
+        let mut req = self
+            .client
+            .update_page()
+            .with_container_name(&self.container_name)
+            .with_blob_name(&self.blob_name)
+            .with_ba512_range(&range)
+            .with_body(&body);
+        if let Some(timeout) = self.timeout {
+            req = req.with_timeout(timeout);
+        }
+        if let Some(ref client_request_id) = self.client_request_id {
+            req = req.with_client_request_id(client_request_id);
+        }
+        for (name, value) in &self.extra_headers {
+            req = req.with_extra_header(name, value);
+        }
+        if let Some(ref api_version) = self.api_version {
+            req = req.with_api_version(api_version);
+        }
+        let bytes = body.len() as u64;
+        req.finalize().map(move |_| bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::storage::blob::vhd_footer::test::footer;
+    use hyper::StatusCode;
+    use std::fs;
+
+    #[test]
+    fn test_non_zero_ranges() {
+        let mut chunk = vec![0u8; 8 * PAGE_SIZE];
+        chunk[PAGE_SIZE + 3] = 1;
+        chunk[3 * PAGE_SIZE] = 1;
+        chunk[5 * PAGE_SIZE - 1] = 1;
+        chunk[8 * PAGE_SIZE - 1] = 1;
+        assert_eq!(
+            non_zero_ranges(&chunk),
+            vec![
+                (PAGE_SIZE, 2 * PAGE_SIZE),
+                (3 * PAGE_SIZE, 5 * PAGE_SIZE),
+                (7 * PAGE_SIZE, 8 * PAGE_SIZE)
+            ]
+        );
+        assert!(non_zero_ranges(&[0; PAGE_SIZE]).is_empty());
+    }
+
+    #[test]
+    fn test_upload_vhd() {
+        let path = std::env::temp_dir().join(format!("upload_vhd_{}.vhd", std::process::id()));
+        let mut contents = vec![0u8; 16 * PAGE_SIZE];
+        contents[PAGE_SIZE..2 * PAGE_SIZE].copy_from_slice(&[1; PAGE_SIZE]);
+        contents[3 * PAGE_SIZE + 10] = 2;
+        contents[4 * PAGE_SIZE + 10] = 3;
+        contents.extend(footer(16 * PAGE_SIZE as u64));
+        fs::write(&path, &contents).unwrap();
+
+        let created = [
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-server-encrypted", "true"),
+        ];
+        let updated = [
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("content-md5", "XUFAKrxLKna5cZ2REBfFkg=="),
+            ("x-ms-blob-sequence-number", "0"),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-server-encrypted", "true"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::CREATED, &created, b"")
+                .with_response(StatusCode::CREATED, &updated, b"")
+                .with_response(StatusCode::CREATED, &updated, b"")
+                .with_response(StatusCode::CREATED, &updated, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+
+        let response = client
+            .upload_vhd()
+            .with_container_name("disks")
+            .with_blob_name("os.vhd")
+            .with_parallelism(2)
+            .finalize(&path)
+            .wait();
+        fs::remove_file(&path).unwrap();
+        let response = response.unwrap();
+        assert_eq!(response.size, 17 * PAGE_SIZE as u64);
+        assert_eq!(response.ranges, 3);
+        assert_eq!(response.uploaded_bytes, 4 * PAGE_SIZE as u64);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].headers["x-ms-blob-type"], "PageBlob");
+        assert_eq!(requests[0].headers["x-ms-blob-content-length"], "8704");
+        let mut ranges = requests[1..]
+            .iter()
+            .map(|request| request.headers["range"].to_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        ranges.sort();
+        assert_eq!(ranges, vec!["bytes=1536-2559", "bytes=512-1023", "bytes=8192-8703"]);
+    }
+
+    #[test]
+    fn test_upload_vhd_invalid() {
+        let path = std::env::temp_dir().join(format!("upload_vhd_invalid_{}.vhd", std::process::id()));
+        let mut contents = vec![0u8; 4 * PAGE_SIZE];
+        contents.extend(footer(8 * PAGE_SIZE as u64));
+        fs::write(&path, &contents).unwrap();
+
+        let transport = Arc::new(ReplayTransport::new());
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+        let response = client
+            .upload_vhd()
+            .with_container_name("disks")
+            .with_blob_name("os.vhd")
+            .finalize(&path)
+            .wait();
+        fs::remove_file(&path).unwrap();
+        match response {
+            Err(AzureError::InvalidVhd(msg)) => assert_eq!(msg, "the footer gives a disk of 4096 bytes but the file holds 2048"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn test_upload_vhd_size_overflow() {
+        let path = std::env::temp_dir().join(format!("upload_vhd_overflow_{}.vhd", std::process::id()));
+        let mut contents = vec![0u8; 4 * PAGE_SIZE];
+        contents.extend(footer(u64::MAX));
+        fs::write(&path, &contents).unwrap();
+
+        let transport = Arc::new(ReplayTransport::new());
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+        let response = client
+            .upload_vhd()
+            .with_container_name("disks")
+            .with_blob_name("os.vhd")
+            .finalize(&path)
+            .wait();
+        fs::remove_file(&path).unwrap();
+        match response {
+            Err(AzureError::InvalidVhd(msg)) => {
+                assert_eq!(msg, "the footer gives a disk of 18446744073709551615 bytes but the file holds 2048")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(transport.requests().is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::azure::core::errors::AzureError;
+use std::convert::TryInto;
+
+/// The length of the footer closing a VHD file.
+pub const VHD_FOOTER_SIZE: u64 = 512;
+
+const COOKIE: &[u8] = b"conectix";
+const CHECKSUM_OFFSET: usize = 64;
+const FIXED_DISK_TYPE: u32 = 2;
+
+/// The footer of a fixed VHD, the format of the disks and images of the
+/// virtual machines: the disk contents followed by this 512 byte footer.
+/// Such a file is uploaded as is to a page blob of the same size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VhdFooter {
+    /// The size of the disk when it was created.
+    pub original_size: u64,
+    /// The size of the disk, without the footer.
+    pub current_size: u64,
+    pub unique_id: [u8; 16],
+}
+
+impl VhdFooter {
+    /// Parses and validates the last 512 bytes of a VHD file: the cookie,
+    /// the checksum and the disk type, which must be fixed (the dynamic
+    /// and differencing disks must be converted first).
+    pub fn parse(footer: &[u8]) -> Result<VhdFooter, AzureError> {
+        if footer.len() != VHD_FOOTER_SIZE as usize {
+            return Err(AzureError::InvalidVhd(format!(
+                "the footer is {} bytes long instead of 512",
+                footer.len()
+            )));
+        }
+        if &footer[0..8] != COOKIE {
+            return Err(AzureError::InvalidVhd("the footer cookie is not conectix".to_owned()));
+        }
+
+        let checksum = be_u32(footer, CHECKSUM_OFFSET);
+        let computed = checksum_of(footer);
+        if checksum != computed {
+            return Err(AzureError::InvalidVhd(format!(
+                "the footer checksum is {:#x} instead of {:#x}",
+                checksum, computed
+            )));
+        }
+
+        let disk_type = be_u32(footer, 60);
+        if disk_type != FIXED_DISK_TYPE {
+            return Err(AzureError::InvalidVhd(format!(
+                "the disk type is {} instead of fixed ({})",
+                disk_type, FIXED_DISK_TYPE
+            )));
+        }
+
+        Ok(VhdFooter {
+            original_size: be_u64(footer, 40),
+            current_size: be_u64(footer, 48),
+            unique_id: footer[68..84].try_into().unwrap(),
+        })
+    }
+}
+
+// the one's complement of the sum of the footer bytes, the checksum
+// excluded
+pub(crate) fn checksum_of(footer: &[u8]) -> u32 {
+    let sum = footer
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i < CHECKSUM_OFFSET || *i >= CHECKSUM_OFFSET + 4)
+        .fold(0u32, |sum, (_, byte)| sum.wrapping_add(u32::from(*byte)));
+    !sum
+}
+
+fn be_u32(footer: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(footer[offset..offset + 4].try_into().unwrap())
+}
+
+fn be_u64(footer: &[u8], offset: usize) -> u64 {
+    u64::from_be_bytes(footer[offset..offset + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    pub(crate) fn footer(current_size: u64) -> Vec<u8> {
+        let mut footer = vec![0u8; VHD_FOOTER_SIZE as usize];
+        footer[0..8].copy_from_slice(COOKIE);
+        footer[12..16].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+        footer[16..24].copy_from_slice(&u64::MAX.to_be_bytes());
+        footer[40..48].copy_from_slice(&current_size.to_be_bytes());
+        footer[48..56].copy_from_slice(&current_size.to_be_bytes());
+        footer[60..64].copy_from_slice(&FIXED_DISK_TYPE.to_be_bytes());
+        footer[68..84].copy_from_slice(&[7; 16]);
+        let checksum = checksum_of(&footer);
+        footer[64..68].copy_from_slice(&checksum.to_be_bytes());
+        footer
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = VhdFooter::parse(&footer(1024 * 1024)).unwrap();
+        assert_eq!(parsed.current_size, 1024 * 1024);
+        assert_eq!(parsed.unique_id, [7; 16]);
+
+        let mut corrupted = footer(1024 * 1024);
+        corrupted[50] ^= 1;
+        assert!(VhdFooter::parse(&corrupted).is_err());
+
+        let mut dynamic = footer(1024 * 1024);
+        dynamic[63] = 3;
+        let checksum = checksum_of(&dynamic);
+        dynamic[64..68].copy_from_slice(&checksum.to_be_bytes());
+        match VhdFooter::parse(&dynamic) {
+            Err(AzureError::InvalidVhd(msg)) => assert_eq!(msg, "the disk type is 3 instead of fixed (2)"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -1,14 +1,45 @@
-use super::rest_client::{perform_request, ServiceType};
+use super::location_mode::LocationStage;
+use super::rest_client::{build_request, AccountKey, ServiceType, SigningStage, AZURE_VERSION};
+use crate::azure::core::bandwidth::{BandwidthLimiter, BandwidthStage};
+use crate::azure::core::cancellation::{CancellationStage, CancellationToken, TimeoutStage};
+use crate::azure::core::circuit_breaker::{CircuitBreaker, CircuitBreakerPolicy};
+use crate::azure::core::client_request_id::ClientRequestIdStage;
+use crate::azure::core::clock_skew::{ClockSkew, ClockSkewStage};
+use crate::azure::core::crypto::HmacKey;
 use crate::azure::core::errors::AzureError;
-use crate::azure::core::No;
-use crate::azure::storage::{blob, container};
+use crate::azure::core::http_client::{default_http_client, hyper_http_client, HttpClient, PoolConfig};
+use crate::azure::core::http_trace::{HttpObserver, HttpTraceStage};
+use crate::azure::core::pipeline::{self, LoggingStage, PipelineFuture, PipelineRequest, Policy, RetryStage, TelemetryHeadersStage};
+use crate::azure::core::proxy::ProxyConfig;
+use crate::azure::core::retry::{NoRetry, RetryPolicy};
+use crate::azure::core::sleeper::{Sleeper, ThreadSleeper};
+use crate::azure::core::telemetry::{LatencyStage, NoopObserver, TelemetryObserver};
+use crate::azure::core::throttling::{Throttler, ThrottlingPolicy, ThrottlingStage};
+use crate::azure::core::tls::TlsConfig;
+use crate::azure::core::{check_api_version, BlobNameSupport, ContainerNameSupport, No};
+use crate::azure::storage::blob::ClientSideEncryption;
+use crate::azure::storage::service::StorageServicePropertiesSupport;
+use crate::azure::storage::{
+    blob, container, file, ping, queue, service, static_website, usage_report, ConnectionString, CredentialsDiagnosis, LocationMode,
+    PingResponse, PropertiesCache, StaticWebsiteUpload, UsageReport,
+};
+use futures::{future, Future};
 use hyper::{self, Method};
-use hyper_tls;
+use serde::de::DeserializeOwned;
 use std::borrow::Borrow;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
+pub type ResponseFuture = Box<dyn Future<Item = hyper::Response<hyper::Body>, Error = AzureError> + Send>;
+
+pub type BlobContentFuture<T> = Box<dyn Future<Item = T, Error = AzureError> + Send>;
+
 pub trait Blob {
     fn list_blobs<'a>(&'a self) -> blob::requests::ListBlobBuilder<'a, No>;
+    fn list_blobs_flat<'a>(&'a self) -> blob::requests::ListBlobsFlatBuilder<'a, No>;
+    fn list_blobs_hierarchical<'a>(&'a self, delimiter: &'a str) -> blob::requests::ListBlobsHierarchicalBuilder<'a, No>;
     fn get_blob<'a>(&'a self) -> blob::requests::GetBlobBuilder<'a, No, No>;
     fn put_block_blob<'a>(&'a self) -> blob::requests::PutBlockBlobBuilder<'a, No, No, No>;
     fn put_page_blob<'a>(&'a self) -> blob::requests::PutPageBlobBuilder<'a, No, No, No>;
@@ -27,6 +58,36 @@ pub trait Blob {
     fn delete_blob<'a>(&'a self) -> blob::requests::DeleteBlobBuilder<'a, No, No, No>;
     fn stream_list_blobs<'a>(&'a self) -> blob::ListBlobStreamBuilder<'a, No>;
     fn stream_blob<'a>(&'a self) -> blob::BlobStreamBuilder<'a, No, No, No>;
+    fn download_blob_stream<'a>(&'a self) -> blob::DownloadBlobStreamBuilder<'a, No, No>;
+    fn set_blob_metadata<'a>(&'a self) -> blob::requests::SetBlobMetadataBuilder<'a, No, No>;
+    fn set_blob_properties<'a>(&'a self) -> blob::requests::SetBlobPropertiesBuilder<'a, No, No>;
+    fn set_blob_tier<'a>(&'a self) -> blob::requests::SetBlobTierBuilder<'a, No, No, No>;
+    fn set_blob_tags<'a>(&'a self) -> blob::requests::SetBlobTagsBuilder<'a, No, No, No>;
+    fn get_blob_tags<'a>(&'a self) -> blob::requests::GetBlobTagsBuilder<'a, No, No>;
+    fn find_blobs_by_tags<'a>(&'a self) -> blob::requests::FindBlobsByTagsBuilder<'a, No>;
+    fn set_blob_immutability_policy<'a>(&'a self) -> blob::requests::SetBlobImmutabilityPolicyBuilder<'a, No, No, No>;
+    fn delete_blob_immutability_policy<'a>(&'a self) -> blob::requests::DeleteBlobImmutabilityPolicyBuilder<'a, No, No>;
+    fn set_blob_legal_hold<'a>(&'a self) -> blob::requests::SetBlobLegalHoldBuilder<'a, No, No, No>;
+    fn upload_records<'a>(&'a self) -> blob::UploadRecordsBuilder<'a, No, No>;
+    fn get_blob_as_bytes(&self, container_name: &str, blob_name: &str) -> BlobContentFuture<Vec<u8>>;
+    fn get_blob_as_text(&self, container_name: &str, blob_name: &str) -> BlobContentFuture<String>;
+    fn get_blob_as_json<T>(&self, container_name: &str, blob_name: &str) -> BlobContentFuture<T>
+    where
+        T: DeserializeOwned + Send + 'static;
+    fn snapshot_blob<'a>(&'a self) -> blob::requests::SnapshotBlobBuilder<'a, No, No>;
+    fn get_blob_properties<'a>(&'a self) -> blob::requests::GetBlobPropertiesBuilder<'a, No, No>;
+    fn blob_exists<'a>(&'a self) -> blob::requests::BlobExistsBuilder<'a, No, No>;
+    fn get_blob_properties_cached(&self, container_name: &str, blob_name: &str) -> BlobContentFuture<blob::Blob>;
+    fn incremental_copy_blob<'a>(&'a self) -> blob::requests::IncrementalCopyBlobBuilder<'a, No, No, No>;
+    fn get_page_ranges<'a>(&'a self) -> blob::requests::GetPageRangesBuilder<'a, No, No>;
+    fn get_page_ranges_diff<'a>(&'a self) -> blob::requests::GetPageRangesDiffBuilder<'a, No, No, No>;
+    fn put_block_from_url<'a>(&'a self) -> blob::requests::PutBlockFromUrlBuilder<'a, No, No, No, No>;
+    fn blob_batch<'a>(&'a self) -> blob::BlobBatchBuilder<'a>;
+    fn blob_versions<'a>(&'a self) -> blob::BlobVersionsBuilder<'a, No, No>;
+    fn sync_directory<'a>(&'a self) -> blob::SyncDirectoryBuilder<'a, No>;
+    fn upload_vhd<'a>(&'a self) -> blob::UploadVhdBuilder<'a, No, No>;
+    fn copy_blob<'a>(&'a self) -> blob::requests::CopyBlobBuilder<'a, No, No, No>;
+    fn copy_blob_from_url<'a>(&'a self) -> blob::requests::CopyBlobFromUrlBuilder<'a, No, No, No>;
 }
 
 pub trait Container {
@@ -36,20 +97,96 @@ pub trait Container {
     fn get_container_acl<'a>(&'a self) -> container::requests::GetACLBuilder<'a, No>;
     fn set_container_acl<'a>(&'a self) -> container::requests::SetACLBuilder<'a, No, No>;
     fn get_container_properties<'a>(&'a self) -> container::requests::GetPropertiesBuilder<'a, No>;
+    fn container_exists<'a>(&'a self) -> container::requests::ExistsBuilder<'a, No>;
+    fn get_container_properties_cached(&self, container_name: &str) -> BlobContentFuture<container::Container>;
     fn acquire_container_lease<'a>(&'a self) -> container::requests::AcquireLeaseBuilder<'a, No, No>;
     fn renew_container_lease<'a>(&'a self) -> container::requests::RenewLeaseBuilder<'a, No, No>;
     fn release_container_lease<'a>(&'a self) -> container::requests::ReleaseLeaseBuilder<'a, No, No>;
     fn break_container_lease<'a>(&'a self) -> container::requests::BreakLeaseBuilder<'a, No>;
+    fn change_container_lease<'a>(&'a self) -> container::requests::ChangeLeaseBuilder<'a, No, No, No>;
+    /// Restores a soft-deleted container, as listed with
+    /// `list_containers().with_include_deleted()`.
+    fn restore_container<'a>(&'a self) -> container::requests::RestoreBuilder<'a, No, No, No>;
+    /// Walks every container and blob in the account and aggregates their
+    /// number and size by container, by top level prefix (as separated by
+    /// `delimiter`) and by access tier. This takes at least a list call
+    /// per container: for big accounts consider feeding the blob
+    /// inventory to `UsageReport::from_inventory_csv` instead.
+    fn usage_report(&self, delimiter: &str) -> BlobContentFuture<UsageReport>;
+    /// Uploads the files below `directory`, recursively, to the `$web`
+    /// container (see `Client::set_static_website`) with the content type
+    /// of their extension. The blob names are the paths relative to
+    /// `directory`; the existing blobs are overwritten but the blobs
+    /// without a matching file are kept.
+    fn upload_static_website(&self, directory: &Path) -> BlobContentFuture<StaticWebsiteUpload>;
+}
+
+pub trait Queue {
+    fn create_queue<'a>(&'a self) -> queue::requests::CreateQueueBuilder<'a, No>;
+    fn delete_queue<'a>(&'a self) -> queue::requests::DeleteQueueBuilder<'a, No>;
+    fn get_queue_metadata<'a>(&'a self) -> queue::requests::GetQueueMetadataBuilder<'a, No>;
+    fn set_queue_metadata<'a>(&'a self) -> queue::requests::SetQueueMetadataBuilder<'a, No>;
+    fn put_message<'a>(&'a self) -> queue::requests::PutMessageBuilder<'a, No, No>;
+    fn get_messages<'a>(&'a self) -> queue::requests::GetMessagesBuilder<'a, No>;
+    fn peek_messages<'a>(&'a self) -> queue::requests::PeekMessagesBuilder<'a, No>;
+    fn delete_message<'a>(&'a self) -> queue::requests::DeleteMessageBuilder<'a, No, No, No>;
+    fn update_message<'a>(&'a self) -> queue::requests::UpdateMessageBuilder<'a, No, No, No, No>;
+}
+
+pub trait File {
+    fn create_share<'a>(&'a self) -> file::requests::CreateShareBuilder<'a, No>;
+    fn delete_share<'a>(&'a self) -> file::requests::DeleteShareBuilder<'a, No>;
+    fn get_share_properties<'a>(&'a self) -> file::requests::GetSharePropertiesBuilder<'a, No>;
+    fn set_share_properties<'a>(&'a self) -> file::requests::SetSharePropertiesBuilder<'a, No, No>;
+    fn set_share_metadata<'a>(&'a self) -> file::requests::SetShareMetadataBuilder<'a, No>;
+    fn create_directory<'a>(&'a self) -> file::requests::CreateDirectoryBuilder<'a, No, No>;
+    fn create_file<'a>(&'a self) -> file::requests::CreateFileBuilder<'a, No, No, No>;
+    fn put_range<'a>(&'a self) -> file::requests::PutRangeBuilder<'a, No, No, No, No>;
+    fn get_file<'a>(&'a self) -> file::requests::GetFileBuilder<'a, No, No>;
+    fn list_directories_and_files<'a>(&'a self) -> file::requests::ListDirectoriesAndFilesBuilder<'a, No>;
 }
 
+/// The endpoint suffix of the storage accounts in the public cloud.
+pub const PUBLIC_ENDPOINT_SUFFIX: &str = "core.windows.net";
+/// The endpoint suffix of the storage accounts in Azure China.
+pub const CHINA_ENDPOINT_SUFFIX: &str = "core.chinacloudapi.cn";
+/// The endpoint suffix of the storage accounts in Azure US Government.
+pub const US_GOVERNMENT_ENDPOINT_SUFFIX: &str = "core.usgovcloudapi.net";
+/// The endpoint suffix of the storage accounts in Azure Germany.
+pub const GERMANY_ENDPOINT_SUFFIX: &str = "core.cloudapi.de";
+
 #[derive(Debug, Clone)]
 pub struct Client {
     account: String,
-    key: String,
+    key: AccountKey,
     sas_token: Option<Vec<(String, String)>>,
-    hc: hyper::Client<hyper_tls::HttpsConnector<hyper::client::HttpConnector>>,
+    bearer_token: Option<String>,
+    hc: Arc<dyn HttpClient>,
     blob_uri: String,
     table_uri: String,
+    queue_uri: String,
+    file_uri: String,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    sleeper: Arc<dyn Sleeper>,
+    retry_policy: Arc<dyn RetryPolicy>,
+    throttler: Option<Arc<Throttler>>,
+    bandwidth_limiter: Option<Arc<BandwidthLimiter>>,
+    clock_skew: Option<Arc<ClockSkew>>,
+    signing_diagnostics: bool,
+    client_request_ids: bool,
+    client_request_id_echo_required: bool,
+    api_version: Option<String>,
+    client_side_encryption: Option<ClientSideEncryption>,
+    policies: Vec<Arc<dyn Policy>>,
+    telemetry_observer: Arc<dyn TelemetryObserver>,
+    properties_cache: Option<Arc<PropertiesCache>>,
+    request_timeout: Option<Duration>,
+    cancellation_token: Option<CancellationToken>,
+    http_observer: Option<Arc<dyn HttpObserver>>,
+    location_mode: LocationMode,
+    proxy: Option<ProxyConfig>,
+    tls_config: TlsConfig,
+    pool_config: PoolConfig,
 }
 
 impl Blob for Client {
@@ -57,6 +194,14 @@ impl Blob for Client {
         blob::requests::ListBlobBuilder::new(self)
     }
 
+    fn list_blobs_flat<'a>(&'a self) -> blob::requests::ListBlobsFlatBuilder<'a, No> {
+        blob::requests::ListBlobsFlatBuilder::new(self)
+    }
+
+    fn list_blobs_hierarchical<'a>(&'a self, delimiter: &'a str) -> blob::requests::ListBlobsHierarchicalBuilder<'a, No> {
+        blob::requests::ListBlobsHierarchicalBuilder::new(self, delimiter)
+    }
+
     fn get_blob<'a>(&'a self) -> blob::requests::GetBlobBuilder<'a, No, No> {
         blob::requests::GetBlobBuilder::new(self)
     }
@@ -128,6 +273,155 @@ impl Blob for Client {
     fn stream_blob<'a>(&'a self) -> blob::BlobStreamBuilder<'a, No, No, No> {
         blob::BlobStreamBuilder::new(self)
     }
+
+    fn download_blob_stream<'a>(&'a self) -> blob::DownloadBlobStreamBuilder<'a, No, No> {
+        blob::DownloadBlobStreamBuilder::new(self)
+    }
+
+    fn set_blob_metadata<'a>(&'a self) -> blob::requests::SetBlobMetadataBuilder<'a, No, No> {
+        blob::requests::SetBlobMetadataBuilder::new(self)
+    }
+
+    fn set_blob_properties<'a>(&'a self) -> blob::requests::SetBlobPropertiesBuilder<'a, No, No> {
+        blob::requests::SetBlobPropertiesBuilder::new(self)
+    }
+
+    fn set_blob_tier<'a>(&'a self) -> blob::requests::SetBlobTierBuilder<'a, No, No, No> {
+        blob::requests::SetBlobTierBuilder::new(self)
+    }
+
+    fn set_blob_tags<'a>(&'a self) -> blob::requests::SetBlobTagsBuilder<'a, No, No, No> {
+        blob::requests::SetBlobTagsBuilder::new(self)
+    }
+
+    fn get_blob_tags<'a>(&'a self) -> blob::requests::GetBlobTagsBuilder<'a, No, No> {
+        blob::requests::GetBlobTagsBuilder::new(self)
+    }
+
+    fn find_blobs_by_tags<'a>(&'a self) -> blob::requests::FindBlobsByTagsBuilder<'a, No> {
+        blob::requests::FindBlobsByTagsBuilder::new(self)
+    }
+
+    fn set_blob_immutability_policy<'a>(&'a self) -> blob::requests::SetBlobImmutabilityPolicyBuilder<'a, No, No, No> {
+        blob::requests::SetBlobImmutabilityPolicyBuilder::new(self)
+    }
+
+    fn delete_blob_immutability_policy<'a>(&'a self) -> blob::requests::DeleteBlobImmutabilityPolicyBuilder<'a, No, No> {
+        blob::requests::DeleteBlobImmutabilityPolicyBuilder::new(self)
+    }
+
+    fn set_blob_legal_hold<'a>(&'a self) -> blob::requests::SetBlobLegalHoldBuilder<'a, No, No, No> {
+        blob::requests::SetBlobLegalHoldBuilder::new(self)
+    }
+
+    fn upload_records<'a>(&'a self) -> blob::UploadRecordsBuilder<'a, No, No> {
+        blob::UploadRecordsBuilder::new(self)
+    }
+
+    fn get_blob_as_bytes(&self, container_name: &str, blob_name: &str) -> BlobContentFuture<Vec<u8>> {
+        Box::new(
+            self.get_blob()
+                .with_container_name(container_name)
+                .with_blob_name(blob_name)
+                .finalize()
+                .map(|response| response.data),
+        )
+    }
+
+    fn get_blob_as_text(&self, container_name: &str, blob_name: &str) -> BlobContentFuture<String> {
+        Box::new(
+            self.get_blob()
+                .with_container_name(container_name)
+                .with_blob_name(blob_name)
+                .finalize()
+                .and_then(|response| response.text()),
+        )
+    }
+
+    fn get_blob_as_json<T>(&self, container_name: &str, blob_name: &str) -> BlobContentFuture<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        Box::new(
+            self.get_blob()
+                .with_container_name(container_name)
+                .with_blob_name(blob_name)
+                .finalize()
+                .and_then(|response| response.json()),
+        )
+    }
+
+    fn snapshot_blob<'a>(&'a self) -> blob::requests::SnapshotBlobBuilder<'a, No, No> {
+        blob::requests::SnapshotBlobBuilder::new(self)
+    }
+
+    fn get_blob_properties<'a>(&'a self) -> blob::requests::GetBlobPropertiesBuilder<'a, No, No> {
+        blob::requests::GetBlobPropertiesBuilder::new(self)
+    }
+
+    fn blob_exists<'a>(&'a self) -> blob::requests::BlobExistsBuilder<'a, No, No> {
+        blob::requests::BlobExistsBuilder::new(self)
+    }
+
+    fn get_blob_properties_cached(&self, container_name: &str, blob_name: &str) -> BlobContentFuture<blob::Blob> {
+        if let Some(blob) = self.properties_cache().and_then(|cache| cache.blob(container_name, blob_name)) {
+            return Box::new(future::ok(blob));
+        }
+
+        let cache = self.properties_cache.clone();
+        Box::new(
+            self.get_blob_properties()
+                .with_container_name(container_name)
+                .with_blob_name(blob_name)
+                .finalize()
+                .map(move |response| {
+                    if let Some(cache) = cache {
+                        cache.insert_blob(response.blob.clone());
+                    }
+                    response.blob
+                }),
+        )
+    }
+
+    fn incremental_copy_blob<'a>(&'a self) -> blob::requests::IncrementalCopyBlobBuilder<'a, No, No, No> {
+        blob::requests::IncrementalCopyBlobBuilder::new(self)
+    }
+
+    fn get_page_ranges<'a>(&'a self) -> blob::requests::GetPageRangesBuilder<'a, No, No> {
+        blob::requests::GetPageRangesBuilder::new(self)
+    }
+
+    fn get_page_ranges_diff<'a>(&'a self) -> blob::requests::GetPageRangesDiffBuilder<'a, No, No, No> {
+        blob::requests::GetPageRangesDiffBuilder::new(self)
+    }
+
+    fn put_block_from_url<'a>(&'a self) -> blob::requests::PutBlockFromUrlBuilder<'a, No, No, No, No> {
+        blob::requests::PutBlockFromUrlBuilder::new(self)
+    }
+
+    fn blob_batch<'a>(&'a self) -> blob::BlobBatchBuilder<'a> {
+        blob::BlobBatchBuilder::new(self)
+    }
+
+    fn blob_versions<'a>(&'a self) -> blob::BlobVersionsBuilder<'a, No, No> {
+        blob::BlobVersionsBuilder::new(self)
+    }
+
+    fn sync_directory<'a>(&'a self) -> blob::SyncDirectoryBuilder<'a, No> {
+        blob::SyncDirectoryBuilder::new(self)
+    }
+
+    fn upload_vhd<'a>(&'a self) -> blob::UploadVhdBuilder<'a, No, No> {
+        blob::UploadVhdBuilder::new(self)
+    }
+
+    fn copy_blob<'a>(&'a self) -> blob::requests::CopyBlobBuilder<'a, No, No, No> {
+        blob::requests::CopyBlobBuilder::new(self)
+    }
+
+    fn copy_blob_from_url<'a>(&'a self) -> blob::requests::CopyBlobFromUrlBuilder<'a, No, No, No> {
+        blob::requests::CopyBlobFromUrlBuilder::new(self)
+    }
 }
 
 impl Container for Client {
@@ -155,6 +449,29 @@ impl Container for Client {
         container::requests::GetPropertiesBuilder::new(self)
     }
 
+    fn container_exists<'a>(&'a self) -> container::requests::ExistsBuilder<'a, No> {
+        container::requests::ExistsBuilder::new(self)
+    }
+
+    fn get_container_properties_cached(&self, container_name: &str) -> BlobContentFuture<container::Container> {
+        if let Some(container) = self.properties_cache().and_then(|cache| cache.container(container_name)) {
+            return Box::new(future::ok(container));
+        }
+
+        let cache = self.properties_cache.clone();
+        Box::new(
+            self.get_container_properties()
+                .with_container_name(container_name)
+                .finalize()
+                .map(move |response| {
+                    if let Some(cache) = cache {
+                        cache.insert_container(response.container.clone());
+                    }
+                    response.container
+                }),
+        )
+    }
+
     fn acquire_container_lease<'a>(&'a self) -> container::requests::AcquireLeaseBuilder<'a, No, No> {
         container::requests::AcquireLeaseBuilder::new(self)
     }
@@ -170,6 +487,102 @@ impl Container for Client {
     fn break_container_lease<'a>(&'a self) -> container::requests::BreakLeaseBuilder<'a, No> {
         container::requests::BreakLeaseBuilder::new(self)
     }
+
+    fn change_container_lease<'a>(&'a self) -> container::requests::ChangeLeaseBuilder<'a, No, No, No> {
+        container::requests::ChangeLeaseBuilder::new(self)
+    }
+
+    fn restore_container<'a>(&'a self) -> container::requests::RestoreBuilder<'a, No, No, No> {
+        container::requests::RestoreBuilder::new(self)
+    }
+
+    fn usage_report(&self, delimiter: &str) -> BlobContentFuture<UsageReport> {
+        Box::new(usage_report::usage_report(self, delimiter))
+    }
+
+    fn upload_static_website(&self, directory: &Path) -> BlobContentFuture<StaticWebsiteUpload> {
+        Box::new(static_website::upload_static_website(self, directory))
+    }
+}
+
+impl Queue for Client {
+    fn create_queue<'a>(&'a self) -> queue::requests::CreateQueueBuilder<'a, No> {
+        queue::requests::CreateQueueBuilder::new(self)
+    }
+
+    fn delete_queue<'a>(&'a self) -> queue::requests::DeleteQueueBuilder<'a, No> {
+        queue::requests::DeleteQueueBuilder::new(self)
+    }
+
+    fn get_queue_metadata<'a>(&'a self) -> queue::requests::GetQueueMetadataBuilder<'a, No> {
+        queue::requests::GetQueueMetadataBuilder::new(self)
+    }
+
+    fn set_queue_metadata<'a>(&'a self) -> queue::requests::SetQueueMetadataBuilder<'a, No> {
+        queue::requests::SetQueueMetadataBuilder::new(self)
+    }
+
+    fn put_message<'a>(&'a self) -> queue::requests::PutMessageBuilder<'a, No, No> {
+        queue::requests::PutMessageBuilder::new(self)
+    }
+
+    fn get_messages<'a>(&'a self) -> queue::requests::GetMessagesBuilder<'a, No> {
+        queue::requests::GetMessagesBuilder::new(self)
+    }
+
+    fn peek_messages<'a>(&'a self) -> queue::requests::PeekMessagesBuilder<'a, No> {
+        queue::requests::PeekMessagesBuilder::new(self)
+    }
+
+    fn delete_message<'a>(&'a self) -> queue::requests::DeleteMessageBuilder<'a, No, No, No> {
+        queue::requests::DeleteMessageBuilder::new(self)
+    }
+
+    fn update_message<'a>(&'a self) -> queue::requests::UpdateMessageBuilder<'a, No, No, No, No> {
+        queue::requests::UpdateMessageBuilder::new(self)
+    }
+}
+
+impl File for Client {
+    fn create_share<'a>(&'a self) -> file::requests::CreateShareBuilder<'a, No> {
+        file::requests::CreateShareBuilder::new(self)
+    }
+
+    fn delete_share<'a>(&'a self) -> file::requests::DeleteShareBuilder<'a, No> {
+        file::requests::DeleteShareBuilder::new(self)
+    }
+
+    fn get_share_properties<'a>(&'a self) -> file::requests::GetSharePropertiesBuilder<'a, No> {
+        file::requests::GetSharePropertiesBuilder::new(self)
+    }
+
+    fn set_share_properties<'a>(&'a self) -> file::requests::SetSharePropertiesBuilder<'a, No, No> {
+        file::requests::SetSharePropertiesBuilder::new(self)
+    }
+
+    fn set_share_metadata<'a>(&'a self) -> file::requests::SetShareMetadataBuilder<'a, No> {
+        file::requests::SetShareMetadataBuilder::new(self)
+    }
+
+    fn create_directory<'a>(&'a self) -> file::requests::CreateDirectoryBuilder<'a, No, No> {
+        file::requests::CreateDirectoryBuilder::new(self)
+    }
+
+    fn create_file<'a>(&'a self) -> file::requests::CreateFileBuilder<'a, No, No, No> {
+        file::requests::CreateFileBuilder::new(self)
+    }
+
+    fn put_range<'a>(&'a self) -> file::requests::PutRangeBuilder<'a, No, No, No, No> {
+        file::requests::PutRangeBuilder::new(self)
+    }
+
+    fn get_file<'a>(&'a self) -> file::requests::GetFileBuilder<'a, No, No> {
+        file::requests::GetFileBuilder::new(self)
+    }
+
+    fn list_directories_and_files<'a>(&'a self) -> file::requests::ListDirectoriesAndFilesBuilder<'a, No> {
+        file::requests::ListDirectoriesAndFilesBuilder::new(self)
+    }
 }
 
 impl Client {
@@ -178,63 +591,662 @@ impl Client {
     }
 
     pub fn azure_sas(account: &str, sas_token: &str) -> Result<Client, AzureError> {
-        let client = hyper::Client::builder().build(hyper_tls::HttpsConnector::new(4)?);
-        let params: Vec<(String, String)> = Url::options()
-            // Any base url will do: we just need to parse the SAS token
-            // to get its query pairs.
-            .base_url(Some(&Url::parse("https://blob.core.windows.net").unwrap()))
-            .parse(sas_token).unwrap()
-            .query_pairs()
-            .map(|p| (String::from(p.0), String::from(p.1)))
-            .collect();
+        Ok(Client::account_client(
+            account,
+            AccountKey::default(),
+            Some(parse_sas_token(sas_token)),
+            default_http_client()?,
+        ))
+    }
 
+    /// Authenticates the requests with an Azure AD access token (for the
+    /// `https://storage.azure.com/` resource) instead of the account key.
+    /// The token is not refreshed: build a new client before it expires.
+    /// Required by `get_user_delegation_key`.
+    pub fn azure_bearer_token(account: &str, bearer_token: &str) -> Result<Client, AzureError> {
+        let client = Client::azure(account, "")?;
         Ok(Client {
-            account: account.to_owned(),
-            key: String::new(),
-            sas_token: Some(params),
-            hc: client,
-            blob_uri: format!("https://{}.blob.core.windows.net", account),
-            table_uri: format!("https://{}.table.core.windows.net", account),
+            bearer_token: Some(bearer_token.to_owned()),
+            ..client
         })
     }
 
     pub fn azure(account: &str, key: &str) -> Result<Client, AzureError> {
-        let client = hyper::Client::builder().build(hyper_tls::HttpsConnector::new(4)?);
+        Ok(Client::account_client(account, AccountKey::new(key), None, default_http_client()?))
+    }
 
-        Ok(Client {
+    /// Starts a `ClientBuilder`, to configure the client in one go.
+    pub fn builder(account: &str) -> ClientBuilder {
+        ClientBuilder::new(account)
+    }
+
+    // the client of an account in the public cloud
+    fn account_client(account: &str, key: AccountKey, sas_token: Option<Vec<(String, String)>>, hc: Arc<dyn HttpClient>) -> Client {
+        Client {
             account: account.to_owned(),
-            key: key.to_owned(),
-            sas_token: None,
-            hc: client,
-            blob_uri: format!("https://{}.blob.core.windows.net", account),
-            table_uri: format!("https://{}.table.core.windows.net", account),
-        })
+            key,
+            sas_token,
+            bearer_token: None,
+            hc,
+            blob_uri: service_uri("https", account, "blob", PUBLIC_ENDPOINT_SUFFIX),
+            table_uri: service_uri("https", account, "table", PUBLIC_ENDPOINT_SUFFIX),
+            queue_uri: service_uri("https", account, "queue", PUBLIC_ENDPOINT_SUFFIX),
+            file_uri: service_uri("https", account, "file", PUBLIC_ENDPOINT_SUFFIX),
+            circuit_breaker: None,
+            sleeper: Arc::new(ThreadSleeper),
+            retry_policy: Arc::new(NoRetry),
+            throttler: None,
+            bandwidth_limiter: None,
+            clock_skew: None,
+            signing_diagnostics: false,
+            client_request_ids: true,
+            client_request_id_echo_required: false,
+            api_version: None,
+            client_side_encryption: None,
+            policies: Vec::new(),
+            telemetry_observer: Arc::new(NoopObserver),
+            properties_cache: None,
+            request_timeout: None,
+            cancellation_token: None,
+            http_observer: None,
+            location_mode: LocationMode::PrimaryOnly,
+            proxy: None,
+            tls_config: TlsConfig::default(),
+            pool_config: PoolConfig::default(),
+        }
     }
 
+    /// The emulator (Azurite or the legacy Storage Emulator) listening on
+    /// its default ports: `http://127.0.0.1:10000/devstoreaccount1` for
+    /// the blobs, 10001 for the queues and 10002 for the tables.
+    pub fn local_emulator() -> Result<Client, AzureError> {
+        Client::emulator(&Url::parse("http://127.0.0.1:10000")?, &Url::parse("http://127.0.0.1:10002")?)
+    }
+
+    /// The emulator at the given endpoints, for example
+    /// `http://azurite:10000` in a container network. The requests are
+    /// addressed path-style and signed with the well-known
+    /// `devstoreaccount1` key.
     pub fn emulator(blob_storage_url: &Url, table_storage_url: &Url) -> Result<Client, AzureError> {
-        let client = hyper::Client::builder().build(hyper_tls::HttpsConnector::new(4)?);
+        let client = default_http_client()?;
 
         let blob_uri = format!("{}devstoreaccount1", blob_storage_url.as_str());
         debug!("blob_uri == {}", blob_uri);
         let table_uri = format!("{}devstoreaccount1", table_storage_url.as_str());
         debug!("table_uri == {}", table_uri);
+        // the emulator listens for queue requests on the port following
+        // the blob one (10000 and 10001 by default)
+        let mut queue_storage_url = blob_storage_url.clone();
+        let queue_port = blob_storage_url.port_or_known_default().map(|port| port + 1);
+        queue_storage_url
+            .set_port(queue_port)
+            .map_err(|_| AzureError::GenericErrorWithText(format!("invalid emulator url {}", blob_storage_url)))?;
+        let queue_uri = format!("{}devstoreaccount1", queue_storage_url.as_str());
+        debug!("queue_uri == {}", queue_uri);
+        // the emulator does not implement the file service: the requests
+        // will fail as they would against any unknown endpoint
+        let file_uri = format!("{}devstoreaccount1", blob_storage_url.as_str());
 
         Ok(Client {
             account: "devstoreaccount1".to_owned(),
-            key: "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==".to_owned(),
+            key: AccountKey::new("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw=="),
             sas_token: None,
+            bearer_token: None,
             hc: client,
             blob_uri,
             table_uri,
+            queue_uri,
+            file_uri,
+            circuit_breaker: None,
+            sleeper: Arc::new(ThreadSleeper),
+            retry_policy: Arc::new(NoRetry),
+            throttler: None,
+            bandwidth_limiter: None,
+            clock_skew: None,
+            signing_diagnostics: false,
+            client_request_ids: true,
+            client_request_id_echo_required: false,
+            api_version: None,
+            client_side_encryption: None,
+            policies: Vec::new(),
+            telemetry_observer: Arc::new(NoopObserver),
+            properties_cache: None,
+            request_timeout: None,
+            cancellation_token: None,
+            http_observer: None,
+            location_mode: LocationMode::PrimaryOnly,
+            proxy: None,
+            tls_config: TlsConfig::default(),
+            pool_config: PoolConfig::default(),
+        })
+    }
+
+    /// Builds the client from a storage connection string (see
+    /// `ConnectionString`). The account is authenticated with the
+    /// `AccountKey` if present, with the `SharedAccessSignature`
+    /// otherwise. The explicit `*Endpoint` settings take precedence over
+    /// the endpoints derived from `DefaultEndpointsProtocol` and
+    /// `EndpointSuffix`. `UseDevelopmentStorage=true` targets the emulator
+    /// on its default ports.
+    pub fn from_connection_string(connection_string: &str) -> Result<Client, AzureError> {
+        let connection_string = ConnectionString::parse(connection_string)?;
+
+        if connection_string.use_development_storage {
+            let proxy_uri = Url::parse(connection_string.development_storage_proxy_uri.unwrap_or("http://127.0.0.1"))?;
+            let emulator_url = |port: u16| -> Result<Url, AzureError> {
+                let mut url = proxy_uri.clone();
+                url.set_port(Some(port))
+                    .map_err(|_| AzureError::InputParametersError(format!("invalid development storage proxy uri {}", proxy_uri)))?;
+                Ok(url)
+            };
+            return Client::emulator(&emulator_url(10000)?, &emulator_url(10002)?);
+        }
+
+        // without AccountName the account is the first label of the blob
+        // endpoint host
+        let account = match connection_string.account_name {
+            Some(account) => account.to_owned(),
+            None => connection_string
+                .blob_endpoint
+                .and_then(|endpoint| Url::parse(endpoint).ok())
+                .and_then(|endpoint| endpoint.host_str().and_then(|host| host.split('.').next()).map(str::to_owned))
+                .ok_or_else(|| AzureError::InputParametersError("invalid connection string: AccountName is missing".to_owned()))?,
+        };
+
+        let client = match (connection_string.account_key, connection_string.sas) {
+            (Some(key), _) => Client::azure(&account, key)?,
+            // azure_sas expects the token as a query string
+            (None, Some(sas)) => Client::azure_sas(&account, &format!("?{}", sas.trim_start_matches('?')))?,
+            (None, None) => {
+                return Err(AzureError::InputParametersError(
+                    "invalid connection string: neither AccountKey nor SharedAccessSignature is present".to_owned(),
+                ));
+            }
+        };
+
+        let protocol = connection_string.default_endpoints_protocol.unwrap_or("https");
+        let suffix = connection_string.endpoint_suffix.unwrap_or(PUBLIC_ENDPOINT_SUFFIX);
+        let endpoint = |explicit: Option<&str>, service: &str| match explicit {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_owned(),
+            None => service_uri(protocol, &account, service, suffix),
+        };
+
+        Ok(Client {
+            blob_uri: endpoint(connection_string.blob_endpoint, "blob"),
+            table_uri: endpoint(connection_string.table_endpoint, "table"),
+            queue_uri: endpoint(connection_string.queue_endpoint, "queue"),
+            file_uri: endpoint(connection_string.file_endpoint, "file"),
+            ..client
+        })
+    }
+
+    /// Addresses the account in another cloud, for example
+    /// `CHINA_ENDPOINT_SUFFIX` for `https://<account>.blob.core.chinacloudapi.cn`.
+    pub fn with_endpoint_suffix(self, endpoint_suffix: &str) -> Client {
+        Client {
+            blob_uri: service_uri("https", &self.account, "blob", endpoint_suffix),
+            table_uri: service_uri("https", &self.account, "table", endpoint_suffix),
+            queue_uri: service_uri("https", &self.account, "queue", endpoint_suffix),
+            file_uri: service_uri("https", &self.account, "file", endpoint_suffix),
+            ..self
+        }
+    }
+
+    /// Sends the blob requests to `blob_uri` instead, for example a
+    /// private endpoint or a custom domain. The requests are still signed
+    /// for the account of the client.
+    pub fn with_blob_uri(self, blob_uri: &str) -> Client {
+        Client {
+            blob_uri: blob_uri.trim_end_matches('/').to_owned(),
+            ..self
+        }
+    }
+
+    /// As `with_blob_uri`, for the table requests.
+    pub fn with_table_uri(self, table_uri: &str) -> Client {
+        Client {
+            table_uri: table_uri.trim_end_matches('/').to_owned(),
+            ..self
+        }
+    }
+
+    /// As `with_blob_uri`, for the queue requests.
+    pub fn with_queue_uri(self, queue_uri: &str) -> Client {
+        Client {
+            queue_uri: queue_uri.trim_end_matches('/').to_owned(),
+            ..self
+        }
+    }
+
+    /// As `with_blob_uri`, for the file requests.
+    pub fn with_file_uri(self, file_uri: &str) -> Client {
+        Client {
+            file_uri: file_uri.trim_end_matches('/').to_owned(),
+            ..self
+        }
+    }
+
+    /// Enables a circuit breaker shared by all the requests sent by this
+    /// client (and its clones).
+    pub fn with_circuit_breaker(self, policy: CircuitBreakerPolicy) -> Client {
+        Client {
+            circuit_breaker: Some(Arc::new(CircuitBreaker::new(policy))),
+            ..self
+        }
+    }
+
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreaker> {
+        self.circuit_breaker.as_ref().map(|cb| cb.as_ref())
+    }
+
+    /// Replaces the timer used when the client has to wait (for example
+    /// between retries). Defaults to `ThreadSleeper`.
+    pub fn with_sleeper(self, sleeper: Arc<dyn Sleeper>) -> Client {
+        Client { sleeper, ..self }
+    }
+
+    pub fn sleeper(&self) -> &Arc<dyn Sleeper> {
+        &self.sleeper
+    }
+
+    /// Sets the policy used to retry the requests failed with a transient
+    /// error. The waits go through the client `Sleeper`. Defaults to
+    /// `NoRetry`.
+    pub fn with_retry_policy(self, retry_policy: Arc<dyn RetryPolicy>) -> Client {
+        Client { retry_policy, ..self }
+    }
+
+    pub fn retry_policy(&self) -> &Arc<dyn RetryPolicy> {
+        &self.retry_policy
+    }
+
+    /// Enables the adaptive throttling of the requests: once an account
+    /// answers `503 Server Busy` or `429 Too Many Requests`, the following
+    /// requests to it are delayed, and no more than the configured number
+    /// of requests per account are sent at the same time, or per second.
+    /// The state is shared with the clones of this client and applies to
+    /// every attempt, retries included. See `ThrottlingPolicy`.
+    pub fn with_throttling(self, policy: ThrottlingPolicy) -> Client {
+        Client {
+            throttler: Some(Arc::new(Throttler::new(policy))),
+            ..self
+        }
+    }
+
+    pub fn throttler(&self) -> Option<&Throttler> {
+        self.throttler.as_ref().map(|throttler| throttler.as_ref())
+    }
+
+    /// Paces the request and response bodies, as they are transferred, so
+    /// that the bytes sent and received stay within `bytes_per_second` on
+    /// average, for example to keep a background backup from saturating
+    /// the network link. The budget is shared with the clones of this
+    /// client and covers every attempt, retries included. See
+    /// `BandwidthLimiter`.
+    pub fn with_bandwidth_limit(self, bytes_per_second: u64) -> Client {
+        Client {
+            bandwidth_limiter: Some(Arc::new(BandwidthLimiter::new(bytes_per_second))),
+            ..self
+        }
+    }
+
+    pub fn bandwidth_limiter(&self) -> Option<&BandwidthLimiter> {
+        self.bandwidth_limiter.as_ref().map(|limiter| limiter.as_ref())
+    }
+
+    /// Fails the attempts not answered within `timeout` with
+    /// `AzureError::TimeoutError`, for example on a hung connection. A
+    /// timed out attempt is retried according to the retry policy. The
+    /// wait goes through the client `Sleeper`. Unlike `with_timeout` on
+    /// the builders, which bounds the processing on the service side, it
+    /// is enforced by the client. It covers each attempt until the
+    /// response headers arrive, not the streaming of the body.
+    pub fn with_request_timeout(self, timeout: Duration) -> Client {
+        Client {
+            request_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// Aborts the requests of this client (and its clones) once `token`
+    /// is cancelled. Clone the client with a token of its own to cancel
+    /// a single operation. See `CancellationToken`, and its limits with
+    /// the response bodies.
+    pub fn with_cancellation_token(self, token: CancellationToken) -> Client {
+        Client {
+            cancellation_token: Some(token),
+            ..self
+        }
+    }
+
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    /// Compensates the skew of the local clock: when a request is
+    /// rejected because its date is too far from the service time, the
+    /// offset is measured from the response `Date` and the request is
+    /// signed again and resent, once. The following requests are dated
+    /// with the corrected time. Skews larger than `max_offset` are not
+    /// compensated. The offset is shared with the clones of this client.
+    pub fn with_clock_skew_compensation(self, max_offset: Duration) -> Client {
+        Client {
+            clock_skew: Some(Arc::new(ClockSkew::new(max_offset))),
+            ..self
+        }
+    }
+
+    pub fn clock_skew(&self) -> Option<&ClockSkew> {
+        self.clock_skew.as_ref().map(|clock_skew| clock_skew.as_ref())
+    }
+
+    /// Logs (at the warn level) the string to sign and the canonicalized
+    /// headers of the requests rejected with 403, next to the string to
+    /// sign reported by the service, to track down signature mismatches.
+    /// The signatures and the keys are redacted. Meant for debugging: the
+    /// body of the 403 responses is buffered.
+    pub fn with_signing_diagnostics(self) -> Client {
+        Client {
+            signing_diagnostics: true,
+            ..self
+        }
+    }
+
+    pub fn signing_diagnostics(&self) -> bool {
+        self.signing_diagnostics
+    }
+
+    /// Sends the reads of a geo-redundant (RA-GRS) account to the
+    /// `-secondary` endpoint, always or when the primary fails. See
+    /// `LocationMode`. Defaults to `LocationMode::PrimaryOnly`.
+    pub fn with_location_mode(self, location_mode: LocationMode) -> Client {
+        Client { location_mode, ..self }
+    }
+
+    pub fn location_mode(&self) -> LocationMode {
+        self.location_mode
+    }
+
+    /// Stops giving the requests sent without an `x-ms-client-request-id`
+    /// (see `ClientRequestIdSupport`) a generated UUID. The retries of a
+    /// request share its id, which the service logs and echoes: the
+    /// responses and `AzureError::client_request_id` return it.
+    pub fn without_client_request_ids(self) -> Client {
+        Client {
+            client_request_ids: false,
+            ..self
+        }
+    }
+
+    pub fn client_request_ids(&self) -> bool {
+        self.client_request_ids
+    }
+
+    /// Fails the requests whose successful response does not echo their
+    /// `x-ms-client-request-id` with `AzureError::ClientRequestIdMismatch`,
+    /// for example when a proxy mixes the responses up. The requests
+    /// failing with an error status keep their own error.
+    pub fn with_client_request_id_echo_required(self) -> Client {
+        Client {
+            client_request_id_echo_required: true,
+            ..self
+        }
+    }
+
+    pub fn client_request_id_echo_required(&self) -> bool {
+        self.client_request_id_echo_required
+    }
+
+    /// Sends the requests with this `x-ms-version` (a date, for example
+    /// `2019-02-02`) instead of `AZURE_VERSION`. The requests needing a
+    /// newer version for the options they use are sent with it, and a
+    /// builder can choose another version with `with_api_version`.
+    pub fn with_api_version(self, api_version: &str) -> Result<Client, AzureError> {
+        check_api_version(api_version)?;
+        Ok(Client {
+            api_version: Some(api_version.to_owned()),
+            ..self
+        })
+    }
+
+    pub fn api_version(&self) -> &str {
+        self.api_version.as_deref().unwrap_or(AZURE_VERSION)
+    }
+
+    /// Encrypts the blobs uploaded with `put_block_blob` and decrypts the
+    /// encrypted blobs downloaded with `get_blob`. See
+    /// `ClientSideEncryption`.
+    pub fn with_client_side_encryption(self, client_side_encryption: ClientSideEncryption) -> Client {
+        Client {
+            client_side_encryption: Some(client_side_encryption),
+            ..self
+        }
+    }
+
+    pub fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.client_side_encryption.as_ref()
+    }
+
+    /// Appends a policy to the request pipeline. The pipeline adds the
+    /// telemetry headers, applies the retry policy and the throttling,
+    /// runs the policies added here (in order) for each attempt, logs and
+    /// finally signs the request. See `Policy`.
+    pub fn with_policy(mut self, policy: Arc<dyn Policy>) -> Client {
+        self.policies.push(policy);
+        self
+    }
+
+    pub fn policies(&self) -> &[Arc<dyn Policy>] {
+        &self.policies
+    }
+
+    /// Sends the requests with `http_client` instead of the default hyper
+    /// client, for example to use another HTTP stack or, in tests, to
+    /// answer them without a network. It is shared with the clones of
+    /// this client.
+    pub fn with_http_client(self, http_client: Arc<dyn HttpClient>) -> Client {
+        Client { hc: http_client, ..self }
+    }
+
+    /// Sends the requests through `proxy` (see `ProxyConfig`) with a new
+    /// hyper client, instead of the proxy of the environment, if any.
+    pub fn with_proxy(self, proxy: ProxyConfig) -> Result<Client, AzureError> {
+        Ok(Client {
+            hc: hyper_http_client(Some(proxy.clone()), &self.tls_config, &self.pool_config)?,
+            proxy: Some(proxy),
+            ..self
         })
     }
 
+    /// The proxy set with `with_proxy`.
+    pub fn proxy(&self) -> Option<&ProxyConfig> {
+        self.proxy.as_ref()
+    }
+
+    /// Sends the requests with a new hyper client using the TLS settings
+    /// of `tls_config`, for example to trust a private certificate
+    /// authority. The proxy set with `with_proxy` (or, failing that, the
+    /// one of the environment) is kept.
+    pub fn with_tls_config(self, tls_config: TlsConfig) -> Result<Client, AzureError> {
+        let proxy = self.proxy.clone().or_else(ProxyConfig::from_env);
+        Ok(Client {
+            hc: hyper_http_client(proxy, &tls_config, &self.pool_config)?,
+            tls_config,
+            ..self
+        })
+    }
+
+    pub fn tls_config(&self) -> &TlsConfig {
+        &self.tls_config
+    }
+
+    /// Sends the requests with a new hyper client using the connection
+    /// pool settings of `pool_config`, for example to keep more idle
+    /// connections for parallel uploads. The proxy and the TLS settings
+    /// are kept, as in `with_tls_config`.
+    pub fn with_pool_config(self, pool_config: PoolConfig) -> Result<Client, AzureError> {
+        let proxy = self.proxy.clone().or_else(ProxyConfig::from_env);
+        Ok(Client {
+            hc: hyper_http_client(proxy, &self.tls_config, &pool_config)?,
+            pool_config,
+            ..self
+        })
+    }
+
+    pub fn pool_config(&self) -> &PoolConfig {
+        &self.pool_config
+    }
+
+    pub fn http_client(&self) -> &Arc<dyn HttpClient> {
+        &self.hc
+    }
+
+    /// Sets the observer receiving the measurements collected by this
+    /// client (and its clones), the duration of every request included
+    /// (see `LatencyObserver`). Defaults to `NoopObserver`.
+    pub fn with_telemetry_observer(self, telemetry_observer: Arc<dyn TelemetryObserver>) -> Client {
+        Client {
+            telemetry_observer,
+            ..self
+        }
+    }
+
+    pub fn telemetry_observer(&self) -> &Arc<dyn TelemetryObserver> {
+        &self.telemetry_observer
+    }
+
+    /// Sets the observer following every HTTP request sent by this client
+    /// (and its clones), retries included, with its sanitized url, its
+    /// client request id, the request id of the response, its status and
+    /// its latency. See `HttpObserver`.
+    pub fn with_http_observer(self, http_observer: Arc<dyn HttpObserver>) -> Client {
+        Client {
+            http_observer: Some(http_observer),
+            ..self
+        }
+    }
+
+    pub fn http_observer(&self) -> Option<&Arc<dyn HttpObserver>> {
+        self.http_observer.as_ref()
+    }
+
+    /// Enables the container and blob properties cache used by the
+    /// `get_*_properties_cached` calls. The cache is shared with the
+    /// clones of this client.
+    pub fn with_properties_cache(self, ttl: Duration) -> Client {
+        Client {
+            properties_cache: Some(Arc::new(PropertiesCache::new(ttl))),
+            ..self
+        }
+    }
+
+    pub fn properties_cache(&self) -> Option<&PropertiesCache> {
+        self.properties_cache.as_ref().map(|cache| cache.as_ref())
+    }
+
+    /// Reads the logging, metrics, CORS (and, for the blob service,
+    /// delete retention and static website) settings of a service.
+    pub fn get_service_properties<'a>(
+        &'a self,
+        storage_service: service::StorageService,
+    ) -> service::requests::GetServicePropertiesBuilder<'a> {
+        service::requests::GetServicePropertiesBuilder::new(self, storage_service)
+    }
+
+    pub fn set_service_properties<'a>(
+        &'a self,
+        storage_service: service::StorageService,
+    ) -> service::requests::SetServicePropertiesBuilder<'a, No> {
+        service::requests::SetServicePropertiesBuilder::new(self, storage_service)
+    }
+
+    /// Reads the geo-replication status of a read-access geo-redundant
+    /// account from its secondary endpoint.
+    pub fn get_service_stats<'a>(&'a self, storage_service: service::StorageService) -> service::requests::GetServiceStatsBuilder<'a> {
+        service::requests::GetServiceStatsBuilder::new(self, storage_service)
+    }
+
+    /// Gets a key to sign user delegation SAS tokens (see
+    /// `UserDelegationKey::blob_sas`). The client must authenticate with
+    /// Azure AD (`Client::azure_bearer_token`).
+    pub fn get_user_delegation_key(&self) -> service::requests::GetUserDelegationKeyBuilder<'_, No> {
+        service::requests::GetUserDelegationKeyBuilder::new(self)
+    }
+
+    /// Enables (or, with `StaticWebsite::disabled`, disables) the static
+    /// website hosting of the account, leaving the other blob service
+    /// properties as they are. Upload the site with
+    /// `Container::upload_static_website`.
+    pub fn set_static_website(
+        &self,
+        static_website: service::StaticWebsite,
+    ) -> impl Future<Item = service::responses::SetServicePropertiesResponse, Error = AzureError> {
+        let properties = service::StorageServiceProperties {
+            static_website: Some(static_website),
+            ..Default::default()
+        };
+        self.set_service_properties(service::StorageService::Blob)
+            .with_storage_service_properties(&properties)
+            .finalize()
+    }
+
+    /// Reads the SKU and the kind of the account.
+    pub fn get_account_information(&self) -> service::requests::GetAccountInformationBuilder<'_> {
+        service::requests::GetAccountInformationBuilder::new(self)
+    }
+
+    /// Sends a cheap authenticated request (Get Account Information) to
+    /// check the credentials and the connectivity, for example at
+    /// startup. Fails with the service error if the key or the SAS token
+    /// are rejected. Goes through the whole pipeline: set a retry policy
+    /// to tolerate transient failures.
+    pub fn ping(&self) -> impl Future<Item = PingResponse, Error = AzureError> {
+        ping::ping(self)
+    }
+
+    /// Pings the account and explains why the request failed, if it
+    /// did: wrong key, clock skew, misspelled account name, firewall and
+    /// so on. Meant to turn a misconfiguration into an actionable message
+    /// at startup. See `CredentialsDiagnosis`.
+    pub fn validate_credentials(&self) -> impl Future<Item = CredentialsDiagnosis, Error = AzureError> {
+        ping::validate_credentials(self)
+    }
+
+    /// Sends `connections` pings at the same time so that as many
+    /// connections to the blob endpoint are opened and kept in the pool
+    /// for the following requests. Fails if any of the pings fails.
+    pub fn warm_up(&self, connections: usize) -> impl Future<Item = Vec<PingResponse>, Error = AzureError> {
+        ping::warm_up(self, connections)
+    }
+
     pub fn account(&self) -> &str {
         &self.account
     }
 
-    pub fn key(&self) -> &str {
-        &self.key
+    /// A copy of the current account key, which `set_key` may replace
+    /// at any time.
+    pub fn key(&self) -> String {
+        self.key.get()
+    }
+
+    pub(crate) fn bearer_token(&self) -> Option<&str> {
+        self.bearer_token.as_deref()
+    }
+
+    /// Replaces the account key, for this client, its clones and the
+    /// requests in flight: a request signed with the old key and rejected
+    /// with 403 is sent once more with the new one. The sub-requests of a
+    /// blob batch are signed when the batch is built, so they are not: see
+    /// `BlobBatchBuilder`. Fails, keeping the current key, if `key` is not
+    /// valid base64.
+    pub fn set_key(&self, key: &str) -> Result<(), AzureError> {
+        HmacKey::from_base64(key)?;
+        self.key.set(key);
+        Ok(())
     }
 
     #[inline]
@@ -247,7 +1259,17 @@ impl Client {
         &self.table_uri
     }
 
-    fn add_sas_token_to_uri(&self, uri: &str) -> String {
+    #[inline]
+    pub(crate) fn queue_uri(&self) -> &str {
+        &self.queue_uri
+    }
+
+    #[inline]
+    pub(crate) fn file_uri(&self) -> &str {
+        &self.file_uri
+    }
+
+    pub(crate) fn add_sas_token_to_uri(&self, uri: &str) -> String {
         match &self.sas_token {
             Some(token) => Url::parse_with_params(uri, token).unwrap().to_string(),
             None => String::from(uri),
@@ -260,13 +1282,14 @@ impl Client {
         method: &Method,
         headers_func: F,
         request_body: Option<&[u8]>,
-    ) -> Result<hyper::client::ResponseFuture, AzureError>
+    ) -> Result<ResponseFuture, AzureError>
     where
         F: FnOnce(&mut ::http::request::Builder),
     {
         let uri = self.add_sas_token_to_uri(uri);
 
-        perform_request(&self.hc, &uri, method, &self.key, headers_func, request_body, ServiceType::Blob)
+        let request = build_request(&uri, method, headers_func, request_body)?;
+        Ok(self.send(request, ServiceType::Blob))
     }
 
     pub(crate) fn perform_table_request<F>(
@@ -275,25 +1298,98 @@ impl Client {
         method: &Method,
         headers_func: F,
         request_str: Option<&[u8]>,
-    ) -> Result<hyper::client::ResponseFuture, AzureError>
+    ) -> Result<ResponseFuture, AzureError>
     where
         F: FnOnce(&mut ::http::request::Builder),
     {
         debug!("segment: {}, method: {:?}", segment, method,);
 
-        let uri = self.add_sas_token_to_uri(
-            (self.get_uri_prefix(ServiceType::Table) + segment).as_str()
-        );
-
-        perform_request(
-            &self.hc,
-            &uri,
-            method,
-            &self.key,
-            headers_func,
-            request_str,
-            ServiceType::Table,
-        )
+        self.perform_table_request_with_uri(&(self.get_uri_prefix(ServiceType::Table) + segment), method, headers_func, request_str)
+    }
+
+    // as perform_table_request, with the whole uri (for example to address
+    // the secondary endpoint)
+    pub(crate) fn perform_table_request_with_uri<F>(
+        &self,
+        uri: &str,
+        method: &Method,
+        headers_func: F,
+        request_str: Option<&[u8]>,
+    ) -> Result<ResponseFuture, AzureError>
+    where
+        F: FnOnce(&mut ::http::request::Builder),
+    {
+        let uri = self.add_sas_token_to_uri(uri);
+
+        let request = build_request(&uri, method, headers_func, request_str)?;
+        Ok(self.send(request, ServiceType::Table))
+    }
+
+    // Sends the request through the pipeline. The transport at the end of
+    // the pipeline goes through the circuit breaker, if any.
+    fn send(&self, request: PipelineRequest, service_type: ServiceType) -> ResponseFuture {
+        let mut policies: Vec<Arc<dyn Policy>> = vec![
+            Arc::new(TelemetryHeadersStage),
+            Arc::new(ClientRequestIdStage::new(self.client_request_ids, self.client_request_id_echo_required)),
+        ];
+        if let Some(token) = &self.cancellation_token {
+            policies.push(Arc::new(CancellationStage::new(token.clone())));
+        }
+        if self.location_mode != LocationMode::PrimaryOnly {
+            let primary_uris = [self.blob_uri(), self.queue_uri(), self.table_uri(), self.file_uri()];
+            policies.push(Arc::new(LocationStage::new(self.location_mode, &self.account, &primary_uris)));
+        }
+        policies.push(Arc::new(RetryStage::new(self.retry_policy.clone(), self.sleeper.clone())));
+        if let Some(timeout) = self.request_timeout {
+            policies.push(Arc::new(TimeoutStage::new(timeout, self.sleeper.clone())));
+        }
+        if let Some(throttler) = &self.throttler {
+            policies.push(Arc::new(ThrottlingStage::new(throttler.clone(), self.sleeper.clone())));
+        }
+        if let Some(limiter) = &self.bandwidth_limiter {
+            policies.push(Arc::new(BandwidthStage::new(limiter.clone(), self.sleeper.clone())));
+        }
+        policies.extend(self.policies.iter().cloned());
+        if let Some(clock_skew) = &self.clock_skew {
+            policies.push(Arc::new(ClockSkewStage::new(clock_skew.clone())));
+        }
+        policies.push(Arc::new(LatencyStage::new(self.telemetry_observer.clone())));
+        if let Some(http_observer) = &self.http_observer {
+            policies.push(Arc::new(HttpTraceStage::new(http_observer.clone())));
+        }
+        policies.push(Arc::new(LoggingStage));
+        policies.push(Arc::new(
+            SigningStage::new(&self.account, self.key.clone(), service_type, self.clock_skew.clone())
+                .with_bearer_token(self.bearer_token.clone())
+                .with_diagnostics(self.signing_diagnostics)
+                .with_api_version(self.api_version()),
+        ));
+
+        let client = self.clone();
+        let transport = move |request: PipelineRequest| -> PipelineFuture {
+            let uri = request.uri.clone();
+            match client.guard(&uri, |_| Ok(client.hc.execute(request))) {
+                Ok(response) => response,
+                Err(error) => Box::new(future::err(error)),
+            }
+        };
+
+        pipeline::send(policies, transport, request)
+    }
+
+    fn guard<P>(&self, uri: &str, perform: P) -> Result<ResponseFuture, AzureError>
+    where
+        P: FnOnce(&str) -> Result<PipelineFuture, AzureError>,
+    {
+        match &self.circuit_breaker {
+            Some(circuit_breaker) => {
+                let host = Url::parse(uri)?.host_str().unwrap_or_default().to_owned();
+                circuit_breaker.check(&host)?;
+                let fut = perform(uri).inspect_err(|_| circuit_breaker.record(&host, false))?;
+                Ok(Box::new(CircuitBreaker::watch(circuit_breaker, host, fut)))
+            }
+            None => perform(uri),
+        }
     }
 
     /// Uri scheme + authority e.g. http://myaccount.table.core.windows.net/
@@ -304,3 +1400,292 @@ impl Client {
         }
     }
 }
+
+fn parse_sas_token(sas_token: &str) -> Vec<(String, String)> {
+    Url::options()
+        // Any base url will do: we just need to parse the SAS token
+        // to get its query pairs.
+        .base_url(Some(&Url::parse("https://blob.core.windows.net").unwrap()))
+        .parse(sas_token)
+        .unwrap()
+        .query_pairs()
+        .map(|p| (String::from(p.0), String::from(p.1)))
+        .collect()
+}
+
+// e.g. https://myaccount.blob.core.windows.net
+fn service_uri(protocol: &str, account: &str, service: &str, endpoint_suffix: &str) -> String {
+    format!("{}://{}.{}.{}", protocol, account, service, endpoint_suffix)
+}
+
+/// Configures a `Client` in one go, see `Client::builder`: the
+/// credentials, the endpoints, the retries and timeouts, and the HTTP
+/// client settings. Unlike the `with_*` methods of `Client` the HTTP
+/// client is built once, with the proxy, TLS and pool settings given.
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    account: String,
+    credentials: Option<Credentials>,
+    endpoint_suffix: Option<String>,
+    api_version: Option<String>,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    sleeper: Option<Arc<dyn Sleeper>>,
+    request_timeout: Option<Duration>,
+    location_mode: LocationMode,
+    proxy: Option<ProxyConfig>,
+    tls_config: TlsConfig,
+    pool_config: PoolConfig,
+    http_client: Option<Arc<dyn HttpClient>>,
+    telemetry_observer: Option<Arc<dyn TelemetryObserver>>,
+    http_observer: Option<Arc<dyn HttpObserver>>,
+}
+
+#[derive(Debug, Clone)]
+enum Credentials {
+    Key(String),
+    SasToken(String),
+    BearerToken(String),
+}
+
+impl ClientBuilder {
+    pub fn new(account: &str) -> ClientBuilder {
+        ClientBuilder {
+            account: account.to_owned(),
+            credentials: None,
+            endpoint_suffix: None,
+            api_version: None,
+            retry_policy: None,
+            sleeper: None,
+            request_timeout: None,
+            location_mode: LocationMode::PrimaryOnly,
+            proxy: None,
+            tls_config: TlsConfig::default(),
+            pool_config: PoolConfig::default(),
+            http_client: None,
+            telemetry_observer: None,
+            http_observer: None,
+        }
+    }
+
+    /// Signs the requests with the account key, as `Client::azure`.
+    pub fn with_key(self, key: &str) -> ClientBuilder {
+        ClientBuilder {
+            credentials: Some(Credentials::Key(key.to_owned())),
+            ..self
+        }
+    }
+
+    /// Authorizes the requests with a SAS token, as `Client::azure_sas`.
+    pub fn with_sas_token(self, sas_token: &str) -> ClientBuilder {
+        ClientBuilder {
+            credentials: Some(Credentials::SasToken(sas_token.to_owned())),
+            ..self
+        }
+    }
+
+    /// Authorizes the requests with an Azure AD access token, as
+    /// `Client::azure_bearer_token`.
+    pub fn with_bearer_token(self, bearer_token: &str) -> ClientBuilder {
+        ClientBuilder {
+            credentials: Some(Credentials::BearerToken(bearer_token.to_owned())),
+            ..self
+        }
+    }
+
+    /// See `Client::with_endpoint_suffix`.
+    pub fn with_endpoint_suffix(self, endpoint_suffix: &str) -> ClientBuilder {
+        ClientBuilder {
+            endpoint_suffix: Some(endpoint_suffix.to_owned()),
+            ..self
+        }
+    }
+
+    /// See `Client::with_api_version`. The version is checked by `build`.
+    pub fn with_api_version(self, api_version: &str) -> ClientBuilder {
+        ClientBuilder {
+            api_version: Some(api_version.to_owned()),
+            ..self
+        }
+    }
+
+    /// See `Client::with_retry_policy`.
+    pub fn with_retry_policy(self, retry_policy: Arc<dyn RetryPolicy>) -> ClientBuilder {
+        ClientBuilder {
+            retry_policy: Some(retry_policy),
+            ..self
+        }
+    }
+
+    /// See `Client::with_sleeper`.
+    pub fn with_sleeper(self, sleeper: Arc<dyn Sleeper>) -> ClientBuilder {
+        ClientBuilder {
+            sleeper: Some(sleeper),
+            ..self
+        }
+    }
+
+    /// See `Client::with_request_timeout`. The connect timeout is part of
+    /// the `PoolConfig`.
+    pub fn with_request_timeout(self, request_timeout: Duration) -> ClientBuilder {
+        ClientBuilder {
+            request_timeout: Some(request_timeout),
+            ..self
+        }
+    }
+
+    /// See `Client::with_location_mode`.
+    pub fn with_location_mode(self, location_mode: LocationMode) -> ClientBuilder {
+        ClientBuilder { location_mode, ..self }
+    }
+
+    /// See `Client::with_proxy`. Without it the proxy of the environment,
+    /// if any, is used.
+    pub fn with_proxy(self, proxy: ProxyConfig) -> ClientBuilder {
+        ClientBuilder {
+            proxy: Some(proxy),
+            ..self
+        }
+    }
+
+    /// See `Client::with_tls_config`.
+    pub fn with_tls_config(self, tls_config: TlsConfig) -> ClientBuilder {
+        ClientBuilder { tls_config, ..self }
+    }
+
+    /// See `Client::with_pool_config`.
+    pub fn with_pool_config(self, pool_config: PoolConfig) -> ClientBuilder {
+        ClientBuilder { pool_config, ..self }
+    }
+
+    /// See `Client::with_http_client`. The proxy, TLS and pool settings
+    /// are then ignored.
+    pub fn with_http_client(self, http_client: Arc<dyn HttpClient>) -> ClientBuilder {
+        ClientBuilder {
+            http_client: Some(http_client),
+            ..self
+        }
+    }
+
+    /// See `Client::with_telemetry_observer`.
+    pub fn with_telemetry_observer(self, telemetry_observer: Arc<dyn TelemetryObserver>) -> ClientBuilder {
+        ClientBuilder {
+            telemetry_observer: Some(telemetry_observer),
+            ..self
+        }
+    }
+
+    /// See `Client::with_http_observer`.
+    pub fn with_http_observer(self, http_observer: Arc<dyn HttpObserver>) -> ClientBuilder {
+        ClientBuilder {
+            http_observer: Some(http_observer),
+            ..self
+        }
+    }
+
+    /// Fails if no credentials were given, if the key is not valid
+    /// base64, if the API version is not supported or if the HTTP client
+    /// cannot be built.
+    pub fn build(self) -> Result<Client, AzureError> {
+        let (key, sas_token, bearer_token) = match self.credentials {
+            Some(Credentials::Key(key)) => {
+                HmacKey::from_base64(&key)?;
+                (AccountKey::new(&key), None, None)
+            }
+            Some(Credentials::SasToken(sas_token)) => (AccountKey::default(), Some(parse_sas_token(&sas_token)), None),
+            Some(Credentials::BearerToken(bearer_token)) => (AccountKey::default(), None, Some(bearer_token)),
+            None => {
+                return Err(AzureError::InputParametersError(
+                    "no credentials: set a key, a SAS token or a bearer token".to_owned(),
+                ));
+            }
+        };
+        if let Some(api_version) = &self.api_version {
+            check_api_version(api_version)?;
+        }
+        let hc = match self.http_client {
+            Some(http_client) => http_client,
+            None => hyper_http_client(
+                self.proxy.clone().or_else(ProxyConfig::from_env),
+                &self.tls_config,
+                &self.pool_config,
+            )?,
+        };
+
+        let mut client = Client::account_client(&self.account, key, sas_token, hc);
+        if let Some(endpoint_suffix) = &self.endpoint_suffix {
+            client = client.with_endpoint_suffix(endpoint_suffix);
+        }
+        client.bearer_token = bearer_token;
+        client.api_version = self.api_version;
+        if let Some(retry_policy) = self.retry_policy {
+            client.retry_policy = retry_policy;
+        }
+        if let Some(sleeper) = self.sleeper {
+            client.sleeper = sleeper;
+        }
+        client.request_timeout = self.request_timeout;
+        client.location_mode = self.location_mode;
+        client.proxy = self.proxy;
+        client.tls_config = self.tls_config;
+        client.pool_config = self.pool_config;
+        if let Some(telemetry_observer) = self.telemetry_observer {
+            client.telemetry_observer = telemetry_observer;
+        }
+        client.http_observer = self.http_observer;
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::retry::FixedRetry;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use hyper::StatusCode;
+
+    #[test]
+    fn test_client_builder() {
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::OK, &[], b""));
+        let client = Client::builder("account")
+            .with_key("YWNjb3VudGtleQ==")
+            .with_endpoint_suffix(CHINA_ENDPOINT_SUFFIX)
+            .with_api_version("2019-02-02")
+            .with_retry_policy(Arc::new(FixedRetry::new(Duration::from_millis(10), 3)))
+            .with_request_timeout(Duration::from_secs(30))
+            .with_location_mode(LocationMode::PrimaryThenSecondary)
+            .with_http_client(transport.clone())
+            .build()
+            .unwrap();
+        assert_eq!(client.key(), "YWNjb3VudGtleQ==");
+        assert_eq!(client.api_version(), "2019-02-02");
+        assert_eq!(client.request_timeout(), Some(Duration::from_secs(30)));
+        assert_eq!(client.location_mode(), LocationMode::PrimaryThenSecondary);
+
+        client
+            .perform_request(&format!("{}/c?restype=container", client.blob_uri()), &Method::GET, |_| {}, None)
+            .unwrap()
+            .wait()
+            .unwrap();
+        let request = &transport.requests()[0];
+        assert_eq!(request.uri, "https://account.blob.core.chinacloudapi.cn/c?restype=container");
+        assert_eq!(request.headers["x-ms-version"], "2019-02-02");
+
+        let client = Client::builder("account")
+            .with_sas_token("?sv=2019-02-02&sig=c2ln")
+            .build()
+            .unwrap();
+        assert_eq!(client.key(), "");
+        assert!(Client::builder("account").with_bearer_token("eyJ0eXAi").build().is_ok());
+    }
+
+    #[test]
+    fn test_client_builder_errors() {
+        assert!(Client::builder("account").build().is_err());
+        assert!(Client::builder("account").with_key("not base64!").build().is_err());
+        assert!(Client::builder("account")
+            .with_key("YWNjb3VudGtleQ==")
+            .with_api_version("yesterday")
+            .build()
+            .is_err());
+    }
+}
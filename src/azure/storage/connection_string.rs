@@ -0,0 +1,148 @@
+use crate::azure::core::errors::AzureError;
+
+/// The settings of a storage connection string, as shown in the portal
+/// and consumed by the other Azure SDKs:
+///
+/// * `DefaultEndpointsProtocol=https;AccountName=<account>;AccountKey=<key>;EndpointSuffix=core.windows.net`
+/// * `BlobEndpoint=https://<account>.blob.core.windows.net/;SharedAccessSignature=sv=...`
+/// * `UseDevelopmentStorage=true`
+///
+/// See `Client::from_connection_string`.
+#[derive(Clone, Default, PartialEq)]
+pub struct ConnectionString<'a> {
+    pub account_name: Option<&'a str>,
+    pub account_key: Option<&'a str>,
+    pub sas: Option<&'a str>,
+    pub default_endpoints_protocol: Option<&'a str>,
+    pub endpoint_suffix: Option<&'a str>,
+    pub blob_endpoint: Option<&'a str>,
+    pub queue_endpoint: Option<&'a str>,
+    pub table_endpoint: Option<&'a str>,
+    pub file_endpoint: Option<&'a str>,
+    pub use_development_storage: bool,
+    pub development_storage_proxy_uri: Option<&'a str>,
+}
+
+impl<'a> ConnectionString<'a> {
+    pub fn parse(connection_string: &'a str) -> Result<ConnectionString<'a>, AzureError> {
+        let mut parsed = ConnectionString::default();
+
+        for pair in connection_string.split(';').map(str::trim).filter(|pair| !pair.is_empty()) {
+            // the keys and the SAS tokens hold '=' too
+            let mut tokens = pair.splitn(2, '=');
+            let name = tokens.next().unwrap_or_default().trim();
+            let value = tokens
+                .next()
+                .map(str::trim)
+                .ok_or_else(|| AzureError::InputParametersError(format!("invalid connection string: {} has no value", name)))?;
+
+            match name {
+                "AccountName" => parsed.account_name = Some(value),
+                "AccountKey" => parsed.account_key = Some(value),
+                "SharedAccessSignature" => parsed.sas = Some(value),
+                "DefaultEndpointsProtocol" => parsed.default_endpoints_protocol = Some(value),
+                "EndpointSuffix" => parsed.endpoint_suffix = Some(value),
+                "BlobEndpoint" => parsed.blob_endpoint = Some(value),
+                "QueueEndpoint" => parsed.queue_endpoint = Some(value),
+                "TableEndpoint" => parsed.table_endpoint = Some(value),
+                "FileEndpoint" => parsed.file_endpoint = Some(value),
+                "UseDevelopmentStorage" => parsed.use_development_storage = value.eq_ignore_ascii_case("true"),
+                "DevelopmentStorageProxyUri" => parsed.development_storage_proxy_uri = Some(value),
+                _ => debug!("ignoring the connection string setting {}", name),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+// the key and the SAS token must not end up in the logs
+impl<'a> std::fmt::Debug for ConnectionString<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ConnectionString")
+            .field("account_name", &self.account_name)
+            .field("default_endpoints_protocol", &self.default_endpoints_protocol)
+            .field("endpoint_suffix", &self.endpoint_suffix)
+            .field("blob_endpoint", &self.blob_endpoint)
+            .field("queue_endpoint", &self.queue_endpoint)
+            .field("table_endpoint", &self.table_endpoint)
+            .field("file_endpoint", &self.file_endpoint)
+            .field("use_development_storage", &self.use_development_storage)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let parsed = ConnectionString::parse(
+            "DefaultEndpointsProtocol=https;AccountName=myaccount;AccountKey=a2V5PT0=;EndpointSuffix=core.chinacloudapi.cn",
+        )
+        .unwrap();
+        assert_eq!(parsed.account_name, Some("myaccount"));
+        assert_eq!(parsed.account_key, Some("a2V5PT0="));
+        assert_eq!(parsed.endpoint_suffix, Some("core.chinacloudapi.cn"));
+        assert!(!format!("{:?}", parsed).contains("a2V5PT0="));
+
+        let parsed =
+            ConnectionString::parse("BlobEndpoint=https://myaccount.blob.core.windows.net/;SharedAccessSignature=sv=2018-11-09&sig=a%3D;")
+                .unwrap();
+        assert_eq!(parsed.blob_endpoint, Some("https://myaccount.blob.core.windows.net/"));
+        assert_eq!(parsed.sas, Some("sv=2018-11-09&sig=a%3D"));
+
+        assert!(
+            ConnectionString::parse("UseDevelopmentStorage=true")
+                .unwrap()
+                .use_development_storage
+        );
+        assert!(ConnectionString::parse("AccountName").is_err());
+    }
+
+    #[test]
+    fn test_client() {
+        use crate::azure::storage::client::Client;
+
+        let client = Client::from_connection_string(
+            "DefaultEndpointsProtocol=http;AccountName=myaccount;AccountKey=a2V5PT0=;EndpointSuffix=core.chinacloudapi.cn",
+        )
+        .unwrap();
+        assert_eq!(client.account(), "myaccount");
+        assert_eq!(client.key(), "a2V5PT0=");
+        assert_eq!(client.blob_uri(), "http://myaccount.blob.core.chinacloudapi.cn");
+        assert_eq!(client.file_uri(), "http://myaccount.file.core.chinacloudapi.cn");
+
+        let client = Client::from_connection_string(
+            "BlobEndpoint=https://myaccount.blob.core.windows.net/;QueueEndpoint=https://queues.contoso.com;SharedAccessSignature=sv=2018-11-09&sig=a%3D",
+        )
+        .unwrap();
+        assert_eq!(client.account(), "myaccount");
+        assert_eq!(client.blob_uri(), "https://myaccount.blob.core.windows.net");
+        assert_eq!(client.queue_uri(), "https://queues.contoso.com");
+        assert_eq!(
+            client.add_sas_token_to_uri("https://myaccount.blob.core.windows.net/c"),
+            "https://myaccount.blob.core.windows.net/c?sv=2018-11-09&sig=a%3D"
+        );
+
+        let client = Client::from_connection_string("UseDevelopmentStorage=true").unwrap();
+        assert_eq!(client.account(), "devstoreaccount1");
+        assert_eq!(client.blob_uri(), "http://127.0.0.1:10000/devstoreaccount1");
+        assert_eq!(client.table_uri(), "http://127.0.0.1:10002/devstoreaccount1");
+
+        assert!(Client::from_connection_string("AccountName=myaccount").is_err());
+    }
+
+    #[test]
+    fn test_endpoint_suffix() {
+        use crate::azure::storage::client::{Client, CHINA_ENDPOINT_SUFFIX};
+
+        let client = Client::azure("myaccount", "a2V5PT0=")
+            .unwrap()
+            .with_endpoint_suffix(CHINA_ENDPOINT_SUFFIX)
+            .with_blob_uri("https://myaccount.privatelink.blob.core.chinacloudapi.cn/");
+        assert_eq!(client.blob_uri(), "https://myaccount.privatelink.blob.core.chinacloudapi.cn");
+        assert_eq!(client.queue_uri(), "https://myaccount.queue.core.chinacloudapi.cn");
+    }
+}
@@ -4,8 +4,13 @@ pub mod responses;
 use crate::azure::core::{
     enumerations,
     errors::{AzureError, TraversingError},
-    headers::{BLOB_PUBLIC_ACCESS, HAS_IMMUTABILITY_POLICY, HAS_LEGAL_HOLD, LEASE_DURATION, LEASE_STATE, LEASE_STATUS, META_PREFIX},
+    headers::{
+        BLOB_PUBLIC_ACCESS, DEFAULT_ENCRYPTION_SCOPE, DELETED_CONTAINER_NAME, DELETED_CONTAINER_VERSION, DENY_ENCRYPTION_SCOPE_OVERRIDE,
+        HAS_IMMUTABILITY_POLICY, HAS_LEGAL_HOLD, IMMUTABLE_STORAGE_WITH_VERSIONING_ENABLED, LEASE_DURATION, LEASE_STATE, LEASE_STATUS,
+    },
     lease::{LeaseDuration, LeaseState, LeaseStatus},
+    metadata::Metadata,
+    metadata_from_headers,
     parsing::{cast_must, cast_optional, traverse, FromStringOptional},
     ClientRequired, ContainerNameRequired, COMPLETE_ENCODE_SET,
 };
@@ -14,11 +19,20 @@ use http::request::Builder;
 use http::HeaderMap;
 use hyper::header;
 use hyper::header::HeaderName;
-use std::collections::HashMap;
 use std::{fmt, str::FromStr};
 use url::percent_encoding::utf8_percent_encode;
 use xml::{Element, Xml};
 
+// the default encryption scope and the version-level immutability are
+// only returned from this version
+pub(crate) const CONTAINER_PROPERTIES_VERSION: &str = "2020-06-12";
+
+// encryption scopes are not available before this version
+pub(crate) const ENCRYPTION_SCOPE_VERSION: &str = "2019-07-07";
+
+// container soft delete is not available before this version
+pub(crate) const SOFT_DELETE_VERSION: &str = "2019-12-12";
+
 create_enum!(PublicAccess, (None, "none"), (Container, "container"), (Blob, "blob"));
 
 pub(crate) fn public_access_from_header(header_map: &HeaderMap) -> Result<PublicAccess, AzureError> {
@@ -44,6 +58,64 @@ pub trait PublicAccessRequired {
     }
 }
 
+pub trait DefaultEncryptionScopeSupport<'a> {
+    type O;
+    fn with_default_encryption_scope(self, default_encryption_scope: &'a str) -> Self::O;
+}
+
+pub trait DefaultEncryptionScopeOption<'a> {
+    fn default_encryption_scope(&self) -> Option<&'a str>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(default_encryption_scope) = self.default_encryption_scope() {
+            builder.header(DEFAULT_ENCRYPTION_SCOPE, default_encryption_scope);
+        }
+    }
+}
+
+pub trait DenyEncryptionScopeOverrideSupport {
+    type O;
+    fn with_deny_encryption_scope_override(self, deny_encryption_scope_override: bool) -> Self::O;
+}
+
+pub trait DenyEncryptionScopeOverrideOption {
+    fn deny_encryption_scope_override(&self) -> Option<bool>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(deny_encryption_scope_override) = self.deny_encryption_scope_override() {
+            builder.header(DENY_ENCRYPTION_SCOPE_OVERRIDE, &deny_encryption_scope_override.to_string() as &str);
+        }
+    }
+}
+
+pub trait DeletedContainerNameSupport<'a> {
+    type O;
+    fn with_deleted_container_name(self, deleted_container_name: &'a str) -> Self::O;
+}
+
+pub trait DeletedContainerNameRequired<'a> {
+    fn deleted_container_name(&self) -> &'a str;
+
+    fn add_header(&self, builder: &mut Builder) {
+        builder.header(DELETED_CONTAINER_NAME, self.deleted_container_name());
+    }
+}
+
+/// The `Container::version` of a deleted container, as listed with
+/// `ListBuilder::with_include_deleted`.
+pub trait DeletedContainerVersionSupport<'a> {
+    type O;
+    fn with_deleted_container_version(self, deleted_container_version: &'a str) -> Self::O;
+}
+
+pub trait DeletedContainerVersionRequired<'a> {
+    fn deleted_container_version(&self) -> &'a str;
+
+    fn add_header(&self, builder: &mut Builder) {
+        builder.header(DELETED_CONTAINER_VERSION, self.deleted_container_version());
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Container {
     pub name: String,
@@ -55,7 +127,24 @@ pub struct Container {
     pub public_access: PublicAccess,
     pub has_immutability_policy: bool,
     pub has_legal_hold: bool,
-    pub metadata: HashMap<String, String>,
+    /// Whether version-level immutability is enabled on the container.
+    pub immutable_storage_with_versioning_enabled: bool,
+    /// The encryption scope applied by default to the blobs of the
+    /// container, if any.
+    pub default_encryption_scope: Option<String>,
+    /// Whether the blobs are prevented from using an encryption scope other
+    /// than the default one.
+    pub deny_encryption_scope_override: bool,
+    pub metadata: Metadata,
+    /// Whether the container is a deleted one, listed with
+    /// `ListBuilder::with_include_deleted`. The fields below are only set
+    /// for those.
+    pub deleted: bool,
+    /// The version to pass to `RestoreBuilder` to restore the container.
+    pub version: Option<String>,
+    pub deleted_time: Option<DateTime<Utc>>,
+    /// The days left before the container is permanently deleted.
+    pub remaining_retention_days: Option<u64>,
 }
 
 impl AsRef<str> for Container {
@@ -76,7 +165,14 @@ impl Container {
             public_access: PublicAccess::None,
             has_immutability_policy: false,
             has_legal_hold: false,
-            metadata: HashMap::new(),
+            immutable_storage_with_versioning_enabled: false,
+            default_encryption_scope: None,
+            deny_encryption_scope_override: false,
+            metadata: Metadata::new(),
+            deleted: false,
+            version: None,
+            deleted_time: None,
+            remaining_retention_days: None,
         }
     }
 
@@ -128,12 +224,21 @@ impl Container {
             None => return Err(AzureError::MissingHeaderError(HAS_LEGAL_HOLD.to_owned())),
         };
 
-        let mut metadata: HashMap<String, String> = HashMap::new();
-        for (key, value) in headers {
-            if key.as_str().starts_with(META_PREFIX) {
-                metadata.insert(key.as_str().to_owned(), value.to_str()?.to_owned());
+        // these are only returned by the recent API versions
+        let optional_flag = |name: &str| -> Result<bool, AzureError> {
+            match headers.get(name) {
+                Some(flag) => Ok(bool::from_str(flag.to_str()?)?),
+                None => Ok(false),
             }
-        }
+        };
+        let immutable_storage_with_versioning_enabled = optional_flag(IMMUTABLE_STORAGE_WITH_VERSIONING_ENABLED)?;
+        let deny_encryption_scope_override = optional_flag(DENY_ENCRYPTION_SCOPE_OVERRIDE)?;
+        let default_encryption_scope = match headers.get(DEFAULT_ENCRYPTION_SCOPE) {
+            Some(default_encryption_scope) => Some(default_encryption_scope.to_str()?.to_owned()),
+            None => None,
+        };
+
+        let metadata = metadata_from_headers(headers)?;
 
         Ok(Container {
             name,
@@ -145,7 +250,14 @@ impl Container {
             public_access,
             has_immutability_policy,
             has_legal_hold,
+            immutable_storage_with_versioning_enabled,
+            default_encryption_scope,
+            deny_encryption_scope_override,
             metadata,
+            deleted: false,
+            version: None,
+            deleted_time: None,
+            remaining_retention_days: None,
         })
     }
 
@@ -167,9 +279,17 @@ impl Container {
 
         let has_immutability_policy = cast_must::<bool>(elem, &["Properties", "HasImmutabilityPolicy"])?;
         let has_legal_hold = cast_must::<bool>(elem, &["Properties", "HasLegalHold"])?;
+        let immutable_storage_with_versioning_enabled =
+            cast_optional::<bool>(elem, &["Properties", "ImmutableStorageWithVersioningEnabled"])?.unwrap_or(false);
+        let default_encryption_scope = cast_optional::<String>(elem, &["Properties", "DefaultEncryptionScope"])?;
+        let deny_encryption_scope_override = cast_optional::<bool>(elem, &["Properties", "DenyEncryptionScopeOverride"])?.unwrap_or(false);
+        let deleted = cast_optional::<bool>(elem, &["Deleted"])?.unwrap_or(false);
+        let version = cast_optional::<String>(elem, &["Version"])?;
+        let deleted_time = cast_optional::<DateTime<Utc>>(elem, &["Properties", "DeletedTime"])?;
+        let remaining_retention_days = cast_optional::<u64>(elem, &["Properties", "RemainingRetentionDays"])?;
 
         let metadata = {
-            let mut hm = HashMap::new();
+            let mut hm = Metadata::new();
             let metadata = traverse(elem, &["Metadata"], true)?;
 
             for m in metadata {
@@ -200,7 +320,7 @@ impl Container {
                         }
                     };
 
-                    hm.insert(key, content);
+                    hm.insert_received(&key, &content);
                 }
             }
 
@@ -217,7 +337,14 @@ impl Container {
             public_access,
             has_immutability_policy,
             has_legal_hold,
+            immutable_storage_with_versioning_enabled,
+            default_encryption_scope,
+            deny_encryption_scope_override,
             metadata,
+            deleted,
+            version,
+            deleted_time,
+            remaining_retention_days,
         })
     }
 }
@@ -227,17 +354,193 @@ pub(crate) fn generate_container_uri<'a, T>(t: &T, params: Option<&str>) -> Stri
 where
     T: ClientRequired<'a> + ContainerNameRequired<'a>,
 {
+    container_uri(t.client().blob_uri(), t.container_name(), params)
+}
+
+/// The uri of a container of the service at `blob_service_uri`, with the
+/// query `params`.
+pub fn container_uri(blob_service_uri: &str, container_name: &str, params: Option<&str>) -> String {
+    let uri = format!("{}/{}", blob_service_uri, utf8_percent_encode(container_name, COMPLETE_ENCODE_SET));
     match params {
-        Some(ref params) => format!(
-            "{}/{}?{}",
-            t.client().blob_uri(),
-            utf8_percent_encode(t.container_name(), COMPLETE_ENCODE_SET),
-            params
-        ),
-        None => format!(
-            "{}/{}",
-            t.client().blob_uri(),
-            utf8_percent_encode(t.container_name(), COMPLETE_ENCODE_SET),
-        ),
+        Some(params) => format!("{}?{}", uri, params),
+        None => uri,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hyper::header::HeaderValue;
+
+    #[test]
+    fn test_from_response() {
+        let mut headers = HeaderMap::new();
+        headers.insert("last-modified", HeaderValue::from_static("Tue, 15 Oct 2019 10:00:00 GMT"));
+        headers.insert("etag", HeaderValue::from_static("\"0x8D75164AB6E1DE4\""));
+        headers.insert(LEASE_STATUS, HeaderValue::from_static("locked"));
+        headers.insert(LEASE_STATE, HeaderValue::from_static("leased"));
+        headers.insert(LEASE_DURATION, HeaderValue::from_static("infinite"));
+        headers.insert(BLOB_PUBLIC_ACCESS, HeaderValue::from_static("blob"));
+        headers.insert(HAS_IMMUTABILITY_POLICY, HeaderValue::from_static("false"));
+        headers.insert(HAS_LEGAL_HOLD, HeaderValue::from_static("true"));
+        headers.insert(DEFAULT_ENCRYPTION_SCOPE, HeaderValue::from_static("scope1"));
+        headers.insert(DENY_ENCRYPTION_SCOPE_OVERRIDE, HeaderValue::from_static("true"));
+        headers.insert("x-ms-meta-owner", HeaderValue::from_static("e2e"));
+
+        let container = Container::from_response("container".to_owned(), &headers).unwrap();
+        assert_eq!(container.lease_status, LeaseStatus::Locked);
+        assert_eq!(container.lease_state, LeaseState::Leased);
+        assert_eq!(container.lease_duration, Some(LeaseDuration::Infinite));
+        assert_eq!(container.public_access, PublicAccess::Blob);
+        assert!(!container.has_immutability_policy && container.has_legal_hold);
+        assert!(!container.immutable_storage_with_versioning_enabled);
+        assert_eq!(container.default_encryption_scope.as_deref(), Some("scope1"));
+        assert!(container.deny_encryption_scope_override);
+        assert_eq!(container.metadata["owner"], "e2e");
+    }
+
+    #[test]
+    fn test_parse_deleted() {
+        let body = "<Container><Name>logs</Name><Deleted>true</Deleted><Version>01D60F8BB59A4652</Version><Properties>\
+                    <Last-Modified>Tue, 15 Oct 2019 10:00:00 GMT</Last-Modified><Etag>\"0x8D75164AB6E1DE4\"</Etag>\
+                    <LeaseStatus>unlocked</LeaseStatus><LeaseState>expired</LeaseState><HasImmutabilityPolicy>false</HasImmutabilityPolicy>\
+                    <HasLegalHold>false</HasLegalHold><DeletedTime>Wed, 16 Oct 2019 08:30:00 GMT</DeletedTime>\
+                    <RemainingRetentionDays>6</RemainingRetentionDays></Properties></Container>";
+        let container = Container::parse(&body.parse::<Element>().unwrap()).unwrap();
+        assert!(container.deleted);
+        assert_eq!(container.version.as_deref(), Some("01D60F8BB59A4652"));
+        assert_eq!(container.deleted_time.unwrap().to_rfc3339(), "2019-10-16T08:30:00+00:00");
+        assert_eq!(container.remaining_retention_days, Some(6));
+    }
+
+    #[test]
+    fn test_restore() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::ContainerNameSupport;
+        use crate::azure::storage::client::{Client, Container};
+        use futures::Future;
+        use hyper::{Method, StatusCode};
+        use std::sync::Arc;
+
+        let transport = Arc::new(ReplayTransport::new().with_response(
+            StatusCode::CREATED,
+            &[
+                ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+                ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ],
+            b"",
+        ));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+        client
+            .restore_container()
+            .with_container_name("restored")
+            .with_deleted_container_name("logs")
+            .with_deleted_container_version("01D60F8BB59A4652")
+            .finalize()
+            .wait()
+            .unwrap();
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::PUT);
+        assert_eq!(
+            request.uri,
+            "https://account.blob.core.windows.net/restored?restype=container&comp=undelete"
+        );
+        assert_eq!(request.headers[DELETED_CONTAINER_NAME], "logs");
+        assert_eq!(request.headers[DELETED_CONTAINER_VERSION], "01D60F8BB59A4652");
+        assert_eq!(request.headers["x-ms-version"], SOFT_DELETE_VERSION);
+    }
+
+    #[test]
+    fn test_exists() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::ContainerNameSupport;
+        use crate::azure::storage::client::{Client, Container};
+        use futures::Future;
+        use hyper::{Method, StatusCode};
+        use std::sync::Arc;
+
+        let properties = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("etag", "\"0x8D75164AB6E1DE4\""),
+            ("x-ms-lease-status", "unlocked"),
+            ("x-ms-lease-state", "available"),
+            ("x-ms-has-immutability-policy", "false"),
+            ("x-ms-has-legal-hold", "false"),
+        ];
+        let missing = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-error-code", "ContainerNotFound"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::OK, &properties, b"")
+                .with_response(StatusCode::NOT_FOUND, &missing, b"")
+                .with_response(StatusCode::FORBIDDEN, &missing[..2], b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+
+        let exists = || client.container_exists().with_container_name("logs").finalize().wait();
+        assert!(exists().unwrap());
+        assert!(!exists().unwrap());
+        assert_eq!(exists().unwrap_err().status(), Some(StatusCode::FORBIDDEN));
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::HEAD);
+        assert_eq!(request.uri, "https://account.blob.core.windows.net/logs?restype=container");
+    }
+
+    #[test]
+    fn test_create_if_not_exists_delete_if_exists() {
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::ContainerNameSupport;
+        use crate::azure::storage::client::{Client, Container};
+        use futures::Future;
+        use hyper::{Method, StatusCode};
+        use std::sync::Arc;
+
+        let headers = [
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::CREATED, &headers, b"")
+                .with_response(StatusCode::CONFLICT, &[("x-ms-error-code", "ContainerAlreadyExists")], b"")
+                .with_response(StatusCode::CONFLICT, &[("x-ms-error-code", "ContainerBeingDeleted")], b"")
+                .with_response(StatusCode::ACCEPTED, &headers, b"")
+                .with_response(StatusCode::NOT_FOUND, &[("x-ms-error-code", "ContainerNotFound")], b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+
+        let create = || {
+            client
+                .create_container()
+                .with_container_name("logs")
+                .with_public_access(PublicAccess::None)
+                .create_if_not_exists()
+                .wait()
+        };
+        assert!(create().unwrap());
+        assert!(!create().unwrap());
+        assert!(create().unwrap_err().is_conflict());
+
+        let delete = || client.delete_container().with_container_name("logs").delete_if_exists().wait();
+        assert!(delete().unwrap());
+        assert!(!delete().unwrap());
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].method, Method::PUT);
+        assert_eq!(requests[3].method, Method::DELETE);
+        assert_eq!(requests[3].uri, "https://account.blob.core.windows.net/logs?restype=container");
     }
 }
@@ -1,9 +1,10 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::headers::LEASE_ACTION;
-use crate::azure::core::lease::LeaseId;
+use crate::azure::core::lease::{LeaseId, LeasePeriod};
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, LeaseDurationRequired,
-    LeaseDurationSupport, LeaseIdOption, LeaseIdSupport, ProposedLeaseIdOption, ProposedLeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseDurationRequired, LeaseDurationSupport, LeaseIdOption,
+    LeaseIdSupport, ProposedLeaseIdOption, ProposedLeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::client::Client;
@@ -25,8 +26,10 @@ where
     client_request_id: Option<&'a str>,
     timeout: Option<u64>,
     lease_id: Option<&'a LeaseId>,
-    lease_duration: Option<i8>,
+    lease_duration: Option<LeasePeriod>,
     proposed_lease_id: Option<&'a LeaseId>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> AcquireLeaseBuilder<'a, No, No> {
@@ -41,6 +44,8 @@ impl<'a> AcquireLeaseBuilder<'a, No, No> {
             timeout: None,
             lease_id: None,
             proposed_lease_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -98,7 +103,7 @@ impl<'a, ContainerNameSet> LeaseDurationRequired for AcquireLeaseBuilder<'a, Con
 where
     ContainerNameSet: ToAssign,
 {
-    fn lease_duration(&self) -> i8 {
+    fn lease_duration(&self) -> LeasePeriod {
         self.lease_duration.unwrap()
     }
 }
@@ -131,6 +136,8 @@ where
             lease_id: self.lease_id,
             lease_duration: self.lease_duration,
             proposed_lease_id: self.proposed_lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -153,6 +160,65 @@ where
             lease_id: self.lease_id,
             lease_duration: self.lease_duration,
             proposed_lease_id: self.proposed_lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, LeaseDurationSet> ExtraHeadersOption<'a> for AcquireLeaseBuilder<'a, ContainerNameSet, LeaseDurationSet>
+where
+    ContainerNameSet: ToAssign,
+    LeaseDurationSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, LeaseDurationSet> ExtraHeadersSupport<'a> for AcquireLeaseBuilder<'a, ContainerNameSet, LeaseDurationSet>
+where
+    ContainerNameSet: ToAssign,
+    LeaseDurationSet: ToAssign,
+{
+    type O = AcquireLeaseBuilder<'a, ContainerNameSet, LeaseDurationSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, LeaseDurationSet> ApiVersionOption<'a> for AcquireLeaseBuilder<'a, ContainerNameSet, LeaseDurationSet>
+where
+    ContainerNameSet: ToAssign,
+    LeaseDurationSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, LeaseDurationSet> ApiVersionSupport<'a> for AcquireLeaseBuilder<'a, ContainerNameSet, LeaseDurationSet>
+where
+    ContainerNameSet: ToAssign,
+    LeaseDurationSet: ToAssign,
+{
+    type O = AcquireLeaseBuilder<'a, ContainerNameSet, LeaseDurationSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        AcquireLeaseBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_lease_duration: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            lease_duration: self.lease_duration,
+            proposed_lease_id: self.proposed_lease_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -175,6 +241,8 @@ where
             lease_id: self.lease_id,
             lease_duration: self.lease_duration,
             proposed_lease_id: self.proposed_lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -197,6 +265,8 @@ where
             lease_id: Some(lease_id),
             lease_duration: self.lease_duration,
             proposed_lease_id: self.proposed_lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -208,7 +278,7 @@ where
 {
     type O = AcquireLeaseBuilder<'a, ContainerNameSet, Yes>;
 
-    fn with_lease_duration(self, lease_duration: i8) -> Self::O {
+    fn with_lease_duration(self, lease_duration: LeasePeriod) -> Self::O {
         AcquireLeaseBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -219,6 +289,8 @@ where
             lease_id: self.lease_id,
             lease_duration: Some(lease_duration),
             proposed_lease_id: self.proposed_lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -241,6 +313,8 @@ where
             lease_id: self.lease_id,
             lease_duration: self.lease_duration,
             proposed_lease_id: Some(proposed_lease_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -270,6 +344,8 @@ impl<'a> AcquireLeaseBuilder<'a, Yes, Yes> {
             &Method::PUT,
             |ref mut request| {
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
                 LeaseIdOption::add_header(&self, request);
                 request.header(LEASE_ACTION, "acquire");
                 LeaseDurationRequired::add_header(&self, request);
@@ -283,4 +359,9 @@ impl<'a> AcquireLeaseBuilder<'a, Yes, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::CREATED))
             .and_then(|(headers, _body)| done(AcquireLeaseResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<AcquireLeaseResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
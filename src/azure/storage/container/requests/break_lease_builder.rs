@@ -1,9 +1,10 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::headers::LEASE_ACTION;
-use crate::azure::core::lease::LeaseId;
+use crate::azure::core::lease::{LeaseBreakPeriod, LeaseId};
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, LeaseBreakPeriodOption,
-    LeaseBreakPeriodSupport, LeaseIdOption, LeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseBreakPeriodOption, LeaseBreakPeriodSupport, LeaseIdOption,
+    LeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::client::Client;
@@ -22,8 +23,10 @@ where
     container_name: Option<&'a str>,
     client_request_id: Option<&'a str>,
     timeout: Option<u64>,
-    lease_break_period: Option<u8>,
+    lease_break_period: Option<LeaseBreakPeriod>,
     lease_id: Option<&'a LeaseId>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> BreakLeaseBuilder<'a, No> {
@@ -36,6 +39,8 @@ impl<'a> BreakLeaseBuilder<'a, No> {
             timeout: None,
             lease_break_period: None,
             lease_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -77,7 +82,7 @@ impl<'a, ContainerNameSet> LeaseBreakPeriodOption for BreakLeaseBuilder<'a, Cont
 where
     ContainerNameSet: ToAssign,
 {
-    fn lease_break_period(&self) -> Option<u8> {
+    fn lease_break_period(&self) -> Option<LeaseBreakPeriod> {
         self.lease_break_period
     }
 }
@@ -106,6 +111,8 @@ where
             timeout: self.timeout,
             lease_break_period: self.lease_break_period,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -125,6 +132,59 @@ where
             timeout: self.timeout,
             lease_break_period: self.lease_break_period,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersOption<'a> for BreakLeaseBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersSupport<'a> for BreakLeaseBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = BreakLeaseBuilder<'a, ContainerNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionOption<'a> for BreakLeaseBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionSupport<'a> for BreakLeaseBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = BreakLeaseBuilder<'a, ContainerNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        BreakLeaseBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            lease_break_period: self.lease_break_period,
+            lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -144,6 +204,8 @@ where
             timeout: Some(timeout),
             lease_break_period: self.lease_break_period,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -154,7 +216,7 @@ where
 {
     type O = BreakLeaseBuilder<'a, ContainerNameSet>;
 
-    fn with_lease_break_period(self, lease_break_period: u8) -> Self::O {
+    fn with_lease_break_period(self, lease_break_period: LeaseBreakPeriod) -> Self::O {
         BreakLeaseBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -163,6 +225,8 @@ where
             timeout: self.timeout,
             lease_break_period: Some(lease_break_period),
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -182,6 +246,8 @@ where
             timeout: self.timeout,
             lease_break_period: self.lease_break_period,
             lease_id: Some(lease_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -206,6 +272,8 @@ impl<'a> BreakLeaseBuilder<'a, Yes> {
             &Method::PUT,
             |ref mut request| {
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
                 LeaseIdOption::add_header(&self, request);
                 request.header(LEASE_ACTION, "break");
                 LeaseBreakPeriodOption::add_header(&self, request);
@@ -218,4 +286,9 @@ impl<'a> BreakLeaseBuilder<'a, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::ACCEPTED))
             .and_then(|(headers, _body)| done(BreakLeaseResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<BreakLeaseResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
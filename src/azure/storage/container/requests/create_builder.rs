@@ -1,14 +1,18 @@
 use crate::azure::core::errors::{check_status_extract_body, AzureError};
+use crate::azure::core::metadata::Metadata;
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, MetadataOption,
-    MetadataSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, MetadataOption, MetadataSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::client::Client;
-use crate::azure::storage::container::{PublicAccess, PublicAccessRequired, PublicAccessSupport};
+use crate::azure::storage::container::{
+    DefaultEncryptionScopeOption, DefaultEncryptionScopeSupport, DenyEncryptionScopeOverrideOption, DenyEncryptionScopeOverrideSupport,
+    PublicAccess, PublicAccessRequired, PublicAccessSupport, ENCRYPTION_SCOPE_VERSION,
+};
+use crate::azure::storage::rest_client::HEADER_VERSION;
 use futures::future::{done, ok, Future};
 use hyper::{Method, StatusCode};
-use std::collections::HashMap;
 use std::marker::PhantomData;
 
 #[derive(Debug, Clone)]
@@ -24,7 +28,11 @@ where
     public_access: PublicAccess,
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
-    metadata: Option<&'a HashMap<&'a str, &'a str>>,
+    metadata: Option<&'a Metadata>,
+    default_encryption_scope: Option<&'a str>,
+    deny_encryption_scope_override: Option<bool>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a, ContainerNameSet, PublicAccessSet> ClientRequired<'a> for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
@@ -42,7 +50,7 @@ where
     ContainerNameSet: ToAssign,
     PublicAccessSet: ToAssign,
 {
-    fn metadata(&self) -> Option<&'a HashMap<&'a str, &'a str>> {
+    fn metadata(&self) -> Option<&'a Metadata> {
         self.metadata
     }
 }
@@ -54,7 +62,7 @@ where
 {
     type O = CreateBuilder<'a, ContainerNameSet, PublicAccessSet>;
 
-    fn with_metadata(self, metadata: &'a HashMap<&'a str, &'a str>) -> Self::O {
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
         CreateBuilder {
             p_container_name: PhantomData {},
             p_public_access: PhantomData {},
@@ -64,6 +72,10 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             metadata: Some(metadata),
+            default_encryption_scope: self.default_encryption_scope,
+            deny_encryption_scope_override: self.deny_encryption_scope_override,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -85,6 +97,10 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             metadata: self.metadata,
+            default_encryption_scope: self.default_encryption_scope,
+            deny_encryption_scope_override: self.deny_encryption_scope_override,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -125,6 +141,68 @@ where
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
             metadata: self.metadata,
+            default_encryption_scope: self.default_encryption_scope,
+            deny_encryption_scope_override: self.deny_encryption_scope_override,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> ExtraHeadersOption<'a> for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> ExtraHeadersSupport<'a> for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    type O = CreateBuilder<'a, ContainerNameSet, PublicAccessSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> ApiVersionOption<'a> for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> ApiVersionSupport<'a> for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    type O = CreateBuilder<'a, ContainerNameSet, PublicAccessSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        CreateBuilder {
+            p_container_name: PhantomData {},
+            p_public_access: PhantomData {},
+            client: self.client,
+            container_name: self.container_name,
+            public_access: self.public_access,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            metadata: self.metadata,
+            default_encryption_scope: self.default_encryption_scope,
+            deny_encryption_scope_override: self.deny_encryption_scope_override,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -146,6 +224,10 @@ where
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
             metadata: self.metadata,
+            default_encryption_scope: self.default_encryption_scope,
+            deny_encryption_scope_override: self.deny_encryption_scope_override,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -185,6 +267,10 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             metadata: self.metadata,
+            default_encryption_scope: self.default_encryption_scope,
+            deny_encryption_scope_override: self.deny_encryption_scope_override,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -209,6 +295,80 @@ impl<'a> CreateBuilder<'a, No, No> {
             timeout: None,
             client_request_id: None,
             metadata: None,
+            default_encryption_scope: None,
+            deny_encryption_scope_override: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> DefaultEncryptionScopeOption<'a> for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    fn default_encryption_scope(&self) -> Option<&'a str> {
+        self.default_encryption_scope
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> DefaultEncryptionScopeSupport<'a> for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    type O = CreateBuilder<'a, ContainerNameSet, PublicAccessSet>;
+
+    fn with_default_encryption_scope(self, default_encryption_scope: &'a str) -> Self::O {
+        CreateBuilder {
+            p_container_name: PhantomData {},
+            p_public_access: PhantomData {},
+            client: self.client,
+            container_name: self.container_name,
+            public_access: self.public_access,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            metadata: self.metadata,
+            default_encryption_scope: Some(default_encryption_scope),
+            deny_encryption_scope_override: self.deny_encryption_scope_override,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> DenyEncryptionScopeOverrideOption for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    fn deny_encryption_scope_override(&self) -> Option<bool> {
+        self.deny_encryption_scope_override
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> DenyEncryptionScopeOverrideSupport for CreateBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    type O = CreateBuilder<'a, ContainerNameSet, PublicAccessSet>;
+
+    fn with_deny_encryption_scope_override(self, deny_encryption_scope_override: bool) -> Self::O {
+        CreateBuilder {
+            p_container_name: PhantomData {},
+            p_public_access: PhantomData {},
+            client: self.client,
+            container_name: self.container_name,
+            public_access: self.public_access,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            metadata: self.metadata,
+            default_encryption_scope: self.default_encryption_scope,
+            deny_encryption_scope_override: Some(deny_encryption_scope_override),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -221,21 +381,51 @@ impl<'a> CreateBuilder<'a, Yes, Yes> {
             uri = format!("{}&{}", uri, nm);
         }
 
-        let req = self.client().perform_request(
-            &uri,
-            &Method::PUT,
-            |ref mut request| {
-                ClientRequestIdOption::add_header(&self, request);
-                PublicAccessRequired::add_header(&self, request);
-                MetadataOption::add_header(&self, request);
-            },
-            Some(&[]),
-        );
+        let required = self.default_encryption_scope().map(|_| ENCRYPTION_SCOPE_VERSION);
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), required);
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                    PublicAccessRequired::add_header(&self, request);
+                    MetadataOption::add_header(&self, request);
+                    DefaultEncryptionScopeOption::add_header(&self, request);
+                    DenyEncryptionScopeOverrideOption::add_header(&self, request);
+                },
+                Some(&[]),
+            )
+        });
 
         done(req)
             .from_err()
             .and_then(move |future_response| check_status_extract_body(future_response, StatusCode::CREATED).and_then(|_| ok(())))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<(), AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+
+    /// Creates the container unless it exists already. Resolves to `true`
+    /// if the container was created, `false` if it was already there.
+    pub fn create_if_not_exists(self) -> impl Future<Item = bool, Error = AzureError> {
+        self.finalize().then(|result| match result {
+            Ok(()) => Ok(true),
+            Err(ref err) if err.is_already_exists() => Ok(false),
+            Err(err) => Err(err),
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn create_if_not_exists_async(self) -> impl std::future::Future<Output = Result<bool, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.create_if_not_exists())
+    }
 }
 
 #[cfg(test)]
@@ -247,7 +437,10 @@ mod test {
         let client = Client::new("a", "b").unwrap();
         let create = CreateBuilder::new(&client)
             .with_container_name("ciccio")
-            .with_public_access(PublicAccess::None);
+            .with_public_access(PublicAccess::None)
+            .with_default_encryption_scope("scope1")
+            .with_deny_encryption_scope_override(true);
+        assert_eq!(create.default_encryption_scope(), Some("scope1"));
         println!("container_name == {}", create.container_name());
         create.public_access();
     }
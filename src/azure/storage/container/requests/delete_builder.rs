@@ -1,8 +1,8 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, LeaseIdOption,
-    LeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::client::Client;
@@ -18,6 +18,8 @@ pub struct DeleteBuilder<'a, ContainerNameSet> {
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
     lease_id: Option<&'a LeaseId>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a, ContainerNameSet> ClientRequired<'a> for DeleteBuilder<'a, ContainerNameSet> {
@@ -49,6 +51,58 @@ where
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersOption<'a> for DeleteBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersSupport<'a> for DeleteBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = DeleteBuilder<'a, ContainerNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionOption<'a> for DeleteBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionSupport<'a> for DeleteBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = DeleteBuilder<'a, ContainerNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self {
+        DeleteBuilder {
+            p_container_name: PhantomData {},
+            client: self.client,
+            container_name: self.container_name,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -76,6 +130,8 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             lease_id: Some(lease_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -103,6 +159,8 @@ where
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -121,6 +179,8 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -140,6 +200,8 @@ impl<'a> DeleteBuilder<'a, No> {
             timeout: None,
             client_request_id: None,
             lease_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -157,6 +219,8 @@ impl<'a> DeleteBuilder<'a, Yes> {
             &Method::DELETE,
             |ref mut request| {
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
                 LeaseIdOption::add_header(&self, request);
             },
             Some(&[]),
@@ -166,6 +230,26 @@ impl<'a> DeleteBuilder<'a, Yes> {
             check_status_extract_headers_and_body(future_response, StatusCode::ACCEPTED).and_then(|_| ok(()))
         })
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<(), AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+
+    /// Deletes the container if it exists. Resolves to `true` if the
+    /// container was deleted, `false` if there was nothing to delete.
+    pub fn delete_if_exists(self) -> impl Future<Item = bool, Error = AzureError> {
+        self.finalize().then(|result| match result {
+            Ok(()) => Ok(true),
+            Err(ref err) if err.is_not_found() => Ok(false),
+            Err(err) => Err(err),
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn delete_if_exists_async(self) -> impl std::future::Future<Output = Result<bool, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.delete_if_exists())
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,8 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, LeaseIdOption,
-    LeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::client::Client;
@@ -22,6 +22,8 @@ where
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
     lease_id: Option<&'a LeaseId>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a, ContainerNameSet> ClientRequired<'a> for GetACLBuilder<'a, ContainerNameSet>
@@ -42,6 +44,8 @@ impl<'a> GetACLBuilder<'a, No> {
             timeout: None,
             client_request_id: None,
             lease_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -66,6 +70,8 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -83,6 +89,8 @@ impl<'a> GetACLBuilder<'a, Yes> {
             &Method::GET,
             |ref mut request| {
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
                 LeaseIdOption::add_header(&self, request);
             },
             None,
@@ -96,6 +104,11 @@ impl<'a> GetACLBuilder<'a, Yes> {
                 done(GetACLResponse::from_response(&body, &headers))
             })
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetACLResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
 
 impl<'a, ContainerNameSet> TimeoutOption for GetACLBuilder<'a, ContainerNameSet>
@@ -121,6 +134,8 @@ where
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -148,6 +163,58 @@ where
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersOption<'a> for GetACLBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersSupport<'a> for GetACLBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = GetACLBuilder<'a, ContainerNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionOption<'a> for GetACLBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionSupport<'a> for GetACLBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = GetACLBuilder<'a, ContainerNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetACLBuilder {
+            p_container_name: PhantomData {},
+            client: self.client,
+            container_name: self.container_name,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -175,6 +242,8 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             lease_id: Some(lease_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -1,12 +1,14 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, LeaseIdOption,
-    LeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::client::Client;
 use crate::azure::storage::container::responses::GetPropertiesResponse;
+use crate::azure::storage::container::CONTAINER_PROPERTIES_VERSION;
+use crate::azure::storage::rest_client::HEADER_VERSION;
 use futures::future::{done, Future};
 use hyper::{Method, StatusCode};
 use std::marker::PhantomData;
@@ -22,6 +24,8 @@ where
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
     lease_id: Option<&'a LeaseId>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a, ContainerNameSet> ClientRequired<'a> for GetPropertiesBuilder<'a, ContainerNameSet>
@@ -42,6 +46,8 @@ impl<'a> GetPropertiesBuilder<'a, No> {
             timeout: None,
             client_request_id: None,
             lease_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -66,6 +72,8 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -78,15 +86,22 @@ impl<'a> GetPropertiesBuilder<'a, Yes> {
             uri = format!("{}&{}", uri, nm);
         }
 
-        let req = self.client().perform_request(
-            &uri,
-            &Method::HEAD,
-            |ref mut request| {
-                ClientRequestIdOption::add_header(&self, request);
-                LeaseIdOption::add_header(&self, request);
-            },
-            None,
-        );
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(CONTAINER_PROPERTIES_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::HEAD,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                    LeaseIdOption::add_header(&self, request);
+                },
+                None,
+            )
+        });
 
         let container_name = self.container_name().to_owned();
 
@@ -95,6 +110,11 @@ impl<'a> GetPropertiesBuilder<'a, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
             .and_then(|(headers, _body)| done(GetPropertiesResponse::from_response(container_name, &headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetPropertiesResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
 
 impl<'a, ContainerNameSet> TimeoutOption for GetPropertiesBuilder<'a, ContainerNameSet>
@@ -120,6 +140,8 @@ where
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -147,6 +169,58 @@ where
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersOption<'a> for GetPropertiesBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet> ExtraHeadersSupport<'a> for GetPropertiesBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = GetPropertiesBuilder<'a, ContainerNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionOption<'a> for GetPropertiesBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet> ApiVersionSupport<'a> for GetPropertiesBuilder<'a, ContainerNameSet>
+where
+    ContainerNameSet: ToAssign,
+{
+    type O = GetPropertiesBuilder<'a, ContainerNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetPropertiesBuilder {
+            p_container_name: PhantomData {},
+            client: self.client,
+            container_name: self.container_name,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -174,6 +248,8 @@ where
             timeout: self.timeout,
             client_request_id: self.client_request_id,
             lease_id: Some(lease_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -1,14 +1,18 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
 use crate::azure::core::incompletevector::IncompleteVector;
 use crate::azure::core::parsing::{cast_optional, traverse};
 use crate::azure::core::{
-    request_id_from_headers, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, NextMarkerOption, NextMarkerSupport,
-    PrefixOption, PrefixSupport, TimeoutOption, TimeoutSupport,
+    client_request_id_from_headers_optional, request_id_from_headers, ApiVersionOption, ApiVersionSupport, ClientRequestIdOption,
+    ClientRequestIdSupport, ClientRequired, ExtraHeadersOption, ExtraHeadersSupport, IncludeDeletedOption, IncludeDeletedSupport,
+    NextMarkerOption, NextMarkerSupport, PrefixOption, PrefixSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::storage::client::Client;
 use crate::azure::storage::container::responses::ListContainersResponse;
-use crate::azure::storage::container::Container;
+use crate::azure::storage::container::{Container, SOFT_DELETE_VERSION};
+use crate::azure::storage::rest_client::HEADER_VERSION;
 use futures::future::{done, Future};
+use futures::{stream, Stream};
 use hyper::{Method, StatusCode};
 use xml::Element;
 
@@ -17,10 +21,13 @@ pub struct ListBuilder<'a> {
     client: &'a Client,
     max_results: u64,
     include_metadata: bool,
+    include_deleted: bool,
     next_marker: Option<&'a str>,
     prefix: Option<&'a str>,
     timeout: Option<u64>,
     client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> ClientRequired<'a> for ListBuilder<'a> {
@@ -35,10 +42,13 @@ impl<'a> ListBuilder<'a> {
             client,
             max_results: 5000,
             include_metadata: false,
+            include_deleted: false,
             next_marker: None,
             prefix: None,
             timeout: None,
             client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -54,10 +64,13 @@ impl<'a> ListBuilder<'a> {
             client: self.client,
             max_results,
             include_metadata: self.include_metadata,
+            include_deleted: self.include_deleted,
             next_marker: self.next_marker,
             prefix: self.prefix,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 
@@ -70,18 +83,26 @@ impl<'a> ListBuilder<'a> {
             client: self.client,
             max_results: self.max_results,
             include_metadata: true,
+            include_deleted: self.include_deleted,
             next_marker: self.next_marker,
             prefix: self.prefix,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 
     pub fn finalize(self) -> impl Future<Item = ListContainersResponse, Error = AzureError> {
         let mut uri = format!("{}?comp=list&maxresults={}", self.client().blob_uri(), self.max_results());
 
-        if self.is_metadata_included() {
-            uri = format!("{}&include=metadata", uri);
+        let include: Vec<&str> = [("metadata", self.is_metadata_included()), ("deleted", self.include_deleted())]
+            .iter()
+            .filter(|(_, included)| *included)
+            .map(|(include, _)| *include)
+            .collect();
+        if !include.is_empty() {
+            uri = format!("{}&include={}", uri, include.join(","));
         }
 
         if let Some(nm) = PrefixOption::to_uri_parameter(&self) {
@@ -96,26 +117,101 @@ impl<'a> ListBuilder<'a> {
             uri = format!("{}&{}", uri, nm);
         }
 
-        let req = self.client().perform_request(
-            &uri,
-            &Method::GET,
-            |ref mut request| {
-                ClientRequestIdOption::add_header(&self, request);
-            },
-            None,
-        );
+        let required = if self.include_deleted() { Some(SOFT_DELETE_VERSION) } else { None };
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), required);
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::GET,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                None,
+            )
+        });
 
         done(req).from_err().and_then(move |future_response| {
             check_status_extract_headers_and_body_as_string(future_response, StatusCode::OK).and_then(move |(headers, body)| {
                 done(incomplete_vector_from_response(&body)).and_then(move |incomplete_vector| {
-                    done(request_id_from_headers(&headers)).map(|request_id| ListContainersResponse {
-                        incomplete_vector,
-                        request_id,
-                    })
+                    let response = || {
+                        Ok(ListContainersResponse {
+                            incomplete_vector,
+                            request_id: request_id_from_headers(&headers)?,
+                            client_request_id: client_request_id_from_headers_optional(&headers)?,
+                            common_headers: CommonStorageResponseHeaders::from_headers(&headers)?,
+                        })
+                    };
+                    done(response())
                 })
             })
         })
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<ListContainersResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+
+    /// Returns every container in the account, transparently following the
+    /// `NextMarker` continuation tokens. If a marker has been specified the
+    /// listing starts from there.
+    pub fn stream(self) -> impl Stream<Item = Container, Error = AzureError> {
+        self.pages()
+            .map(|response| stream::iter_ok(response.incomplete_vector.vector))
+            .flatten()
+    }
+
+    /// Same as `stream` but yields one `ListContainersResponse` per page.
+    pub fn pages(self) -> impl Stream<Item = ListContainersResponse, Error = AzureError> {
+        let client = self.client().clone();
+        let max_results = self.max_results;
+        let include_metadata = self.include_metadata;
+        let include_deleted = self.include_deleted;
+        let prefix = self.prefix.map(|v| v.to_owned());
+        let timeout = self.timeout;
+        let client_request_id = self.client_request_id.map(|v| v.to_owned());
+        let api_version = self.api_version.map(|v| v.to_owned());
+
+        // None means the listing is over
+        let start = Some(self.next_marker.map(|v| v.to_owned()));
+
+        stream::unfold(start, move |state| {
+            let marker = state?;
+
+            let mut req = ListBuilder::new(&client).with_max_results(max_results);
+
+            if include_metadata {
+                req = req.include_metadata();
+            }
+            if include_deleted {
+                req = req.with_include_deleted();
+            }
+            if let Some(ref marker) = &marker {
+                req = req.with_next_marker(marker);
+            }
+            if let Some(ref prefix) = &prefix {
+                req = req.with_prefix(prefix);
+            }
+            if let Some(timeout) = timeout {
+                req = req.with_timeout(timeout);
+            }
+            if let Some(ref client_request_id) = &client_request_id {
+                req = req.with_client_request_id(client_request_id);
+            }
+            if let Some(ref api_version) = &api_version {
+                req = req.with_api_version(api_version);
+            }
+
+            Some(req.finalize().map(|response| {
+                let next = response.incomplete_vector.token().map(|t| Some(t.to_owned()));
+                (response, next)
+            }))
+        })
+    }
 }
 
 impl<'a> PrefixOption<'a> for ListBuilder<'a> {
@@ -132,10 +228,13 @@ impl<'a> PrefixSupport<'a> for ListBuilder<'a> {
             client: self.client,
             max_results: self.max_results,
             include_metadata: self.include_metadata,
+            include_deleted: self.include_deleted,
             next_marker: self.next_marker,
             prefix: Some(prefix),
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -154,10 +253,40 @@ impl<'a> TimeoutSupport for ListBuilder<'a> {
             client: self.client,
             max_results: self.max_results,
             include_metadata: self.include_metadata,
+            include_deleted: self.include_deleted,
             next_marker: self.next_marker,
             prefix: self.prefix,
             timeout: Some(timeout),
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a> IncludeDeletedOption for ListBuilder<'a> {
+    fn include_deleted(&self) -> bool {
+        self.include_deleted
+    }
+}
+
+impl<'a> IncludeDeletedSupport for ListBuilder<'a> {
+    type O = ListBuilder<'a>;
+
+    /// Lists the soft-deleted containers too, with their `deleted`,
+    /// `version`, `deleted_time` and `remaining_retention_days`.
+    fn with_include_deleted(self) -> Self::O {
+        ListBuilder {
+            client: self.client,
+            max_results: self.max_results,
+            include_metadata: self.include_metadata,
+            include_deleted: true,
+            next_marker: self.next_marker,
+            prefix: self.prefix,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -179,10 +308,13 @@ impl<'a> NextMarkerSupport<'a> for ListBuilder<'a> {
             client: self.client,
             max_results: self.max_results,
             include_metadata: self.include_metadata,
+            include_deleted: self.include_deleted,
             next_marker: Some(next_marker),
             prefix: self.prefix,
             timeout: self.timeout,
             client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -201,10 +333,53 @@ impl<'a> ClientRequestIdSupport<'a> for ListBuilder<'a> {
             client: self.client,
             max_results: self.max_results,
             include_metadata: self.include_metadata,
+            include_deleted: self.include_deleted,
             next_marker: self.next_marker,
             prefix: self.prefix,
             timeout: self.timeout,
             client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a> ExtraHeadersOption<'a> for ListBuilder<'a> {
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a> ExtraHeadersSupport<'a> for ListBuilder<'a> {
+    type O = ListBuilder<'a>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a> ApiVersionOption<'a> for ListBuilder<'a> {
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a> ApiVersionSupport<'a> for ListBuilder<'a> {
+    type O = ListBuilder<'a>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ListBuilder {
+            client: self.client,
+            max_results: self.max_results,
+            include_metadata: self.include_metadata,
+            include_deleted: self.include_deleted,
+            next_marker: self.next_marker,
+            prefix: self.prefix,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
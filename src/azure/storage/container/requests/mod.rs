@@ -1,20 +1,26 @@
 mod acquire_lease_builder;
 mod break_lease_builder;
+mod change_lease_builder;
 mod create_builder;
 mod delete_builder;
+mod exists_builder;
 mod get_acl_builder;
 mod get_properties_builder;
 mod list_builder;
 mod release_lease_builder;
 mod renew_lease_builder;
+mod restore_builder;
 mod set_acl_builder;
 pub use self::acquire_lease_builder::AcquireLeaseBuilder;
 pub use self::break_lease_builder::BreakLeaseBuilder;
+pub use self::change_lease_builder::ChangeLeaseBuilder;
 pub use self::create_builder::CreateBuilder;
 pub use self::delete_builder::DeleteBuilder;
+pub use self::exists_builder::ExistsBuilder;
 pub use self::get_acl_builder::GetACLBuilder;
 pub use self::get_properties_builder::GetPropertiesBuilder;
 pub use self::list_builder::ListBuilder;
 pub use self::release_lease_builder::ReleaseLeaseBuilder;
 pub use self::renew_lease_builder::RenewLeaseBuilder;
+pub use self::restore_builder::RestoreBuilder;
 pub use self::set_acl_builder::SetACLBuilder;
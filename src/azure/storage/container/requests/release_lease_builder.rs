@@ -2,8 +2,8 @@ use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureErr
 use crate::azure::core::headers::LEASE_ACTION;
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, LeaseIdRequired,
-    LeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdRequired, LeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, ToAssign, Yes};
 use crate::azure::storage::client::Client;
@@ -25,6 +25,8 @@ where
     client_request_id: Option<&'a str>,
     timeout: Option<u64>,
     lease_id: Option<&'a LeaseId>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a> ReleaseLeaseBuilder<'a, No, No> {
@@ -37,6 +39,8 @@ impl<'a> ReleaseLeaseBuilder<'a, No, No> {
             lease_id: None,
             client_request_id: None,
             timeout: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -105,6 +109,8 @@ where
             client_request_id: self.client_request_id,
             timeout: self.timeout,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -125,6 +131,63 @@ where
             client_request_id: Some(client_request_id),
             timeout: self.timeout,
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, LeaseIdSet> ExtraHeadersOption<'a> for ReleaseLeaseBuilder<'a, ContainerNameSet, LeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, LeaseIdSet> ExtraHeadersSupport<'a> for ReleaseLeaseBuilder<'a, ContainerNameSet, LeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+{
+    type O = ReleaseLeaseBuilder<'a, ContainerNameSet, LeaseIdSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, LeaseIdSet> ApiVersionOption<'a> for ReleaseLeaseBuilder<'a, ContainerNameSet, LeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, LeaseIdSet> ApiVersionSupport<'a> for ReleaseLeaseBuilder<'a, ContainerNameSet, LeaseIdSet>
+where
+    ContainerNameSet: ToAssign,
+    LeaseIdSet: ToAssign,
+{
+    type O = ReleaseLeaseBuilder<'a, ContainerNameSet, LeaseIdSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ReleaseLeaseBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_lease_id: PhantomData {},
+            container_name: self.container_name,
+            client_request_id: self.client_request_id,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -145,6 +208,8 @@ where
             client_request_id: self.client_request_id,
             timeout: Some(timeout),
             lease_id: self.lease_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -165,6 +230,8 @@ where
             client_request_id: self.client_request_id,
             timeout: self.timeout,
             lease_id: Some(lease_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -194,6 +261,8 @@ impl<'a> ReleaseLeaseBuilder<'a, Yes, Yes> {
             &Method::PUT,
             |ref mut request| {
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
                 LeaseIdRequired::add_header(&self, request);
                 request.header(LEASE_ACTION, "release");
             },
@@ -205,4 +274,9 @@ impl<'a> ReleaseLeaseBuilder<'a, Yes, Yes> {
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
             .and_then(|(headers, _body)| done(ReleaseLeaseResponse::from_headers(&headers)))
     }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<ReleaseLeaseResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
 }
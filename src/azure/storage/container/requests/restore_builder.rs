@@ -0,0 +1,368 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, TimeoutOption, TimeoutSupport,
+};
+use crate::azure::core::{No, ToAssign, Yes};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::container::responses::RestoreResponse;
+use crate::azure::storage::container::{
+    generate_container_uri, DeletedContainerNameRequired, DeletedContainerNameSupport, DeletedContainerVersionRequired,
+    DeletedContainerVersionSupport, SOFT_DELETE_VERSION,
+};
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use futures::future::{done, Future};
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+#[derive(Debug, Clone)]
+pub struct RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    client: &'a Client,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_deleted_container_name: PhantomData<DeletedContainerNameSet>,
+    p_deleted_container_version: PhantomData<DeletedContainerVersionSet>,
+    container_name: Option<&'a str>,
+    deleted_container_name: Option<&'a str>,
+    deleted_container_version: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> RestoreBuilder<'a, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> RestoreBuilder<'a, No, No, No> {
+        RestoreBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_deleted_container_name: PhantomData {},
+            deleted_container_name: None,
+            p_deleted_container_version: PhantomData {},
+            deleted_container_version: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> ClientRequired<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, DeletedContainerNameSet, DeletedContainerVersionSet> ContainerNameRequired<'a>
+    for RestoreBuilder<'a, Yes, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerVersionSet> DeletedContainerNameRequired<'a>
+    for RestoreBuilder<'a, ContainerNameSet, Yes, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    fn deleted_container_name(&self) -> &'a str {
+        self.deleted_container_name.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet> DeletedContainerVersionRequired<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+{
+    fn deleted_container_version(&self) -> &'a str {
+        self.deleted_container_version.unwrap()
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> TimeoutOption
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> ClientRequestIdOption<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> ExtraHeadersOption<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> ApiVersionOption<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> ContainerNameSupport<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    type O = RestoreBuilder<'a, Yes, DeletedContainerNameSet, DeletedContainerVersionSet>;
+
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        RestoreBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_deleted_container_name: PhantomData {},
+            p_deleted_container_version: PhantomData {},
+            container_name: Some(container_name),
+            deleted_container_name: self.deleted_container_name,
+            deleted_container_version: self.deleted_container_version,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> DeletedContainerNameSupport<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    type O = RestoreBuilder<'a, ContainerNameSet, Yes, DeletedContainerVersionSet>;
+
+    fn with_deleted_container_name(self, deleted_container_name: &'a str) -> Self::O {
+        RestoreBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_deleted_container_name: PhantomData {},
+            p_deleted_container_version: PhantomData {},
+            container_name: self.container_name,
+            deleted_container_name: Some(deleted_container_name),
+            deleted_container_version: self.deleted_container_version,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> DeletedContainerVersionSupport<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    type O = RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, Yes>;
+
+    fn with_deleted_container_version(self, deleted_container_version: &'a str) -> Self::O {
+        RestoreBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_deleted_container_name: PhantomData {},
+            p_deleted_container_version: PhantomData {},
+            container_name: self.container_name,
+            deleted_container_name: self.deleted_container_name,
+            deleted_container_version: Some(deleted_container_version),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> TimeoutSupport
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    type O = RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        RestoreBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_deleted_container_name: PhantomData {},
+            p_deleted_container_version: PhantomData {},
+            container_name: self.container_name,
+            deleted_container_name: self.deleted_container_name,
+            deleted_container_version: self.deleted_container_version,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> ClientRequestIdSupport<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    type O = RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        RestoreBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_deleted_container_name: PhantomData {},
+            p_deleted_container_version: PhantomData {},
+            container_name: self.container_name,
+            deleted_container_name: self.deleted_container_name,
+            deleted_container_version: self.deleted_container_version,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> ExtraHeadersSupport<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    type O = RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet> ApiVersionSupport<'a>
+    for RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+    type O = RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        RestoreBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_deleted_container_name: PhantomData {},
+            p_deleted_container_version: PhantomData {},
+            container_name: self.container_name,
+            deleted_container_name: self.deleted_container_name,
+            deleted_container_version: self.deleted_container_version,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+    RestoreBuilder<'a, ContainerNameSet, DeletedContainerNameSet, DeletedContainerVersionSet>
+where
+    ContainerNameSet: ToAssign,
+    DeletedContainerNameSet: ToAssign,
+    DeletedContainerVersionSet: ToAssign,
+{
+}
+
+impl<'a> RestoreBuilder<'a, Yes, Yes, Yes> {
+    /// Restores the deleted container as `container_name`, which must not
+    /// exist. The restored container gets back its blobs, their snapshots
+    /// and versions, and its metadata.
+    pub fn finalize(self) -> impl Future<Item = RestoreResponse, Error = AzureError> {
+        let mut uri = generate_container_uri(&self, Some("restype=container&comp=undelete"));
+
+        if let Some(nm) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, nm);
+        }
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(SOFT_DELETE_VERSION));
+        let req = api_version.and_then(|api_version| {
+            self.client().perform_request(
+                &uri,
+                &Method::PUT,
+                |ref mut request| {
+                    DeletedContainerNameRequired::add_header(&self, request);
+                    DeletedContainerVersionRequired::add_header(&self, request);
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(&[]),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::CREATED))
+            .and_then(|(headers, _body)| done(RestoreResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<RestoreResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -1,12 +1,13 @@
 use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
 use crate::azure::core::lease::LeaseId;
 use crate::azure::core::{
-    ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired, ContainerNameSupport, LeaseIdOption,
-    LeaseIdSupport, TimeoutOption, TimeoutSupport,
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContainerNameRequired,
+    ContainerNameSupport, ExtraHeadersOption, ExtraHeadersSupport, LeaseIdOption, LeaseIdSupport, TimeoutOption, TimeoutSupport,
 };
 use crate::azure::core::{No, StoredAccessPolicyList, ToAssign, Yes};
 use crate::azure::storage::client::Client;
-use crate::azure::storage::container::{public_access_from_header, PublicAccess, PublicAccessRequired, PublicAccessSupport};
+use crate::azure::storage::container::responses::SetACLResponse;
+use crate::azure::storage::container::{PublicAccess, PublicAccessRequired, PublicAccessSupport};
 use futures::future::{done, Future};
 use hyper::{Method, StatusCode};
 use std::marker::PhantomData;
@@ -26,6 +27,8 @@ where
     client_request_id: Option<&'a str>,
     lease_id: Option<&'a LeaseId>,
     stored_access_policy_list: Option<&'a StoredAccessPolicyList>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
 }
 
 impl<'a, ContainerNameSet, PublicAccessSet> ClientRequired<'a> for SetACLBuilder<'a, ContainerNameSet, PublicAccessSet>
@@ -50,6 +53,8 @@ impl<'a> SetACLBuilder<'a, No, No> {
             client_request_id: None,
             lease_id: None,
             stored_access_policy_list: None,
+            extra_headers: Vec::new(),
+            api_version: None,
         }
     }
 }
@@ -81,6 +86,8 @@ where
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
             stored_access_policy_list: self.stored_access_policy_list,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -106,12 +113,14 @@ where
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
             stored_access_policy_list: Some(sapl),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
 
 impl<'a> SetACLBuilder<'a, Yes, Yes> {
-    pub fn finalize(self) -> impl Future<Item = PublicAccess, Error = AzureError> {
+    pub fn finalize(self) -> impl Future<Item = SetACLResponse, Error = AzureError> {
         let mut uri = format!("{}/{}?restype=container&comp=acl", self.client().blob_uri(), self.container_name());
 
         if let Some(nm) = TimeoutOption::to_uri_parameter(&self) {
@@ -130,6 +139,8 @@ impl<'a> SetACLBuilder<'a, Yes, Yes> {
             &Method::PUT,
             |ref mut request| {
                 ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
                 LeaseIdOption::add_header(&self, request);
                 PublicAccessRequired::add_header(&self, request);
             },
@@ -142,7 +153,12 @@ impl<'a> SetACLBuilder<'a, Yes, Yes> {
         done(req)
             .from_err()
             .and_then(move |future_response| check_status_extract_headers_and_body(future_response, StatusCode::OK))
-            .and_then(|(headers, _body)| done(public_access_from_header(&headers)))
+            .and_then(|(headers, _body)| done(SetACLResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<SetACLResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
     }
 }
 
@@ -174,6 +190,8 @@ where
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
             stored_access_policy_list: self.stored_access_policy_list,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -206,6 +224,65 @@ where
             client_request_id: Some(client_request_id),
             lease_id: self.lease_id,
             stored_access_policy_list: self.stored_access_policy_list,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> ExtraHeadersOption<'a> for SetACLBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> ExtraHeadersSupport<'a> for SetACLBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    type O = SetACLBuilder<'a, ContainerNameSet, PublicAccessSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> ApiVersionOption<'a> for SetACLBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ContainerNameSet, PublicAccessSet> ApiVersionSupport<'a> for SetACLBuilder<'a, ContainerNameSet, PublicAccessSet>
+where
+    ContainerNameSet: ToAssign,
+    PublicAccessSet: ToAssign,
+{
+    type O = SetACLBuilder<'a, ContainerNameSet, PublicAccessSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SetACLBuilder {
+            p_container_name: PhantomData {},
+            p_public_access: PhantomData {},
+            client: self.client,
+            container_name: self.container_name,
+            public_access: self.public_access,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            lease_id: self.lease_id,
+            stored_access_policy_list: self.stored_access_policy_list,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
         }
     }
 }
@@ -238,6 +315,8 @@ where
             client_request_id: self.client_request_id,
             lease_id: Some(lease_id),
             stored_access_policy_list: self.stored_access_policy_list,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -260,6 +339,8 @@ where
             client_request_id: self.client_request_id,
             lease_id: self.lease_id,
             stored_access_policy_list: self.stored_access_policy_list,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
         }
     }
 }
@@ -7,5 +7,6 @@ response_from_headers!(BreakLeaseResponse,
 		       last_modified_from_headers -> last_modified: DateTime<Utc>,
                        lease_time_from_headers -> lease_time: u8,
 		       request_id_from_headers -> request_id: RequestId,
+		       client_request_id_from_headers_optional -> client_request_id: Option<String>,
 		       date_from_headers -> date: DateTime<Utc>
 );
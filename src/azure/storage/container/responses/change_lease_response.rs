@@ -0,0 +1,3 @@
+use crate::azure::storage::container::responses::AcquireLeaseResponse;
+
+pub type ChangeLeaseResponse = AcquireLeaseResponse;
@@ -1,6 +1,7 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
 use crate::azure::core::headers::REQUEST_ID;
-use crate::azure::core::{RequestId, StoredAccessPolicyList};
+use crate::azure::core::{client_request_id_from_headers_optional, RequestId, StoredAccessPolicyList};
 use crate::azure::storage::container::{public_access_from_header, PublicAccess};
 use chrono::{DateTime, FixedOffset};
 use http::header;
@@ -14,8 +15,10 @@ pub struct GetACLResponse {
     pub etag: String,
     pub last_modified: DateTime<FixedOffset>,
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub date: DateTime<FixedOffset>,
     pub stored_access_policy_list: StoredAccessPolicyList,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl GetACLResponse {
@@ -55,15 +58,71 @@ impl GetACLResponse {
         };
         let date = DateTime::parse_from_rfc2822(date)?;
 
-        let stored_access_policy_list = StoredAccessPolicyList::from_xml(&body[3..])?;
+        let stored_access_policy_list = StoredAccessPolicyList::from_xml(body.trim_start_matches('\u{feff}'))?;
 
         Ok(GetACLResponse {
             public_access,
             etag: etag.to_owned(),
             last_modified,
             request_id: Uuid::parse_str(request_id)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
             date,
             stored_access_policy_list,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::HeaderValue;
+
+    const BODY: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?><SignedIdentifiers><SignedIdentifier><Id>MTIzNDU2Nzg5MDEyMzQ1Njc4OTAxMjM0NTY3ODkwMTI=</Id>\
+<AccessPolicy><Start>2019-10-15T10:00:00.0000000Z</Start><Expiry>2019-10-16T10:00:00.0000000Z</Expiry><Permission>rwd</Permission>\
+</AccessPolicy></SignedIdentifier></SignedIdentifiers>";
+
+    fn headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ETAG, HeaderValue::from_static("\"0x8D75159EA3C1F8E\""));
+        headers.insert(header::LAST_MODIFIED, HeaderValue::from_static("Tue, 15 Oct 2019 09:00:00 GMT"));
+        headers.insert(REQUEST_ID, HeaderValue::from_static("6ee5ea5b-0003-0040-4b77-2e5c7f000000"));
+        headers.insert(header::DATE, HeaderValue::from_static("Tue, 15 Oct 2019 10:00:00 GMT"));
+        headers.insert("x-ms-blob-public-access", HeaderValue::from_static("container"));
+        headers
+    }
+
+    fn assert_acl(response: &GetACLResponse) {
+        assert_eq!(response.public_access, PublicAccess::Container);
+        assert_eq!(response.etag, "\"0x8D75159EA3C1F8E\"");
+        let policies = &response.stored_access_policy_list.stored_access;
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].id, "MTIzNDU2Nzg5MDEyMzQ1Njc4OTAxMjM0NTY3ODkwMTI=");
+        assert_eq!(policies[0].start, DateTime::parse_from_rfc3339("2019-10-15T10:00:00Z").unwrap());
+        assert_eq!(policies[0].expiry, DateTime::parse_from_rfc3339("2019-10-16T10:00:00Z").unwrap());
+        assert_eq!(policies[0].permission, "rwd");
+    }
+
+    #[test]
+    fn test_body_with_bom() {
+        let body = Chunk::from(format!("\u{feff}{}", BODY));
+        assert_acl(&GetACLResponse::from_response(&body, &headers()).unwrap());
+    }
+
+    #[test]
+    fn test_body_without_bom() {
+        let body = Chunk::from(BODY);
+        assert_acl(&GetACLResponse::from_response(&body, &headers()).unwrap());
+    }
+
+    #[test]
+    fn test_no_policies() {
+        for body in &[
+            "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?><SignedIdentifiers />",
+            "<SignedIdentifiers />",
+        ] {
+            let response = GetACLResponse::from_response(&Chunk::from(*body), &headers()).unwrap();
+            assert!(response.stored_access_policy_list.stored_access.is_empty());
+        }
+    }
+}
@@ -1,6 +1,7 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::errors::AzureError;
 use crate::azure::core::headers::REQUEST_ID;
-use crate::azure::core::RequestId;
+use crate::azure::core::{client_request_id_from_headers_optional, RequestId};
 use crate::azure::storage::container::Container;
 use chrono::{DateTime, FixedOffset};
 use http::HeaderMap;
@@ -11,7 +12,9 @@ use uuid::Uuid;
 pub struct GetPropertiesResponse {
     pub container: Container,
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
     pub date: DateTime<FixedOffset>,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl GetPropertiesResponse {
@@ -34,7 +37,9 @@ impl GetPropertiesResponse {
         Ok(GetPropertiesResponse {
             container,
             request_id,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
             date,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
         })
     }
 }
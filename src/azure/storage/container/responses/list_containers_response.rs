@@ -1,3 +1,4 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
 use crate::azure::core::incompletevector::IncompleteVector;
 use crate::azure::core::RequestId;
 use crate::azure::storage::container::Container;
@@ -6,6 +7,8 @@ use crate::azure::storage::container::Container;
 pub struct ListContainersResponse {
     pub incomplete_vector: IncompleteVector<Container>,
     pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub common_headers: CommonStorageResponseHeaders,
 }
 
 impl ListContainersResponse {
@@ -1,14 +1,20 @@
 mod acquire_lease_response;
 mod break_lease_response;
+mod change_lease_response;
 mod get_acl_response;
 mod get_properties_response;
 mod list_containers_response;
 mod release_lease_response;
 mod renew_lease_response;
+mod restore_response;
+mod set_acl_response;
 pub use self::acquire_lease_response::AcquireLeaseResponse;
 pub use self::break_lease_response::BreakLeaseResponse;
+pub use self::change_lease_response::ChangeLeaseResponse;
 pub use self::get_acl_response::GetACLResponse;
 pub use self::get_properties_response::GetPropertiesResponse;
 pub use self::list_containers_response::ListContainersResponse;
 pub use self::release_lease_response::ReleaseLeaseResponse;
 pub use self::renew_lease_response::RenewLeaseResponse;
+pub use self::restore_response::RestoreResponse;
+pub use self::set_acl_response::SetACLResponse;
@@ -0,0 +1,183 @@
+use std::fmt;
+use std::str::FromStr;
+
+macro_rules! storage_error_codes {
+    ($($code:ident),* $(,)*) => {
+        /// The error codes returned by the storage services (in the
+        /// `x-ms-error-code` header and in the error body). The codes this
+        /// crate does not know are kept in `Other`.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum StorageErrorCode {
+            $($code,)*
+            Other(String),
+        }
+
+        impl FromStr for StorageErrorCode {
+            type Err = ();
+
+            fn from_str(s: &str) -> Result<StorageErrorCode, ()> {
+                match s {
+                    $(stringify!($code) => Ok(StorageErrorCode::$code),)*
+                    other => Ok(StorageErrorCode::Other(other.to_owned())),
+                }
+            }
+        }
+
+        impl AsRef<str> for StorageErrorCode {
+            fn as_ref(&self) -> &str {
+                match self {
+                    $(StorageErrorCode::$code => stringify!($code),)*
+                    StorageErrorCode::Other(other) => other,
+                }
+            }
+        }
+    };
+}
+
+storage_error_codes!(
+    // common
+    AccountIsDisabled,
+    AuthenticationFailed,
+    AuthorizationFailure,
+    AuthorizationPermissionMismatch,
+    ConditionHeadersNotSupported,
+    ConditionNotMet,
+    InternalError,
+    InvalidHeaderValue,
+    InvalidInput,
+    InvalidMd5,
+    InvalidQueryParameterValue,
+    InvalidResourceName,
+    InvalidUri,
+    Md5Mismatch,
+    MissingRequiredHeader,
+    OperationTimedOut,
+    RequestBodyTooLarge,
+    ResourceAlreadyExists,
+    ResourceNotFound,
+    ResourceTypeMismatch,
+    ServerBusy,
+    // blob
+    BlobAlreadyExists,
+    BlobArchived,
+    BlobBeingRehydrated,
+    BlobNotFound,
+    BlobOverwritten,
+    ContainerAlreadyExists,
+    ContainerBeingDeleted,
+    ContainerDisabled,
+    ContainerNotFound,
+    InvalidBlobOrBlock,
+    InvalidBlobTier,
+    InvalidBlobType,
+    InvalidBlockId,
+    InvalidBlockList,
+    InvalidPageRange,
+    InvalidRange,
+    LeaseAlreadyBroken,
+    LeaseAlreadyPresent,
+    LeaseIdMismatchWithBlobOperation,
+    LeaseIdMismatchWithContainerOperation,
+    LeaseIdMismatchWithLeaseOperation,
+    LeaseIdMissing,
+    LeaseIsBreakingAndCannotBeAcquired,
+    LeaseIsBreakingAndCannotBeChanged,
+    LeaseIsBrokenAndCannotBeRenewed,
+    LeaseLost,
+    LeaseNotPresentWithBlobOperation,
+    LeaseNotPresentWithContainerOperation,
+    LeaseNotPresentWithLeaseOperation,
+    NoPendingCopyOperation,
+    PendingCopyOperation,
+    SequenceNumberConditionNotMet,
+    SnapshotsPresent,
+    TargetConditionNotMet,
+    // queue
+    MessageNotFound,
+    MessageTooLarge,
+    PopReceiptMismatch,
+    QueueAlreadyExists,
+    QueueBeingDeleted,
+    QueueDisabled,
+    QueueNotEmpty,
+    QueueNotFound,
+    // table
+    EntityAlreadyExists,
+    EntityNotFound,
+    EntityTooLarge,
+    PropertiesNeedValue,
+    TableAlreadyExists,
+    TableBeingDeleted,
+    TableNotFound,
+    UpdateConditionNotSatisfied,
+    // file
+    ParentNotFound,
+    ShareAlreadyExists,
+    ShareBeingDeleted,
+    ShareDisabled,
+    ShareNotFound,
+);
+
+impl StorageErrorCode {
+    /// The resource the request tried to create exists already.
+    pub fn is_already_exists(&self) -> bool {
+        matches!(
+            self,
+            StorageErrorCode::ResourceAlreadyExists
+                | StorageErrorCode::BlobAlreadyExists
+                | StorageErrorCode::ContainerAlreadyExists
+                | StorageErrorCode::QueueAlreadyExists
+                | StorageErrorCode::EntityAlreadyExists
+                | StorageErrorCode::TableAlreadyExists
+                | StorageErrorCode::ShareAlreadyExists
+        )
+    }
+
+    /// The lease given with the request is not the active one (or there
+    /// is no active lease).
+    pub fn is_lease_mismatch(&self) -> bool {
+        matches!(
+            self,
+            StorageErrorCode::LeaseIdMismatchWithBlobOperation
+                | StorageErrorCode::LeaseIdMismatchWithContainerOperation
+                | StorageErrorCode::LeaseIdMismatchWithLeaseOperation
+                | StorageErrorCode::LeaseIdMissing
+                | StorageErrorCode::LeaseLost
+                | StorageErrorCode::LeaseNotPresentWithBlobOperation
+                | StorageErrorCode::LeaseNotPresentWithContainerOperation
+                | StorageErrorCode::LeaseNotPresentWithLeaseOperation
+        )
+    }
+
+    /// A conditional header (`If-Match` and the like) did not match.
+    pub fn is_condition_not_met(&self) -> bool {
+        matches!(
+            self,
+            StorageErrorCode::ConditionNotMet
+                | StorageErrorCode::SequenceNumberConditionNotMet
+                | StorageErrorCode::TargetConditionNotMet
+                | StorageErrorCode::UpdateConditionNotSatisfied
+        )
+    }
+}
+
+impl fmt::Display for StorageErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("BlobNotFound".parse(), Ok(StorageErrorCode::BlobNotFound));
+        assert_eq!("SomethingNew".parse(), Ok(StorageErrorCode::Other("SomethingNew".to_owned())));
+        assert_eq!(StorageErrorCode::LeaseIdMissing.to_string(), "LeaseIdMissing");
+        assert!(StorageErrorCode::ContainerAlreadyExists.is_already_exists());
+        assert!(StorageErrorCode::LeaseIdMismatchWithBlobOperation.is_lease_mismatch());
+        assert!(!StorageErrorCode::BlobNotFound.is_condition_not_met());
+    }
+}
@@ -0,0 +1,255 @@
+pub mod requests;
+pub mod responses;
+
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::headers::{FILE_CONTENT_LENGTH, SHARE_QUOTA};
+use crate::azure::core::incompletevector::IncompleteVector;
+use crate::azure::core::parsing::{cast_must, cast_optional, traverse};
+use crate::azure::core::{ClientRequired, COMPLETE_ENCODE_SET};
+use http::request::Builder;
+use url::percent_encoding::utf8_percent_encode;
+use xml::Element;
+use xml::Xml::ElementNode;
+
+pub trait ShareNameSupport<'a> {
+    type O;
+    fn with_share_name(self, share_name: &'a str) -> Self::O;
+}
+
+pub trait ShareNameRequired<'a> {
+    fn share_name(&self) -> &'a str;
+}
+
+/// Path of a directory, relative to the share root (for example
+/// `logs/2019`).
+pub trait DirectoryPathSupport<'a> {
+    type O;
+    fn with_directory_path(self, directory_path: &'a str) -> Self::O;
+}
+
+pub trait DirectoryPathRequired<'a> {
+    fn directory_path(&self) -> &'a str;
+}
+
+pub trait DirectoryPathOption<'a> {
+    fn directory_path(&self) -> Option<&'a str>;
+}
+
+/// Path of a file, relative to the share root (for example
+/// `logs/2019/app.log`).
+pub trait FilePathSupport<'a> {
+    type O;
+    fn with_file_path(self, file_path: &'a str) -> Self::O;
+}
+
+pub trait FilePathRequired<'a> {
+    fn file_path(&self) -> &'a str;
+}
+
+/// Share quota, in GiB.
+pub trait ShareQuotaSupport {
+    type O;
+    fn with_share_quota(self, share_quota: u64) -> Self::O;
+}
+
+pub trait ShareQuotaOption {
+    fn share_quota(&self) -> Option<u64>;
+
+    fn add_header(&self, builder: &mut Builder) {
+        if let Some(share_quota) = self.share_quota() {
+            builder.header(SHARE_QUOTA, &share_quota.to_string() as &str);
+        }
+    }
+}
+
+pub trait ShareQuotaRequired {
+    fn share_quota(&self) -> u64;
+
+    fn add_header(&self, builder: &mut Builder) {
+        builder.header(SHARE_QUOTA, &self.share_quota().to_string() as &str);
+    }
+}
+
+/// Size of the file, in bytes. Files are created with their maximum size
+/// and then written with `PutRange`.
+pub trait FileSizeSupport {
+    type O;
+    fn with_file_size(self, file_size: u64) -> Self::O;
+}
+
+pub trait FileSizeRequired {
+    fn file_size(&self) -> u64;
+
+    fn add_header(&self, builder: &mut Builder) {
+        builder.header(FILE_CONTENT_LENGTH, &self.file_size().to_string() as &str);
+    }
+}
+
+/// An entry of a directory listing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DirectoryItem {
+    Directory(String),
+    File { name: String, content_length: u64 },
+}
+
+impl DirectoryItem {
+    pub fn name(&self) -> &str {
+        match self {
+            DirectoryItem::Directory(name) => name,
+            DirectoryItem::File { name, .. } => name,
+        }
+    }
+
+    pub fn is_directory(&self) -> bool {
+        match self {
+            DirectoryItem::Directory(_) => true,
+            DirectoryItem::File { .. } => false,
+        }
+    }
+}
+
+pub(crate) fn incomplete_vector_from_response(body: &str) -> Result<IncompleteVector<DirectoryItem>, AzureError> {
+    let elem: Element = body.parse()?;
+
+    let mut v = Vec::new();
+
+    // directories and files are interleaved so we cannot use traverse
+    // on each kind
+    for entries in traverse(&elem, &["Entries"], true)? {
+        for node in &entries.children {
+            let node = match node {
+                ElementNode(node) => node,
+                _ => continue,
+            };
+
+            match &node.name as &str {
+                "Directory" => v.push(DirectoryItem::Directory(cast_must::<String>(node, &["Name"])?)),
+                "File" => v.push(DirectoryItem::File {
+                    name: cast_must::<String>(node, &["Name"])?,
+                    content_length: cast_must::<u64>(node, &["Properties", "Content-Length"])?,
+                }),
+                _ => {}
+            }
+        }
+    }
+
+    let next_marker = match cast_optional::<String>(&elem, &["NextMarker"])? {
+        Some(ref nm) if nm.is_empty() => None,
+        Some(nm) => Some(nm),
+        None => None,
+    };
+
+    Ok(IncompleteVector::new(next_marker, v))
+}
+
+// Slashes separate the path segments so they must not be encoded.
+fn encode_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| utf8_percent_encode(segment, COMPLETE_ENCODE_SET).to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub(crate) fn generate_share_uri<'a, T>(t: &T, params: &str) -> String
+where
+    T: ClientRequired<'a> + ShareNameRequired<'a>,
+{
+    format!(
+        "{}/{}?{}",
+        t.client().file_uri(),
+        utf8_percent_encode(t.share_name(), COMPLETE_ENCODE_SET),
+        params
+    )
+}
+
+pub(crate) fn generate_directory_uri<'a, T>(t: &T, directory_path: Option<&str>, params: &str) -> String
+where
+    T: ClientRequired<'a> + ShareNameRequired<'a>,
+{
+    match directory_path {
+        Some(directory_path) => format!(
+            "{}/{}/{}?{}",
+            t.client().file_uri(),
+            utf8_percent_encode(t.share_name(), COMPLETE_ENCODE_SET),
+            encode_path(directory_path.trim_matches('/')),
+            params
+        ),
+        None => generate_share_uri(t, params),
+    }
+}
+
+pub(crate) fn generate_file_uri<'a, T>(t: &T, params: Option<&str>) -> String
+where
+    T: ClientRequired<'a> + ShareNameRequired<'a> + FilePathRequired<'a>,
+{
+    match params {
+        Some(ref params) => format!(
+            "{}/{}/{}?{}",
+            t.client().file_uri(),
+            utf8_percent_encode(t.share_name(), COMPLETE_ENCODE_SET),
+            encode_path(t.file_path().trim_start_matches('/')),
+            params
+        ),
+        None => format!(
+            "{}/{}/{}",
+            t.client().file_uri(),
+            utf8_percent_encode(t.share_name(), COMPLETE_ENCODE_SET),
+            encode_path(t.file_path().trim_start_matches('/'))
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_incomplete_vector_from_response() {
+        let body = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+            <EnumerationResults ServiceEndpoint=\"https://myaccount.file.core.windows.net/\" ShareName=\"myshare\" DirectoryPath=\"logs\">
+              <Marker>string-value</Marker>
+              <MaxResults>3</MaxResults>
+              <Entries>
+                <File>
+                  <Name>a.log</Name>
+                  <Properties>
+                    <Content-Length>1024</Content-Length>
+                  </Properties>
+                </File>
+                <Directory>
+                  <Name>archive</Name>
+                  <Properties />
+                </Directory>
+                <File>
+                  <Name>b.log</Name>
+                  <Properties>
+                    <Content-Length>0</Content-Length>
+                  </Properties>
+                </File>
+              </Entries>
+              <NextMarker>c.log</NextMarker>
+            </EnumerationResults>";
+
+        let iv = incomplete_vector_from_response(body).unwrap();
+        assert_eq!(iv.token(), Some("c.log"));
+        assert_eq!(
+            iv.vector,
+            vec![
+                DirectoryItem::File {
+                    name: "a.log".to_owned(),
+                    content_length: 1024
+                },
+                DirectoryItem::Directory("archive".to_owned()),
+                DirectoryItem::File {
+                    name: "b.log".to_owned(),
+                    content_length: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_path() {
+        assert_eq!(encode_path("logs/2019 march/a&b.log"), "logs/2019%20march/a%26b.log");
+    }
+}
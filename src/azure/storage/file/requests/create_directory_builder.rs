@@ -0,0 +1,322 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, MetadataOption, MetadataSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::file::responses::CreateDirectoryResponse;
+use crate::azure::storage::file::{
+    generate_directory_uri, DirectoryPathRequired, DirectoryPathSupport, ShareNameRequired, ShareNameSupport,
+};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    client: &'a Client,
+    p_share_name: PhantomData<ShareNameSet>,
+    p_directory_path: PhantomData<DirectoryPathSet>,
+    share_name: Option<&'a str>,
+    directory_path: Option<&'a str>,
+    metadata: Option<&'a Metadata>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> CreateDirectoryBuilder<'a, No, No> {
+    pub(crate) fn new(client: &'a Client) -> CreateDirectoryBuilder<'a, No, No> {
+        CreateDirectoryBuilder {
+            client,
+            p_share_name: PhantomData {},
+            share_name: None,
+            p_directory_path: PhantomData {},
+            directory_path: None,
+            metadata: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> ClientRequired<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, DirectoryPathSet> ShareNameRequired<'a> for CreateDirectoryBuilder<'a, Yes, DirectoryPathSet>
+where
+    DirectoryPathSet: ToAssign,
+{
+    fn share_name(&self) -> &'a str {
+        self.share_name.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet> DirectoryPathRequired<'a> for CreateDirectoryBuilder<'a, ShareNameSet, Yes>
+where
+    ShareNameSet: ToAssign,
+{
+    fn directory_path(&self) -> &'a str {
+        self.directory_path.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> MetadataOption<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    fn metadata(&self) -> Option<&'a Metadata> {
+        self.metadata
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> TimeoutOption for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> ClientRequestIdOption<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> ShareNameSupport<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    type O = CreateDirectoryBuilder<'a, Yes, DirectoryPathSet>;
+
+    fn with_share_name(self, share_name: &'a str) -> Self::O {
+        CreateDirectoryBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_directory_path: PhantomData {},
+            share_name: Some(share_name),
+            directory_path: self.directory_path,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> DirectoryPathSupport<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    type O = CreateDirectoryBuilder<'a, ShareNameSet, Yes>;
+
+    fn with_directory_path(self, directory_path: &'a str) -> Self::O {
+        CreateDirectoryBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_directory_path: PhantomData {},
+            share_name: self.share_name,
+            directory_path: Some(directory_path),
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> MetadataSupport<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    type O = CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>;
+
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
+        CreateDirectoryBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_directory_path: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            metadata: Some(metadata),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> TimeoutSupport for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    type O = CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        CreateDirectoryBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_directory_path: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            metadata: self.metadata,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> ClientRequestIdSupport<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    type O = CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        CreateDirectoryBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_directory_path: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> ExtraHeadersOption<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> ExtraHeadersSupport<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    type O = CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> ApiVersionOption<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ShareNameSet, DirectoryPathSet> ApiVersionSupport<'a> for CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+    type O = CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        CreateDirectoryBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_directory_path: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ShareNameSet, DirectoryPathSet> CreateDirectoryBuilder<'a, ShareNameSet, DirectoryPathSet>
+where
+    ShareNameSet: ToAssign,
+    DirectoryPathSet: ToAssign,
+{
+}
+
+impl<'a> CreateDirectoryBuilder<'a, Yes, Yes> {
+    /// Creates the directory. The parent directory must exist.
+    pub fn finalize(self) -> impl Future<Item = CreateDirectoryResponse, Error = AzureError> {
+        let mut uri = generate_directory_uri(&self, Some(self.directory_path()), "restype=directory");
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::PUT,
+            |ref mut request| {
+                MetadataOption::add_header(&self, request);
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(&[]),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
+            .and_then(move |(headers, _body)| done(CreateDirectoryResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<CreateDirectoryResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,450 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::headers::{FILE_CONTENT_TYPE, FILE_TYPE};
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ContentTypeOption,
+    ContentTypeSupport, ExtraHeadersOption, ExtraHeadersSupport, MetadataOption, MetadataSupport, No, TimeoutOption, TimeoutSupport,
+    ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::file::responses::CreateFileResponse;
+use crate::azure::storage::file::{
+    generate_file_uri, FilePathRequired, FilePathSupport, FileSizeRequired, FileSizeSupport, ShareNameRequired, ShareNameSupport,
+};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    client: &'a Client,
+    p_share_name: PhantomData<ShareNameSet>,
+    p_file_path: PhantomData<FilePathSet>,
+    p_file_size: PhantomData<FileSizeSet>,
+    share_name: Option<&'a str>,
+    file_path: Option<&'a str>,
+    file_size: Option<u64>,
+    content_type: Option<&'a str>,
+    metadata: Option<&'a Metadata>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> CreateFileBuilder<'a, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> CreateFileBuilder<'a, No, No, No> {
+        CreateFileBuilder {
+            client,
+            p_share_name: PhantomData {},
+            share_name: None,
+            p_file_path: PhantomData {},
+            file_path: None,
+            p_file_size: PhantomData {},
+            file_size: None,
+            content_type: None,
+            metadata: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ClientRequired<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, FilePathSet, FileSizeSet> ShareNameRequired<'a> for CreateFileBuilder<'a, Yes, FilePathSet, FileSizeSet>
+where
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn share_name(&self) -> &'a str {
+        self.share_name.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, FileSizeSet> FilePathRequired<'a> for CreateFileBuilder<'a, ShareNameSet, Yes, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn file_path(&self) -> &'a str {
+        self.file_path.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet> FileSizeRequired for CreateFileBuilder<'a, ShareNameSet, FilePathSet, Yes>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+{
+    fn file_size(&self) -> u64 {
+        self.file_size.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ContentTypeOption<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn content_type(&self) -> Option<&'a str> {
+        self.content_type
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> MetadataOption<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn metadata(&self) -> Option<&'a Metadata> {
+        self.metadata
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> TimeoutOption for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ClientRequestIdOption<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ShareNameSupport<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, Yes, FilePathSet, FileSizeSet>;
+
+    fn with_share_name(self, share_name: &'a str) -> Self::O {
+        CreateFileBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_file_size: PhantomData {},
+            share_name: Some(share_name),
+            file_path: self.file_path,
+            file_size: self.file_size,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> FilePathSupport<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, ShareNameSet, Yes, FileSizeSet>;
+
+    fn with_file_path(self, file_path: &'a str) -> Self::O {
+        CreateFileBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_file_size: PhantomData {},
+            share_name: self.share_name,
+            file_path: Some(file_path),
+            file_size: self.file_size,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> FileSizeSupport for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, ShareNameSet, FilePathSet, Yes>;
+
+    fn with_file_size(self, file_size: u64) -> Self::O {
+        CreateFileBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_file_size: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            file_size: Some(file_size),
+            content_type: self.content_type,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ContentTypeSupport<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>;
+
+    fn with_content_type(self, content_type: &'a str) -> Self::O {
+        CreateFileBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_file_size: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            file_size: self.file_size,
+            content_type: Some(content_type),
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> MetadataSupport<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>;
+
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
+        CreateFileBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_file_size: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            file_size: self.file_size,
+            content_type: self.content_type,
+            metadata: Some(metadata),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> TimeoutSupport for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        CreateFileBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_file_size: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            file_size: self.file_size,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ClientRequestIdSupport<'a>
+    for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        CreateFileBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_file_size: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            file_size: self.file_size,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ExtraHeadersOption<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ExtraHeadersSupport<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ApiVersionOption<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> ApiVersionSupport<'a> for CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+    type O = CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        CreateFileBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_file_size: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            file_size: self.file_size,
+            content_type: self.content_type,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ShareNameSet, FilePathSet, FileSizeSet> CreateFileBuilder<'a, ShareNameSet, FilePathSet, FileSizeSet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    FileSizeSet: ToAssign,
+{
+}
+
+impl<'a> CreateFileBuilder<'a, Yes, Yes, Yes> {
+    /// Creates (or replaces) the file. The file is allocated with the
+    /// specified size and zero filled: write its contents with
+    /// `put_range`.
+    pub fn finalize(self) -> impl Future<Item = CreateFileResponse, Error = AzureError> {
+        let mut uri = generate_file_uri(&self, None);
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}?{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::PUT,
+            |ref mut request| {
+                request.header(FILE_TYPE, "file");
+                FileSizeRequired::add_header(&self, request);
+                // the file content type is sent in its own header, the
+                // request itself has no body
+                if let Some(content_type) = self.content_type() {
+                    request.header(FILE_CONTENT_TYPE, content_type);
+                }
+                MetadataOption::add_header(&self, request);
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(&[]),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
+            .and_then(move |(headers, _body)| done(CreateFileResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<CreateFileResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
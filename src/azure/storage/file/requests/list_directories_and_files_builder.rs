@@ -0,0 +1,379 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, MaxResultsOption, MaxResultsSupport, NextMarkerOption, NextMarkerSupport, No, PrefixOption, PrefixSupport,
+    TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::file::responses::ListDirectoriesAndFilesResponse;
+use crate::azure::storage::file::{generate_directory_uri, DirectoryPathOption, DirectoryPathSupport, ShareNameRequired, ShareNameSupport};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_share_name: PhantomData<ShareNameSet>,
+    share_name: Option<&'a str>,
+    directory_path: Option<&'a str>,
+    prefix: Option<&'a str>,
+    next_marker: Option<&'a str>,
+    max_results: Option<u32>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> ListDirectoriesAndFilesBuilder<'a, No> {
+    pub(crate) fn new(client: &'a Client) -> ListDirectoriesAndFilesBuilder<'a, No> {
+        ListDirectoriesAndFilesBuilder {
+            client,
+            p_share_name: PhantomData {},
+            share_name: None,
+            directory_path: None,
+            prefix: None,
+            next_marker: None,
+            max_results: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> ClientRequired<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> ShareNameRequired<'a> for ListDirectoriesAndFilesBuilder<'a, Yes> {
+    fn share_name(&self) -> &'a str {
+        self.share_name.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet> DirectoryPathOption<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn directory_path(&self) -> Option<&'a str> {
+        self.directory_path
+    }
+}
+
+impl<'a, ShareNameSet> PrefixOption<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn prefix(&self) -> Option<&'a str> {
+        self.prefix
+    }
+}
+
+impl<'a, ShareNameSet> NextMarkerOption<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn next_marker(&self) -> Option<&'a str> {
+        self.next_marker
+    }
+}
+
+impl<'a, ShareNameSet> MaxResultsOption for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn max_results(&self) -> Option<u32> {
+        self.max_results
+    }
+}
+
+impl<'a, ShareNameSet> TimeoutOption for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ShareNameSet> ClientRequestIdOption<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ShareNameSet> ShareNameSupport<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, Yes>;
+
+    fn with_share_name(self, share_name: &'a str) -> Self::O {
+        ListDirectoriesAndFilesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: Some(share_name),
+            directory_path: self.directory_path,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> DirectoryPathSupport<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, ShareNameSet>;
+
+    fn with_directory_path(self, directory_path: &'a str) -> Self::O {
+        ListDirectoriesAndFilesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            directory_path: Some(directory_path),
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> PrefixSupport<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, ShareNameSet>;
+
+    fn with_prefix(self, prefix: &'a str) -> Self::O {
+        ListDirectoriesAndFilesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            prefix: Some(prefix),
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> NextMarkerSupport<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, ShareNameSet>;
+
+    fn with_next_marker(self, next_marker: &'a str) -> Self::O {
+        ListDirectoriesAndFilesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            prefix: self.prefix,
+            next_marker: Some(next_marker),
+            max_results: self.max_results,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> MaxResultsSupport for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, ShareNameSet>;
+
+    fn with_max_results(self, max_results: u32) -> Self::O {
+        ListDirectoriesAndFilesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: Some(max_results),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> TimeoutSupport for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, ShareNameSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        ListDirectoriesAndFilesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> ClientRequestIdSupport<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, ShareNameSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        ListDirectoriesAndFilesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> ExtraHeadersOption<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ShareNameSet> ExtraHeadersSupport<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, ShareNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ShareNameSet> ApiVersionOption<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ShareNameSet> ApiVersionSupport<'a> for ListDirectoriesAndFilesBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = ListDirectoriesAndFilesBuilder<'a, ShareNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        ListDirectoriesAndFilesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            directory_path: self.directory_path,
+            prefix: self.prefix,
+            next_marker: self.next_marker,
+            max_results: self.max_results,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ShareNameSet> ListDirectoriesAndFilesBuilder<'a, ShareNameSet> where ShareNameSet: ToAssign {}
+
+impl<'a> ListDirectoriesAndFilesBuilder<'a, Yes> {
+    /// Lists the directories and files directly below the directory (the
+    /// share root if not specified). The listing is not recursive.
+    pub fn finalize(self) -> impl Future<Item = ListDirectoriesAndFilesResponse, Error = AzureError> {
+        let mut uri = generate_directory_uri(&self, self.directory_path(), "restype=directory&comp=list");
+
+        if let Some(prefix) = PrefixOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, prefix);
+        }
+        if let Some(next_marker) = NextMarkerOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, next_marker);
+        }
+        if let Some(max_results) = MaxResultsOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, max_results);
+        }
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::GET,
+            |ref mut request| {
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            None,
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body_as_string(response, StatusCode::OK))
+            .and_then(move |(headers, body)| done(ListDirectoriesAndFilesResponse::from_response(&headers, &body)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<ListDirectoriesAndFilesResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,20 @@
+mod create_directory_builder;
+mod create_file_builder;
+mod create_share_builder;
+mod delete_share_builder;
+mod get_file_builder;
+mod get_share_properties_builder;
+mod list_directories_and_files_builder;
+mod put_range_builder;
+mod set_share_metadata_builder;
+mod set_share_properties_builder;
+pub use self::create_directory_builder::CreateDirectoryBuilder;
+pub use self::create_file_builder::CreateFileBuilder;
+pub use self::create_share_builder::CreateShareBuilder;
+pub use self::delete_share_builder::DeleteShareBuilder;
+pub use self::get_file_builder::GetFileBuilder;
+pub use self::get_share_properties_builder::GetSharePropertiesBuilder;
+pub use self::list_directories_and_files_builder::ListDirectoriesAndFilesBuilder;
+pub use self::put_range_builder::PutRangeBuilder;
+pub use self::set_share_metadata_builder::SetShareMetadataBuilder;
+pub use self::set_share_properties_builder::SetSharePropertiesBuilder;
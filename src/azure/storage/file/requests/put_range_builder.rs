@@ -0,0 +1,484 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::headers::FILE_WRITE;
+use crate::azure::core::range::Range;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, BodyRequired, BodySupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    ContentMD5Option, ContentMD5Support, ExtraHeadersOption, ExtraHeadersSupport, No, RangeRequired, RangeSupport, TimeoutOption,
+    TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::file::responses::PutRangeResponse;
+use crate::azure::storage::file::{generate_file_uri, FilePathRequired, FilePathSupport, ShareNameRequired, ShareNameSupport};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    client: &'a Client,
+    p_share_name: PhantomData<ShareNameSet>,
+    p_file_path: PhantomData<FilePathSet>,
+    p_range: PhantomData<RangeSet>,
+    p_body: PhantomData<BodySet>,
+    share_name: Option<&'a str>,
+    file_path: Option<&'a str>,
+    range: Option<&'a Range>,
+    body: Option<&'a [u8]>,
+    content_md5: Option<&'a [u8]>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> PutRangeBuilder<'a, No, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> PutRangeBuilder<'a, No, No, No, No> {
+        PutRangeBuilder {
+            client,
+            p_share_name: PhantomData {},
+            share_name: None,
+            p_file_path: PhantomData {},
+            file_path: None,
+            p_range: PhantomData {},
+            range: None,
+            p_body: PhantomData {},
+            body: None,
+            content_md5: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ClientRequired<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, FilePathSet, RangeSet, BodySet> ShareNameRequired<'a> for PutRangeBuilder<'a, Yes, FilePathSet, RangeSet, BodySet>
+where
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn share_name(&self) -> &'a str {
+        self.share_name.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, RangeSet, BodySet> FilePathRequired<'a> for PutRangeBuilder<'a, ShareNameSet, Yes, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn file_path(&self) -> &'a str {
+        self.file_path.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, BodySet> RangeRequired<'a> for PutRangeBuilder<'a, ShareNameSet, FilePathSet, Yes, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn range(&self) -> &'a Range {
+        self.range.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet> BodyRequired<'a> for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, Yes>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+{
+    fn body(&self) -> &'a [u8] {
+        self.body.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ContentMD5Option<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn content_md5(&self) -> Option<&'a [u8]> {
+        self.content_md5
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> TimeoutOption for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ClientRequestIdOption<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ShareNameSupport<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, Yes, FilePathSet, RangeSet, BodySet>;
+
+    fn with_share_name(self, share_name: &'a str) -> Self::O {
+        PutRangeBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_range: PhantomData {},
+            p_body: PhantomData {},
+            share_name: Some(share_name),
+            file_path: self.file_path,
+            range: self.range,
+            body: self.body,
+            content_md5: self.content_md5,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> FilePathSupport<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, ShareNameSet, Yes, RangeSet, BodySet>;
+
+    fn with_file_path(self, file_path: &'a str) -> Self::O {
+        PutRangeBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_range: PhantomData {},
+            p_body: PhantomData {},
+            share_name: self.share_name,
+            file_path: Some(file_path),
+            range: self.range,
+            body: self.body,
+            content_md5: self.content_md5,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> RangeSupport<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, ShareNameSet, FilePathSet, Yes, BodySet>;
+
+    fn with_range(self, range: &'a Range) -> Self::O {
+        PutRangeBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_range: PhantomData {},
+            p_body: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            range: Some(range),
+            body: self.body,
+            content_md5: self.content_md5,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> BodySupport<'a> for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, Yes>;
+
+    fn with_body(self, body: &'a [u8]) -> Self::O {
+        PutRangeBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_range: PhantomData {},
+            p_body: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            range: self.range,
+            body: Some(body),
+            content_md5: self.content_md5,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ContentMD5Support<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>;
+
+    fn with_content_md5(self, content_md5: &'a [u8]) -> Self::O {
+        PutRangeBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_range: PhantomData {},
+            p_body: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            range: self.range,
+            body: self.body,
+            content_md5: Some(content_md5),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> TimeoutSupport for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        PutRangeBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_range: PhantomData {},
+            p_body: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            range: self.range,
+            body: self.body,
+            content_md5: self.content_md5,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ClientRequestIdSupport<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        PutRangeBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_range: PhantomData {},
+            p_body: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            range: self.range,
+            body: self.body,
+            content_md5: self.content_md5,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ExtraHeadersOption<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ExtraHeadersSupport<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ApiVersionOption<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> ApiVersionSupport<'a>
+    for PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+    type O = PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        PutRangeBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_file_path: PhantomData {},
+            p_range: PhantomData {},
+            p_body: PhantomData {},
+            share_name: self.share_name,
+            file_path: self.file_path,
+            range: self.range,
+            body: self.body,
+            content_md5: self.content_md5,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ShareNameSet, FilePathSet, RangeSet, BodySet> PutRangeBuilder<'a, ShareNameSet, FilePathSet, RangeSet, BodySet>
+where
+    ShareNameSet: ToAssign,
+    FilePathSet: ToAssign,
+    RangeSet: ToAssign,
+    BodySet: ToAssign,
+{
+}
+
+impl<'a> PutRangeBuilder<'a, Yes, Yes, Yes, Yes> {
+    /// Writes the body in the specified range of the file. The range end
+    /// is inclusive so the body length must be `end - start + 1` bytes
+    /// (4 MiB at most).
+    pub fn finalize(self) -> impl Future<Item = PutRangeResponse, Error = AzureError> {
+        let mut uri = generate_file_uri(&self, Some("comp=range"));
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::PUT,
+            |ref mut request| {
+                RangeRequired::add_header(&self, request);
+                request.header(FILE_WRITE, "update");
+                ContentMD5Option::add_header(&self, request);
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(self.body()),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
+            .and_then(move |(headers, _body)| done(PutRangeResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<PutRangeResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,254 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, MetadataOption, MetadataSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::file::responses::SetShareMetadataResponse;
+use crate::azure::storage::file::{generate_share_uri, ShareNameRequired, ShareNameSupport};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_share_name: PhantomData<ShareNameSet>,
+    share_name: Option<&'a str>,
+    metadata: Option<&'a Metadata>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> SetShareMetadataBuilder<'a, No> {
+    pub(crate) fn new(client: &'a Client) -> SetShareMetadataBuilder<'a, No> {
+        SetShareMetadataBuilder {
+            client,
+            p_share_name: PhantomData {},
+            share_name: None,
+            metadata: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> ClientRequired<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> ShareNameRequired<'a> for SetShareMetadataBuilder<'a, Yes> {
+    fn share_name(&self) -> &'a str {
+        self.share_name.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet> MetadataOption<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn metadata(&self) -> Option<&'a Metadata> {
+        self.metadata
+    }
+}
+
+impl<'a, ShareNameSet> TimeoutOption for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ShareNameSet> ClientRequestIdOption<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ShareNameSet> ShareNameSupport<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = SetShareMetadataBuilder<'a, Yes>;
+
+    fn with_share_name(self, share_name: &'a str) -> Self::O {
+        SetShareMetadataBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: Some(share_name),
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> MetadataSupport<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = SetShareMetadataBuilder<'a, ShareNameSet>;
+
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
+        SetShareMetadataBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            metadata: Some(metadata),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> TimeoutSupport for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = SetShareMetadataBuilder<'a, ShareNameSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        SetShareMetadataBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            metadata: self.metadata,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> ClientRequestIdSupport<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = SetShareMetadataBuilder<'a, ShareNameSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        SetShareMetadataBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet> ExtraHeadersOption<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ShareNameSet> ExtraHeadersSupport<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = SetShareMetadataBuilder<'a, ShareNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ShareNameSet> ApiVersionOption<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ShareNameSet> ApiVersionSupport<'a> for SetShareMetadataBuilder<'a, ShareNameSet>
+where
+    ShareNameSet: ToAssign,
+{
+    type O = SetShareMetadataBuilder<'a, ShareNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SetShareMetadataBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            share_name: self.share_name,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ShareNameSet> SetShareMetadataBuilder<'a, ShareNameSet> where ShareNameSet: ToAssign {}
+
+impl<'a> SetShareMetadataBuilder<'a, Yes> {
+    /// Replaces the share metadata. Calling it without metadata clears
+    /// it.
+    pub fn finalize(self) -> impl Future<Item = SetShareMetadataResponse, Error = AzureError> {
+        let mut uri = generate_share_uri(&self, "restype=share&comp=metadata");
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::PUT,
+            |ref mut request| {
+                MetadataOption::add_header(&self, request);
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(&[]),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::OK))
+            .and_then(move |(headers, _body)| done(SetShareMetadataResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<SetShareMetadataResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
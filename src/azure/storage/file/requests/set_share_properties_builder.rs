@@ -0,0 +1,279 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::file::responses::SetSharePropertiesResponse;
+use crate::azure::storage::file::{generate_share_uri, ShareNameRequired, ShareNameSupport, ShareQuotaRequired, ShareQuotaSupport};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    client: &'a Client,
+    p_share_name: PhantomData<ShareNameSet>,
+    p_share_quota: PhantomData<ShareQuotaSet>,
+    share_name: Option<&'a str>,
+    share_quota: Option<u64>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> SetSharePropertiesBuilder<'a, No, No> {
+    pub(crate) fn new(client: &'a Client) -> SetSharePropertiesBuilder<'a, No, No> {
+        SetSharePropertiesBuilder {
+            client,
+            p_share_name: PhantomData {},
+            share_name: None,
+            p_share_quota: PhantomData {},
+            share_quota: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ClientRequired<'a> for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, ShareQuotaSet> ShareNameRequired<'a> for SetSharePropertiesBuilder<'a, Yes, ShareQuotaSet>
+where
+    ShareQuotaSet: ToAssign,
+{
+    fn share_name(&self) -> &'a str {
+        self.share_name.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet> ShareQuotaRequired for SetSharePropertiesBuilder<'a, ShareNameSet, Yes>
+where
+    ShareNameSet: ToAssign,
+{
+    fn share_quota(&self) -> u64 {
+        self.share_quota.unwrap()
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> TimeoutOption for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ClientRequestIdOption<'a> for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ShareNameSupport<'a> for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    type O = SetSharePropertiesBuilder<'a, Yes, ShareQuotaSet>;
+
+    fn with_share_name(self, share_name: &'a str) -> Self::O {
+        SetSharePropertiesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_share_quota: PhantomData {},
+            share_name: Some(share_name),
+            share_quota: self.share_quota,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ShareQuotaSupport for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    type O = SetSharePropertiesBuilder<'a, ShareNameSet, Yes>;
+
+    fn with_share_quota(self, share_quota: u64) -> Self::O {
+        SetSharePropertiesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_share_quota: PhantomData {},
+            share_name: self.share_name,
+            share_quota: Some(share_quota),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> TimeoutSupport for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    type O = SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        SetSharePropertiesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_share_quota: PhantomData {},
+            share_name: self.share_name,
+            share_quota: self.share_quota,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ClientRequestIdSupport<'a> for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    type O = SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        SetSharePropertiesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_share_quota: PhantomData {},
+            share_name: self.share_name,
+            share_quota: self.share_quota,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ExtraHeadersOption<'a> for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ExtraHeadersSupport<'a> for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    type O = SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ApiVersionOption<'a> for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ShareNameSet, ShareQuotaSet> ApiVersionSupport<'a> for SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+    type O = SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SetSharePropertiesBuilder {
+            client: self.client,
+            p_share_name: PhantomData {},
+            p_share_quota: PhantomData {},
+            share_name: self.share_name,
+            share_quota: self.share_quota,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ShareNameSet, ShareQuotaSet> SetSharePropertiesBuilder<'a, ShareNameSet, ShareQuotaSet>
+where
+    ShareNameSet: ToAssign,
+    ShareQuotaSet: ToAssign,
+{
+}
+
+impl<'a> SetSharePropertiesBuilder<'a, Yes, Yes> {
+    /// Changes the share quota.
+    pub fn finalize(self) -> impl Future<Item = SetSharePropertiesResponse, Error = AzureError> {
+        let mut uri = generate_share_uri(&self, "restype=share&comp=properties");
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::PUT,
+            |ref mut request| {
+                ShareQuotaRequired::add_header(&self, request);
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(&[]),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::OK))
+            .and_then(move |(headers, _body)| done(SetSharePropertiesResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<SetSharePropertiesResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
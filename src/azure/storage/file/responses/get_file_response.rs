@@ -0,0 +1,39 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{
+    client_request_id_from_headers_optional, date_from_headers, etag_from_headers, last_modified_from_headers, request_id_from_headers,
+    RequestId,
+};
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetFileResponse {
+    pub data: Vec<u8>,
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl GetFileResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &[u8]) -> Result<GetFileResponse, AzureError> {
+        let etag = etag_from_headers(headers)?;
+        let last_modified = last_modified_from_headers(headers)?;
+        let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
+        let date = date_from_headers(headers)?;
+
+        Ok(GetFileResponse {
+            data: body.to_vec(),
+            etag,
+            last_modified,
+            request_id,
+            client_request_id,
+            date,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
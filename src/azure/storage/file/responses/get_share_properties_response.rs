@@ -0,0 +1,13 @@
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::RequestId;
+use chrono::{DateTime, Utc};
+
+response_from_headers!(GetSharePropertiesResponse ,
+		       share_quota_from_headers -> share_quota: u64,
+		       metadata_from_headers -> metadata: Metadata,
+		       etag_from_headers -> etag: String,
+		       last_modified_from_headers -> last_modified: DateTime<Utc>,
+		       request_id_from_headers -> request_id: RequestId,
+		       client_request_id_from_headers_optional -> client_request_id: Option<String>,
+		       date_from_headers -> date: DateTime<Utc>
+);
@@ -0,0 +1,33 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::incompletevector::IncompleteVector;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::storage::file::{incomplete_vector_from_response, DirectoryItem};
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListDirectoriesAndFilesResponse {
+    pub incomplete_vector: IncompleteVector<DirectoryItem>,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl ListDirectoriesAndFilesResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &str) -> Result<ListDirectoriesAndFilesResponse, AzureError> {
+        let incomplete_vector = incomplete_vector_from_response(body)?;
+        let request_id = request_id_from_headers(headers)?;
+        let client_request_id = client_request_id_from_headers_optional(headers)?;
+        let date = date_from_headers(headers)?;
+
+        Ok(ListDirectoriesAndFilesResponse {
+            incomplete_vector,
+            request_id,
+            client_request_id,
+            date,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
@@ -0,0 +1,20 @@
+mod create_directory_response;
+mod create_file_response;
+mod create_share_response;
+mod delete_share_response;
+mod get_file_response;
+mod get_share_properties_response;
+mod list_directories_and_files_response;
+mod put_range_response;
+mod set_share_metadata_response;
+mod set_share_properties_response;
+pub use self::create_directory_response::CreateDirectoryResponse;
+pub use self::create_file_response::CreateFileResponse;
+pub use self::create_share_response::CreateShareResponse;
+pub use self::delete_share_response::DeleteShareResponse;
+pub use self::get_file_response::GetFileResponse;
+pub use self::get_share_properties_response::GetSharePropertiesResponse;
+pub use self::list_directories_and_files_response::ListDirectoriesAndFilesResponse;
+pub use self::put_range_response::PutRangeResponse;
+pub use self::set_share_metadata_response::SetShareMetadataResponse;
+pub use self::set_share_properties_response::SetSharePropertiesResponse;
@@ -0,0 +1,245 @@
+use crate::azure::core::errors::{is_retriable_status, AzureError};
+use crate::azure::core::pipeline::{Next, PipelineFuture, PipelineRequest, Policy};
+use crate::azure::storage::service::secondary_uri;
+use futures::{future, Future};
+use hyper::Method;
+
+/// Where the read requests (`GET` and `HEAD`) of a geo-redundant (RA-GRS)
+/// account are sent, see `Client::with_location_mode`. The writes always
+/// go to the primary endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LocationMode {
+    #[default]
+    PrimaryOnly,
+    /// Reads from the read-only `-secondary` endpoint. The data there
+    /// lags behind the primary region, see `get_blob_service_stats`.
+    SecondaryOnly,
+    /// Reads from the primary endpoint and, if it fails with a transient
+    /// error once the retries are exhausted or its circuit breaker is
+    /// open, from the secondary one.
+    PrimaryThenSecondary,
+}
+
+/// Sends the reads to the secondary endpoint according to the
+/// `LocationMode`. The request is signed afterwards, and the signature
+/// names the account without the `-secondary` suffix as the service
+/// expects.
+#[derive(Debug, Clone)]
+pub(crate) struct LocationStage {
+    mode: LocationMode,
+    // the primary service uris with their secondary counterpart
+    endpoints: Vec<(String, String)>,
+}
+
+impl LocationStage {
+    pub(crate) fn new(mode: LocationMode, account: &str, primary_uris: &[&str]) -> LocationStage {
+        LocationStage {
+            mode,
+            endpoints: primary_uris
+                .iter()
+                .map(|primary_uri| (primary_uri.to_string(), secondary_uri(primary_uri, account)))
+                .collect(),
+        }
+    }
+
+    fn to_secondary(&self, request: &PipelineRequest) -> Option<PipelineRequest> {
+        if request.method != Method::GET && request.method != Method::HEAD {
+            return None;
+        }
+        let (primary, secondary) = self.endpoints.iter().find(|(primary, _)| is_below(&request.uri, primary))?;
+        Some(PipelineRequest {
+            uri: format!("{}{}", secondary, &request.uri[primary.len()..]),
+            ..request.clone()
+        })
+    }
+}
+
+// `uri` is `base` or one of its children
+fn is_below(uri: &str, base: &str) -> bool {
+    uri.starts_with(base) && uri[base.len()..].chars().next().is_none_or(|c| c == '/' || c == '?')
+}
+
+impl Policy for LocationStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        let secondary = match self.to_secondary(&request) {
+            Some(secondary) => secondary,
+            None => return next.send(request),
+        };
+
+        match self.mode {
+            LocationMode::PrimaryOnly => next.send(request),
+            LocationMode::SecondaryOnly => next.send(secondary),
+            LocationMode::PrimaryThenSecondary => Box::new(next.send(request).then(move |result| -> PipelineFuture {
+                let failed = match &result {
+                    Ok(response) => is_retriable_status(response.status()),
+                    Err(AzureError::CircuitBreakerOpen(_)) => true,
+                    Err(error) => error.is_retriable(),
+                };
+                if failed {
+                    debug!(
+                        "{} {} failed on the primary endpoint: reading from the secondary",
+                        secondary.method, secondary.uri
+                    );
+                    next.send(secondary)
+                } else {
+                    Box::new(future::result::<_, AzureError>(result))
+                }
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::core::{BlobNameSupport, ContainerNameSupport};
+    use crate::azure::storage::client::{Blob, Client, Container};
+    use crate::azure::storage::container::PublicAccess;
+    use crate::azure::storage::container::PublicAccessSupport;
+    use hyper::StatusCode;
+    use std::sync::Arc;
+
+    fn client(mode: LocationMode, transport: &Arc<ReplayTransport>) -> Client {
+        Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone())
+            .with_location_mode(mode)
+    }
+
+    fn get_blob_properties(client: &Client) -> Result<(), AzureError> {
+        client
+            .get_blob_properties()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .finalize()
+            .wait()
+            .map(|_| ())
+    }
+
+    const PROPERTIES: [(&str, &str); 10] = [
+        ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+        ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ("etag", "\"0x8D75157AA1C4E03\""),
+        ("content-length", "3"),
+        ("x-ms-blob-type", "BlockBlob"),
+        ("x-ms-creation-time", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ("x-ms-lease-status", "unlocked"),
+        ("x-ms-lease-state", "available"),
+        ("x-ms-server-encrypted", "true"),
+    ];
+
+    #[test]
+    fn test_secondary_only() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::OK, &PROPERTIES, b"")
+                .with_response(StatusCode::CREATED, &PROPERTIES[..2], b""),
+        );
+        let client = client(LocationMode::SecondaryOnly, &transport);
+
+        get_blob_properties(&client).unwrap();
+        // the writes stay on the primary
+        client
+            .create_container()
+            .with_container_name("c")
+            .with_public_access(PublicAccess::None)
+            .finalize()
+            .wait()
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].uri, "https://account-secondary.blob.core.windows.net/c/b");
+        assert_eq!(requests[1].uri, "https://account.blob.core.windows.net/c?restype=container");
+    }
+
+    #[test]
+    fn test_primary_then_secondary() {
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::SERVICE_UNAVAILABLE, &PROPERTIES[..2], b"")
+                .with_response(StatusCode::OK, &PROPERTIES, b"")
+                .with_response(StatusCode::NOT_FOUND, &PROPERTIES[..2], b""),
+        );
+        let client = client(LocationMode::PrimaryThenSecondary, &transport);
+
+        get_blob_properties(&client).unwrap();
+        // a 404 is an answer, not a failure of the primary
+        assert!(get_blob_properties(&client).unwrap_err().is_not_found());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].uri, "https://account.blob.core.windows.net/c/b");
+        assert_eq!(requests[1].uri, "https://account-secondary.blob.core.windows.net/c/b");
+        assert_eq!(requests[2].uri, "https://account.blob.core.windows.net/c/b");
+    }
+
+    #[test]
+    fn test_open_circuit_reads_from_secondary() {
+        use crate::azure::core::circuit_breaker::{CircuitBreakerPolicy, CircuitState};
+
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::SERVICE_UNAVAILABLE, &PROPERTIES[..2], b"")
+                .with_response(StatusCode::OK, &PROPERTIES, b"")
+                .with_response(StatusCode::OK, &PROPERTIES, b""),
+        );
+        let client = client(LocationMode::PrimaryThenSecondary, &transport)
+            .with_circuit_breaker(CircuitBreakerPolicy::new().with_minimum_requests(1));
+
+        get_blob_properties(&client).unwrap();
+        let circuit_breaker = client.circuit_breaker().unwrap();
+        assert_eq!(circuit_breaker.state("account.blob.core.windows.net"), CircuitState::Open);
+        // the primary is not even tried while its circuit is open
+        get_blob_properties(&client).unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].uri, "https://account.blob.core.windows.net/c/b");
+        assert_eq!(requests[1].uri, "https://account-secondary.blob.core.windows.net/c/b");
+        assert_eq!(requests[2].uri, "https://account-secondary.blob.core.windows.net/c/b");
+    }
+
+    #[test]
+    fn test_secondary_uris() {
+        let stage = LocationStage::new(
+            LocationMode::SecondaryOnly,
+            "devstoreaccount1",
+            &["http://127.0.0.1:10000/devstoreaccount1"],
+        );
+        let request = |uri: &str| PipelineRequest {
+            method: Method::GET,
+            uri: uri.to_owned(),
+            headers: Default::default(),
+            body: None,
+            pacer: None,
+        };
+
+        assert_eq!(
+            stage
+                .to_secondary(&request("http://127.0.0.1:10000/devstoreaccount1/c?restype=container"))
+                .unwrap()
+                .uri,
+            "http://127.0.0.1:10000/devstoreaccount1-secondary/c?restype=container"
+        );
+        assert!(stage.to_secondary(&request("http://127.0.0.1:10000/devstoreaccount10/c")).is_none());
+    }
+
+    #[test]
+    fn test_secondary_signature() {
+        use crate::azure::storage::rest_client::{generate_authorization, ServiceType};
+
+        // the secondary is signed as the primary: for the account, not
+        // for `account-secondary`
+        let headers = Default::default();
+        let sign = |uri: &str| {
+            let url = url::Url::parse(uri).unwrap();
+            generate_authorization(&headers, &url, &Method::GET, "account", "YWNjb3VudGtleQ==", ServiceType::Blob)
+        };
+        assert_eq!(
+            sign("https://account-secondary.blob.core.windows.net/c/b"),
+            sign("https://account.blob.core.windows.net/c/b")
+        );
+    }
+}
@@ -1,8 +1,46 @@
 pub mod blob;
 pub mod client;
 pub mod container;
+pub mod file;
+pub mod queue;
+mod multipart;
 mod rest_client;
+pub use self::rest_client::{canonicalized_resource, canonicalized_resource_table, AZURE_VERSION};
+pub mod service;
 pub mod table;
 
+#[cfg(feature = "test_azurite")]
+pub mod azurite;
+
+mod account_registry;
+pub use self::account_registry::{AccountRegistry, CredentialsProvider};
+
+mod connection_string;
+pub use self::connection_string::ConnectionString;
+
+mod error_code;
+pub use self::error_code::StorageErrorCode;
+
 mod into_azure_path;
 pub use self::into_azure_path::IntoAzurePath;
+
+mod location_mode;
+pub use self::location_mode::LocationMode;
+
+mod operation;
+pub use self::operation::{Operation, OperationFuture};
+
+mod ping;
+pub use self::ping::{CredentialsDiagnosis, PingResponse};
+
+mod properties_cache;
+pub use self::properties_cache::PropertiesCache;
+
+mod static_website;
+pub use self::static_website::{content_type_for, StaticWebsiteUpload, WEB_CONTAINER};
+
+#[cfg(test)]
+mod uri_properties;
+
+pub(crate) mod usage_report;
+pub use self::usage_report::{ContainerUsage, UsageReport, UsageTotals};
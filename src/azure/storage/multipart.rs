@@ -0,0 +1,63 @@
+//! Helpers parsing the `multipart/mixed` responses of the batch requests
+//! (table entity group transactions and blob batches).
+use crate::azure::core::errors::AzureError;
+use hyper::StatusCode;
+
+/// The boundary of a multipart body, taken from its first delimiter.
+pub(crate) fn boundary(body: &str) -> Result<&str, AzureError> {
+    body.lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .filter(|line| line.starts_with("--"))
+        .map(|line| &line[2..])
+        .ok_or_else(|| AzureError::GenericErrorWithText("batch response without boundary".to_owned()))
+}
+
+pub(crate) fn split_parts<'b>(body: &'b str, boundary: &str) -> Vec<&'b str> {
+    let delimiter = format!("--{}", boundary);
+    body.split(&delimiter as &str)
+        .skip(1)
+        .take_while(|part| !part.starts_with("--"))
+        .map(|part| part.trim_start_matches(&['\r', '\n'][..]))
+        .collect()
+}
+
+// splits headers from content at the first empty line
+pub(crate) fn split_headers(part: &str) -> (Vec<(&str, &str)>, &str) {
+    let (head, content) = match (part.find("\r\n\r\n"), part.find("\n\n")) {
+        (Some(crlf), Some(lf)) if lf < crlf => (&part[..lf], &part[lf + 2..]),
+        (Some(crlf), _) => (&part[..crlf], &part[crlf + 4..]),
+        (None, Some(lf)) => (&part[..lf], &part[lf + 2..]),
+        (None, None) => (part, ""),
+    };
+
+    let headers = head
+        .lines()
+        .filter_map(|line| {
+            let mut tokens = line.splitn(2, ':');
+            Some((tokens.next()?.trim(), tokens.next()?.trim()))
+        })
+        .collect();
+
+    (headers, content)
+}
+
+pub(crate) fn find_header<'b>(headers: &[(&'b str, &'b str)], name: &str) -> Option<&'b str> {
+    headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| *v)
+}
+
+/// Splits an embedded HTTP response in its status code and the rest
+/// (headers and body).
+pub(crate) fn split_status_line(http: &str) -> Result<(StatusCode, &str), AzureError> {
+    let mut lines = http.splitn(2, '\n');
+    let status_line = lines.next().unwrap_or_default().trim();
+    let status_code = status_line
+        .split_whitespace()
+        .nth(1)
+        .ok_or_else(|| AzureError::GenericErrorWithText(format!("invalid batch status line {}", status_line)))?
+        .parse::<u16>()?;
+    let status_code = StatusCode::from_u16(status_code)
+        .map_err(|_| AzureError::GenericErrorWithText(format!("invalid batch status line {}", status_line)))?;
+
+    Ok((status_code, lines.next().unwrap_or_default()))
+}
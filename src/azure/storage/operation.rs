@@ -0,0 +1,315 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::{
+    AccessTierOption, AccessTierSupport, BlobNameRequired, BlobNameSupport, ContainerNameRequired, ContainerNameSupport,
+    CopySourceRequired, CopySourceSupport, DeleteSnapshotsMethod, DeleteSnapshotsMethodRequired, DeleteSnapshotsMethodSupport,
+    MetadataOption, MetadataSupport, VersionIdOption, VersionIdSupport, Yes,
+};
+use crate::azure::storage::blob::requests::{CopyBlobBuilder, DeleteBlobBuilder};
+use crate::azure::storage::client::{Blob, Client, Container, Queue};
+use crate::azure::storage::container::requests::DeleteBuilder;
+use crate::azure::storage::queue::requests::{DeleteMessageBuilder, DeleteQueueBuilder, PutMessageBuilder};
+use crate::azure::storage::queue::{
+    MessageIdRequired, MessageIdSupport, MessageTextRequired, MessageTextSupport, MessageTtlOption, MessageTtlSupport, PopReceiptRequired,
+    PopReceiptSupport, QueueNameRequired, QueueNameSupport, VisibilityTimeoutOption, VisibilityTimeoutSupport,
+};
+use futures::Future;
+
+pub type OperationFuture = Box<dyn Future<Item = (), Error = AzureError> + Send>;
+
+/// A storage operation described by its kind and its parameters, without
+/// the client, to be stored (for example as JSON in a queue message) and
+/// executed later, by another process, with `execute`. Built from the
+/// matching builder once its required parameters are set:
+///
+/// ```no_run
+/// # use azure_sdk_for_rust::prelude::*;
+/// # use azure_sdk_for_rust::core::DeleteSnapshotsMethod;
+/// # use azure_sdk_for_rust::storage::client::{Blob, Client};
+/// # use azure_sdk_for_rust::storage::Operation;
+/// # let client = Client::new("account", "a2V5").unwrap();
+/// let builder = client
+///     .delete_blob()
+///     .with_container_name("logs")
+///     .with_blob_name("2019/10/15.log")
+///     .with_delete_snapshots_method(DeleteSnapshotsMethod::Include);
+/// let work_item = serde_json::to_string(&Operation::from(&builder)).unwrap();
+///
+/// // in the worker
+/// let operation: Operation = serde_json::from_str(&work_item).unwrap();
+/// let future = operation.execute(&client);
+/// ```
+///
+/// Only the parameters listed are kept: the timeouts, the client request
+/// ids, the conditions, the leases and the extra headers belong to the
+/// attempt and are not.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum Operation {
+    DeleteContainer {
+        container_name: String,
+    },
+    DeleteBlob {
+        container_name: String,
+        blob_name: String,
+        #[serde(with = "delete_snapshots_method")]
+        delete_snapshots_method: DeleteSnapshotsMethod,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version_id: Option<String>,
+    },
+    CopyBlob {
+        container_name: String,
+        blob_name: String,
+        copy_source: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        metadata: Option<Metadata>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        access_tier: Option<String>,
+    },
+    DeleteQueue {
+        queue_name: String,
+    },
+    PutMessage {
+        queue_name: String,
+        message_text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility_timeout: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message_ttl: Option<i64>,
+    },
+    DeleteMessage {
+        queue_name: String,
+        message_id: String,
+        pop_receipt: String,
+    },
+}
+
+impl Operation {
+    /// Builds the request with `client` and sends it. The response is
+    /// dropped: a worker only needs to know whether the operation
+    /// succeeded.
+    pub fn execute(&self, client: &Client) -> OperationFuture {
+        match self {
+            Operation::DeleteContainer { container_name } => {
+                Box::new(client.delete_container().with_container_name(container_name).finalize())
+            }
+            Operation::DeleteBlob {
+                container_name,
+                blob_name,
+                delete_snapshots_method,
+                version_id,
+            } => {
+                let builder = client
+                    .delete_blob()
+                    .with_container_name(container_name)
+                    .with_blob_name(blob_name)
+                    .with_delete_snapshots_method(*delete_snapshots_method);
+                match version_id {
+                    Some(version_id) => Box::new(builder.with_version_id(version_id).finalize().map(|_| ())),
+                    None => Box::new(builder.finalize().map(|_| ())),
+                }
+            }
+            Operation::CopyBlob {
+                container_name,
+                blob_name,
+                copy_source,
+                metadata,
+                access_tier,
+            } => {
+                let builder = client
+                    .copy_blob()
+                    .with_container_name(container_name)
+                    .with_blob_name(blob_name)
+                    .with_copy_source(copy_source);
+                let builder = match metadata {
+                    Some(metadata) => builder.with_metadata(metadata),
+                    None => builder,
+                };
+                let builder = match access_tier {
+                    Some(access_tier) => builder.with_access_tier(access_tier),
+                    None => builder,
+                };
+                Box::new(builder.finalize().map(|_| ()))
+            }
+            Operation::DeleteQueue { queue_name } => Box::new(client.delete_queue().with_queue_name(queue_name).finalize().map(|_| ())),
+            Operation::PutMessage {
+                queue_name,
+                message_text,
+                visibility_timeout,
+                message_ttl,
+            } => {
+                let builder = client.put_message().with_queue_name(queue_name).with_message_text(message_text);
+                let builder = match visibility_timeout {
+                    Some(visibility_timeout) => builder.with_visibility_timeout(*visibility_timeout),
+                    None => builder,
+                };
+                let builder = match message_ttl {
+                    Some(message_ttl) => builder.with_message_ttl(*message_ttl),
+                    None => builder,
+                };
+                Box::new(builder.finalize().map(|_| ()))
+            }
+            Operation::DeleteMessage {
+                queue_name,
+                message_id,
+                pop_receipt,
+            } => Box::new(
+                client
+                    .delete_message()
+                    .with_queue_name(queue_name)
+                    .with_message_id(message_id)
+                    .with_pop_receipt(pop_receipt)
+                    .finalize()
+                    .map(|_| ()),
+            ),
+        }
+    }
+}
+
+impl<'a> From<&DeleteBuilder<'a, Yes>> for Operation {
+    fn from(builder: &DeleteBuilder<'a, Yes>) -> Operation {
+        Operation::DeleteContainer {
+            container_name: builder.container_name().to_owned(),
+        }
+    }
+}
+
+impl<'a> From<&DeleteBlobBuilder<'a, Yes, Yes, Yes>> for Operation {
+    fn from(builder: &DeleteBlobBuilder<'a, Yes, Yes, Yes>) -> Operation {
+        Operation::DeleteBlob {
+            container_name: builder.container_name().to_owned(),
+            blob_name: builder.blob_name().to_owned(),
+            delete_snapshots_method: builder.delete_snapshots_method(),
+            version_id: builder.version_id().map(str::to_owned),
+        }
+    }
+}
+
+impl<'a> From<&CopyBlobBuilder<'a, Yes, Yes, Yes>> for Operation {
+    fn from(builder: &CopyBlobBuilder<'a, Yes, Yes, Yes>) -> Operation {
+        Operation::CopyBlob {
+            container_name: builder.container_name().to_owned(),
+            blob_name: builder.blob_name().to_owned(),
+            copy_source: builder.copy_source().to_owned(),
+            metadata: builder.metadata().cloned(),
+            access_tier: builder.access_tier().map(str::to_owned),
+        }
+    }
+}
+
+impl<'a> From<&DeleteQueueBuilder<'a, Yes>> for Operation {
+    fn from(builder: &DeleteQueueBuilder<'a, Yes>) -> Operation {
+        Operation::DeleteQueue {
+            queue_name: builder.queue_name().to_owned(),
+        }
+    }
+}
+
+impl<'a> From<&PutMessageBuilder<'a, Yes, Yes>> for Operation {
+    fn from(builder: &PutMessageBuilder<'a, Yes, Yes>) -> Operation {
+        Operation::PutMessage {
+            queue_name: builder.queue_name().to_owned(),
+            message_text: builder.message_text().to_owned(),
+            visibility_timeout: builder.visibility_timeout(),
+            message_ttl: builder.message_ttl(),
+        }
+    }
+}
+
+impl<'a> From<&DeleteMessageBuilder<'a, Yes, Yes, Yes>> for Operation {
+    fn from(builder: &DeleteMessageBuilder<'a, Yes, Yes, Yes>) -> Operation {
+        Operation::DeleteMessage {
+            queue_name: builder.queue_name().to_owned(),
+            message_id: builder.message_id().to_owned(),
+            pop_receipt: builder.pop_receipt().to_owned(),
+        }
+    }
+}
+
+// as the service spells it: "include" or "only"
+mod delete_snapshots_method {
+    use crate::azure::core::DeleteSnapshotsMethod;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(method: &DeleteSnapshotsMethod, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(method.as_ref())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DeleteSnapshotsMethod, D::Error> {
+        let method = String::deserialize(deserializer)?;
+        method
+            .parse()
+            .map_err(|_| D::Error::custom(format!("unknown delete snapshots method {}", method)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use hyper::{Method, StatusCode};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_round_trip() {
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap();
+        let builder = client
+            .delete_blob()
+            .with_container_name("logs")
+            .with_blob_name("2019/10/15.log")
+            .with_delete_snapshots_method(DeleteSnapshotsMethod::Only);
+        let json = serde_json::to_string(&Operation::from(&builder)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"operation":"delete_blob","container_name":"logs","blob_name":"2019/10/15.log","delete_snapshots_method":"only"}"#
+        );
+        assert_eq!(serde_json::from_str::<Operation>(&json).unwrap(), Operation::from(&builder));
+
+        let mut metadata = Metadata::new();
+        metadata.insert("origin", "archive").unwrap();
+        let builder = client
+            .copy_blob()
+            .with_container_name("c")
+            .with_blob_name("b")
+            .with_copy_source("https://other.blob.core.windows.net/c/b")
+            .with_metadata(&metadata);
+        let operation: Operation = serde_json::from_str(&serde_json::to_string(&Operation::from(&builder)).unwrap()).unwrap();
+        match &operation {
+            Operation::CopyBlob { metadata, access_tier, .. } => {
+                assert_eq!(metadata.as_ref().unwrap()["origin"], "archive");
+                assert_eq!(*access_tier, None);
+            }
+            other => panic!("unexpected operation {:?}", other),
+        }
+
+        assert!(serde_json::from_str::<Operation>(r#"{"operation":"format_disk"}"#).is_err());
+    }
+
+    #[test]
+    fn test_execute() {
+        let transport = Arc::new(ReplayTransport::new().with_response(
+            StatusCode::NO_CONTENT,
+            &[
+                ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+                ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ],
+            b"",
+        ));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+
+        let operation: Operation =
+            serde_json::from_str(r#"{"operation":"delete_message","queue_name":"jobs","message_id":"42","pop_receipt":"AgAAAA=="}"#)
+                .unwrap();
+        operation.execute(&client).wait().unwrap();
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::DELETE);
+        assert_eq!(
+            request.uri,
+            "https://account.queue.core.windows.net/jobs/messages/42?popreceipt=AgAAAA%3D%3D"
+        );
+    }
+}
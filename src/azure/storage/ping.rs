@@ -0,0 +1,208 @@
+use super::rest_client::HEADER_VERSION;
+use crate::azure::core::clock_skew::MAX_CLOCK_SKEW_MINUTES;
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::headers::{ACCOUNT_KIND, SKU_NAME};
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::service::ACCOUNT_INFORMATION_VERSION;
+use crate::azure::storage::StorageErrorCode;
+use chrono::{DateTime, Utc};
+use futures::future::{done, join_all};
+use futures::Future;
+use hyper::{HeaderMap, Method, StatusCode};
+use std::time::{Duration, Instant};
+
+// how the name resolution failures read on Linux, macOS and Windows
+const NAME_RESOLUTION_ERRORS: &[&str] = &["failed to lookup address", "nodename nor servname", "No such host is known"];
+
+/// The outcome of a successful `Client::ping`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub sku_name: Option<String>,
+    pub account_kind: Option<String>,
+    /// Time between the request and the response headers, retries
+    /// included.
+    pub elapsed: Duration,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl PingResponse {
+    fn from_headers(headers: &HeaderMap, elapsed: Duration) -> Result<PingResponse, AzureError> {
+        let optional = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_owned())
+        };
+
+        Ok(PingResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            date: date_from_headers(headers)?,
+            sku_name: optional(SKU_NAME),
+            account_kind: optional(ACCOUNT_KIND),
+            elapsed,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
+
+/// What `Client::validate_credentials` found out about the client
+/// configuration.
+#[derive(Debug)]
+pub enum CredentialsDiagnosis {
+    /// The credentials were accepted.
+    Valid(PingResponse),
+    /// The account key, or the SAS token signature, is wrong.
+    InvalidKey,
+    /// The credentials were rejected because the local clock is too far
+    /// from the service one. The duration is the service time minus the
+    /// local time.
+    ClockSkew(chrono::Duration),
+    /// The SAS token is valid but does not grant the permission to get the
+    /// account information.
+    InsufficientPermissions,
+    /// The storage account is disabled.
+    AccountDisabled,
+    /// The account endpoint does not resolve: the account name is probably
+    /// misspelled.
+    AccountNotFound,
+    /// The service refused the request because of the account firewall or
+    /// virtual network rules.
+    NetworkAccessDenied,
+    /// The endpoint could not be reached (connection refused or timed out),
+    /// for example because it resolves to a private endpoint outside of the
+    /// network of the client. The string is the transport error.
+    Unreachable(String),
+    /// The request failed for any other reason.
+    Other(AzureError),
+}
+
+impl CredentialsDiagnosis {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, CredentialsDiagnosis::Valid(_))
+    }
+
+    fn from_error(error: AzureError, now: DateTime<Utc>) -> CredentialsDiagnosis {
+        match error {
+            AzureError::UnexpectedHTTPResult(ref result) if result.status_code() == StatusCode::FORBIDDEN => match result.error_code() {
+                Some(StorageErrorCode::AuthenticationFailed) => match result.date().map(|date| date - now) {
+                    Some(skew) if skew.num_minutes().abs() >= MAX_CLOCK_SKEW_MINUTES => CredentialsDiagnosis::ClockSkew(skew),
+                    _ => CredentialsDiagnosis::InvalidKey,
+                },
+                Some(StorageErrorCode::AuthorizationFailure) => CredentialsDiagnosis::NetworkAccessDenied,
+                Some(StorageErrorCode::AuthorizationPermissionMismatch) => CredentialsDiagnosis::InsufficientPermissions,
+                Some(StorageErrorCode::AccountIsDisabled) => CredentialsDiagnosis::AccountDisabled,
+                _ => CredentialsDiagnosis::Other(error),
+            },
+            AzureError::HyperError(ref hyper_error) if hyper_error.is_connect() => {
+                let message = hyper_error.to_string();
+                if NAME_RESOLUTION_ERRORS.iter().any(|pattern| message.contains(pattern)) {
+                    CredentialsDiagnosis::AccountNotFound
+                } else {
+                    CredentialsDiagnosis::Unreachable(message)
+                }
+            }
+            error => CredentialsDiagnosis::Other(error),
+        }
+    }
+}
+
+pub(crate) fn ping(client: &Client) -> impl Future<Item = PingResponse, Error = AzureError> {
+    let uri = format!("{}/?restype=account&comp=properties", client.blob_uri());
+    trace!("uri == {:?}", uri);
+
+    let start = Instant::now();
+    let req = client.perform_request(
+        &uri,
+        &Method::GET,
+        |ref mut request| {
+            request.header(HEADER_VERSION, ACCOUNT_INFORMATION_VERSION);
+        },
+        None,
+    );
+
+    done(req)
+        .from_err()
+        .and_then(|response| check_status_extract_headers_and_body(response, StatusCode::OK))
+        .and_then(move |(headers, _body)| done(PingResponse::from_headers(&headers, start.elapsed())))
+}
+
+pub(crate) fn warm_up(client: &Client, connections: usize) -> impl Future<Item = Vec<PingResponse>, Error = AzureError> {
+    join_all((0..connections).map(|_| ping(client)).collect::<Vec<_>>())
+}
+
+pub(crate) fn validate_credentials(client: &Client) -> impl Future<Item = CredentialsDiagnosis, Error = AzureError> {
+    ping(client).then(|result| {
+        Ok(match result {
+            Ok(response) => CredentialsDiagnosis::Valid(response),
+            Err(error) => CredentialsDiagnosis::from_error(error, Utc::now()),
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::errors::UnexpectedHTTPResult;
+    use crate::azure::core::headers::ERROR_CODE;
+    use hyper::header::HeaderValue;
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ms-request-id", HeaderValue::from_static("0f1d5e3b-501e-0048-0b6e-4a5e3f000000"));
+        headers.insert("date", HeaderValue::from_static("Tue, 15 Oct 2019 10:00:00 GMT"));
+        headers.insert(SKU_NAME, HeaderValue::from_static("Standard_RAGRS"));
+        headers.insert(ACCOUNT_KIND, HeaderValue::from_static("StorageV2"));
+
+        let response = PingResponse::from_headers(&headers, Duration::from_millis(12)).unwrap();
+        assert_eq!(response.sku_name.as_deref(), Some("Standard_RAGRS"));
+        assert_eq!(response.account_kind.as_deref(), Some("StorageV2"));
+        assert_eq!(response.elapsed, Duration::from_millis(12));
+
+        headers.remove(SKU_NAME);
+        assert_eq!(
+            PingResponse::from_headers(&headers, Duration::from_millis(12)).unwrap().sku_name,
+            None
+        );
+    }
+
+    fn forbidden(error_code: &str, date: &'static str) -> AzureError {
+        let mut headers = HeaderMap::new();
+        headers.insert(ERROR_CODE, HeaderValue::from_str(error_code).unwrap());
+        headers.insert("date", HeaderValue::from_static(date));
+        AzureError::UnexpectedHTTPResult(UnexpectedHTTPResult::new(StatusCode::OK, StatusCode::FORBIDDEN, "").with_headers(&headers))
+    }
+
+    #[test]
+    fn test_diagnosis() {
+        let now = DateTime::parse_from_rfc3339("2019-10-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let diagnose = |error| CredentialsDiagnosis::from_error(error, now);
+
+        match diagnose(forbidden("AuthenticationFailed", "Tue, 15 Oct 2019 10:01:00 GMT")) {
+            CredentialsDiagnosis::InvalidKey => {}
+            diagnosis => panic!("unexpected {:?}", diagnosis),
+        }
+        match diagnose(forbidden("AuthenticationFailed", "Tue, 15 Oct 2019 09:30:00 GMT")) {
+            CredentialsDiagnosis::ClockSkew(skew) => assert_eq!(skew, chrono::Duration::minutes(-30)),
+            diagnosis => panic!("unexpected {:?}", diagnosis),
+        }
+        match diagnose(forbidden("AuthorizationFailure", "Tue, 15 Oct 2019 10:00:00 GMT")) {
+            CredentialsDiagnosis::NetworkAccessDenied => {}
+            diagnosis => panic!("unexpected {:?}", diagnosis),
+        }
+        match diagnose(forbidden("AuthorizationPermissionMismatch", "Tue, 15 Oct 2019 10:00:00 GMT")) {
+            CredentialsDiagnosis::InsufficientPermissions => {}
+            diagnosis => panic!("unexpected {:?}", diagnosis),
+        }
+        match diagnose(AzureError::GenericError) {
+            CredentialsDiagnosis::Other(AzureError::GenericError) => {}
+            diagnosis => panic!("unexpected {:?}", diagnosis),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use crate::azure::storage::blob::Blob;
+use crate::azure::storage::container::Container;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Time based cache of container and blob properties. Once enabled on
+/// the `Client` (see `Client::with_properties_cache`) the
+/// `get_container_properties_cached` and `get_blob_properties_cached`
+/// calls answer from here until the entry is older than the TTL, sparing
+/// a HEAD request.
+///
+/// The cache does not know about changes made by other clients (or by
+/// this one, for that matter): call the `invalidate_*` methods after
+/// modifying a blob or container if you need to read the fresh values.
+#[derive(Debug)]
+pub struct PropertiesCache {
+    ttl: Duration,
+    containers: Mutex<TtlMap<String, Container>>,
+    blobs: Mutex<TtlMap<(String, String), Blob>>,
+}
+
+impl PropertiesCache {
+    pub fn new(ttl: Duration) -> PropertiesCache {
+        PropertiesCache {
+            ttl,
+            containers: Mutex::new(TtlMap::new(ttl)),
+            blobs: Mutex::new(TtlMap::new(ttl)),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn container(&self, container_name: &str) -> Option<Container> {
+        self.containers.lock().unwrap().get(&container_name.to_owned())
+    }
+
+    pub fn blob(&self, container_name: &str, blob_name: &str) -> Option<Blob> {
+        self.blobs.lock().unwrap().get(&(container_name.to_owned(), blob_name.to_owned()))
+    }
+
+    pub(crate) fn insert_container(&self, container: Container) {
+        self.containers.lock().unwrap().insert(container.name.clone(), container);
+    }
+
+    pub(crate) fn insert_blob(&self, blob: Blob) {
+        self.blobs
+            .lock()
+            .unwrap()
+            .insert((blob.container_name.clone(), blob.name.clone()), blob);
+    }
+
+    /// Forgets the container properties along with the properties of
+    /// every blob it contains.
+    pub fn invalidate_container(&self, container_name: &str) {
+        self.containers.lock().unwrap().remove(&container_name.to_owned());
+        self.blobs.lock().unwrap().retain(|(container, _)| container != container_name);
+    }
+
+    pub fn invalidate_blob(&self, container_name: &str, blob_name: &str) {
+        self.blobs
+            .lock()
+            .unwrap()
+            .remove(&(container_name.to_owned(), blob_name.to_owned()));
+    }
+
+    pub fn clear(&self) {
+        self.containers.lock().unwrap().retain(|_| false);
+        self.blobs.lock().unwrap().retain(|_| false);
+    }
+}
+
+#[derive(Debug)]
+struct TtlMap<K, V>
+where
+    K: Eq + Hash,
+{
+    ttl: Duration,
+    entries: HashMap<K, (V, Instant)>,
+}
+
+impl<K, V> TtlMap<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    fn new(ttl: Duration) -> TtlMap<K, V> {
+        TtlMap {
+            ttl,
+            entries: HashMap::new(),
+        }
+    }
+
+    // expired entries are dropped as they are found
+    fn get(&mut self, key: &K) -> Option<V> {
+        let fresh = match self.entries.get(key) {
+            Some((_, inserted)) => inserted.elapsed() < self.ttl,
+            None => return None,
+        };
+
+        if fresh {
+            self.entries.get(key).map(|(value, _)| value.clone())
+        } else {
+            self.entries.remove(key);
+            None
+        }
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        self.entries.insert(key, (value, Instant::now()));
+    }
+
+    fn remove(&mut self, key: &K) {
+        self.entries.remove(key);
+    }
+
+    fn retain<F>(&mut self, f: F)
+    where
+        F: Fn(&K) -> bool,
+    {
+        self.entries.retain(|key, _| f(key));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ttl_map() {
+        let mut map = TtlMap::new(Duration::from_secs(60));
+        map.insert(1, "one");
+        map.insert(2, "two");
+        assert_eq!(map.get(&1), Some("one"));
+
+        map.remove(&1);
+        assert_eq!(map.get(&1), None);
+
+        map.retain(|key| *key != 2);
+        assert_eq!(map.get(&2), None);
+    }
+
+    #[test]
+    fn test_ttl_map_expiration() {
+        let mut map = TtlMap::new(Duration::from_secs(0));
+        map.insert(1, "one");
+        assert_eq!(map.get(&1), None);
+        assert!(map.entries.is_empty());
+    }
+
+    #[test]
+    fn test_invalidate_container() {
+        let cache = PropertiesCache::new(Duration::from_secs(60));
+        cache.insert_container(Container::new("a"));
+        cache.insert_container(Container::new("b"));
+        assert!(cache.container("a").is_some());
+
+        cache.invalidate_container("a");
+        assert!(cache.container("a").is_none());
+        assert!(cache.container("b").is_some());
+
+        cache.clear();
+        assert!(cache.container("b").is_none());
+    }
+}
@@ -0,0 +1,216 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::shutdown::ShutdownHandle;
+use crate::azure::core::sleeper::Sleeper;
+use crate::azure::storage::client::{Client, Queue};
+use crate::azure::storage::queue::QueueNameSupport;
+use chrono::{DateTime, Utc};
+use futures::future::{self, Either};
+use futures::prelude::*;
+use futures::{stream, Async};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Approximate number of messages in a queue at a given (server) time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueDepthSample {
+    pub queue_name: String,
+    pub approximate_messages_count: u64,
+    pub sampled_at: DateTime<Utc>,
+}
+
+/// Periodically samples the approximate message count of a set of
+/// queues, for example to drive autoscaling decisions. Every sample is
+/// reported to the client `TelemetryObserver` (see
+/// `Client::with_telemetry_observer`) and yielded by the `run` stream.
+///
+/// The count is approximate: Azure updates it lazily so it can lag behind
+/// the actual queue content.
+#[derive(Debug, Clone)]
+pub struct QueueDepthPoller {
+    client: Client,
+    queue_names: Vec<String>,
+    interval: Duration,
+}
+
+impl QueueDepthPoller {
+    pub fn new(client: &Client, queue_names: &[&str], interval: Duration) -> QueueDepthPoller {
+        QueueDepthPoller {
+            client: client.clone(),
+            queue_names: queue_names.iter().map(|queue_name| (*queue_name).to_owned()).collect(),
+            interval,
+        }
+    }
+
+    pub fn queue_names(&self) -> &[String] {
+        &self.queue_names
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Samples every queue once, concurrently. A queue that cannot be
+    /// sampled (for example because it has been deleted) is reported to
+    /// the observer with `on_queue_depth_error` and left out of the
+    /// result: the future itself never fails.
+    pub fn sample(&self) -> impl Future<Item = Vec<QueueDepthSample>, Error = AzureError> {
+        let samples: Vec<_> = self
+            .queue_names
+            .iter()
+            .map(|queue_name| {
+                let observer = self.client.telemetry_observer().clone();
+                let queue_name = queue_name.clone();
+
+                self.client
+                    .get_queue_metadata()
+                    .with_queue_name(&queue_name)
+                    .finalize()
+                    .then(move |res| match res {
+                        Ok(response) => {
+                            observer.on_queue_depth(&queue_name, response.approximate_messages_count, response.date);
+                            Ok(Some(QueueDepthSample {
+                                queue_name,
+                                approximate_messages_count: response.approximate_messages_count,
+                                sampled_at: response.date,
+                            }))
+                        }
+                        Err(err) => {
+                            warn!("cannot sample the depth of queue {}: {}", queue_name, err);
+                            observer.on_queue_depth_error(&queue_name, &err);
+                            Ok(None)
+                        }
+                    })
+            })
+            .collect();
+
+        future::join_all(samples).map(|samples| samples.into_iter().flatten().collect())
+    }
+
+    /// Samples the queues immediately and then every `interval`, forever.
+    /// Drop the stream to stop polling. The wait between samples goes
+    /// through the client `Sleeper`.
+    pub fn run(self) -> impl Stream<Item = Vec<QueueDepthSample>, Error = AzureError> {
+        let sleeper = self.client.sleeper().clone();
+        let interval = self.interval;
+        let poller = Arc::new(self);
+
+        poll(sleeper, interval, None, move || poller.sample())
+    }
+
+    /// As `run`, until `shutdown` is requested: the sample being taken is
+    /// completed and yielded, the wait for the next one is cut short and
+    /// the stream ends. `ShutdownHandle::shutdown` resolves once the
+    /// stream has ended.
+    pub fn run_until_shutdown(self, shutdown: &ShutdownHandle) -> impl Stream<Item = Vec<QueueDepthSample>, Error = AzureError> {
+        let sleeper = self.client.sleeper().clone();
+        let interval = self.interval;
+        let poller = Arc::new(self);
+
+        poll(sleeper, interval, Some(shutdown.clone()), move || poller.sample())
+    }
+}
+
+// Calls f right away and then every interval, until the shutdown is
+// requested. f is called after the wait so the requests are signed with a
+// fresh date.
+fn poll<F, R>(
+    sleeper: Arc<dyn Sleeper>,
+    interval: Duration,
+    shutdown: Option<ShutdownHandle>,
+    f: F,
+) -> impl Stream<Item = R::Item, Error = AzureError>
+where
+    F: Fn() -> R,
+    R: Future<Error = AzureError>,
+{
+    let f = Arc::new(f);
+    let stopping = shutdown.clone();
+
+    let items = stream::unfold(true, move |first| {
+        if stopping.as_ref().is_some_and(ShutdownHandle::is_shutdown_requested) {
+            return None;
+        }
+
+        let wait = match (first, &stopping) {
+            (true, _) => Either::A(future::ok(())),
+            (false, None) => Either::B(Either::A(sleeper.sleep(interval))),
+            (false, Some(stopping)) => Either::B(Either::B(
+                sleeper.sleep(interval).select(stopping.requested()).map(|_| ()).map_err(|(err, _)| err),
+            )),
+        };
+
+        let f = f.clone();
+        let stopping = stopping.clone();
+        Some(wait.and_then(move |_| {
+            // the shutdown may have been requested during the wait
+            if stopping.as_ref().is_some_and(ShutdownHandle::is_shutdown_requested) {
+                Either::A(future::ok((None, false)))
+            } else {
+                Either::B(f().map(|item| (Some(item), false)))
+            }
+        }))
+    });
+
+    items
+        .take_while(|item| Ok(item.is_some()))
+        .filter_map(|item| item)
+        .chain(stream::poll_fn(move || {
+            if let Some(shutdown) = &shutdown {
+                shutdown.set_stopped();
+            }
+            Ok(Async::Ready(None))
+        }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::sleeper::VirtualSleeper;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_poll() {
+        let sleeper = Arc::new(VirtualSleeper::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = calls.clone();
+        let samples = poll(sleeper.clone(), Duration::from_secs(30), None, move || {
+            future::ok(counter.fetch_add(1, Ordering::SeqCst))
+        })
+        .take(3)
+        .collect()
+        .wait()
+        .unwrap();
+
+        assert_eq!(samples, vec![0, 1, 2]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        // no wait before the first sample
+        assert_eq!(sleeper.sleeps(), vec![Duration::from_secs(30), Duration::from_secs(30)]);
+    }
+
+    #[test]
+    fn test_poll_until_shutdown() {
+        let sleeper = Arc::new(VirtualSleeper::new());
+        let shutdown = ShutdownHandle::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = calls.clone();
+        let stopping = shutdown.clone();
+        let samples = poll(sleeper.clone(), Duration::from_secs(30), Some(shutdown.clone()), move || {
+            let call = counter.fetch_add(1, Ordering::SeqCst);
+            if call == 1 {
+                // requested while the second sample is in flight
+                stopping.request_shutdown();
+            }
+            future::ok(call)
+        })
+        .collect()
+        .wait()
+        .unwrap();
+
+        // the sample in flight is completed, no other one is taken
+        assert_eq!(samples, vec![0, 1]);
+        assert!(shutdown.is_stopped());
+        shutdown.shutdown().wait().unwrap();
+    }
+}
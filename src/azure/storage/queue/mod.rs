@@ -0,0 +1,279 @@
+pub mod requests;
+pub mod responses;
+
+mod depth_poller;
+pub use self::depth_poller::{QueueDepthPoller, QueueDepthSample};
+
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::parsing::{cast_must, cast_optional, traverse};
+use crate::azure::core::{ClientRequired, COMPLETE_ENCODE_SET};
+use chrono::{DateTime, Utc};
+use url::percent_encoding::utf8_percent_encode;
+use xml::Element;
+
+pub trait QueueNameSupport<'a> {
+    type O;
+    fn with_queue_name(self, queue_name: &'a str) -> Self::O;
+}
+
+pub trait QueueNameRequired<'a> {
+    fn queue_name(&self) -> &'a str;
+}
+
+pub trait MessageIdSupport<'a> {
+    type O;
+    fn with_message_id(self, message_id: &'a str) -> Self::O;
+}
+
+pub trait MessageIdRequired<'a> {
+    fn message_id(&self) -> &'a str;
+}
+
+pub trait PopReceiptSupport<'a> {
+    type O;
+    fn with_pop_receipt(self, pop_receipt: &'a str) -> Self::O;
+}
+
+pub trait PopReceiptRequired<'a> {
+    fn pop_receipt(&self) -> &'a str;
+
+    fn to_uri_parameter(&self) -> String {
+        format!("popreceipt={}", utf8_percent_encode(self.pop_receipt(), COMPLETE_ENCODE_SET))
+    }
+}
+
+pub trait MessageTextSupport<'a> {
+    type O;
+    fn with_message_text(self, message_text: &'a str) -> Self::O;
+}
+
+pub trait MessageTextRequired<'a> {
+    fn message_text(&self) -> &'a str;
+
+    fn to_body(&self) -> String {
+        message_body(self.message_text())
+    }
+}
+
+pub trait MessageTextOption<'a> {
+    fn message_text(&self) -> Option<&'a str>;
+
+    fn to_body(&self) -> Option<String> {
+        self.message_text().map(message_body)
+    }
+}
+
+/// Visibility timeout, in seconds.
+pub trait VisibilityTimeoutSupport {
+    type O;
+    fn with_visibility_timeout(self, visibility_timeout: u64) -> Self::O;
+}
+
+pub trait VisibilityTimeoutOption {
+    fn visibility_timeout(&self) -> Option<u64>;
+
+    fn to_uri_parameter(&self) -> Option<String> {
+        self.visibility_timeout().map(|vt| format!("visibilitytimeout={}", vt))
+    }
+}
+
+pub trait VisibilityTimeoutRequired {
+    fn visibility_timeout(&self) -> u64;
+
+    fn to_uri_parameter(&self) -> String {
+        format!("visibilitytimeout={}", self.visibility_timeout())
+    }
+}
+
+/// Message time to live, in seconds. -1 means the message never expires.
+pub trait MessageTtlSupport {
+    type O;
+    fn with_message_ttl(self, message_ttl: i64) -> Self::O;
+}
+
+pub trait MessageTtlOption {
+    fn message_ttl(&self) -> Option<i64>;
+
+    fn to_uri_parameter(&self) -> Option<String> {
+        self.message_ttl().map(|ttl| format!("messagettl={}", ttl))
+    }
+}
+
+/// Number of messages to retrieve, between 1 and 32. Azure defaults
+/// to a single message.
+pub trait NumberOfMessagesSupport {
+    type O;
+    fn with_number_of_messages(self, number_of_messages: u32) -> Self::O;
+}
+
+pub trait NumberOfMessagesOption {
+    fn number_of_messages(&self) -> Option<u32>;
+
+    fn to_uri_parameter(&self) -> Option<String> {
+        self.number_of_messages().map(|n| format!("numofmessages={}", n))
+    }
+}
+
+/// A message as returned by GetMessages and PeekMessages. Peeked messages
+/// are not dequeued so they have neither a pop receipt nor a next
+/// visibility time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueMessage {
+    pub message_id: String,
+    pub insertion_time: DateTime<Utc>,
+    pub expiration_time: DateTime<Utc>,
+    pub pop_receipt: Option<String>,
+    pub time_next_visible: Option<DateTime<Utc>>,
+    pub dequeue_count: u64,
+    pub message_text: String,
+}
+
+impl QueueMessage {
+    fn parse(elem: &Element) -> Result<QueueMessage, AzureError> {
+        let message_id = cast_must::<String>(elem, &["MessageId"])?;
+        let insertion_time = cast_must::<DateTime<Utc>>(elem, &["InsertionTime"])?;
+        let expiration_time = cast_must::<DateTime<Utc>>(elem, &["ExpirationTime"])?;
+        let pop_receipt = cast_optional::<String>(elem, &["PopReceipt"])?;
+        let time_next_visible = cast_optional::<DateTime<Utc>>(elem, &["TimeNextVisible"])?;
+        let dequeue_count = cast_must::<u64>(elem, &["DequeueCount"])?;
+        let message_text = cast_must::<String>(elem, &["MessageText"])?;
+
+        Ok(QueueMessage {
+            message_id,
+            insertion_time,
+            expiration_time,
+            pop_receipt,
+            time_next_visible,
+            dequeue_count,
+            message_text,
+        })
+    }
+}
+
+pub(crate) fn messages_from_response(body: &str) -> Result<Vec<QueueMessage>, AzureError> {
+    let elem: Element = body.parse()?;
+
+    let mut v = Vec::new();
+    for message in traverse(&elem, &["QueueMessage"], true)? {
+        v.push(QueueMessage::parse(message)?);
+    }
+
+    Ok(v)
+}
+
+// The text is sent as is (XML escaped). If you need to store binary
+// data encode it (for example in base64) before.
+fn message_body(message_text: &str) -> String {
+    format!(
+        "<QueueMessage><MessageText>{}</MessageText></QueueMessage>",
+        xml::escape(message_text)
+    )
+}
+
+pub(crate) fn generate_queue_uri<'a, T>(t: &T, params: Option<&str>) -> String
+where
+    T: ClientRequired<'a> + QueueNameRequired<'a>,
+{
+    match params {
+        Some(ref params) => format!(
+            "{}/{}?{}",
+            t.client().queue_uri(),
+            utf8_percent_encode(t.queue_name(), COMPLETE_ENCODE_SET),
+            params
+        ),
+        None => format!(
+            "{}/{}",
+            t.client().queue_uri(),
+            utf8_percent_encode(t.queue_name(), COMPLETE_ENCODE_SET)
+        ),
+    }
+}
+
+pub(crate) fn generate_messages_uri<'a, T>(t: &T, params: Option<&str>) -> String
+where
+    T: ClientRequired<'a> + QueueNameRequired<'a>,
+{
+    match params {
+        Some(ref params) => format!(
+            "{}/{}/messages?{}",
+            t.client().queue_uri(),
+            utf8_percent_encode(t.queue_name(), COMPLETE_ENCODE_SET),
+            params
+        ),
+        None => format!(
+            "{}/{}/messages",
+            t.client().queue_uri(),
+            utf8_percent_encode(t.queue_name(), COMPLETE_ENCODE_SET)
+        ),
+    }
+}
+
+pub(crate) fn generate_message_uri<'a, T>(t: &T, params: Option<&str>) -> String
+where
+    T: ClientRequired<'a> + QueueNameRequired<'a> + MessageIdRequired<'a>,
+{
+    match params {
+        Some(ref params) => format!(
+            "{}/{}/messages/{}?{}",
+            t.client().queue_uri(),
+            utf8_percent_encode(t.queue_name(), COMPLETE_ENCODE_SET),
+            utf8_percent_encode(t.message_id(), COMPLETE_ENCODE_SET),
+            params
+        ),
+        None => format!(
+            "{}/{}/messages/{}",
+            t.client().queue_uri(),
+            utf8_percent_encode(t.queue_name(), COMPLETE_ENCODE_SET),
+            utf8_percent_encode(t.message_id(), COMPLETE_ENCODE_SET)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn test_messages_from_response() {
+        let body = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+            <QueueMessagesList>
+              <QueueMessage>
+                <MessageId>5974b586-0df3-4e2d-ad0c-18e3892bfca2</MessageId>
+                <InsertionTime>Fri, 09 Oct 2009 21:04:30 GMT</InsertionTime>
+                <ExpirationTime>Fri, 16 Oct 2009 21:04:30 GMT</ExpirationTime>
+                <PopReceipt>YzQ4Yzg1MDItYTc0Ny00OWNjLTkxYTUtZGM0MDFiZDAwYzEw</PopReceipt>
+                <TimeNextVisible>Fri, 09 Oct 2009 23:29:20 GMT</TimeNextVisible>
+                <DequeueCount>1</DequeueCount>
+                <MessageText>a &lt;b&gt; &amp; c</MessageText>
+              </QueueMessage>
+              <QueueMessage>
+                <MessageId>8c0e4e49-86b5-4e55-9a25-1d4ab0e8f4a1</MessageId>
+                <InsertionTime>Fri, 09 Oct 2009 21:05:30 GMT</InsertionTime>
+                <ExpirationTime>Fri, 16 Oct 2009 21:05:30 GMT</ExpirationTime>
+                <DequeueCount>0</DequeueCount>
+                <MessageText>peeked</MessageText>
+              </QueueMessage>
+            </QueueMessagesList>";
+
+        let messages = messages_from_response(body).unwrap();
+        assert_eq!(messages.len(), 2);
+
+        assert_eq!(messages[0].message_id, "5974b586-0df3-4e2d-ad0c-18e3892bfca2");
+        assert_eq!(messages[0].message_text, "a <b> & c");
+        assert_eq!(messages[0].dequeue_count, 1);
+        assert_eq!(messages[0].time_next_visible.unwrap().hour(), 23);
+        assert!(messages[0].pop_receipt.is_some());
+
+        assert_eq!(messages[1].pop_receipt, None);
+        assert_eq!(messages[1].time_next_visible, None);
+    }
+
+    #[test]
+    fn test_message_body() {
+        assert_eq!(
+            message_body("1 < 2"),
+            "<QueueMessage><MessageText>1 &lt; 2</MessageText></QueueMessage>"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, MetadataOption, MetadataSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::queue::responses::CreateQueueResponse;
+use crate::azure::storage::queue::{generate_queue_uri, QueueNameRequired, QueueNameSupport};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_queue_name: PhantomData<QueueNameSet>,
+    queue_name: Option<&'a str>,
+    metadata: Option<&'a Metadata>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> CreateQueueBuilder<'a, No> {
+    pub(crate) fn new(client: &'a Client) -> CreateQueueBuilder<'a, No> {
+        CreateQueueBuilder {
+            client,
+            p_queue_name: PhantomData {},
+            queue_name: None,
+            metadata: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequired<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> QueueNameRequired<'a> for CreateQueueBuilder<'a, Yes> {
+    fn queue_name(&self) -> &'a str {
+        self.queue_name.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet> MetadataOption<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn metadata(&self) -> Option<&'a Metadata> {
+        self.metadata
+    }
+}
+
+impl<'a, QueueNameSet> TimeoutOption for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequestIdOption<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, QueueNameSet> QueueNameSupport<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = CreateQueueBuilder<'a, Yes>;
+
+    fn with_queue_name(self, queue_name: &'a str) -> Self::O {
+        CreateQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: Some(queue_name),
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> MetadataSupport<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = CreateQueueBuilder<'a, QueueNameSet>;
+
+    fn with_metadata(self, metadata: &'a Metadata) -> Self::O {
+        CreateQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            metadata: Some(metadata),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> TimeoutSupport for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = CreateQueueBuilder<'a, QueueNameSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        CreateQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            metadata: self.metadata,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequestIdSupport<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = CreateQueueBuilder<'a, QueueNameSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        CreateQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ExtraHeadersOption<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, QueueNameSet> ExtraHeadersSupport<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = CreateQueueBuilder<'a, QueueNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, QueueNameSet> ApiVersionOption<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, QueueNameSet> ApiVersionSupport<'a> for CreateQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = CreateQueueBuilder<'a, QueueNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        CreateQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            metadata: self.metadata,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, QueueNameSet> CreateQueueBuilder<'a, QueueNameSet> where QueueNameSet: ToAssign {}
+
+impl<'a> CreateQueueBuilder<'a, Yes> {
+    pub fn finalize(self) -> impl Future<Item = CreateQueueResponse, Error = AzureError> {
+        let mut uri = generate_queue_uri(&self, None);
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}?{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::PUT,
+            |ref mut request| {
+                MetadataOption::add_header(&self, request);
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(&[]),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::CREATED))
+            .and_then(move |(headers, _body)| done(CreateQueueResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<CreateQueueResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,353 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::queue::responses::DeleteMessageResponse;
+use crate::azure::storage::queue::{
+    generate_message_uri, MessageIdRequired, MessageIdSupport, PopReceiptRequired, PopReceiptSupport, QueueNameRequired, QueueNameSupport,
+};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    client: &'a Client,
+    p_queue_name: PhantomData<QueueNameSet>,
+    p_message_id: PhantomData<MessageIdSet>,
+    p_pop_receipt: PhantomData<PopReceiptSet>,
+    queue_name: Option<&'a str>,
+    message_id: Option<&'a str>,
+    pop_receipt: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> DeleteMessageBuilder<'a, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> DeleteMessageBuilder<'a, No, No, No> {
+        DeleteMessageBuilder {
+            client,
+            p_queue_name: PhantomData {},
+            queue_name: None,
+            p_message_id: PhantomData {},
+            message_id: None,
+            p_pop_receipt: PhantomData {},
+            pop_receipt: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> ClientRequired<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, MessageIdSet, PopReceiptSet> QueueNameRequired<'a> for DeleteMessageBuilder<'a, Yes, MessageIdSet, PopReceiptSet>
+where
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    fn queue_name(&self) -> &'a str {
+        self.queue_name.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet, PopReceiptSet> MessageIdRequired<'a> for DeleteMessageBuilder<'a, QueueNameSet, Yes, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    fn message_id(&self) -> &'a str {
+        self.message_id.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet> PopReceiptRequired<'a> for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, Yes>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+{
+    fn pop_receipt(&self) -> &'a str {
+        self.pop_receipt.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> TimeoutOption for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> ClientRequestIdOption<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> QueueNameSupport<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    type O = DeleteMessageBuilder<'a, Yes, MessageIdSet, PopReceiptSet>;
+
+    fn with_queue_name(self, queue_name: &'a str) -> Self::O {
+        DeleteMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            queue_name: Some(queue_name),
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> MessageIdSupport<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    type O = DeleteMessageBuilder<'a, QueueNameSet, Yes, PopReceiptSet>;
+
+    fn with_message_id(self, message_id: &'a str) -> Self::O {
+        DeleteMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: Some(message_id),
+            pop_receipt: self.pop_receipt,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> PopReceiptSupport<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    type O = DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, Yes>;
+
+    fn with_pop_receipt(self, pop_receipt: &'a str) -> Self::O {
+        DeleteMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: Some(pop_receipt),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> TimeoutSupport for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    type O = DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        DeleteMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> ClientRequestIdSupport<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    type O = DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        DeleteMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> ExtraHeadersOption<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> ExtraHeadersSupport<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    type O = DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> ApiVersionOption<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> ApiVersionSupport<'a>
+    for DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    type O = DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        DeleteMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> DeleteMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+}
+
+impl<'a> DeleteMessageBuilder<'a, Yes, Yes, Yes> {
+    pub fn finalize(self) -> impl Future<Item = DeleteMessageResponse, Error = AzureError> {
+        let mut uri = generate_message_uri(&self, Some(&PopReceiptRequired::to_uri_parameter(&self)));
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::DELETE,
+            |ref mut request| {
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            None,
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::NO_CONTENT))
+            .and_then(move |(headers, _body)| done(DeleteMessageResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<DeleteMessageResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
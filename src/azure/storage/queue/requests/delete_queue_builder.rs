@@ -0,0 +1,216 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::queue::responses::DeleteQueueResponse;
+use crate::azure::storage::queue::{generate_queue_uri, QueueNameRequired, QueueNameSupport};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_queue_name: PhantomData<QueueNameSet>,
+    queue_name: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> DeleteQueueBuilder<'a, No> {
+    pub(crate) fn new(client: &'a Client) -> DeleteQueueBuilder<'a, No> {
+        DeleteQueueBuilder {
+            client,
+            p_queue_name: PhantomData {},
+            queue_name: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequired<'a> for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> QueueNameRequired<'a> for DeleteQueueBuilder<'a, Yes> {
+    fn queue_name(&self) -> &'a str {
+        self.queue_name.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet> TimeoutOption for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequestIdOption<'a> for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, QueueNameSet> QueueNameSupport<'a> for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = DeleteQueueBuilder<'a, Yes>;
+
+    fn with_queue_name(self, queue_name: &'a str) -> Self::O {
+        DeleteQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: Some(queue_name),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> TimeoutSupport for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = DeleteQueueBuilder<'a, QueueNameSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        DeleteQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequestIdSupport<'a> for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = DeleteQueueBuilder<'a, QueueNameSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        DeleteQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ExtraHeadersOption<'a> for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, QueueNameSet> ExtraHeadersSupport<'a> for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = DeleteQueueBuilder<'a, QueueNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, QueueNameSet> ApiVersionOption<'a> for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, QueueNameSet> ApiVersionSupport<'a> for DeleteQueueBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = DeleteQueueBuilder<'a, QueueNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        DeleteQueueBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, QueueNameSet> DeleteQueueBuilder<'a, QueueNameSet> where QueueNameSet: ToAssign {}
+
+impl<'a> DeleteQueueBuilder<'a, Yes> {
+    /// Deletes the queue along with all its messages.
+    pub fn finalize(self) -> impl Future<Item = DeleteQueueResponse, Error = AzureError> {
+        let mut uri = generate_queue_uri(&self, None);
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}?{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::DELETE,
+            |ref mut request| {
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            None,
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::NO_CONTENT))
+            .and_then(move |(headers, _body)| done(DeleteQueueResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<DeleteQueueResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,299 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::queue::responses::GetMessagesResponse;
+use crate::azure::storage::queue::{
+    generate_messages_uri, NumberOfMessagesOption, NumberOfMessagesSupport, QueueNameRequired, QueueNameSupport, VisibilityTimeoutOption,
+    VisibilityTimeoutSupport,
+};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    client: &'a Client,
+    p_queue_name: PhantomData<QueueNameSet>,
+    queue_name: Option<&'a str>,
+    number_of_messages: Option<u32>,
+    visibility_timeout: Option<u64>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> GetMessagesBuilder<'a, No> {
+    pub(crate) fn new(client: &'a Client) -> GetMessagesBuilder<'a, No> {
+        GetMessagesBuilder {
+            client,
+            p_queue_name: PhantomData {},
+            queue_name: None,
+            number_of_messages: None,
+            visibility_timeout: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequired<'a> for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> QueueNameRequired<'a> for GetMessagesBuilder<'a, Yes> {
+    fn queue_name(&self) -> &'a str {
+        self.queue_name.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet> NumberOfMessagesOption for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn number_of_messages(&self) -> Option<u32> {
+        self.number_of_messages
+    }
+}
+
+impl<'a, QueueNameSet> VisibilityTimeoutOption for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn visibility_timeout(&self) -> Option<u64> {
+        self.visibility_timeout
+    }
+}
+
+impl<'a, QueueNameSet> TimeoutOption for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequestIdOption<'a> for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, QueueNameSet> QueueNameSupport<'a> for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = GetMessagesBuilder<'a, Yes>;
+
+    fn with_queue_name(self, queue_name: &'a str) -> Self::O {
+        GetMessagesBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: Some(queue_name),
+            number_of_messages: self.number_of_messages,
+            visibility_timeout: self.visibility_timeout,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> NumberOfMessagesSupport for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = GetMessagesBuilder<'a, QueueNameSet>;
+
+    fn with_number_of_messages(self, number_of_messages: u32) -> Self::O {
+        GetMessagesBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            number_of_messages: Some(number_of_messages),
+            visibility_timeout: self.visibility_timeout,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> VisibilityTimeoutSupport for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = GetMessagesBuilder<'a, QueueNameSet>;
+
+    fn with_visibility_timeout(self, visibility_timeout: u64) -> Self::O {
+        GetMessagesBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            number_of_messages: self.number_of_messages,
+            visibility_timeout: Some(visibility_timeout),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> TimeoutSupport for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = GetMessagesBuilder<'a, QueueNameSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        GetMessagesBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            number_of_messages: self.number_of_messages,
+            visibility_timeout: self.visibility_timeout,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ClientRequestIdSupport<'a> for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = GetMessagesBuilder<'a, QueueNameSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        GetMessagesBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            number_of_messages: self.number_of_messages,
+            visibility_timeout: self.visibility_timeout,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet> ExtraHeadersOption<'a> for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, QueueNameSet> ExtraHeadersSupport<'a> for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = GetMessagesBuilder<'a, QueueNameSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, QueueNameSet> ApiVersionOption<'a> for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, QueueNameSet> ApiVersionSupport<'a> for GetMessagesBuilder<'a, QueueNameSet>
+where
+    QueueNameSet: ToAssign,
+{
+    type O = GetMessagesBuilder<'a, QueueNameSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetMessagesBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            queue_name: self.queue_name,
+            number_of_messages: self.number_of_messages,
+            visibility_timeout: self.visibility_timeout,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, QueueNameSet> GetMessagesBuilder<'a, QueueNameSet> where QueueNameSet: ToAssign {}
+
+impl<'a> GetMessagesBuilder<'a, Yes> {
+    /// Dequeues up to `number_of_messages` messages. They stay in the
+    /// queue, invisible for the visibility timeout (30 seconds if not
+    /// specified): delete them using their pop receipt once processed.
+    pub fn finalize(self) -> impl Future<Item = GetMessagesResponse, Error = AzureError> {
+        let mut uri = generate_messages_uri(&self, None);
+
+        let params = vec![
+            NumberOfMessagesOption::to_uri_parameter(&self),
+            VisibilityTimeoutOption::to_uri_parameter(&self),
+            TimeoutOption::to_uri_parameter(&self),
+        ];
+        let params: Vec<String> = params.into_iter().flatten().collect();
+        if !params.is_empty() {
+            uri = format!("{}?{}", uri, params.join("&"));
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::GET,
+            |ref mut request| {
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            None,
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body_as_string(response, StatusCode::OK))
+            .and_then(move |(headers, body)| done(GetMessagesResponse::from_response(&headers, &body)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetMessagesResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,18 @@
+mod create_queue_builder;
+mod delete_message_builder;
+mod delete_queue_builder;
+mod get_messages_builder;
+mod get_queue_metadata_builder;
+mod peek_messages_builder;
+mod put_message_builder;
+mod set_queue_metadata_builder;
+mod update_message_builder;
+pub use self::create_queue_builder::CreateQueueBuilder;
+pub use self::delete_message_builder::DeleteMessageBuilder;
+pub use self::delete_queue_builder::DeleteQueueBuilder;
+pub use self::get_messages_builder::GetMessagesBuilder;
+pub use self::get_queue_metadata_builder::GetQueueMetadataBuilder;
+pub use self::peek_messages_builder::PeekMessagesBuilder;
+pub use self::put_message_builder::PutMessageBuilder;
+pub use self::set_queue_metadata_builder::SetQueueMetadataBuilder;
+pub use self::update_message_builder::UpdateMessageBuilder;
@@ -0,0 +1,372 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::queue::responses::PutMessageResponse;
+use crate::azure::storage::queue::{
+    generate_messages_uri, MessageTextRequired, MessageTextSupport, MessageTtlOption, MessageTtlSupport, QueueNameRequired,
+    QueueNameSupport, VisibilityTimeoutOption, VisibilityTimeoutSupport,
+};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    client: &'a Client,
+    p_queue_name: PhantomData<QueueNameSet>,
+    p_message_text: PhantomData<MessageTextSet>,
+    queue_name: Option<&'a str>,
+    message_text: Option<&'a str>,
+    visibility_timeout: Option<u64>,
+    message_ttl: Option<i64>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> PutMessageBuilder<'a, No, No> {
+    pub(crate) fn new(client: &'a Client) -> PutMessageBuilder<'a, No, No> {
+        PutMessageBuilder {
+            client,
+            p_queue_name: PhantomData {},
+            queue_name: None,
+            p_message_text: PhantomData {},
+            message_text: None,
+            visibility_timeout: None,
+            message_ttl: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> ClientRequired<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, MessageTextSet> QueueNameRequired<'a> for PutMessageBuilder<'a, Yes, MessageTextSet>
+where
+    MessageTextSet: ToAssign,
+{
+    fn queue_name(&self) -> &'a str {
+        self.queue_name.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet> MessageTextRequired<'a> for PutMessageBuilder<'a, QueueNameSet, Yes>
+where
+    QueueNameSet: ToAssign,
+{
+    fn message_text(&self) -> &'a str {
+        self.message_text.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> VisibilityTimeoutOption for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    fn visibility_timeout(&self) -> Option<u64> {
+        self.visibility_timeout
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> MessageTtlOption for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    fn message_ttl(&self) -> Option<i64> {
+        self.message_ttl
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> TimeoutOption for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> ClientRequestIdOption<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> QueueNameSupport<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    type O = PutMessageBuilder<'a, Yes, MessageTextSet>;
+
+    fn with_queue_name(self, queue_name: &'a str) -> Self::O {
+        PutMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_text: PhantomData {},
+            queue_name: Some(queue_name),
+            message_text: self.message_text,
+            visibility_timeout: self.visibility_timeout,
+            message_ttl: self.message_ttl,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> MessageTextSupport<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    type O = PutMessageBuilder<'a, QueueNameSet, Yes>;
+
+    fn with_message_text(self, message_text: &'a str) -> Self::O {
+        PutMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_text: PhantomData {},
+            queue_name: self.queue_name,
+            message_text: Some(message_text),
+            visibility_timeout: self.visibility_timeout,
+            message_ttl: self.message_ttl,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> VisibilityTimeoutSupport for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    type O = PutMessageBuilder<'a, QueueNameSet, MessageTextSet>;
+
+    fn with_visibility_timeout(self, visibility_timeout: u64) -> Self::O {
+        PutMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_text: PhantomData {},
+            queue_name: self.queue_name,
+            message_text: self.message_text,
+            visibility_timeout: Some(visibility_timeout),
+            message_ttl: self.message_ttl,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> MessageTtlSupport for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    type O = PutMessageBuilder<'a, QueueNameSet, MessageTextSet>;
+
+    fn with_message_ttl(self, message_ttl: i64) -> Self::O {
+        PutMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_text: PhantomData {},
+            queue_name: self.queue_name,
+            message_text: self.message_text,
+            visibility_timeout: self.visibility_timeout,
+            message_ttl: Some(message_ttl),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> TimeoutSupport for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    type O = PutMessageBuilder<'a, QueueNameSet, MessageTextSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        PutMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_text: PhantomData {},
+            queue_name: self.queue_name,
+            message_text: self.message_text,
+            visibility_timeout: self.visibility_timeout,
+            message_ttl: self.message_ttl,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> ClientRequestIdSupport<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    type O = PutMessageBuilder<'a, QueueNameSet, MessageTextSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        PutMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_text: PhantomData {},
+            queue_name: self.queue_name,
+            message_text: self.message_text,
+            visibility_timeout: self.visibility_timeout,
+            message_ttl: self.message_ttl,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> ExtraHeadersOption<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> ExtraHeadersSupport<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    type O = PutMessageBuilder<'a, QueueNameSet, MessageTextSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> ApiVersionOption<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, QueueNameSet, MessageTextSet> ApiVersionSupport<'a> for PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+    type O = PutMessageBuilder<'a, QueueNameSet, MessageTextSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        PutMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_text: PhantomData {},
+            queue_name: self.queue_name,
+            message_text: self.message_text,
+            visibility_timeout: self.visibility_timeout,
+            message_ttl: self.message_ttl,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, QueueNameSet, MessageTextSet> PutMessageBuilder<'a, QueueNameSet, MessageTextSet>
+where
+    QueueNameSet: ToAssign,
+    MessageTextSet: ToAssign,
+{
+}
+
+impl<'a> PutMessageBuilder<'a, Yes, Yes> {
+    /// Adds the message to the back of the queue. The visibility timeout
+    /// delays the first time the message can be dequeued.
+    pub fn finalize(self) -> impl Future<Item = PutMessageResponse, Error = AzureError> {
+        let mut uri = generate_messages_uri(&self, None);
+
+        let params = vec![
+            VisibilityTimeoutOption::to_uri_parameter(&self),
+            MessageTtlOption::to_uri_parameter(&self),
+            TimeoutOption::to_uri_parameter(&self),
+        ];
+        let params: Vec<String> = params.into_iter().flatten().collect();
+        if !params.is_empty() {
+            uri = format!("{}?{}", uri, params.join("&"));
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let body = MessageTextRequired::to_body(&self);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::POST,
+            |ref mut request| {
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(body.as_bytes()),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body_as_string(response, StatusCode::CREATED))
+            .and_then(move |(headers, body)| done(PutMessageResponse::from_response(&headers, &body)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<PutMessageResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
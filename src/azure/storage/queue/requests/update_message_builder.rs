@@ -0,0 +1,498 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::queue::responses::UpdateMessageResponse;
+use crate::azure::storage::queue::{
+    generate_message_uri, MessageIdRequired, MessageIdSupport, MessageTextOption, MessageTextSupport, PopReceiptRequired,
+    PopReceiptSupport, QueueNameRequired, QueueNameSupport, VisibilityTimeoutRequired, VisibilityTimeoutSupport,
+};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    client: &'a Client,
+    p_queue_name: PhantomData<QueueNameSet>,
+    p_message_id: PhantomData<MessageIdSet>,
+    p_pop_receipt: PhantomData<PopReceiptSet>,
+    p_visibility_timeout: PhantomData<VisibilityTimeoutSet>,
+    queue_name: Option<&'a str>,
+    message_id: Option<&'a str>,
+    pop_receipt: Option<&'a str>,
+    visibility_timeout: Option<u64>,
+    message_text: Option<&'a str>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> UpdateMessageBuilder<'a, No, No, No, No> {
+    pub(crate) fn new(client: &'a Client) -> UpdateMessageBuilder<'a, No, No, No, No> {
+        UpdateMessageBuilder {
+            client,
+            p_queue_name: PhantomData {},
+            queue_name: None,
+            p_message_id: PhantomData {},
+            message_id: None,
+            p_pop_receipt: PhantomData {},
+            pop_receipt: None,
+            p_visibility_timeout: PhantomData {},
+            visibility_timeout: None,
+            message_text: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> ClientRequired<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> QueueNameRequired<'a>
+    for UpdateMessageBuilder<'a, Yes, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn queue_name(&self) -> &'a str {
+        self.queue_name.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet, PopReceiptSet, VisibilityTimeoutSet> MessageIdRequired<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, Yes, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn message_id(&self) -> &'a str {
+        self.message_id.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, VisibilityTimeoutSet> PopReceiptRequired<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, Yes, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn pop_receipt(&self) -> &'a str {
+        self.pop_receipt.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet> VisibilityTimeoutRequired
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, Yes>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+{
+    fn visibility_timeout(&self) -> u64 {
+        self.visibility_timeout.unwrap()
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> MessageTextOption<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn message_text(&self) -> Option<&'a str> {
+        self.message_text
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> TimeoutOption
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> ClientRequestIdOption<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> QueueNameSupport<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, Yes, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>;
+
+    fn with_queue_name(self, queue_name: &'a str) -> Self::O {
+        UpdateMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            p_visibility_timeout: PhantomData {},
+            queue_name: Some(queue_name),
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            visibility_timeout: self.visibility_timeout,
+            message_text: self.message_text,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> MessageIdSupport<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, QueueNameSet, Yes, PopReceiptSet, VisibilityTimeoutSet>;
+
+    fn with_message_id(self, message_id: &'a str) -> Self::O {
+        UpdateMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            p_visibility_timeout: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: Some(message_id),
+            pop_receipt: self.pop_receipt,
+            visibility_timeout: self.visibility_timeout,
+            message_text: self.message_text,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> PopReceiptSupport<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, Yes, VisibilityTimeoutSet>;
+
+    fn with_pop_receipt(self, pop_receipt: &'a str) -> Self::O {
+        UpdateMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            p_visibility_timeout: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: Some(pop_receipt),
+            visibility_timeout: self.visibility_timeout,
+            message_text: self.message_text,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> VisibilityTimeoutSupport
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, Yes>;
+
+    fn with_visibility_timeout(self, visibility_timeout: u64) -> Self::O {
+        UpdateMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            p_visibility_timeout: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            visibility_timeout: Some(visibility_timeout),
+            message_text: self.message_text,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> MessageTextSupport<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>;
+
+    fn with_message_text(self, message_text: &'a str) -> Self::O {
+        UpdateMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            p_visibility_timeout: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            visibility_timeout: self.visibility_timeout,
+            message_text: Some(message_text),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> TimeoutSupport
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        UpdateMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            p_visibility_timeout: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            visibility_timeout: self.visibility_timeout,
+            message_text: self.message_text,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> ClientRequestIdSupport<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        UpdateMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            p_visibility_timeout: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            visibility_timeout: self.visibility_timeout,
+            message_text: self.message_text,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> ExtraHeadersOption<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> ExtraHeadersSupport<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> ApiVersionOption<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet> ApiVersionSupport<'a>
+    for UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+    type O = UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        UpdateMessageBuilder {
+            client: self.client,
+            p_queue_name: PhantomData {},
+            p_message_id: PhantomData {},
+            p_pop_receipt: PhantomData {},
+            p_visibility_timeout: PhantomData {},
+            queue_name: self.queue_name,
+            message_id: self.message_id,
+            pop_receipt: self.pop_receipt,
+            visibility_timeout: self.visibility_timeout,
+            message_text: self.message_text,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+    UpdateMessageBuilder<'a, QueueNameSet, MessageIdSet, PopReceiptSet, VisibilityTimeoutSet>
+where
+    QueueNameSet: ToAssign,
+    MessageIdSet: ToAssign,
+    PopReceiptSet: ToAssign,
+    VisibilityTimeoutSet: ToAssign,
+{
+}
+
+impl<'a> UpdateMessageBuilder<'a, Yes, Yes, Yes, Yes> {
+    /// Extends (or shortens) the message invisibility and optionally
+    /// replaces its text. The pop receipt changes: use the one returned
+    /// in the response for further operations on the message.
+    pub fn finalize(self) -> impl Future<Item = UpdateMessageResponse, Error = AzureError> {
+        let mut uri = generate_message_uri(
+            &self,
+            Some(&format!(
+                "{}&{}",
+                PopReceiptRequired::to_uri_parameter(&self),
+                VisibilityTimeoutRequired::to_uri_parameter(&self)
+            )),
+        );
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            uri = format!("{}&{}", uri, timeout);
+        }
+
+        trace!("uri == {:?}", uri);
+
+        let body = MessageTextOption::to_body(&self);
+
+        let req = self.client().perform_request(
+            &uri,
+            &Method::PUT,
+            |ref mut request| {
+                ClientRequestIdOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+                ApiVersionOption::add_header(&self, request);
+            },
+            Some(body.as_ref().map(|b| b.as_bytes()).unwrap_or(&[])),
+        );
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::NO_CONTENT))
+            .and_then(move |(headers, _body)| done(UpdateMessageResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<UpdateMessageResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,11 @@
+use crate::azure::core::metadata::Metadata;
+use crate::azure::core::RequestId;
+use chrono::{DateTime, Utc};
+
+response_from_headers!(GetQueueMetadataResponse ,
+		       approximate_messages_count_from_headers -> approximate_messages_count: u64,
+		       metadata_from_headers -> metadata: Metadata,
+		       request_id_from_headers -> request_id: RequestId,
+		       client_request_id_from_headers_optional -> client_request_id: Option<String>,
+		       date_from_headers -> date: DateTime<Utc>
+);
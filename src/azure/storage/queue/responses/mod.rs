@@ -0,0 +1,18 @@
+mod create_queue_response;
+mod delete_message_response;
+mod delete_queue_response;
+mod get_messages_response;
+mod get_queue_metadata_response;
+mod peek_messages_response;
+mod put_message_response;
+mod set_queue_metadata_response;
+mod update_message_response;
+pub use self::create_queue_response::CreateQueueResponse;
+pub use self::delete_message_response::DeleteMessageResponse;
+pub use self::delete_queue_response::DeleteQueueResponse;
+pub use self::get_messages_response::GetMessagesResponse;
+pub use self::get_queue_metadata_response::GetQueueMetadataResponse;
+pub use self::peek_messages_response::PeekMessagesResponse;
+pub use self::put_message_response::PutMessageResponse;
+pub use self::set_queue_metadata_response::SetQueueMetadataResponse;
+pub use self::update_message_response::UpdateMessageResponse;
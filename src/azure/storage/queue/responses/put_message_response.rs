@@ -0,0 +1,39 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::parsing::{cast_must, traverse_single_must};
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+use xml::Element;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PutMessageResponse {
+    pub message_id: String,
+    pub insertion_time: DateTime<Utc>,
+    pub expiration_time: DateTime<Utc>,
+    pub pop_receipt: String,
+    pub time_next_visible: DateTime<Utc>,
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl PutMessageResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &str) -> Result<PutMessageResponse, AzureError> {
+        let elem: Element = body.parse()?;
+        let message = traverse_single_must(&elem, &["QueueMessage"])?;
+
+        Ok(PutMessageResponse {
+            message_id: cast_must::<String>(message, &["MessageId"])?,
+            insertion_time: cast_must::<DateTime<Utc>>(message, &["InsertionTime"])?,
+            expiration_time: cast_must::<DateTime<Utc>>(message, &["ExpirationTime"])?,
+            pop_receipt: cast_must::<String>(message, &["PopReceipt"])?,
+            time_next_visible: cast_must::<DateTime<Utc>>(message, &["TimeNextVisible"])?,
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            date: date_from_headers(headers)?,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
@@ -1,14 +1,17 @@
 use crate::azure::core::{
+    clock_skew::ClockSkew,
+    crypto::{shared_key_authorization, HmacKey},
     errors::AzureError,
     headers,
+    pipeline::{Next, PipelineFuture, PipelineRequest, Policy},
     util::{format_header_value, HeaderMapExt, RequestBuilderExt},
 };
-use base64;
 use chrono;
+use futures::future;
+use futures::{Future, Stream};
 use hyper::{self, header, HeaderMap, Method};
-use hyper_tls;
-use ring::{digest::SHA256, hmac};
 use std::fmt::Write;
+use std::sync::{Arc, RwLock};
 use url;
 
 #[derive(Debug, Clone, Copy)]
@@ -18,31 +21,30 @@ pub enum ServiceType {
     Table,
 }
 
-const AZURE_VERSION: &str = "2017-11-09";
+/// The `x-ms-version` sent by default. See `Client::with_api_version`.
+pub const AZURE_VERSION: &str = "2017-11-09";
 
 pub const HEADER_VERSION: &str = "x-ms-version"; //=> [String] }
 pub const HEADER_DATE: &str = "x-ms-date"; //=> [String] }
 
-fn generate_authorization(h: &HeaderMap, u: &url::Url, method: &Method, hmac_key: &str, service_type: ServiceType) -> String {
-    let str_to_sign = string_to_sign(h, u, method, service_type);
+/// Appended to the account name to address the read-only secondary
+/// endpoint of a geo-redundant account.
+pub(crate) const SECONDARY_SUFFIX: &str = "-secondary";
+
+pub(crate) fn generate_authorization(
+    h: &HeaderMap,
+    u: &url::Url,
+    method: &Method,
+    account: &str,
+    hmac_key: &str,
+    service_type: ServiceType,
+) -> String {
+    let str_to_sign = string_to_sign(h, u, method, account, service_type);
 
     // debug!("\nstr_to_sign == {:?}\n", str_to_sign);
     // debug!("str_to_sign == {}", str_to_sign);
 
-    let auth = encode_str_to_sign(&str_to_sign, hmac_key);
-    // debug!("auth == {:?}", auth);
-
-    format!("SharedKey {}:{}", get_account(u), auth)
-}
-
-fn encode_str_to_sign(str_to_sign: &str, hmac_key: &str) -> String {
-    let key = hmac::SigningKey::new(&SHA256, &base64::decode(hmac_key).unwrap());
-    let sig = hmac::sign(&key, str_to_sign.as_bytes());
-
-    // let res = hmac.result();
-    // debug!("{:?}", res.code());
-
-    base64::encode(sig.as_ref())
+    shared_key_authorization(account, &HmacKey::from_base64(hmac_key).unwrap(), &str_to_sign)
 }
 
 #[inline]
@@ -55,7 +57,7 @@ fn add_if_exists<K: header::AsHeaderName>(h: &HeaderMap, key: K) -> &str {
 
 #[allow(unknown_lints)]
 #[clippy::needless_pass_by_value]
-fn string_to_sign(h: &HeaderMap, u: &url::Url, method: &Method, service_type: ServiceType) -> String {
+fn string_to_sign(h: &HeaderMap, u: &url::Url, method: &Method, account: &str, service_type: ServiceType) -> String {
     match service_type {
         ServiceType::Table => {
             let mut s = String::new();
@@ -66,7 +68,7 @@ fn string_to_sign(h: &HeaderMap, u: &url::Url, method: &Method, service_type: Se
                 add_if_exists(h, headers::CONTENT_MD5),
                 add_if_exists(h, header::CONTENT_TYPE),
                 add_if_exists(h, HEADER_DATE),
-                canonicalized_resource_table(u)
+                canonicalized_resource_table(u, account)
             )
             .unwrap();
             s
@@ -95,7 +97,7 @@ fn string_to_sign(h: &HeaderMap, u: &url::Url, method: &Method, service_type: Se
                 add_if_exists(h, header::IF_UNMODIFIED_SINCE),
                 add_if_exists(h, header::RANGE),
                 canonicalize_header(h),
-                canonicalized_resource(u)
+                canonicalized_resource(u, account)
             )
             .unwrap();
             s
@@ -140,34 +142,29 @@ fn canonicalize_header(h: &HeaderMap) -> String {
     can
 }
 
-#[inline]
-fn get_account(u: &url::Url) -> &str {
-    match u.host().unwrap().clone() {
-        url::Host::Domain(dm) => {
-            // debug!("dom == {:?}", dm);
-
-            let first_dot = dm.find('.').unwrap();
-            &dm[0..first_dot]
-        }
-        url::Host::Ipv4(_) => {
-            // this must be the emulator
-            "devstoreaccount1"
-        }
-        _ => panic!("only Domains are supported in canonicalized_resource"),
+// The account is the one of the client, whatever the host: the custom
+// domains, the private endpoints and the secondary endpoint are signed
+// as the primary one. The emulator is addressed path-style, so its
+// account shows up twice.
+
+/// The canonicalized resource the table requests are signed with: only
+/// the `comp` parameter of the query is part of it.
+pub fn canonicalized_resource_table(u: &url::Url, account: &str) -> String {
+    match u.query_pairs().find(|(k, _)| k == "comp") {
+        Some((_, comp)) => format!("/{}{}?comp={}", account, u.path(), comp),
+        None => format!("/{}{}", account, u.path()),
     }
 }
 
-// For table
-fn canonicalized_resource_table(u: &url::Url) -> String {
-    format!("/{}{}", get_account(u), u.path())
-}
-
-fn canonicalized_resource(u: &url::Url) -> String {
+/// The canonicalized resource the blob, queue and file requests are
+/// signed with: the path of the uri and, one per line, the query
+/// parameters sorted by name with their values sorted and comma
+/// separated.
+pub fn canonicalized_resource(u: &url::Url, account: &str) -> String {
     let mut can_res: String = String::new();
     can_res += "/";
 
-    let account = get_account(u);
-    can_res += &account;
+    can_res += account;
 
     let paths = u.path_segments().unwrap();
 
@@ -232,31 +229,18 @@ fn lexy_sort(vec: &url::form_urlencoded::Parse, query_param: &str) -> Vec<(Strin
     v_values
 }
 
-#[allow(unknown_lints)]
-#[clippy::too_many_arguments]
-pub fn perform_request<F>(
-    client: &hyper::Client<hyper_tls::HttpsConnector<hyper::client::HttpConnector>>,
+/// Builds the request as specified by the caller. The date and the
+/// signature are added by `SigningStage`, right before each attempt.
+pub(crate) fn build_request<F>(
     uri: &str,
     http_method: &Method,
-    azure_key: &str,
     headers_func: F,
     request_body: Option<&[u8]>,
-    service_type: ServiceType,
-) -> Result<hyper::client::ResponseFuture, AzureError>
+) -> Result<PipelineRequest, AzureError>
 where
     F: FnOnce(&mut ::http::request::Builder),
 {
-    let dt = chrono::Utc::now();
-    let time = format!("{}", dt.format("%a, %d %h %Y %T GMT"));
-
-    let url = url::Url::parse(uri)?;
-
-    // for header in additional_headers.iter() {
-    //     debug!("{:?}", header.value_string());
-    //     h.set();
-    // }
     let mut request = hyper::Request::builder();
-    request.method(http_method.clone()).uri(uri);
 
     // let's add content length to avoid "chunking" errors.
     match request_body {
@@ -269,19 +253,236 @@ where
     // a Cow with 'static lifetime...
     headers_func(&mut request);
 
-    request.header_bytes(HEADER_DATE, time).header_static(HEADER_VERSION, AZURE_VERSION);
+    let headers = request.body(())?.into_parts().0.headers;
+
+    Ok(PipelineRequest {
+        method: http_method.clone(),
+        uri: uri.to_owned(),
+        headers,
+        body: request_body.map(Vec::from),
+        pacer: None,
+    })
+}
+
+/// The account key, shared by a `Client`, its clones and the requests in
+/// flight so that `Client::set_key` reaches all of them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AccountKey(Arc<RwLock<String>>);
+
+impl AccountKey {
+    pub(crate) fn new(key: &str) -> AccountKey {
+        AccountKey(Arc::new(RwLock::new(key.to_owned())))
+    }
+
+    pub(crate) fn get(&self) -> String {
+        self.0.read().unwrap().clone()
+    }
+
+    pub(crate) fn set(&self, key: &str) {
+        *self.0.write().unwrap() = key.to_owned();
+    }
+}
+
+/// Last stage of the storage pipeline: dates and signs the request with
+/// the account Shared Key. A retried request is signed again, with the
+/// current time (corrected by the clock skew compensation, if enabled).
+/// A request rejected with 403 after the account key was rotated is
+/// signed with the new key and sent once more.
+#[derive(Debug, Clone)]
+pub(crate) struct SigningStage {
+    account: String,
+    azure_key: AccountKey,
+    service_type: ServiceType,
+    clock_skew: Option<Arc<ClockSkew>>,
+    bearer_token: Option<String>,
+    diagnostics: bool,
+    api_version: String,
+}
+
+impl SigningStage {
+    pub(crate) fn new(account: &str, azure_key: AccountKey, service_type: ServiceType, clock_skew: Option<Arc<ClockSkew>>) -> SigningStage {
+        SigningStage {
+            account: account.to_owned(),
+            azure_key,
+            service_type,
+            clock_skew,
+            bearer_token: None,
+            diagnostics: false,
+            api_version: AZURE_VERSION.to_owned(),
+        }
+    }
+
+    /// Authorizes the requests with the Azure AD token instead of the
+    /// Shared Key signature.
+    pub(crate) fn with_bearer_token(self, bearer_token: Option<String>) -> SigningStage {
+        SigningStage { bearer_token, ..self }
+    }
+
+    /// Logs what was signed when the service answers 403. See
+    /// `Client::with_signing_diagnostics`.
+    pub(crate) fn with_diagnostics(self, diagnostics: bool) -> SigningStage {
+        SigningStage { diagnostics, ..self }
+    }
+
+    /// The `x-ms-version` of the requests not setting one.
+    pub(crate) fn with_api_version(self, api_version: &str) -> SigningStage {
+        SigningStage {
+            api_version: api_version.to_owned(),
+            ..self
+        }
+    }
+
+    // neither a bearer token nor a SAS signature: the account key is used
+    fn uses_account_key(&self, request: &PipelineRequest) -> bool {
+        self.bearer_token.is_none()
+            && url::Url::parse(&request.uri)
+                .map(|url| url.query_pairs().all(|p| p.0 != "sig"))
+                .unwrap_or(false)
+    }
+
+    fn sign(&self, request: &mut PipelineRequest, azure_key: &str) -> Result<Option<SigningDump>, AzureError> {
+        let dt = match &self.clock_skew {
+            Some(clock_skew) => clock_skew.now(),
+            None => chrono::Utc::now(),
+        };
+        let time = format!("{}", dt.format("%a, %d %h %Y %T GMT"));
+
+        request.headers.insert(HEADER_DATE, format_header_value(time)?);
+        if !request.headers.contains_key(HEADER_VERSION) {
+            request.headers.insert(HEADER_VERSION, format_header_value(&self.api_version)?);
+        }
+
+        let url = url::Url::parse(&request.uri)?;
+
+        // We sign the request only if it is not already signed (with the signature of an
+        // SAS token for example)
+        if let Some(bearer_token) = &self.bearer_token {
+            request
+                .headers
+                .insert(header::AUTHORIZATION, format_header_value(format!("Bearer {}", bearer_token))?);
+        } else if url.query_pairs().find(|p| p.0 == "sig").is_none() {
+            let auth = generate_authorization(&request.headers, &url, &request.method, &self.account, azure_key, self.service_type);
+            request.headers.insert(header::AUTHORIZATION, format_header_value(auth)?);
+        }
+
+        if self.diagnostics {
+            Ok(Some(SigningDump::new(&request.headers, &url, &request.method, &self.account, self.service_type)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn sign_and_send(&self, mut request: PipelineRequest, azure_key: &str, next: Next) -> PipelineFuture {
+        match self.sign(&mut request, azure_key) {
+            Ok(None) => next.send(request),
+            Ok(Some(dump)) => Box::new(next.send(request).and_then(move |response| dump.log_if_forbidden(response))),
+            Err(error) => Box::new(future::err(error)),
+        }
+    }
+}
+
+impl Policy for SigningStage {
+    fn send(&self, request: PipelineRequest, next: Next) -> PipelineFuture {
+        let azure_key = self.azure_key.get();
+        if !self.uses_account_key(&request) {
+            return self.sign_and_send(request, &azure_key, next);
+        }
+
+        let stage = self.clone();
+        let unsigned = request.clone();
+        let first = self.sign_and_send(request, &azure_key, next.clone());
+        Box::new(first.and_then(move |response| -> PipelineFuture {
+            let current_key = stage.azure_key.get();
+            if response.status() == hyper::StatusCode::FORBIDDEN && current_key != azure_key {
+                debug!(
+                    "{} {} was rejected with 403 after the account key was rotated: retrying with the new key",
+                    unsigned.method, unsigned.uri
+                );
+                stage.sign_and_send(unsigned, &current_key, next)
+            } else {
+                Box::new(future::ok(response))
+            }
+        }))
+    }
+}
+
+/// What the client signed, to compare with the string to sign the
+/// service reports in the `AuthenticationErrorDetail` of a 403. The
+/// signatures, SAS included, and the customer-provided keys are redacted.
+#[derive(Debug, Clone, PartialEq)]
+struct SigningDump {
+    method: Method,
+    uri: String,
+    string_to_sign: String,
+    canonicalized_headers: String,
+}
+
+impl SigningDump {
+    fn new(h: &HeaderMap, u: &url::Url, method: &Method, account: &str, service_type: ServiceType) -> SigningDump {
+        SigningDump {
+            method: method.clone(),
+            uri: redact(u.as_str()),
+            string_to_sign: redact(&string_to_sign(h, u, method, account, service_type)),
+            canonicalized_headers: redact(&canonicalize_header(h)),
+        }
+    }
 
-    let b = request_body.map(|v| Vec::from(v).into()).unwrap_or_else(hyper::Body::empty);
-    let mut request = request.body(b)?;
+    fn log_if_forbidden(self, response: hyper::Response<hyper::Body>) -> PipelineFuture {
+        if response.status() != hyper::StatusCode::FORBIDDEN {
+            return Box::new(future::ok(response));
+        }
 
-    // We sign the request only if it is not already signed (with the signature of an
-    // SAS token for example)
-    if url.query_pairs().find(|p| p.0 == "sig").is_none() {
-        let auth = generate_authorization(request.headers(), &url, http_method, azure_key, service_type);
-        request.headers_mut().insert(header::AUTHORIZATION, format_header_value(auth)?);
+        // the body is read to get the service side of the story, and put
+        // back for the caller
+        let (parts, body) = response.into_parts();
+        Box::new(body.concat2().from_err().map(move |body| {
+            let detail = std::str::from_utf8(&body)
+                .ok()
+                .and_then(authentication_error_detail)
+                .map(redact)
+                .unwrap_or_default();
+            warn!(
+                "{} {} was rejected with 403.\nstring to sign:\n{:?}\ncanonicalized headers:\n{:?}\nservice detail: {}",
+                self.method, self.uri, self.string_to_sign, self.canonicalized_headers, detail
+            );
+            hyper::Response::from_parts(parts, hyper::Body::from(body))
+        }))
     }
+}
+
+fn authentication_error_detail(body: &str) -> Option<&str> {
+    let start = body.find("<AuthenticationErrorDetail>")? + "<AuthenticationErrorDetail>".len();
+    let end = body[start..].find("</AuthenticationErrorDetail>")?;
+    Some(&body[start..start + end])
+}
+
+const REDACTED: &str = "REDACTED";
 
-    Ok(client.request(request))
+// hides the SAS signatures (in the uri or in a copy source) and the
+// customer-provided keys
+fn redact(s: &str) -> String {
+    let mut redacted = String::with_capacity(s.len());
+    for (i, line) in s.split('\n').enumerate() {
+        if i > 0 {
+            redacted.push('\n');
+        }
+        if line.starts_with(headers::ENCRYPTION_KEY) && line[headers::ENCRYPTION_KEY.len()..].starts_with(':') {
+            redacted.push_str(headers::ENCRYPTION_KEY);
+            redacted.push(':');
+            redacted.push_str(REDACTED);
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(pos) = rest.find("sig=") {
+            redacted.push_str(&rest[..pos + "sig=".len()]);
+            redacted.push_str(REDACTED);
+            rest = &rest[pos + "sig=".len()..];
+            rest = &rest[rest.find(|c: char| c == '&' || c.is_whitespace() || c == '\'').unwrap_or(rest.len())..];
+        }
+        redacted.push_str(rest);
+    }
+    redacted
 }
 
 mod test {
@@ -309,6 +510,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_canonicalized_resource_path_style() {
+        use super::*;
+
+        let uri = url::Url::parse("http://127.0.0.1:10000/devstoreaccount1/mycontainer?restype=container").unwrap();
+        assert_eq!(
+            canonicalized_resource(&uri, "devstoreaccount1"),
+            "/devstoreaccount1/devstoreaccount1/mycontainer\nrestype:container"
+        );
+
+        let uri = url::Url::parse("http://azurite:10002/devstoreaccount1/mytable").unwrap();
+        assert_eq!(
+            canonicalized_resource_table(&uri, "devstoreaccount1"),
+            "/devstoreaccount1/devstoreaccount1/mytable"
+        );
+    }
+
+    #[test]
+    fn test_canonicalized_resource_custom_endpoint() {
+        use super::*;
+
+        let uri = url::Url::parse("https://myaccount-secondary.blob.core.windows.net/mycontainer").unwrap();
+        assert_eq!(canonicalized_resource(&uri, "myaccount"), "/myaccount/mycontainer");
+
+        let uri = url::Url::parse("https://storage.contoso.com/mycontainer/myblob").unwrap();
+        assert_eq!(canonicalized_resource(&uri, "myaccount"), "/myaccount/mycontainer/myblob");
+    }
+
     #[test]
     fn str_to_sign_test() {
         use super::*;
@@ -328,7 +557,7 @@ mod test {
         headers.insert(HEADER_DATE, format_header_value(time).unwrap());
         headers.insert(HEADER_VERSION, header::HeaderValue::from_static(AZURE_VERSION));
 
-        let s = string_to_sign(&headers, &u, &method, service_type);
+        let s = string_to_sign(&headers, &u, &method, "mindrust", service_type);
 
         assert_eq!(
             s,
@@ -343,7 +572,7 @@ Wed, 03 May 2017 14:04:56 GMT
     #[test]
     fn test_canonicalize_resource_10() {
         let url = url::Url::parse("https://mindrust.table.core.windows.net/TABLES").unwrap();
-        assert_eq!(super::canonicalized_resource(&url), "/mindrust/TABLES");
+        assert_eq!(super::canonicalized_resource(&url, "mindrust"), "/mindrust/TABLES");
     }
 
     #[test]
@@ -354,7 +583,7 @@ Wed, 03 May 2017 14:04:56 GMT
         )
         .unwrap();
         assert_eq!(
-            super::canonicalized_resource(&url),
+            super::canonicalized_resource(&url, "myaccount"),
             "/myaccount/mycontainer\ncomp:metadata\nrestype:container"
         );
     }
@@ -368,7 +597,7 @@ Wed, 03 May 2017 14:04:56 GMT
         )
         .unwrap();
         assert_eq!(
-            super::canonicalized_resource(&url),
+            super::canonicalized_resource(&url, "myaccount"),
             "/myaccount/mycontainer\ncomp:list\ninclude:metadata,snapshots,\
              uncommittedblobs\nrestype:container"
         );
@@ -381,7 +610,8 @@ Wed, 03 May 2017 14:04:56 GMT
              net/mycontainer/myblob",
         )
         .unwrap();
-        assert_eq!(super::canonicalized_resource(&url), "/myaccount-secondary/mycontainer/myblob");
+        // the secondary endpoint is signed with the primary account name
+        assert_eq!(super::canonicalized_resource(&url, "myaccount"), "/myaccount/mycontainer/myblob");
     }
 
     #[test]
@@ -392,7 +622,7 @@ Wed, 03 May 2017 14:04:56 GMT
             .to_owned();
 
         assert_eq!(
-            super::encode_str_to_sign(&str_to_sign, &hmac_key),
+            super::HmacKey::from_base64(&hmac_key).unwrap().sign_str(&str_to_sign),
             "gZzaRaIkvC9jYRY123tq3xXZdsMAcgAbjKQo8y0p0Fs=".to_owned()
         );
     }
@@ -405,7 +635,7 @@ Wed, 03 May 2017 14:04:56 GMT
             .to_owned();
 
         assert_eq!(
-            super::encode_str_to_sign(&str_to_sign, &hmac_key),
+            super::HmacKey::from_base64(&hmac_key).unwrap().sign_str(&str_to_sign),
             "YuKoXELO9M9HXeeGaSXBr4Nk+CgPAEQhcwJ6tVtBRCw=".to_owned()
         );
     }
@@ -418,8 +648,191 @@ Wed, 03 May 2017 14:04:56 GMT
             .to_owned();
 
         assert_eq!(
-            super::encode_str_to_sign(&str_to_sign, &hmac_key),
+            super::HmacKey::from_base64(&hmac_key).unwrap().sign_str(&str_to_sign),
             "YuKoXELO9M9HXeeGaSXBr4Nk+CgPAEQhcwJ6tVtBRCw=".to_owned()
         );
     }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            super::redact("https://a.blob.core.windows.net/c/b?sv=2018-03-28&sig=abc%2Bd&se=1"),
+            "https://a.blob.core.windows.net/c/b?sv=2018-03-28&sig=REDACTED&se=1"
+        );
+        assert_eq!(
+            super::redact("x-ms-date:Tue, 15 Oct 2019\nx-ms-encryption-key:c2VjcmV0\nx-ms-encryption-key-sha256:aGFzaA==\n"),
+            "x-ms-date:Tue, 15 Oct 2019\nx-ms-encryption-key:REDACTED\nx-ms-encryption-key-sha256:aGFzaA==\n"
+        );
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        use super::*;
+
+        let stage =
+            SigningStage::new("account", AccountKey::default(), ServiceType::Blob, None).with_bearer_token(Some("eyJ0eXAi".to_owned()));
+        let mut request = PipelineRequest {
+            method: Method::POST,
+            uri: "https://account.blob.core.windows.net/?restype=service&comp=userdelegationkey".to_owned(),
+            headers: HeaderMap::new(),
+            body: None,
+            pacer: None,
+        };
+
+        stage.sign(&mut request, "").unwrap();
+        assert_eq!(request.headers[header::AUTHORIZATION], "Bearer eyJ0eXAi");
+    }
+
+    #[test]
+    fn test_signing_diagnostics() {
+        use super::*;
+        use crate::azure::core::pipeline;
+
+        let body = "<?xml version=\"1.0\" encoding=\"utf-8\"?><Error><Code>AuthenticationFailed</Code>\
+                    <AuthenticationErrorDetail>Server used following string to sign: 'GET'.</AuthenticationErrorDetail></Error>";
+        let stage = SigningStage::new("account", AccountKey::new("YWNjb3VudGtleQ=="), ServiceType::Blob, None).with_diagnostics(true);
+        let request = PipelineRequest {
+            method: Method::GET,
+            uri: "https://account.blob.core.windows.net/container?restype=container".to_owned(),
+            headers: HeaderMap::new(),
+            body: None,
+            pacer: None,
+        };
+
+        let dump = stage.sign(&mut request.clone(), "YWNjb3VudGtleQ==").unwrap().unwrap();
+        assert!(dump.string_to_sign.starts_with("GET\n"));
+        assert!(dump.canonicalized_headers.contains("x-ms-version:"));
+        assert_eq!(super::authentication_error_detail(body), Some("Server used following string to sign: 'GET'."));
+
+        // the 403 body is still there for the caller
+        let response = pipeline::send(
+            vec![Arc::new(stage)],
+            move |_| -> PipelineFuture {
+                let mut response = hyper::Response::new(hyper::Body::from(body));
+                *response.status_mut() = hyper::StatusCode::FORBIDDEN;
+                Box::new(future::ok(response))
+            },
+            request,
+        )
+        .wait()
+        .unwrap();
+        assert_eq!(response.status(), hyper::StatusCode::FORBIDDEN);
+        assert_eq!(&response.into_body().concat2().wait().unwrap()[..], body.as_bytes());
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        use super::*;
+        use crate::azure::core::pipeline;
+        use std::sync::Mutex;
+
+        let key = AccountKey::new("b2xka2V5");
+        let stage = SigningStage::new("account", key.clone(), ServiceType::Blob, None);
+        let request = PipelineRequest {
+            method: Method::GET,
+            uri: "https://account.blob.core.windows.net/container?restype=container".to_owned(),
+            headers: HeaderMap::new(),
+            body: None,
+            pacer: None,
+        };
+        let authorizations = Arc::new(Mutex::new(Vec::new()));
+
+        // the key is rotated while the first attempt is in flight
+        let sent = authorizations.clone();
+        let rotated = key.clone();
+        let transport = move |request: PipelineRequest| -> PipelineFuture {
+            let mut sent = sent.lock().unwrap();
+            sent.push(request.headers[header::AUTHORIZATION].clone());
+            rotated.set("bmV3a2V5");
+            let mut response = hyper::Response::new(hyper::Body::empty());
+            if sent.len() == 1 {
+                *response.status_mut() = hyper::StatusCode::FORBIDDEN;
+            }
+            Box::new(future::ok(response))
+        };
+        let response = pipeline::send(vec![Arc::new(stage.clone())], transport, request.clone())
+            .wait()
+            .unwrap();
+        assert_eq!(response.status(), hyper::StatusCode::OK);
+        let sent = authorizations.lock().unwrap().clone();
+        assert_eq!(sent.len(), 2);
+        assert_ne!(sent[0], sent[1]);
+
+        // a 403 with the current key is the caller's business
+        let attempts = Arc::new(Mutex::new(0));
+        let counted = attempts.clone();
+        let transport = move |_| -> PipelineFuture {
+            *counted.lock().unwrap() += 1;
+            let mut response = hyper::Response::new(hyper::Body::empty());
+            *response.status_mut() = hyper::StatusCode::FORBIDDEN;
+            Box::new(future::ok(response))
+        };
+        let response = pipeline::send(vec![Arc::new(stage)], transport, request).wait().unwrap();
+        assert_eq!(response.status(), hyper::StatusCode::FORBIDDEN);
+        assert_eq!(*attempts.lock().unwrap(), 1);
+
+        // the clones of a client share the key
+        let client = crate::azure::storage::client::Client::azure("account", "b2xka2V5").unwrap();
+        client.clone().set_key("bmV3a2V5").unwrap();
+        assert_eq!(client.key(), "bmV3a2V5");
+        assert!(client.set_key("not base64!").is_err());
+        assert_eq!(client.key(), "bmV3a2V5");
+    }
+
+    #[test]
+    fn test_api_version() {
+        use super::*;
+        use crate::azure::core::test_transport::ReplayTransport;
+        use crate::azure::core::{
+            ApiVersionSupport, BlobNameSupport, ContainerNameSupport, DeleteSnapshotsMethod, DeleteSnapshotsMethodSupport, VersionIdSupport,
+        };
+        use crate::azure::storage::client::{Blob, Client, Container};
+
+        let deleted = [
+            ("x-ms-delete-type-permanent", "false"),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(hyper::StatusCode::ACCEPTED, &[], b"")
+                .with_response(hyper::StatusCode::ACCEPTED, &deleted, b"")
+                .with_response(hyper::StatusCode::ACCEPTED, &deleted, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone())
+            .with_api_version("2019-02-02")
+            .unwrap();
+        let delete_version = || {
+            client
+                .delete_blob()
+                .with_container_name("c")
+                .with_blob_name("b")
+                .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+                .with_version_id("2019-10-15T10:00:00.0000000Z")
+        };
+
+        client.delete_container().with_container_name("c").finalize().wait().unwrap();
+        delete_version().finalize().wait().unwrap();
+        delete_version().with_api_version("2020-10-02").finalize().wait().unwrap();
+        // versions are not available before 2019-12-12
+        assert!(delete_version().with_api_version("2019-07-07").finalize().wait().is_err());
+
+        let versions: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|request| request.headers[HEADER_VERSION].to_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(versions, vec!["2019-02-02", "2019-12-12", "2020-10-02"]);
+    }
+
+    #[test]
+    fn test_canonicalized_resource_table() {
+        let url = url::Url::parse("https://account.table.core.windows.net/?restype=service&comp=properties").unwrap();
+        assert_eq!(super::canonicalized_resource_table(&url, "account"), "/account/?comp=properties");
+
+        let url = url::Url::parse("https://account.table.core.windows.net/mytable()?$top=1").unwrap();
+        assert_eq!(super::canonicalized_resource_table(&url, "account"), "/account/mytable()");
+    }
 }
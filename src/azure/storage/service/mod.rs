@@ -0,0 +1,125 @@
+pub mod requests;
+pub mod responses;
+
+mod service_properties;
+pub use self::service_properties::{CorsRule, Logging, Metrics, RetentionPolicy, StaticWebsite, StorageServiceProperties};
+mod service_stats;
+pub use self::service_stats::{GeoReplication, GeoReplicationStatus, ServiceStats};
+mod user_delegation_key;
+pub use self::user_delegation_key::UserDelegationKey;
+pub(crate) use self::user_delegation_key::sas_time;
+
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::ClientRequired;
+use crate::azure::storage::client::ResponseFuture;
+use crate::azure::storage::rest_client::SECONDARY_SUFFIX;
+use chrono::{DateTime, Utc};
+use hyper::Method;
+
+// the static website settings are not available before this version
+pub(crate) const SERVICE_PROPERTIES_VERSION: &str = "2018-03-28";
+
+// Get Account Information is not available before this version
+pub(crate) const ACCOUNT_INFORMATION_VERSION: &str = "2018-03-28";
+
+// Get User Delegation Key is not available before this version
+pub(crate) const USER_DELEGATION_KEY_VERSION: &str = "2018-11-09";
+
+/// The services of a storage account with account level properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageService {
+    Blob,
+    Queue,
+    Table,
+}
+
+pub trait StorageServicePropertiesSupport<'a> {
+    type O;
+    fn with_storage_service_properties(self, storage_service_properties: &'a StorageServiceProperties) -> Self::O;
+}
+
+pub trait StorageServicePropertiesRequired<'a> {
+    fn storage_service_properties(&self) -> &'a StorageServiceProperties;
+}
+
+pub trait StartSupport {
+    type O;
+    fn with_start(self, start: DateTime<Utc>) -> Self::O;
+}
+
+pub trait StartOption {
+    fn start(&self) -> Option<DateTime<Utc>>;
+}
+
+pub trait ExpirySupport {
+    type O;
+    fn with_expiry(self, expiry: DateTime<Utc>) -> Self::O;
+}
+
+pub trait ExpiryRequired {
+    fn expiry(&self) -> DateTime<Utc>;
+}
+
+// Sends a request to the root of the service (`?restype=service&...`),
+// signed the way the service expects. The read-only secondary endpoint of
+// a geo-redundant account is addressed with `secondary`.
+pub(crate) fn perform_service_request<'a, T, F>(
+    t: &T,
+    storage_service: StorageService,
+    secondary: bool,
+    params: &str,
+    method: &Method,
+    headers_func: F,
+    request_body: Option<&[u8]>,
+) -> Result<ResponseFuture, AzureError>
+where
+    T: ClientRequired<'a>,
+    F: FnOnce(&mut ::http::request::Builder),
+{
+    let client = t.client();
+    let uri = match storage_service {
+        StorageService::Blob => client.blob_uri(),
+        StorageService::Queue => client.queue_uri(),
+        StorageService::Table => client.table_uri(),
+    };
+    let uri = if secondary {
+        secondary_uri(uri, client.account())
+    } else {
+        uri.to_owned()
+    };
+    let uri = format!("{}/?{}", uri, params);
+
+    match storage_service {
+        StorageService::Table => client.perform_table_request_with_uri(&uri, method, headers_func, request_body),
+        _ => client.perform_request(&uri, method, headers_func, request_body),
+    }
+}
+
+// The secondary endpoint adds the suffix to the account name, that is the
+// first label of the host or, for the emulator, the last segment of the
+// path.
+pub(crate) fn secondary_uri(primary_uri: &str, account: &str) -> String {
+    let host_prefix = format!("//{}.", account);
+    if primary_uri.contains(&host_prefix) {
+        primary_uri.replacen(&host_prefix, &format!("//{}{}.", account, SECONDARY_SUFFIX), 1)
+    } else {
+        format!("{}{}", primary_uri, SECONDARY_SUFFIX)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_secondary_uri() {
+        assert_eq!(
+            secondary_uri("https://account.blob.core.windows.net", "account"),
+            "https://account-secondary.blob.core.windows.net"
+        );
+        assert_eq!(
+            secondary_uri("http://127.0.0.1:10000/devstoreaccount1", "devstoreaccount1"),
+            "http://127.0.0.1:10000/devstoreaccount1-secondary"
+        );
+    }
+}
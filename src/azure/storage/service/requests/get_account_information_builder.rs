@@ -0,0 +1,154 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, TimeoutOption, TimeoutSupport,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use crate::azure::storage::service::responses::GetAccountInformationResponse;
+use crate::azure::storage::service::{perform_service_request, StorageService, ACCOUNT_INFORMATION_VERSION};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+#[derive(Debug, Clone)]
+pub struct GetAccountInformationBuilder<'a> {
+    client: &'a Client,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> GetAccountInformationBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> GetAccountInformationBuilder<'a> {
+        GetAccountInformationBuilder {
+            client,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a> ClientRequired<'a> for GetAccountInformationBuilder<'a> {
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> TimeoutOption for GetAccountInformationBuilder<'a> {
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a> ClientRequestIdOption<'a> for GetAccountInformationBuilder<'a> {
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a> TimeoutSupport for GetAccountInformationBuilder<'a> {
+    type O = GetAccountInformationBuilder<'a>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        GetAccountInformationBuilder {
+            client: self.client,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a> ClientRequestIdSupport<'a> for GetAccountInformationBuilder<'a> {
+    type O = GetAccountInformationBuilder<'a>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        GetAccountInformationBuilder {
+            client: self.client,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a> ExtraHeadersOption<'a> for GetAccountInformationBuilder<'a> {
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a> ExtraHeadersSupport<'a> for GetAccountInformationBuilder<'a> {
+    type O = GetAccountInformationBuilder<'a>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a> ApiVersionOption<'a> for GetAccountInformationBuilder<'a> {
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a> ApiVersionSupport<'a> for GetAccountInformationBuilder<'a> {
+    type O = GetAccountInformationBuilder<'a>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetAccountInformationBuilder {
+            client: self.client,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a> GetAccountInformationBuilder<'a> {}
+impl<'a> GetAccountInformationBuilder<'a> {
+    pub fn finalize(self) -> impl Future<Item = GetAccountInformationResponse, Error = AzureError> {
+        let mut params = "restype=account&comp=properties".to_owned();
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            params = format!("{}&{}", params, timeout);
+        }
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(ACCOUNT_INFORMATION_VERSION));
+        let req = api_version.and_then(|api_version| {
+            perform_service_request(
+                &self,
+                StorageService::Blob,
+                false,
+                &params,
+                &Method::GET,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                None,
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::OK))
+            .and_then(|(headers, _body)| done(GetAccountInformationResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetAccountInformationResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
@@ -0,0 +1,164 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, TimeoutOption, TimeoutSupport,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use crate::azure::storage::service::responses::GetServiceStatsResponse;
+use crate::azure::storage::service::{perform_service_request, StorageService, SERVICE_PROPERTIES_VERSION};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+#[derive(Debug, Clone)]
+pub struct GetServiceStatsBuilder<'a> {
+    client: &'a Client,
+    storage_service: StorageService,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> GetServiceStatsBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, storage_service: StorageService) -> GetServiceStatsBuilder<'a> {
+        GetServiceStatsBuilder {
+            client,
+            storage_service,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a> ClientRequired<'a> for GetServiceStatsBuilder<'a> {
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> TimeoutOption for GetServiceStatsBuilder<'a> {
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a> ClientRequestIdOption<'a> for GetServiceStatsBuilder<'a> {
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a> TimeoutSupport for GetServiceStatsBuilder<'a> {
+    type O = GetServiceStatsBuilder<'a>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        GetServiceStatsBuilder {
+            client: self.client,
+            storage_service: self.storage_service,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a> ClientRequestIdSupport<'a> for GetServiceStatsBuilder<'a> {
+    type O = GetServiceStatsBuilder<'a>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        GetServiceStatsBuilder {
+            client: self.client,
+            storage_service: self.storage_service,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a> ExtraHeadersOption<'a> for GetServiceStatsBuilder<'a> {
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a> ExtraHeadersSupport<'a> for GetServiceStatsBuilder<'a> {
+    type O = GetServiceStatsBuilder<'a>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a> ApiVersionOption<'a> for GetServiceStatsBuilder<'a> {
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a> ApiVersionSupport<'a> for GetServiceStatsBuilder<'a> {
+    type O = GetServiceStatsBuilder<'a>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetServiceStatsBuilder {
+            client: self.client,
+            storage_service: self.storage_service,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a> GetServiceStatsBuilder<'a> {}
+impl<'a> GetServiceStatsBuilder<'a> {
+    pub fn storage_service(&self) -> StorageService {
+        self.storage_service
+    }
+
+    // the statistics are only served by the secondary endpoint
+    pub fn finalize(self) -> impl Future<Item = GetServiceStatsResponse, Error = AzureError> {
+        let mut params = "restype=service&comp=stats".to_owned();
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            params = format!("{}&{}", params, timeout);
+        }
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(SERVICE_PROPERTIES_VERSION));
+        let req = api_version.and_then(|api_version| {
+            perform_service_request(
+                &self,
+                self.storage_service,
+                true,
+                &params,
+                &Method::GET,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                None,
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body_as_string(response, StatusCode::OK))
+            .and_then(move |(headers, body)| done(GetServiceStatsResponse::from_response(&headers, &body)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetServiceStatsResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
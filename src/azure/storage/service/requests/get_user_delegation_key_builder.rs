@@ -0,0 +1,269 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body_as_string, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use crate::azure::storage::service::responses::GetUserDelegationKeyResponse;
+use crate::azure::storage::service::{
+    perform_service_request, sas_time, ExpiryRequired, ExpirySupport, StartOption, StartSupport, StorageService,
+    USER_DELEGATION_KEY_VERSION,
+};
+use chrono::{DateTime, Utc};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    client: &'a Client,
+    p_expiry: PhantomData<ExpirySet>,
+    expiry: Option<DateTime<Utc>>,
+    start: Option<DateTime<Utc>>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> GetUserDelegationKeyBuilder<'a, No> {
+    pub(crate) fn new(client: &'a Client) -> GetUserDelegationKeyBuilder<'a, No> {
+        GetUserDelegationKeyBuilder {
+            client,
+            p_expiry: PhantomData {},
+            expiry: None,
+            start: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, ExpirySet> ClientRequired<'a> for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> ExpiryRequired for GetUserDelegationKeyBuilder<'a, Yes> {
+    fn expiry(&self) -> DateTime<Utc> {
+        self.expiry.unwrap()
+    }
+}
+
+impl<'a, ExpirySet> StartOption for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    fn start(&self) -> Option<DateTime<Utc>> {
+        self.start
+    }
+}
+
+impl<'a, ExpirySet> TimeoutOption for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, ExpirySet> ClientRequestIdOption<'a> for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, ExpirySet> ExpirySupport for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    type O = GetUserDelegationKeyBuilder<'a, Yes>;
+
+    fn with_expiry(self, expiry: DateTime<Utc>) -> Self::O {
+        GetUserDelegationKeyBuilder {
+            client: self.client,
+            p_expiry: PhantomData {},
+            expiry: Some(expiry),
+            start: self.start,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ExpirySet> StartSupport for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    type O = GetUserDelegationKeyBuilder<'a, ExpirySet>;
+
+    fn with_start(self, start: DateTime<Utc>) -> Self::O {
+        GetUserDelegationKeyBuilder {
+            client: self.client,
+            p_expiry: PhantomData {},
+            expiry: self.expiry,
+            start: Some(start),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ExpirySet> TimeoutSupport for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    type O = GetUserDelegationKeyBuilder<'a, ExpirySet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        GetUserDelegationKeyBuilder {
+            client: self.client,
+            p_expiry: PhantomData {},
+            expiry: self.expiry,
+            start: self.start,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ExpirySet> ClientRequestIdSupport<'a> for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    type O = GetUserDelegationKeyBuilder<'a, ExpirySet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        GetUserDelegationKeyBuilder {
+            client: self.client,
+            p_expiry: PhantomData {},
+            expiry: self.expiry,
+            start: self.start,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, ExpirySet> ExtraHeadersOption<'a> for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, ExpirySet> ExtraHeadersSupport<'a> for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    type O = GetUserDelegationKeyBuilder<'a, ExpirySet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, ExpirySet> ApiVersionOption<'a> for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, ExpirySet> ApiVersionSupport<'a> for GetUserDelegationKeyBuilder<'a, ExpirySet>
+where
+    ExpirySet: ToAssign,
+{
+    type O = GetUserDelegationKeyBuilder<'a, ExpirySet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        GetUserDelegationKeyBuilder {
+            client: self.client,
+            p_expiry: PhantomData {},
+            expiry: self.expiry,
+            start: self.start,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, ExpirySet> GetUserDelegationKeyBuilder<'a, ExpirySet> where ExpirySet: ToAssign {}
+impl<'a> GetUserDelegationKeyBuilder<'a, Yes> {
+    /// Only Azure AD authentication is accepted: the client must be
+    /// created with `Client::azure_bearer_token`. The expiry must be
+    /// within seven days.
+    pub fn finalize(self) -> impl Future<Item = GetUserDelegationKeyResponse, Error = AzureError> {
+        let mut params = "restype=service&comp=userdelegationkey".to_owned();
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            params = format!("{}&{}", params, timeout);
+        }
+
+        let mut xml = "<?xml version=\"1.0\" encoding=\"utf-8\"?><KeyInfo>".to_owned();
+        if let Some(start) = self.start() {
+            xml.push_str(&format!("<Start>{}</Start>", sas_time(&start)));
+        }
+        xml.push_str(&format!("<Expiry>{}</Expiry></KeyInfo>", sas_time(&self.expiry())));
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(USER_DELEGATION_KEY_VERSION));
+        let req = api_version.and_then(|api_version| {
+            perform_service_request(
+                &self,
+                StorageService::Blob,
+                false,
+                &params,
+                &Method::POST,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(xml.as_bytes()),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body_as_string(response, StatusCode::OK))
+            .and_then(move |(headers, body)| done(GetUserDelegationKeyResponse::from_response(&headers, &body)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<GetUserDelegationKeyResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
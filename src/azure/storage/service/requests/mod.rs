@@ -0,0 +1,10 @@
+mod get_account_information_builder;
+mod get_service_properties_builder;
+mod get_service_stats_builder;
+mod get_user_delegation_key_builder;
+mod set_service_properties_builder;
+pub use self::get_account_information_builder::GetAccountInformationBuilder;
+pub use self::get_service_properties_builder::GetServicePropertiesBuilder;
+pub use self::get_service_stats_builder::GetServiceStatsBuilder;
+pub use self::get_user_delegation_key_builder::GetUserDelegationKeyBuilder;
+pub use self::set_service_properties_builder::SetServicePropertiesBuilder;
@@ -0,0 +1,244 @@
+use crate::azure::core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::azure::core::{
+    ApiVersionOption, ApiVersionSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption,
+    ExtraHeadersSupport, No, TimeoutOption, TimeoutSupport, ToAssign, Yes,
+};
+use crate::azure::storage::client::Client;
+use crate::azure::storage::rest_client::HEADER_VERSION;
+use crate::azure::storage::service::responses::SetServicePropertiesResponse;
+use crate::azure::storage::service::{
+    perform_service_request, StorageService, StorageServiceProperties, StorageServicePropertiesRequired, StorageServicePropertiesSupport,
+    SERVICE_PROPERTIES_VERSION,
+};
+use futures::future::done;
+use futures::prelude::*;
+use hyper::{Method, StatusCode};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    client: &'a Client,
+    storage_service: StorageService,
+    p_storage_service_properties: PhantomData<StorageServicePropertiesSet>,
+    storage_service_properties: Option<&'a StorageServiceProperties>,
+    timeout: Option<u64>,
+    client_request_id: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> SetServicePropertiesBuilder<'a, No> {
+    pub(crate) fn new(client: &'a Client, storage_service: StorageService) -> SetServicePropertiesBuilder<'a, No> {
+        SetServicePropertiesBuilder {
+            client,
+            storage_service,
+            p_storage_service_properties: PhantomData {},
+            storage_service_properties: None,
+            timeout: None,
+            client_request_id: None,
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> ClientRequired<'a> for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    fn client(&self) -> &'a Client {
+        self.client
+    }
+}
+
+impl<'a> StorageServicePropertiesRequired<'a> for SetServicePropertiesBuilder<'a, Yes> {
+    fn storage_service_properties(&self) -> &'a StorageServiceProperties {
+        self.storage_service_properties.unwrap()
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> TimeoutOption for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> ClientRequestIdOption<'a> for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> StorageServicePropertiesSupport<'a> for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    type O = SetServicePropertiesBuilder<'a, Yes>;
+
+    fn with_storage_service_properties(self, storage_service_properties: &'a StorageServiceProperties) -> Self::O {
+        SetServicePropertiesBuilder {
+            client: self.client,
+            storage_service: self.storage_service,
+            p_storage_service_properties: PhantomData {},
+            storage_service_properties: Some(storage_service_properties),
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> TimeoutSupport for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    type O = SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>;
+
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        SetServicePropertiesBuilder {
+            client: self.client,
+            storage_service: self.storage_service,
+            p_storage_service_properties: PhantomData {},
+            storage_service_properties: self.storage_service_properties,
+            timeout: Some(timeout),
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> ClientRequestIdSupport<'a> for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    type O = SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>;
+
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        SetServicePropertiesBuilder {
+            client: self.client,
+            storage_service: self.storage_service,
+            p_storage_service_properties: PhantomData {},
+            storage_service_properties: self.storage_service_properties,
+            timeout: self.timeout,
+            client_request_id: Some(client_request_id),
+            extra_headers: self.extra_headers,
+            api_version: self.api_version,
+        }
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> ExtraHeadersOption<'a> for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> ExtraHeadersSupport<'a> for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    type O = SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> ApiVersionOption<'a> for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> ApiVersionSupport<'a> for SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    type O = SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        SetServicePropertiesBuilder {
+            client: self.client,
+            storage_service: self.storage_service,
+            p_storage_service_properties: PhantomData {},
+            storage_service_properties: self.storage_service_properties,
+            timeout: self.timeout,
+            client_request_id: self.client_request_id,
+            extra_headers: self.extra_headers,
+            api_version: Some(api_version),
+        }
+    }
+}
+
+// methods callable regardless
+impl<'a, StorageServicePropertiesSet> SetServicePropertiesBuilder<'a, StorageServicePropertiesSet> where
+    StorageServicePropertiesSet: ToAssign
+{
+}
+impl<'a> SetServicePropertiesBuilder<'a, Yes> {
+    pub fn finalize(self) -> impl Future<Item = SetServicePropertiesResponse, Error = AzureError> {
+        let mut params = "restype=service&comp=properties".to_owned();
+
+        if let Some(timeout) = TimeoutOption::to_uri_parameter(&self) {
+            params = format!("{}&{}", params, timeout);
+        }
+
+        let xml = self.storage_service_properties().to_xml();
+
+        let api_version = ApiVersionOption::required_api_version(&self, self.client(), Some(SERVICE_PROPERTIES_VERSION));
+        let req = api_version.and_then(|api_version| {
+            perform_service_request(
+                &self,
+                self.storage_service,
+                false,
+                &params,
+                &Method::PUT,
+                |ref mut request| {
+                    ClientRequestIdOption::add_header(&self, request);
+                    ExtraHeadersOption::add_header(&self, request);
+                    if let Some(api_version) = api_version {
+                        request.header(HEADER_VERSION, api_version);
+                    }
+                },
+                Some(xml.as_bytes()),
+            )
+        });
+
+        done(req)
+            .from_err()
+            .and_then(move |response| check_status_extract_headers_and_body(response, StatusCode::ACCEPTED))
+            .and_then(move |(headers, _body)| done(SetServicePropertiesResponse::from_headers(&headers)))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<SetServicePropertiesResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
+
+impl<'a, StorageServicePropertiesSet> SetServicePropertiesBuilder<'a, StorageServicePropertiesSet>
+where
+    StorageServicePropertiesSet: ToAssign,
+{
+    pub fn storage_service(&self) -> StorageService {
+        self.storage_service
+    }
+}
@@ -0,0 +1,24 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{client_request_id_from_headers_optional, request_id_from_headers, RequestId};
+use crate::azure::storage::service::StorageServiceProperties;
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetServicePropertiesResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub storage_service_properties: StorageServiceProperties,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl GetServicePropertiesResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &str) -> Result<GetServicePropertiesResponse, AzureError> {
+        Ok(GetServicePropertiesResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            storage_service_properties: StorageServiceProperties::from_xml(body)?,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::storage::service::ServiceStats;
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetServiceStatsResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub service_stats: ServiceStats,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl GetServiceStatsResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &str) -> Result<GetServiceStatsResponse, AzureError> {
+        Ok(GetServiceStatsResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            date: date_from_headers(headers)?,
+            service_stats: ServiceStats::from_xml(body)?,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
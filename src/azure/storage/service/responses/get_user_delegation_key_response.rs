@@ -0,0 +1,27 @@
+use crate::azure::core::common_headers::CommonStorageResponseHeaders;
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{client_request_id_from_headers_optional, date_from_headers, request_id_from_headers, RequestId};
+use crate::azure::storage::service::UserDelegationKey;
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetUserDelegationKeyResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub user_delegation_key: UserDelegationKey,
+    pub common_headers: CommonStorageResponseHeaders,
+}
+
+impl GetUserDelegationKeyResponse {
+    pub(crate) fn from_response(headers: &HeaderMap, body: &str) -> Result<GetUserDelegationKeyResponse, AzureError> {
+        Ok(GetUserDelegationKeyResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers)?,
+            date: date_from_headers(headers)?,
+            user_delegation_key: UserDelegationKey::from_xml(body)?,
+            common_headers: CommonStorageResponseHeaders::from_headers(headers)?,
+        })
+    }
+}
@@ -0,0 +1,10 @@
+mod get_account_information_response;
+mod get_service_properties_response;
+mod get_service_stats_response;
+mod get_user_delegation_key_response;
+mod set_service_properties_response;
+pub use self::get_account_information_response::GetAccountInformationResponse;
+pub use self::get_service_properties_response::GetServicePropertiesResponse;
+pub use self::get_service_stats_response::GetServiceStatsResponse;
+pub use self::get_user_delegation_key_response::GetUserDelegationKeyResponse;
+pub use self::set_service_properties_response::SetServicePropertiesResponse;
@@ -0,0 +1,6 @@
+use crate::azure::core::RequestId;
+
+response_from_headers!(SetServicePropertiesResponse ,
+               request_id_from_headers -> request_id: RequestId,
+               client_request_id_from_headers_optional -> client_request_id: Option<String>
+);
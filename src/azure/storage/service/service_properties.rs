@@ -0,0 +1,374 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::parsing::{cast_must, cast_optional, traverse, traverse_single_optional};
+use std::fmt::Write;
+use xml::Element;
+
+/// The properties of the blob, queue or table service of an account, as
+/// read by Get Service Properties and written by Set Service Properties.
+///
+/// On set, the elements left to `None` are not changed by the service.
+/// `default_service_version`, `delete_retention_policy` and
+/// `static_website` are only supported by the blob service.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StorageServiceProperties {
+    pub logging: Option<Logging>,
+    pub hour_metrics: Option<Metrics>,
+    pub minute_metrics: Option<Metrics>,
+    /// `Some(vec![])` removes all the CORS rules.
+    pub cors: Option<Vec<CorsRule>>,
+    pub default_service_version: Option<String>,
+    pub delete_retention_policy: Option<RetentionPolicy>,
+    pub static_website: Option<StaticWebsite>,
+}
+
+/// Storage Analytics logging.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Logging {
+    pub version: String,
+    pub delete: bool,
+    pub read: bool,
+    pub write: bool,
+    pub retention_policy: RetentionPolicy,
+}
+
+/// Storage Analytics metrics, aggregated by hour or by minute.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    pub version: String,
+    pub enabled: bool,
+    /// Whether the metrics include the statistics of each API operation.
+    /// Only meaningful when `enabled` is true.
+    pub include_apis: Option<bool>,
+    pub retention_policy: RetentionPolicy,
+}
+
+/// How many days the logs, the metrics or the deleted blobs are kept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetentionPolicy {
+    pub enabled: bool,
+    pub days: Option<u64>,
+}
+
+impl RetentionPolicy {
+    pub fn disabled() -> RetentionPolicy {
+        RetentionPolicy {
+            enabled: false,
+            days: None,
+        }
+    }
+
+    pub fn days(days: u64) -> RetentionPolicy {
+        RetentionPolicy {
+            enabled: true,
+            days: Some(days),
+        }
+    }
+}
+
+/// A Cross-Origin Resource Sharing rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorsRule {
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub max_age_in_seconds: u64,
+    pub exposed_headers: Vec<String>,
+    pub allowed_headers: Vec<String>,
+}
+
+/// The static website hosting of the blob service: the files of the
+/// `$web` container are served from the web endpoint of the account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticWebsite {
+    pub enabled: bool,
+    pub index_document: Option<String>,
+    pub error_document_404_path: Option<String>,
+}
+
+impl StaticWebsite {
+    /// Serves `index_document` for the directory requests (including the
+    /// root) and, if set, `error_document_404_path` for the missing
+    /// files.
+    pub fn enabled(index_document: &str, error_document_404_path: Option<&str>) -> StaticWebsite {
+        StaticWebsite {
+            enabled: true,
+            index_document: Some(index_document.to_owned()),
+            error_document_404_path: error_document_404_path.map(str::to_owned),
+        }
+    }
+
+    pub fn disabled() -> StaticWebsite {
+        StaticWebsite {
+            enabled: false,
+            index_document: None,
+            error_document_404_path: None,
+        }
+    }
+}
+
+impl StorageServiceProperties {
+    pub fn from_xml(xml: &str) -> Result<StorageServiceProperties, AzureError> {
+        let elem: Element = xml.trim_start_matches('\u{feff}').parse()?;
+
+        let logging = match traverse_single_optional(&elem, &["Logging"])? {
+            Some(node) => Some(Logging {
+                version: cast_must::<String>(node, &["Version"])?,
+                delete: cast_must::<bool>(node, &["Delete"])?,
+                read: cast_must::<bool>(node, &["Read"])?,
+                write: cast_must::<bool>(node, &["Write"])?,
+                retention_policy: RetentionPolicy::from_node(node, "RetentionPolicy")?.unwrap_or_else(RetentionPolicy::disabled),
+            }),
+            None => None,
+        };
+
+        let cors = match traverse_single_optional(&elem, &["Cors"])? {
+            Some(node) => Some(
+                traverse(node, &["CorsRule"], true)?
+                    .into_iter()
+                    .map(CorsRule::from_node)
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            None => None,
+        };
+
+        let static_website = match traverse_single_optional(&elem, &["StaticWebsite"])? {
+            Some(node) => Some(StaticWebsite {
+                enabled: cast_must::<bool>(node, &["Enabled"])?,
+                index_document: cast_optional::<String>(node, &["IndexDocument"])?,
+                error_document_404_path: cast_optional::<String>(node, &["ErrorDocument404Path"])?,
+            }),
+            None => None,
+        };
+
+        Ok(StorageServiceProperties {
+            logging,
+            hour_metrics: Metrics::from_node(&elem, "HourMetrics")?,
+            minute_metrics: Metrics::from_node(&elem, "MinuteMetrics")?,
+            cors,
+            default_service_version: cast_optional::<String>(&elem, &["DefaultServiceVersion"])?,
+            delete_retention_policy: RetentionPolicy::from_node(&elem, "DeleteRetentionPolicy")?,
+            static_website,
+        })
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut s = String::new();
+        s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<StorageServiceProperties>\n");
+
+        if let Some(logging) = &self.logging {
+            s.push_str("\t<Logging>\n");
+            writeln!(
+                s,
+                "\t\t<Version>{}</Version>\n\t\t<Delete>{}</Delete>\n\t\t<Read>{}</Read>\n\t\t<Write>{}</Write>",
+                logging.version, logging.delete, logging.read, logging.write
+            )
+            .unwrap();
+            logging.retention_policy.write_xml(&mut s, "RetentionPolicy", "\t\t");
+            s.push_str("\t</Logging>\n");
+        }
+        if let Some(hour_metrics) = &self.hour_metrics {
+            hour_metrics.write_xml(&mut s, "HourMetrics");
+        }
+        if let Some(minute_metrics) = &self.minute_metrics {
+            minute_metrics.write_xml(&mut s, "MinuteMetrics");
+        }
+        if let Some(cors) = &self.cors {
+            s.push_str("\t<Cors>\n");
+            for rule in cors {
+                write!(
+                    s,
+                    "\t\t<CorsRule>\n\t\t\t<AllowedOrigins>{}</AllowedOrigins>\n\t\t\t<AllowedMethods>{}</AllowedMethods>\n\
+                     \t\t\t<MaxAgeInSeconds>{}</MaxAgeInSeconds>\n\t\t\t<ExposedHeaders>{}</ExposedHeaders>\n\
+                     \t\t\t<AllowedHeaders>{}</AllowedHeaders>\n\t\t</CorsRule>\n",
+                    rule.allowed_origins.join(","),
+                    rule.allowed_methods.join(","),
+                    rule.max_age_in_seconds,
+                    rule.exposed_headers.join(","),
+                    rule.allowed_headers.join(",")
+                )
+                .unwrap();
+            }
+            s.push_str("\t</Cors>\n");
+        }
+        if let Some(default_service_version) = &self.default_service_version {
+            writeln!(s, "\t<DefaultServiceVersion>{}</DefaultServiceVersion>", default_service_version).unwrap();
+        }
+        if let Some(delete_retention_policy) = &self.delete_retention_policy {
+            delete_retention_policy.write_xml(&mut s, "DeleteRetentionPolicy", "\t");
+        }
+        if let Some(static_website) = &self.static_website {
+            writeln!(s, "\t<StaticWebsite>\n\t\t<Enabled>{}</Enabled>", static_website.enabled).unwrap();
+            if let Some(index_document) = &static_website.index_document {
+                writeln!(s, "\t\t<IndexDocument>{}</IndexDocument>", index_document).unwrap();
+            }
+            if let Some(error_document_404_path) = &static_website.error_document_404_path {
+                writeln!(s, "\t\t<ErrorDocument404Path>{}</ErrorDocument404Path>", error_document_404_path).unwrap();
+            }
+            s.push_str("\t</StaticWebsite>\n");
+        }
+
+        s.push_str("</StorageServiceProperties>");
+        s
+    }
+}
+
+impl Metrics {
+    fn from_node(elem: &Element, name: &str) -> Result<Option<Metrics>, AzureError> {
+        let node = match traverse_single_optional(elem, &[name])? {
+            Some(node) => node,
+            None => return Ok(None),
+        };
+
+        Ok(Some(Metrics {
+            version: cast_must::<String>(node, &["Version"])?,
+            enabled: cast_must::<bool>(node, &["Enabled"])?,
+            include_apis: cast_optional::<bool>(node, &["IncludeAPIs"])?,
+            retention_policy: RetentionPolicy::from_node(node, "RetentionPolicy")?.unwrap_or_else(RetentionPolicy::disabled),
+        }))
+    }
+
+    fn write_xml(&self, s: &mut String, name: &str) {
+        writeln!(
+            s,
+            "\t<{}>\n\t\t<Version>{}</Version>\n\t\t<Enabled>{}</Enabled>",
+            name, self.version, self.enabled
+        )
+        .unwrap();
+        // the service rejects IncludeAPIs when the metrics are disabled
+        if let (true, Some(include_apis)) = (self.enabled, self.include_apis) {
+            writeln!(s, "\t\t<IncludeAPIs>{}</IncludeAPIs>", include_apis).unwrap();
+        }
+        self.retention_policy.write_xml(s, "RetentionPolicy", "\t\t");
+        writeln!(s, "\t</{}>", name).unwrap();
+    }
+}
+
+impl RetentionPolicy {
+    fn from_node(elem: &Element, name: &str) -> Result<Option<RetentionPolicy>, AzureError> {
+        let node = match traverse_single_optional(elem, &[name])? {
+            Some(node) => node,
+            None => return Ok(None),
+        };
+
+        Ok(Some(RetentionPolicy {
+            enabled: cast_must::<bool>(node, &["Enabled"])?,
+            days: cast_optional::<u64>(node, &["Days"])?,
+        }))
+    }
+
+    fn write_xml(&self, s: &mut String, name: &str, indent: &str) {
+        writeln!(s, "{}<{}>\n{}\t<Enabled>{}</Enabled>", indent, name, indent, self.enabled).unwrap();
+        if let (true, Some(days)) = (self.enabled, self.days) {
+            writeln!(s, "{}\t<Days>{}</Days>", indent, days).unwrap();
+        }
+        writeln!(s, "{}</{}>", indent, name).unwrap();
+    }
+}
+
+impl CorsRule {
+    fn from_node(node: &Element) -> Result<CorsRule, AzureError> {
+        let list = |name: &str| -> Result<Vec<String>, AzureError> {
+            Ok(cast_optional::<String>(node, &[name])?
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(|item| item.trim().to_owned())
+                        .filter(|item| !item.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default())
+        };
+
+        Ok(CorsRule {
+            allowed_origins: list("AllowedOrigins")?,
+            allowed_methods: list("AllowedMethods")?,
+            max_age_in_seconds: cast_must::<u64>(node, &["MaxAgeInSeconds"])?,
+            exposed_headers: list("ExposedHeaders")?,
+            allowed_headers: list("AllowedHeaders")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const XML: &str = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<StorageServiceProperties>
+    <Logging>
+        <Version>1.0</Version>
+        <Read>false</Read>
+        <Write>true</Write>
+        <Delete>true</Delete>
+        <RetentionPolicy><Enabled>true</Enabled><Days>7</Days></RetentionPolicy>
+    </Logging>
+    <HourMetrics>
+        <Version>1.0</Version>
+        <Enabled>true</Enabled>
+        <IncludeAPIs>false</IncludeAPIs>
+        <RetentionPolicy><Enabled>true</Enabled><Days>7</Days></RetentionPolicy>
+    </HourMetrics>
+    <MinuteMetrics>
+        <Version>1.0</Version>
+        <Enabled>false</Enabled>
+        <RetentionPolicy><Enabled>false</Enabled></RetentionPolicy>
+    </MinuteMetrics>
+    <Cors>
+        <CorsRule>
+            <AllowedMethods>GET,PUT</AllowedMethods>
+            <AllowedOrigins>http://www.contoso.com,http://www.fabrikam.com</AllowedOrigins>
+            <AllowedHeaders>x-ms-meta-*</AllowedHeaders>
+            <ExposedHeaders />
+            <MaxAgeInSeconds>200</MaxAgeInSeconds>
+        </CorsRule>
+    </Cors>
+    <DeleteRetentionPolicy><Enabled>true</Enabled><Days>5</Days></DeleteRetentionPolicy>
+    <StaticWebsite><Enabled>true</Enabled><IndexDocument>index.html</IndexDocument></StaticWebsite>
+</StorageServiceProperties>";
+
+    #[test]
+    fn test_parse() {
+        let properties = StorageServiceProperties::from_xml(XML).unwrap();
+
+        let logging = properties.logging.as_ref().unwrap();
+        assert!(!logging.read && logging.write && logging.delete);
+        assert_eq!(logging.retention_policy, RetentionPolicy::days(7));
+
+        assert_eq!(properties.hour_metrics.as_ref().unwrap().include_apis, Some(false));
+        let minute_metrics = properties.minute_metrics.as_ref().unwrap();
+        assert!(!minute_metrics.enabled);
+        assert_eq!(minute_metrics.retention_policy, RetentionPolicy::disabled());
+
+        let cors = properties.cors.as_ref().unwrap();
+        assert_eq!(cors.len(), 1);
+        assert_eq!(cors[0].allowed_origins, vec!["http://www.contoso.com", "http://www.fabrikam.com"]);
+        assert_eq!(cors[0].allowed_methods, vec!["GET", "PUT"]);
+        assert!(cors[0].exposed_headers.is_empty());
+        assert_eq!(cors[0].max_age_in_seconds, 200);
+
+        assert_eq!(properties.default_service_version, None);
+        assert_eq!(properties.delete_retention_policy, Some(RetentionPolicy::days(5)));
+        assert_eq!(
+            properties.static_website,
+            Some(StaticWebsite {
+                enabled: true,
+                index_document: Some("index.html".to_owned()),
+                error_document_404_path: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_xml_roundtrip() {
+        let properties = StorageServiceProperties::from_xml(XML).unwrap();
+        assert_eq!(StorageServiceProperties::from_xml(&properties.to_xml()).unwrap(), properties);
+
+        let only_cors = StorageServiceProperties {
+            cors: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert_eq!(
+            only_cors.to_xml(),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<StorageServiceProperties>\n\t<Cors>\n\t</Cors>\n</StorageServiceProperties>"
+        );
+    }
+}
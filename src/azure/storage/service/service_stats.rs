@@ -0,0 +1,81 @@
+use crate::azure::core::{
+    enumerations,
+    errors::{AzureError, TraversingError},
+    parsing::{cast_must, cast_optional, from_azure_time, FromStringOptional},
+};
+use chrono::{DateTime, Duration, Utc};
+use std::{fmt, str::FromStr};
+use xml::Element;
+
+create_enum!(
+    GeoReplicationStatus,
+    (Live, "live"),
+    (Bootstrap, "bootstrap"),
+    (Unavailable, "unavailable")
+);
+
+/// The replication statistics of a service, as returned by the secondary
+/// endpoint of a read-access geo-redundant account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceStats {
+    pub geo_replication: GeoReplication,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoReplication {
+    pub status: GeoReplicationStatus,
+    /// The writes to the primary before this time are readable from the
+    /// secondary. Missing while the replication is bootstrapping.
+    pub last_sync_time: Option<DateTime<Utc>>,
+}
+
+impl GeoReplication {
+    /// How far behind the secondary is, as of `now`.
+    pub fn replication_lag(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.last_sync_time.map(|last_sync_time| now - last_sync_time)
+    }
+}
+
+impl ServiceStats {
+    pub fn from_xml(xml: &str) -> Result<ServiceStats, AzureError> {
+        let elem: Element = xml.trim_start_matches('\u{feff}').parse()?;
+
+        let last_sync_time = match cast_optional::<String>(&elem, &["GeoReplication", "LastSyncTime"])? {
+            Some(ref last_sync_time) if !last_sync_time.is_empty() => Some(from_azure_time(last_sync_time)?),
+            _ => None,
+        };
+
+        Ok(ServiceStats {
+            geo_replication: GeoReplication {
+                status: cast_must::<GeoReplicationStatus>(&elem, &["GeoReplication", "Status"])?,
+                last_sync_time,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let xml = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>
+            <StorageServiceStats>
+                <GeoReplication>
+                    <Status>live</Status>
+                    <LastSyncTime>Tue, 15 Oct 2019 09:59:00 GMT</LastSyncTime>
+                </GeoReplication>
+            </StorageServiceStats>";
+
+        let stats = ServiceStats::from_xml(xml).unwrap();
+        assert_eq!(stats.geo_replication.status, GeoReplicationStatus::Live);
+        let now = DateTime::parse_from_rfc3339("2019-10-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(stats.geo_replication.replication_lag(now), Some(Duration::minutes(1)));
+
+        let xml = "<StorageServiceStats><GeoReplication><Status>bootstrap</Status><LastSyncTime /></GeoReplication></StorageServiceStats>";
+        let stats = ServiceStats::from_xml(xml).unwrap();
+        assert_eq!(stats.geo_replication.status, GeoReplicationStatus::Bootstrap);
+        assert_eq!(stats.geo_replication.last_sync_time, None);
+    }
+}
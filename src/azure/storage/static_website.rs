@@ -0,0 +1,169 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{BlobNameSupport, BodySupport, ContainerNameSupport, ContentTypeSupport};
+use crate::azure::storage::client::{Blob, Client};
+use futures::future::{self, Future};
+use futures::stream::{self, Stream};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The container holding the files of the static website of an account.
+pub const WEB_CONTAINER: &str = "$web";
+
+// how many files are uploaded at the same time
+const UPLOAD_CONCURRENCY: usize = 8;
+
+/// The files uploaded by `Container::upload_static_website`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StaticWebsiteUpload {
+    /// The blob names, in upload order.
+    pub blob_names: Vec<String>,
+    pub bytes: u64,
+}
+
+/// The `Content-Type` a browser expects for the file at `path`, from its
+/// extension. Unknown extensions give `application/octet-stream`.
+pub fn content_type_for(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    match &extension as &str {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "application/javascript; charset=utf-8",
+        "json" | "map" => "application/json",
+        "webmanifest" => "application/manifest+json",
+        "txt" | "md" => "text/plain; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "eot" => "application/vnd.ms-fontobject",
+        "wasm" => "application/wasm",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+// The files below `directory`, recursively, with their blob names: the
+// path relative to `directory` with `/` separators.
+pub(crate) fn list_files(directory: &Path) -> Result<Vec<(PathBuf, String)>, AzureError> {
+    let mut files = Vec::new();
+    let mut directories = vec![directory.to_owned()];
+    while let Some(current) = directories.pop() {
+        let mut entries = fs::read_dir(&current)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                directories.push(path);
+                continue;
+            }
+            let blob_name = path
+                .strip_prefix(directory)
+                .unwrap_or(&path)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((path, blob_name));
+        }
+    }
+    Ok(files)
+}
+
+// Each file is read when its upload starts, so that at most
+// UPLOAD_CONCURRENCY files are held in memory.
+pub(crate) fn upload_static_website(client: &Client, directory: &Path) -> impl Future<Item = StaticWebsiteUpload, Error = AzureError> {
+    let client = client.clone();
+    future::result(list_files(directory))
+        .and_then(move |files| {
+            stream::iter_ok(files)
+                .map(move |(path, blob_name)| {
+                    let client = client.clone();
+                    future::result(fs::read(&path).map_err(AzureError::from)).and_then(move |body| {
+                        let length = body.len() as u64;
+                        client
+                            .put_block_blob()
+                            .with_container_name(WEB_CONTAINER)
+                            .with_blob_name(&blob_name)
+                            .with_content_type(content_type_for(&path))
+                            .with_body(&body)
+                            .finalize()
+                            .map(move |_| (blob_name, length))
+                    })
+                })
+                .buffered(UPLOAD_CONCURRENCY)
+                .fold(StaticWebsiteUpload::default(), |mut upload, (blob_name, length)| {
+                    upload.blob_names.push(blob_name);
+                    upload.bytes += length;
+                    Ok::<_, AzureError>(upload)
+                })
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::storage::client::Container;
+    use hyper::StatusCode;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_content_type_for() {
+        assert_eq!(content_type_for(Path::new("index.HTML")), "text/html; charset=utf-8");
+        assert_eq!(content_type_for(Path::new("img/logo.svg")), "image/svg+xml");
+        assert_eq!(content_type_for(Path::new("LICENSE")), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_upload_static_website() {
+        let directory = std::env::temp_dir().join(format!("static_website_{}", std::process::id()));
+        fs::create_dir_all(directory.join("css")).unwrap();
+        fs::write(directory.join("index.html"), "<html></html>").unwrap();
+        fs::write(directory.join("css").join("site.css"), "body {}").unwrap();
+
+        let uploaded = [
+            ("etag", "\"0x8D75157AA1C4E03\""),
+            ("last-modified", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("content-md5", "1B2M2Y8AsgTpgAmY7PhCfg=="),
+            ("x-ms-request-id", "6ee5ea5b-0003-0040-4b77-2e5c7f000000"),
+            ("date", "Tue, 15 Oct 2019 10:00:00 GMT"),
+            ("x-ms-request-server-encrypted", "true"),
+        ];
+        let transport = Arc::new(
+            ReplayTransport::new()
+                .with_response(StatusCode::CREATED, &uploaded, b"")
+                .with_response(StatusCode::CREATED, &uploaded, b""),
+        );
+        let client = Client::azure("account", "YWNjb3VudGtleQ==").unwrap().with_http_client(transport.clone());
+        let upload = client.upload_static_website(&directory).wait();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let upload = upload.unwrap();
+        assert_eq!(upload.blob_names, vec!["index.html", "css/site.css"]);
+        assert_eq!(upload.bytes, 20);
+        let requests = transport.requests();
+        assert_eq!(requests[0].uri, "https://account.blob.core.windows.net/$web/index.html");
+        assert_eq!(requests[0].headers["content-type"], "text/html; charset=utf-8");
+        assert_eq!(requests[1].uri, "https://account.blob.core.windows.net/$web/css%2Fsite.css");
+        assert_eq!(requests[1].headers["content-type"], "text/css; charset=utf-8");
+    }
+}
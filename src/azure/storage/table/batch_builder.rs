@@ -0,0 +1,476 @@
+use super::{entity_path, get_json_mime_nometadata, TableService};
+use crate::azure::core::errors::{check_status_extract_body, AzureError};
+use crate::azure::core::{ApiVersionOption, ApiVersionSupport, ExtraHeadersOption, ExtraHeadersSupport};
+use crate::azure::storage::multipart::{boundary, find_header, split_headers, split_parts, split_status_line};
+use crate::azure::storage::rest_client::ServiceType;
+use futures::future::*;
+use hyper::{header, Method, StatusCode};
+use serde::Serialize;
+use serde_json;
+use uuid::Uuid;
+
+/// Azure rejects batches with more than 100 operations.
+pub const MAX_BATCH_OPERATIONS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+enum BatchOperation {
+    Insert(String),
+    Update(String, String, Option<String>),
+    Merge(String, String, Option<String>),
+    Delete(String, Option<String>),
+}
+
+/// Entity group transaction: a set of insert, update, merge and delete
+/// operations on the entities of a single partition, executed
+/// atomically. Either every operation succeeds or none is applied.
+///
+/// Update, merge and delete accept the ETag the entity must match (as
+/// returned by a previous read); without it the operation is
+/// unconditional (`If-Match: *`).
+#[derive(Clone)]
+pub struct BatchBuilder<'a> {
+    service: &'a TableService,
+    table_name: &'a str,
+    partition_key: &'a str,
+    operations: Vec<BatchOperation>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> BatchBuilder<'a> {
+    pub(crate) fn new(service: &'a TableService, table_name: &'a str, partition_key: &'a str) -> BatchBuilder<'a> {
+        BatchBuilder {
+            service,
+            table_name,
+            partition_key,
+            operations: Vec::new(),
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+
+    pub fn table_name(&self) -> &'a str {
+        self.table_name
+    }
+
+    pub fn partition_key(&self) -> &'a str {
+        self.partition_key
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn insert<T: Serialize>(mut self, entity: &T) -> Result<Self, AzureError> {
+        let body = self.serialize(entity)?;
+        self.operations.push(BatchOperation::Insert(body));
+        Ok(self)
+    }
+
+    pub fn update<T: Serialize>(mut self, row_key: &str, entity: &T, etag: Option<&str>) -> Result<Self, AzureError> {
+        let body = self.serialize(entity)?;
+        self.operations
+            .push(BatchOperation::Update(row_key.to_owned(), body, etag.map(|e| e.to_owned())));
+        Ok(self)
+    }
+
+    pub fn merge<T: Serialize>(mut self, row_key: &str, entity: &T, etag: Option<&str>) -> Result<Self, AzureError> {
+        let body = self.serialize(entity)?;
+        self.operations
+            .push(BatchOperation::Merge(row_key.to_owned(), body, etag.map(|e| e.to_owned())));
+        Ok(self)
+    }
+
+    pub fn delete(mut self, row_key: &str, etag: Option<&str>) -> Self {
+        self.operations
+            .push(BatchOperation::Delete(row_key.to_owned(), etag.map(|e| e.to_owned())));
+        self
+    }
+
+    // the transaction cannot span partitions: catch the mistake here
+    // instead of waiting for the (less clear) Azure error
+    fn serialize<T: Serialize>(&self, entity: &T) -> Result<String, AzureError> {
+        let value = serde_json::to_value(entity)?;
+        if let Some(partition_key) = value.get("PartitionKey").and_then(|pk| pk.as_str()) {
+            if partition_key != self.partition_key {
+                return Err(AzureError::GenericErrorWithText(format!(
+                    "entity partition key {} does not match the batch partition key {}",
+                    partition_key, self.partition_key
+                )));
+            }
+        }
+        Ok(value.to_string())
+    }
+
+    /// Sends the batch. The future fails only if the batch as a whole is
+    /// rejected: the outcome of the single operations is in the returned
+    /// `BatchResponse`.
+    pub fn finalize(self) -> impl Future<Item = BatchResponse, Error = AzureError> {
+        let payload = if self.operations.is_empty() || self.operations.len() > MAX_BATCH_OPERATIONS {
+            Err(AzureError::GenericErrorWithText(format!(
+                "a batch must contain between 1 and {} operations, found {}",
+                MAX_BATCH_OPERATIONS,
+                self.operations.len()
+            )))
+        } else {
+            let batch_boundary = format!("batch_{}", Uuid::new_v4());
+            let changeset_boundary = format!("changeset_{}", Uuid::new_v4());
+            let payload = generate_payload(
+                &self.service.client.get_uri_prefix(ServiceType::Table),
+                self.table_name,
+                self.partition_key,
+                &self.operations,
+                &batch_boundary,
+                &changeset_boundary,
+            );
+            Ok((payload, format!("multipart/mixed; boundary={}", batch_boundary)))
+        };
+
+        let req = payload.and_then(|(payload, content_type)| {
+            ApiVersionOption::required_api_version(&self, &self.service.client, None)?;
+            self.service.request("$batch", &Method::POST, Some(&payload), |ref mut request| {
+                request.header(header::CONTENT_TYPE, &content_type as &str);
+                ApiVersionOption::add_header(&self, request);
+                ExtraHeadersOption::add_header(&self, request);
+            })
+        });
+
+        done(req).from_err().and_then(move |future_response| {
+            check_status_extract_body(future_response, StatusCode::ACCEPTED).and_then(move |body| done(parse_batch_response(&body)))
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub fn finalize_async(self) -> impl std::future::Future<Output = Result<BatchResponse, AzureError>> {
+        crate::azure::core::async_compat::into_std_future(self.finalize())
+    }
+}
+
+impl<'a> ExtraHeadersOption<'a> for BatchBuilder<'a> {
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a> ExtraHeadersSupport<'a> for BatchBuilder<'a> {
+    type O = Self;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a> ApiVersionOption<'a> for BatchBuilder<'a> {
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a> ApiVersionSupport<'a> for BatchBuilder<'a> {
+    type O = Self;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        BatchBuilder {
+            api_version: Some(api_version),
+            ..self
+        }
+    }
+}
+
+fn generate_payload(
+    uri_prefix: &str,
+    table_name: &str,
+    partition_key: &str,
+    operations: &[BatchOperation],
+    batch_boundary: &str,
+    changeset_boundary: &str,
+) -> String {
+    let mut payload = format!(
+        "--{}\r\nContent-Type: multipart/mixed; boundary={}\r\n\r\n",
+        batch_boundary, changeset_boundary
+    );
+
+    for (content_id, operation) in operations.iter().enumerate() {
+        let (method, path, body, etag) = match operation {
+            BatchOperation::Insert(body) => ("POST", table_name.to_owned(), Some(body), None),
+            BatchOperation::Update(row_key, body, etag) => (
+                "PUT",
+                entity_path(table_name, partition_key, row_key),
+                Some(body),
+                Some(etag.as_ref().map(|e| e as &str).unwrap_or("*")),
+            ),
+            BatchOperation::Merge(row_key, body, etag) => (
+                "MERGE",
+                entity_path(table_name, partition_key, row_key),
+                Some(body),
+                Some(etag.as_ref().map(|e| e as &str).unwrap_or("*")),
+            ),
+            BatchOperation::Delete(row_key, etag) => (
+                "DELETE",
+                entity_path(table_name, partition_key, row_key),
+                None,
+                Some(etag.as_ref().map(|e| e as &str).unwrap_or("*")),
+            ),
+        };
+
+        payload.push_str(&format!(
+            "--{}\r\nContent-Type: application/http\r\nContent-Transfer-Encoding: binary\r\n\r\n",
+            changeset_boundary
+        ));
+        payload.push_str(&format!("{} {}{} HTTP/1.1\r\n", method, uri_prefix, path));
+        payload.push_str(&format!("Content-ID: {}\r\n", content_id));
+        payload.push_str(&format!("Accept: {}\r\n", get_json_mime_nometadata()));
+        if let Some(etag) = etag {
+            payload.push_str(&format!("If-Match: {}\r\n", etag));
+        }
+        match body {
+            Some(body) => {
+                payload.push_str("Content-Type: application/json\r\n\r\n");
+                payload.push_str(body);
+                payload.push_str("\r\n");
+            }
+            None => payload.push_str("\r\n"),
+        }
+    }
+
+    payload.push_str(&format!("--{}--\r\n--{}--\r\n", changeset_boundary, batch_boundary));
+    payload
+}
+
+/// The outcome of a batch. If the transaction succeeded there is a
+/// response for each operation, in the same order they were added to the
+/// `BatchBuilder`. If it failed nothing has been applied and Azure only
+/// returns the response of the failing operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResponse {
+    pub operations: Vec<BatchOperationResponse>,
+}
+
+impl BatchResponse {
+    pub fn is_successful(&self) -> bool {
+        self.error().is_none()
+    }
+
+    pub fn error(&self) -> Option<&BatchOperationError> {
+        self.operations.iter().filter_map(|operation| operation.error.as_ref()).next()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOperationResponse {
+    pub status_code: StatusCode,
+    /// The new ETag of the inserted, updated or merged entity.
+    pub etag: Option<String>,
+    pub error: Option<BatchOperationError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOperationError {
+    /// The index of the failing operation, if reported by Azure.
+    pub operation_index: Option<usize>,
+    pub code: String,
+    pub message: String,
+}
+
+fn parse_operation(part: &str) -> Result<BatchOperationResponse, AzureError> {
+    let (_, http) = split_headers(part);
+    let (status_code, rest) = split_status_line(http)?;
+
+    let (headers, body) = split_headers(rest);
+    let etag = find_header(&headers, "ETag").map(|etag| etag.to_owned());
+
+    let error = if status_code.is_success() {
+        None
+    } else {
+        Some(parse_operation_error(body.trim(), status_code))
+    };
+
+    Ok(BatchOperationResponse { status_code, etag, error })
+}
+
+// The message starts with the index of the failing operation, as in
+// "1:The specified entity already exists.".
+fn parse_operation_error(body: &str, status_code: StatusCode) -> BatchOperationError {
+    let error = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| value.get("odata.error").cloned());
+
+    let code = error
+        .as_ref()
+        .and_then(|error| error.get("code"))
+        .and_then(|code| code.as_str())
+        .map(|code| code.to_owned())
+        .unwrap_or_else(|| status_code.to_string());
+    let message = error
+        .as_ref()
+        .and_then(|error| error.pointer("/message/value"))
+        .and_then(|message| message.as_str())
+        .unwrap_or(body);
+
+    let mut tokens = message.splitn(2, ':');
+    match (tokens.next().map(|index| index.parse::<usize>()), tokens.next()) {
+        (Some(Ok(operation_index)), Some(message)) => BatchOperationError {
+            operation_index: Some(operation_index),
+            code,
+            message: message.to_owned(),
+        },
+        _ => BatchOperationError {
+            operation_index: None,
+            code,
+            message: message.to_owned(),
+        },
+    }
+}
+
+pub(crate) fn parse_batch_response(body: &str) -> Result<BatchResponse, AzureError> {
+    // the first line is the batch delimiter
+    let boundary = boundary(body)?;
+
+    let mut operations = Vec::new();
+
+    for part in split_parts(body, boundary) {
+        let (headers, content) = split_headers(part);
+        let content_type = find_header(&headers, "Content-Type").unwrap_or_default();
+
+        match content_type.find("boundary=") {
+            Some(pos) if content_type.starts_with("multipart/mixed") => {
+                let changeset_boundary = content_type[pos + "boundary=".len()..].trim();
+                for operation in split_parts(content, changeset_boundary) {
+                    operations.push(parse_operation(operation)?);
+                }
+            }
+            _ => operations.push(parse_operation(part)?),
+        }
+    }
+
+    Ok(BatchResponse { operations })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::storage::client::Client;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_extra_headers_and_api_version() {
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::BAD_REQUEST, &[], b""));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+        let service = TableService::new(client);
+
+        let batch = service.batch_builder("Blogs", "pk").delete("1", None);
+        assert!(batch
+            .clone()
+            .with_extra_header("x-ms-foo", "bar")
+            .with_api_version("2019-02-02")
+            .finalize()
+            .wait()
+            .is_err());
+        assert!(batch.with_api_version("2019").finalize().wait().is_err());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers["x-ms-version"], "2019-02-02");
+        assert_eq!(requests[0].headers["x-ms-foo"], "bar");
+    }
+
+    #[test]
+    fn test_generate_payload() {
+        let operations = vec![
+            BatchOperation::Insert("{\"RowKey\":\"1\"}".to_owned()),
+            BatchOperation::Merge("2".to_owned(), "{\"Rating\":1}".to_owned(), Some("W/\"1\"".to_owned())),
+            BatchOperation::Delete("3".to_owned(), None),
+        ];
+
+        let payload = generate_payload("https://a.table.core.windows.net/", "Blogs", "pk", &operations, "b", "c");
+        let expected = "--b\r\nContent-Type: multipart/mixed; boundary=c\r\n\r\n\
+--c\r\nContent-Type: application/http\r\nContent-Transfer-Encoding: binary\r\n\r\n\
+POST https://a.table.core.windows.net/Blogs HTTP/1.1\r\nContent-ID: 0\r\nAccept: application/json; odata=nometadata\r\n\
+Content-Type: application/json\r\n\r\n{\"RowKey\":\"1\"}\r\n\
+--c\r\nContent-Type: application/http\r\nContent-Transfer-Encoding: binary\r\n\r\n\
+MERGE https://a.table.core.windows.net/Blogs(PartitionKey='pk',RowKey='2') HTTP/1.1\r\nContent-ID: 1\r\n\
+Accept: application/json; odata=nometadata\r\nIf-Match: W/\"1\"\r\nContent-Type: application/json\r\n\r\n{\"Rating\":1}\r\n\
+--c\r\nContent-Type: application/http\r\nContent-Transfer-Encoding: binary\r\n\r\n\
+DELETE https://a.table.core.windows.net/Blogs(PartitionKey='pk',RowKey='3') HTTP/1.1\r\nContent-ID: 2\r\n\
+Accept: application/json; odata=nometadata\r\nIf-Match: *\r\n\r\n\
+--c--\r\n--b--\r\n";
+
+        assert_eq!(payload, expected);
+    }
+
+    #[test]
+    fn test_parse_batch_response() {
+        let body = "--batchresponse_4c637ba4\r
+Content-Type: multipart/mixed; boundary=changesetresponse_5a24a9c6\r
+\r
+--changesetresponse_5a24a9c6\r
+Content-Type: application/http\r
+Content-Transfer-Encoding: binary\r
+\r
+HTTP/1.1 201 Created\r
+Content-ID: 0\r
+ETag: W/\"datetime'2019-04-01T10%3A00%3A00.1Z'\"\r
+\r
+{\"PartitionKey\":\"pk\",\"RowKey\":\"1\"}\r
+--changesetresponse_5a24a9c6\r
+Content-Type: application/http\r
+Content-Transfer-Encoding: binary\r
+\r
+HTTP/1.1 204 No Content\r
+Content-ID: 1\r
+\r
+\r
+--changesetresponse_5a24a9c6--\r
+--batchresponse_4c637ba4--\r
+";
+
+        let response = parse_batch_response(body).unwrap();
+        assert!(response.is_successful());
+        assert_eq!(response.operations.len(), 2);
+        assert_eq!(response.operations[0].status_code, StatusCode::CREATED);
+        assert_eq!(
+            response.operations[0].etag,
+            Some("W/\"datetime'2019-04-01T10%3A00%3A00.1Z'\"".to_owned())
+        );
+        assert_eq!(response.operations[1].status_code, StatusCode::NO_CONTENT);
+        assert_eq!(response.operations[1].etag, None);
+    }
+
+    #[test]
+    fn test_parse_batch_response_failure() {
+        let body = "--batchresponse_4c637ba4\n\
+Content-Type: multipart/mixed; boundary=changesetresponse_5a24a9c6\n\
+\n\
+--changesetresponse_5a24a9c6\n\
+Content-Type: application/http\n\
+Content-Transfer-Encoding: binary\n\
+\n\
+HTTP/1.1 409 Conflict\n\
+Content-ID: 1\n\
+Content-Type: application/json;odata=nometadata;streaming=true;charset=utf-8\n\
+\n\
+{\"odata.error\":{\"code\":\"EntityAlreadyExists\",\"message\":{\"lang\":\"en-US\",\"value\":\"1:The specified entity already exists.\"}}}\n\
+--changesetresponse_5a24a9c6--\n\
+--batchresponse_4c637ba4--\n";
+
+        let response = parse_batch_response(body).unwrap();
+        assert!(!response.is_successful());
+        assert_eq!(
+            response.error(),
+            Some(&BatchOperationError {
+                operation_index: Some(1),
+                code: "EntityAlreadyExists".to_owned(),
+                message: "The specified entity already exists.".to_owned(),
+            })
+        );
+        assert_eq!(response.operations[0].status_code, StatusCode::CONFLICT);
+    }
+}
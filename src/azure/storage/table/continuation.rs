@@ -0,0 +1,69 @@
+use crate::azure::core::COMPLETE_ENCODE_SET;
+use hyper::header::HeaderMap;
+use url::percent_encoding::utf8_percent_encode;
+
+const HEADER_NEXT_PARTITION_KEY: &str = "x-ms-continuation-NextPartitionKey";
+const HEADER_NEXT_ROW_KEY: &str = "x-ms-continuation-NextRowKey";
+
+/// Position from which a table query resumes. Azure returns it, as
+/// `x-ms-continuation-*` headers, when a query result is split in more
+/// than one page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinuationToken {
+    pub next_partition_key: String,
+    pub next_row_key: Option<String>,
+}
+
+impl ContinuationToken {
+    /// Returns `None` if the response is the last page.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<ContinuationToken> {
+        let next_partition_key = headers.get(HEADER_NEXT_PARTITION_KEY)?.to_str().ok()?.to_owned();
+        let next_row_key = headers.get(HEADER_NEXT_ROW_KEY).and_then(|v| v.to_str().ok()).map(|v| v.to_owned());
+
+        Some(ContinuationToken {
+            next_partition_key,
+            next_row_key,
+        })
+    }
+
+    pub(crate) fn to_query_string(&self) -> String {
+        let mut qs = format!(
+            "NextPartitionKey={}",
+            utf8_percent_encode(&self.next_partition_key, COMPLETE_ENCODE_SET)
+        );
+        if let Some(ref next_row_key) = self.next_row_key {
+            qs.push_str("&NextRowKey=");
+            qs.push_str(&utf8_percent_encode(next_row_key, COMPLETE_ENCODE_SET).to_string());
+        }
+        qs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hyper::header::HeaderValue;
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(ContinuationToken::from_headers(&headers), None);
+
+        headers.insert(HEADER_NEXT_PARTITION_KEY, HeaderValue::from_static("1!8!cGsx"));
+        headers.insert(HEADER_NEXT_ROW_KEY, HeaderValue::from_static("1!8!cm+x"));
+
+        let token = ContinuationToken::from_headers(&headers).unwrap();
+        assert_eq!(token.next_partition_key, "1!8!cGsx");
+        assert_eq!(token.next_row_key, Some("1!8!cm+x".to_owned()));
+        assert_eq!(token.to_query_string(), "NextPartitionKey=1!8!cGsx&NextRowKey=1!8!cm%2Bx");
+    }
+
+    #[test]
+    fn test_to_query_string_without_row_key() {
+        let token = ContinuationToken {
+            next_partition_key: "1!8!cGsx".to_owned(),
+            next_row_key: None,
+        };
+        assert_eq!(token.to_query_string(), "NextPartitionKey=1!8!cGsx");
+    }
+}
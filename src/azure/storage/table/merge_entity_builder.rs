@@ -0,0 +1,122 @@
+use super::{entity_headers, TableService};
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{ApiVersionOption, ApiVersionSupport, ExtraHeadersOption, ExtraHeadersSupport};
+use futures::future::*;
+use hyper::Method;
+use serde::Serialize;
+
+/// Updates the properties of an entity that are present in the new value,
+/// leaving the others untouched (Merge Entity). The ETag returned by
+/// `finalize` is the one of the new version of the entity.
+///
+/// By default the operation is unconditional (`If-Match: *`) and fails if
+/// the entity does not exist.
+#[derive(Clone)]
+pub struct MergeEntityBuilder<'a> {
+    service: &'a TableService,
+    table_name: &'a str,
+    partition_key: &'a str,
+    row_key: &'a str,
+    if_match: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> MergeEntityBuilder<'a> {
+    pub(crate) fn new(service: &'a TableService, table_name: &'a str, partition_key: &'a str, row_key: &'a str) -> MergeEntityBuilder<'a> {
+        MergeEntityBuilder {
+            service,
+            table_name,
+            partition_key,
+            row_key,
+            if_match: Some("*"),
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+
+    pub fn table_name(&self) -> &'a str {
+        self.table_name
+    }
+
+    pub fn partition_key(&self) -> &'a str {
+        self.partition_key
+    }
+
+    pub fn row_key(&self) -> &'a str {
+        self.row_key
+    }
+
+    /// The ETag the entity must match, `None` if the entity is created
+    /// when missing.
+    pub fn etag(&self) -> Option<&'a str> {
+        self.if_match
+    }
+
+    /// Applies the operation only if the entity still has the given ETag
+    /// (as returned by a previous read or write), otherwise the future
+    /// fails with `412 Precondition Failed`. Pass `*` to match any
+    /// version.
+    pub fn with_etag(self, etag: &'a str) -> Self {
+        MergeEntityBuilder {
+            if_match: Some(etag),
+            ..self
+        }
+    }
+
+    /// Merges the entity, creating it if missing (Insert Or Merge Entity).
+    /// Overrides `with_etag`.
+    pub fn insert_or_merge(self) -> Self {
+        MergeEntityBuilder { if_match: None, ..self }
+    }
+
+    /// Sends the entity and returns its new ETag.
+    pub fn finalize<T: Serialize>(self, entity: &T) -> impl Future<Item = String, Error = AzureError> {
+        let api_version = match ApiVersionOption::required_api_version(&self, &self.service.client, None) {
+            Ok(api_version) => api_version,
+            Err(error) => return Either::A(err(error)),
+        };
+        let headers = entity_headers(self.if_match, api_version, &self.extra_headers);
+
+        Either::B(self.service.write_entity(
+            self.table_name,
+            self.partition_key,
+            self.row_key,
+            Method::from_bytes(b"MERGE").unwrap(),
+            &headers,
+            entity,
+        ))
+    }
+}
+
+impl<'a> ExtraHeadersOption<'a> for MergeEntityBuilder<'a> {
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a> ExtraHeadersSupport<'a> for MergeEntityBuilder<'a> {
+    type O = Self;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a> ApiVersionOption<'a> for MergeEntityBuilder<'a> {
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a> ApiVersionSupport<'a> for MergeEntityBuilder<'a> {
+    type O = Self;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        MergeEntityBuilder {
+            api_version: Some(api_version),
+            ..self
+        }
+    }
+}
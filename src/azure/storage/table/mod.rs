@@ -1,13 +1,25 @@
 mod batch;
+mod batch_builder;
+mod continuation;
+mod merge_entity_builder;
+mod update_entity_builder;
 
 pub use self::batch::BatchItem;
+pub use self::batch_builder::{BatchBuilder, BatchOperationError, BatchOperationResponse, BatchResponse, MAX_BATCH_OPERATIONS};
+pub use self::continuation::ContinuationToken;
+pub use self::merge_entity_builder::MergeEntityBuilder;
+pub use self::update_entity_builder::UpdateEntityBuilder;
 
 use self::batch::generate_batch_payload;
-use crate::azure::core::errors::{check_status_extract_body, extract_status_and_body, AzureError, UnexpectedHTTPResult};
-use crate::azure::storage::client::Client;
+use crate::azure::core::errors::{
+    check_status_extract_body, check_status_extract_headers_and_body, check_status_extract_headers_and_body_as_string,
+    extract_status_and_body, AzureError, UnexpectedHTTPResult,
+};
+use crate::azure::core::etag_from_headers;
+use crate::azure::core::headers::API_VERSION;
+use crate::azure::storage::client::{Client, ResponseFuture};
 use crate::azure::storage::rest_client::ServiceType;
 use hyper::{
-    client::ResponseFuture,
     header::{self, HeaderValue},
     Method, StatusCode,
 };
@@ -16,8 +28,12 @@ use serde::Serialize;
 use serde_json;
 
 use futures::future::*;
+use futures::prelude::*;
+use futures::stream;
 
 const TABLE_TABLES: &str = "TABLES";
+// the service never returns more than 1000 entities per page
+const MAX_PAGE_SIZE: usize = 1000;
 
 pub struct TableService {
     client: Client,
@@ -82,19 +98,83 @@ impl TableService {
         table_name: &str,
         query: Option<&str>,
     ) -> impl Future<Item = Vec<T>, Error = AzureError> {
-        let mut path = table_name.to_owned();
+        self.query_entities_page(table_name, query, None, None)
+            .map(|(entities, _)| entities)
+    }
+
+    /// Returns the entities matching the query, transparently following the
+    /// `NextPartitionKey`/`NextRowKey` continuation tokens. The pages are
+    /// requested lazily, as the stream is consumed. If `max_results` is
+    /// specified the stream ends after that many entities and no further
+    /// page is requested.
+    pub fn query_entities_stream<T: DeserializeOwned>(
+        &self,
+        table_name: &str,
+        query: Option<&str>,
+        max_results: Option<usize>,
+    ) -> impl Stream<Item = T, Error = AzureError> {
+        let client = self.client.clone();
+        let table_name = table_name.to_owned();
+        let query = query.map(|v| v.to_owned());
+
+        // None means the query is over
+        let start = Some((None, max_results));
+
+        stream::unfold(start, move |state| {
+            let (continuation, remaining): (Option<ContinuationToken>, Option<usize>) = state?;
+            if remaining == Some(0) {
+                return None;
+            }
+
+            let page =
+                TableService::new(client.clone()).query_entities_page(&table_name, query.as_deref(), continuation.as_ref(), remaining);
+
+            Some(page.map(move |(mut entities, continuation)| {
+                if let Some(remaining) = remaining {
+                    entities.truncate(remaining);
+                }
+                let remaining = remaining.map(|remaining| remaining - entities.len());
+                let next = continuation.map(|continuation| (Some(continuation), remaining));
+                (stream::iter_ok(entities), next)
+            }))
+        })
+        .flatten()
+    }
+
+    fn query_entities_page<T: DeserializeOwned>(
+        &self,
+        table_name: &str,
+        query: Option<&str>,
+        continuation: Option<&ContinuationToken>,
+        top: Option<usize>,
+    ) -> impl Future<Item = (Vec<T>, Option<ContinuationToken>), Error = AzureError> {
+        let mut params = Vec::new();
         if let Some(clause) = query {
+            params.push(clause.to_owned());
+        }
+        if let Some(top) = top {
+            // a $top specified in the query takes precedence
+            if !query.is_some_and(|clause| clause.contains("$top=")) {
+                params.push(format!("$top={}", top.min(MAX_PAGE_SIZE)));
+            }
+        }
+        if let Some(continuation) = continuation {
+            params.push(continuation.to_query_string());
+        }
+
+        let mut path = table_name.to_owned();
+        if !params.is_empty() {
             path.push_str("?");
-            path.push_str(clause);
+            path.push_str(&params.join("&"));
         }
 
         let req = self.request_with_default_header(path.as_str(), &Method::GET, None);
 
         done(req).from_err().and_then(move |future_response| {
-            check_status_extract_body(future_response, StatusCode::OK).and_then(move |body| {
+            check_status_extract_headers_and_body_as_string(future_response, StatusCode::OK).and_then(move |(headers, body)| {
                 done(serde_json::from_str::<EntityCollection<T>>(&body))
                     .from_err()
-                    .and_then(|ec| ok(ec.value))
+                    .map(move |ec| (ec.value, ContinuationToken::from_headers(&headers)))
             })
         })
     }
@@ -143,6 +223,65 @@ impl TableService {
             .and_then(move |future_response| check_status_extract_body(future_response, StatusCode::NO_CONTENT).and_then(move |_| ok(())))
     }
 
+    /// Replaces an entity, optionally checking its ETag. See
+    /// `UpdateEntityBuilder`.
+    pub fn update_entity_builder<'a>(&'a self, table_name: &'a str, partition_key: &'a str, row_key: &'a str) -> UpdateEntityBuilder<'a> {
+        UpdateEntityBuilder::new(self, table_name, partition_key, row_key)
+    }
+
+    /// Merges the properties of an entity, optionally checking its ETag.
+    /// See `MergeEntityBuilder`.
+    pub fn merge_entity_builder<'a>(&'a self, table_name: &'a str, partition_key: &'a str, row_key: &'a str) -> MergeEntityBuilder<'a> {
+        MergeEntityBuilder::new(self, table_name, partition_key, row_key)
+    }
+
+    pub fn insert_or_replace_entity_builder<'a>(
+        &'a self,
+        table_name: &'a str,
+        partition_key: &'a str,
+        row_key: &'a str,
+    ) -> UpdateEntityBuilder<'a> {
+        self.update_entity_builder(table_name, partition_key, row_key).insert_or_replace()
+    }
+
+    pub fn insert_or_merge_entity_builder<'a>(
+        &'a self,
+        table_name: &'a str,
+        partition_key: &'a str,
+        row_key: &'a str,
+    ) -> MergeEntityBuilder<'a> {
+        self.merge_entity_builder(table_name, partition_key, row_key).insert_or_merge()
+    }
+
+    // PUT or MERGE of a single entity, returning the new ETag. Without
+    // If-Match Azure creates the entity if it does not exist.
+    fn write_entity<T: Serialize>(
+        &self,
+        table_name: &str,
+        partition_key: &str,
+        row_key: &str,
+        method: Method,
+        headers: &[(&str, &str)],
+        entity: &T,
+    ) -> impl Future<Item = String, Error = AzureError> {
+        let path = &entity_path(table_name, partition_key, row_key);
+
+        let req = serde_json::to_string(entity).map_err(AzureError::from).and_then(|body| {
+            self.request(path, &method, Some(&body), |ref mut request| {
+                request.header(header::ACCEPT, HeaderValue::from_static(get_json_mime_nometadata()));
+                request.header(header::CONTENT_TYPE, HeaderValue::from_static(get_default_json_mime()));
+                for (name, value) in headers {
+                    request.header(*name, *value);
+                }
+            })
+        });
+
+        done(req).from_err().and_then(move |future_response| {
+            check_status_extract_headers_and_body(future_response, StatusCode::NO_CONTENT)
+                .and_then(move |(headers, _)| done(etag_from_headers(&headers)))
+        })
+    }
+
     pub fn delete_entity(&self, table_name: &str, partition_key: &str, row_key: &str) -> impl Future<Item = (), Error = AzureError> {
         let path = &entity_path(table_name, partition_key, row_key);
 
@@ -181,6 +320,13 @@ impl TableService {
         })
     }
 
+    /// Starts an entity group transaction on the partition. Unlike
+    /// `batch` it supports every entity operation and reports the outcome
+    /// of each of them.
+    pub fn batch_builder<'a>(&'a self, table_name: &'a str, partition_key: &'a str) -> BatchBuilder<'a> {
+        BatchBuilder::new(self, table_name, partition_key)
+    }
+
     fn request_with_default_header(&self, segment: &str, method: &Method, request_str: Option<&str>) -> Result<ResponseFuture, AzureError> {
         self.request(segment, method, request_str, |ref mut request| {
             request.header(header::ACCEPT, HeaderValue::from_static(get_json_mime_nometadata()));
@@ -219,6 +365,23 @@ struct EntityCollection<T> {
     value: Vec<T>,
 }
 
+// the If-Match, x-ms-version and extra headers of an entity write
+fn entity_headers<'a>(
+    if_match: Option<&'a str>,
+    api_version: Option<&'a str>,
+    extra_headers: &[(&'a str, &'a str)],
+) -> Vec<(&'a str, &'a str)> {
+    let mut headers = Vec::with_capacity(extra_headers.len() + 2);
+    if let Some(if_match) = if_match {
+        headers.push((header::IF_MATCH.as_str(), if_match));
+    }
+    if let Some(api_version) = api_version {
+        headers.push((API_VERSION, api_version));
+    }
+    headers.extend_from_slice(extra_headers);
+    headers
+}
+
 #[inline]
 fn entity_path(table_name: &str, partition_key: &str, row_key: &str) -> String {
     table_name.to_owned() + "(PartitionKey='" + partition_key + "',RowKey='" + row_key + "')"
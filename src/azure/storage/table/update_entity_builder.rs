@@ -0,0 +1,157 @@
+use super::{entity_headers, TableService};
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::{ApiVersionOption, ApiVersionSupport, ExtraHeadersOption, ExtraHeadersSupport};
+use futures::future::*;
+use hyper::Method;
+use serde::Serialize;
+
+/// Replaces an entity (Update Entity). The ETag returned by `finalize` is the
+/// one of the new version of the entity.
+///
+/// By default the operation is unconditional (`If-Match: *`) and fails if
+/// the entity does not exist.
+#[derive(Clone)]
+pub struct UpdateEntityBuilder<'a> {
+    service: &'a TableService,
+    table_name: &'a str,
+    partition_key: &'a str,
+    row_key: &'a str,
+    if_match: Option<&'a str>,
+    extra_headers: Vec<(&'a str, &'a str)>,
+    api_version: Option<&'a str>,
+}
+
+impl<'a> UpdateEntityBuilder<'a> {
+    pub(crate) fn new(service: &'a TableService, table_name: &'a str, partition_key: &'a str, row_key: &'a str) -> UpdateEntityBuilder<'a> {
+        UpdateEntityBuilder {
+            service,
+            table_name,
+            partition_key,
+            row_key,
+            if_match: Some("*"),
+            extra_headers: Vec::new(),
+            api_version: None,
+        }
+    }
+
+    pub fn table_name(&self) -> &'a str {
+        self.table_name
+    }
+
+    pub fn partition_key(&self) -> &'a str {
+        self.partition_key
+    }
+
+    pub fn row_key(&self) -> &'a str {
+        self.row_key
+    }
+
+    /// The ETag the entity must match, `None` if the entity is created
+    /// when missing.
+    pub fn etag(&self) -> Option<&'a str> {
+        self.if_match
+    }
+
+    /// Applies the operation only if the entity still has the given ETag
+    /// (as returned by a previous read or write), otherwise the future
+    /// fails with `412 Precondition Failed`. Pass `*` to match any
+    /// version.
+    pub fn with_etag(self, etag: &'a str) -> Self {
+        UpdateEntityBuilder {
+            if_match: Some(etag),
+            ..self
+        }
+    }
+
+    /// Replaces the entity, creating it if missing (Insert Or Replace Entity).
+    /// Overrides `with_etag`.
+    pub fn insert_or_replace(self) -> Self {
+        UpdateEntityBuilder { if_match: None, ..self }
+    }
+
+    /// Sends the entity and returns its new ETag.
+    pub fn finalize<T: Serialize>(self, entity: &T) -> impl Future<Item = String, Error = AzureError> {
+        let api_version = match ApiVersionOption::required_api_version(&self, &self.service.client, None) {
+            Ok(api_version) => api_version,
+            Err(error) => return Either::A(err(error)),
+        };
+        let headers = entity_headers(self.if_match, api_version, &self.extra_headers);
+
+        Either::B(
+            self.service
+                .write_entity(self.table_name, self.partition_key, self.row_key, Method::PUT, &headers, entity),
+        )
+    }
+}
+
+impl<'a> ExtraHeadersOption<'a> for UpdateEntityBuilder<'a> {
+    fn extra_headers(&self) -> &[(&'a str, &'a str)] {
+        &self.extra_headers
+    }
+}
+
+impl<'a> ExtraHeadersSupport<'a> for UpdateEntityBuilder<'a> {
+    type O = Self;
+
+    fn with_extra_header(mut self, name: &'a str, value: &'a str) -> Self::O {
+        self.extra_headers.push((name, value));
+        self
+    }
+}
+
+impl<'a> ApiVersionOption<'a> for UpdateEntityBuilder<'a> {
+    fn api_version(&self) -> Option<&'a str> {
+        self.api_version
+    }
+}
+
+impl<'a> ApiVersionSupport<'a> for UpdateEntityBuilder<'a> {
+    type O = Self;
+
+    fn with_api_version(self, api_version: &'a str) -> Self::O {
+        UpdateEntityBuilder {
+            api_version: Some(api_version),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::azure::core::test_transport::ReplayTransport;
+    use crate::azure::storage::client::Client;
+    use hyper::StatusCode;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_extra_headers_and_api_version() {
+        let transport = Arc::new(ReplayTransport::new().with_response(StatusCode::NO_CONTENT, &[("etag", "W/\"1\"")], b""));
+        let client = Client::azure("account", "YWNjb3VudGtleQ==")
+            .unwrap()
+            .with_http_client(transport.clone());
+        let service = TableService::new(client);
+        let entity = serde_json::json!({ "Rating": 1 });
+
+        let etag = service
+            .update_entity_builder("Blogs", "pk", "1")
+            .with_extra_header("x-ms-foo", "bar")
+            .with_api_version("2019-02-02")
+            .finalize(&entity)
+            .wait()
+            .unwrap();
+        assert_eq!(etag, "W/\"1\"");
+        assert!(service
+            .update_entity_builder("Blogs", "pk", "1")
+            .with_api_version("latest")
+            .finalize(&entity)
+            .wait()
+            .is_err());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers["if-match"], "*");
+        assert_eq!(requests[0].headers["x-ms-version"], "2019-02-02");
+        assert_eq!(requests[0].headers["x-ms-foo"], "bar");
+    }
+}
@@ -0,0 +1,266 @@
+use crate::azure::core::errors::AzureError;
+use crate::azure::core::ContainerNameSupport;
+use crate::azure::storage::client::{Blob, Client, Container};
+use futures::prelude::*;
+use std::collections::BTreeMap;
+
+/// Number of blobs and their total size in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageTotals {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, content_length: u64) {
+        self.count += 1;
+        self.bytes += content_length;
+    }
+}
+
+/// Usage of a single container, broken down by top level prefix and by
+/// access tier. The prefix of a blob is its name up to (and including)
+/// the first delimiter: blobs at the container root are grouped under the
+/// empty prefix. Blobs without an access tier (for example page blobs)
+/// are grouped under `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerUsage {
+    pub totals: UsageTotals,
+    pub by_prefix: BTreeMap<String, UsageTotals>,
+    pub by_tier: BTreeMap<Option<String>, UsageTotals>,
+}
+
+impl ContainerUsage {
+    fn add_blob(&mut self, prefix: &str, content_length: u64, access_tier: Option<&str>) {
+        self.totals.add(content_length);
+        self.by_prefix.entry(prefix.to_owned()).or_default().add(content_length);
+        self.by_tier
+            .entry(access_tier.map(|tier| tier.to_owned()))
+            .or_default()
+            .add(content_length);
+    }
+}
+
+/// Storage account usage, as returned by `Container::usage_report` or
+/// built from blob inventory data with `UsageReport::from_inventory_csv`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageReport {
+    delimiter: String,
+    pub containers: BTreeMap<String, ContainerUsage>,
+}
+
+impl UsageReport {
+    pub fn new(delimiter: &str) -> UsageReport {
+        UsageReport {
+            delimiter: delimiter.to_owned(),
+            containers: BTreeMap::new(),
+        }
+    }
+
+    pub fn delimiter(&self) -> &str {
+        &self.delimiter
+    }
+
+    /// Adds an empty container to the report. Containers are added
+    /// implicitly by `add_blob` so this is only needed to list the empty
+    /// ones.
+    pub fn add_container(&mut self, container_name: &str) {
+        self.containers.entry(container_name.to_owned()).or_default();
+    }
+
+    pub fn add_blob(&mut self, container_name: &str, blob_name: &str, content_length: u64, access_tier: Option<&str>) {
+        let prefix = prefix(blob_name, &self.delimiter);
+        self.containers
+            .entry(container_name.to_owned())
+            .or_default()
+            .add_blob(prefix, content_length, access_tier);
+    }
+
+    /// Account wide totals.
+    pub fn totals(&self) -> UsageTotals {
+        self.containers.values().fold(UsageTotals::default(), |acc, usage| UsageTotals {
+            count: acc.count + usage.totals.count,
+            bytes: acc.bytes + usage.totals.bytes,
+        })
+    }
+
+    /// Account wide totals by access tier.
+    pub fn by_tier(&self) -> BTreeMap<Option<String>, UsageTotals> {
+        let mut by_tier: BTreeMap<Option<String>, UsageTotals> = BTreeMap::new();
+        for usage in self.containers.values() {
+            for (tier, totals) in &usage.by_tier {
+                let entry = by_tier.entry(tier.clone()).or_default();
+                entry.count += totals.count;
+                entry.bytes += totals.bytes;
+            }
+        }
+        by_tier
+    }
+
+    /// Builds the report from a blob inventory CSV file. Walking a large
+    /// account blob by blob takes a lot of list calls: if blob inventory
+    /// is enabled its (daily) output is much cheaper to read.
+    ///
+    /// The file must have a header row containing at least the `Name`
+    /// and `Content-Length` fields (`AccessTier` is used if present). The
+    /// name must include the container, as in `container/blob`.
+    pub fn from_inventory_csv(csv: &str, delimiter: &str) -> Result<UsageReport, AzureError> {
+        let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+
+        let header = match lines.next() {
+            Some(header) => split_csv_line(header)?,
+            None => return Ok(UsageReport::new(delimiter)),
+        };
+        let column = |name: &str| header.iter().position(|field| field == name);
+
+        let name_idx = column("Name").ok_or_else(|| AzureError::GenericErrorWithText("inventory without Name field".to_owned()))?;
+        let length_idx = column("Content-Length")
+            .ok_or_else(|| AzureError::GenericErrorWithText("inventory without Content-Length field".to_owned()))?;
+        let tier_idx = column("AccessTier");
+
+        let mut report = UsageReport::new(delimiter);
+
+        for line in lines {
+            let fields = split_csv_line(line)?;
+            let field = |idx: usize| {
+                fields
+                    .get(idx)
+                    .ok_or_else(|| AzureError::GenericErrorWithText(format!("missing field in inventory line {}", line)))
+            };
+
+            let name = field(name_idx)?;
+            let mut tokens = name.splitn(2, '/');
+            let container_name = tokens.next().unwrap_or_default();
+            let blob_name = tokens.next().unwrap_or_default();
+
+            let content_length = field(length_idx)?.parse::<u64>()?;
+            let access_tier = match tier_idx {
+                Some(idx) => Some(field(idx)?.as_str()).filter(|tier| !tier.is_empty()),
+                None => None,
+            };
+
+            report.add_blob(container_name, blob_name, content_length, access_tier);
+        }
+
+        Ok(report)
+    }
+}
+
+fn prefix<'a>(blob_name: &'a str, delimiter: &str) -> &'a str {
+    match blob_name.find(delimiter) {
+        Some(pos) if !delimiter.is_empty() => &blob_name[..pos + delimiter.len()],
+        _ => "",
+    }
+}
+
+// Minimal RFC 4180 parsing: fields can be quoted (with "" as the escaped
+// quote) but cannot span multiple lines.
+fn split_csv_line(line: &str) -> Result<Vec<String>, AzureError> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(AzureError::GenericErrorWithText(format!("unterminated quote in line {}", line)));
+    }
+
+    fields.push(field);
+    Ok(fields)
+}
+
+pub(crate) fn usage_report(client: &Client, delimiter: &str) -> impl Future<Item = UsageReport, Error = AzureError> {
+    let client = client.clone();
+    let delimiter = delimiter.to_owned();
+    let report_delimiter = delimiter.clone();
+
+    // containers are walked one at a time to keep the number of
+    // concurrent list calls (and the throttling risk) low
+    let containers = client.list_containers().stream();
+
+    containers
+        .and_then(move |container| {
+            let delimiter = delimiter.clone();
+            client
+                .list_blobs()
+                .with_container_name(&container.name)
+                .stream()
+                .fold(ContainerUsage::default(), move |mut usage, blob| {
+                    usage.add_blob(
+                        prefix(&blob.name, &delimiter),
+                        blob.content_length,
+                        blob.access_tier.as_ref().map(|tier| tier as &str),
+                    );
+                    Ok::<_, AzureError>(usage)
+                })
+                .map(move |usage| (container.name, usage))
+        })
+        .fold(UsageReport::new(&report_delimiter), |mut report, (container_name, usage)| {
+            report.containers.insert(container_name, usage);
+            Ok::<_, AzureError>(report)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_add_blob() {
+        let mut report = UsageReport::new("/");
+        report.add_container("empty");
+        report.add_blob("logs", "2019/01/a.log", 10, Some("Hot"));
+        report.add_blob("logs", "2019/02/b.log", 20, Some("Cool"));
+        report.add_blob("logs", "2020/c.log", 30, Some("Cool"));
+        report.add_blob("logs", "readme.txt", 5, None);
+        report.add_blob("images", "a.png", 100, Some("Hot"));
+
+        let logs = &report.containers["logs"];
+        assert_eq!(logs.totals, UsageTotals { count: 4, bytes: 65 });
+        assert_eq!(logs.by_prefix["2019/"], UsageTotals { count: 2, bytes: 30 });
+        assert_eq!(logs.by_prefix[""], UsageTotals { count: 1, bytes: 5 });
+        assert_eq!(logs.by_tier[&Some("Cool".to_owned())], UsageTotals { count: 2, bytes: 50 });
+        assert_eq!(logs.by_tier[&None], UsageTotals { count: 1, bytes: 5 });
+
+        assert_eq!(report.containers["empty"], ContainerUsage::default());
+        assert_eq!(report.totals(), UsageTotals { count: 5, bytes: 165 });
+        assert_eq!(report.by_tier()[&Some("Hot".to_owned())], UsageTotals { count: 2, bytes: 110 });
+    }
+
+    #[test]
+    fn test_from_inventory_csv() {
+        let csv = "Name,Creation-Time,Content-Length,BlobType,AccessTier\n\
+                   logs/2019/a.log,\"Mon, 01 Apr 2019 10:00:00 GMT\",10,BlockBlob,Hot\n\
+                   \"logs/\"\"quoted\"\".log\",\"Mon, 01 Apr 2019 10:00:00 GMT\",20,BlockBlob,Cool\n\
+                   \"disks/os,1.vhd\",\"Mon, 01 Apr 2019 10:00:00 GMT\",512,PageBlob,\n";
+
+        let report = UsageReport::from_inventory_csv(csv, "/").unwrap();
+        assert_eq!(report.totals(), UsageTotals { count: 3, bytes: 542 });
+        assert_eq!(report.containers["logs"].by_prefix["2019/"], UsageTotals { count: 1, bytes: 10 });
+        assert_eq!(report.containers["disks"].by_tier[&None], UsageTotals { count: 1, bytes: 512 });
+
+        assert!(UsageReport::from_inventory_csv("Name,AccessTier\n", "/").is_err());
+    }
+
+    #[test]
+    fn test_split_csv_line() {
+        assert_eq!(split_csv_line("a,\"b,c\",\"d\"\"e\",").unwrap(), vec!["a", "b,c", "d\"e", ""]);
+        assert!(split_csv_line("a,\"b").is_err());
+    }
+}
@@ -1,26 +1,47 @@
-#![recursion_limit = "128"]
+#![recursion_limit = "256"]
 
 extern crate base64;
 extern crate chrono;
 extern crate futures;
 extern crate http;
 extern crate hyper;
+#[cfg(feature = "rustls")]
+extern crate hyper_rustls;
+#[cfg(feature = "native-tls")]
 extern crate hyper_tls;
 extern crate md5;
+#[cfg(feature = "native-tls")]
+extern crate native_tls;
+#[cfg(feature = "openssl")]
+extern crate openssl;
+#[cfg(feature = "ring")]
 extern crate ring;
 extern crate time;
+extern crate tokio_io;
+#[cfg(feature = "rustls")]
+extern crate untrusted;
 #[macro_use]
 extern crate url;
 extern crate uuid;
+#[cfg(feature = "rustls")]
+extern crate webpki;
+#[cfg(feature = "rustls")]
+extern crate webpki_roots;
 extern crate xml;
 #[macro_use]
 extern crate log;
 #[macro_use]
 extern crate quick_error;
+extern crate rand;
+#[cfg(feature = "rustls")]
+extern crate rustls;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate bytes;
+extern crate flate2;
+#[cfg(feature = "async")]
+extern crate futures_util;
 extern crate serde_json;
 extern crate serde_xml_rs;
 extern crate smallvec;
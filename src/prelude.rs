@@ -1,20 +1,29 @@
 pub use crate::azure::core::{
-    AccessTierOption, AccessTierSupport, BA512RangeOption, BA512RangeRequired, BA512RangeSupport, BlobNameRequired, BlobNameSupport,
+    AccessTierOption, AccessTierRequired, AccessTierSupport, ApiVersionOption, ApiVersionSupport, BA512RangeOption, BA512RangeRequired, BA512RangeSupport, BlobNameRequired, BlobNameSupport,
     BlockIdRequired, BlockIdSupport, BlockListRequired, BlockListSupport, BlockListTypeRequired, BlockListTypeSupport, BodyRequired,
-    BodySupport, CacheControlOption, CacheControlSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired,
+    BodySupport, CacheControlOption, CacheControlSupport, ClientRequestIdOption, ClientRequestIdSupport, ClientRequired, ExtraHeadersOption, ExtraHeadersSupport,
     ContainerNameRequired, ContainerNameSupport, ContentDispositionOption, ContentDispositionSupport, ContentEncodingOption,
     ContentEncodingSupport, ContentLanguageOption, ContentLanguageSupport, ContentLengthOption, ContentLengthRequired,
     ContentLengthSupport, ContentMD5Option, ContentMD5Support, ContentTypeOption, ContentTypeSupport, DeleteSnapshotsMethodSupport,
     DelimiterOption, DelimiterSupport, IfMatchConditionOption, IfMatchConditionSupport, IfSinceConditionOption, IfSinceConditionSupport,
     IncludeCopyOption, IncludeCopySupport, IncludeDeletedOption, IncludeDeletedSupport, IncludeListOptions, IncludeMetadataOption,
-    IncludeMetadataSupport, IncludeSnapshotsOption, IncludeSnapshotsSupport, IncludeUncommittedBlobsOption, IncludeUncommittedBlobsSupport,
-    LeaseBreakPeriodOption, LeaseBreakPeriodRequired, LeaseBreakPeriodSupport, LeaseDurationRequired, LeaseDurationSupport, LeaseIdOption,
-    LeaseIdRequired, LeaseIdSupport, MaxResultsOption, MaxResultsSupport, MetadataOption, MetadataSupport, NextMarkerOption,
-    NextMarkerSupport, PageBlobLengthRequired, PageBlobLengthSupport, PrefixOption, PrefixSupport, ProposedLeaseIdOption,
-    ProposedLeaseIdRequired, ProposedLeaseIdSupport, RangeOption, RangeSupport, SequenceNumberConditionOption,
-    SequenceNumberConditionSupport, SequenceNumberOption, SequenceNumberSupport, SnapshotOption, SnapshotRequired, SnapshotSupport,
-    StoredAccessPolicy, StoredAccessPolicyList, TimeoutOption, TimeoutSupport,
+    IncludeMetadataSupport, IncludeSnapshotsOption, IncludeSnapshotsSupport, IncludeTagsOption, IncludeTagsSupport, IncludeUncommittedBlobsOption, IncludeUncommittedBlobsSupport,
+    IncludeVersionsOption, IncludeVersionsSupport, LeaseBreakPeriodOption, LeaseBreakPeriodRequired, LeaseBreakPeriodSupport,
+    LeaseDurationRequired, LeaseDurationSupport, LeaseIdOption, LeaseIdRequired, LeaseIdSupport, MaxResultsOption, MaxResultsSupport,
+    MetadataOption, MetadataSupport, NextMarkerOption, NextMarkerSupport, PageBlobLengthRequired, PageBlobLengthSupport, PrefixOption,
+    PrefixSupport, ProgressOption, ProgressSupport, ProposedLeaseIdOption, ProposedLeaseIdRequired, ProposedLeaseIdSupport, RangeOption, RangeSupport,
+    SequenceNumberConditionOption, SequenceNumberConditionSupport, SequenceNumberOption, SequenceNumberSupport, SnapshotOption,
+    SnapshotRequired, SnapshotSupport, StoredAccessPolicy, StoredAccessPolicyList, TimeoutOption, TimeoutSupport, VersionIdOption,
+    VersionIdSupport,
+};
+pub use crate::azure::storage::blob::{
+    ImmutabilityPolicyModeSupport, ImmutabilityPolicyUntilDateSupport, LegalHoldSupport, TagFilterSupport, TagsSupport,
+};
+pub use crate::azure::storage::container::{DeletedContainerNameSupport, DeletedContainerVersionSupport, PublicAccessSupport};
+pub use crate::azure::storage::file::{DirectoryPathSupport, FilePathSupport, FileSizeSupport, ShareNameSupport, ShareQuotaSupport};
+pub use crate::azure::storage::queue::{
+    MessageIdSupport, MessageTextSupport, MessageTtlSupport, NumberOfMessagesSupport, PopReceiptSupport, QueueNameSupport,
+    VisibilityTimeoutSupport,
 };
-pub use crate::azure::storage::container::PublicAccessSupport;
 
-pub use crate::azure::storage::client::{Blob as BlobTrait, Client, Container as ContainerTrait};
+pub use crate::azure::storage::client::{Blob as BlobTrait, Client, ClientBuilder, Container as ContainerTrait, File as FileTrait, Queue as QueueTrait};
@@ -4,7 +4,6 @@ extern crate chrono;
 extern crate env_logger;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 extern crate tokio_core;
 #[macro_use]
 extern crate log;
@@ -13,9 +12,9 @@ extern crate serde;
 extern crate uuid;
 
 use azure_sdk_for_rust::core::errors::AzureError;
+use azure_sdk_for_rust::core::metadata::Metadata;
 use azure_sdk_for_rust::prelude::*;
 use azure_sdk_for_rust::storage::container::{PublicAccess, PublicAccessSupport};
-use std::collections::HashMap;
 use tokio_core::reactor::Core;
 
 #[test]
@@ -43,9 +42,9 @@ fn put_append_blob() {
         ).unwrap();
     }
 
-    let mut metadata = HashMap::new();
-    metadata.insert("attrib", "value");
-    metadata.insert("second", "something");
+    let mut metadata = Metadata::new();
+    metadata.insert("attrib", "value").unwrap();
+    metadata.insert("second", "something").unwrap();
 
     // calculate md5 too!
     let _digest = md5::compute(&data[..]);
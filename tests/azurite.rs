@@ -0,0 +1,152 @@
+#![cfg(all(test, feature = "test_azurite"))]
+extern crate azure_sdk_for_rust;
+extern crate futures;
+#[macro_use]
+extern crate serde_derive;
+extern crate tokio_core;
+
+use azure_sdk_for_rust::core::errors::AzureError;
+use azure_sdk_for_rust::core::{
+    BlobNameSupport, BodySupport, ContainerNameSupport, ContentTypeSupport, DeleteSnapshotsMethod, DeleteSnapshotsMethodSupport,
+    PrefixSupport,
+};
+use azure_sdk_for_rust::storage::azurite;
+use azure_sdk_for_rust::storage::client::Client;
+use azure_sdk_for_rust::storage::container::{PublicAccess, PublicAccessSupport};
+use azure_sdk_for_rust::storage::queue::{MessageIdSupport, MessageTextSupport, PopReceiptSupport, QueueNameSupport};
+use azure_sdk_for_rust::storage::table::TableService;
+use futures::Stream;
+use tokio_core::reactor::Core;
+
+#[test]
+fn container_and_blobs() {
+    use azure_sdk_for_rust::storage::client::{Blob, Container};
+
+    let (client, mut core) = initialize().unwrap();
+    let container_name = azurite::unique_name("container");
+
+    core.run(
+        client
+            .create_container()
+            .with_container_name(&container_name)
+            .with_public_access(PublicAccess::None)
+            .finalize(),
+    )
+    .unwrap();
+
+    let future = client.list_containers().with_prefix(&container_name).finalize();
+    let containers = core.run(future).unwrap().incomplete_vector;
+    assert_eq!(containers.len(), 1);
+    assert_eq!(containers[0].name, container_name);
+
+    for blob_name in &["a", "b/c"] {
+        let future = client
+            .put_block_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(blob_name)
+            .with_content_type("text/plain")
+            .with_body(blob_name.as_bytes())
+            .finalize();
+        core.run(future).unwrap();
+    }
+
+    let future = client.list_blobs().with_container_name(&container_name).stream().collect();
+    let names: Vec<String> = core.run(future).unwrap().into_iter().map(|blob| blob.name).collect();
+    assert_eq!(names, vec!["a", "b/c"]);
+
+    let future = client
+        .get_blob()
+        .with_container_name(&container_name)
+        .with_blob_name("b/c")
+        .finalize();
+    let response = core.run(future).unwrap();
+    assert_eq!(response.data, b"b/c");
+    assert_eq!(response.blob.content_type.as_ref().map(|s| s as &str), Some("text/plain"));
+
+    let future = client
+        .delete_blob()
+        .with_container_name(&container_name)
+        .with_blob_name("a")
+        .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client
+        .get_blob_properties()
+        .with_container_name(&container_name)
+        .with_blob_name("a")
+        .finalize();
+    assert!(core.run(future).is_err());
+
+    core.run(client.delete_container().with_container_name(&container_name).finalize())
+        .unwrap();
+}
+
+#[test]
+fn queue_messages() {
+    use azure_sdk_for_rust::storage::client::Queue;
+
+    let (client, mut core) = initialize().unwrap();
+    let queue_name = azurite::unique_name("queue");
+
+    core.run(client.create_queue().with_queue_name(&queue_name).finalize()).unwrap();
+
+    let future = client
+        .put_message()
+        .with_queue_name(&queue_name)
+        .with_message_text("hello")
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client.get_messages().with_queue_name(&queue_name).finalize();
+    let message = core.run(future).unwrap().messages.remove(0);
+    assert_eq!(message.message_text, "hello");
+
+    let future = client
+        .delete_message()
+        .with_queue_name(&queue_name)
+        .with_message_id(&message.message_id)
+        .with_pop_receipt(message.pop_receipt.as_ref().unwrap())
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client.peek_messages().with_queue_name(&queue_name).finalize();
+    assert!(core.run(future).unwrap().messages.is_empty());
+
+    core.run(client.delete_queue().with_queue_name(&queue_name).finalize()).unwrap();
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[allow(non_snake_case)]
+struct Entity {
+    PartitionKey: String,
+    RowKey: String,
+    value: u32,
+}
+
+#[test]
+fn table_entities() {
+    let (client, mut core) = initialize().unwrap();
+    let table_service = TableService::new(client);
+    let table_name = azurite::unique_name("table");
+
+    core.run(table_service.create_table(table_name.as_str())).unwrap();
+
+    let entity = Entity {
+        PartitionKey: "p".to_owned(),
+        RowKey: "r".to_owned(),
+        value: 42,
+    };
+    core.run(table_service.insert_entity(&table_name, &entity)).unwrap();
+
+    let read: Option<Entity> = core.run(table_service.get_entity(&table_name, "p", "r")).unwrap();
+    assert_eq!(read, Some(entity));
+
+    core.run(table_service.delete_entity(&table_name, "p", "r")).unwrap();
+    let read: Option<Entity> = core.run(table_service.get_entity(&table_name, "p", "r")).unwrap();
+    assert_eq!(read, None);
+}
+
+fn initialize() -> Result<(Client, Core), AzureError> {
+    Ok((azurite::client()?, Core::new()?))
+}
@@ -4,7 +4,6 @@ extern crate chrono;
 extern crate env_logger;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 extern crate tokio_core;
 #[macro_use]
 extern crate log;
@@ -13,6 +12,8 @@ extern crate serde;
 extern crate uuid;
 
 use azure_sdk_for_rust::core::errors::AzureError;
+use azure_sdk_for_rust::core::lease::{LeaseBreakPeriod, LeasePeriod};
+use azure_sdk_for_rust::core::modify_conditions::IfMatchCondition;
 use azure_sdk_for_rust::core::DeleteSnapshotsMethod;
 use azure_sdk_for_rust::prelude::*;
 use azure_sdk_for_rust::storage::{
@@ -21,7 +22,7 @@ use azure_sdk_for_rust::storage::{
     container::{Container, PublicAccess, PublicAccessSupport},
 };
 use chrono::{Duration, FixedOffset, Utc};
-use futures::Future;
+use futures::{Future, Stream};
 use std::ops::Add;
 use std::ops::Deref;
 use tokio_core::reactor::Core;
@@ -91,7 +92,7 @@ fn create_and_delete_container() {
     let future = client
         .acquire_container_lease()
         .with_container_name(&cont_list[0].name)
-        .with_lease_duration(30)
+        .with_lease_duration(LeasePeriod::fixed(30).unwrap())
         .finalize();
     let res = core.run(future).unwrap();
     let lease_id = res.lease_id;
@@ -182,7 +183,7 @@ fn put_and_get_block_list() {
         .acquire_blob_lease()
         .with_container_name(&container.name)
         .with_blob_name(name)
-        .with_lease_duration(60)
+        .with_lease_duration(LeasePeriod::fixed(60).unwrap())
         .finalize();
     let res = core.run(future).unwrap();
     println!("Acquire lease == {:?}", res);
@@ -202,7 +203,7 @@ fn put_and_get_block_list() {
         .break_blob_lease()
         .with_container_name(&container.name)
         .with_blob_name(name)
-        .with_lease_break_period(15)
+        .with_lease_break_period(LeaseBreakPeriod::new(15).unwrap())
         .finalize();
     let res = core.run(future).unwrap();
     println!("Break lease == {:?}", res);
@@ -308,6 +309,673 @@ fn put_block_blob() {
     trace!("created {:?}", blob_name);
 }
 
+#[test]
+fn set_blob_metadata_and_properties() {
+    use azure_sdk_for_rust::core::metadata::Metadata;
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let blob_name: &'static str = "m2";
+    let container_name: &'static str = "rust-upload-test";
+    let data = b"abcdef";
+
+    if core
+        .run(client.list_containers().finalize())
+        .unwrap()
+        .incomplete_vector
+        .iter()
+        .find(|x| x.name == container_name)
+        .is_none()
+    {
+        core.run(
+            client
+                .create_container()
+                .with_container_name(container_name)
+                .with_public_access(PublicAccess::Blob)
+                .finalize(),
+        ).unwrap();
+    }
+
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_content_type("text/plain")
+        .with_body(&data[..])
+        .finalize();
+    let res = core.run(future).unwrap();
+
+    let mut metadata = Metadata::new();
+    metadata.insert("pollo", "arrosto").unwrap();
+
+    let future = client
+        .set_blob_metadata()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_metadata(&metadata)
+        .with_if_match_condition(IfMatchCondition::Match(&res.etag))
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client
+        .set_blob_properties()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_content_type("application/octet-stream")
+        .with_cache_control("no-cache")
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client
+        .get_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .finalize();
+    let blob = core.run(future).unwrap().blob;
+
+    assert_eq!(blob.metadata.get("pollo").map(|s| s as &str), Some("arrosto"));
+    assert_eq!(blob.content_type.as_ref().map(|s| s as &str), Some("application/octet-stream"));
+    assert_eq!(blob.cache_control.as_ref().map(|s| s as &str), Some("no-cache"));
+
+    core.run(
+        client
+            .delete_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(&blob_name)
+            .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+            .finalize(),
+    ).unwrap();
+}
+
+#[test]
+fn get_blob_as_json_and_text() {
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+    use std::collections::HashMap;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let blob_name: &'static str = "document.json";
+    let container_name: &'static str = "rust-upload-test";
+
+    if core
+        .run(client.list_containers().finalize())
+        .unwrap()
+        .incomplete_vector
+        .iter()
+        .find(|x| x.name == container_name)
+        .is_none()
+    {
+        core.run(
+            client
+                .create_container()
+                .with_container_name(container_name)
+                .with_public_access(PublicAccess::Blob)
+                .finalize(),
+        ).unwrap();
+    }
+
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_content_type("application/json; charset=utf-8")
+        .with_body(&b"{\"pollo\":\"arrosto\"}"[..])
+        .finalize();
+    core.run(future).unwrap();
+
+    let text = core.run(client.get_blob_as_text(&container_name, &blob_name)).unwrap();
+    assert_eq!(text, "{\"pollo\":\"arrosto\"}");
+
+    let document: HashMap<String, String> = core.run(client.get_blob_as_json(&container_name, &blob_name)).unwrap();
+    assert_eq!(document.get("pollo").map(|s| s as &str), Some("arrosto"));
+
+    let bytes = core.run(client.get_blob_as_bytes(&container_name, &blob_name)).unwrap();
+    assert_eq!(bytes.len(), text.len());
+
+    core.run(
+        client
+            .delete_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(&blob_name)
+            .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+            .finalize(),
+    ).unwrap();
+}
+
+#[test]
+fn gzip_roundtrip() {
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let blob_name: &'static str = "compressed.txt";
+    let container_name: &'static str = "rust-upload-test";
+
+    if core
+        .run(client.list_containers().finalize())
+        .unwrap()
+        .incomplete_vector
+        .iter()
+        .find(|x| x.name == container_name)
+        .is_none()
+    {
+        core.run(
+            client
+                .create_container()
+                .with_container_name(container_name)
+                .with_public_access(PublicAccess::Blob)
+                .finalize(),
+        ).unwrap();
+    }
+
+    let text = "repeat after me ".repeat(1000);
+
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_content_type("text/plain")
+        .with_body(text.as_bytes())
+        .with_gzip()
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client
+        .get_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .finalize();
+    let res = core.run(future).unwrap();
+    assert_eq!(res.blob.content_encoding.as_ref().map(|s| s as &str), Some("gzip"));
+    assert_eq!(res.data, text.as_bytes());
+
+    let future = client
+        .get_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_decompress(false)
+        .finalize();
+    assert!(core.run(future).unwrap().data.len() < text.len());
+
+    core.run(
+        client
+            .delete_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(&blob_name)
+            .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+            .finalize(),
+    ).unwrap();
+}
+
+#[test]
+fn snapshot_blob() {
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let blob_name: &'static str = "snapshotted";
+    let container_name: &'static str = "rust-upload-test";
+
+    if core
+        .run(client.list_containers().finalize())
+        .unwrap()
+        .incomplete_vector
+        .iter()
+        .find(|x| x.name == container_name)
+        .is_none()
+    {
+        core.run(
+            client
+                .create_container()
+                .with_container_name(container_name)
+                .with_public_access(PublicAccess::Blob)
+                .finalize(),
+        ).unwrap();
+    }
+
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_body(&b"version 1"[..])
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client
+        .snapshot_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .finalize();
+    let snapshot = core.run(future).unwrap().snapshot;
+
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_body(&b"version 2"[..])
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client
+        .get_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_snapshot(snapshot)
+        .finalize();
+    assert_eq!(core.run(future).unwrap().data, b"version 1".to_vec());
+
+    let future = client
+        .get_blob_properties()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_snapshot(snapshot)
+        .finalize();
+    assert_eq!(core.run(future).unwrap().blob.content_length, 9);
+
+    let future = client
+        .delete_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_delete_snapshots_method(DeleteSnapshotsMethod::Only)
+        .with_snapshot(snapshot)
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client
+        .get_blob_properties()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_snapshot(snapshot)
+        .finalize();
+    assert!(core.run(future).unwrap_err().is_not_found());
+
+    core.run(
+        client
+            .delete_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(&blob_name)
+            .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+            .finalize(),
+    ).unwrap();
+}
+
+#[test]
+fn list_blobs_hierarchical() {
+    use azure_sdk_for_rust::storage::blob::BlobHierarchyItem;
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let container_name: &'static str = "rust-hierarchy-test";
+
+    core.run(
+        client
+            .create_container()
+            .with_container_name(container_name)
+            .with_public_access(PublicAccess::None)
+            .finalize(),
+    ).unwrap();
+
+    for blob_name in &["a/1", "a/2", "a/b/3", "c"] {
+        let future = client
+            .put_block_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(blob_name)
+            .with_body(&b"x"[..])
+            .finalize();
+        core.run(future).unwrap();
+    }
+
+    let future = client.list_blobs_hierarchical("/").with_container_name(&container_name).finalize();
+    let items = core.run(future).unwrap().incomplete_vector.vector;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0], BlobHierarchyItem::Prefix("a/".to_owned()));
+    assert_eq!(items[1].blob().map(|b| &b.name as &str), Some("c"));
+
+    let future = client
+        .list_blobs_hierarchical("/")
+        .with_container_name(&container_name)
+        .with_prefix("a/")
+        .finalize();
+    let names: Vec<String> = core
+        .run(future)
+        .unwrap()
+        .incomplete_vector
+        .iter()
+        .map(|item| item.name().to_owned())
+        .collect();
+    assert_eq!(names, vec!["a/1", "a/2", "a/b/"]);
+
+    let future = client.list_blobs_flat().with_container_name(&container_name).finalize();
+    assert_eq!(core.run(future).unwrap().incomplete_vector.len(), 4);
+
+    core.run(client.delete_container().with_container_name(container_name).finalize())
+        .unwrap();
+}
+
+#[test]
+fn list_blobs_stream() {
+    use azure_sdk_for_rust::core::MaxResultsSupport;
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let container_name: &'static str = "rust-list-stream-test";
+
+    core.run(
+        client
+            .create_container()
+            .with_container_name(container_name)
+            .with_public_access(PublicAccess::None)
+            .finalize(),
+    ).unwrap();
+
+    for blob_name in &["1", "2", "3"] {
+        let future = client
+            .put_block_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(blob_name)
+            .with_body(&b"x"[..])
+            .finalize();
+        core.run(future).unwrap();
+    }
+
+    // one blob per page forces the stream to follow the markers
+    let future = client
+        .list_blobs()
+        .with_container_name(&container_name)
+        .with_max_results(1)
+        .stream()
+        .collect();
+    let names: Vec<String> = core.run(future).unwrap().into_iter().map(|b| b.name).collect();
+    assert_eq!(names, vec!["1", "2", "3"]);
+
+    let future = client
+        .list_blobs()
+        .with_container_name(&container_name)
+        .with_max_results(2)
+        .pages()
+        .collect();
+    let pages = core.run(future).unwrap();
+    assert_eq!(pages.len(), 2);
+    assert!(pages[1].incomplete_vector.is_complete());
+
+    core.run(client.delete_container().with_container_name(container_name).finalize())
+        .unwrap();
+}
+
+#[test]
+fn properties_cache() {
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+    use std::time::Duration;
+
+    let (client, mut core) = initialize().unwrap();
+    let client = client.with_properties_cache(Duration::from_secs(300));
+
+    let container_name: &'static str = "rust-properties-cache-test";
+    let blob_name: &'static str = "cached";
+
+    core.run(
+        client
+            .create_container()
+            .with_container_name(container_name)
+            .with_public_access(PublicAccess::None)
+            .finalize(),
+    ).unwrap();
+
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(blob_name)
+        .with_body(&b"abc"[..])
+        .finalize();
+    core.run(future).unwrap();
+
+    let blob = core.run(client.get_blob_properties_cached(container_name, blob_name)).unwrap();
+    assert_eq!(blob.content_length, 3);
+    assert!(client.properties_cache().unwrap().blob(container_name, blob_name).is_some());
+
+    // the cache does not see the change until invalidated
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(blob_name)
+        .with_body(&b"abcdef"[..])
+        .finalize();
+    core.run(future).unwrap();
+    let blob = core.run(client.get_blob_properties_cached(container_name, blob_name)).unwrap();
+    assert_eq!(blob.content_length, 3);
+
+    client.properties_cache().unwrap().invalidate_blob(container_name, blob_name);
+    let blob = core.run(client.get_blob_properties_cached(container_name, blob_name)).unwrap();
+    assert_eq!(blob.content_length, 6);
+
+    let container = core.run(client.get_container_properties_cached(container_name)).unwrap();
+    assert_eq!(container.name, container_name);
+
+    core.run(client.delete_container().with_container_name(container_name).finalize())
+        .unwrap();
+    client.properties_cache().unwrap().invalidate_container(container_name);
+    assert!(client.properties_cache().unwrap().blob(container_name, blob_name).is_none());
+}
+
+#[test]
+fn usage_report() {
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+    use azure_sdk_for_rust::storage::UsageTotals;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let container_name: &'static str = "rust-usage-report-test";
+
+    core.run(
+        client
+            .create_container()
+            .with_container_name(container_name)
+            .with_public_access(PublicAccess::None)
+            .finalize(),
+    ).unwrap();
+
+    for (blob_name, body) in &[("a/1", &b"abc"[..]), ("a/2", &b"de"[..]), ("root", &b"f"[..])] {
+        let future = client
+            .put_block_blob()
+            .with_container_name(container_name)
+            .with_blob_name(blob_name)
+            .with_body(body)
+            .finalize();
+        core.run(future).unwrap();
+    }
+
+    let report = core.run(client.usage_report("/")).unwrap();
+    let usage = &report.containers[container_name];
+    assert_eq!(usage.totals, UsageTotals { count: 3, bytes: 6 });
+    assert_eq!(usage.by_prefix["a/"], UsageTotals { count: 2, bytes: 5 });
+    assert_eq!(usage.by_prefix[""], UsageTotals { count: 1, bytes: 1 });
+
+    core.run(client.delete_container().with_container_name(container_name).finalize())
+        .unwrap();
+}
+
+#[test]
+fn conditional_requests() {
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+    use hyper::StatusCode;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let blob_name: &'static str = "conditional";
+    let container_name: &'static str = "rust-upload-test";
+
+    if core
+        .run(client.list_containers().finalize())
+        .unwrap()
+        .incomplete_vector
+        .iter()
+        .find(|x| x.name == container_name)
+        .is_none()
+    {
+        core.run(
+            client
+                .create_container()
+                .with_container_name(container_name)
+                .with_public_access(PublicAccess::Blob)
+                .finalize(),
+        ).unwrap();
+    }
+
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_body(&b"first"[..])
+        .finalize();
+    let first = core.run(future).unwrap();
+
+    // the blob exists so this must fail
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_body(&b"second"[..])
+        .with_if_match_condition(IfMatchCondition::NotMatch("*"))
+        .finalize();
+    let err = core.run(future).unwrap_err();
+    assert!(err.is_conflict() || err.status() == Some(StatusCode::PRECONDITION_FAILED));
+
+    let future = client
+        .put_block_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_body(&b"second"[..])
+        .with_if_match_condition(IfMatchCondition::Match(&first.etag))
+        .finalize();
+    let second = core.run(future).unwrap();
+
+    // stale etag
+    let future = client
+        .get_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_if_match_condition(IfMatchCondition::Match(&first.etag))
+        .finalize();
+    assert_eq!(core.run(future).unwrap_err().status(), Some(StatusCode::PRECONDITION_FAILED));
+
+    let future = client
+        .get_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_if_match_condition(IfMatchCondition::Match(&second.etag))
+        .finalize();
+    assert_eq!(core.run(future).unwrap().data, b"second".to_vec());
+
+    let future = client
+        .delete_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+        .with_if_match_condition(IfMatchCondition::Match(&first.etag))
+        .finalize();
+    assert_eq!(core.run(future).unwrap_err().status(), Some(StatusCode::PRECONDITION_FAILED));
+
+    core.run(
+        client
+            .delete_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(&blob_name)
+            .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+            .with_if_match_condition(IfMatchCondition::Match(&second.etag))
+            .finalize(),
+    ).unwrap();
+}
+
+#[test]
+fn upload_records() {
+    use azure_sdk_for_rust::storage::blob::RecordFraming;
+    use azure_sdk_for_rust::storage::client::Blob as BlobTrait;
+    use azure_sdk_for_rust::storage::client::Container as ContainerTrait;
+    use std::collections::BTreeMap;
+
+    let (client, mut core) = initialize().unwrap();
+
+    let blob_name: &'static str = "records.csv";
+    let container_name: &'static str = "rust-upload-test";
+
+    if core
+        .run(client.list_containers().finalize())
+        .unwrap()
+        .incomplete_vector
+        .iter()
+        .find(|x| x.name == container_name)
+        .is_none()
+    {
+        core.run(
+            client
+                .create_container()
+                .with_container_name(container_name)
+                .with_public_access(PublicAccess::Blob)
+                .finalize(),
+        ).unwrap();
+    }
+
+    let records = (0..100).map(|i| {
+        let mut record = BTreeMap::new();
+        record.insert("id", i.to_string());
+        record.insert("name", format!("item, {}", i));
+        record
+    });
+
+    // a tiny block size forces several blocks
+    let future = client
+        .upload_records()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_framing(RecordFraming::Csv)
+        .with_block_size(256)
+        .finalize_iter(records);
+    core.run(future).unwrap();
+
+    let future = client
+        .get_blob()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .finalize();
+    let res = core.run(future).unwrap();
+
+    let text = String::from_utf8(res.data).unwrap();
+    let lines: Vec<&str> = text.split_terminator("\r\n").collect();
+    assert_eq!(lines.len(), 101);
+    assert_eq!(lines[0], "id,name");
+    assert_eq!(lines[100], "99,\"item, 99\"");
+    assert_eq!(res.blob.content_type.as_ref().map(|s| s as &str), Some("text/csv"));
+
+    let future = client
+        .get_block_list()
+        .with_container_name(&container_name)
+        .with_blob_name(&blob_name)
+        .with_block_list_type(BlockListType::Committed)
+        .finalize();
+    assert!(core.run(future).unwrap().block_with_size_list.blocks.len() > 1);
+
+    core.run(
+        client
+            .delete_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(&blob_name)
+            .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+            .finalize(),
+    ).unwrap();
+}
+
 fn initialize() -> Result<(Client, Core), AzureError> {
     let account = std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
     let master_key = std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
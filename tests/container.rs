@@ -4,19 +4,20 @@ extern crate chrono;
 extern crate env_logger;
 extern crate futures;
 extern crate hyper;
-extern crate hyper_tls;
 extern crate log;
 extern crate serde;
 extern crate tokio_core;
 extern crate uuid;
 
 use azure_sdk_for_rust::core::errors::AzureError;
-use azure_sdk_for_rust::core::{ContainerNameSupport, LeaseBreakPeriodSupport, LeaseDurationSupport, LeaseIdSupport};
+use azure_sdk_for_rust::core::lease::{LeaseBreakPeriod, LeasePeriod};
+use azure_sdk_for_rust::core::{ContainerNameSupport, LeaseBreakPeriodSupport, LeaseDurationSupport, LeaseIdSupport, ProposedLeaseIdSupport};
 use azure_sdk_for_rust::storage::{
     client::Client,
     container::{PublicAccess, PublicAccessSupport},
 };
 use tokio_core::reactor::Core;
+use uuid::Uuid;
 
 #[test]
 fn lease() {
@@ -36,7 +37,7 @@ fn lease() {
     let future = client
         .acquire_container_lease()
         .with_container_name(container_name)
-        .with_lease_duration(30)
+        .with_lease_duration(LeasePeriod::fixed(30).unwrap())
         .finalize();
     let res = core.run(future).unwrap();
     let lease_id = res.lease_id;
@@ -48,6 +49,17 @@ fn lease() {
         .finalize();
     let _res = core.run(future).unwrap();
 
+    let proposed_lease_id = Uuid::new_v4();
+    let future = client
+        .change_container_lease()
+        .with_container_name(container_name)
+        .with_lease_id(&lease_id)
+        .with_proposed_lease_id(&proposed_lease_id)
+        .finalize();
+    let res = core.run(future).unwrap();
+    assert_eq!(res.lease_id, proposed_lease_id);
+    let lease_id = res.lease_id;
+
     let future = client
         .release_container_lease()
         .with_container_name(container_name)
@@ -78,14 +90,14 @@ fn break_lease() {
     let future = client
         .acquire_container_lease()
         .with_container_name(container_name)
-        .with_lease_duration(30)
+        .with_lease_duration(LeasePeriod::fixed(30).unwrap())
         .finalize();
     let _res = core.run(future).unwrap();
 
     let future = client
         .break_container_lease()
         .with_container_name(container_name)
-        .with_lease_break_period(0)
+        .with_lease_break_period(LeaseBreakPeriod::immediate())
         .finalize();
     let res = core.run(future).unwrap();
     assert!(res.lease_time == 0);
@@ -95,6 +107,49 @@ fn break_lease() {
     core.run(cont_delete).unwrap();
 }
 
+#[test]
+fn list_containers_stream() {
+    use azure_sdk_for_rust::core::PrefixSupport;
+    use azure_sdk_for_rust::storage::client::Container;
+    use futures::Stream;
+
+    let container_names = ["azuresdkrustlist0", "azuresdkrustlist1", "azuresdkrustlist2"];
+
+    let (client, mut core) = initialize().unwrap();
+    for container_name in &container_names {
+        core.run(
+            client
+                .create_container()
+                .with_container_name(container_name)
+                .with_public_access(PublicAccess::None)
+                .finalize(),
+        ).unwrap();
+    }
+
+    // one container per page forces the stream to follow the markers
+    let future = client
+        .list_containers()
+        .with_prefix("azuresdkrustlist")
+        .with_max_results(1)
+        .stream()
+        .collect();
+    let names: Vec<String> = core.run(future).unwrap().into_iter().map(|c| c.name).collect();
+    assert_eq!(names, container_names);
+
+    let future = client
+        .list_containers()
+        .with_prefix("azuresdkrustlist")
+        .with_max_results(2)
+        .pages()
+        .collect();
+    assert_eq!(core.run(future).unwrap().len(), 2);
+
+    for container_name in &container_names {
+        core.run(client.delete_container().with_container_name(container_name).finalize())
+            .unwrap();
+    }
+}
+
 fn initialize() -> Result<(Client, Core), AzureError> {
     let account = std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
     let master_key = std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
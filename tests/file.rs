@@ -0,0 +1,111 @@
+#![cfg(all(test, feature = "test_e2e"))]
+extern crate azure_sdk_for_rust;
+extern crate futures;
+extern crate tokio_core;
+
+use azure_sdk_for_rust::core::errors::AzureError;
+use azure_sdk_for_rust::core::range::Range;
+use azure_sdk_for_rust::core::{BodySupport, ContentTypeSupport, MetadataSupport, RangeSupport};
+use azure_sdk_for_rust::storage::client::Client;
+use azure_sdk_for_rust::storage::file::{
+    DirectoryItem, DirectoryPathSupport, FilePathSupport, FileSizeSupport, ShareNameSupport, ShareQuotaSupport,
+};
+use azure_sdk_for_rust::core::metadata::Metadata;
+use tokio_core::reactor::Core;
+
+#[test]
+fn share_directories_and_files() {
+    use azure_sdk_for_rust::storage::client::File;
+
+    let share_name: &'static str = "azuresdkrustetoetsshare";
+
+    let (client, mut core) = initialize().unwrap();
+
+    core.run(client.create_share().with_share_name(share_name).with_share_quota(1).finalize())
+        .unwrap();
+
+    let future = client
+        .set_share_properties()
+        .with_share_name(share_name)
+        .with_share_quota(2)
+        .finalize();
+    core.run(future).unwrap();
+
+    let mut metadata = Metadata::new();
+    metadata.insert("owner", "e2e").unwrap();
+    let future = client
+        .set_share_metadata()
+        .with_share_name(share_name)
+        .with_metadata(&metadata)
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client.get_share_properties().with_share_name(share_name).finalize();
+    let properties = core.run(future).unwrap();
+    assert_eq!(properties.share_quota, 2);
+    assert_eq!(properties.metadata["owner"], "e2e");
+
+    let future = client
+        .create_directory()
+        .with_share_name(share_name)
+        .with_directory_path("some dir")
+        .finalize();
+    core.run(future).unwrap();
+
+    let data = b"0123456789";
+    let future = client
+        .create_file()
+        .with_share_name(share_name)
+        .with_file_path("some dir/a&b.txt")
+        .with_file_size(data.len() as u64)
+        .with_content_type("text/plain")
+        .finalize();
+    core.run(future).unwrap();
+
+    let range = Range::new(0, data.len() as u64 - 1);
+    let future = client
+        .put_range()
+        .with_share_name(share_name)
+        .with_file_path("some dir/a&b.txt")
+        .with_range(&range)
+        .with_body(data)
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client.list_directories_and_files().with_share_name(share_name).finalize();
+    let root = core.run(future).unwrap().incomplete_vector;
+    assert_eq!(root.vector, vec![DirectoryItem::Directory("some dir".to_owned())]);
+
+    let future = client
+        .list_directories_and_files()
+        .with_share_name(share_name)
+        .with_directory_path("some dir")
+        .finalize();
+    let files = core.run(future).unwrap().incomplete_vector;
+    assert_eq!(
+        files.vector,
+        vec![DirectoryItem::File {
+            name: "a&b.txt".to_owned(),
+            content_length: 10
+        }]
+    );
+
+    let range = Range::new(2, 4);
+    let future = client
+        .get_file()
+        .with_share_name(share_name)
+        .with_file_path("some dir/a&b.txt")
+        .with_range(&range)
+        .finalize();
+    assert_eq!(core.run(future).unwrap().data, b"234");
+
+    core.run(client.delete_share().with_share_name(share_name).finalize()).unwrap();
+}
+
+fn initialize() -> Result<(Client, Core), AzureError> {
+    let account = std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let master_key = std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
+    let core = Core::new()?;
+
+    Ok((Client::new(&account, &master_key)?, core))
+}
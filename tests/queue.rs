@@ -0,0 +1,130 @@
+#![cfg(all(test, feature = "test_e2e"))]
+extern crate azure_sdk_for_rust;
+extern crate futures;
+extern crate tokio_core;
+
+use azure_sdk_for_rust::core::errors::AzureError;
+use azure_sdk_for_rust::core::MetadataSupport;
+use azure_sdk_for_rust::storage::client::Client;
+use azure_sdk_for_rust::storage::queue::{
+    MessageIdSupport, MessageTextSupport, NumberOfMessagesSupport, PopReceiptSupport, QueueNameSupport, VisibilityTimeoutSupport,
+};
+use azure_sdk_for_rust::core::metadata::Metadata;
+use tokio_core::reactor::Core;
+
+#[test]
+fn queue_messages() {
+    use azure_sdk_for_rust::storage::client::Queue;
+
+    let queue_name: &'static str = "azuresdkrustetoetsqueue";
+
+    let (client, mut core) = initialize().unwrap();
+
+    let mut metadata = Metadata::new();
+    metadata.insert("owner", "e2e").unwrap();
+    core.run(
+        client
+            .create_queue()
+            .with_queue_name(queue_name)
+            .with_metadata(&metadata)
+            .finalize(),
+    )
+    .unwrap();
+
+    for text in &["first", "second <&>"] {
+        let future = client.put_message().with_queue_name(queue_name).with_message_text(text).finalize();
+        core.run(future).unwrap();
+    }
+
+    let future = client.get_queue_metadata().with_queue_name(queue_name).finalize();
+    let response = core.run(future).unwrap();
+    assert_eq!(response.approximate_messages_count, 2);
+    assert_eq!(response.metadata["owner"], "e2e");
+
+    let future = client
+        .peek_messages()
+        .with_queue_name(queue_name)
+        .with_number_of_messages(2)
+        .finalize();
+    let peeked = core.run(future).unwrap().messages;
+    assert_eq!(peeked.len(), 2);
+    assert_eq!(peeked[1].message_text, "second <&>");
+    assert!(peeked[0].pop_receipt.is_none());
+
+    let future = client
+        .get_messages()
+        .with_queue_name(queue_name)
+        .with_number_of_messages(1)
+        .with_visibility_timeout(30)
+        .finalize();
+    let message = core.run(future).unwrap().messages.remove(0);
+    assert_eq!(message.message_text, "first");
+    assert_eq!(message.dequeue_count, 1);
+
+    let future = client
+        .update_message()
+        .with_queue_name(queue_name)
+        .with_message_id(&message.message_id)
+        .with_pop_receipt(message.pop_receipt.as_ref().unwrap())
+        .with_visibility_timeout(60)
+        .with_message_text("first (updated)")
+        .finalize();
+    let updated = core.run(future).unwrap();
+
+    let future = client
+        .delete_message()
+        .with_queue_name(queue_name)
+        .with_message_id(&message.message_id)
+        .with_pop_receipt(&updated.pop_receipt)
+        .finalize();
+    core.run(future).unwrap();
+
+    let future = client.peek_messages().with_queue_name(queue_name).finalize();
+    let peeked = core.run(future).unwrap().messages;
+    assert_eq!(peeked.len(), 1);
+    assert_eq!(peeked[0].message_text, "second <&>");
+
+    core.run(client.delete_queue().with_queue_name(queue_name).finalize()).unwrap();
+}
+
+#[test]
+fn queue_depth_poller() {
+    use azure_sdk_for_rust::core::telemetry::RecordingObserver;
+    use azure_sdk_for_rust::storage::client::Queue;
+    use azure_sdk_for_rust::storage::queue::QueueDepthPoller;
+    use futures::Stream;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let queue_name: &'static str = "azuresdkrustetoetsdepth";
+
+    let (client, mut core) = initialize().unwrap();
+    let observer = Arc::new(RecordingObserver::new());
+    let client = client.with_telemetry_observer(observer.clone());
+
+    core.run(client.create_queue().with_queue_name(queue_name).finalize()).unwrap();
+    let future = client.put_message().with_queue_name(queue_name).with_message_text("one").finalize();
+    core.run(future).unwrap();
+
+    let poller = QueueDepthPoller::new(&client, &[queue_name, "azuresdkrustetoetsmissing"], Duration::from_secs(1));
+    let rounds = core.run(poller.run().take(2).collect()).unwrap();
+    assert_eq!(rounds.len(), 2);
+    assert_eq!(rounds[0].len(), 1);
+    assert_eq!(rounds[0][0].approximate_messages_count, 1);
+
+    assert_eq!(
+        observer.queue_depths(),
+        vec![(queue_name.to_owned(), 1), (queue_name.to_owned(), 1)]
+    );
+    assert_eq!(observer.queue_depth_errors().len(), 2);
+
+    core.run(client.delete_queue().with_queue_name(queue_name).finalize()).unwrap();
+}
+
+fn initialize() -> Result<(Client, Core), AzureError> {
+    let account = std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let master_key = std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
+    let core = Core::new()?;
+
+    Ok((Client::new(&account, &master_key)?, core))
+}
@@ -131,3 +131,84 @@ fn code() -> Result<(), Box<std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn create_blob_and_download_stream_back() {
+    download_code().unwrap();
+}
+
+fn download_code() -> Result<(), Box<std::error::Error>> {
+    let container_name = "azuresdkforrust";
+    let file_name = "azure_sdk_for_rust_download_stream_test.txt";
+
+    let account = std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let master_key = std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
+
+    let mut reactor = Core::new()?;
+    let client = Client::new(&account, &master_key)?;
+
+    if reactor
+        .run(client.list_containers().finalize())?
+        .incomplete_vector
+        .iter()
+        .find(|x| x.name == container_name)
+        .is_none()
+    {
+        reactor.run(
+            client
+                .create_container()
+                .with_container_name(container_name)
+                .with_public_access(PublicAccess::Blob)
+                .finalize(),
+        )?;
+    }
+
+    let string = "0123456789ABCDEF".repeat(100);
+
+    reactor.run(
+        client
+            .put_block_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(file_name)
+            .with_content_type("text/plain")
+            .with_body(string.as_ref())
+            .finalize(),
+    )?;
+
+    // we use an awkward chunk size and a bit of prefetching
+    // to make sure chunks are reassembled in the right order.
+    let stream = client
+        .download_blob_stream()
+        .with_container_name(&container_name)
+        .with_blob_name(file_name)
+        .with_chunk_size(77)
+        .with_parallelism(4)
+        .finalize();
+
+    let result = reactor.run(stream.concat2())?;
+    assert_eq!(string.as_bytes(), &result[..]);
+
+    // ranges are inclusive
+    let range = Range::new(16, 47);
+    let stream = client
+        .download_blob_stream()
+        .with_container_name(&container_name)
+        .with_blob_name(file_name)
+        .with_range(&range)
+        .with_chunk_size(10)
+        .finalize();
+
+    let result = reactor.run(stream.concat2())?;
+    assert_eq!(&string.as_bytes()[16..48], &result[..]);
+
+    reactor.run(
+        client
+            .delete_blob()
+            .with_container_name(&container_name)
+            .with_blob_name(file_name)
+            .with_delete_snapshots_method(DeleteSnapshotsMethod::Include)
+            .finalize(),
+    )?;
+
+    Ok(())
+}